@@ -4,6 +4,7 @@ use crate::{
     subscription::{
         book::{OrderBookEvent, OrderBookL1},
         candle::Candle,
+        funding::FundingRate,
         liquidation::Liquidation,
         trade::PublicTrade,
     },
@@ -98,6 +99,13 @@ impl<InstrumentKey> MarketEvent<InstrumentKey, DataKind> {
         }
     }
 
+    pub fn as_funding_rate(&self) -> Option<MarketEvent<&InstrumentKey, &FundingRate>> {
+        match &self.kind {
+            DataKind::FundingRate(funding_rate) => Some(self.as_event(funding_rate)),
+            _ => None,
+        }
+    }
+
     fn as_event<'a, K>(&'a self, kind: &'a K) -> MarketEvent<&'a InstrumentKey, &'a K> {
         MarketEvent {
             time_exchange: self.time_exchange,
@@ -127,6 +135,7 @@ pub enum DataKind {
     OrderBook(OrderBookEvent),
     Candle(Candle),
     Liquidation(Liquidation),
+    FundingRate(FundingRate),
 }
 
 impl DataKind {
@@ -137,6 +146,7 @@ impl DataKind {
             DataKind::OrderBook(_) => "l2",
             DataKind::Candle(_) => "candle",
             DataKind::Liquidation(_) => "liquidation",
+            DataKind::FundingRate(_) => "funding_rate",
         }
     }
 }
@@ -220,3 +230,19 @@ impl<InstrumentKey> From<MarketEvent<InstrumentKey, Liquidation>>
         value.map_kind(Liquidation::into)
     }
 }
+
+impl<InstrumentKey> From<MarketStreamResult<InstrumentKey, FundingRate>>
+    for MarketStreamResult<InstrumentKey, DataKind>
+{
+    fn from(value: MarketStreamResult<InstrumentKey, FundingRate>) -> Self {
+        value.map_ok(MarketEvent::from)
+    }
+}
+
+impl<InstrumentKey> From<MarketEvent<InstrumentKey, FundingRate>>
+    for MarketEvent<InstrumentKey, DataKind>
+{
+    fn from(value: MarketEvent<InstrumentKey, FundingRate>) -> Self {
+        value.map_kind(FundingRate::into)
+    }
+}
@@ -0,0 +1,34 @@
+use super::SubscriptionKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubscriptionKind`] that yields [`FundingRate`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub struct FundingRates;
+
+impl SubscriptionKind for FundingRates {
+    type Event = FundingRate;
+
+    fn as_str(&self) -> &'static str {
+        "funding_rates"
+    }
+}
+
+impl std::fmt::Display for FundingRates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Normalised Barter perpetual [`FundingRate`] model.
+///
+/// The `rate` is the fraction of position notional value paid by longs to shorts (or by shorts
+/// to longs if negative) at the next funding settlement, `time_next_funding`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct FundingRate {
+    pub rate: f64,
+    pub time_next_funding: DateTime<Utc>,
+}
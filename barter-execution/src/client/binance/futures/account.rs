@@ -0,0 +1,392 @@
+use crate::{
+    AccountEventKind, UnindexedAccountEvent,
+    balance::{AssetBalance, Balance},
+    client::{
+        binance::futures::{BinanceFuturesPositions, parse_order_kind_conditional},
+        fees_quote,
+    },
+    error::{ApiError, OrderError},
+    order::{
+        Order, OrderKey,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::snapshot::Snapshot;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use smol_str::SmolStr;
+use tracing::warn;
+
+/// Binance USD-M user data stream event.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/user-data-streams>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "e")]
+pub enum BinanceFuturesUserEvent {
+    #[serde(rename = "ACCOUNT_UPDATE")]
+    AccountUpdate(BinanceFuturesAccountUpdate),
+    #[serde(rename = "ORDER_TRADE_UPDATE")]
+    OrderTradeUpdate(Box<BinanceFuturesOrderTradeUpdate>),
+    #[serde(rename = "MARGIN_CALL")]
+    MarginCall(BinanceFuturesMarginCall),
+    /// `listenKey` is no longer valid, so the user data stream will stop sending events.
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired,
+    /// Other events (eg/ `ACCOUNT_CONFIG_UPDATE`) that do not map to an
+    /// [`UnindexedAccountEvent`].
+    #[serde(other)]
+    Other,
+}
+
+impl BinanceFuturesUserEvent {
+    /// Map the [`BinanceFuturesUserEvent`] into zero or more [`UnindexedAccountEvent`]s.
+    ///
+    /// The provided [`BinanceFuturesPositions`] are updated with any position changes, so the
+    /// `LONG` & `SHORT` legs of a hedge mode account can be netted into a single position.
+    pub fn into_account_events(
+        self,
+        positions: &mut BinanceFuturesPositions,
+    ) -> Vec<UnindexedAccountEvent> {
+        match self {
+            Self::AccountUpdate(update) => update.into_account_events(positions),
+            Self::OrderTradeUpdate(update) => update.into_account_events(),
+            Self::MarginCall(margin_call) => {
+                warn!(
+                    cross_wallet_balance = %margin_call.cross_wallet_balance,
+                    positions = ?margin_call.positions,
+                    "Binance USD-M margin call"
+                );
+                vec![]
+            }
+            Self::ListenKeyExpired | Self::Other => vec![],
+        }
+    }
+}
+
+/// Binance USD-M user data stream balance and position update.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "e": "ACCOUNT_UPDATE",
+///     "E": 1564745798939,
+///     "T": 1564745798938,
+///     "a": {
+///         "m": "ORDER",
+///         "B": [
+///             { "a": "USDT", "wb": "122624.12345678", "cw": "100.12345678", "bc": "50.12345678" }
+///         ],
+///         "P": [
+///             {
+///                 "s": "BTCUSDT",
+///                 "pa": "0",
+///                 "ep": "0.00000",
+///                 "bep": "0",
+///                 "cr": "200",
+///                 "up": "0",
+///                 "mt": "isolated",
+///                 "iw": "0.00000000",
+///                 "ps": "BOTH"
+///             }
+///         ]
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesAccountUpdate {
+    #[serde(
+        rename = "T",
+        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+    )]
+    pub time_transaction: DateTime<Utc>,
+    #[serde(rename = "a")]
+    pub data: BinanceFuturesAccountUpdateData,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesAccountUpdateData {
+    #[serde(rename = "B", default)]
+    pub balances: Vec<BinanceFuturesAccountUpdateBalance>,
+    #[serde(rename = "P", default)]
+    pub positions: Vec<BinanceFuturesAccountUpdatePosition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesAccountUpdateBalance {
+    #[serde(rename = "a")]
+    pub asset: AssetNameExchange,
+    #[serde(rename = "wb")]
+    pub wallet_balance: Decimal,
+    #[serde(rename = "cw")]
+    pub cross_wallet_balance: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesAccountUpdatePosition {
+    #[serde(rename = "s")]
+    pub symbol: InstrumentNameExchange,
+    #[serde(rename = "pa")]
+    pub quantity: Decimal,
+    #[serde(rename = "ep")]
+    pub price_entry: Decimal,
+    #[serde(rename = "up")]
+    pub pnl_unrealised: Decimal,
+    #[serde(rename = "ps")]
+    pub position_side: SmolStr,
+}
+
+impl BinanceFuturesAccountUpdate {
+    /// Map the [`BinanceFuturesAccountUpdate`] into a [`AssetBalance`] snapshot for each updated
+    /// balance, followed by a netted [`ExchangePosition`](crate::position::ExchangePosition)
+    /// snapshot for each updated symbol.
+    pub fn into_account_events(
+        self,
+        positions: &mut BinanceFuturesPositions,
+    ) -> Vec<UnindexedAccountEvent> {
+        let time_exchange = self.time_transaction;
+
+        let balances = self.data.balances.into_iter().map(|balance| {
+            UnindexedAccountEvent::new(
+                ExchangeId::BinanceFuturesUsd,
+                AccountEventKind::BalanceSnapshot(Snapshot(AssetBalance::new(
+                    balance.asset,
+                    Balance::new(balance.wallet_balance, balance.cross_wallet_balance),
+                    time_exchange,
+                ))),
+            )
+        });
+
+        let mut symbols = Vec::with_capacity(self.data.positions.len());
+        for position in self.data.positions {
+            positions.update(
+                position.symbol.clone(),
+                position.position_side,
+                position.quantity,
+                position.price_entry,
+                position.pnl_unrealised,
+            );
+            if !symbols.contains(&position.symbol) {
+                symbols.push(position.symbol);
+            }
+        }
+
+        let positions = symbols.into_iter().map(|symbol| {
+            UnindexedAccountEvent::new(
+                ExchangeId::BinanceFuturesUsd,
+                AccountEventKind::PositionSnapshot(Snapshot(
+                    positions.position(symbol, time_exchange),
+                )),
+            )
+        });
+
+        balances.chain(positions).collect()
+    }
+}
+
+/// Binance USD-M user data stream margin call, sent when the margin ratio of a position reaches
+/// the maintenance threshold.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesMarginCall {
+    #[serde(rename = "cw", default)]
+    pub cross_wallet_balance: Decimal,
+    #[serde(rename = "p", default)]
+    pub positions: Vec<BinanceFuturesMarginCallPosition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesMarginCallPosition {
+    #[serde(rename = "s")]
+    pub symbol: InstrumentNameExchange,
+    #[serde(rename = "ps")]
+    pub position_side: SmolStr,
+    #[serde(rename = "pa")]
+    pub quantity: Decimal,
+    #[serde(rename = "mm")]
+    pub margin_maintenance: Decimal,
+}
+
+/// Binance USD-M user data stream order update.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "e": "ORDER_TRADE_UPDATE",
+///     "E": 1568879465651,
+///     "T": 1568879465650,
+///     "o": {
+///         "s": "BTCUSDT",
+///         "c": "TEST",
+///         "S": "SELL",
+///         "o": "LIMIT",
+///         "f": "GTC",
+///         "q": "0.001",
+///         "p": "9910",
+///         "x": "TRADE",
+///         "X": "PARTIALLY_FILLED",
+///         "i": 8886774,
+///         "l": "0.0005",
+///         "z": "0.0005",
+///         "L": "9910",
+///         "N": "USDT",
+///         "n": "0.00198200",
+///         "T": 1568879465650,
+///         "t": 1568879,
+///         "ps": "BOTH"
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesOrderTradeUpdate {
+    #[serde(rename = "o")]
+    pub order: BinanceFuturesOrderUpdate,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesOrderUpdate {
+    #[serde(rename = "s")]
+    pub symbol: InstrumentNameExchange,
+    #[serde(rename = "c")]
+    pub client_order_id: SmolStr,
+    #[serde(rename = "S")]
+    pub side: Side,
+    #[serde(rename = "o")]
+    pub kind: SmolStr,
+    #[serde(rename = "f")]
+    pub time_in_force: SmolStr,
+    #[serde(rename = "q")]
+    pub quantity: Decimal,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "sp", default)]
+    pub stop_price: Decimal,
+    #[serde(rename = "wt", default)]
+    pub working_type: SmolStr,
+    #[serde(rename = "AP", default)]
+    pub activation_price: Option<Decimal>,
+    #[serde(rename = "cr", default)]
+    pub callback_rate: Option<Decimal>,
+    #[serde(rename = "x")]
+    pub execution_type: SmolStr,
+    #[serde(rename = "X")]
+    pub status: SmolStr,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l")]
+    pub last_quantity: Decimal,
+    #[serde(rename = "z")]
+    pub cumulative_quantity: Decimal,
+    #[serde(rename = "L")]
+    pub last_price: Decimal,
+    #[serde(rename = "n", default)]
+    pub commission: Decimal,
+    #[serde(rename = "N", default)]
+    pub commission_asset: Option<AssetNameExchange>,
+    #[serde(
+        rename = "T",
+        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+    )]
+    pub time_transaction: DateTime<Utc>,
+    #[serde(rename = "t")]
+    pub trade_id: i64,
+}
+
+impl BinanceFuturesOrderTradeUpdate {
+    /// Map the [`BinanceFuturesOrderTradeUpdate`] into a [`Trade`] (if the order was filled),
+    /// followed by an [`Order`] snapshot.
+    pub fn into_account_events(self) -> Vec<UnindexedAccountEvent> {
+        let order = self.order;
+        let mut events = Vec::with_capacity(2);
+
+        // CALCULATED executions are liquidation and ADL fills
+        if order.execution_type == "TRADE" || order.execution_type == "CALCULATED" {
+            let fees = order
+                .commission_asset
+                .as_ref()
+                .map(|asset| fees_quote(&order.symbol, asset, order.commission, order.last_price))
+                .unwrap_or_default();
+
+            events.push(UnindexedAccountEvent::new(
+                ExchangeId::BinanceFuturesUsd,
+                Trade {
+                    id: TradeId::new(order.trade_id.to_string()),
+                    order_id: OrderId::new(order.order_id.to_string()),
+                    instrument: order.symbol.clone(),
+                    strategy: StrategyId::unknown(),
+                    time_exchange: order.time_transaction,
+                    side: order.side,
+                    price: order.last_price,
+                    quantity: order.last_quantity,
+                    fees: AssetFees::quote_fees(fees),
+                },
+            ));
+        }
+
+        let Some((kind, time_in_force)) = parse_order_kind_conditional(
+            &order.kind,
+            &order.time_in_force,
+            order.stop_price,
+            &order.working_type,
+            order.activation_price,
+            order.callback_rate,
+        ) else {
+            warn!(
+                symbol = %order.symbol,
+                order_id = order.order_id,
+                kind = %order.kind,
+                "ignoring Binance USD-M ORDER_TRADE_UPDATE for unsupported order type"
+            );
+            return events;
+        };
+
+        let order_id = OrderId::new(order.order_id.to_string());
+        let state = match order.status.as_str() {
+            "NEW" | "PARTIALLY_FILLED" => OrderState::active(Open::new(
+                order_id,
+                order.time_transaction,
+                order.cumulative_quantity,
+            )),
+            "FILLED" => OrderState::fully_filled(),
+            "CANCELED" => OrderState::inactive(Cancelled::new(order_id, order.time_transaction)),
+            "EXPIRED" | "EXPIRED_IN_MATCH" => OrderState::expired(),
+            "REJECTED" => OrderState::inactive(OrderError::Rejected(ApiError::OrderRejected(
+                "Binance USD-M order rejected".to_string(),
+            ))),
+            status => {
+                warn!(
+                    symbol = %order.symbol,
+                    order_id = order.order_id,
+                    %status,
+                    "ignoring Binance USD-M ORDER_TRADE_UPDATE with unknown order status"
+                );
+                return events;
+            }
+        };
+
+        events.push(UnindexedAccountEvent::new(
+            ExchangeId::BinanceFuturesUsd,
+            AccountEventKind::OrderSnapshot(Snapshot(Order {
+                key: OrderKey::new(
+                    ExchangeId::BinanceFuturesUsd,
+                    order.symbol,
+                    StrategyId::unknown(),
+                    ClientOrderId::new(order.client_order_id),
+                ),
+                side: order.side,
+                price: order.price,
+                quantity: order.quantity,
+                kind,
+                time_in_force,
+                state,
+            })),
+        ));
+
+        events
+    }
+}
@@ -0,0 +1,816 @@
+use crate::{
+    InstrumentAccountSnapshot, UnindexedAccountEvent, UnindexedAccountSnapshot,
+    balance::AssetBalance,
+    client::{
+        ExecutionClient,
+        binance::{
+            BinanceApiKey, BinanceParser, BinanceRequestSigner, DEFAULT_RECV_WINDOW_MS,
+            ListenKeyKeepAlive, SignedQuery,
+            futures::{
+                account::BinanceFuturesUserEvent,
+                request::{
+                    BinanceFuturesAccount, BinanceFuturesPosition, CancelOrder, CancelOrderParams,
+                    CreateListenKey, FetchAccount, FetchOpenOrders, FetchPositionMode, FetchTrades,
+                    FetchTradesParams, KeepAliveListenKey, LeverageParams, OpenOrder,
+                    OpenOrderParams, PositionModeParams, SetLeverage, SetPositionMode,
+                },
+            },
+            parse_order_kind, request_signer,
+        },
+        fees_quote, order_error,
+    },
+    error::{ApiError, OrderError, UnindexedClientError, UnindexedOrderError},
+    order::{
+        Order, OrderKey, OrderKind, OrderTrigger, TimeInForce, TrailingOffset, TriggerSource,
+        id::{OrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        state::{Cancelled, Open, OrderState},
+    },
+    position::ExchangePosition,
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::{
+    StreamParser,
+    http::rest::client::RestClient,
+    websocket::{WebSocketParser, connect},
+};
+use chrono::{DateTime, Utc};
+use fnv::FnvHashMap;
+use futures::{StreamExt, stream::BoxStream};
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Formatter},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn};
+
+/// Binance USD-M Http request and response models.
+pub mod request;
+
+/// Binance USD-M user data stream event models.
+pub mod account;
+
+/// Maximum number of trades Binance USD-M returns per `userTrades` request.
+const FETCH_TRADES_LIMIT: u16 = 1000;
+
+/// Binance USD-M account position mode.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/trade/rest-api/Change-Position-Mode>
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BinancePositionMode {
+    /// Single `BOTH` position per symbol.
+    #[default]
+    OneWay,
+    /// Independent `LONG` and `SHORT` positions per symbol.
+    Hedge,
+}
+
+/// [`BinanceFuturesUsd`] execution client configuration.
+///
+/// Only the API credentials are required, with the remaining fields defaulting to the Binance
+/// USD-M production endpoints, one-way position mode, and the existing symbol leverage.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct BinanceFuturesUsdConfig {
+    pub api_key: String,
+    pub secret: String,
+    #[serde(default = "default_base_url_rest")]
+    pub base_url_rest: String,
+    #[serde(default = "default_base_url_ws")]
+    pub base_url_ws: String,
+    #[serde(default = "default_recv_window_ms")]
+    pub recv_window_ms: u64,
+    /// Interval at which the user data stream `listenKey` is kept alive.
+    ///
+    /// Binance closes user data streams 60 minutes after the last keep-alive.
+    #[serde(default = "default_keep_alive_interval_secs")]
+    pub keep_alive_interval_secs: u64,
+    /// Account position mode, applied before the first account snapshot.
+    #[serde(default)]
+    pub position_mode: BinancePositionMode,
+    /// Initial leverage per symbol, applied before the first account snapshot.
+    #[serde(default)]
+    pub leverage: BTreeMap<InstrumentNameExchange, u32>,
+}
+
+impl BinanceFuturesUsdConfig {
+    /// Construct a [`BinanceFuturesUsdConfig`] for the Binance USD-M production endpoints.
+    pub fn new<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            api_key: api_key.into(),
+            secret: secret.into(),
+            base_url_rest: default_base_url_rest(),
+            base_url_ws: default_base_url_ws(),
+            recv_window_ms: default_recv_window_ms(),
+            keep_alive_interval_secs: default_keep_alive_interval_secs(),
+            position_mode: BinancePositionMode::default(),
+            leverage: BTreeMap::new(),
+        }
+    }
+
+    /// Construct a [`BinanceFuturesUsdConfig`] for the Binance USD-M Futures testnet endpoints.
+    ///
+    /// Note that the Futures testnet requires separate API credentials.
+    pub fn testnet<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            base_url_rest: "https://testnet.binancefuture.com".to_string(),
+            base_url_ws: "wss://stream.binancefuture.com".to_string(),
+            ..Self::new(api_key, secret)
+        }
+    }
+}
+
+impl Debug for BinanceFuturesUsdConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinanceFuturesUsdConfig")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field("base_url_rest", &self.base_url_rest)
+            .field("base_url_ws", &self.base_url_ws)
+            .field("recv_window_ms", &self.recv_window_ms)
+            .field("keep_alive_interval_secs", &self.keep_alive_interval_secs)
+            .field("position_mode", &self.position_mode)
+            .field("leverage", &self.leverage)
+            .finish()
+    }
+}
+
+fn default_base_url_rest() -> String {
+    "https://fapi.binance.com".to_string()
+}
+
+fn default_base_url_ws() -> String {
+    "wss://fstream.binance.com".to_string()
+}
+
+fn default_recv_window_ms() -> u64 {
+    DEFAULT_RECV_WINDOW_MS
+}
+
+fn default_keep_alive_interval_secs() -> u64 {
+    30 * 60
+}
+
+/// Binance USD-M positions keyed by symbol and `positionSide`.
+///
+/// Used to net the `LONG` & `SHORT` legs of a hedge mode account into a single
+/// [`ExchangePosition`], since user data stream updates only contain the changed legs.
+#[derive(Debug, Clone, Default)]
+pub struct BinanceFuturesPositions(
+    FnvHashMap<(InstrumentNameExchange, SmolStr), BinanceFuturesPositionLeg>,
+);
+
+/// Single `positionSide` leg of a [`BinanceFuturesPositions`] symbol position.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct BinanceFuturesPositionLeg {
+    pub quantity: Decimal,
+    pub price_entry: Decimal,
+    pub pnl_unrealised: Decimal,
+}
+
+impl BinanceFuturesPositions {
+    /// Update the position leg of the provided symbol and `positionSide`.
+    pub fn update(
+        &mut self,
+        symbol: InstrumentNameExchange,
+        position_side: SmolStr,
+        quantity: Decimal,
+        price_entry: Decimal,
+        pnl_unrealised: Decimal,
+    ) {
+        self.0.insert(
+            (symbol, position_side),
+            BinanceFuturesPositionLeg {
+                quantity,
+                price_entry,
+                pnl_unrealised,
+            },
+        );
+    }
+
+    /// Net all position legs of the provided symbol into a single [`ExchangePosition`].
+    ///
+    /// The netted entry price is that of the largest leg, and a symbol without any legs is
+    /// flat.
+    pub fn position(
+        &self,
+        symbol: InstrumentNameExchange,
+        time_exchange: DateTime<Utc>,
+    ) -> ExchangePosition<InstrumentNameExchange> {
+        let legs = self
+            .0
+            .iter()
+            .filter(|((leg_symbol, _), _)| *leg_symbol == symbol)
+            .map(|(_, leg)| leg);
+
+        let (quantity, pnl_unrealised, largest) = legs.fold(
+            (
+                Decimal::ZERO,
+                Decimal::ZERO,
+                None::<&BinanceFuturesPositionLeg>,
+            ),
+            |(quantity, pnl_unrealised, largest), leg| {
+                let largest = match largest {
+                    Some(largest) if largest.quantity.abs() >= leg.quantity.abs() => largest,
+                    _ => leg,
+                };
+                (
+                    quantity + leg.quantity,
+                    pnl_unrealised + leg.pnl_unrealised,
+                    Some(largest),
+                )
+            },
+        );
+
+        let price_entry = match largest {
+            Some(leg) if !quantity.is_zero() => leg.price_entry,
+            _ => Decimal::ZERO,
+        };
+
+        ExchangePosition::new(symbol, quantity, price_entry, pnl_unrealised, time_exchange)
+    }
+}
+
+impl FromIterator<BinanceFuturesPosition> for BinanceFuturesPositions {
+    fn from_iter<Iter>(iter: Iter) -> Self
+    where
+        Iter: IntoIterator<Item = BinanceFuturesPosition>,
+    {
+        let mut positions = Self::default();
+        for position in iter {
+            positions.update(
+                position.symbol,
+                position.position_side,
+                position.position_amt,
+                position.entry_price,
+                position.unrealized_profit,
+            );
+        }
+        positions
+    }
+}
+
+/// Binance USD-M Futures live [`ExecutionClient`].
+///
+/// Orders and account state are actioned via `SIGNED` Http endpoints, while the
+/// [`AccountStream`](ExecutionClient::AccountStream) is built on the user data stream, which is
+/// kept alive for as long as the stream is not dropped.
+///
+/// The configured [`BinancePositionMode`] and symbol leverage are applied once, before the first
+/// [`ExecutionClient::account_snapshot`] or [`ExecutionClient::account_stream`]. Positions are
+/// reported as [`ExchangePosition`]s, with hedge mode `LONG` & `SHORT` legs netted together.
+///
+/// Binance only provides trade history per symbol, so [`ExecutionClient::fetch_trades`] fetches
+/// trades for the instruments previously provided to [`ExecutionClient::account_snapshot`] or
+/// [`ExecutionClient::account_stream`].
+#[derive(Debug, Clone)]
+pub struct BinanceFuturesUsd {
+    pub config: BinanceFuturesUsdConfig,
+    rest_signed: Arc<RestClient<'static, BinanceRequestSigner, BinanceParser>>,
+    rest_api_key: Arc<RestClient<'static, BinanceApiKey, BinanceParser>>,
+    instruments: Arc<RwLock<Vec<InstrumentNameExchange>>>,
+    configured: Arc<OnceCell<()>>,
+}
+
+impl BinanceFuturesUsd {
+    /// Set the account [`BinancePositionMode`], if it differs from the current mode.
+    ///
+    /// Binance rejects position mode changes while there are open positions or orders.
+    pub async fn set_position_mode(
+        &self,
+        position_mode: BinancePositionMode,
+    ) -> Result<(), UnindexedClientError> {
+        let (current, _) = self
+            .rest_signed
+            .execute(FetchPositionMode {
+                query: self.query(()),
+            })
+            .await?;
+
+        let target = PositionModeParams::from(position_mode);
+        if current == target {
+            return Ok(());
+        }
+
+        self.rest_signed
+            .execute(SetPositionMode {
+                query: self.query(target),
+            })
+            .await?;
+
+        info!(?position_mode, "set Binance USD-M position mode");
+        Ok(())
+    }
+
+    /// Set the initial leverage of the provided symbol.
+    pub async fn set_leverage(
+        &self,
+        symbol: InstrumentNameExchange,
+        leverage: u32,
+    ) -> Result<(), UnindexedClientError> {
+        let (response, _) = self
+            .rest_signed
+            .execute(SetLeverage {
+                query: self.query(LeverageParams { symbol, leverage }),
+            })
+            .await?;
+
+        info!(
+            symbol = %response.symbol,
+            leverage = response.leverage,
+            "set Binance USD-M leverage"
+        );
+        Ok(())
+    }
+
+    /// Open a reduce only order, which can only reduce the size of an existing position.
+    ///
+    /// Equivalent to [`ExecutionClient::open_order`] with
+    /// [`OrderFlags::reduce_only`](crate::order::OrderFlags::reduce_only).
+    ///
+    /// In [`BinancePositionMode::Hedge`] the order is sent to the position side it reduces.
+    pub async fn open_order_reduce_only(
+        &self,
+        mut request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
+    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+        request.state.flags.reduce_only = true;
+        self.open_order(request).await
+    }
+
+    async fn configure_account(&self) -> Result<(), UnindexedClientError> {
+        self.configured
+            .get_or_try_init(|| async {
+                self.set_position_mode(self.config.position_mode).await?;
+                for (symbol, leverage) in &self.config.leverage {
+                    self.set_leverage(symbol.clone(), *leverage).await?;
+                }
+                Ok(())
+            })
+            .await
+            .map(|_| ())
+    }
+
+    fn query<Params>(&self, params: Params) -> SignedQuery<Params> {
+        SignedQuery::new(params, self.config.recv_window_ms)
+    }
+
+    fn record_instruments(&self, instruments: &[InstrumentNameExchange]) {
+        let mut tracked = self
+            .instruments
+            .write()
+            .expect("BinanceFuturesUsd instruments lock poisoned");
+
+        for instrument in instruments {
+            if !tracked.contains(instrument) {
+                tracked.push(instrument.clone());
+            }
+        }
+    }
+
+    fn tracked_instruments(&self) -> Vec<InstrumentNameExchange> {
+        self.instruments
+            .read()
+            .expect("BinanceFuturesUsd instruments lock poisoned")
+            .clone()
+    }
+
+    async fn fetch_account(&self) -> Result<BinanceFuturesAccount, UnindexedClientError> {
+        let (account, _) = self
+            .rest_signed
+            .execute(FetchAccount {
+                query: self.query(()),
+            })
+            .await?;
+
+        Ok(account)
+    }
+
+    async fn fetch_symbol_trades(
+        &self,
+        symbol: InstrumentNameExchange,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let (trades, _) = self
+            .rest_signed
+            .execute(FetchTrades {
+                query: self.query(FetchTradesParams {
+                    symbol,
+                    start_time: time_since.timestamp_millis(),
+                    limit: FETCH_TRADES_LIMIT,
+                }),
+            })
+            .await?;
+
+        Ok(trades
+            .into_iter()
+            .map(|trade| Trade {
+                id: TradeId::new(trade.id.to_string()),
+                order_id: OrderId::new(trade.order_id.to_string()),
+                fees: AssetFees::quote_fees(fees_quote(
+                    &trade.symbol,
+                    &trade.commission_asset,
+                    trade.commission,
+                    trade.price,
+                )),
+                instrument: trade.symbol,
+                strategy: StrategyId::unknown(),
+                time_exchange: trade.time,
+                side: trade.side,
+                price: trade.price,
+                quantity: trade.qty,
+            })
+            .collect())
+    }
+}
+
+impl ExecutionClient for BinanceFuturesUsd {
+    const EXCHANGE: ExchangeId = ExchangeId::BinanceFuturesUsd;
+    type Config = BinanceFuturesUsdConfig;
+    type AccountStream = BoxStream<'static, UnindexedAccountEvent>;
+
+    fn new(config: Self::Config) -> Self {
+        let rest_signed = RestClient::new(
+            config.base_url_rest.clone(),
+            request_signer(&config.api_key, &config.secret),
+            BinanceParser,
+        );
+
+        let rest_api_key = RestClient::new(
+            config.base_url_rest.clone(),
+            BinanceApiKey {
+                api_key: config.api_key.clone(),
+            },
+            BinanceParser,
+        );
+
+        Self {
+            config,
+            rest_signed: Arc::new(rest_signed),
+            rest_api_key: Arc::new(rest_api_key),
+            instruments: Arc::new(RwLock::new(Vec::new())),
+            configured: Arc::new(OnceCell::new()),
+        }
+    }
+
+    async fn account_snapshot(
+        &self,
+        assets: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<UnindexedAccountSnapshot, UnindexedClientError> {
+        self.record_instruments(instruments);
+        self.configure_account().await?;
+
+        let account = self.fetch_account().await?;
+        let open_orders = self.fetch_open_orders().await?;
+
+        let time_exchange = account
+            .assets
+            .iter()
+            .map(|asset| asset.update_time)
+            .chain(
+                account
+                    .positions
+                    .iter()
+                    .map(|position| position.update_time),
+            )
+            .max()
+            .unwrap_or_else(Utc::now);
+
+        let balances = account
+            .assets
+            .into_iter()
+            .filter(|asset| assets.contains(&asset.asset))
+            .map(AssetBalance::from)
+            .collect();
+
+        let positions = account
+            .positions
+            .into_iter()
+            .filter(|position| instruments.contains(&position.symbol))
+            .collect::<BinanceFuturesPositions>();
+
+        let mut orders_by_instrument = open_orders
+            .into_iter()
+            .filter(|order| instruments.contains(&order.key.instrument))
+            .into_group_map_by(|order| order.key.instrument.clone());
+
+        let instruments = instruments
+            .iter()
+            .map(|instrument| {
+                let orders = orders_by_instrument
+                    .remove(instrument)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|order| Order {
+                        key: order.key,
+                        side: order.side,
+                        price: order.price,
+                        quantity: order.quantity,
+                        kind: order.kind,
+                        time_in_force: order.time_in_force,
+                        state: OrderState::active(order.state),
+                    })
+                    .collect();
+
+                InstrumentAccountSnapshot::new(
+                    instrument.clone(),
+                    orders,
+                    Some(positions.position(instrument.clone(), time_exchange)),
+                )
+            })
+            .collect();
+
+        Ok(UnindexedAccountSnapshot::new(
+            ExchangeId::BinanceFuturesUsd,
+            balances,
+            instruments,
+        ))
+    }
+
+    async fn account_stream(
+        &self,
+        _: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<Self::AccountStream, UnindexedClientError> {
+        self.record_instruments(instruments);
+        self.configure_account().await?;
+
+        // Seed the position legs, since ACCOUNT_UPDATE events only contain the changed legs
+        let positions = self
+            .fetch_account()
+            .await?
+            .positions
+            .into_iter()
+            .collect::<BinanceFuturesPositions>();
+
+        let (listen_key, _) = self
+            .rest_api_key
+            .execute(CreateListenKey)
+            .await
+            .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+
+        let websocket = connect(format!(
+            "{}/ws/{}",
+            self.config.base_url_ws, listen_key.listen_key
+        ))
+        .await
+        .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+
+        info!(
+            exchange = %ExchangeId::BinanceFuturesUsd,
+            "connected to Binance USD-M user data stream"
+        );
+
+        let keep_alive = ListenKeyKeepAlive(tokio::spawn(keep_alive_listen_key(
+            Arc::clone(&self.rest_api_key),
+            Duration::from_secs(self.config.keep_alive_interval_secs),
+        )));
+
+        Ok(websocket
+            .filter_map(|message| {
+                std::future::ready(
+                    <WebSocketParser as StreamParser<BinanceFuturesUserEvent>>::parse(message),
+                )
+            })
+            .scan(
+                (keep_alive, positions),
+                |(_keep_alive, positions), event| {
+                    std::future::ready(match event {
+                        Ok(BinanceFuturesUserEvent::ListenKeyExpired) => {
+                            warn!(
+                                "Binance USD-M user data stream listenKey expired, ending stream"
+                            );
+                            None
+                        }
+                        Ok(event) => Some(event.into_account_events(positions)),
+                        Err(error) => {
+                            warn!(
+                                ?error,
+                                "Binance USD-M user data stream failed, ending stream"
+                            );
+                            None
+                        }
+                    })
+                },
+            )
+            .flat_map(futures::stream::iter)
+            .boxed())
+    }
+
+    async fn cancel_order(
+        &self,
+        request: OrderRequestCancel<ExchangeId, &InstrumentNameExchange>,
+    ) -> UnindexedOrderResponseCancel {
+        let result = self
+            .rest_signed
+            .execute(CancelOrder {
+                query: self.query(CancelOrderParams {
+                    symbol: request.key.instrument.clone(),
+                    orig_client_order_id: request.key.cid.clone(),
+                }),
+            })
+            .await
+            .map(|(response, _)| {
+                Cancelled::new(
+                    OrderId::new(response.order_id.to_string()),
+                    response.update_time,
+                )
+            })
+            .map_err(order_error);
+
+        UnindexedOrderResponseCancel {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            state: result,
+        }
+    }
+
+    async fn open_order(
+        &self,
+        request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
+    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+        let params = OpenOrderParams::new(
+            request.key.instrument.clone(),
+            request.key.cid.clone(),
+            request.state.side,
+            request.state.kind,
+            request.state.time_in_force,
+            request.state.price,
+            request.state.quantity,
+            request.state.flags,
+            self.config.position_mode,
+        );
+
+        let state = match params {
+            Some(params) => self
+                .rest_signed
+                .execute(OpenOrder {
+                    query: self.query(params),
+                })
+                .await
+                .map(|(response, _)| {
+                    debug!(
+                        order_id = response.order_id,
+                        status = %response.status,
+                        "Binance USD-M order opened"
+                    );
+                    Open::new(
+                        OrderId::new(response.order_id.to_string()),
+                        response.update_time,
+                        response.executed_qty,
+                    )
+                })
+                .map_err(order_error),
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                "Binance USD-M does not support {} orders with {}",
+                request.state.kind, request.state.time_in_force
+            )))),
+        };
+
+        Order {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            side: request.state.side,
+            price: request.state.price,
+            quantity: request.state.quantity,
+            kind: request.state.kind,
+            time_in_force: request.state.time_in_force,
+            state,
+        }
+    }
+
+    async fn fetch_balances(
+        &self,
+    ) -> Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError> {
+        Ok(self
+            .fetch_account()
+            .await?
+            .assets
+            .into_iter()
+            .map(AssetBalance::from)
+            .collect())
+    }
+
+    async fn fetch_open_orders(
+        &self,
+    ) -> Result<Vec<Order<ExchangeId, InstrumentNameExchange, Open>>, UnindexedClientError> {
+        let (orders, _) = self
+            .rest_signed
+            .execute(FetchOpenOrders {
+                query: self.query(()),
+            })
+            .await?;
+
+        Ok(orders
+            .into_iter()
+            .filter_map(|order| order.into_open_order())
+            .collect())
+    }
+
+    async fn fetch_trades(
+        &self,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let trades = futures::future::try_join_all(
+            self.tracked_instruments()
+                .into_iter()
+                .map(|symbol| self.fetch_symbol_trades(symbol, time_since)),
+        )
+        .await?;
+
+        Ok(trades.into_iter().flatten().collect())
+    }
+}
+
+/// Periodically keep the user data stream `listenKey` alive.
+///
+/// Binance USD-M only allows a single `listenKey` per account, so the keep-alive request does not
+/// reference it. Runs until aborted by the [`ListenKeyKeepAlive`] guard held by the account
+/// stream.
+async fn keep_alive_listen_key(
+    rest_api_key: Arc<RestClient<'static, BinanceApiKey, BinanceParser>>,
+    interval: Duration,
+) {
+    let mut interval = tokio::time::interval(interval);
+
+    // First tick completes immediately, and the listenKey was only just created
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        match rest_api_key.execute(KeepAliveListenKey).await {
+            Ok(_) => debug!("Binance USD-M user data stream listenKey kept alive"),
+            Err(error) => warn!(
+                ?error,
+                "failed to keep alive Binance USD-M user data stream listenKey"
+            ),
+        }
+    }
+}
+
+/// Parse a Binance USD-M order type and time in force into an [`OrderKind`] and
+/// [`TimeInForce`], including the conditional `STOP`, `TAKE_PROFIT` and `TRAILING_STOP_MARKET`
+/// order types.
+///
+/// `MARK_PRICE` working types are triggered by the [`TriggerSource::Mark`] price, while
+/// `CONTRACT_PRICE` working types are triggered by the [`TriggerSource::Last`] price.
+///
+/// Trailing stop `callback_rate`s are percentages (eg/ 1 for 1%), and are activated at the
+/// `activation_price` if provided, otherwise the `stop_price`.
+pub fn parse_order_kind_conditional(
+    kind: &str,
+    time_in_force: &str,
+    stop_price: Decimal,
+    working_type: &str,
+    activation_price: Option<Decimal>,
+    callback_rate: Option<Decimal>,
+) -> Option<(OrderKind, TimeInForce)> {
+    let source = match working_type {
+        "MARK_PRICE" => TriggerSource::Mark,
+        _ => TriggerSource::Last,
+    };
+    let trigger = OrderTrigger::new(stop_price, source);
+
+    let kind = match kind {
+        "STOP_MARKET" => OrderKind::StopMarket { trigger },
+        "STOP" => OrderKind::StopLimit { trigger },
+        "TAKE_PROFIT_MARKET" => OrderKind::TakeProfitMarket { trigger },
+        "TAKE_PROFIT" => OrderKind::TakeProfitLimit { trigger },
+        "TRAILING_STOP_MARKET" => OrderKind::TrailingStop {
+            trigger: OrderTrigger::new(activation_price.unwrap_or(stop_price), source),
+            offset: TrailingOffset::Percent(callback_rate? / Decimal::ONE_HUNDRED),
+        },
+        _ => return parse_order_kind(kind, time_in_force),
+    };
+
+    let (_, time_in_force) = parse_order_kind("LIMIT", time_in_force)?;
+    Some((kind, time_in_force))
+}
@@ -0,0 +1,692 @@
+use crate::{
+    balance::{AssetBalance, Balance},
+    client::binance::{
+        ListenKey, SignedQuery,
+        futures::{BinancePositionMode, parse_order_kind_conditional},
+    },
+    order::{
+        Order, OrderFlags, OrderKey, OrderKind, TimeInForce, TrailingOffset, TriggerSource,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::Open,
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::http::rest::RestRequest;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, de::IgnoredAny};
+use smol_str::SmolStr;
+use std::borrow::Cow;
+use tracing::warn;
+
+/// Binance USD-M `SIGNED` request to fetch the account information, including all asset
+/// balances and positions.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/account/rest-api/Account-Information-V2>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchAccount {
+    pub query: SignedQuery<()>,
+}
+
+impl RestRequest for FetchAccount {
+    type Response = BinanceFuturesAccount;
+    type QueryParams = SignedQuery<()>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v2/account")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// Binance USD-M account information response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "totalWalletBalance": "23.72469206",
+///     "availableBalance": "23.72469206",
+///     "assets": [
+///         {
+///             "asset": "USDT",
+///             "walletBalance": "23.72469206",
+///             "unrealizedProfit": "0.00000000",
+///             "marginBalance": "23.72469206",
+///             "availableBalance": "23.72469206",
+///             "updateTime": 1625474304765
+///         }
+///     ],
+///     "positions": [
+///         {
+///             "symbol": "BTCUSDT",
+///             "initialMargin": "0",
+///             "unrealizedProfit": "0.00000000",
+///             "leverage": "100",
+///             "isolated": true,
+///             "entryPrice": "0.00000",
+///             "positionSide": "BOTH",
+///             "positionAmt": "0",
+///             "updateTime": 0
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceFuturesAccount {
+    pub assets: Vec<BinanceFuturesAsset>,
+    pub positions: Vec<BinanceFuturesPosition>,
+}
+
+/// Binance USD-M margin asset balance, where the `total` balance is the `walletBalance`, and the
+/// `free` balance is the `availableBalance`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFuturesAsset {
+    pub asset: AssetNameExchange,
+    pub wallet_balance: Decimal,
+    pub available_balance: Decimal,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub update_time: DateTime<Utc>,
+}
+
+impl From<BinanceFuturesAsset> for AssetBalance<AssetNameExchange> {
+    fn from(value: BinanceFuturesAsset) -> Self {
+        AssetBalance::new(
+            value.asset,
+            Balance::new(value.wallet_balance, value.available_balance),
+            value.update_time,
+        )
+    }
+}
+
+/// Binance USD-M position of one `positionSide`, which is `BOTH` in one-way mode, and `LONG` or
+/// `SHORT` in hedge mode.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFuturesPosition {
+    pub symbol: InstrumentNameExchange,
+    pub position_side: SmolStr,
+    pub position_amt: Decimal,
+    pub entry_price: Decimal,
+    pub unrealized_profit: Decimal,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub update_time: DateTime<Utc>,
+}
+
+/// Binance USD-M `SIGNED` request to fetch all open orders.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/trade/rest-api/Current-All-Open-Orders>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchOpenOrders {
+    pub query: SignedQuery<()>,
+}
+
+impl RestRequest for FetchOpenOrders {
+    type Response = Vec<BinanceFuturesOrder>;
+    type QueryParams = SignedQuery<()>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/openOrders")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// Binance USD-M order.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "avgPrice": "0.00000",
+///     "clientOrderId": "abc",
+///     "executedQty": "0",
+///     "orderId": 1917641,
+///     "origQty": "0.40",
+///     "price": "0",
+///     "reduceOnly": false,
+///     "side": "BUY",
+///     "positionSide": "SHORT",
+///     "status": "NEW",
+///     "symbol": "BTCUSDT",
+///     "time": 1579276756075,
+///     "timeInForce": "GTC",
+///     "type": "LIMIT",
+///     "updateTime": 1579276756075
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFuturesOrder {
+    pub symbol: InstrumentNameExchange,
+    pub order_id: u64,
+    pub client_order_id: SmolStr,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub time_in_force: SmolStr,
+    #[serde(rename = "type")]
+    pub kind: SmolStr,
+    pub side: Side,
+    #[serde(default)]
+    pub stop_price: Decimal,
+    #[serde(default)]
+    pub working_type: SmolStr,
+    #[serde(default)]
+    pub activate_price: Option<Decimal>,
+    #[serde(default)]
+    pub price_rate: Option<Decimal>,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub update_time: DateTime<Utc>,
+}
+
+impl BinanceFuturesOrder {
+    /// Convert into an [`Order`] in the [`Open`] state, returning `None` if the Binance order
+    /// type is not supported.
+    pub fn into_open_order(self) -> Option<Order<ExchangeId, InstrumentNameExchange, Open>> {
+        let Some((kind, time_in_force)) = parse_order_kind_conditional(
+            &self.kind,
+            &self.time_in_force,
+            self.stop_price,
+            &self.working_type,
+            self.activate_price,
+            self.price_rate,
+        ) else {
+            warn!(
+                symbol = %self.symbol,
+                order_id = self.order_id,
+                kind = %self.kind,
+                "ignoring Binance USD-M open order with unsupported type"
+            );
+            return None;
+        };
+
+        Some(Order {
+            key: OrderKey::new(
+                ExchangeId::BinanceFuturesUsd,
+                self.symbol,
+                StrategyId::unknown(),
+                ClientOrderId::new(self.client_order_id),
+            ),
+            side: self.side,
+            price: self.price,
+            quantity: self.orig_qty,
+            kind,
+            time_in_force,
+            state: Open::new(
+                OrderId::new(self.order_id.to_string()),
+                self.update_time,
+                self.executed_qty,
+            ),
+        })
+    }
+}
+
+/// Binance USD-M `SIGNED` request to open a new order.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/trade/rest-api/New-Order>
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenOrder {
+    pub query: SignedQuery<OpenOrderParams>,
+}
+
+impl RestRequest for OpenOrder {
+    type Response = BinanceFuturesOrderResponse;
+    type QueryParams = SignedQuery<OpenOrderParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/order")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`OpenOrder`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrderParams {
+    pub symbol: InstrumentNameExchange,
+    pub side: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_side: Option<&'static str>,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activation_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_rate: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_position: Option<bool>,
+    pub new_client_order_id: ClientOrderId,
+    pub new_order_resp_type: &'static str,
+}
+
+impl OpenOrderParams {
+    /// Construct [`OpenOrderParams`] from the provided order fields, returning `None` if the
+    /// [`OrderKind`] and [`TimeInForce`] combination is not supported by Binance USD-M.
+    ///
+    /// Conditional [`OrderKind`]s are sent as `STOP`, `TAKE_PROFIT` & `TRAILING_STOP_MARKET`
+    /// orders, which do not support the [`TriggerSource::Index`] price, nor absolute
+    /// [`TrailingOffset::Price`] offsets.
+    ///
+    /// In [`BinancePositionMode::OneWay`], reduce only orders use the `reduceOnly` parameter.
+    /// In [`BinancePositionMode::Hedge`] (which does not accept `reduceOnly`), the order is
+    /// instead sent to the `positionSide` it reduces.
+    ///
+    /// [`OrderFlags`] `close_position` `STOP_MARKET` & `TAKE_PROFIT_MARKET` orders use the
+    /// `closePosition` parameter (without a quantity), and are otherwise sent as reduce only.
+    pub fn new(
+        symbol: InstrumentNameExchange,
+        cid: ClientOrderId,
+        side: Side,
+        kind: OrderKind,
+        time_in_force: TimeInForce,
+        price: Decimal,
+        quantity: Decimal,
+        flags: OrderFlags,
+        position_mode: BinancePositionMode,
+    ) -> Option<Self> {
+        let (kind_binance, limit) = match kind {
+            OrderKind::Market => ("MARKET", false),
+            OrderKind::Limit => ("LIMIT", true),
+            OrderKind::StopMarket { .. } => ("STOP_MARKET", false),
+            OrderKind::StopLimit { .. } => ("STOP", true),
+            OrderKind::TakeProfitMarket { .. } => ("TAKE_PROFIT_MARKET", false),
+            OrderKind::TakeProfitLimit { .. } => ("TAKE_PROFIT", true),
+            OrderKind::TrailingStop { .. } => ("TRAILING_STOP_MARKET", false),
+        };
+
+        let (time_in_force, price) = match (limit, time_in_force) {
+            (false, _) => (None, None),
+            (true, TimeInForce::GoodUntilCancelled { post_only: true }) => {
+                (Some("GTX"), Some(price))
+            }
+            (true, TimeInForce::GoodUntilCancelled { post_only: false }) => {
+                (Some("GTC"), Some(price))
+            }
+            (true, TimeInForce::ImmediateOrCancel) => (Some("IOC"), Some(price)),
+            (true, TimeInForce::FillOrKill) => (Some("FOK"), Some(price)),
+            (true, TimeInForce::GoodUntilEndOfDay) => return None,
+        };
+
+        let working_type = match kind.trigger().map(|trigger| trigger.source) {
+            None => None,
+            Some(TriggerSource::Last) => Some("CONTRACT_PRICE"),
+            Some(TriggerSource::Mark) => Some("MARK_PRICE"),
+            Some(TriggerSource::Index) => return None,
+        };
+
+        let (stop_price, activation_price, callback_rate) = match kind {
+            OrderKind::Market | OrderKind::Limit => (None, None, None),
+            OrderKind::TrailingStop {
+                trigger,
+                offset: TrailingOffset::Percent(percent),
+            } => (
+                None,
+                Some(trigger.price),
+                Some(percent * Decimal::ONE_HUNDRED),
+            ),
+            OrderKind::TrailingStop {
+                offset: TrailingOffset::Price(_),
+                ..
+            } => return None,
+            OrderKind::StopMarket { trigger }
+            | OrderKind::StopLimit { trigger }
+            | OrderKind::TakeProfitMarket { trigger }
+            | OrderKind::TakeProfitLimit { trigger } => (Some(trigger.price), None, None),
+        };
+
+        let close_position = flags.close_position
+            && matches!(
+                kind,
+                OrderKind::StopMarket { .. } | OrderKind::TakeProfitMarket { .. }
+            );
+
+        let (position_side, reduce_only) = match position_mode {
+            // Binance rejects reduceOnly when closePosition is sent
+            BinancePositionMode::OneWay => (
+                None,
+                (flags.is_reducing() && !close_position).then_some(true),
+            ),
+            BinancePositionMode::Hedge => match (side, flags.is_reducing()) {
+                (Side::Buy, false) | (Side::Sell, true) => (Some("LONG"), None),
+                (Side::Sell, false) | (Side::Buy, true) => (Some("SHORT"), None),
+            },
+        };
+
+        Some(Self {
+            symbol,
+            side: match side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            },
+            position_side,
+            kind: kind_binance,
+            time_in_force,
+            quantity: (!close_position).then_some(quantity),
+            price,
+            stop_price,
+            working_type,
+            activation_price,
+            callback_rate,
+            reduce_only,
+            close_position: close_position.then_some(true),
+            new_client_order_id: cid,
+            new_order_resp_type: "RESULT",
+        })
+    }
+}
+
+/// Binance USD-M [`OpenOrder`] & [`CancelOrder`] response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "clientOrderId": "testOrder",
+///     "cumQty": "0",
+///     "cumQuote": "0",
+///     "executedQty": "0",
+///     "orderId": 22542179,
+///     "avgPrice": "0.00000",
+///     "origQty": "10",
+///     "price": "0",
+///     "reduceOnly": false,
+///     "side": "BUY",
+///     "positionSide": "SHORT",
+///     "status": "NEW",
+///     "symbol": "BTCUSDT",
+///     "timeInForce": "GTD",
+///     "type": "TRAILING_STOP_MARKET",
+///     "updateTime": 1566818724722
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFuturesOrderResponse {
+    pub order_id: u64,
+    pub executed_qty: Decimal,
+    pub status: SmolStr,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub update_time: DateTime<Utc>,
+}
+
+/// Binance USD-M `SIGNED` request to cancel an open order.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/trade/rest-api/Cancel-Order>
+#[derive(Debug, Clone, Serialize)]
+pub struct CancelOrder {
+    pub query: SignedQuery<CancelOrderParams>,
+}
+
+impl RestRequest for CancelOrder {
+    type Response = BinanceFuturesOrderResponse;
+    type QueryParams = SignedQuery<CancelOrderParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/order")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::DELETE
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`CancelOrder`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderParams {
+    pub symbol: InstrumentNameExchange,
+    pub orig_client_order_id: ClientOrderId,
+}
+
+/// Binance USD-M `SIGNED` request to fetch the account trades of a symbol.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/trade/rest-api/Account-Trade-List>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchTrades {
+    pub query: SignedQuery<FetchTradesParams>,
+}
+
+impl RestRequest for FetchTrades {
+    type Response = Vec<BinanceFuturesTrade>;
+    type QueryParams = SignedQuery<FetchTradesParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/userTrades")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchTrades`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchTradesParams {
+    pub symbol: InstrumentNameExchange,
+    pub start_time: i64,
+    pub limit: u16,
+}
+
+/// Binance USD-M account trade.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "buyer": false,
+///     "commission": "-0.07819010",
+///     "commissionAsset": "USDT",
+///     "id": 698759,
+///     "maker": false,
+///     "orderId": 25851813,
+///     "price": "7819.01",
+///     "qty": "0.002",
+///     "quoteQty": "15.63802",
+///     "realizedPnl": "-0.91539999",
+///     "side": "SELL",
+///     "positionSide": "SHORT",
+///     "symbol": "BTCUSDT",
+///     "time": 1569514978020
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFuturesTrade {
+    pub symbol: InstrumentNameExchange,
+    pub id: u64,
+    pub order_id: u64,
+    pub side: Side,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: AssetNameExchange,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+}
+
+/// Binance USD-M `SIGNED` request to fetch the account position mode.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/account/rest-api/Get-Current-Position-Mode>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchPositionMode {
+    pub query: SignedQuery<()>,
+}
+
+impl RestRequest for FetchPositionMode {
+    type Response = PositionModeParams;
+    type QueryParams = SignedQuery<()>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/positionSide/dual")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// Binance USD-M `SIGNED` request to change the account position mode.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/trade/rest-api/Change-Position-Mode>
+#[derive(Debug, Clone, Serialize)]
+pub struct SetPositionMode {
+    pub query: SignedQuery<PositionModeParams>,
+}
+
+impl RestRequest for SetPositionMode {
+    type Response = IgnoredAny;
+    type QueryParams = SignedQuery<PositionModeParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/positionSide/dual")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// Binance USD-M position mode, where `dualSidePosition` is true in hedge mode.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionModeParams {
+    pub dual_side_position: bool,
+}
+
+impl From<BinancePositionMode> for PositionModeParams {
+    fn from(value: BinancePositionMode) -> Self {
+        Self {
+            dual_side_position: matches!(value, BinancePositionMode::Hedge),
+        }
+    }
+}
+
+/// Binance USD-M `SIGNED` request to change the initial leverage of a symbol.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/trade/rest-api/Change-Initial-Leverage>
+#[derive(Debug, Clone, Serialize)]
+pub struct SetLeverage {
+    pub query: SignedQuery<LeverageParams>,
+}
+
+impl RestRequest for SetLeverage {
+    type Response = LeverageParams;
+    type QueryParams = SignedQuery<LeverageParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/leverage")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`SetLeverage`] request specific query parameters, and response.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct LeverageParams {
+    pub symbol: InstrumentNameExchange,
+    pub leverage: u32,
+}
+
+/// Binance USD-M `USER_STREAM` request to create a user data stream `listenKey`.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/user-data-streams/Start-User-Data-Stream>
+#[derive(Debug, Copy, Clone)]
+pub struct CreateListenKey;
+
+impl RestRequest for CreateListenKey {
+    type Response = ListenKey;
+    type QueryParams = ();
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/listenKey")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+}
+
+/// Binance USD-M `USER_STREAM` request to extend the validity of the user data stream
+/// `listenKey` by 60 minutes.
+///
+/// See docs: <https://developers.binance.com/docs/derivatives/usds-margined-futures/user-data-streams/Keepalive-User-Data-Stream>
+#[derive(Debug, Copy, Clone)]
+pub struct KeepAliveListenKey;
+
+impl RestRequest for KeepAliveListenKey {
+    type Response = IgnoredAny;
+    type QueryParams = ();
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/fapi/v1/listenKey")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::PUT
+    }
+}
@@ -1 +1,252 @@
+use crate::{
+    error::{ApiError, ClientError, ConnectivityError},
+    order::{OrderKind, TimeInForce},
+};
+use barter_instrument::{asset::name::AssetNameExchange, instrument::name::InstrumentNameExchange};
+use barter_integration::{
+    error::SocketError,
+    protocol::http::{
+        BuildStrategy, HttpParser,
+        private::{RequestSigner, Signer, encoder::HexEncoder},
+        rest::RestRequest,
+    },
+};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt::Debug;
+use tokio::task::JoinHandle;
 
+/// Binance Spot [`ExecutionClient`](super::ExecutionClient) implementation.
+pub mod spot;
+
+/// Binance USD-M Futures [`ExecutionClient`](super::ExecutionClient) implementation.
+pub mod futures;
+
+/// Http header containing the Binance API key.
+pub const HEADER_BINANCE_API_KEY: &str = "X-MBX-APIKEY";
+
+/// Default number of milliseconds after the request `timestamp` that Binance will still accept a
+/// signed request for.
+pub const DEFAULT_RECV_WINDOW_MS: u64 = 5000;
+
+/// Convenient type alias for the HMAC-SHA256 [`RequestSigner`] used to sign Binance
+/// `SIGNED` endpoint requests.
+pub type BinanceRequestSigner = RequestSigner<BinanceSigner, Hmac<Sha256>, HexEncoder>;
+
+/// Construct a [`BinanceRequestSigner`] from the provided API credentials.
+pub fn request_signer(api_key: &str, secret: &str) -> BinanceRequestSigner {
+    let mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC-SHA256 accepts keys of any length");
+
+    RequestSigner::new(
+        BinanceSigner {
+            api_key: api_key.to_string(),
+        },
+        mac,
+        HexEncoder,
+    )
+}
+
+/// Query parameters of a Binance `SIGNED` endpoint request.
+///
+/// The request specific `Params` are flattened alongside the mandatory `timestamp` and
+/// `recvWindow` parameters, which are included in the signed query string.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedQuery<Params> {
+    #[serde(flatten)]
+    pub params: Params,
+    pub timestamp: i64,
+    pub recv_window: u64,
+}
+
+impl<Params> SignedQuery<Params> {
+    /// Construct a new [`Self`] timestamped with the current time.
+    pub fn new(params: Params, recv_window: u64) -> Self {
+        Self {
+            params,
+            timestamp: Utc::now().timestamp_millis(),
+            recv_window,
+        }
+    }
+}
+
+/// Binance user data stream `listenKey` response.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKey {
+    pub listen_key: String,
+}
+
+/// Aborts the user data stream `listenKey` keep-alive task when dropped, ensuring it does not
+/// outlive the account stream it keeps alive.
+#[derive(Debug)]
+pub struct ListenKeyKeepAlive(pub JoinHandle<()>);
+
+impl Drop for ListenKeyKeepAlive {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Binance API specific [`Signer`] logic.
+///
+/// Binance `SIGNED` endpoints require the HMAC-SHA256 signature of the full url encoded query
+/// string (including the `timestamp`) to be appended as the `signature` query parameter.
+#[derive(Debug, Clone)]
+pub struct BinanceSigner {
+    pub api_key: String,
+}
+
+/// Configuration required to sign a Binance [`RestRequest`].
+#[derive(Debug)]
+pub struct BinanceSignConfig<'a> {
+    pub api_key: &'a str,
+    pub query: String,
+}
+
+impl Signer for BinanceSigner {
+    type Config<'a>
+        = BinanceSignConfig<'a>
+    where
+        Self: 'a;
+
+    fn config<'a, Request>(
+        &'a self,
+        _: Request,
+        builder: &reqwest::RequestBuilder,
+    ) -> Result<Self::Config<'a>, SocketError>
+    where
+        Request: RestRequest,
+    {
+        let query = builder
+            .try_clone()
+            .ok_or_else(|| SocketError::Unsupported {
+                entity: "Binance".to_string(),
+                item: "streaming request bodies".to_string(),
+            })?
+            .build()?
+            .url()
+            .query()
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(BinanceSignConfig {
+            api_key: self.api_key.as_str(),
+            query,
+        })
+    }
+
+    fn add_bytes_to_sign<M>(mac: &mut M, config: &Self::Config<'_>)
+    where
+        M: Mac,
+    {
+        mac.update(config.query.as_bytes());
+    }
+
+    fn build_signed_request(
+        config: Self::Config<'_>,
+        builder: reqwest::RequestBuilder,
+        signature: String,
+    ) -> Result<reqwest::Request, SocketError> {
+        builder
+            .header(HEADER_BINANCE_API_KEY, config.api_key)
+            .query(&[("signature", signature)])
+            .build()
+            .map_err(SocketError::from)
+    }
+}
+
+/// [`BuildStrategy`] for Binance `USER_STREAM` endpoints, which only require the API key header.
+#[derive(Debug, Clone)]
+pub struct BinanceApiKey {
+    pub api_key: String,
+}
+
+impl BuildStrategy for BinanceApiKey {
+    fn build<Request>(
+        &self,
+        _: Request,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Request, SocketError>
+    where
+        Request: RestRequest,
+    {
+        builder
+            .header(HEADER_BINANCE_API_KEY, &self.api_key)
+            .build()
+            .map_err(SocketError::from)
+    }
+}
+
+/// Binance API error response.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/errors>
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct BinanceApiError {
+    pub code: i64,
+    pub msg: String,
+}
+
+/// [`HttpParser`] for Binance Http APIs, mapping [`BinanceApiError`]s to [`ClientError`]s.
+#[derive(Debug, Copy, Clone)]
+pub struct BinanceParser;
+
+impl HttpParser for BinanceParser {
+    type ApiError = BinanceApiError;
+    type OutputError = ClientError<AssetNameExchange, InstrumentNameExchange>;
+
+    fn parse_api_error(&self, status: StatusCode, error: Self::ApiError) -> Self::OutputError {
+        if status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::IM_A_TEAPOT {
+            return ClientError::Api(ApiError::RateLimit);
+        }
+
+        let BinanceApiError { code, msg } = error;
+
+        match code {
+            // TOO_MANY_REQUESTS & TOO_MANY_ORDERS
+            -1003 | -1015 => ClientError::Api(ApiError::RateLimit),
+            // UNAUTHORIZED, INVALID_TIMESTAMP, BAD_API_KEY_FMT & REJECTED_MBX_KEY
+            -1002 | -1021 | -1022 | -2014 | -2015 => ClientError::Connectivity(
+                ConnectivityError::Socket(format!("Binance authentication failed: {msg}")),
+            ),
+            // CANCEL_REJECTED (eg/ unknown order) & NO_SUCH_ORDER
+            -2011 | -2013 => ClientError::Api(ApiError::OrderRejected(msg)),
+            _ => ClientError::Api(ApiError::OrderRejected(format!(
+                "Binance error code {code}: {msg}"
+            ))),
+        }
+    }
+}
+
+/// Parse a Binance order type and time in force into an [`OrderKind`] and [`TimeInForce`].
+///
+/// Post only orders are represented by the Spot `LIMIT_MAKER` order type, and the Futures `GTX`
+/// time in force.
+///
+/// Returns `None` for unsupported order types (eg/ `STOP_LOSS`, `TAKE_PROFIT_LIMIT`, etc.).
+pub fn parse_order_kind(kind: &str, time_in_force: &str) -> Option<(OrderKind, TimeInForce)> {
+    let kind = match kind {
+        "MARKET" => OrderKind::Market,
+        "LIMIT" => OrderKind::Limit,
+        "LIMIT_MAKER" => {
+            return Some((
+                OrderKind::Limit,
+                TimeInForce::GoodUntilCancelled { post_only: true },
+            ));
+        }
+        _ => return None,
+    };
+
+    let time_in_force = match time_in_force {
+        "GTX" => TimeInForce::GoodUntilCancelled { post_only: true },
+        "IOC" => TimeInForce::ImmediateOrCancel,
+        "FOK" => TimeInForce::FillOrKill,
+        _ => TimeInForce::GoodUntilCancelled { post_only: false },
+    };
+
+    Some((kind, time_in_force))
+}
@@ -0,0 +1,264 @@
+use crate::{
+    AccountEventKind, UnindexedAccountEvent,
+    balance::{AssetBalance, Balance},
+    client::{binance::parse_order_kind, fees_quote},
+    error::{ApiError, OrderError},
+    order::{
+        Order, OrderKey,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::snapshot::Snapshot;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use smol_str::SmolStr;
+use tracing::warn;
+
+/// Binance Spot user data stream event.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/user-data-stream>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "e")]
+pub enum BinanceSpotUserEvent {
+    #[serde(rename = "outboundAccountPosition")]
+    AccountPosition(BinanceSpotAccountPosition),
+    #[serde(rename = "executionReport")]
+    ExecutionReport(Box<BinanceSpotExecutionReport>),
+    /// `listenKey` is no longer valid, so the user data stream will stop sending events.
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired,
+    /// Other events (eg/ `balanceUpdate`) that do not map to an [`UnindexedAccountEvent`].
+    #[serde(other)]
+    Other,
+}
+
+impl BinanceSpotUserEvent {
+    /// Map the [`BinanceSpotUserEvent`] into zero or more [`UnindexedAccountEvent`]s.
+    pub fn into_account_events(self) -> Vec<UnindexedAccountEvent> {
+        match self {
+            Self::AccountPosition(position) => position
+                .balances
+                .into_iter()
+                .map(|balance| {
+                    UnindexedAccountEvent::new(
+                        ExchangeId::BinanceSpot,
+                        AccountEventKind::BalanceSnapshot(Snapshot(AssetBalance::new(
+                            balance.asset,
+                            Balance::new(balance.free + balance.locked, balance.free),
+                            position.time_last_update,
+                        ))),
+                    )
+                })
+                .collect(),
+            Self::ExecutionReport(report) => report.into_account_events(),
+            Self::ListenKeyExpired | Self::Other => vec![],
+        }
+    }
+}
+
+/// Binance Spot user data stream account balance update, sent whenever an account balance
+/// changes.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "e": "outboundAccountPosition",
+///     "E": 1564034571105,
+///     "u": 1564034571073,
+///     "B": [
+///         { "a": "ETH", "f": "10000.000000", "l": "0.000000" }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceSpotAccountPosition {
+    #[serde(
+        rename = "u",
+        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+    )]
+    pub time_last_update: DateTime<Utc>,
+    #[serde(rename = "B")]
+    pub balances: Vec<BinanceSpotAccountPositionBalance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceSpotAccountPositionBalance {
+    #[serde(rename = "a")]
+    pub asset: AssetNameExchange,
+    #[serde(rename = "f")]
+    pub free: Decimal,
+    #[serde(rename = "l")]
+    pub locked: Decimal,
+}
+
+/// Binance Spot user data stream order update.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "e": "executionReport",
+///     "E": 1499405658658,
+///     "s": "ETHBTC",
+///     "c": "mUvoqJxFIILMdfAW5iGSOW",
+///     "S": "BUY",
+///     "o": "LIMIT",
+///     "f": "GTC",
+///     "q": "1.00000000",
+///     "p": "0.10264410",
+///     "x": "TRADE",
+///     "X": "PARTIALLY_FILLED",
+///     "r": "NONE",
+///     "i": 4293153,
+///     "l": "0.50000000",
+///     "z": "0.50000000",
+///     "L": "0.10264410",
+///     "n": "0.00050000",
+///     "N": "ETH",
+///     "T": 1499405658657,
+///     "t": 718277,
+///     "C": ""
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceSpotExecutionReport {
+    #[serde(rename = "s")]
+    pub symbol: InstrumentNameExchange,
+    #[serde(rename = "c")]
+    pub client_order_id: SmolStr,
+    /// Original client order id of the cancelled order, empty if the order was not cancelled.
+    #[serde(rename = "C", default)]
+    pub orig_client_order_id: SmolStr,
+    #[serde(rename = "S")]
+    pub side: Side,
+    #[serde(rename = "o")]
+    pub kind: SmolStr,
+    #[serde(rename = "f")]
+    pub time_in_force: SmolStr,
+    #[serde(rename = "q")]
+    pub quantity: Decimal,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "x")]
+    pub execution_type: SmolStr,
+    #[serde(rename = "X")]
+    pub status: SmolStr,
+    #[serde(rename = "r")]
+    pub reject_reason: SmolStr,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l")]
+    pub last_quantity: Decimal,
+    #[serde(rename = "z")]
+    pub cumulative_quantity: Decimal,
+    #[serde(rename = "L")]
+    pub last_price: Decimal,
+    #[serde(rename = "n", default)]
+    pub commission: Decimal,
+    #[serde(rename = "N", default)]
+    pub commission_asset: Option<AssetNameExchange>,
+    #[serde(
+        rename = "T",
+        deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+    )]
+    pub time_transaction: DateTime<Utc>,
+    #[serde(rename = "t")]
+    pub trade_id: i64,
+}
+
+impl BinanceSpotExecutionReport {
+    /// Map the [`BinanceSpotExecutionReport`] into a [`Trade`] (if the order was filled),
+    /// followed by an [`Order`] snapshot.
+    pub fn into_account_events(self) -> Vec<UnindexedAccountEvent> {
+        let mut events = Vec::with_capacity(2);
+
+        if self.execution_type == "TRADE" {
+            let fees = self
+                .commission_asset
+                .as_ref()
+                .map(|asset| fees_quote(&self.symbol, asset, self.commission, self.last_price))
+                .unwrap_or_default();
+
+            events.push(UnindexedAccountEvent::new(
+                ExchangeId::BinanceSpot,
+                Trade {
+                    id: TradeId::new(self.trade_id.to_string()),
+                    order_id: OrderId::new(self.order_id.to_string()),
+                    instrument: self.symbol.clone(),
+                    strategy: StrategyId::unknown(),
+                    time_exchange: self.time_transaction,
+                    side: self.side,
+                    price: self.last_price,
+                    quantity: self.last_quantity,
+                    fees: AssetFees::quote_fees(fees),
+                },
+            ));
+        }
+
+        let Some((kind, time_in_force)) = parse_order_kind(&self.kind, &self.time_in_force) else {
+            warn!(
+                symbol = %self.symbol,
+                order_id = self.order_id,
+                kind = %self.kind,
+                "ignoring Binance Spot executionReport for unsupported order type"
+            );
+            return events;
+        };
+
+        let order_id = OrderId::new(self.order_id.to_string());
+        let state = match self.status.as_str() {
+            "NEW" | "PARTIALLY_FILLED" | "PENDING_CANCEL" => OrderState::active(Open::new(
+                order_id,
+                self.time_transaction,
+                self.cumulative_quantity,
+            )),
+            "FILLED" => OrderState::fully_filled(),
+            "CANCELED" => OrderState::inactive(Cancelled::new(order_id, self.time_transaction)),
+            "EXPIRED" | "EXPIRED_IN_MATCH" => OrderState::expired(),
+            "REJECTED" => OrderState::inactive(OrderError::Rejected(ApiError::OrderRejected(
+                self.reject_reason.to_string(),
+            ))),
+            status => {
+                warn!(
+                    symbol = %self.symbol,
+                    order_id = self.order_id,
+                    %status,
+                    "ignoring Binance Spot executionReport with unknown order status"
+                );
+                return events;
+            }
+        };
+
+        let cid = if self.orig_client_order_id.is_empty() {
+            self.client_order_id
+        } else {
+            self.orig_client_order_id
+        };
+
+        events.push(UnindexedAccountEvent::new(
+            ExchangeId::BinanceSpot,
+            AccountEventKind::OrderSnapshot(Snapshot(Order {
+                key: OrderKey::new(
+                    ExchangeId::BinanceSpot,
+                    self.symbol,
+                    StrategyId::unknown(),
+                    ClientOrderId::new(cid),
+                ),
+                side: self.side,
+                price: self.price,
+                quantity: self.quantity,
+                kind,
+                time_in_force,
+                state,
+            })),
+        ));
+
+        events
+    }
+}
@@ -0,0 +1,617 @@
+use crate::{
+    InstrumentAccountSnapshot, UnindexedAccountEvent, UnindexedAccountSnapshot,
+    balance::AssetBalance,
+    client::{
+        ExecutionClient,
+        binance::{
+            BinanceApiKey, BinanceParser, BinanceRequestSigner, DEFAULT_RECV_WINDOW_MS,
+            ListenKeyKeepAlive, SignedQuery, request_signer,
+            spot::{
+                account::BinanceSpotUserEvent,
+                request::{
+                    CancelOrder, CancelOrderParams, CreateListenKey, FetchAccount, FetchOpenOrders,
+                    FetchTrades, FetchTradesParams, KeepAliveListenKey, ListenKeyParams, OpenOrder,
+                    OpenOrderListOco, OpenOrderListOcoParams, OpenOrderParams,
+                },
+            },
+        },
+        fees_quote, order_error,
+    },
+    error::{ApiError, OrderError, UnindexedClientError, UnindexedOrderError},
+    order::{
+        Order, OrderKey,
+        group::OrderGroup,
+        id::{OrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side,
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::{
+    StreamParser,
+    http::rest::client::RestClient,
+    websocket::{WebSocketParser, connect},
+};
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+/// Binance Spot Http request and response models.
+pub mod request;
+
+/// Binance Spot user data stream event models.
+pub mod account;
+
+/// Maximum number of trades Binance Spot returns per `myTrades` request.
+const FETCH_TRADES_LIMIT: u16 = 1000;
+
+/// [`BinanceSpot`] execution client configuration.
+///
+/// Only the API credentials are required, with the remaining fields defaulting to the Binance
+/// Spot production endpoints.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct BinanceSpotConfig {
+    pub api_key: String,
+    pub secret: String,
+    #[serde(default = "default_base_url_rest")]
+    pub base_url_rest: String,
+    #[serde(default = "default_base_url_ws")]
+    pub base_url_ws: String,
+    #[serde(default = "default_recv_window_ms")]
+    pub recv_window_ms: u64,
+    /// Interval at which the user data stream `listenKey` is kept alive.
+    ///
+    /// Binance closes user data streams 60 minutes after the last keep-alive.
+    #[serde(default = "default_keep_alive_interval_secs")]
+    pub keep_alive_interval_secs: u64,
+}
+
+impl BinanceSpotConfig {
+    /// Construct a [`BinanceSpotConfig`] for the Binance Spot production endpoints.
+    pub fn new<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            api_key: api_key.into(),
+            secret: secret.into(),
+            base_url_rest: default_base_url_rest(),
+            base_url_ws: default_base_url_ws(),
+            recv_window_ms: default_recv_window_ms(),
+            keep_alive_interval_secs: default_keep_alive_interval_secs(),
+        }
+    }
+
+    /// Construct a [`BinanceSpotConfig`] for the Binance Spot testnet endpoints.
+    ///
+    /// Note that the Spot testnet requires separate API credentials.
+    pub fn testnet<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            base_url_rest: "https://testnet.binance.vision".to_string(),
+            base_url_ws: "wss://stream.testnet.binance.vision".to_string(),
+            ..Self::new(api_key, secret)
+        }
+    }
+}
+
+impl Debug for BinanceSpotConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinanceSpotConfig")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field("base_url_rest", &self.base_url_rest)
+            .field("base_url_ws", &self.base_url_ws)
+            .field("recv_window_ms", &self.recv_window_ms)
+            .field("keep_alive_interval_secs", &self.keep_alive_interval_secs)
+            .finish()
+    }
+}
+
+fn default_base_url_rest() -> String {
+    "https://api.binance.com".to_string()
+}
+
+fn default_base_url_ws() -> String {
+    "wss://stream.binance.com:9443".to_string()
+}
+
+fn default_recv_window_ms() -> u64 {
+    DEFAULT_RECV_WINDOW_MS
+}
+
+fn default_keep_alive_interval_secs() -> u64 {
+    30 * 60
+}
+
+/// Binance Spot live [`ExecutionClient`].
+///
+/// Orders and account state are actioned via `SIGNED` Http endpoints, while the
+/// [`AccountStream`](ExecutionClient::AccountStream) is built on the user data stream, which is
+/// kept alive for as long as the stream is not dropped.
+///
+/// Binance only provides trade history per symbol, so [`ExecutionClient::fetch_trades`] fetches
+/// trades for the instruments previously provided to [`ExecutionClient::account_snapshot`] or
+/// [`ExecutionClient::account_stream`].
+///
+/// Spot orders cannot reduce a position, so open requests with reducing
+/// [`OrderFlags`](crate::order::OrderFlags) are rejected without a request.
+#[derive(Debug, Clone)]
+pub struct BinanceSpot {
+    pub config: BinanceSpotConfig,
+    rest_signed: Arc<RestClient<'static, BinanceRequestSigner, BinanceParser>>,
+    rest_api_key: Arc<RestClient<'static, BinanceApiKey, BinanceParser>>,
+    instruments: Arc<RwLock<Vec<InstrumentNameExchange>>>,
+}
+
+impl BinanceSpot {
+    fn query<Params>(&self, params: Params) -> SignedQuery<Params> {
+        SignedQuery::new(params, self.config.recv_window_ms)
+    }
+
+    fn record_instruments(&self, instruments: &[InstrumentNameExchange]) {
+        let mut tracked = self
+            .instruments
+            .write()
+            .expect("BinanceSpot instruments lock poisoned");
+
+        for instrument in instruments {
+            if !tracked.contains(instrument) {
+                tracked.push(instrument.clone());
+            }
+        }
+    }
+
+    fn tracked_instruments(&self) -> Vec<InstrumentNameExchange> {
+        self.instruments
+            .read()
+            .expect("BinanceSpot instruments lock poisoned")
+            .clone()
+    }
+
+    async fn fetch_symbol_trades(
+        &self,
+        symbol: InstrumentNameExchange,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let (trades, _) = self
+            .rest_signed
+            .execute(FetchTrades {
+                query: self.query(FetchTradesParams {
+                    symbol,
+                    start_time: time_since.timestamp_millis(),
+                    limit: FETCH_TRADES_LIMIT,
+                }),
+            })
+            .await?;
+
+        Ok(trades
+            .into_iter()
+            .map(|trade| Trade {
+                id: TradeId::new(trade.id.to_string()),
+                order_id: OrderId::new(trade.order_id.to_string()),
+                fees: AssetFees::quote_fees(fees_quote(
+                    &trade.symbol,
+                    &trade.commission_asset,
+                    trade.commission,
+                    trade.price,
+                )),
+                instrument: trade.symbol,
+                strategy: StrategyId::unknown(),
+                time_exchange: trade.time,
+                side: if trade.is_buyer {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                },
+                price: trade.price,
+                quantity: trade.qty,
+            })
+            .collect())
+    }
+}
+
+impl ExecutionClient for BinanceSpot {
+    const EXCHANGE: ExchangeId = ExchangeId::BinanceSpot;
+    type Config = BinanceSpotConfig;
+    type AccountStream = BoxStream<'static, UnindexedAccountEvent>;
+
+    fn new(config: Self::Config) -> Self {
+        let rest_signed = RestClient::new(
+            config.base_url_rest.clone(),
+            request_signer(&config.api_key, &config.secret),
+            BinanceParser,
+        );
+
+        let rest_api_key = RestClient::new(
+            config.base_url_rest.clone(),
+            BinanceApiKey {
+                api_key: config.api_key.clone(),
+            },
+            BinanceParser,
+        );
+
+        Self {
+            config,
+            rest_signed: Arc::new(rest_signed),
+            rest_api_key: Arc::new(rest_api_key),
+            instruments: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    async fn account_snapshot(
+        &self,
+        assets: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<UnindexedAccountSnapshot, UnindexedClientError> {
+        self.record_instruments(instruments);
+
+        let balances = self.fetch_balances().await?;
+        let open_orders = self.fetch_open_orders().await?;
+
+        let balances = balances
+            .into_iter()
+            .filter(|balance| assets.contains(&balance.asset))
+            .collect();
+
+        let mut orders_by_instrument = open_orders
+            .into_iter()
+            .filter(|order| instruments.contains(&order.key.instrument))
+            .into_group_map_by(|order| order.key.instrument.clone());
+
+        let instruments = instruments
+            .iter()
+            .map(|instrument| {
+                let orders = orders_by_instrument
+                    .remove(instrument)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|order| Order {
+                        key: order.key,
+                        side: order.side,
+                        price: order.price,
+                        quantity: order.quantity,
+                        kind: order.kind,
+                        time_in_force: order.time_in_force,
+                        state: OrderState::active(order.state),
+                    })
+                    .collect();
+
+                InstrumentAccountSnapshot::new(instrument.clone(), orders, None)
+            })
+            .collect();
+
+        Ok(UnindexedAccountSnapshot::new(
+            ExchangeId::BinanceSpot,
+            balances,
+            instruments,
+        ))
+    }
+
+    async fn account_stream(
+        &self,
+        _: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<Self::AccountStream, UnindexedClientError> {
+        self.record_instruments(instruments);
+
+        let (listen_key, _) = self
+            .rest_api_key
+            .execute(CreateListenKey)
+            .await
+            .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+        let listen_key = listen_key.listen_key;
+
+        let websocket = connect(format!("{}/ws/{}", self.config.base_url_ws, listen_key))
+            .await
+            .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+
+        info!(
+            exchange = %ExchangeId::BinanceSpot,
+            "connected to Binance Spot user data stream"
+        );
+
+        let keep_alive = ListenKeyKeepAlive(tokio::spawn(keep_alive_listen_key(
+            Arc::clone(&self.rest_api_key),
+            listen_key,
+            Duration::from_secs(self.config.keep_alive_interval_secs),
+        )));
+
+        Ok(websocket
+            .filter_map(|message| {
+                std::future::ready(
+                    <WebSocketParser as StreamParser<BinanceSpotUserEvent>>::parse(message),
+                )
+            })
+            .scan(keep_alive, |_keep_alive, event| {
+                std::future::ready(match event {
+                    Ok(BinanceSpotUserEvent::ListenKeyExpired) => {
+                        warn!("Binance Spot user data stream listenKey expired, ending stream");
+                        None
+                    }
+                    Ok(event) => Some(event.into_account_events()),
+                    Err(error) => {
+                        warn!(
+                            ?error,
+                            "Binance Spot user data stream failed, ending stream"
+                        );
+                        None
+                    }
+                })
+            })
+            .flat_map(futures::stream::iter)
+            .boxed())
+    }
+
+    async fn cancel_order(
+        &self,
+        request: OrderRequestCancel<ExchangeId, &InstrumentNameExchange>,
+    ) -> UnindexedOrderResponseCancel {
+        let result = self
+            .rest_signed
+            .execute(CancelOrder {
+                query: self.query(CancelOrderParams {
+                    symbol: request.key.instrument.clone(),
+                    orig_client_order_id: request.key.cid.clone(),
+                }),
+            })
+            .await
+            .map(|(response, _)| {
+                Cancelled::new(
+                    OrderId::new(response.order_id.to_string()),
+                    response.transact_time.unwrap_or_else(Utc::now),
+                )
+            })
+            .map_err(order_error);
+
+        UnindexedOrderResponseCancel {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            state: result,
+        }
+    }
+
+    async fn open_order(
+        &self,
+        request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
+    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+        let params = OpenOrderParams::new(
+            request.key.instrument.clone(),
+            request.key.cid.clone(),
+            request.state.side,
+            request.state.kind,
+            request.state.time_in_force,
+            request.state.price,
+            request.state.quantity,
+            request.state.flags,
+        );
+
+        let state = match params {
+            _ if request.state.flags.is_reducing() => {
+                Err(OrderError::Rejected(ApiError::OrderRejected(
+                    "Binance Spot does not support reduce only or close position orders"
+                        .to_string(),
+                )))
+            }
+            Some(params) => self
+                .rest_signed
+                .execute(OpenOrder {
+                    query: self.query(params),
+                })
+                .await
+                .map(|(response, _)| {
+                    debug!(
+                        order_id = response.order_id,
+                        status = %response.status,
+                        "Binance Spot order opened"
+                    );
+                    Open::new(
+                        OrderId::new(response.order_id.to_string()),
+                        response.transact_time,
+                        response.executed_qty,
+                    )
+                })
+                .map_err(order_error),
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                "Binance Spot does not support {} orders with {}",
+                request.state.kind, request.state.time_in_force
+            )))),
+        };
+
+        Order {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            side: request.state.side,
+            price: request.state.price,
+            quantity: request.state.quantity,
+            kind: request.state.kind,
+            time_in_force: request.state.time_in_force,
+            state,
+        }
+    }
+
+    /// Binance Spot natively supports One-Cancels-Other groups of a stop-loss order and a limit
+    /// maker or take-profit order (see [`OpenOrderListOcoParams::new`]).
+    fn supports_order_group<ExchangeKey, InstrumentKey>(
+        &self,
+        group: &OrderGroup<ExchangeKey, InstrumentKey>,
+    ) -> bool
+    where
+        InstrumentKey: PartialEq,
+    {
+        OpenOrderListOcoParams::supports(group)
+    }
+
+    async fn open_order_group(
+        &self,
+        group: OrderGroup<ExchangeId, &InstrumentNameExchange>,
+    ) -> Vec<Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>> {
+        let Some(params) = OpenOrderListOcoParams::new(&group) else {
+            return futures::stream::FuturesUnordered::from_iter(
+                group
+                    .orders_initial()
+                    .map(|request| self.open_order(request.clone())),
+            )
+            .collect()
+            .await;
+        };
+
+        let result = self
+            .rest_signed
+            .execute(OpenOrderListOco {
+                query: self.query(params),
+            })
+            .await
+            .map(|(response, _)| response.order_reports)
+            .map_err(order_error);
+
+        group
+            .orders()
+            .map(|request| {
+                let state = match &result {
+                    Ok(reports) => reports
+                        .iter()
+                        .find(|report| report.client_order_id == request.key.cid)
+                        .map(|report| {
+                            debug!(
+                                order_id = report.order_id,
+                                status = %report.status,
+                                "Binance Spot OCO order opened"
+                            );
+                            Open::new(
+                                OrderId::new(report.order_id.to_string()),
+                                report.transact_time,
+                                report.executed_qty,
+                            )
+                        })
+                        .ok_or_else(|| {
+                            OrderError::Rejected(ApiError::OrderRejected(format!(
+                                "Binance Spot OCO response missing order: {}",
+                                request.key.cid
+                            )))
+                        }),
+                    Err(error) => Err(error.clone()),
+                };
+
+                Order {
+                    key: OrderKey {
+                        exchange: request.key.exchange,
+                        instrument: request.key.instrument.clone(),
+                        strategy: request.key.strategy.clone(),
+                        cid: request.key.cid.clone(),
+                    },
+                    side: request.state.side,
+                    price: request.state.price,
+                    quantity: request.state.quantity,
+                    kind: request.state.kind,
+                    time_in_force: request.state.time_in_force,
+                    state,
+                }
+            })
+            .collect()
+    }
+
+    async fn fetch_balances(
+        &self,
+    ) -> Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError> {
+        let (account, _) = self
+            .rest_signed
+            .execute(FetchAccount {
+                query: self.query(()),
+            })
+            .await?;
+
+        let time_exchange = account.update_time;
+
+        Ok(account
+            .balances
+            .into_iter()
+            .map(|balance| balance.into_asset_balance(time_exchange))
+            .collect())
+    }
+
+    async fn fetch_open_orders(
+        &self,
+    ) -> Result<Vec<Order<ExchangeId, InstrumentNameExchange, Open>>, UnindexedClientError> {
+        let (orders, _) = self
+            .rest_signed
+            .execute(FetchOpenOrders {
+                query: self.query(()),
+            })
+            .await?;
+
+        Ok(orders
+            .into_iter()
+            .filter_map(|order| order.into_open_order())
+            .collect())
+    }
+
+    async fn fetch_trades(
+        &self,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let trades = futures::future::try_join_all(
+            self.tracked_instruments()
+                .into_iter()
+                .map(|symbol| self.fetch_symbol_trades(symbol, time_since)),
+        )
+        .await?;
+
+        Ok(trades.into_iter().flatten().collect())
+    }
+}
+
+/// Periodically keep the user data stream `listenKey` alive.
+///
+/// Runs until aborted by the [`ListenKeyKeepAlive`] guard held by the account stream.
+async fn keep_alive_listen_key(
+    rest_api_key: Arc<RestClient<'static, BinanceApiKey, BinanceParser>>,
+    listen_key: String,
+    interval: Duration,
+) {
+    let mut interval = tokio::time::interval(interval);
+
+    // First tick completes immediately, and the listenKey was only just created
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let request = KeepAliveListenKey {
+            query: ListenKeyParams {
+                listen_key: listen_key.clone(),
+            },
+        };
+
+        match rest_api_key.execute(request).await {
+            Ok(_) => debug!("Binance Spot user data stream listenKey kept alive"),
+            Err(error) => warn!(
+                ?error,
+                "failed to keep alive Binance Spot user data stream listenKey"
+            ),
+        }
+    }
+}
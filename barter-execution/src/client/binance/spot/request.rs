@@ -0,0 +1,784 @@
+use crate::{
+    balance::{AssetBalance, Balance},
+    client::binance::{ListenKey, SignedQuery, parse_order_kind},
+    order::{
+        Order, OrderFlags, OrderKey, OrderKind, TimeInForce, TriggerSource,
+        group::{OrderGroup, OrderGroupKind},
+        id::{ClientOrderId, OrderGroupId, OrderId, StrategyId},
+        request::OrderRequestOpen,
+        state::Open,
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::http::rest::RestRequest;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, de::IgnoredAny};
+use smol_str::SmolStr;
+use std::borrow::Cow;
+use tracing::warn;
+
+/// Binance Spot `SIGNED` request to fetch the account information, including all asset balances.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/account-endpoints#account-information-user_data>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchAccount {
+    pub query: SignedQuery<()>,
+}
+
+impl RestRequest for FetchAccount {
+    type Response = BinanceSpotAccount;
+    type QueryParams = SignedQuery<()>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/account")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// Binance Spot account information response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "makerCommission": 15,
+///     "canTrade": true,
+///     "updateTime": 123456789,
+///     "accountType": "SPOT",
+///     "balances": [
+///         { "asset": "BTC", "free": "4723846.89208129", "locked": "0.00000000" },
+///         { "asset": "LTC", "free": "4763368.68006011", "locked": "0.00000000" }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotAccount {
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub update_time: DateTime<Utc>,
+    pub balances: Vec<BinanceSpotBalance>,
+}
+
+/// Binance Spot asset balance, where the `total` balance is `free` + `locked`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BinanceSpotBalance {
+    pub asset: AssetNameExchange,
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+impl BinanceSpotBalance {
+    pub fn into_asset_balance(
+        self,
+        time_exchange: DateTime<Utc>,
+    ) -> AssetBalance<AssetNameExchange> {
+        AssetBalance::new(
+            self.asset,
+            Balance::new(self.free + self.locked, self.free),
+            time_exchange,
+        )
+    }
+}
+
+/// Binance Spot `SIGNED` request to fetch all open orders.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#current-open-orders-user_data>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchOpenOrders {
+    pub query: SignedQuery<()>,
+}
+
+impl RestRequest for FetchOpenOrders {
+    type Response = Vec<BinanceSpotOrder>;
+    type QueryParams = SignedQuery<()>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/openOrders")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// Binance Spot order.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "symbol": "LTCBTC",
+///     "orderId": 1,
+///     "orderListId": -1,
+///     "clientOrderId": "myOrder1",
+///     "price": "0.1",
+///     "origQty": "1.0",
+///     "executedQty": "0.0",
+///     "cummulativeQuoteQty": "0.0",
+///     "status": "NEW",
+///     "timeInForce": "GTC",
+///     "type": "LIMIT",
+///     "side": "BUY",
+///     "time": 1499827319559,
+///     "updateTime": 1499827319559,
+///     "isWorking": true
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotOrder {
+    pub symbol: InstrumentNameExchange,
+    pub order_id: u64,
+    pub client_order_id: SmolStr,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub time_in_force: SmolStr,
+    #[serde(rename = "type")]
+    pub kind: SmolStr,
+    pub side: Side,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub update_time: DateTime<Utc>,
+}
+
+impl BinanceSpotOrder {
+    /// Convert into an [`Order`] in the [`Open`] state, returning `None` if the Binance order
+    /// type or time in force is not supported.
+    pub fn into_open_order(self) -> Option<Order<ExchangeId, InstrumentNameExchange, Open>> {
+        let Some((kind, time_in_force)) = parse_order_kind(&self.kind, &self.time_in_force) else {
+            warn!(
+                symbol = %self.symbol,
+                order_id = self.order_id,
+                kind = %self.kind,
+                time_in_force = %self.time_in_force,
+                "ignoring Binance Spot open order with unsupported type"
+            );
+            return None;
+        };
+
+        Some(Order {
+            key: OrderKey::new(
+                ExchangeId::BinanceSpot,
+                self.symbol,
+                StrategyId::unknown(),
+                ClientOrderId::new(self.client_order_id),
+            ),
+            side: self.side,
+            price: self.price,
+            quantity: self.orig_qty,
+            kind,
+            time_in_force,
+            state: Open::new(
+                OrderId::new(self.order_id.to_string()),
+                self.update_time,
+                self.executed_qty,
+            ),
+        })
+    }
+}
+
+/// Binance Spot `SIGNED` request to open a new order.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#new-order-trade>
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenOrder {
+    pub query: SignedQuery<OpenOrderParams>,
+}
+
+impl RestRequest for OpenOrder {
+    type Response = BinanceSpotOrderResponse;
+    type QueryParams = SignedQuery<OpenOrderParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/order")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`OpenOrder`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrderParams {
+    pub symbol: InstrumentNameExchange,
+    pub side: &'static str,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<&'static str>,
+    pub quantity: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Decimal>,
+    pub new_client_order_id: ClientOrderId,
+    pub new_order_resp_type: &'static str,
+}
+
+impl OpenOrderParams {
+    /// Construct [`OpenOrderParams`] from the provided order fields, returning `None` if the
+    /// [`OrderKind`] and [`TimeInForce`] combination is not supported by Binance Spot.
+    ///
+    /// Spot orders cannot reduce a position, so reducing [`OrderFlags`] also return `None`.
+    pub fn new(
+        symbol: InstrumentNameExchange,
+        cid: ClientOrderId,
+        side: Side,
+        kind: OrderKind,
+        time_in_force: TimeInForce,
+        price: Decimal,
+        quantity: Decimal,
+        flags: OrderFlags,
+    ) -> Option<Self> {
+        if flags.is_reducing() {
+            return None;
+        }
+
+        let (kind, time_in_force, price) = match (kind, time_in_force) {
+            (OrderKind::Market, _) => ("MARKET", None, None),
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only: true }) => {
+                ("LIMIT_MAKER", None, Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only: false }) => {
+                ("LIMIT", Some("GTC"), Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::ImmediateOrCancel) => {
+                ("LIMIT", Some("IOC"), Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::FillOrKill) => ("LIMIT", Some("FOK"), Some(price)),
+            (OrderKind::Limit, TimeInForce::GoodUntilEndOfDay) => return None,
+            // Conditional orders are not supported
+            _ => return None,
+        };
+
+        Some(Self {
+            symbol,
+            side: match side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            },
+            kind,
+            time_in_force,
+            quantity,
+            price,
+            new_client_order_id: cid,
+            new_order_resp_type: "RESULT",
+        })
+    }
+}
+
+/// Binance Spot [`OpenOrder`] `RESULT` response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "symbol": "BTCUSDT",
+///     "orderId": 28,
+///     "orderListId": -1,
+///     "clientOrderId": "6gCrw2kRUAF9CvJDGP16IP",
+///     "transactTime": 1507725176595,
+///     "price": "0.00000000",
+///     "origQty": "10.00000000",
+///     "executedQty": "10.00000000",
+///     "cummulativeQuoteQty": "10.00000000",
+///     "status": "FILLED",
+///     "timeInForce": "GTC",
+///     "type": "MARKET",
+///     "side": "SELL"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotOrderResponse {
+    pub order_id: u64,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub transact_time: DateTime<Utc>,
+    pub executed_qty: Decimal,
+    pub status: SmolStr,
+}
+
+/// Binance Spot `SIGNED` request to open a One-Cancels-Other order list.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#new-order-list---oco-trade>
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenOrderListOco {
+    pub query: SignedQuery<OpenOrderListOcoParams>,
+}
+
+impl RestRequest for OpenOrderListOco {
+    type Response = BinanceSpotOrderListResponse;
+    type QueryParams = SignedQuery<OpenOrderListOcoParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/orderList/oco")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`OpenOrderListOco`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrderListOcoParams {
+    pub symbol: InstrumentNameExchange,
+    pub list_client_order_id: OrderGroupId,
+    pub side: &'static str,
+    pub quantity: Decimal,
+    pub above_type: &'static str,
+    pub above_client_order_id: ClientOrderId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub above_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub above_stop_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub above_time_in_force: Option<&'static str>,
+    pub below_type: &'static str,
+    pub below_client_order_id: ClientOrderId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub below_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub below_stop_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub below_time_in_force: Option<&'static str>,
+    pub new_order_resp_type: &'static str,
+}
+
+impl OpenOrderListOcoParams {
+    /// Construct [`OpenOrderListOcoParams`] from the provided [`OrderGroup`], returning `None`
+    /// if it cannot be opened as a Binance Spot OCO order list.
+    ///
+    /// Binance Spot OCO order lists consist of a stop-loss leg and a limit maker or take-profit
+    /// leg, for the same symbol, side and quantity. A sell OCO has the stop-loss leg below, and
+    /// a buy OCO has the stop-loss leg above, the other leg.
+    ///
+    /// Note that `Limit` legs are opened as `LIMIT_MAKER`, and so are rejected if they would
+    /// immediately match.
+    pub fn new(group: &OrderGroup<ExchangeId, &InstrumentNameExchange>) -> Option<Self> {
+        let OcoLegs { above, below } = OcoLegs::new(group)?;
+        let primary = &group.primary;
+
+        Some(Self {
+            symbol: primary.key.instrument.clone(),
+            list_client_order_id: group.id.clone(),
+            side: match primary.state.side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            },
+            quantity: primary.state.quantity,
+            above_type: above.kind,
+            above_client_order_id: above.cid,
+            above_price: above.price,
+            above_stop_price: above.stop_price,
+            above_time_in_force: above.time_in_force,
+            below_type: below.kind,
+            below_client_order_id: below.cid,
+            below_price: below.price,
+            below_stop_price: below.stop_price,
+            below_time_in_force: below.time_in_force,
+            new_order_resp_type: "RESULT",
+        })
+    }
+
+    /// Returns true if the provided [`OrderGroup`] can be opened as a Binance Spot OCO order list.
+    pub fn supports<ExchangeKey, InstrumentKey>(
+        group: &OrderGroup<ExchangeKey, InstrumentKey>,
+    ) -> bool
+    where
+        InstrumentKey: PartialEq,
+    {
+        OcoLegs::new(group).is_some()
+    }
+}
+
+/// Above and below legs of an [`OpenOrderListOcoParams`] order list.
+struct OcoLegs {
+    above: OcoLeg,
+    below: OcoLeg,
+}
+
+impl OcoLegs {
+    fn new<ExchangeKey, InstrumentKey>(
+        group: &OrderGroup<ExchangeKey, InstrumentKey>,
+    ) -> Option<Self>
+    where
+        InstrumentKey: PartialEq,
+    {
+        let OrderGroup {
+            id: _,
+            kind: OrderGroupKind::OneCancelsOther,
+            primary,
+            contingent,
+        } = group
+        else {
+            return None;
+        };
+
+        let [other] = contingent.as_slice() else {
+            return None;
+        };
+
+        if primary.key.instrument != other.key.instrument
+            || primary.state.side != other.state.side
+            || primary.state.quantity != other.state.quantity
+        {
+            return None;
+        }
+
+        let primary_leg = OcoLeg::new(primary)?;
+        let other_leg = OcoLeg::new(other)?;
+
+        match (primary.state.side, primary_leg.stop, other_leg.stop) {
+            (Side::Sell, false, true) | (Side::Buy, true, false) => Some(Self {
+                above: primary_leg,
+                below: other_leg,
+            }),
+            (Side::Sell, true, false) | (Side::Buy, false, true) => Some(Self {
+                above: other_leg,
+                below: primary_leg,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Single leg of an [`OpenOrderListOcoParams`] order list.
+struct OcoLeg {
+    stop: bool,
+    kind: &'static str,
+    cid: ClientOrderId,
+    price: Option<Decimal>,
+    stop_price: Option<Decimal>,
+    time_in_force: Option<&'static str>,
+}
+
+impl OcoLeg {
+    fn new<ExchangeKey, InstrumentKey>(
+        order: &OrderRequestOpen<ExchangeKey, InstrumentKey>,
+    ) -> Option<Self> {
+        let price = order.state.price;
+
+        let (stop, kind, price, trigger, time_in_force) =
+            match (order.state.kind, order.state.time_in_force) {
+                (OrderKind::Limit, TimeInForce::GoodUntilCancelled { .. }) => {
+                    (false, "LIMIT_MAKER", Some(price), None, None)
+                }
+                (OrderKind::StopMarket { trigger }, _) => {
+                    (true, "STOP_LOSS", None, Some(trigger), None)
+                }
+                (OrderKind::StopLimit { trigger }, TimeInForce::GoodUntilCancelled { .. }) => (
+                    true,
+                    "STOP_LOSS_LIMIT",
+                    Some(price),
+                    Some(trigger),
+                    Some("GTC"),
+                ),
+                (OrderKind::TakeProfitMarket { trigger }, _) => {
+                    (false, "TAKE_PROFIT", None, Some(trigger), None)
+                }
+                (
+                    OrderKind::TakeProfitLimit { trigger },
+                    TimeInForce::GoodUntilCancelled { .. },
+                ) => (
+                    false,
+                    "TAKE_PROFIT_LIMIT",
+                    Some(price),
+                    Some(trigger),
+                    Some("GTC"),
+                ),
+                _ => return None,
+            };
+
+        // Binance Spot conditional orders only trigger from the last traded price
+        if trigger.is_some_and(|trigger| trigger.source != TriggerSource::Last) {
+            return None;
+        }
+
+        Some(Self {
+            stop,
+            kind,
+            cid: order.key.cid.clone(),
+            price,
+            stop_price: trigger.map(|trigger| trigger.price),
+            time_in_force,
+        })
+    }
+}
+
+/// Binance Spot [`OpenOrderListOco`] `RESULT` response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "orderListId": 1,
+///     "contingencyType": "OCO",
+///     "listStatusType": "EXEC_STARTED",
+///     "listOrderStatus": "EXECUTING",
+///     "listClientOrderId": "lH1YDkuQKWiXVXHPSKYEIp",
+///     "transactionTime": 1710485608839,
+///     "symbol": "LTCBTC",
+///     "orders": [],
+///     "orderReports": [
+///         {
+///             "symbol": "LTCBTC",
+///             "orderId": 10,
+///             "orderListId": 1,
+///             "clientOrderId": "44nZvqpemY7sVYgPYbvPih",
+///             "transactTime": 1710485608839,
+///             "price": "1.00000000",
+///             "origQty": "5.00000000",
+///             "executedQty": "0.00000000",
+///             "cummulativeQuoteQty": "0.00000000",
+///             "status": "NEW",
+///             "timeInForce": "GTC",
+///             "type": "STOP_LOSS_LIMIT",
+///             "side": "SELL",
+///             "stopPrice": "1.00000000"
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotOrderListResponse {
+    pub order_reports: Vec<BinanceSpotOrderListReport>,
+}
+
+/// Single order report of a [`BinanceSpotOrderListResponse`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotOrderListReport {
+    pub client_order_id: ClientOrderId,
+    pub order_id: u64,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub transact_time: DateTime<Utc>,
+    pub executed_qty: Decimal,
+    pub status: SmolStr,
+}
+
+/// Binance Spot `SIGNED` request to cancel an open order.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/trading-endpoints#cancel-order-trade>
+#[derive(Debug, Clone, Serialize)]
+pub struct CancelOrder {
+    pub query: SignedQuery<CancelOrderParams>,
+}
+
+impl RestRequest for CancelOrder {
+    type Response = BinanceSpotCancelResponse;
+    type QueryParams = SignedQuery<CancelOrderParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/order")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::DELETE
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`CancelOrder`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderParams {
+    pub symbol: InstrumentNameExchange,
+    pub orig_client_order_id: ClientOrderId,
+}
+
+/// Binance Spot [`CancelOrder`] response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "symbol": "LTCBTC",
+///     "origClientOrderId": "myOrder1",
+///     "orderId": 4,
+///     "orderListId": -1,
+///     "clientOrderId": "cancelMyOrder1",
+///     "transactTime": 1684804350068,
+///     "price": "2.00000000",
+///     "origQty": "1.00000000",
+///     "executedQty": "0.00000000",
+///     "status": "CANCELED",
+///     "timeInForce": "GTC",
+///     "type": "LIMIT",
+///     "side": "BUY"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotCancelResponse {
+    pub order_id: u64,
+    #[serde(default, deserialize_with = "de_option_u64_epoch_ms_as_datetime_utc")]
+    pub transact_time: Option<DateTime<Utc>>,
+}
+
+/// Binance Spot `SIGNED` request to fetch the account trades of a symbol.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/rest-api/account-endpoints#account-trade-list-user_data>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchTrades {
+    pub query: SignedQuery<FetchTradesParams>,
+}
+
+impl RestRequest for FetchTrades {
+    type Response = Vec<BinanceSpotTrade>;
+    type QueryParams = SignedQuery<FetchTradesParams>;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/myTrades")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchTrades`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchTradesParams {
+    pub symbol: InstrumentNameExchange,
+    pub start_time: i64,
+    pub limit: u16,
+}
+
+/// Binance Spot account trade.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "symbol": "BNBBTC",
+///     "id": 28457,
+///     "orderId": 100234,
+///     "orderListId": -1,
+///     "price": "4.00000100",
+///     "qty": "12.00000000",
+///     "quoteQty": "48.000012",
+///     "commission": "10.10000000",
+///     "commissionAsset": "BNB",
+///     "time": 1499865549590,
+///     "isBuyer": true,
+///     "isMaker": false,
+///     "isBestMatch": true
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotTrade {
+    pub symbol: InstrumentNameExchange,
+    pub id: u64,
+    pub order_id: u64,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: AssetNameExchange,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    pub is_buyer: bool,
+}
+
+/// Binance Spot `USER_STREAM` request to create a user data stream `listenKey`.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/user-data-stream#create-a-listenkey-user_stream>
+#[derive(Debug, Copy, Clone)]
+pub struct CreateListenKey;
+
+impl RestRequest for CreateListenKey {
+    type Response = ListenKey;
+    type QueryParams = ();
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/userDataStream")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+}
+
+/// Binance Spot `USER_STREAM` request to extend the validity of a user data stream `listenKey`
+/// by 60 minutes.
+///
+/// See docs: <https://developers.binance.com/docs/binance-spot-api-docs/user-data-stream#pingkeep-alive-a-listenkey-user_stream>
+#[derive(Debug, Clone, Serialize)]
+pub struct KeepAliveListenKey {
+    pub query: ListenKeyParams,
+}
+
+impl RestRequest for KeepAliveListenKey {
+    type Response = IgnoredAny;
+    type QueryParams = ListenKeyParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/userDataStream")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::PUT
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`KeepAliveListenKey`] request specific query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyParams {
+    pub listen_key: String,
+}
+
+fn de_option_u64_epoch_ms_as_datetime_utc<'de, D>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    Option::<u64>::deserialize(deserializer).map(|epoch_ms| {
+        epoch_ms.map(|epoch_ms| {
+            barter_integration::de::datetime_utc_from_epoch_duration(
+                std::time::Duration::from_millis(epoch_ms),
+            )
+        })
+    })
+}
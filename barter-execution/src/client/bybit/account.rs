@@ -0,0 +1,218 @@
+use crate::{
+    AccountEventKind, UnindexedAccountEvent,
+    client::bybit::{
+        BybitCategory, parse_order_kind,
+        request::{BybitExecution, BybitOrder, BybitWallet},
+    },
+    error::{ApiError, OrderError},
+    order::{
+        Order, OrderKey, OrderSnapshot,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::{Cancelled, Open, OrderState},
+    },
+};
+use barter_instrument::{
+    asset::name::AssetNameExchange, exchange::ExchangeId, instrument::name::InstrumentNameExchange,
+};
+use barter_integration::snapshot::Snapshot;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use tracing::warn;
+
+/// Bybit V5 private WebSocket request.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/ws/connect>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BybitWsRequest {
+    pub op: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<serde_json::Value>,
+}
+
+impl BybitWsRequest {
+    /// Authenticate the private connection with a signature of `GET/realtime{expires}`.
+    pub fn auth(api_key: &str, expires: i64, signature: String) -> Self {
+        Self {
+            op: "auth",
+            args: vec![api_key.into(), expires.into(), signature.into()],
+        }
+    }
+
+    /// Subscribe to the private `order`, `execution` & `wallet` topics.
+    pub fn subscribe() -> Self {
+        Self {
+            op: "subscribe",
+            args: vec!["order".into(), "execution".into(), "wallet".into()],
+        }
+    }
+
+    /// Application level heartbeat, required to keep the private connection alive.
+    pub fn ping() -> Self {
+        Self {
+            op: "ping",
+            args: vec![],
+        }
+    }
+}
+
+/// Bybit V5 private WebSocket message.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum BybitPrivateMessage {
+    Topic(BybitTopic),
+    Response(BybitWsResponse),
+}
+
+/// Bybit V5 private WebSocket `op` response.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "success": true,
+///     "ret_msg": "",
+///     "op": "auth",
+///     "conn_id": "cejreaspqfh3sjdnldmg-p"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BybitWsResponse {
+    pub op: SmolStr,
+    #[serde(default)]
+    pub success: bool,
+    #[serde(default)]
+    pub ret_msg: String,
+}
+
+/// Bybit V5 private WebSocket topic update.
+///
+/// The unified account streams updates for every category, so `order` and `execution` updates
+/// must be filtered by [`BybitCategory`].
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/websocket/private/order>
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "topic")]
+pub enum BybitTopic {
+    #[serde(rename = "order")]
+    Order { data: Vec<BybitOrder> },
+    #[serde(rename = "execution")]
+    Execution { data: Vec<BybitExecution> },
+    #[serde(rename = "wallet")]
+    Wallet {
+        #[serde(
+            rename = "creationTime",
+            deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc"
+        )]
+        time_exchange: DateTime<Utc>,
+        data: Vec<BybitWallet>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl BybitTopic {
+    /// Map the [`BybitTopic`] update into zero or more [`UnindexedAccountEvent`]s for the
+    /// provided exchange [`BybitCategory`].
+    pub fn into_account_events(
+        self,
+        exchange: ExchangeId,
+        category: BybitCategory,
+    ) -> Vec<UnindexedAccountEvent> {
+        let is_category = |update_category: Option<BybitCategory>| {
+            update_category.is_none_or(|update_category| update_category == category)
+        };
+
+        match self {
+            Self::Order { data } => data
+                .into_iter()
+                .filter(|order| is_category(order.category))
+                .filter_map(|order| order_snapshot(exchange, order))
+                .map(|order| {
+                    UnindexedAccountEvent::new(
+                        exchange,
+                        AccountEventKind::OrderSnapshot(Snapshot(order)),
+                    )
+                })
+                .collect(),
+            Self::Execution { data } => data
+                .into_iter()
+                .filter(|execution| is_category(execution.category) && execution.is_fill())
+                .map(|execution| UnindexedAccountEvent::new(exchange, execution.into_trade()))
+                .collect(),
+            Self::Wallet {
+                time_exchange,
+                data,
+            } => data
+                .into_iter()
+                .flat_map(|wallet| wallet.coin)
+                .map(|coin| {
+                    UnindexedAccountEvent::new(
+                        exchange,
+                        AccountEventKind::BalanceSnapshot(Snapshot(
+                            coin.into_asset_balance(time_exchange),
+                        )),
+                    )
+                })
+                .collect(),
+            Self::Other => vec![],
+        }
+    }
+}
+
+/// Map a [`BybitOrder`] update into an [`Order`] snapshot, returning `None` if the order type
+/// or status is not supported.
+fn order_snapshot(
+    exchange: ExchangeId,
+    order: BybitOrder,
+) -> Option<OrderSnapshot<ExchangeId, AssetNameExchange, InstrumentNameExchange>> {
+    let Some((kind, time_in_force)) = parse_order_kind(&order.order_type, &order.time_in_force)
+    else {
+        warn!(
+            %exchange,
+            symbol = %order.symbol,
+            order_id = %order.order_id,
+            kind = %order.order_type,
+            "ignoring Bybit order update for unsupported order type"
+        );
+        return None;
+    };
+
+    let order_id = OrderId::new(order.order_id.clone());
+    let state = match order.order_status.as_str() {
+        "New" | "PartiallyFilled" | "Untriggered" | "Triggered" => {
+            OrderState::active(Open::new(order_id, order.updated_time, order.cum_exec_qty))
+        }
+        "Filled" => OrderState::fully_filled(),
+        "Cancelled" | "PartiallyFilledCanceled" | "Deactivated" => {
+            OrderState::inactive(Cancelled::new(order_id, order.updated_time))
+        }
+        "Rejected" => OrderState::inactive(OrderError::Rejected(ApiError::OrderRejected(
+            order.reject_reason.to_string(),
+        ))),
+        status => {
+            warn!(
+                %exchange,
+                symbol = %order.symbol,
+                order_id = %order.order_id,
+                %status,
+                "ignoring Bybit order update with unknown order status"
+            );
+            return None;
+        }
+    };
+
+    Some(Order {
+        key: OrderKey::new(
+            exchange,
+            order.symbol,
+            StrategyId::unknown(),
+            ClientOrderId::new(order.order_link_id),
+        ),
+        side: order.side,
+        price: order.price,
+        quantity: order.qty,
+        kind,
+        time_in_force,
+        state,
+    })
+}
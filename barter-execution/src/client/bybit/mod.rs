@@ -0,0 +1,844 @@
+use crate::{
+    InstrumentAccountSnapshot, UnindexedAccountEvent, UnindexedAccountSnapshot,
+    balance::AssetBalance,
+    client::{
+        ExecutionClient,
+        bybit::{
+            account::{BybitPrivateMessage, BybitWsRequest},
+            request::{
+                CancelOrder, CancelOrderBody, FetchExecutions, FetchExecutionsParams,
+                FetchOpenOrders, FetchOpenOrdersParams, FetchWalletBalance,
+                FetchWalletBalanceParams, OpenOrder, OpenOrderBody,
+            },
+        },
+        order_error,
+    },
+    error::{
+        ApiError, ClientError, ConnectivityError, OrderError, UnindexedClientError,
+        UnindexedOrderError,
+    },
+    order::{
+        Order, OrderKey, OrderKind, TimeInForce,
+        id::OrderId,
+        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::Trade,
+};
+use barter_instrument::{
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::{
+    error::SocketError,
+    protocol::{
+        StreamParser,
+        http::{
+            HttpParser,
+            private::{
+                RequestSigner, Signer,
+                encoder::{Encoder, HexEncoder},
+            },
+            rest::{RestRequest, client::RestClient},
+        },
+        websocket::{WebSocket, WebSocketParser, WsMessage, WsSink, connect},
+    },
+};
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt, stream::BoxStream};
+use hmac::{Hmac, Mac};
+use itertools::Itertools;
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::Sha256;
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+/// Bybit V5 Http request and response models.
+pub mod request;
+
+/// Bybit V5 private WebSocket request and topic models.
+pub mod account;
+
+/// Http header containing the Bybit API key.
+pub const HEADER_BYBIT_API_KEY: &str = "X-BAPI-API-KEY";
+
+/// Http header containing the Bybit request timestamp.
+pub const HEADER_BYBIT_TIMESTAMP: &str = "X-BAPI-TIMESTAMP";
+
+/// Http header containing the Bybit request `recv_window`.
+pub const HEADER_BYBIT_RECV_WINDOW: &str = "X-BAPI-RECV-WINDOW";
+
+/// Http header containing the Bybit request signature.
+pub const HEADER_BYBIT_SIGN: &str = "X-BAPI-SIGN";
+
+/// Default number of milliseconds after the request timestamp that Bybit will still accept a
+/// signed request for.
+pub const DEFAULT_RECV_WINDOW_MS: u64 = 5000;
+
+/// Interval at which the private WebSocket heartbeat is sent, as recommended by Bybit.
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Maximum time to wait for a private WebSocket `auth` or `subscribe` response.
+const WS_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum number of open orders Bybit returns per page.
+const FETCH_OPEN_ORDERS_LIMIT: u16 = 50;
+
+/// Maximum number of executions Bybit returns per page.
+const FETCH_EXECUTIONS_LIMIT: u16 = 100;
+
+/// Bybit V5 product category.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BybitCategory {
+    Spot,
+    Linear,
+    Inverse,
+    Option,
+}
+
+/// Bybit V5 unified account server, defining the [`ExchangeId`] and [`BybitCategory`] actioned
+/// by a [`Bybit`] execution client.
+pub trait BybitServer {
+    const EXCHANGE: ExchangeId;
+    const CATEGORY: BybitCategory;
+
+    /// Settle coin of the category, required to fetch all open derivative orders.
+    const SETTLE_COIN: Option<&'static str>;
+}
+
+/// [`Bybit`] spot [`BybitServer`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct BybitServerSpot;
+
+impl BybitServer for BybitServerSpot {
+    const EXCHANGE: ExchangeId = ExchangeId::BybitSpot;
+    const CATEGORY: BybitCategory = BybitCategory::Spot;
+    const SETTLE_COIN: Option<&'static str> = None;
+}
+
+/// [`Bybit`] USDT perpetuals [`BybitServer`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct BybitServerPerpetualsUsd;
+
+impl BybitServer for BybitServerPerpetualsUsd {
+    const EXCHANGE: ExchangeId = ExchangeId::BybitPerpetualsUsd;
+    const CATEGORY: BybitCategory = BybitCategory::Linear;
+    const SETTLE_COIN: Option<&'static str> = Some("USDT");
+}
+
+/// [`Bybit`] spot execution client.
+pub type BybitSpot = Bybit<BybitServerSpot>;
+
+/// [`Bybit`] USDT perpetuals execution client.
+pub type BybitPerpetualsUsd = Bybit<BybitServerPerpetualsUsd>;
+
+/// [`Bybit`] execution client configuration.
+///
+/// Only the API credentials are required, with the remaining fields defaulting to the Bybit
+/// production endpoints.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct BybitConfig {
+    pub api_key: String,
+    pub secret: String,
+    #[serde(default = "default_base_url_rest")]
+    pub base_url_rest: String,
+    #[serde(default = "default_base_url_ws")]
+    pub base_url_ws: String,
+    #[serde(default = "default_recv_window_ms")]
+    pub recv_window_ms: u64,
+}
+
+impl BybitConfig {
+    /// Construct a [`BybitConfig`] for the Bybit production endpoints.
+    pub fn new<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            api_key: api_key.into(),
+            secret: secret.into(),
+            base_url_rest: default_base_url_rest(),
+            base_url_ws: default_base_url_ws(),
+            recv_window_ms: default_recv_window_ms(),
+        }
+    }
+
+    /// Construct a [`BybitConfig`] for the Bybit testnet endpoints.
+    ///
+    /// Note that the Bybit testnet requires separate API credentials.
+    pub fn testnet<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            base_url_rest: "https://api-testnet.bybit.com".to_string(),
+            base_url_ws: "wss://stream-testnet.bybit.com/v5/private".to_string(),
+            ..Self::new(api_key, secret)
+        }
+    }
+}
+
+impl Debug for BybitConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BybitConfig")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field("base_url_rest", &self.base_url_rest)
+            .field("base_url_ws", &self.base_url_ws)
+            .field("recv_window_ms", &self.recv_window_ms)
+            .finish()
+    }
+}
+
+fn default_base_url_rest() -> String {
+    "https://api.bybit.com".to_string()
+}
+
+fn default_base_url_ws() -> String {
+    "wss://stream.bybit.com/v5/private".to_string()
+}
+
+fn default_recv_window_ms() -> u64 {
+    DEFAULT_RECV_WINDOW_MS
+}
+
+/// Convenient type alias for the HMAC-SHA256 [`RequestSigner`] used to sign Bybit V5 requests.
+pub type BybitRequestSigner = RequestSigner<BybitSigner, Hmac<Sha256>, HexEncoder>;
+
+/// Construct a [`BybitRequestSigner`] from the provided API credentials.
+pub fn request_signer(api_key: &str, secret: &str, recv_window_ms: u64) -> BybitRequestSigner {
+    RequestSigner::new(
+        BybitSigner {
+            api_key: api_key.to_string(),
+            recv_window_ms,
+        },
+        hmac_sha256(secret),
+        HexEncoder,
+    )
+}
+
+fn hmac_sha256(secret: &str) -> Hmac<Sha256> {
+    Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC-SHA256 accepts keys of any length")
+}
+
+/// Bybit V5 API specific [`Signer`] logic.
+///
+/// Bybit requires the HMAC-SHA256 signature of `{timestamp}{api_key}{recv_window}{payload}`,
+/// where the payload is the query string for `GET` requests, and the JSON body for `POST`
+/// requests.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/guide#authentication>
+#[derive(Debug, Clone)]
+pub struct BybitSigner {
+    pub api_key: String,
+    pub recv_window_ms: u64,
+}
+
+/// Configuration required to sign a Bybit [`RestRequest`].
+#[derive(Debug)]
+pub struct BybitSignConfig<'a> {
+    pub api_key: &'a str,
+    pub timestamp: i64,
+    pub recv_window_ms: u64,
+    pub payload: String,
+}
+
+impl Signer for BybitSigner {
+    type Config<'a>
+        = BybitSignConfig<'a>
+    where
+        Self: 'a;
+
+    fn config<'a, Request>(
+        &'a self,
+        _: Request,
+        builder: &reqwest::RequestBuilder,
+    ) -> Result<Self::Config<'a>, SocketError>
+    where
+        Request: RestRequest,
+    {
+        let request = builder
+            .try_clone()
+            .ok_or_else(|| SocketError::Unsupported {
+                entity: "Bybit".to_string(),
+                item: "streaming request bodies".to_string(),
+            })?
+            .build()?;
+
+        let payload = match request.body().and_then(|body| body.as_bytes()) {
+            Some(body) => String::from_utf8_lossy(body).to_string(),
+            None => request.url().query().unwrap_or_default().to_string(),
+        };
+
+        Ok(BybitSignConfig {
+            api_key: self.api_key.as_str(),
+            timestamp: Utc::now().timestamp_millis(),
+            recv_window_ms: self.recv_window_ms,
+            payload,
+        })
+    }
+
+    fn add_bytes_to_sign<M>(mac: &mut M, config: &Self::Config<'_>)
+    where
+        M: Mac,
+    {
+        mac.update(config.timestamp.to_string().as_bytes());
+        mac.update(config.api_key.as_bytes());
+        mac.update(config.recv_window_ms.to_string().as_bytes());
+        mac.update(config.payload.as_bytes());
+    }
+
+    fn build_signed_request(
+        config: Self::Config<'_>,
+        builder: reqwest::RequestBuilder,
+        signature: String,
+    ) -> Result<reqwest::Request, SocketError> {
+        builder
+            .header(HEADER_BYBIT_API_KEY, config.api_key)
+            .header(HEADER_BYBIT_TIMESTAMP, config.timestamp)
+            .header(HEADER_BYBIT_RECV_WINDOW, config.recv_window_ms)
+            .header(HEADER_BYBIT_SIGN, signature)
+            .build()
+            .map_err(SocketError::from)
+    }
+}
+
+/// Bybit V5 API error, identified by a non-zero `retCode`.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/error>
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitApiError {
+    pub ret_code: i64,
+    pub ret_msg: String,
+}
+
+/// [`HttpParser`] for Bybit V5 Http APIs, mapping [`BybitApiError`]s to [`ClientError`]s.
+#[derive(Debug, Copy, Clone)]
+pub struct BybitParser;
+
+impl HttpParser for BybitParser {
+    type ApiError = BybitApiError;
+    type OutputError = ClientError<AssetNameExchange, InstrumentNameExchange>;
+
+    /// Bybit responds with Http status 200 for most errors, so the `retCode` must be checked
+    /// before attempting to deserialise the `Response`.
+    fn parse<Response>(
+        &self,
+        status: StatusCode,
+        payload: &[u8],
+    ) -> Result<Response, Self::OutputError>
+    where
+        Response: DeserializeOwned,
+    {
+        match serde_json::from_slice::<BybitApiError>(payload) {
+            Ok(error) if error.ret_code != 0 => return Err(self.parse_api_error(status, error)),
+            Err(_)
+                if status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::FORBIDDEN =>
+            {
+                return Err(ClientError::Api(ApiError::RateLimit));
+            }
+            _ => {}
+        }
+
+        serde_json::from_slice::<Response>(payload).map_err(|error| {
+            error!(
+                status_code = ?status,
+                ?error,
+                response_body = %String::from_utf8_lossy(payload),
+                "error deserializing Bybit HTTP response"
+            );
+            ClientError::from(SocketError::DeserialiseBinary {
+                error,
+                payload: payload.to_vec(),
+            })
+        })
+    }
+
+    fn parse_api_error(&self, _: StatusCode, error: Self::ApiError) -> Self::OutputError {
+        let BybitApiError { ret_code, ret_msg } = error;
+
+        match ret_code {
+            // Too many visits & IP rate limit exceeded
+            10006 | 10018 => ClientError::Api(ApiError::RateLimit),
+            // Invalid timestamp, API key, signature, permissions or IP & expired API key
+            10002 | 10003 | 10004 | 10005 | 10007 | 10009 | 10010 | 33004 => {
+                ClientError::Connectivity(ConnectivityError::Socket(format!(
+                    "Bybit authentication failed: {ret_msg}"
+                )))
+            }
+            // Order does not exist (derivatives & spot)
+            110001 | 170213 => ClientError::Api(ApiError::OrderRejected(ret_msg)),
+            // Order has already been cancelled
+            110010 => ClientError::Api(ApiError::OrderAlreadyCancelled),
+            _ => ClientError::Api(ApiError::OrderRejected(format!(
+                "Bybit retCode {ret_code}: {ret_msg}"
+            ))),
+        }
+    }
+}
+
+/// Parse a Bybit order type and time in force into an [`OrderKind`] and [`TimeInForce`].
+///
+/// Returns `None` for unsupported order types.
+pub fn parse_order_kind(kind: &str, time_in_force: &str) -> Option<(OrderKind, TimeInForce)> {
+    let kind = match kind {
+        "Market" => OrderKind::Market,
+        "Limit" => OrderKind::Limit,
+        _ => return None,
+    };
+
+    let time_in_force = match time_in_force {
+        "PostOnly" => TimeInForce::GoodUntilCancelled { post_only: true },
+        "IOC" => TimeInForce::ImmediateOrCancel,
+        "FOK" => TimeInForce::FillOrKill,
+        _ => TimeInForce::GoodUntilCancelled { post_only: false },
+    };
+
+    Some((kind, time_in_force))
+}
+
+/// Aborts the private WebSocket heartbeat task when dropped, ensuring it does not outlive the
+/// account stream it keeps alive.
+#[derive(Debug)]
+pub struct BybitHeartbeat(pub JoinHandle<()>);
+
+impl Drop for BybitHeartbeat {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Bybit V5 unified trading account live [`ExecutionClient`], generic over the [`BybitServer`]
+/// category being traded.
+///
+/// Orders and account state are actioned via signed Http endpoints, while the
+/// [`AccountStream`](ExecutionClient::AccountStream) is built on the authenticated private
+/// WebSocket `order`, `execution` & `wallet` topics.
+///
+/// The unified account shares balances across categories, so balance updates are not filtered
+/// by category.
+///
+/// Spot orders cannot reduce a position, so spot open requests with reducing
+/// [`OrderFlags`](crate::order::OrderFlags) are rejected without a request.
+#[derive(Debug)]
+pub struct Bybit<Server> {
+    pub config: BybitConfig,
+    rest: Arc<RestClient<'static, BybitRequestSigner, BybitParser>>,
+    server: PhantomData<Server>,
+}
+
+impl<Server> Clone for Bybit<Server> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            rest: Arc::clone(&self.rest),
+            server: PhantomData,
+        }
+    }
+}
+
+impl<Server> Bybit<Server>
+where
+    Server: BybitServer,
+{
+    /// Generate the private WebSocket `auth` request, which is valid for 10 seconds.
+    fn ws_auth(&self) -> BybitWsRequest {
+        let expires = Utc::now().timestamp_millis() + 10_000;
+
+        let mut mac = hmac_sha256(&self.config.secret);
+        mac.update(format!("GET/realtime{expires}").as_bytes());
+        let signature = HexEncoder.encode(mac.finalize().into_bytes());
+
+        BybitWsRequest::auth(&self.config.api_key, expires, signature)
+    }
+}
+
+impl<Server> ExecutionClient for Bybit<Server>
+where
+    Server: BybitServer + Debug + Send + Sync + 'static,
+{
+    const EXCHANGE: ExchangeId = Server::EXCHANGE;
+    type Config = BybitConfig;
+    type AccountStream = BoxStream<'static, UnindexedAccountEvent>;
+
+    fn new(config: Self::Config) -> Self {
+        let rest = RestClient::new(
+            config.base_url_rest.clone(),
+            request_signer(&config.api_key, &config.secret, config.recv_window_ms),
+            BybitParser,
+        );
+
+        Self {
+            config,
+            rest: Arc::new(rest),
+            server: PhantomData,
+        }
+    }
+
+    async fn account_snapshot(
+        &self,
+        assets: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<UnindexedAccountSnapshot, UnindexedClientError> {
+        let balances = self.fetch_balances().await?;
+        let open_orders = self.fetch_open_orders().await?;
+
+        let balances = balances
+            .into_iter()
+            .filter(|balance| assets.contains(&balance.asset))
+            .collect();
+
+        let mut orders_by_instrument = open_orders
+            .into_iter()
+            .filter(|order| instruments.contains(&order.key.instrument))
+            .into_group_map_by(|order| order.key.instrument.clone());
+
+        let instruments = instruments
+            .iter()
+            .map(|instrument| {
+                let orders = orders_by_instrument
+                    .remove(instrument)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|order| Order {
+                        key: order.key,
+                        side: order.side,
+                        price: order.price,
+                        quantity: order.quantity,
+                        kind: order.kind,
+                        time_in_force: order.time_in_force,
+                        state: OrderState::active(order.state),
+                    })
+                    .collect();
+
+                InstrumentAccountSnapshot::new(instrument.clone(), orders, None)
+            })
+            .collect();
+
+        Ok(UnindexedAccountSnapshot::new(
+            Server::EXCHANGE,
+            balances,
+            instruments,
+        ))
+    }
+
+    async fn account_stream(
+        &self,
+        _: &[AssetNameExchange],
+        _: &[InstrumentNameExchange],
+    ) -> Result<Self::AccountStream, UnindexedClientError> {
+        let mut websocket = connect(self.config.base_url_ws.as_str())
+            .await
+            .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+
+        send_ws_request(&mut websocket, self.ws_auth()).await?;
+        await_ws_response(&mut websocket, "auth").await?;
+        send_ws_request(&mut websocket, BybitWsRequest::subscribe()).await?;
+        await_ws_response(&mut websocket, "subscribe").await?;
+
+        info!(
+            exchange = %Server::EXCHANGE,
+            "connected to Bybit private WebSocket"
+        );
+
+        let (sink, stream) = websocket.split();
+        let heartbeat = BybitHeartbeat(tokio::spawn(heartbeat(sink, PING_INTERVAL)));
+
+        Ok(stream
+            .filter_map(|message| {
+                std::future::ready(
+                    <WebSocketParser as StreamParser<BybitPrivateMessage>>::parse(message),
+                )
+            })
+            .scan(heartbeat, |_heartbeat, message| {
+                std::future::ready(match message {
+                    Ok(BybitPrivateMessage::Topic(topic)) => {
+                        Some(topic.into_account_events(Server::EXCHANGE, Server::CATEGORY))
+                    }
+                    Ok(BybitPrivateMessage::Response(response)) => {
+                        if !response.success && response.op != "pong" {
+                            warn!(
+                                op = %response.op,
+                                ret_msg = %response.ret_msg,
+                                "Bybit private WebSocket request failed"
+                            );
+                        }
+                        Some(vec![])
+                    }
+                    Err(error) => {
+                        warn!(?error, "Bybit private WebSocket failed, ending stream");
+                        None
+                    }
+                })
+            })
+            .flat_map(futures::stream::iter)
+            .boxed())
+    }
+
+    async fn cancel_order(
+        &self,
+        request: OrderRequestCancel<ExchangeId, &InstrumentNameExchange>,
+    ) -> UnindexedOrderResponseCancel {
+        let result = self
+            .rest
+            .execute(CancelOrder {
+                body: CancelOrderBody {
+                    category: Server::CATEGORY,
+                    symbol: request.key.instrument.clone(),
+                    order_link_id: request.key.cid.clone(),
+                },
+            })
+            .await
+            .map(|(response, _)| {
+                Cancelled::new(OrderId::new(response.result.order_id), response.time)
+            })
+            .map_err(order_error);
+
+        UnindexedOrderResponseCancel {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            state: result,
+        }
+    }
+
+    async fn open_order(
+        &self,
+        request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
+    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+        let body = OpenOrderBody::new(
+            Server::CATEGORY,
+            request.key.instrument.clone(),
+            request.key.cid.clone(),
+            request.state.side,
+            request.state.kind,
+            request.state.time_in_force,
+            request.state.price,
+            request.state.quantity,
+            request.state.flags,
+        );
+
+        let state = match body {
+            _ if Server::CATEGORY == BybitCategory::Spot && request.state.flags.is_reducing() => {
+                Err(OrderError::Rejected(ApiError::OrderRejected(
+                    "Bybit spot does not support reduce only or close position orders".to_string(),
+                )))
+            }
+            Some(body) => self
+                .rest
+                .execute(OpenOrder { body })
+                .await
+                .map(|(response, _)| {
+                    debug!(
+                        exchange = %Server::EXCHANGE,
+                        order_id = %response.result.order_id,
+                        "Bybit order opened"
+                    );
+                    Open::new(
+                        OrderId::new(response.result.order_id),
+                        response.time,
+                        Decimal::ZERO,
+                    )
+                })
+                .map_err(order_error),
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                "Bybit does not support {} orders with {}",
+                request.state.kind, request.state.time_in_force
+            )))),
+        };
+
+        Order {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            side: request.state.side,
+            price: request.state.price,
+            quantity: request.state.quantity,
+            kind: request.state.kind,
+            time_in_force: request.state.time_in_force,
+            state,
+        }
+    }
+
+    async fn fetch_balances(
+        &self,
+    ) -> Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError> {
+        let (response, _) = self
+            .rest
+            .execute(FetchWalletBalance {
+                query: FetchWalletBalanceParams {
+                    account_type: "UNIFIED",
+                },
+            })
+            .await?;
+
+        let time_exchange = response.time;
+
+        Ok(response
+            .result
+            .list
+            .into_iter()
+            .flat_map(|wallet| wallet.coin)
+            .map(|coin| coin.into_asset_balance(time_exchange))
+            .collect())
+    }
+
+    async fn fetch_open_orders(
+        &self,
+    ) -> Result<Vec<Order<ExchangeId, InstrumentNameExchange, Open>>, UnindexedClientError> {
+        let mut orders = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (response, _) = self
+                .rest
+                .execute(FetchOpenOrders {
+                    query: FetchOpenOrdersParams {
+                        category: Server::CATEGORY,
+                        settle_coin: Server::SETTLE_COIN,
+                        limit: FETCH_OPEN_ORDERS_LIMIT,
+                        cursor,
+                    },
+                })
+                .await?;
+
+            orders.extend(
+                response
+                    .result
+                    .list
+                    .into_iter()
+                    .filter_map(|order| order.into_open_order(Server::EXCHANGE)),
+            );
+
+            if response.result.next_page_cursor.is_empty() {
+                break Ok(orders);
+            }
+            cursor = Some(response.result.next_page_cursor);
+        }
+    }
+
+    /// Fetch account trades since the provided time.
+    ///
+    /// Bybit only returns executions within 7 days of the provided start time.
+    async fn fetch_trades(
+        &self,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let mut trades = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (response, _) = self
+                .rest
+                .execute(FetchExecutions {
+                    query: FetchExecutionsParams {
+                        category: Server::CATEGORY,
+                        start_time: time_since.timestamp_millis(),
+                        limit: FETCH_EXECUTIONS_LIMIT,
+                        cursor,
+                    },
+                })
+                .await?;
+
+            trades.extend(
+                response
+                    .result
+                    .list
+                    .into_iter()
+                    .filter(|execution| execution.is_fill())
+                    .map(|execution| execution.into_trade()),
+            );
+
+            if response.result.next_page_cursor.is_empty() {
+                break Ok(trades);
+            }
+            cursor = Some(response.result.next_page_cursor);
+        }
+    }
+}
+
+async fn send_ws_request(
+    websocket: &mut WebSocket,
+    request: BybitWsRequest,
+) -> Result<(), UnindexedClientError> {
+    websocket
+        .send(request.to_ws_message())
+        .await
+        .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))
+}
+
+/// Wait for the private WebSocket response to the provided `op`, returning an error if the
+/// request failed.
+async fn await_ws_response(
+    websocket: &mut WebSocket,
+    op: &str,
+) -> Result<(), UnindexedClientError> {
+    let response = async {
+        while let Some(message) = websocket.next().await {
+            match <WebSocketParser as StreamParser<BybitPrivateMessage>>::parse(message) {
+                Some(Ok(BybitPrivateMessage::Response(response))) if response.op == op => {
+                    return if response.success {
+                        Ok(())
+                    } else {
+                        Err(format!("Bybit {op} failed: {}", response.ret_msg))
+                    };
+                }
+                Some(Err(error)) => return Err(error.to_string()),
+                _ => {}
+            }
+        }
+        Err(format!(
+            "Bybit private WebSocket closed before {op} response"
+        ))
+    };
+
+    tokio::time::timeout(WS_RESPONSE_TIMEOUT, response)
+        .await
+        .unwrap_or_else(|_| Err(format!("Bybit {op} response timed out")))
+        .map_err(UnindexedClientError::AccountStream)
+}
+
+/// Periodically send the private WebSocket heartbeat.
+///
+/// Runs until aborted by the [`BybitHeartbeat`] guard held by the account stream.
+async fn heartbeat(mut sink: WsSink, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+
+    // First tick completes immediately, and the connection was only just authenticated
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        if let Err(error) = sink.send(BybitWsRequest::ping().to_ws_message()).await {
+            warn!(?error, "failed to send Bybit private WebSocket heartbeat");
+            break;
+        }
+    }
+}
+
+impl BybitWsRequest {
+    fn to_ws_message(&self) -> WsMessage {
+        WsMessage::text(
+            serde_json::to_string(self).expect("BybitWsRequest serialisation is infallible"),
+        )
+    }
+}
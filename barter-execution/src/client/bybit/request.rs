@@ -0,0 +1,542 @@
+use crate::{
+    balance::{AssetBalance, Balance},
+    client::{
+        bybit::{BybitCategory, parse_order_kind},
+        de_decimal_or_zero, fees_quote,
+    },
+    order::{
+        Order, OrderFlags, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::Open,
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side,
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::http::rest::RestRequest;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use std::borrow::Cow;
+use tracing::warn;
+
+/// Bybit V5 Http response envelope.
+///
+/// Bybit responds with Http status 200 for most errors, so the
+/// [`BybitParser`](super::BybitParser) only yields a [`BybitResponse`] if the `retCode` is 0.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "retCode": 0,
+///     "retMsg": "OK",
+///     "result": {},
+///     "retExtInfo": {},
+///     "time": 1672211918471
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitResponse<T> {
+    pub result: T,
+    #[serde(deserialize_with = "barter_integration::de::de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+}
+
+/// Bybit V5 paginated list result.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitPage<T> {
+    pub list: Vec<T>,
+    #[serde(default)]
+    pub next_page_cursor: String,
+}
+
+/// Bybit V5 signed request to fetch the unified account wallet balance.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/account/wallet-balance>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchWalletBalance {
+    pub query: FetchWalletBalanceParams,
+}
+
+impl RestRequest for FetchWalletBalance {
+    type Response = BybitResponse<BybitPage<BybitWallet>>;
+    type QueryParams = FetchWalletBalanceParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/v5/account/wallet-balance")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchWalletBalance`] request query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchWalletBalanceParams {
+    pub account_type: &'static str,
+}
+
+/// Bybit V5 unified account wallet.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "accountType": "UNIFIED",
+///     "totalEquity": "3.31216591",
+///     "coin": [
+///         {
+///             "coin": "BTC",
+///             "equity": "0.00012",
+///             "walletBalance": "0.00012",
+///             "locked": "0",
+///             "availableToWithdraw": "",
+///             "unrealisedPnl": "0"
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitWallet {
+    pub coin: Vec<BybitWalletCoin>,
+}
+
+/// Bybit V5 unified account coin balance, where the `free` balance is the `walletBalance` less
+/// any balance `locked` by open spot orders.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitWalletCoin {
+    pub coin: AssetNameExchange,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub wallet_balance: Decimal,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub locked: Decimal,
+}
+
+impl BybitWalletCoin {
+    /// Map into an [`AssetBalance`] at the provided exchange time.
+    pub fn into_asset_balance(
+        self,
+        time_exchange: DateTime<Utc>,
+    ) -> AssetBalance<AssetNameExchange> {
+        AssetBalance::new(
+            self.coin,
+            Balance::new(self.wallet_balance, self.wallet_balance - self.locked),
+            time_exchange,
+        )
+    }
+}
+
+/// Bybit V5 signed request to fetch open orders.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/order/open-order>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchOpenOrders {
+    pub query: FetchOpenOrdersParams,
+}
+
+impl RestRequest for FetchOpenOrders {
+    type Response = BybitResponse<BybitPage<BybitOrder>>;
+    type QueryParams = FetchOpenOrdersParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/v5/order/realtime")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchOpenOrders`] request query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchOpenOrdersParams {
+    pub category: BybitCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settle_coin: Option<&'static str>,
+    pub limit: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Bybit V5 order, as returned by the open orders endpoint and the private `order` topic.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "category": "linear",
+///     "symbol": "ETHUSDT",
+///     "orderId": "fd4300ae-7847-404e-b947-b46980a4d140",
+///     "orderLinkId": "test-000005",
+///     "side": "Buy",
+///     "orderType": "Limit",
+///     "timeInForce": "GTC",
+///     "price": "1600.00",
+///     "qty": "0.10",
+///     "cumExecQty": "0.00",
+///     "orderStatus": "New",
+///     "rejectReason": "EC_NoError",
+///     "createdTime": "1684738540559",
+///     "updatedTime": "1684738540561"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitOrder {
+    /// Only present in private `order` topic updates.
+    #[serde(default)]
+    pub category: Option<BybitCategory>,
+    pub symbol: InstrumentNameExchange,
+    pub order_id: SmolStr,
+    pub order_link_id: SmolStr,
+    pub side: Side,
+    pub order_type: SmolStr,
+    pub time_in_force: SmolStr,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub price: Decimal,
+    pub qty: Decimal,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub cum_exec_qty: Decimal,
+    pub order_status: SmolStr,
+    #[serde(default)]
+    pub reject_reason: SmolStr,
+    #[serde(deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc")]
+    pub updated_time: DateTime<Utc>,
+}
+
+impl BybitOrder {
+    /// Convert into an [`Order`] in the [`Open`] state, returning `None` if the Bybit order
+    /// type is not supported.
+    pub fn into_open_order(
+        self,
+        exchange: ExchangeId,
+    ) -> Option<Order<ExchangeId, InstrumentNameExchange, Open>> {
+        let Some((kind, time_in_force)) = parse_order_kind(&self.order_type, &self.time_in_force)
+        else {
+            warn!(
+                %exchange,
+                symbol = %self.symbol,
+                order_id = %self.order_id,
+                kind = %self.order_type,
+                "ignoring Bybit open order with unsupported type"
+            );
+            return None;
+        };
+
+        Some(Order {
+            key: OrderKey::new(
+                exchange,
+                self.symbol,
+                StrategyId::unknown(),
+                ClientOrderId::new(self.order_link_id),
+            ),
+            side: self.side,
+            price: self.price,
+            quantity: self.qty,
+            kind,
+            time_in_force,
+            state: Open::new(
+                OrderId::new(self.order_id),
+                self.updated_time,
+                self.cum_exec_qty,
+            ),
+        })
+    }
+}
+
+/// Bybit V5 signed request to open a new order.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/order/create-order>
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenOrder {
+    pub body: OpenOrderBody,
+}
+
+impl RestRequest for OpenOrder {
+    type Response = BybitResponse<BybitOrderResponse>;
+    type QueryParams = ();
+    type Body = OpenOrderBody;
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/v5/order/create")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+}
+
+/// [`OpenOrder`] request body.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrderBody {
+    pub category: BybitCategory,
+    pub symbol: InstrumentNameExchange,
+    pub side: &'static str,
+    pub order_type: &'static str,
+    pub qty: Decimal,
+    /// Spot market orders are sized in the quote asset by default, so are explicitly sized in
+    /// the base asset to match the [`OrderRequestOpen`](crate::order::request::OrderRequestOpen)
+    /// quantity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_unit: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_on_trigger: Option<bool>,
+    pub order_link_id: ClientOrderId,
+}
+
+impl OpenOrderBody {
+    /// Construct an [`OpenOrderBody`] from the provided order fields, returning `None` if the
+    /// [`OrderKind`] and [`TimeInForce`] combination is not supported by Bybit.
+    ///
+    /// `close_position` orders are sent as `closeOnTrigger` reduce only orders. Spot orders cannot
+    /// reduce a position, so reducing [`OrderFlags`] return `None` for the spot category.
+    pub fn new(
+        category: BybitCategory,
+        symbol: InstrumentNameExchange,
+        cid: ClientOrderId,
+        side: Side,
+        kind: OrderKind,
+        time_in_force: TimeInForce,
+        price: Decimal,
+        quantity: Decimal,
+        flags: OrderFlags,
+    ) -> Option<Self> {
+        if category == BybitCategory::Spot && flags.is_reducing() {
+            return None;
+        }
+
+        let (order_type, time_in_force, price) = match (kind, time_in_force) {
+            (OrderKind::Market, _) => ("Market", None, None),
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only: true }) => {
+                ("Limit", Some("PostOnly"), Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only: false }) => {
+                ("Limit", Some("GTC"), Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::ImmediateOrCancel) => {
+                ("Limit", Some("IOC"), Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::FillOrKill) => ("Limit", Some("FOK"), Some(price)),
+            (OrderKind::Limit, TimeInForce::GoodUntilEndOfDay) => return None,
+            // Conditional orders are not supported
+            _ => return None,
+        };
+
+        let market_unit =
+            (category == BybitCategory::Spot && order_type == "Market").then_some("baseCoin");
+
+        let reduce_only = flags.is_reducing().then_some(true);
+        let close_on_trigger = flags.close_position.then_some(true);
+
+        Some(Self {
+            category,
+            symbol,
+            side: match side {
+                Side::Buy => "Buy",
+                Side::Sell => "Sell",
+            },
+            order_type,
+            qty: quantity,
+            market_unit,
+            price,
+            time_in_force,
+            reduce_only,
+            close_on_trigger,
+            order_link_id: cid,
+        })
+    }
+}
+
+/// Bybit V5 [`OpenOrder`] & [`CancelOrder`] response result.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "orderId": "1321003749386327552",
+///     "orderLinkId": "spot-test-postonly"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitOrderResponse {
+    pub order_id: SmolStr,
+    pub order_link_id: SmolStr,
+}
+
+/// Bybit V5 signed request to cancel an open order.
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/order/cancel-order>
+#[derive(Debug, Clone, Serialize)]
+pub struct CancelOrder {
+    pub body: CancelOrderBody,
+}
+
+impl RestRequest for CancelOrder {
+    type Response = BybitResponse<BybitOrderResponse>;
+    type QueryParams = ();
+    type Body = CancelOrderBody;
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/v5/order/cancel")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+}
+
+/// [`CancelOrder`] request body.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderBody {
+    pub category: BybitCategory,
+    pub symbol: InstrumentNameExchange,
+    pub order_link_id: ClientOrderId,
+}
+
+/// Bybit V5 signed request to fetch account executions (trades).
+///
+/// See docs: <https://bybit-exchange.github.io/docs/v5/order/execution>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchExecutions {
+    pub query: FetchExecutionsParams,
+}
+
+impl RestRequest for FetchExecutions {
+    type Response = BybitResponse<BybitPage<BybitExecution>>;
+    type QueryParams = FetchExecutionsParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/v5/execution/list")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchExecutions`] request query parameters.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchExecutionsParams {
+    pub category: BybitCategory,
+    pub start_time: i64,
+    pub limit: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Bybit V5 execution, as returned by the executions endpoint and the private `execution` topic.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "category": "linear",
+///     "symbol": "XRPUSDT",
+///     "execFee": "0.005061",
+///     "execId": "7e2ae69c-4edf-5800-a352-893d52b446aa",
+///     "execPrice": "0.3374",
+///     "execQty": "25",
+///     "execType": "Trade",
+///     "feeCurrency": "",
+///     "orderId": "f6e324ff-99c2-4e89-9739-3086e47f9381",
+///     "orderLinkId": "",
+///     "side": "Sell",
+///     "execTime": "1672364174443"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitExecution {
+    /// Only present in private `execution` topic updates.
+    #[serde(default)]
+    pub category: Option<BybitCategory>,
+    pub symbol: InstrumentNameExchange,
+    pub exec_id: SmolStr,
+    pub order_id: SmolStr,
+    pub side: Side,
+    pub exec_type: SmolStr,
+    pub exec_price: Decimal,
+    pub exec_qty: Decimal,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub exec_fee: Decimal,
+    /// Spot fee currency, which is empty (or absent) for derivatives that charge fees in the
+    /// settle coin.
+    #[serde(default)]
+    pub fee_currency: SmolStr,
+    #[serde(deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc")]
+    pub exec_time: DateTime<Utc>,
+}
+
+impl BybitExecution {
+    /// Returns true if this execution is an order fill (rather than eg/ a funding settlement).
+    pub fn is_fill(&self) -> bool {
+        matches!(self.exec_type.as_str(), "Trade" | "AdlTrade" | "BustTrade")
+    }
+
+    /// Convert into a [`Trade`], with fees converted into the quote asset.
+    pub fn into_trade(self) -> Trade<QuoteAsset, InstrumentNameExchange> {
+        let fees = if self.fee_currency.is_empty() {
+            self.exec_fee
+        } else {
+            fees_quote(
+                &self.symbol,
+                &AssetNameExchange::new(self.fee_currency),
+                self.exec_fee,
+                self.exec_price,
+            )
+        };
+
+        Trade {
+            id: TradeId::new(self.exec_id),
+            order_id: OrderId::new(self.order_id),
+            instrument: self.symbol,
+            strategy: StrategyId::unknown(),
+            time_exchange: self.exec_time,
+            side: self.side,
+            price: self.exec_price,
+            quantity: self.exec_qty,
+            fees: AssetFees::quote_fees(fees),
+        }
+    }
+}
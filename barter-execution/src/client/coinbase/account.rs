@@ -0,0 +1,386 @@
+use crate::{
+    AccountEventKind, UnindexedAccountEvent,
+    client::de_decimal_or_zero,
+    error::{ApiError, OrderError},
+    order::{
+        Order, OrderKey, OrderKind, OrderSnapshot, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::{CancelInFlight, Cancelled, Open, OrderState},
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side,
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::{protocol::websocket::WsMessage, snapshot::Snapshot};
+use chrono::{DateTime, Utc};
+use fnv::FnvHashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use tracing::warn;
+
+/// Coinbase Advanced Trade WebSocket `subscribe` request, authenticated with a JWT.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoinbaseWsSubscribe {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub channel: &'static str,
+    pub jwt: String,
+}
+
+impl CoinbaseWsSubscribe {
+    /// Subscribe to the provided `channel` (eg/ `user` or `heartbeats`).
+    pub fn new(channel: &'static str, jwt: String) -> Self {
+        Self {
+            kind: "subscribe",
+            channel,
+            jwt,
+        }
+    }
+
+    /// Serialise into a text [`WsMessage`].
+    pub fn to_ws_message(&self) -> WsMessage {
+        WsMessage::text(
+            serde_json::to_string(self).expect("CoinbaseWsSubscribe serialisation is infallible"),
+        )
+    }
+}
+
+/// Coinbase Advanced Trade WebSocket message.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum CoinbaseWsMessage {
+    Channel(CoinbaseChannelMessage),
+    Error(CoinbaseWsError),
+}
+
+/// Coinbase Advanced Trade WebSocket channel message, identified by the `channel` field.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "channel": "user",
+///     "client_id": "",
+///     "timestamp": "2023-02-09T20:33:57.609931463Z",
+///     "sequence_num": 0,
+///     "events": []
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "channel", rename_all = "lowercase")]
+pub enum CoinbaseChannelMessage {
+    User {
+        timestamp: DateTime<Utc>,
+        events: Vec<CoinbaseUserEvent>,
+    },
+    Subscriptions,
+    /// Eg/ `heartbeats` channel messages.
+    #[serde(other)]
+    Other,
+}
+
+/// Coinbase Advanced Trade WebSocket error.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "type": "error",
+///     "message": "authentication failure"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseWsError {
+    #[serde(rename = "type")]
+    pub kind: SmolStr,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// Coinbase Advanced Trade `user` channel event.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseUserEvent {
+    #[serde(rename = "type")]
+    pub kind: CoinbaseUserEventKind,
+    #[serde(default)]
+    pub orders: Vec<CoinbaseUserOrder>,
+}
+
+/// Coinbase Advanced Trade `user` channel event type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoinbaseUserEventKind {
+    Snapshot,
+    Update,
+}
+
+/// Coinbase Advanced Trade `user` channel order.
+///
+/// The channel does not publish individual fills, so trades are derived from changes in the
+/// `cumulative_quantity`, `filled_value` & `total_fees` (see [`CoinbaseOrderFills`]).
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "order_id": "0000-000000-000000",
+///     "client_order_id": "11111-000000-000000",
+///     "product_id": "BTC-USD",
+///     "order_side": "BUY",
+///     "order_type": "Limit",
+///     "time_in_force": "GOOD_UNTIL_CANCELLED",
+///     "post_only": false,
+///     "status": "OPEN",
+///     "limit_price": "29000",
+///     "cumulative_quantity": "0.05",
+///     "leaves_quantity": "0.15",
+///     "avg_price": "29000",
+///     "filled_value": "1450",
+///     "total_fees": "1.45",
+///     "reject_reason": "",
+///     "creation_time": "2023-11-14T22:13:20Z"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseUserOrder {
+    pub order_id: SmolStr,
+    pub client_order_id: SmolStr,
+    pub product_id: InstrumentNameExchange,
+    pub order_side: Side,
+    pub order_type: SmolStr,
+    #[serde(default)]
+    pub time_in_force: SmolStr,
+    #[serde(default)]
+    pub post_only: bool,
+    pub status: SmolStr,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub limit_price: Decimal,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub cumulative_quantity: Decimal,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub leaves_quantity: Decimal,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub avg_price: Decimal,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub filled_value: Decimal,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub total_fees: Decimal,
+    #[serde(default)]
+    pub reject_reason: String,
+}
+
+impl CoinbaseUserOrder {
+    /// Returns true if the order is no longer active.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.status.as_str(),
+            "FILLED" | "CANCELLED" | "EXPIRED" | "FAILED"
+        )
+    }
+
+    /// Map into an [`Order`] snapshot, returning `None` if the order type or status is not
+    /// supported.
+    ///
+    /// Coinbase order statuses map to an [`OrderState`] as follows:
+    /// - `PENDING`, `OPEN` & `QUEUED`: [`Open`]
+    /// - `CANCEL_QUEUED`: [`CancelInFlight`]
+    /// - `FILLED`: fully filled
+    /// - `CANCELLED`: [`Cancelled`]
+    /// - `EXPIRED`: expired
+    /// - `FAILED`: open failed with the `reject_reason`
+    pub fn order_snapshot(
+        &self,
+        exchange: ExchangeId,
+        time_exchange: DateTime<Utc>,
+    ) -> Option<OrderSnapshot<ExchangeId, AssetNameExchange, InstrumentNameExchange>> {
+        let Some((kind, time_in_force)) =
+            parse_order_kind(&self.order_type, &self.time_in_force, self.post_only)
+        else {
+            warn!(
+                %exchange,
+                instrument = %self.product_id,
+                order_id = %self.order_id,
+                kind = %self.order_type,
+                time_in_force = %self.time_in_force,
+                "ignoring Coinbase order update for unsupported order type"
+            );
+            return None;
+        };
+
+        let order_id = OrderId::new(self.order_id.clone());
+        let open = || Open::new(order_id.clone(), time_exchange, self.cumulative_quantity);
+
+        let state = match self.status.as_str() {
+            "PENDING" | "OPEN" | "QUEUED" => OrderState::active(open()),
+            "CANCEL_QUEUED" => OrderState::active(CancelInFlight::new(Some(open()))),
+            "FILLED" => OrderState::fully_filled(),
+            "CANCELLED" => OrderState::inactive(Cancelled::new(order_id, time_exchange)),
+            "EXPIRED" => OrderState::expired(),
+            "FAILED" => OrderState::inactive(OrderError::Rejected(ApiError::OrderRejected(
+                format!("Coinbase order failed: {}", self.reject_reason),
+            ))),
+            status => {
+                warn!(
+                    %exchange,
+                    instrument = %self.product_id,
+                    order_id = %self.order_id,
+                    %status,
+                    "ignoring Coinbase order update with unknown order status"
+                );
+                return None;
+            }
+        };
+
+        Some(Order {
+            key: OrderKey::new(
+                exchange,
+                self.product_id.clone(),
+                StrategyId::unknown(),
+                ClientOrderId::new(self.client_order_id.clone()),
+            ),
+            side: self.order_side,
+            price: self.limit_price,
+            quantity: self.cumulative_quantity + self.leaves_quantity,
+            kind,
+            time_in_force,
+            state,
+        })
+    }
+}
+
+/// Parse a Coinbase `user` channel order type, time in force & post only flag into an
+/// [`OrderKind`] and [`TimeInForce`].
+///
+/// Returns `None` for unsupported order types (eg/ `Stop Limit`) and time in force (eg/
+/// `GOOD_UNTIL_DATE_TIME`).
+pub fn parse_order_kind(
+    order_type: &str,
+    time_in_force: &str,
+    post_only: bool,
+) -> Option<(OrderKind, TimeInForce)> {
+    if order_type.eq_ignore_ascii_case("market") {
+        return Some((OrderKind::Market, TimeInForce::ImmediateOrCancel));
+    }
+
+    if !order_type.eq_ignore_ascii_case("limit") {
+        return None;
+    }
+
+    match time_in_force {
+        "" | "GOOD_UNTIL_CANCELLED" => Some((
+            OrderKind::Limit,
+            TimeInForce::GoodUntilCancelled { post_only },
+        )),
+        "IMMEDIATE_OR_CANCEL" => Some((OrderKind::Limit, TimeInForce::ImmediateOrCancel)),
+        "FILL_OR_KILL" => Some((OrderKind::Limit, TimeInForce::FillOrKill)),
+        _ => None,
+    }
+}
+
+/// Cumulative fill state of an active order, used to derive trades from `user` channel
+/// updates.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+struct CoinbaseFillState {
+    quantity: Decimal,
+    value: Decimal,
+    fees: Decimal,
+}
+
+/// Cache of the cumulative fill state of active Coinbase orders, keyed by `order_id`.
+///
+/// Each `user` channel update that increases the `cumulative_quantity` generates a [`Trade`]
+/// for the difference, priced using the change in `filled_value` and charged the change in
+/// `total_fees` (which Coinbase denominates in the quote asset).
+#[derive(Debug, Clone, Default)]
+pub struct CoinbaseOrderFills(FnvHashMap<SmolStr, CoinbaseFillState>);
+
+impl CoinbaseOrderFills {
+    /// Replace the cached fill state with that of the orders contained in a `user` channel
+    /// snapshot.
+    pub fn insert_snapshot(&mut self, orders: &[CoinbaseUserOrder]) {
+        self.0 = orders
+            .iter()
+            .filter(|order| !order.is_terminal())
+            .map(|order| {
+                (
+                    order.order_id.clone(),
+                    CoinbaseFillState {
+                        quantity: order.cumulative_quantity,
+                        value: order.filled_value,
+                        fees: order.total_fees,
+                    },
+                )
+            })
+            .collect();
+    }
+
+    /// Process a `user` channel order update, generating a `Trade` (if the update was caused
+    /// by a fill) followed by an `OrderSnapshot`.
+    ///
+    /// Orders are removed from the cache once they are no longer active.
+    pub fn update(
+        &mut self,
+        exchange: ExchangeId,
+        time_exchange: DateTime<Utc>,
+        order: CoinbaseUserOrder,
+    ) -> Vec<UnindexedAccountEvent> {
+        let previous = self.0.get(&order.order_id).copied().unwrap_or_default();
+        let trade = fill(&order, previous, time_exchange)
+            .map(|trade| UnindexedAccountEvent::new(exchange, trade));
+
+        if order.is_terminal() {
+            self.0.remove(&order.order_id);
+        } else {
+            self.0.insert(
+                order.order_id.clone(),
+                CoinbaseFillState {
+                    quantity: order.cumulative_quantity,
+                    value: order.filled_value,
+                    fees: order.total_fees,
+                },
+            );
+        }
+
+        let snapshot = order.order_snapshot(exchange, time_exchange).map(|order| {
+            UnindexedAccountEvent::new(exchange, AccountEventKind::OrderSnapshot(Snapshot(order)))
+        });
+
+        trade.into_iter().chain(snapshot).collect()
+    }
+}
+
+/// Generate a [`Trade`] for any increase in the `cumulative_quantity` of the order since the
+/// `previous` fill state.
+fn fill(
+    order: &CoinbaseUserOrder,
+    previous: CoinbaseFillState,
+    time_exchange: DateTime<Utc>,
+) -> Option<Trade<QuoteAsset, InstrumentNameExchange>> {
+    let quantity = order.cumulative_quantity - previous.quantity;
+    if quantity <= Decimal::ZERO {
+        return None;
+    }
+
+    let value = order.filled_value - previous.value;
+    let price = if value > Decimal::ZERO {
+        value / quantity
+    } else {
+        order.avg_price
+    };
+
+    Some(Trade {
+        id: TradeId::new(format!("{}-{}", order.order_id, order.cumulative_quantity)),
+        order_id: OrderId::new(order.order_id.clone()),
+        instrument: order.product_id.clone(),
+        strategy: StrategyId::unknown(),
+        time_exchange,
+        side: order.order_side,
+        price,
+        quantity,
+        fees: AssetFees::quote_fees(order.total_fees - previous.fees),
+    })
+}
@@ -0,0 +1,855 @@
+use crate::{
+    InstrumentAccountSnapshot, UnindexedAccountEvent, UnindexedAccountSnapshot,
+    balance::AssetBalance,
+    client::{
+        ExecutionClient,
+        coinbase::{
+            account::{
+                CoinbaseChannelMessage, CoinbaseOrderFills, CoinbaseUserEvent,
+                CoinbaseUserEventKind, CoinbaseWsMessage, CoinbaseWsSubscribe,
+            },
+            request::{
+                CancelOrders, CancelOrdersBody, CoinbaseOrderConfiguration, CreateOrder,
+                CreateOrderBody, FetchAccounts, FetchFills, FetchFillsParams, FetchOpenOrders,
+                FetchOpenOrdersParams, FetchPageParams,
+            },
+        },
+        order_error,
+    },
+    error::{
+        ApiError, ClientError, ConnectivityError, OrderError, UnindexedClientError,
+        UnindexedOrderError,
+    },
+    order::{
+        Order, OrderKey,
+        id::OrderId,
+        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::Trade,
+};
+use barter_instrument::{
+    Side,
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::{
+    error::SocketError,
+    protocol::{
+        StreamParser,
+        http::{
+            BuildStrategy, HttpParser,
+            private::{
+                Signer,
+                encoder::{Encoder, HexEncoder},
+            },
+            rest::{RestRequest, client::RestClient},
+        },
+        websocket::{WebSocket, WebSocketParser, connect},
+    },
+};
+use base64::Engine;
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{SinkExt, StreamExt, stream::BoxStream};
+use hmac::Mac;
+use itertools::Itertools;
+use p256::{
+    SecretKey,
+    ecdsa::{Signature, SigningKey, signature::Signer as _},
+    pkcs8::DecodePrivateKey,
+};
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{error, info, warn};
+
+/// Coinbase Advanced Trade Http request and response models.
+pub mod request;
+
+/// Coinbase Advanced Trade WebSocket `user` channel models.
+pub mod account;
+
+/// Coinbase Developer Platform JWT issuer.
+const JWT_ISSUER: &str = "cdp";
+
+/// Lifetime of each generated JWT, which is the maximum accepted by Coinbase.
+const JWT_EXPIRY_SECS: i64 = 120;
+
+/// Maximum time to wait for the WebSocket `user` channel snapshot after subscribing.
+const WS_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum number of accounts, orders or fills requested per page.
+const FETCH_LIMIT: u16 = 250;
+
+/// [`Coinbase`] execution client configuration.
+///
+/// Only the Coinbase Developer Platform API credentials are required, with the remaining fields
+/// defaulting to the Coinbase production endpoints:
+/// - `api_key`: API key name (eg/ "organizations/{org_id}/apiKeys/{key_id}").
+/// - `secret`: PEM encoded EC (P-256) private key, which may contain escaped newlines.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct CoinbaseConfig {
+    pub api_key: String,
+    pub secret: String,
+    #[serde(default = "default_base_url_rest")]
+    pub base_url_rest: String,
+    #[serde(default = "default_base_url_ws")]
+    pub base_url_ws: String,
+}
+
+impl CoinbaseConfig {
+    /// Validate the API credentials, returning an error if the `secret` is not a valid EC
+    /// private key.
+    pub fn validate(&self) -> Result<(), String> {
+        CoinbaseSigner::new(&self.api_key, &self.secret).map(|_| ())
+    }
+
+    /// Construct a [`CoinbaseConfig`] for the Coinbase production endpoints.
+    pub fn new<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            api_key: api_key.into(),
+            secret: secret.into(),
+            base_url_rest: default_base_url_rest(),
+            base_url_ws: default_base_url_ws(),
+        }
+    }
+}
+
+impl Debug for CoinbaseConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CoinbaseConfig")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field("base_url_rest", &self.base_url_rest)
+            .field("base_url_ws", &self.base_url_ws)
+            .finish()
+    }
+}
+
+fn default_base_url_rest() -> String {
+    "https://api.coinbase.com".to_string()
+}
+
+fn default_base_url_ws() -> String {
+    "wss://advanced-trade-ws-user.coinbase.com".to_string()
+}
+
+/// Coinbase Advanced Trade API specific [`Signer`] logic.
+///
+/// Rather than an HMAC signature, Coinbase authenticates each request with a short-lived JWT
+/// signed using ES256 (ECDSA P-256 with SHA-256), passed as a `Bearer` token. The JWT claims
+/// include the request method, host & path.
+///
+/// Since ES256 does not fit the [`Mac`] based
+/// [`RequestSigner`](barter_integration::protocol::http::private::RequestSigner), the
+/// [`CoinbaseSigner`] implements [`BuildStrategy`] directly, generating the JWT in
+/// [`Signer::config`] and ignoring the `signature` passed to [`Signer::build_signed_request`].
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/rest-api-auth>
+#[derive(Clone)]
+pub struct CoinbaseSigner {
+    pub key_name: String,
+    signing_key: SigningKey,
+}
+
+impl Debug for CoinbaseSigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CoinbaseSigner")
+            .field("key_name", &self.key_name)
+            .field("signing_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Coinbase JWT header.
+#[derive(Debug, Serialize)]
+struct JwtHeader<'a> {
+    alg: &'static str,
+    kid: &'a str,
+    nonce: String,
+    typ: &'static str,
+}
+
+/// Coinbase JWT claims, where the `uri` is only required for Http requests.
+#[derive(Debug, Serialize)]
+struct JwtClaims<'a> {
+    sub: &'a str,
+    iss: &'static str,
+    nbf: i64,
+    exp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri: Option<&'a str>,
+}
+
+impl CoinbaseSigner {
+    /// Construct a [`CoinbaseSigner`] from the provided API key name and PEM encoded EC private
+    /// key (SEC1 or PKCS#8).
+    ///
+    /// Returns an error if the private key is invalid.
+    pub fn new(key_name: &str, secret: &str) -> Result<Self, String> {
+        let pem = secret.replace("\\n", "\n");
+        let signing_key = SecretKey::from_sec1_pem(&pem)
+            .or_else(|_| SecretKey::from_pkcs8_pem(&pem))
+            .map(SigningKey::from)
+            .map_err(|error| {
+                format!("Coinbase API secret is not a valid EC private key: {error}")
+            })?;
+
+        Ok(Self {
+            key_name: key_name.to_string(),
+            signing_key,
+        })
+    }
+
+    /// Generate an ES256 signed JWT, including the provided `uri` claim (eg/
+    /// "GET api.coinbase.com/api/v3/brokerage/accounts") if authenticating an Http request.
+    pub fn jwt(&self, uri: Option<&str>) -> Result<String, SocketError> {
+        let now = Utc::now().timestamp();
+        let header = JwtHeader {
+            alg: "ES256",
+            kid: &self.key_name,
+            nonce: HexEncoder.encode(rand::random::<[u8; 16]>()),
+            typ: "JWT",
+        };
+        let claims = JwtClaims {
+            sub: &self.key_name,
+            iss: JWT_ISSUER,
+            nbf: now,
+            exp: now + JWT_EXPIRY_SECS,
+            uri,
+        };
+
+        let message = format!(
+            "{}.{}",
+            base64_url_json(&header)?,
+            base64_url_json(&claims)?
+        );
+        let signature: Signature = self.signing_key.sign(message.as_bytes());
+
+        Ok(format!(
+            "{message}.{}",
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        ))
+    }
+}
+
+/// Serialise the provided value as base64url (no padding) encoded JSON.
+fn base64_url_json<T>(value: &T) -> Result<String, SocketError>
+where
+    T: Serialize,
+{
+    serde_json::to_vec(value)
+        .map(|json| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json))
+        .map_err(SocketError::Serialise)
+}
+
+/// Configuration required to sign a Coinbase [`RestRequest`].
+#[derive(Debug)]
+pub struct CoinbaseSignConfig {
+    pub jwt: String,
+}
+
+impl Signer for CoinbaseSigner {
+    type Config<'a>
+        = CoinbaseSignConfig
+    where
+        Self: 'a;
+
+    fn config<'a, Request>(
+        &'a self,
+        _: Request,
+        builder: &reqwest::RequestBuilder,
+    ) -> Result<Self::Config<'a>, SocketError>
+    where
+        Request: RestRequest,
+    {
+        let request = builder
+            .try_clone()
+            .ok_or_else(|| SocketError::Unsupported {
+                entity: "Coinbase".to_string(),
+                item: "streaming request bodies".to_string(),
+            })?
+            .build()?;
+
+        let uri = format!(
+            "{} {}{}",
+            request.method(),
+            request.url().host_str().unwrap_or_default(),
+            request.url().path()
+        );
+
+        Ok(CoinbaseSignConfig {
+            jwt: self.jwt(Some(&uri))?,
+        })
+    }
+
+    /// Coinbase requests are not HMAC signed, so there are no bytes to sign.
+    fn add_bytes_to_sign<M>(_: &mut M, _: &Self::Config<'_>)
+    where
+        M: Mac,
+    {
+    }
+
+    fn build_signed_request(
+        config: Self::Config<'_>,
+        builder: reqwest::RequestBuilder,
+        _: String,
+    ) -> Result<reqwest::Request, SocketError> {
+        builder
+            .bearer_auth(config.jwt)
+            .build()
+            .map_err(SocketError::from)
+    }
+}
+
+impl BuildStrategy for CoinbaseSigner {
+    fn build<Request>(
+        &self,
+        request: Request,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Request, SocketError>
+    where
+        Request: RestRequest,
+    {
+        let config = self.config(request, &builder)?;
+        Self::build_signed_request(config, builder, String::new())
+    }
+}
+
+/// Coinbase Advanced Trade API error, returned with a non-success Http status.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "error": "UNAUTHENTICATED",
+///     "message": "Unauthenticated",
+///     "error_details": ""
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct CoinbaseApiError {
+    #[serde(default)]
+    pub error: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// [`HttpParser`] for Coinbase Advanced Trade Http APIs, mapping [`CoinbaseApiError`]s to
+/// [`ClientError`]s.
+#[derive(Debug, Copy, Clone)]
+pub struct CoinbaseParser;
+
+impl HttpParser for CoinbaseParser {
+    type ApiError = CoinbaseApiError;
+    type OutputError = ClientError<AssetNameExchange, InstrumentNameExchange>;
+
+    /// Coinbase error payloads may be empty, so the Http status is checked before attempting to
+    /// deserialise the `Response`.
+    fn parse<Response>(
+        &self,
+        status: StatusCode,
+        payload: &[u8],
+    ) -> Result<Response, Self::OutputError>
+    where
+        Response: DeserializeOwned,
+    {
+        if !status.is_success() {
+            let error = serde_json::from_slice::<CoinbaseApiError>(payload).unwrap_or_else(|_| {
+                CoinbaseApiError {
+                    error: status.to_string(),
+                    message: String::from_utf8_lossy(payload).to_string(),
+                }
+            });
+            return Err(self.parse_api_error(status, error));
+        }
+
+        serde_json::from_slice::<Response>(payload).map_err(|error| {
+            error!(
+                status_code = ?status,
+                ?error,
+                response_body = %String::from_utf8_lossy(payload),
+                "error deserializing Coinbase HTTP response"
+            );
+            ClientError::from(SocketError::DeserialiseBinary {
+                error,
+                payload: payload.to_vec(),
+            })
+        })
+    }
+
+    fn parse_api_error(&self, status: StatusCode, error: Self::ApiError) -> Self::OutputError {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => ClientError::Api(ApiError::RateLimit),
+            StatusCode::UNAUTHORIZED => ClientError::Connectivity(ConnectivityError::Socket(
+                format!("Coinbase authentication failed: {}", error.message),
+            )),
+            StatusCode::SERVICE_UNAVAILABLE => {
+                ClientError::Connectivity(ConnectivityError::ExchangeOffline(ExchangeId::Coinbase))
+            }
+            _ => ClientError::Api(ApiError::OrderRejected(format!(
+                "Coinbase {}: {}",
+                error.error, error.message
+            ))),
+        }
+    }
+}
+
+/// Coinbase Advanced Trade live [`ExecutionClient`].
+///
+/// Orders are opened, cancelled and fetched via the signed Http endpoints, while the
+/// [`AccountStream`](ExecutionClient::AccountStream) is built on the WebSocket `user` channel.
+///
+/// Instruments are identified by their Coinbase product id (eg/ "BTC-USD").
+///
+/// Spot orders cannot reduce a position, so open requests with reducing
+/// [`OrderFlags`](crate::order::OrderFlags) are rejected without a request.
+///
+/// Coinbase orders can only be cancelled by exchange [`OrderId`], so cancel requests must
+/// include the [`OrderId`] received when the order was opened.
+#[derive(Debug, Clone)]
+pub struct Coinbase {
+    pub config: CoinbaseConfig,
+    signer: CoinbaseSigner,
+    rest: Arc<RestClient<'static, CoinbaseSigner, CoinbaseParser>>,
+}
+
+impl ExecutionClient for Coinbase {
+    const EXCHANGE: ExchangeId = ExchangeId::Coinbase;
+    type Config = CoinbaseConfig;
+    type AccountStream = BoxStream<'static, UnindexedAccountEvent>;
+
+    /// # Panics
+    /// Panics if the [`CoinbaseConfig`] `secret` is invalid, which the `barter` system builder
+    /// checks beforehand via [`CoinbaseConfig::validate`].
+    fn new(config: Self::Config) -> Self {
+        let signer = CoinbaseSigner::new(&config.api_key, &config.secret)
+            .unwrap_or_else(|error| panic!("invalid CoinbaseConfig: {error}"));
+        let rest = RestClient::new(config.base_url_rest.clone(), signer.clone(), CoinbaseParser);
+
+        Self {
+            config,
+            signer,
+            rest: Arc::new(rest),
+        }
+    }
+
+    async fn account_snapshot(
+        &self,
+        assets: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<UnindexedAccountSnapshot, UnindexedClientError> {
+        let balances = self.fetch_balances().await?;
+        let open_orders = self.fetch_open_orders().await?;
+
+        let balances = balances
+            .into_iter()
+            .filter(|balance| assets.contains(&balance.asset))
+            .collect();
+
+        let mut orders_by_instrument = open_orders
+            .into_iter()
+            .filter(|order| instruments.contains(&order.key.instrument))
+            .into_group_map_by(|order| order.key.instrument.clone());
+
+        let instruments = instruments
+            .iter()
+            .map(|instrument| {
+                let orders = orders_by_instrument
+                    .remove(instrument)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|order| Order {
+                        key: order.key,
+                        side: order.side,
+                        price: order.price,
+                        quantity: order.quantity,
+                        kind: order.kind,
+                        time_in_force: order.time_in_force,
+                        state: OrderState::active(order.state),
+                    })
+                    .collect();
+
+                InstrumentAccountSnapshot::new(instrument.clone(), orders, None)
+            })
+            .collect();
+
+        Ok(UnindexedAccountSnapshot::new(
+            Self::EXCHANGE,
+            balances,
+            instruments,
+        ))
+    }
+
+    async fn account_stream(
+        &self,
+        _: &[AssetNameExchange],
+        _: &[InstrumentNameExchange],
+    ) -> Result<Self::AccountStream, UnindexedClientError> {
+        let mut websocket = connect(self.config.base_url_ws.as_str())
+            .await
+            .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+
+        // Heartbeats keep the connection alive when there are no user channel updates
+        for channel in ["user", "heartbeats"] {
+            let subscribe = CoinbaseWsSubscribe::new(channel, self.signer.jwt(None)?);
+            websocket
+                .send(subscribe.to_ws_message())
+                .await
+                .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+        }
+
+        // Initial snapshot seeds the fill state used to derive trades from updates
+        let mut fills = CoinbaseOrderFills::default();
+        await_user_snapshot(&mut websocket, &mut fills).await?;
+
+        info!(
+            exchange = %Self::EXCHANGE,
+            "connected to Coinbase user WebSocket"
+        );
+
+        Ok(websocket
+            .filter_map(|message| {
+                std::future::ready(<WebSocketParser as StreamParser<CoinbaseWsMessage>>::parse(
+                    message,
+                ))
+            })
+            .scan(fills, |fills, message| {
+                std::future::ready(match message {
+                    Ok(CoinbaseWsMessage::Channel(CoinbaseChannelMessage::User {
+                        timestamp,
+                        events,
+                    })) => Some(into_account_events(fills, timestamp, events)),
+                    Ok(CoinbaseWsMessage::Channel(_)) => Some(vec![]),
+                    Ok(CoinbaseWsMessage::Error(error)) => {
+                        warn!(
+                            message = %error.message,
+                            "received Coinbase user WebSocket error"
+                        );
+                        Some(vec![])
+                    }
+                    Err(error @ SocketError::Deserialise { .. }) => {
+                        warn!(
+                            ?error,
+                            "failed to deserialise Coinbase user WebSocket message"
+                        );
+                        Some(vec![])
+                    }
+                    Err(error) => {
+                        warn!(?error, "Coinbase user WebSocket failed, ending stream");
+                        None
+                    }
+                })
+            })
+            .flat_map(futures::stream::iter)
+            .boxed())
+    }
+
+    async fn cancel_order(
+        &self,
+        request: OrderRequestCancel<ExchangeId, &InstrumentNameExchange>,
+    ) -> UnindexedOrderResponseCancel {
+        let state = match request.state.id {
+            Some(order_id) => self.cancel(order_id).await,
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(
+                "Coinbase requires the exchange OrderId to cancel an order".to_string(),
+            ))),
+        };
+
+        UnindexedOrderResponseCancel {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            state,
+        }
+    }
+
+    async fn open_order(
+        &self,
+        request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
+    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+        let configuration = CoinbaseOrderConfiguration::new(
+            request.state.kind,
+            request.state.time_in_force,
+            request.state.price,
+            request.state.quantity,
+        );
+
+        let state = match configuration {
+            _ if request.state.flags.is_reducing() => {
+                Err(OrderError::Rejected(ApiError::OrderRejected(
+                    "Coinbase spot does not support reduce only or close position orders"
+                        .to_string(),
+                )))
+            }
+            Some(order_configuration) => {
+                self.open(CreateOrderBody {
+                    client_order_id: request.key.cid.clone(),
+                    product_id: request.key.instrument.clone(),
+                    side: match request.state.side {
+                        Side::Buy => "BUY",
+                        Side::Sell => "SELL",
+                    },
+                    order_configuration,
+                })
+                .await
+            }
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                "Coinbase does not support {} orders with {}",
+                request.state.kind, request.state.time_in_force
+            )))),
+        };
+
+        Order {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            side: request.state.side,
+            price: request.state.price,
+            quantity: request.state.quantity,
+            kind: request.state.kind,
+            time_in_force: request.state.time_in_force,
+            state,
+        }
+    }
+
+    /// Fetch the balances of every Coinbase trading account.
+    ///
+    /// Coinbase accounts do not provide a last updated time, so the current time is used.
+    async fn fetch_balances(
+        &self,
+    ) -> Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError> {
+        let mut balances = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (response, _) = self
+                .rest
+                .execute(FetchAccounts {
+                    query: FetchPageParams {
+                        limit: FETCH_LIMIT,
+                        cursor,
+                    },
+                })
+                .await?;
+
+            let time_exchange = Utc::now();
+            balances.extend(
+                response
+                    .accounts
+                    .into_iter()
+                    .map(|account| account.into_balance(time_exchange)),
+            );
+
+            if !response.has_next || response.cursor.is_empty() {
+                break Ok(balances);
+            }
+            cursor = Some(response.cursor);
+        }
+    }
+
+    async fn fetch_open_orders(
+        &self,
+    ) -> Result<Vec<Order<ExchangeId, InstrumentNameExchange, Open>>, UnindexedClientError> {
+        let mut orders = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (response, _) = self
+                .rest
+                .execute(FetchOpenOrders {
+                    query: FetchOpenOrdersParams {
+                        order_status: "OPEN",
+                        limit: FETCH_LIMIT,
+                        cursor,
+                    },
+                })
+                .await?;
+
+            orders.extend(
+                response
+                    .orders
+                    .into_iter()
+                    .filter_map(|order| order.into_open_order(Self::EXCHANGE)),
+            );
+
+            if !response.has_next || response.cursor.is_empty() {
+                break Ok(orders);
+            }
+            cursor = Some(response.cursor);
+        }
+    }
+
+    async fn fetch_trades(
+        &self,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let mut trades = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (response, _) = self
+                .rest
+                .execute(FetchFills {
+                    query: FetchFillsParams {
+                        start_sequence_timestamp: time_since
+                            .to_rfc3339_opts(SecondsFormat::Millis, true),
+                        limit: FETCH_LIMIT,
+                        cursor,
+                    },
+                })
+                .await?;
+
+            let page_len = response.fills.len();
+            trades.extend(response.fills.into_iter().map(Trade::from));
+
+            if page_len == 0 || response.cursor.is_empty() {
+                break Ok(trades);
+            }
+            cursor = Some(response.cursor);
+        }
+    }
+}
+
+impl Coinbase {
+    /// Open an order, mapping any `error_response` to an [`OrderError`].
+    ///
+    /// Coinbase does not return the order creation time, so the current time is used.
+    async fn open(&self, body: CreateOrderBody) -> Result<Open, UnindexedOrderError> {
+        let (response, _) = self
+            .rest
+            .execute(CreateOrder { body })
+            .await
+            .map_err(order_error)?;
+
+        match (response.success, response.success_response) {
+            (true, Some(success)) => Ok(Open::new(
+                OrderId::new(success.order_id),
+                Utc::now(),
+                Decimal::ZERO,
+            )),
+            _ => {
+                let failure =
+                    response
+                        .error_response
+                        .unwrap_or_else(|| request::CoinbaseOrderFailure {
+                            error: "UNKNOWN_FAILURE_REASON".to_string(),
+                            message: String::new(),
+                        });
+
+                Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                    "Coinbase {}: {}",
+                    failure.error, failure.message
+                ))))
+            }
+        }
+    }
+
+    /// Cancel an order by exchange [`OrderId`], mapping any `failure_reason` to an
+    /// [`OrderError`].
+    ///
+    /// Coinbase does not return the cancellation time, so the current time is used.
+    async fn cancel(&self, order_id: OrderId) -> Result<Cancelled, UnindexedOrderError> {
+        let (response, _) = self
+            .rest
+            .execute(CancelOrders {
+                body: CancelOrdersBody {
+                    order_ids: vec![order_id.clone()],
+                },
+            })
+            .await
+            .map_err(order_error)?;
+
+        match response.results.into_iter().next() {
+            Some(result) if result.success => Ok(Cancelled::new(order_id, Utc::now())),
+            Some(result) => Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                "Coinbase cancel failed: {}",
+                result.failure_reason
+            )))),
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(
+                "Coinbase cancel failed: empty response".to_string(),
+            ))),
+        }
+    }
+}
+
+/// Map `user` channel events into zero or more [`UnindexedAccountEvent`]s.
+fn into_account_events(
+    fills: &mut CoinbaseOrderFills,
+    time_exchange: DateTime<Utc>,
+    events: Vec<CoinbaseUserEvent>,
+) -> Vec<UnindexedAccountEvent> {
+    events
+        .into_iter()
+        .flat_map(|event| match event.kind {
+            CoinbaseUserEventKind::Snapshot => {
+                fills.insert_snapshot(&event.orders);
+                vec![]
+            }
+            CoinbaseUserEventKind::Update => event
+                .orders
+                .into_iter()
+                .flat_map(|order| fills.update(Coinbase::EXCHANGE, time_exchange, order))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Wait for the initial `user` channel snapshot, seeding the provided [`CoinbaseOrderFills`].
+async fn await_user_snapshot(
+    websocket: &mut WebSocket,
+    fills: &mut CoinbaseOrderFills,
+) -> Result<(), UnindexedClientError> {
+    let snapshot = async {
+        while let Some(message) = websocket.next().await {
+            match <WebSocketParser as StreamParser<CoinbaseWsMessage>>::parse(message) {
+                Some(Ok(CoinbaseWsMessage::Channel(CoinbaseChannelMessage::User {
+                    events,
+                    ..
+                }))) => {
+                    if let Some(snapshot) = events
+                        .iter()
+                        .find(|event| event.kind == CoinbaseUserEventKind::Snapshot)
+                    {
+                        fills.insert_snapshot(&snapshot.orders);
+                        return Ok(());
+                    }
+                }
+                Some(Ok(CoinbaseWsMessage::Error(error))) => {
+                    return Err(UnindexedClientError::AccountStream(format!(
+                        "Coinbase user WebSocket subscription failed: {}",
+                        error.message
+                    )));
+                }
+                Some(Err(error)) => {
+                    return Err(UnindexedClientError::AccountStream(error.to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        Err(UnindexedClientError::AccountStream(
+            "Coinbase user WebSocket closed before snapshot".to_string(),
+        ))
+    };
+
+    tokio::time::timeout(WS_RESPONSE_TIMEOUT, snapshot)
+        .await
+        .unwrap_or(Err(UnindexedClientError::Connectivity(
+            ConnectivityError::Timeout,
+        )))
+}
@@ -0,0 +1,563 @@
+use crate::{
+    balance::{AssetBalance, Balance},
+    order::{
+        Order, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::Open,
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side,
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::http::rest::RestRequest;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use std::borrow::Cow;
+use tracing::warn;
+
+/// Coinbase Advanced Trade signed request to fetch a page of trading accounts.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_getaccounts>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchAccounts {
+    pub query: FetchPageParams,
+}
+
+impl RestRequest for FetchAccounts {
+    type Response = CoinbaseAccounts;
+    type QueryParams = FetchPageParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/brokerage/accounts")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// Cursor paginated request query parameters.
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchPageParams {
+    pub limit: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Coinbase Advanced Trade page of trading accounts.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "accounts": [
+///         {
+///             "uuid": "8bfc20d7-f7c6-4422-bf07-8243ca4169fe",
+///             "currency": "BTC",
+///             "available_balance": { "value": "1.2", "currency": "BTC" },
+///             "hold": { "value": "0.3", "currency": "BTC" }
+///         }
+///     ],
+///     "has_next": false,
+///     "cursor": ""
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseAccounts {
+    pub accounts: Vec<CoinbaseAccount>,
+    #[serde(default)]
+    pub has_next: bool,
+    #[serde(default)]
+    pub cursor: String,
+}
+
+/// Coinbase Advanced Trade trading account, holding a single currency.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseAccount {
+    pub currency: AssetNameExchange,
+    pub available_balance: CoinbaseAmount,
+    pub hold: CoinbaseAmount,
+}
+
+/// Coinbase Advanced Trade currency amount.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseAmount {
+    pub value: Decimal,
+}
+
+impl CoinbaseAccount {
+    /// Convert into an [`AssetBalance`], where the total balance is the sum of the available and
+    /// held amounts.
+    ///
+    /// Coinbase accounts do not provide a last updated time, so the provided time is used.
+    pub fn into_balance(self, time_exchange: DateTime<Utc>) -> AssetBalance<AssetNameExchange> {
+        AssetBalance::new(
+            self.currency,
+            Balance::new(
+                self.available_balance.value + self.hold.value,
+                self.available_balance.value,
+            ),
+            time_exchange,
+        )
+    }
+}
+
+/// Coinbase Advanced Trade signed request to fetch a page of open orders.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_gethistoricalorders>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchOpenOrders {
+    pub query: FetchOpenOrdersParams,
+}
+
+impl RestRequest for FetchOpenOrders {
+    type Response = CoinbaseOrders;
+    type QueryParams = FetchOpenOrdersParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/brokerage/orders/historical/batch")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchOpenOrders`] request query parameters.
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchOpenOrdersParams {
+    pub order_status: &'static str,
+    pub limit: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Coinbase Advanced Trade page of orders.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseOrders {
+    pub orders: Vec<CoinbaseOrder>,
+    #[serde(default)]
+    pub has_next: bool,
+    #[serde(default)]
+    pub cursor: String,
+}
+
+/// Coinbase Advanced Trade order.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "order_id": "0000-000000-000000",
+///     "product_id": "BTC-USD",
+///     "side": "BUY",
+///     "client_order_id": "11111-000000-000000",
+///     "status": "OPEN",
+///     "order_configuration": {
+///         "limit_limit_gtc": { "base_size": "0.2", "limit_price": "29000", "post_only": true }
+///     },
+///     "filled_size": "0.05",
+///     "created_time": "2023-11-14T22:13:20Z"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseOrder {
+    pub order_id: SmolStr,
+    pub product_id: InstrumentNameExchange,
+    pub side: Side,
+    pub client_order_id: SmolStr,
+    pub status: SmolStr,
+    pub order_configuration: CoinbaseOrderConfiguration,
+    #[serde(default)]
+    pub filled_size: Decimal,
+    pub created_time: DateTime<Utc>,
+}
+
+impl CoinbaseOrder {
+    /// Convert into an [`Order`] in the [`Open`] state, returning `None` if the Coinbase order
+    /// configuration is not supported.
+    pub fn into_open_order(
+        self,
+        exchange: ExchangeId,
+    ) -> Option<Order<ExchangeId, InstrumentNameExchange, Open>> {
+        let Some((kind, time_in_force, price, quantity)) = self.order_configuration.parse() else {
+            warn!(
+                %exchange,
+                instrument = %self.product_id,
+                order_id = %self.order_id,
+                "ignoring Coinbase open order with unsupported configuration"
+            );
+            return None;
+        };
+
+        Some(Order {
+            key: OrderKey::new(
+                exchange,
+                self.product_id,
+                StrategyId::unknown(),
+                ClientOrderId::new(self.client_order_id),
+            ),
+            side: self.side,
+            price,
+            quantity,
+            kind,
+            time_in_force,
+            state: Open::new(
+                OrderId::new(self.order_id),
+                self.created_time,
+                self.filled_size,
+            ),
+        })
+    }
+}
+
+/// Coinbase Advanced Trade order configuration, which defines the order type and time in force.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_postorder>
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinbaseOrderConfiguration {
+    MarketMarketIoc {
+        base_size: Decimal,
+    },
+    LimitLimitGtc {
+        base_size: Decimal,
+        limit_price: Decimal,
+        #[serde(default)]
+        post_only: bool,
+    },
+    LimitLimitFok {
+        base_size: Decimal,
+        limit_price: Decimal,
+    },
+    SorLimitIoc {
+        base_size: Decimal,
+        limit_price: Decimal,
+    },
+    /// Eg/ `limit_limit_gtd`, `stop_limit_stop_limit_gtc`, or market orders sized in the quote
+    /// asset.
+    #[serde(untagged)]
+    Unsupported(serde_json::Value),
+}
+
+impl CoinbaseOrderConfiguration {
+    /// Construct a [`CoinbaseOrderConfiguration`] from the provided order fields, returning
+    /// `None` if the [`OrderKind`] and [`TimeInForce`] combination is not supported by Coinbase.
+    pub fn new(
+        kind: OrderKind,
+        time_in_force: TimeInForce,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Option<Self> {
+        match (kind, time_in_force) {
+            (OrderKind::Market, _) => Some(Self::MarketMarketIoc {
+                base_size: quantity,
+            }),
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only }) => {
+                Some(Self::LimitLimitGtc {
+                    base_size: quantity,
+                    limit_price: price,
+                    post_only,
+                })
+            }
+            (OrderKind::Limit, TimeInForce::FillOrKill) => Some(Self::LimitLimitFok {
+                base_size: quantity,
+                limit_price: price,
+            }),
+            (OrderKind::Limit, TimeInForce::ImmediateOrCancel) => Some(Self::SorLimitIoc {
+                base_size: quantity,
+                limit_price: price,
+            }),
+            (OrderKind::Limit, TimeInForce::GoodUntilEndOfDay) => None,
+            // Conditional orders are not supported
+            _ => None,
+        }
+    }
+
+    /// Parse into an [`OrderKind`], [`TimeInForce`], price & quantity, returning `None` if the
+    /// configuration is not supported.
+    ///
+    /// Market orders have no price, so the price is zero.
+    pub fn parse(&self) -> Option<(OrderKind, TimeInForce, Decimal, Decimal)> {
+        match *self {
+            Self::MarketMarketIoc { base_size } => Some((
+                OrderKind::Market,
+                TimeInForce::ImmediateOrCancel,
+                Decimal::ZERO,
+                base_size,
+            )),
+            Self::LimitLimitGtc {
+                base_size,
+                limit_price,
+                post_only,
+            } => Some((
+                OrderKind::Limit,
+                TimeInForce::GoodUntilCancelled { post_only },
+                limit_price,
+                base_size,
+            )),
+            Self::LimitLimitFok {
+                base_size,
+                limit_price,
+            } => Some((
+                OrderKind::Limit,
+                TimeInForce::FillOrKill,
+                limit_price,
+                base_size,
+            )),
+            Self::SorLimitIoc {
+                base_size,
+                limit_price,
+            } => Some((
+                OrderKind::Limit,
+                TimeInForce::ImmediateOrCancel,
+                limit_price,
+                base_size,
+            )),
+            Self::Unsupported(_) => None,
+        }
+    }
+}
+
+/// Coinbase Advanced Trade signed request to open an order.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_postorder>
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateOrder {
+    pub body: CreateOrderBody,
+}
+
+impl RestRequest for CreateOrder {
+    type Response = CreateOrderResponse;
+    type QueryParams = ();
+    type Body = CreateOrderBody;
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/brokerage/orders")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+}
+
+/// [`CreateOrder`] request body.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CreateOrderBody {
+    pub client_order_id: ClientOrderId,
+    pub product_id: InstrumentNameExchange,
+    pub side: &'static str,
+    pub order_configuration: CoinbaseOrderConfiguration,
+}
+
+/// [`CreateOrder`] response.
+///
+/// Coinbase responds with Http status 200 if the order is rejected, populating the
+/// `error_response` rather than the `success_response`.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "success": false,
+///     "error_response": {
+///         "error": "INSUFFICIENT_FUND",
+///         "message": "Insufficient balance in source account"
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateOrderResponse {
+    pub success: bool,
+    #[serde(default)]
+    pub success_response: Option<CreateOrderSuccess>,
+    #[serde(default)]
+    pub error_response: Option<CoinbaseOrderFailure>,
+}
+
+/// [`CreateOrder`] success response.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreateOrderSuccess {
+    pub order_id: SmolStr,
+}
+
+/// Coinbase Advanced Trade order failure reason.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseOrderFailure {
+    #[serde(default)]
+    pub error: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// Coinbase Advanced Trade signed request to cancel orders by exchange `order_id`.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_cancelorders>
+#[derive(Debug, Clone, Serialize)]
+pub struct CancelOrders {
+    pub body: CancelOrdersBody,
+}
+
+impl RestRequest for CancelOrders {
+    type Response = CancelOrdersResponse;
+    type QueryParams = ();
+    type Body = CancelOrdersBody;
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/brokerage/orders/batch_cancel")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+}
+
+/// [`CancelOrders`] request body.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CancelOrdersBody {
+    pub order_ids: Vec<OrderId>,
+}
+
+/// [`CancelOrders`] response, containing a result for each requested `order_id`.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "results": [
+///         { "success": false, "failure_reason": "UNKNOWN_CANCEL_ORDER", "order_id": "0000-000000" }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CancelOrdersResponse {
+    pub results: Vec<CancelOrderResult>,
+}
+
+/// [`CancelOrders`] result for a single `order_id`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CancelOrderResult {
+    pub success: bool,
+    #[serde(default)]
+    pub failure_reason: String,
+    pub order_id: SmolStr,
+}
+
+/// Coinbase Advanced Trade signed request to fetch a page of fills (trades).
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/reference/retailbrokerageapi_getfills>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchFills {
+    pub query: FetchFillsParams,
+}
+
+impl RestRequest for FetchFills {
+    type Response = CoinbaseFills;
+    type QueryParams = FetchFillsParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v3/brokerage/orders/historical/fills")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchFills`] request query parameters.
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchFillsParams {
+    pub start_sequence_timestamp: String,
+    pub limit: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Coinbase Advanced Trade page of fills, with an empty `cursor` indicating the last page.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseFills {
+    pub fills: Vec<CoinbaseFill>,
+    #[serde(default)]
+    pub cursor: String,
+}
+
+/// Coinbase Advanced Trade fill.
+///
+/// The `commission` is denominated in the quote asset. If `size_in_quote` is true the `size`
+/// is also denominated in the quote asset.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "entry_id": "22222-2222222-22222222",
+///     "trade_id": "1111-11111-111111",
+///     "order_id": "0000-000000-000000",
+///     "trade_time": "2023-11-14T22:13:21Z",
+///     "price": "29000",
+///     "size": "0.05",
+///     "commission": "1.45",
+///     "product_id": "BTC-USD",
+///     "side": "BUY",
+///     "size_in_quote": false
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CoinbaseFill {
+    pub trade_id: SmolStr,
+    pub order_id: SmolStr,
+    pub trade_time: DateTime<Utc>,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub commission: Decimal,
+    pub product_id: InstrumentNameExchange,
+    pub side: Side,
+    #[serde(default)]
+    pub size_in_quote: bool,
+}
+
+impl From<CoinbaseFill> for Trade<QuoteAsset, InstrumentNameExchange> {
+    fn from(value: CoinbaseFill) -> Self {
+        let quantity = if value.size_in_quote && !value.price.is_zero() {
+            value.size / value.price
+        } else {
+            value.size
+        };
+
+        Trade {
+            id: TradeId::new(value.trade_id),
+            order_id: OrderId::new(value.order_id),
+            instrument: value.product_id,
+            strategy: StrategyId::unknown(),
+            time_exchange: value.trade_time,
+            side: value.side,
+            price: value.price,
+            quantity,
+            fees: AssetFees::quote_fees(value.commission),
+        }
+    }
+}
@@ -0,0 +1,461 @@
+use crate::{
+    AccountEventKind, UnindexedAccountEvent,
+    balance::{AssetBalance, Balance},
+    client::{fees_quote, kraken::parse_order_kind, kraken::request::KrakenOrderResult},
+    order::{
+        Order, OrderKey, OrderSnapshot,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side,
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::snapshot::Snapshot;
+use chrono::{DateTime, Utc};
+use fnv::FnvHashMap;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use smol_str::SmolStr;
+use tracing::warn;
+
+/// Kraken WebSocket v2 private message.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum KrakenWsMessage {
+    Channel(KrakenChannelMessage),
+    Response(KrakenWsResponse),
+}
+
+/// Kraken WebSocket v2 channel message, identified by the `channel` field.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "channel": "executions",
+///     "type": "update",
+///     "data": [],
+///     "sequence": 2
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "channel", rename_all = "lowercase")]
+pub enum KrakenChannelMessage {
+    Executions {
+        #[serde(rename = "type")]
+        kind: KrakenMessageKind,
+        data: Vec<KrakenExecution>,
+    },
+    Balances {
+        #[serde(rename = "type")]
+        kind: KrakenMessageKind,
+        data: Vec<KrakenBalance>,
+    },
+    /// Eg/ `heartbeat` & `status` channel messages.
+    #[serde(other)]
+    Other,
+}
+
+/// Kraken WebSocket v2 channel message type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KrakenMessageKind {
+    Snapshot,
+    Update,
+}
+
+/// Kraken WebSocket v2 method response, correlated to the request via the `req_id`.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "method": "add_order",
+///     "req_id": 7,
+///     "result": {
+///         "order_id": "OPS23M-VS41G-DDE5Z2",
+///         "cl_ord_id": "7c5b2d6a"
+///     },
+///     "success": true,
+///     "time_in": "2023-09-21T14:15:07.197274Z",
+///     "time_out": "2023-09-21T14:15:07.205301Z"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenWsResponse {
+    pub method: SmolStr,
+    #[serde(default)]
+    pub req_id: Option<u64>,
+    #[serde(default)]
+    pub success: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub result: Option<KrakenOrderResult>,
+    #[serde(default)]
+    pub time_out: Option<DateTime<Utc>>,
+}
+
+/// Kraken WebSocket v2 `executions` channel entry.
+///
+/// Snapshot entries contain the full order (or trade) details, whereas update entries only
+/// contain the fields that have changed, so updates are merged into a [`KrakenOrders`] cache
+/// before being mapped.
+///
+/// ### Raw Payload Examples
+/// #### Trade Update
+/// ```json
+/// {
+///     "order_id": "OK4GJX-KSTLS-7DZZO5",
+///     "cl_ord_id": "7c5b2d6a",
+///     "exec_id": "TGKJCD-J3FUB-3DQHG4",
+///     "exec_type": "trade",
+///     "symbol": "BTC/USD",
+///     "side": "sell",
+///     "order_type": "limit",
+///     "order_qty": 0.5,
+///     "limit_price": 26500.0,
+///     "last_qty": 0.2,
+///     "last_price": 26500.0,
+///     "cum_qty": 0.2,
+///     "order_status": "partially_filled",
+///     "fees": [{ "asset": "USD", "qty": 2.12 }],
+///     "timestamp": "2023-09-22T10:33:05.709993Z"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenExecution {
+    pub order_id: SmolStr,
+    #[serde(default)]
+    pub cl_ord_id: Option<SmolStr>,
+    #[serde(default)]
+    pub exec_type: Option<SmolStr>,
+    #[serde(default)]
+    pub exec_id: Option<SmolStr>,
+    #[serde(default)]
+    pub symbol: Option<InstrumentNameExchange>,
+    #[serde(default)]
+    pub side: Option<Side>,
+    #[serde(default)]
+    pub order_type: Option<SmolStr>,
+    #[serde(default)]
+    pub time_in_force: Option<SmolStr>,
+    #[serde(default)]
+    pub post_only: Option<bool>,
+    #[serde(default)]
+    pub order_qty: Option<Decimal>,
+    #[serde(default)]
+    pub limit_price: Option<Decimal>,
+    #[serde(default)]
+    pub cum_qty: Option<Decimal>,
+    #[serde(default)]
+    pub last_qty: Option<Decimal>,
+    #[serde(default)]
+    pub last_price: Option<Decimal>,
+    #[serde(default)]
+    pub order_status: Option<SmolStr>,
+    #[serde(default)]
+    pub fees: Vec<KrakenFee>,
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Kraken fee charged for a trade, denominated in the provided `asset`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenFee {
+    pub asset: AssetNameExchange,
+    pub qty: Decimal,
+}
+
+impl KrakenExecution {
+    /// Construct a [`KrakenExecution`] for the provided `order_id`, with no other details.
+    pub fn new(order_id: SmolStr) -> Self {
+        Self {
+            order_id,
+            cl_ord_id: None,
+            exec_type: None,
+            exec_id: None,
+            symbol: None,
+            side: None,
+            order_type: None,
+            time_in_force: None,
+            post_only: None,
+            order_qty: None,
+            limit_price: None,
+            cum_qty: None,
+            last_qty: None,
+            last_price: None,
+            order_status: None,
+            fees: vec![],
+            timestamp: None,
+        }
+    }
+
+    /// Returns true if this entry describes a trade rather than an order status change.
+    pub fn is_trade(&self) -> bool {
+        self.exec_type.as_deref() == Some("trade")
+    }
+
+    /// Returns true if the order is no longer active.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.order_status.as_deref(),
+            Some("filled" | "canceled" | "expired")
+        )
+    }
+
+    /// Overwrite the fields of this cached order with any present in the provided update.
+    pub fn merge(&mut self, update: &Self) {
+        fn merge<T: Clone>(current: &mut Option<T>, update: &Option<T>) {
+            if let Some(update) = update {
+                *current = Some(update.clone());
+            }
+        }
+
+        merge(&mut self.cl_ord_id, &update.cl_ord_id);
+        merge(&mut self.exec_type, &update.exec_type);
+        merge(&mut self.exec_id, &update.exec_id);
+        merge(&mut self.symbol, &update.symbol);
+        merge(&mut self.side, &update.side);
+        merge(&mut self.order_type, &update.order_type);
+        merge(&mut self.time_in_force, &update.time_in_force);
+        merge(&mut self.post_only, &update.post_only);
+        merge(&mut self.order_qty, &update.order_qty);
+        merge(&mut self.limit_price, &update.limit_price);
+        merge(&mut self.cum_qty, &update.cum_qty);
+        merge(&mut self.order_status, &update.order_status);
+        merge(&mut self.timestamp, &update.timestamp);
+    }
+
+    /// Generate a [`Trade`] if this entry describes a trade, using the provided (merged) `order`
+    /// for any order details not included in the entry itself.
+    pub fn trade(&self, order: &Self) -> Option<Trade<QuoteAsset, InstrumentNameExchange>> {
+        if !self.is_trade() {
+            return None;
+        }
+
+        let (Some(instrument), Some(side), Some(price), Some(quantity)) = (
+            self.symbol.as_ref().or(order.symbol.as_ref()),
+            self.side.or(order.side),
+            self.last_price,
+            self.last_qty,
+        ) else {
+            warn!(
+                order_id = %self.order_id,
+                "ignoring Kraken trade missing instrument, side, price or quantity"
+            );
+            return None;
+        };
+
+        let fees = self
+            .fees
+            .iter()
+            .map(|fee| fees_quote(instrument, &fee.asset, fee.qty, price))
+            .sum();
+
+        Some(Trade {
+            id: TradeId::new(self.exec_id.clone().unwrap_or_default()),
+            order_id: OrderId::new(self.order_id.clone()),
+            instrument: instrument.clone(),
+            strategy: StrategyId::unknown(),
+            time_exchange: self.timestamp.or(order.timestamp).unwrap_or_else(Utc::now),
+            side,
+            price,
+            quantity,
+            fees: AssetFees::quote_fees(fees),
+        })
+    }
+
+    /// Map into an [`Order`] snapshot, returning `None` if required order details have not
+    /// yet been received, or the order type or status is not supported.
+    pub fn order_snapshot(
+        &self,
+        exchange: ExchangeId,
+    ) -> Option<OrderSnapshot<ExchangeId, AssetNameExchange, InstrumentNameExchange>> {
+        let order = self.order(exchange)?;
+        let order_id = OrderId::new(self.order_id.clone());
+        let time_exchange = self.timestamp.unwrap_or_else(Utc::now);
+
+        let state = match self.order_status.as_deref()? {
+            "pending_new" | "new" | "partially_filled" => OrderState::active(Open::new(
+                order_id,
+                time_exchange,
+                self.cum_qty.unwrap_or_default(),
+            )),
+            "filled" => OrderState::fully_filled(),
+            "canceled" => OrderState::inactive(Cancelled::new(order_id, time_exchange)),
+            "expired" => OrderState::expired(),
+            status => {
+                warn!(
+                    %exchange,
+                    order_id = %self.order_id,
+                    %status,
+                    "ignoring Kraken order update with unknown order status"
+                );
+                return None;
+            }
+        };
+
+        Some(Order {
+            key: order.key,
+            side: order.side,
+            price: order.price,
+            quantity: order.quantity,
+            kind: order.kind,
+            time_in_force: order.time_in_force,
+            state,
+        })
+    }
+
+    /// Map into an [`Order`] in the [`Open`] state, returning `None` if the order is not active,
+    /// or the order type is not supported.
+    pub fn open_order(
+        &self,
+        exchange: ExchangeId,
+    ) -> Option<Order<ExchangeId, InstrumentNameExchange, Open>> {
+        if !matches!(
+            self.order_status.as_deref(),
+            Some("pending_new" | "new" | "partially_filled")
+        ) {
+            return None;
+        }
+
+        let order = self.order(exchange)?;
+
+        Some(Order {
+            key: order.key,
+            side: order.side,
+            price: order.price,
+            quantity: order.quantity,
+            kind: order.kind,
+            time_in_force: order.time_in_force,
+            state: Open::new(
+                OrderId::new(self.order_id.clone()),
+                self.timestamp.unwrap_or_else(Utc::now),
+                self.cum_qty.unwrap_or_default(),
+            ),
+        })
+    }
+
+    fn order(&self, exchange: ExchangeId) -> Option<Order<ExchangeId, InstrumentNameExchange, ()>> {
+        let (Some(instrument), Some(side), Some(order_type), Some(quantity)) = (
+            self.symbol.clone(),
+            self.side,
+            self.order_type.as_deref(),
+            self.order_qty,
+        ) else {
+            return None;
+        };
+
+        let Some((kind, time_in_force)) = parse_order_kind(
+            order_type,
+            self.time_in_force.as_deref(),
+            self.post_only.unwrap_or_default(),
+        ) else {
+            warn!(
+                %exchange,
+                %instrument,
+                order_id = %self.order_id,
+                kind = %order_type,
+                "ignoring Kraken order with unsupported type"
+            );
+            return None;
+        };
+
+        Some(Order {
+            key: OrderKey::new(
+                exchange,
+                instrument,
+                StrategyId::unknown(),
+                ClientOrderId::new(self.cl_ord_id.clone().unwrap_or_default()),
+            ),
+            side,
+            price: self.limit_price.unwrap_or_default(),
+            quantity,
+            kind,
+            time_in_force,
+            state: (),
+        })
+    }
+}
+
+/// Kraken WebSocket v2 `balances` channel entry.
+///
+/// Kraken does not stream the balance held by open orders, so the free balance is reported as
+/// equal to the total.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "asset": "BTC",
+///     "asset_class": "currency",
+///     "balance": 1.2,
+///     "timestamp": "2023-09-22T10:33:05.709993Z"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenBalance {
+    pub asset: AssetNameExchange,
+    pub balance: Decimal,
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl From<KrakenBalance> for AssetBalance<AssetNameExchange> {
+    fn from(value: KrakenBalance) -> Self {
+        AssetBalance::new(
+            value.asset,
+            Balance::new(value.balance, value.balance),
+            value.timestamp.unwrap_or_else(Utc::now),
+        )
+    }
+}
+
+/// Cache of active Kraken orders, keyed by `order_id`, used to merge partial `executions`
+/// channel updates into complete orders.
+#[derive(Debug, Clone, Default)]
+pub struct KrakenOrders(FnvHashMap<SmolStr, KrakenExecution>);
+
+impl KrakenOrders {
+    /// Replace the cached orders with those contained in an `executions` channel snapshot.
+    pub fn insert_snapshot(&mut self, snapshot: Vec<KrakenExecution>) {
+        self.0 = snapshot
+            .into_iter()
+            .filter(|execution| !execution.is_trade() && !execution.is_terminal())
+            .map(|order| (order.order_id.clone(), order))
+            .collect();
+    }
+
+    /// Merge an `executions` channel update into the cached order, generating a `Trade` (if the
+    /// update was caused by a fill) followed by an `OrderSnapshot`.
+    ///
+    /// Orders are removed from the cache once they are no longer active.
+    pub fn update(
+        &mut self,
+        exchange: ExchangeId,
+        update: KrakenExecution,
+    ) -> Vec<UnindexedAccountEvent> {
+        let order = self
+            .0
+            .entry(update.order_id.clone())
+            .or_insert_with(|| KrakenExecution::new(update.order_id.clone()));
+        order.merge(&update);
+
+        let trade = update
+            .trade(order)
+            .map(|trade| UnindexedAccountEvent::new(exchange, trade));
+
+        let snapshot = order.order_snapshot(exchange).map(|order| {
+            UnindexedAccountEvent::new(exchange, AccountEventKind::OrderSnapshot(Snapshot(order)))
+        });
+
+        if order.is_terminal() {
+            self.0.remove(&update.order_id);
+        }
+
+        trade.into_iter().chain(snapshot).collect()
+    }
+}
@@ -0,0 +1,903 @@
+use crate::{
+    AccountEventKind, InstrumentAccountSnapshot, UnindexedAccountEvent, UnindexedAccountSnapshot,
+    balance::AssetBalance,
+    client::{
+        ExecutionClient,
+        kraken::{
+            account::{
+                KrakenBalance, KrakenChannelMessage, KrakenExecution, KrakenMessageKind,
+                KrakenOrders, KrakenWsMessage, KrakenWsResponse,
+            },
+            order_entry::KrakenOrderEntry,
+            request::{
+                GetWebSocketsToken, KrakenAddOrderParams, KrakenCancelOrderParams, KrakenNonceBody,
+                KrakenSubscribeParams, KrakenWsRequest,
+            },
+        },
+        order_error,
+    },
+    error::{
+        ApiError, ClientError, ConnectivityError, OrderError, UnindexedClientError,
+        UnindexedOrderError,
+    },
+    order::{
+        Order, OrderKey, OrderKind, TimeInForce,
+        id::OrderId,
+        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::Trade,
+};
+use barter_instrument::{
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::{
+    error::SocketError,
+    protocol::{
+        StreamParser,
+        http::{
+            HttpParser,
+            private::{RequestSigner, Signer, encoder::Base64Encoder},
+            rest::{RestRequest, client::RestClient},
+        },
+        websocket::{WebSocket, WebSocketParser, connect},
+    },
+    snapshot::Snapshot,
+};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt, stream::BoxStream};
+use hmac::{Hmac, Mac};
+use itertools::Itertools;
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256, Sha512};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+/// Kraken Http & WebSocket v2 request and response models.
+pub mod request;
+
+/// Kraken WebSocket v2 private channel models.
+pub mod account;
+
+/// Kraken WebSocket v2 order entry, correlating method responses to requests by `req_id`.
+pub mod order_entry;
+
+/// Http header containing the Kraken API key.
+pub const HEADER_KRAKEN_API_KEY: &str = "API-Key";
+
+/// Http header containing the Kraken request signature.
+pub const HEADER_KRAKEN_API_SIGN: &str = "API-Sign";
+
+/// Interval at which a `ping` is sent on the order entry connection, which has no channel
+/// subscriptions to keep it alive.
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum time to wait for a WebSocket `subscribe` response, and any requested snapshot.
+const WS_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// [`Kraken`] execution client configuration.
+///
+/// Only the API credentials are required, with the remaining fields defaulting to the Kraken
+/// production endpoints. The `secret` is the base64 encoded private key provided by Kraken.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct KrakenConfig {
+    pub api_key: String,
+    pub secret: String,
+    #[serde(default = "default_base_url_rest")]
+    pub base_url_rest: String,
+    #[serde(default = "default_base_url_ws")]
+    pub base_url_ws: String,
+    /// Maximum time to wait for an order entry response before returning
+    /// [`ConnectivityError::Timeout`].
+    #[serde(default = "default_order_timeout_ms")]
+    pub order_timeout_ms: u64,
+}
+
+impl KrakenConfig {
+    /// Validate the API credentials, returning an error if the `secret` is not valid base64.
+    pub fn validate(&self) -> Result<(), String> {
+        request_signer(&self.api_key, &self.secret).map(|_| ())
+    }
+
+    /// Construct a [`KrakenConfig`] for the Kraken production endpoints.
+    pub fn new<S>(api_key: S, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            api_key: api_key.into(),
+            secret: secret.into(),
+            base_url_rest: default_base_url_rest(),
+            base_url_ws: default_base_url_ws(),
+            order_timeout_ms: default_order_timeout_ms(),
+        }
+    }
+}
+
+impl Debug for KrakenConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KrakenConfig")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field("base_url_rest", &self.base_url_rest)
+            .field("base_url_ws", &self.base_url_ws)
+            .field("order_timeout_ms", &self.order_timeout_ms)
+            .finish()
+    }
+}
+
+fn default_base_url_rest() -> String {
+    "https://api.kraken.com".to_string()
+}
+
+fn default_base_url_ws() -> String {
+    "wss://ws-auth.kraken.com/v2".to_string()
+}
+
+fn default_order_timeout_ms() -> u64 {
+    5000
+}
+
+/// Generates the strictly increasing `nonce` required by every Kraken private Http request.
+///
+/// Nonces are microseconds since the epoch, incremented if required to ensure requests issued
+/// within the same microsecond remain unique.
+#[derive(Debug, Clone, Default)]
+pub struct KrakenNonce(Arc<AtomicU64>);
+
+impl KrakenNonce {
+    /// Generate the next `nonce`.
+    pub fn next(&self) -> u64 {
+        let now = u64::try_from(Utc::now().timestamp_micros()).unwrap_or_default();
+        let previous = self
+            .0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |previous| {
+                Some(previous.max(now.saturating_sub(1)) + 1)
+            })
+            .expect("update closure always returns Some");
+
+        previous.max(now.saturating_sub(1)) + 1
+    }
+}
+
+/// Convenient type alias for the HMAC-SHA512 [`RequestSigner`] used to sign Kraken requests.
+pub type KrakenRequestSigner = RequestSigner<KrakenSigner, Hmac<Sha512>, Base64Encoder>;
+
+/// Construct a [`KrakenRequestSigner`] from the provided API credentials.
+///
+/// Kraken secrets are base64 encoded, so the secret is decoded before being used as the HMAC
+/// key. Returns an error if the secret is not valid base64.
+pub fn request_signer(api_key: &str, secret: &str) -> Result<KrakenRequestSigner, String> {
+    let key = base64::engine::general_purpose::STANDARD
+        .decode(secret)
+        .map_err(|error| format!("Kraken API secret is not valid base64: {error}"))?;
+
+    Ok(RequestSigner::new(
+        KrakenSigner {
+            api_key: api_key.to_string(),
+        },
+        Hmac::<Sha512>::new_from_slice(&key).expect("HMAC-SHA512 accepts keys of any length"),
+        Base64Encoder,
+    ))
+}
+
+/// Kraken API specific [`Signer`] logic.
+///
+/// Kraken requires the base64 encoded HMAC-SHA512 signature of
+/// `{uri_path}{SHA256(nonce + post_data)}`, keyed with the base64 decoded secret. The `nonce` is
+/// read from the request body, which every private request must include.
+///
+/// See docs: <https://docs.kraken.com/api/docs/guides/spot-rest-auth>
+#[derive(Debug, Clone)]
+pub struct KrakenSigner {
+    pub api_key: String,
+}
+
+/// Configuration required to sign a Kraken [`RestRequest`].
+#[derive(Debug)]
+pub struct KrakenSignConfig<'a> {
+    pub api_key: &'a str,
+    pub path: String,
+    pub nonce: u64,
+    pub body: String,
+}
+
+impl Signer for KrakenSigner {
+    type Config<'a>
+        = KrakenSignConfig<'a>
+    where
+        Self: 'a;
+
+    fn config<'a, Request>(
+        &'a self,
+        _: Request,
+        builder: &reqwest::RequestBuilder,
+    ) -> Result<Self::Config<'a>, SocketError>
+    where
+        Request: RestRequest,
+    {
+        let request = builder
+            .try_clone()
+            .ok_or_else(|| SocketError::Unsupported {
+                entity: "Kraken".to_string(),
+                item: "streaming request bodies".to_string(),
+            })?
+            .build()?;
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| String::from_utf8_lossy(body).to_string())
+            .unwrap_or_default();
+
+        let KrakenNonceBody { nonce } =
+            serde_json::from_str(&body).map_err(|error| SocketError::Deserialise {
+                error,
+                payload: body.clone(),
+            })?;
+
+        Ok(KrakenSignConfig {
+            api_key: self.api_key.as_str(),
+            path: request.url().path().to_string(),
+            nonce,
+            body,
+        })
+    }
+
+    fn add_bytes_to_sign<M>(mac: &mut M, config: &Self::Config<'_>)
+    where
+        M: Mac,
+    {
+        let digest = Sha256::new()
+            .chain_update(config.nonce.to_string())
+            .chain_update(&config.body)
+            .finalize();
+
+        mac.update(config.path.as_bytes());
+        mac.update(&digest);
+    }
+
+    fn build_signed_request(
+        config: Self::Config<'_>,
+        builder: reqwest::RequestBuilder,
+        signature: String,
+    ) -> Result<reqwest::Request, SocketError> {
+        builder
+            .header(HEADER_KRAKEN_API_KEY, config.api_key)
+            .header(HEADER_KRAKEN_API_SIGN, signature)
+            .build()
+            .map_err(SocketError::from)
+    }
+}
+
+/// Kraken API error, identified by a non-empty `error` array.
+///
+/// See docs: <https://docs.kraken.com/api/docs/guides/spot-errors>
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct KrakenApiError {
+    pub error: Vec<String>,
+}
+
+/// [`HttpParser`] for Kraken Http APIs, mapping [`KrakenApiError`]s to [`ClientError`]s.
+#[derive(Debug, Copy, Clone)]
+pub struct KrakenParser;
+
+impl HttpParser for KrakenParser {
+    type ApiError = KrakenApiError;
+    type OutputError = ClientError<AssetNameExchange, InstrumentNameExchange>;
+
+    /// Kraken responds with Http status 200 for most errors, so the `error` array must be
+    /// checked before attempting to deserialise the `Response`.
+    fn parse<Response>(
+        &self,
+        status: StatusCode,
+        payload: &[u8],
+    ) -> Result<Response, Self::OutputError>
+    where
+        Response: DeserializeOwned,
+    {
+        match serde_json::from_slice::<KrakenApiError>(payload) {
+            Ok(error) if !error.error.is_empty() => {
+                return Err(self.parse_api_error(status, error));
+            }
+            Err(_) if status == StatusCode::TOO_MANY_REQUESTS => {
+                return Err(ClientError::Api(ApiError::RateLimit));
+            }
+            _ => {}
+        }
+
+        serde_json::from_slice::<Response>(payload).map_err(|error| {
+            error!(
+                status_code = ?status,
+                ?error,
+                response_body = %String::from_utf8_lossy(payload),
+                "error deserializing Kraken HTTP response"
+            );
+            ClientError::from(SocketError::DeserialiseBinary {
+                error,
+                payload: payload.to_vec(),
+            })
+        })
+    }
+
+    fn parse_api_error(&self, _: StatusCode, error: Self::ApiError) -> Self::OutputError {
+        api_error(&error.error.join(", "))
+    }
+}
+
+/// Map a Kraken error message (eg/ "EOrder:Insufficient funds") to a [`ClientError`].
+pub fn api_error(message: &str) -> UnindexedClientError {
+    match message {
+        message if message.contains("Rate limit") || message.contains("Too many requests") => {
+            ClientError::Api(ApiError::RateLimit)
+        }
+        message
+            if message.starts_with("EAPI:Invalid key")
+                || message.starts_with("EAPI:Invalid signature")
+                || message.starts_with("EAPI:Invalid nonce")
+                || message.starts_with("EGeneral:Permission denied") =>
+        {
+            ClientError::Connectivity(ConnectivityError::Socket(format!(
+                "Kraken authentication failed: {message}"
+            )))
+        }
+        message
+            if message.starts_with("EService:Unavailable")
+                || message.starts_with("EService:Busy") =>
+        {
+            ClientError::Connectivity(ConnectivityError::ExchangeOffline(ExchangeId::Kraken))
+        }
+        message => ClientError::Api(ApiError::OrderRejected(format!("Kraken error: {message}"))),
+    }
+}
+
+/// Parse a Kraken order type, time in force & post only flag into an [`OrderKind`] and
+/// [`TimeInForce`].
+///
+/// Returns `None` for unsupported order types (eg/ `stop-loss`) and time in force (eg/ `GTD`).
+pub fn parse_order_kind(
+    order_type: &str,
+    time_in_force: Option<&str>,
+    post_only: bool,
+) -> Option<(OrderKind, TimeInForce)> {
+    match (order_type, time_in_force) {
+        ("market", _) => Some((OrderKind::Market, TimeInForce::ImmediateOrCancel)),
+        ("limit", None) => Some((
+            OrderKind::Limit,
+            TimeInForce::GoodUntilCancelled { post_only },
+        )),
+        ("limit", Some(time_in_force)) if time_in_force.eq_ignore_ascii_case("gtc") => Some((
+            OrderKind::Limit,
+            TimeInForce::GoodUntilCancelled { post_only },
+        )),
+        ("limit", Some(time_in_force)) if time_in_force.eq_ignore_ascii_case("ioc") => {
+            Some((OrderKind::Limit, TimeInForce::ImmediateOrCancel))
+        }
+        _ => None,
+    }
+}
+
+/// Kraken private channel snapshots received after subscribing.
+#[derive(Debug, Clone, Default)]
+pub struct KrakenWsSnapshot {
+    pub executions: Vec<KrakenExecution>,
+    pub balances: Vec<KrakenBalance>,
+}
+
+/// Kraken Spot live [`ExecutionClient`], built on the WebSocket v2 API.
+///
+/// Private WebSocket connections are authenticated with a token fetched from the signed
+/// `GetWebSocketsToken` Http endpoint:
+/// - Orders are opened and cancelled over a dedicated order entry connection, with each
+///   response correlated to its request via the `req_id` echoed by Kraken. The connection is
+///   established on first use, and re-established on the next request if it fails.
+/// - The [`AccountStream`](ExecutionClient::AccountStream) subscribes to the `executions` &
+///   `balances` channels.
+/// - Account snapshots are taken from the `executions` & `balances` channel snapshots.
+///
+/// Instruments are identified by their WebSocket v2 symbol (eg/ "BTC/USD"), and assets by
+/// their WebSocket v2 name (eg/ "BTC").
+///
+/// Spot orders cannot reduce a position, so open requests with reducing
+/// [`OrderFlags`](crate::order::OrderFlags) are rejected without a request.
+#[derive(Debug, Clone)]
+pub struct Kraken {
+    pub config: KrakenConfig,
+    rest: Arc<RestClient<'static, KrakenRequestSigner, KrakenParser>>,
+    nonce: KrakenNonce,
+    order_entry: Arc<Mutex<Option<KrakenOrderEntry>>>,
+}
+
+impl Kraken {
+    /// Fetch a token used to authenticate private WebSocket connections.
+    async fn fetch_token(&self) -> Result<String, UnindexedClientError> {
+        let (response, _) = self
+            .rest
+            .execute(GetWebSocketsToken {
+                body: KrakenNonceBody {
+                    nonce: self.nonce.next(),
+                },
+            })
+            .await?;
+
+        Ok(response.result.token)
+    }
+
+    /// Fetch a token and connect to the private WebSocket.
+    async fn connect_private(&self) -> Result<(WebSocket, String), UnindexedClientError> {
+        let token = self.fetch_token().await?;
+
+        let websocket = connect(self.config.base_url_ws.as_str())
+            .await
+            .map_err(|error| {
+                UnindexedClientError::Connectivity(ConnectivityError::Socket(error.to_string()))
+            })?;
+
+        Ok((websocket, token))
+    }
+
+    /// Connect to the private WebSocket and subscribe to the provided channels, returning
+    /// any requested snapshots.
+    async fn subscribe<F>(
+        &self,
+        subscriptions: F,
+    ) -> Result<(WebSocket, KrakenWsSnapshot), UnindexedClientError>
+    where
+        F: FnOnce(&str) -> Vec<KrakenSubscribeParams>,
+    {
+        let (mut websocket, token) = self.connect_private().await?;
+        let snapshot = subscribe(&mut websocket, subscriptions(&token)).await?;
+        Ok((websocket, snapshot))
+    }
+
+    /// Get the current order entry connection, establishing a new one if required.
+    async fn order_entry(&self) -> Result<KrakenOrderEntry, UnindexedClientError> {
+        let mut order_entry = self.order_entry.lock().await;
+
+        match order_entry.as_ref() {
+            Some(current) if !current.is_closed() => Ok(current.clone()),
+            _ => {
+                let (websocket, token) = self.connect_private().await?;
+                let connection = KrakenOrderEntry::spawn(websocket, token);
+                info!(exchange = %Self::EXCHANGE, "connected Kraken order entry WebSocket");
+                *order_entry = Some(connection.clone());
+                Ok(connection)
+            }
+        }
+    }
+
+    /// Send an order entry `method` request, mapping any `error` in the response.
+    async fn order_method<Params>(
+        &self,
+        method: &'static str,
+        params: Params,
+    ) -> Result<KrakenWsResponse, UnindexedClientError>
+    where
+        Params: Serialize,
+    {
+        let params = serde_json::to_value(params).map_err(|error| {
+            UnindexedClientError::Connectivity(ConnectivityError::Socket(error.to_string()))
+        })?;
+
+        let response = self
+            .order_entry()
+            .await?
+            .request(
+                method,
+                params,
+                Duration::from_millis(self.config.order_timeout_ms),
+            )
+            .await?;
+
+        if response.success {
+            Ok(response)
+        } else {
+            Err(api_error(response.error.as_deref().unwrap_or_default()))
+        }
+    }
+}
+
+impl ExecutionClient for Kraken {
+    const EXCHANGE: ExchangeId = ExchangeId::Kraken;
+    type Config = KrakenConfig;
+    type AccountStream = BoxStream<'static, UnindexedAccountEvent>;
+
+    /// # Panics
+    /// Panics if the [`KrakenConfig`] `secret` is invalid, which the `barter` system builder
+    /// checks beforehand via [`KrakenConfig::validate`].
+    fn new(config: Self::Config) -> Self {
+        let signer = request_signer(&config.api_key, &config.secret)
+            .unwrap_or_else(|error| panic!("invalid KrakenConfig: {error}"));
+        let rest = RestClient::new(config.base_url_rest.clone(), signer, KrakenParser);
+
+        Self {
+            config,
+            rest: Arc::new(rest),
+            nonce: KrakenNonce::default(),
+            order_entry: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    async fn account_snapshot(
+        &self,
+        assets: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<UnindexedAccountSnapshot, UnindexedClientError> {
+        let (_, snapshot) = self
+            .subscribe(|token| {
+                vec![
+                    KrakenSubscribeParams::executions(token.to_string(), true, false),
+                    KrakenSubscribeParams::balances(token.to_string(), true),
+                ]
+            })
+            .await?;
+
+        let balances = snapshot
+            .balances
+            .into_iter()
+            .filter(|balance| assets.contains(&balance.asset))
+            .map(AssetBalance::from)
+            .collect();
+
+        let mut orders_by_instrument = snapshot
+            .executions
+            .iter()
+            .filter_map(|order| order.open_order(Self::EXCHANGE))
+            .filter(|order| instruments.contains(&order.key.instrument))
+            .into_group_map_by(|order| order.key.instrument.clone());
+
+        let instruments = instruments
+            .iter()
+            .map(|instrument| {
+                let orders = orders_by_instrument
+                    .remove(instrument)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|order| Order {
+                        key: order.key,
+                        side: order.side,
+                        price: order.price,
+                        quantity: order.quantity,
+                        kind: order.kind,
+                        time_in_force: order.time_in_force,
+                        state: OrderState::active(order.state),
+                    })
+                    .collect();
+
+                InstrumentAccountSnapshot::new(instrument.clone(), orders, None)
+            })
+            .collect();
+
+        Ok(UnindexedAccountSnapshot::new(
+            Self::EXCHANGE,
+            balances,
+            instruments,
+        ))
+    }
+
+    async fn account_stream(
+        &self,
+        _: &[AssetNameExchange],
+        _: &[InstrumentNameExchange],
+    ) -> Result<Self::AccountStream, UnindexedClientError> {
+        // Open orders snapshot seeds the cache that partial executions updates are merged into
+        let (websocket, snapshot) = self
+            .subscribe(|token| {
+                vec![
+                    KrakenSubscribeParams::executions(token.to_string(), true, false),
+                    KrakenSubscribeParams::balances(token.to_string(), false),
+                ]
+            })
+            .await
+            .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+
+        info!(
+            exchange = %Self::EXCHANGE,
+            "connected to Kraken private WebSocket"
+        );
+
+        let mut orders = KrakenOrders::default();
+        orders.insert_snapshot(snapshot.executions);
+
+        Ok(websocket
+            .filter_map(|message| {
+                std::future::ready(<WebSocketParser as StreamParser<KrakenWsMessage>>::parse(
+                    message,
+                ))
+            })
+            .scan(orders, |orders, message| {
+                std::future::ready(match message {
+                    Ok(KrakenWsMessage::Channel(message)) => {
+                        Some(into_account_events(orders, message))
+                    }
+                    Ok(KrakenWsMessage::Response(_)) => Some(vec![]),
+                    Err(error @ SocketError::Deserialise { .. }) => {
+                        warn!(
+                            ?error,
+                            "failed to deserialise Kraken private WebSocket message"
+                        );
+                        Some(vec![])
+                    }
+                    Err(error) => {
+                        warn!(?error, "Kraken private WebSocket failed, ending stream");
+                        None
+                    }
+                })
+            })
+            .flat_map(futures::stream::iter)
+            .boxed())
+    }
+
+    async fn cancel_order(
+        &self,
+        request: OrderRequestCancel<ExchangeId, &InstrumentNameExchange>,
+    ) -> UnindexedOrderResponseCancel {
+        let params = KrakenCancelOrderParams {
+            cl_ord_id: vec![request.key.cid.clone()],
+        };
+
+        let result = self
+            .order_method("cancel_order", params)
+            .await
+            .map(|response| {
+                let order_id = response
+                    .result
+                    .and_then(|result| result.order_id)
+                    .map(OrderId::new)
+                    .or(request.state.id)
+                    .unwrap_or_else(|| OrderId::new(request.key.cid.0.clone()));
+
+                Cancelled::new(order_id, response.time_out.unwrap_or_else(Utc::now))
+            })
+            .map_err(order_error);
+
+        UnindexedOrderResponseCancel {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            state: result,
+        }
+    }
+
+    async fn open_order(
+        &self,
+        request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
+    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+        let params = KrakenAddOrderParams::new(
+            request.key.instrument.clone(),
+            request.key.cid.clone(),
+            request.state.side,
+            request.state.kind,
+            request.state.time_in_force,
+            request.state.price,
+            request.state.quantity,
+        );
+
+        let state = match params {
+            _ if request.state.flags.is_reducing() => {
+                Err(OrderError::Rejected(ApiError::OrderRejected(
+                    "Kraken spot does not support reduce only or close position orders".to_string(),
+                )))
+            }
+            Some(params) => self
+                .order_method("add_order", params)
+                .await
+                .map(|response| {
+                    Open::new(
+                        OrderId::new(
+                            response
+                                .result
+                                .and_then(|result| result.order_id)
+                                .unwrap_or_default(),
+                        ),
+                        response.time_out.unwrap_or_else(Utc::now),
+                        Decimal::ZERO,
+                    )
+                })
+                .map_err(order_error),
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                "Kraken does not support {} orders with {}",
+                request.state.kind, request.state.time_in_force
+            )))),
+        };
+
+        Order {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            side: request.state.side,
+            price: request.state.price,
+            quantity: request.state.quantity,
+            kind: request.state.kind,
+            time_in_force: request.state.time_in_force,
+            state,
+        }
+    }
+
+    async fn fetch_balances(
+        &self,
+    ) -> Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError> {
+        let (_, snapshot) = self
+            .subscribe(|token| vec![KrakenSubscribeParams::balances(token.to_string(), true)])
+            .await?;
+
+        Ok(snapshot
+            .balances
+            .into_iter()
+            .map(AssetBalance::from)
+            .collect())
+    }
+
+    async fn fetch_open_orders(
+        &self,
+    ) -> Result<Vec<Order<ExchangeId, InstrumentNameExchange, Open>>, UnindexedClientError> {
+        let (_, snapshot) = self
+            .subscribe(|token| {
+                vec![KrakenSubscribeParams::executions(
+                    token.to_string(),
+                    true,
+                    false,
+                )]
+            })
+            .await?;
+
+        Ok(snapshot
+            .executions
+            .iter()
+            .filter_map(|order| order.open_order(Self::EXCHANGE))
+            .collect())
+    }
+
+    /// Fetch account trades since the provided time.
+    ///
+    /// The Kraken `executions` channel snapshot only contains the 50 most recent trades.
+    async fn fetch_trades(
+        &self,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let (_, snapshot) = self
+            .subscribe(|token| {
+                vec![KrakenSubscribeParams::executions(
+                    token.to_string(),
+                    false,
+                    true,
+                )]
+            })
+            .await?;
+
+        Ok(snapshot
+            .executions
+            .iter()
+            .filter_map(|execution| execution.trade(execution))
+            .filter(|trade| trade.time_exchange >= time_since)
+            .collect())
+    }
+}
+
+/// Map a private channel message into zero or more [`UnindexedAccountEvent`]s.
+fn into_account_events(
+    orders: &mut KrakenOrders,
+    message: KrakenChannelMessage,
+) -> Vec<UnindexedAccountEvent> {
+    match message {
+        KrakenChannelMessage::Executions {
+            kind: KrakenMessageKind::Snapshot,
+            data,
+        } => {
+            orders.insert_snapshot(data);
+            vec![]
+        }
+        KrakenChannelMessage::Executions {
+            kind: KrakenMessageKind::Update,
+            data,
+        } => data
+            .into_iter()
+            .flat_map(|update| orders.update(Kraken::EXCHANGE, update))
+            .collect(),
+        KrakenChannelMessage::Balances { data, .. } => data
+            .into_iter()
+            .map(|balance| {
+                UnindexedAccountEvent::new(
+                    Kraken::EXCHANGE,
+                    AccountEventKind::BalanceSnapshot(Snapshot(AssetBalance::from(balance))),
+                )
+            })
+            .collect(),
+        KrakenChannelMessage::Other => vec![],
+    }
+}
+
+/// Subscribe to the provided private channels, waiting for each `subscribe` response and
+/// any requested snapshots.
+async fn subscribe(
+    websocket: &mut WebSocket,
+    subscriptions: Vec<KrakenSubscribeParams>,
+) -> Result<KrakenWsSnapshot, UnindexedClientError> {
+    let mut pending_responses = subscriptions.len();
+    let mut pending_snapshots = subscriptions
+        .iter()
+        .filter(|params| {
+            [params.snap_orders, params.snap_trades, params.snapshot].contains(&Some(true))
+        })
+        .map(|params| params.channel)
+        .collect::<Vec<_>>();
+
+    for (req_id, params) in (1..).zip(subscriptions) {
+        websocket
+            .send(KrakenWsRequest::subscribe(req_id, params).to_ws_message())
+            .await
+            .map_err(|error| {
+                UnindexedClientError::Connectivity(ConnectivityError::Socket(error.to_string()))
+            })?;
+    }
+
+    let response = async {
+        let mut snapshot = KrakenWsSnapshot::default();
+
+        while pending_responses > 0 || !pending_snapshots.is_empty() {
+            let Some(message) = websocket.next().await else {
+                return Err(UnindexedClientError::Connectivity(
+                    ConnectivityError::Socket(
+                        "Kraken private WebSocket closed before subscribe response".to_string(),
+                    ),
+                ));
+            };
+
+            match <WebSocketParser as StreamParser<KrakenWsMessage>>::parse(message) {
+                Some(Ok(KrakenWsMessage::Response(response))) if response.method == "subscribe" => {
+                    if !response.success {
+                        return Err(api_error(response.error.as_deref().unwrap_or_default()));
+                    }
+                    pending_responses = pending_responses.saturating_sub(1);
+                }
+                Some(Ok(KrakenWsMessage::Channel(KrakenChannelMessage::Executions {
+                    kind: KrakenMessageKind::Snapshot,
+                    data,
+                }))) => {
+                    snapshot.executions = data;
+                    pending_snapshots.retain(|channel| *channel != "executions");
+                }
+                Some(Ok(KrakenWsMessage::Channel(KrakenChannelMessage::Balances {
+                    kind: KrakenMessageKind::Snapshot,
+                    data,
+                }))) => {
+                    snapshot.balances = data;
+                    pending_snapshots.retain(|channel| *channel != "balances");
+                }
+                Some(Err(error)) => {
+                    return Err(UnindexedClientError::Connectivity(
+                        ConnectivityError::Socket(error.to_string()),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(snapshot)
+    };
+
+    tokio::time::timeout(WS_RESPONSE_TIMEOUT, response)
+        .await
+        .unwrap_or(Err(UnindexedClientError::Connectivity(
+            ConnectivityError::Timeout,
+        )))
+}
@@ -0,0 +1,197 @@
+use crate::{
+    client::kraken::{
+        PING_INTERVAL,
+        account::{KrakenWsMessage, KrakenWsResponse},
+        request::KrakenWsRequest,
+    },
+    error::{ConnectivityError, UnindexedClientError},
+};
+use barter_integration::{
+    error::SocketError,
+    protocol::{
+        StreamParser,
+        websocket::{WebSocket, WebSocketParser, WsError, WsMessage},
+    },
+};
+use fnv::FnvHashMap;
+use futures::{SinkExt, StreamExt};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, warn};
+
+/// Handle to an authenticated Kraken WebSocket v2 connection used for order entry.
+///
+/// Each request is assigned a unique `req_id`, which Kraken echoes in the corresponding
+/// [`KrakenWsResponse`], allowing responses to be correlated with their request regardless of
+/// the order they arrive in.
+///
+/// Kraken authenticates each order entry request with the `token` used to establish the
+/// connection. The connection is owned by a background task that ends if the connection fails,
+/// or once every handle has been dropped.
+#[derive(Debug, Clone)]
+pub struct KrakenOrderEntry {
+    pub token: String,
+    tx: mpsc::UnboundedSender<KrakenOrderEntryRequest>,
+    request_id: Arc<AtomicU64>,
+}
+
+#[derive(Debug)]
+struct KrakenOrderEntryRequest {
+    request: KrakenWsRequest,
+    response_tx: oneshot::Sender<KrakenWsResponse>,
+}
+
+impl KrakenOrderEntry {
+    /// Spawn the order entry task for the provided [`WebSocket`], authenticating requests with
+    /// the provided `token`.
+    pub fn spawn(websocket: WebSocket, token: String) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(websocket, rx));
+
+        Self {
+            token,
+            tx,
+            request_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Returns true if the underlying connection has ended.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Send a `method` request authenticated with the connection `token`, waiting up to
+    /// `timeout` for the correlated [`KrakenWsResponse`].
+    pub async fn request(
+        &self,
+        method: &'static str,
+        mut params: serde_json::Value,
+        timeout: Duration,
+    ) -> Result<KrakenWsResponse, UnindexedClientError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        if let serde_json::Value::Object(params) = &mut params {
+            params.insert("token".to_string(), self.token.clone().into());
+        }
+
+        let request = KrakenWsRequest {
+            method,
+            params: Some(params),
+            req_id: Some(self.request_id.fetch_add(1, Ordering::Relaxed)),
+        };
+
+        self.tx
+            .send(KrakenOrderEntryRequest {
+                request,
+                response_tx,
+            })
+            .map_err(|_| connection_closed())?;
+
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(connection_closed()),
+            Err(_) => Err(UnindexedClientError::Connectivity(
+                ConnectivityError::Timeout,
+            )),
+        }
+    }
+}
+
+fn connection_closed() -> UnindexedClientError {
+    UnindexedClientError::Connectivity(ConnectivityError::Socket(
+        "Kraken order entry connection closed".to_string(),
+    ))
+}
+
+/// Run the order entry connection, forwarding requests and routing responses to the requester
+/// with the matching `req_id`.
+///
+/// In-flight requests are failed (by dropping their response channel) if the connection ends.
+async fn run(websocket: WebSocket, mut rx: mpsc::UnboundedReceiver<KrakenOrderEntryRequest>) {
+    let (mut sink, mut stream) = websocket.split();
+    let mut in_flight = FnvHashMap::<u64, oneshot::Sender<KrakenWsResponse>>::default();
+    let mut heartbeat = tokio::time::interval(PING_INTERVAL);
+
+    // First tick completes immediately, and the connection was only just established
+    heartbeat.tick().await;
+
+    loop {
+        tokio::select! {
+            request = rx.recv() => {
+                let Some(KrakenOrderEntryRequest { request, response_tx }) = request else {
+                    debug!("Kraken order entry handles dropped, closing connection");
+                    break;
+                };
+
+                if let Err(error) = sink.send(request.to_ws_message()).await {
+                    warn!(?error, "failed to send Kraken order entry request, closing connection");
+                    break;
+                }
+
+                if let Some(req_id) = request.req_id {
+                    in_flight.insert(req_id, response_tx);
+                }
+            }
+            message = stream.next() => {
+                let Some(message) = message else {
+                    warn!("Kraken order entry connection ended");
+                    break;
+                };
+
+                if !handle_message(message, &mut in_flight) {
+                    break;
+                }
+            }
+            _ = heartbeat.tick() => {
+                if let Err(error) = sink.send(KrakenWsRequest::ping().to_ws_message()).await {
+                    warn!(?error, "failed to send Kraken order entry heartbeat, closing connection");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Route an order entry connection message to the requester with the matching `req_id`,
+/// returning false if the connection has failed.
+fn handle_message(
+    message: Result<WsMessage, WsError>,
+    in_flight: &mut FnvHashMap<u64, oneshot::Sender<KrakenWsResponse>>,
+) -> bool {
+    match <WebSocketParser as StreamParser<KrakenWsMessage>>::parse(message) {
+        Some(Ok(KrakenWsMessage::Response(response))) => {
+            let Some(req_id) = response.req_id else {
+                // Eg/ heartbeat `pong` responses
+                return true;
+            };
+
+            match in_flight.remove(&req_id) {
+                Some(response_tx) => {
+                    // Requester may have timed out, so failure is ignored
+                    let _ = response_tx.send(response);
+                }
+                None => warn!(
+                    req_id,
+                    method = %response.method,
+                    "received Kraken order entry response for unknown request id"
+                ),
+            }
+        }
+        Some(Ok(KrakenWsMessage::Channel(_))) | None => {}
+        Some(Err(error @ SocketError::Deserialise { .. })) => {
+            warn!(?error, "failed to deserialise Kraken order entry message");
+        }
+        Some(Err(error)) => {
+            warn!(?error, "Kraken order entry connection failed");
+            return false;
+        }
+    }
+
+    true
+}
@@ -0,0 +1,273 @@
+use crate::order::{OrderKind, TimeInForce, id::ClientOrderId};
+use barter_instrument::{Side, instrument::name::InstrumentNameExchange};
+use barter_integration::protocol::{http::rest::RestRequest, websocket::WsMessage};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use serde::{Deserialize, Serialize, Serializer};
+use smol_str::SmolStr;
+use std::borrow::Cow;
+
+/// Kraken Http response envelope.
+///
+/// The [`KrakenParser`](super::KrakenParser) only yields a [`KrakenResponse`] if the `error`
+/// array is empty.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "error": [],
+///     "result": {}
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenResponse<T> {
+    pub result: T,
+}
+
+/// Kraken private Http request body containing only the mandatory `nonce`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct KrakenNonceBody {
+    pub nonce: u64,
+}
+
+/// Kraken signed request to fetch a token used to authenticate private WebSocket requests.
+///
+/// The token must be used to establish a connection within 15 minutes, but does not expire
+/// once a connection is established.
+///
+/// See docs: <https://docs.kraken.com/api/docs/rest-api/get-websockets-token>
+#[derive(Debug, Clone, Serialize)]
+pub struct GetWebSocketsToken {
+    pub body: KrakenNonceBody,
+}
+
+impl RestRequest for GetWebSocketsToken {
+    type Response = KrakenResponse<KrakenWebSocketsToken>;
+    type QueryParams = ();
+    type Body = KrakenNonceBody;
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/0/private/GetWebSocketsToken")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::POST
+    }
+
+    fn body(&self) -> Option<&Self::Body> {
+        Some(&self.body)
+    }
+}
+
+/// Kraken private WebSocket authentication token.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "token": "1Dwc4lzSwNWOAwkMdqhssNNFhs1ed606d1WcF3XfEMw",
+///     "expires": 900
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenWebSocketsToken {
+    pub token: String,
+}
+
+/// Kraken WebSocket v2 request.
+///
+/// Requests with a `req_id` are responded to with a
+/// [`KrakenWsResponse`](super::account::KrakenWsResponse) containing the same `req_id`.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/add_order>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KrakenWsRequest {
+    pub method: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub req_id: Option<u64>,
+}
+
+impl KrakenWsRequest {
+    /// Application level heartbeat, used to keep connections without subscriptions alive.
+    pub fn ping() -> Self {
+        Self {
+            method: "ping",
+            params: None,
+            req_id: None,
+        }
+    }
+
+    /// Subscribe to the private channel described by the provided [`KrakenSubscribeParams`].
+    pub fn subscribe(req_id: u64, params: KrakenSubscribeParams) -> Self {
+        Self {
+            method: "subscribe",
+            params: Some(
+                serde_json::to_value(params)
+                    .expect("KrakenSubscribeParams serialisation is infallible"),
+            ),
+            req_id: Some(req_id),
+        }
+    }
+
+    /// Serialise into a text [`WsMessage`].
+    pub fn to_ws_message(&self) -> WsMessage {
+        WsMessage::text(
+            serde_json::to_string(self).expect("KrakenWsRequest serialisation is infallible"),
+        )
+    }
+}
+
+/// Kraken WebSocket v2 private channel `subscribe` parameters.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/executions>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KrakenSubscribeParams {
+    pub channel: &'static str,
+    pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snap_orders: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snap_trades: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<bool>,
+}
+
+impl KrakenSubscribeParams {
+    /// Subscribe to the `executions` channel, optionally requesting a snapshot of open orders
+    /// and the most recent 50 trades.
+    pub fn executions(token: String, snap_orders: bool, snap_trades: bool) -> Self {
+        Self {
+            channel: "executions",
+            token,
+            snap_orders: Some(snap_orders),
+            snap_trades: Some(snap_trades),
+            snapshot: None,
+        }
+    }
+
+    /// Subscribe to the `balances` channel, optionally requesting a snapshot of all balances.
+    pub fn balances(token: String, snapshot: bool) -> Self {
+        Self {
+            channel: "balances",
+            token,
+            snap_orders: None,
+            snap_trades: None,
+            snapshot: Some(snapshot),
+        }
+    }
+}
+
+/// Kraken WebSocket v2 `add_order` parameters.
+///
+/// Kraken expects quantities & prices as JSON numbers. The `token` is added by the
+/// [`KrakenOrderEntry`](super::order_entry::KrakenOrderEntry) connection.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/add_order>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KrakenAddOrderParams {
+    pub order_type: &'static str,
+    pub side: &'static str,
+    #[serde(serialize_with = "ser_decimal_as_f64")]
+    pub order_qty: Decimal,
+    pub symbol: InstrumentNameExchange,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "ser_option_decimal_as_f64"
+    )]
+    pub limit_price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<&'static str>,
+    pub post_only: bool,
+    pub cl_ord_id: ClientOrderId,
+}
+
+impl KrakenAddOrderParams {
+    /// Construct [`KrakenAddOrderParams`] from the provided order fields, returning `None` if
+    /// the [`OrderKind`] and [`TimeInForce`] combination is not supported by Kraken.
+    pub fn new(
+        symbol: InstrumentNameExchange,
+        cid: ClientOrderId,
+        side: Side,
+        kind: OrderKind,
+        time_in_force: TimeInForce,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Option<Self> {
+        let (order_type, limit_price, time_in_force, post_only) = match (kind, time_in_force) {
+            (OrderKind::Market, _) => ("market", None, None, false),
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only }) => {
+                ("limit", Some(price), Some("gtc"), post_only)
+            }
+            (OrderKind::Limit, TimeInForce::ImmediateOrCancel) => {
+                ("limit", Some(price), Some("ioc"), false)
+            }
+            (OrderKind::Limit, TimeInForce::FillOrKill | TimeInForce::GoodUntilEndOfDay) => {
+                return None;
+            }
+            // Conditional orders are not supported
+            _ => return None,
+        };
+
+        Some(Self {
+            order_type,
+            side: match side {
+                Side::Buy => "buy",
+                Side::Sell => "sell",
+            },
+            order_qty: quantity,
+            symbol,
+            limit_price,
+            time_in_force,
+            post_only,
+            cl_ord_id: cid,
+        })
+    }
+}
+
+/// Kraken WebSocket v2 `cancel_order` parameters. The `token` is added by the
+/// [`KrakenOrderEntry`](super::order_entry::KrakenOrderEntry) connection.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/cancel_order>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KrakenCancelOrderParams {
+    pub cl_ord_id: Vec<ClientOrderId>,
+}
+
+/// Kraken WebSocket v2 `add_order` & `cancel_order` result.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "order_id": "OPS23M-VS41G-DDE5Z2",
+///     "cl_ord_id": "7c5b2d6a"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenOrderResult {
+    #[serde(default)]
+    pub order_id: Option<SmolStr>,
+    #[serde(default)]
+    pub cl_ord_id: Option<SmolStr>,
+}
+
+fn ser_decimal_as_f64<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value.to_f64() {
+        Some(value) => serializer.serialize_f64(value),
+        None => Err(serde::ser::Error::custom(format!(
+            "{value} cannot be represented as f64"
+        ))),
+    }
+}
+
+fn ser_option_decimal_as_f64<S>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => ser_decimal_as_f64(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
@@ -3,12 +3,20 @@ use crate::{
     balance::AssetBalance,
     client::ExecutionClient,
     error::{ConnectivityError, UnindexedClientError, UnindexedOrderError},
-    exchange::mock::request::MockExchangeRequest,
+    exchange::mock::{
+        expiry::ExpirySettlement, faults::FaultConfig, fees::FeeSchedule, latency::LatencyConfig,
+        margin::MarginConfig, request::MockExchangeRequest,
+    },
     order::{
         Order, OrderEvent, OrderKey,
-        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        group::OrderGroup,
+        request::{
+            OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseAmend,
+            UnindexedOrderResponseCancel,
+        },
         state::Open,
     },
+    slippage::SlippageConfig,
     trade::Trade,
 };
 use barter_instrument::{
@@ -18,10 +26,13 @@ use barter_instrument::{
 };
 use chrono::{DateTime, Utc};
 use derive_more::Constructor;
-use futures::stream::BoxStream;
+use futures::stream::{BoxStream, FuturesOrdered};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc, oneshot,
+};
 use tokio_stream::{StreamExt, wrappers::BroadcastStream};
 use tracing::error;
 
@@ -31,8 +42,24 @@ use tracing::error;
 pub struct MockExecutionConfig {
     pub mocked_exchange: ExchangeId,
     pub initial_state: UnindexedAccountSnapshot,
+    /// Constant round trip latency, used if no `latency` is provided.
     pub latency_ms: u64,
+    #[serde(default)]
+    pub latency: Option<LatencyConfig>,
+    /// Flat quote asset fee rate charged on every fill, used if no `fee_schedule` is provided.
     pub fees_percent: Decimal,
+    #[serde(default)]
+    pub fee_schedule: Option<FeeSchedule>,
+    #[serde(default)]
+    pub slippage: SlippageConfig,
+    #[serde(default)]
+    pub faults: FaultConfig,
+    /// Margin configuration used for `Perpetual` and `Future` instruments.
+    #[serde(default)]
+    pub margin: MarginConfig,
+    /// Settlement of `Future` positions still open at contract expiry.
+    #[serde(default)]
+    pub expiry_settlement: ExpirySettlement,
 }
 
 #[derive(Debug, Constructor)]
@@ -41,6 +68,7 @@ pub struct MockExecutionClientConfig<FnTime> {
     pub clock: FnTime,
     pub request_tx: mpsc::UnboundedSender<MockExchangeRequest>,
     pub event_rx: broadcast::Receiver<UnindexedAccountEvent>,
+    pub disconnect_rx: broadcast::Receiver<()>,
 }
 
 impl<FnTime> Clone for MockExecutionClientConfig<FnTime>
@@ -53,6 +81,7 @@ where
             clock: self.clock.clone(),
             request_tx: self.request_tx.clone(),
             event_rx: self.event_rx.resubscribe(),
+            disconnect_rx: self.disconnect_rx.resubscribe(),
         }
     }
 }
@@ -63,6 +92,7 @@ pub struct MockExecution<FnTime> {
     pub clock: FnTime,
     pub request_tx: mpsc::UnboundedSender<MockExchangeRequest>,
     pub event_rx: broadcast::Receiver<UnindexedAccountEvent>,
+    pub disconnect_rx: broadcast::Receiver<()>,
 }
 
 impl<FnTime> Clone for MockExecution<FnTime>
@@ -75,6 +105,7 @@ where
             clock: self.clock.clone(),
             request_tx: self.request_tx.clone(),
             event_rx: self.event_rx.resubscribe(),
+            disconnect_rx: self.disconnect_rx.resubscribe(),
         }
     }
 }
@@ -102,6 +133,7 @@ where
             clock: config.clock,
             request_tx: config.request_tx,
             event_rx: config.event_rx,
+            disconnect_rx: config.disconnect_rx,
         }
     }
 
@@ -135,7 +167,15 @@ where
         _: &[AssetNameExchange],
         _: &[InstrumentNameExchange],
     ) -> Result<Self::AccountStream, UnindexedClientError> {
-        Ok(futures::StreamExt::boxed(
+        // MockExchange may inject a Fault::Disconnect to drop the AccountStream
+        let mut disconnect_rx = self.disconnect_rx.resubscribe();
+        let disconnected = async move {
+            if let Err(RecvError::Closed) = disconnect_rx.recv().await {
+                std::future::pending::<()>().await
+            }
+        };
+
+        let stream =
             BroadcastStream::new(self.event_rx.resubscribe()).map_while(|result| match result {
                 Ok(event) => Some(event),
                 Err(error) => {
@@ -145,14 +185,18 @@ where
                     );
                     None
                 }
-            }),
-        ))
+            });
+
+        Ok(futures::StreamExt::boxed(futures::StreamExt::take_until(
+            stream,
+            disconnected,
+        )))
     }
 
-    async fn cancel_order(
+    fn cancel_order(
         &self,
         request: OrderRequestCancel<ExchangeId, &InstrumentNameExchange>,
-    ) -> UnindexedOrderResponseCancel {
+    ) -> impl Future<Output = UnindexedOrderResponseCancel> + Send {
         let (response_tx, response_rx) = oneshot::channel();
 
         let key = OrderKey {
@@ -162,52 +206,60 @@ where
             cid: request.key.cid.clone(),
         };
 
-        if self
+        // Send eagerly so requests are queued at the MockExchange in the order they are issued
+        let sent = self
             .request_tx
             .send(MockExchangeRequest::cancel_order(
                 self.time_request(),
                 response_tx,
                 into_owned_request(request),
             ))
-            .is_err()
-        {
-            return UnindexedOrderResponseCancel {
-                key,
-                state: Err(UnindexedOrderError::Connectivity(
-                    ConnectivityError::ExchangeOffline(self.mocked_exchange),
-                )),
+            .is_ok();
+
+        let mocked_exchange = self.mocked_exchange;
+        async move {
+            let response = match sent {
+                true => response_rx.await.ok(),
+                false => None,
             };
-        }
 
-        match response_rx.await {
-            Ok(response) => response,
-            Err(_) => UnindexedOrderResponseCancel {
+            response.unwrap_or(UnindexedOrderResponseCancel {
                 key,
                 state: Err(UnindexedOrderError::Connectivity(
-                    ConnectivityError::ExchangeOffline(self.mocked_exchange),
+                    ConnectivityError::ExchangeOffline(mocked_exchange),
                 )),
-            },
+            })
         }
     }
 
-    async fn open_order(
+    fn open_order(
         &self,
         request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
-    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+    ) -> impl Future<
+        Output = Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>,
+    > + Send {
         let (response_tx, response_rx) = oneshot::channel();
 
         let request = into_owned_request(request);
 
-        if self
+        // Send eagerly so requests are queued at the MockExchange in the order they are issued
+        let sent = self
             .request_tx
             .send(MockExchangeRequest::open_order(
                 self.time_request(),
                 response_tx,
                 request.clone(),
             ))
-            .is_err()
-        {
-            return Order {
+            .is_ok();
+
+        let mocked_exchange = self.mocked_exchange;
+        async move {
+            let response = match sent {
+                true => response_rx.await.ok(),
+                false => None,
+            };
+
+            response.unwrap_or(Order {
                 key: request.key,
                 side: request.state.side,
                 price: request.state.price,
@@ -215,14 +267,38 @@ where
                 kind: request.state.kind,
                 time_in_force: request.state.time_in_force,
                 state: Err(UnindexedOrderError::Connectivity(
-                    ConnectivityError::ExchangeOffline(self.mocked_exchange),
+                    ConnectivityError::ExchangeOffline(mocked_exchange),
                 )),
-            };
+            })
         }
+    }
 
-        match response_rx.await {
-            Ok(response) => response,
-            Err(_) => Order {
+    fn amend_order(
+        &self,
+        request: OrderRequestAmend<ExchangeId, &InstrumentNameExchange>,
+    ) -> impl Future<Output = UnindexedOrderResponseAmend> + Send {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let request = into_owned_request(request);
+
+        // Send eagerly so requests are queued at the MockExchange in the order they are issued
+        let sent = self
+            .request_tx
+            .send(MockExchangeRequest::amend_order(
+                self.time_request(),
+                response_tx,
+                request.clone(),
+            ))
+            .is_ok();
+
+        let mocked_exchange = self.mocked_exchange;
+        async move {
+            let response = match sent {
+                true => response_rx.await.ok(),
+                false => None,
+            };
+
+            response.unwrap_or(Order {
                 key: request.key,
                 side: request.state.side,
                 price: request.state.price,
@@ -230,12 +306,27 @@ where
                 kind: request.state.kind,
                 time_in_force: request.state.time_in_force,
                 state: Err(UnindexedOrderError::Connectivity(
-                    ConnectivityError::ExchangeOffline(self.mocked_exchange),
+                    ConnectivityError::ExchangeOffline(mocked_exchange),
                 )),
-            },
+            })
         }
     }
 
+    fn open_order_group(
+        &self,
+        group: OrderGroup<ExchangeId, &InstrumentNameExchange>,
+    ) -> impl Future<
+        Output = Vec<Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>>,
+    > + Send {
+        // Open initial orders eagerly so they are queued at the MockExchange immediately
+        let opens = group
+            .orders_initial()
+            .map(|request| self.open_order(request.clone()))
+            .collect::<FuturesOrdered<_>>();
+
+        futures::StreamExt::collect(opens)
+    }
+
     async fn fetch_balances(
         &self,
     ) -> Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError> {
@@ -1,10 +1,17 @@
 use crate::{
     UnindexedAccountEvent, UnindexedAccountSnapshot,
     balance::AssetBalance,
-    error::{UnindexedClientError, UnindexedOrderError},
+    error::{
+        ApiError, ClientError, ConnectivityError, OrderError, UnindexedClientError,
+        UnindexedOrderError,
+    },
     order::{
-        Order,
-        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        Order, OrderEvent, OrderFlags, OrderKey,
+        group::OrderGroup,
+        request::{
+            OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, RequestCancel, RequestOpen,
+            UnindexedOrderResponseAmend, UnindexedOrderResponseCancel,
+        },
         state::Open,
     },
     trade::Trade,
@@ -15,11 +22,18 @@ use barter_instrument::{
     instrument::name::InstrumentNameExchange,
 };
 use chrono::{DateTime, Utc};
-use futures::Stream;
+use futures::{Stream, StreamExt};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer};
 use std::future::Future;
+use tracing::warn;
 
-mod binance;
+pub mod binance;
+pub mod bybit;
+pub mod coinbase;
+pub mod kraken;
 pub mod mock;
+pub mod okx;
 
 pub trait ExecutionClient
 where
@@ -77,6 +91,108 @@ where
         )
     }
 
+    /// Amend the price and/or quantity of an open order.
+    ///
+    /// Defaults to a cancel-replace, which loses the queue priority of the original order.
+    /// Clients of exchanges that natively support amending open orders should override this.
+    ///
+    /// If the cancel succeeds but the replacement open fails, an
+    /// [`ApiError::OrderReplaceFailed`] is returned, since the original order is no longer open.
+    fn amend_order(
+        &self,
+        request: OrderRequestAmend<ExchangeId, &InstrumentNameExchange>,
+    ) -> impl Future<Output = UnindexedOrderResponseAmend> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let OrderRequestAmend { key, state: amend } = request;
+
+            let cancel = OrderRequestCancel::new(key.clone(), RequestCancel::new(amend.id.clone()));
+            let state = match amend.validate_quantity() {
+                Err(error) => Err(OrderError::Rejected(error)),
+                Ok(()) => match self.cancel_order(cancel).await.state {
+                    Ok(_cancelled) => {
+                        let open = OrderEvent::new(
+                            key.clone(),
+                            RequestOpen::new(
+                                amend.side,
+                                amend.price,
+                                amend.quantity_remaining(),
+                                amend.kind,
+                                amend.time_in_force,
+                                OrderFlags::default(),
+                            ),
+                        );
+
+                        self.open_order(open)
+                            .await
+                            .state
+                            .map(|open| Open {
+                                filled_quantity: amend.filled_quantity + open.filled_quantity,
+                                ..open
+                            })
+                            .map_err(|error| {
+                                OrderError::Rejected(ApiError::OrderReplaceFailed(
+                                    error.to_string(),
+                                ))
+                            })
+                    }
+                    Err(error) => Err(error),
+                },
+            };
+
+            Order {
+                key: OrderKey::new(key.exchange, key.instrument.clone(), key.strategy, key.cid),
+                side: amend.side,
+                price: amend.price,
+                quantity: amend.quantity,
+                kind: amend.kind,
+                time_in_force: amend.time_in_force,
+                state,
+            }
+        }
+    }
+
+    /// Returns true if the exchange natively supports the provided [`OrderGroup`], in which case
+    /// [`Self::open_order_group`] opens it as a single exchange managed order group.
+    ///
+    /// Defaults to false, since few exchanges support contingent order groups.
+    fn supports_order_group<ExchangeKey, InstrumentKey>(
+        &self,
+        _: &OrderGroup<ExchangeKey, InstrumentKey>,
+    ) -> bool
+    where
+        InstrumentKey: PartialEq,
+    {
+        false
+    }
+
+    /// Open an [`OrderGroup`], returning a response for each order opened.
+    ///
+    /// Defaults to individually opening the [`OrderGroup::orders_initial`], leaving the
+    /// contingent behaviour of the group to be emulated client-side. Clients of exchanges that
+    /// natively support order groups should override this.
+    fn open_order_group(
+        &self,
+        group: OrderGroup<ExchangeId, &InstrumentNameExchange>,
+    ) -> impl Future<
+        Output = Vec<Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>>,
+    > + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            futures::stream::FuturesUnordered::from_iter(
+                group
+                    .orders_initial()
+                    .map(|request| self.open_order(request.clone())),
+            )
+            .collect()
+            .await
+        }
+    }
+
     fn fetch_balances(
         &self,
     ) -> impl Future<Output = Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError>>;
@@ -92,3 +208,62 @@ where
         time_since: DateTime<Utc>,
     ) -> impl Future<Output = Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError>>;
 }
+
+/// Calculate the quote asset equivalent of the `commission` charged in `commission_asset` for a
+/// fill of the provided symbol at `price`.
+///
+/// Exchanges generally charge fees in the base or quote asset of the symbol, unless a third asset
+/// (eg/ BNB) is used to pay for fees. Fees paid in a third asset cannot be converted without its
+/// price, so are logged and recorded as zero.
+pub fn fees_quote(
+    symbol: &InstrumentNameExchange,
+    commission_asset: &AssetNameExchange,
+    commission: Decimal,
+    price: Decimal,
+) -> Decimal {
+    let symbol = symbol.name().as_str();
+    let asset = commission_asset.name().as_str();
+
+    if commission.is_zero() || asset.is_empty() {
+        Decimal::ZERO
+    } else if symbol.ends_with(asset) {
+        commission
+    } else if symbol.starts_with(asset) {
+        commission * price
+    } else {
+        warn!(
+            %symbol,
+            %asset,
+            %commission,
+            "fees paid in third asset cannot be converted to quote, recording zero"
+        );
+        Decimal::ZERO
+    }
+}
+
+/// Convert a [`ClientError`] produced while actioning an order request into an [`OrderError`].
+pub fn order_error<AssetKey, InstrumentKey>(
+    error: ClientError<AssetKey, InstrumentKey>,
+) -> OrderError<AssetKey, InstrumentKey> {
+    match error {
+        ClientError::Connectivity(error) => OrderError::Connectivity(error),
+        ClientError::Api(error) => OrderError::Rejected(error),
+        ClientError::AccountSnapshot(error) | ClientError::AccountStream(error) => {
+            OrderError::Connectivity(ConnectivityError::Socket(error))
+        }
+    }
+}
+
+/// Deserialize a decimal `String`, which exchanges such as Bybit & OKX may send empty, as a
+/// [`Decimal`] (defaulting to zero).
+pub fn de_decimal_or_zero<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+    if value.is_empty() {
+        Ok(Decimal::ZERO)
+    } else {
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
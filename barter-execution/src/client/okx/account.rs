@@ -0,0 +1,370 @@
+use crate::{
+    AccountEventKind, UnindexedAccountEvent,
+    balance::AssetBalance,
+    client::okx::{
+        OkxTradeMode, parse_order_kind,
+        request::{OkxAccount, OkxOrder},
+    },
+    order::{
+        Order, OrderFlags, OrderKey, OrderKind, OrderSnapshot, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::{Cancelled, Open, OrderState},
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::{protocol::websocket::WsMessage, snapshot::Snapshot};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use tracing::warn;
+
+/// OKX V5 private WebSocket request.
+///
+/// Requests with an `id` (eg/ `order` & `cancel-order`) are responded to with an
+/// [`OkxWsOpResponse`] containing the same `id`.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-websocket>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OkxWsRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub op: &'static str,
+    pub args: Vec<serde_json::Value>,
+}
+
+impl OkxWsRequest {
+    /// Authenticate the private connection with a signature of
+    /// `{timestamp}GET/users/self/verify`.
+    pub fn login(api_key: &str, passphrase: &str, timestamp: i64, sign: String) -> Self {
+        Self {
+            id: None,
+            op: "login",
+            args: vec![serde_json::json!({
+                "apiKey": api_key,
+                "passphrase": passphrase,
+                "timestamp": timestamp.to_string(),
+                "sign": sign,
+            })],
+        }
+    }
+
+    /// Subscribe to the private `orders` channel for all instrument types, and the `account`
+    /// channel.
+    pub fn subscribe() -> Self {
+        Self {
+            id: None,
+            op: "subscribe",
+            args: vec![
+                serde_json::json!({ "channel": "orders", "instType": "ANY" }),
+                serde_json::json!({ "channel": "account" }),
+            ],
+        }
+    }
+
+    /// Serialise into a text [`WsMessage`].
+    pub fn to_ws_message(&self) -> WsMessage {
+        WsMessage::text(
+            serde_json::to_string(self).expect("OkxWsRequest serialisation is infallible"),
+        )
+    }
+}
+
+/// OKX V5 private WebSocket `order` request arguments.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-place-order>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOrderArgs {
+    pub inst_id: InstrumentNameExchange,
+    pub td_mode: OkxTradeMode,
+    pub cl_ord_id: ClientOrderId,
+    pub side: &'static str,
+    pub ord_type: &'static str,
+    pub sz: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub px: Option<Decimal>,
+    /// Spot market orders are sized in the quote asset by default for buys, so are explicitly
+    /// sized in the base asset to match the
+    /// [`OrderRequestOpen`](crate::order::request::OrderRequestOpen) quantity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tgt_ccy: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+}
+
+impl OkxOrderArgs {
+    /// Construct [`OkxOrderArgs`] from the provided order fields, returning `None` if the
+    /// [`OrderKind`] and [`TimeInForce`] combination is not supported by OKX.
+    ///
+    /// OKX has no close position flag for regular orders, so `close_position` orders are sent as
+    /// reduce only. Cash trade mode orders cannot reduce a position, so reducing [`OrderFlags`]
+    /// return `None`.
+    pub fn new(
+        td_mode: OkxTradeMode,
+        inst_id: InstrumentNameExchange,
+        cid: ClientOrderId,
+        side: Side,
+        kind: OrderKind,
+        time_in_force: TimeInForce,
+        price: Decimal,
+        quantity: Decimal,
+        flags: OrderFlags,
+    ) -> Option<Self> {
+        if td_mode == OkxTradeMode::Cash && flags.is_reducing() {
+            return None;
+        }
+
+        let (ord_type, px) = match (kind, time_in_force) {
+            (OrderKind::Market, _) => ("market", None),
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only: true }) => {
+                ("post_only", Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::GoodUntilCancelled { post_only: false }) => {
+                ("limit", Some(price))
+            }
+            (OrderKind::Limit, TimeInForce::ImmediateOrCancel) => ("ioc", Some(price)),
+            (OrderKind::Limit, TimeInForce::FillOrKill) => ("fok", Some(price)),
+            (OrderKind::Limit, TimeInForce::GoodUntilEndOfDay) => return None,
+            // Conditional orders are placed via the separate OKX algo order API
+            _ => return None,
+        };
+
+        let tgt_ccy =
+            (td_mode == OkxTradeMode::Cash && kind == OrderKind::Market).then_some("base_ccy");
+
+        let reduce_only = flags.is_reducing().then_some(true);
+
+        Some(Self {
+            inst_id,
+            td_mode,
+            cl_ord_id: cid,
+            side: match side {
+                Side::Buy => "buy",
+                Side::Sell => "sell",
+            },
+            ord_type,
+            sz: quantity,
+            px,
+            tgt_ccy,
+            reduce_only,
+        })
+    }
+}
+
+/// OKX V5 private WebSocket `cancel-order` request arguments.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-cancel-order>
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxCancelArgs {
+    pub inst_id: InstrumentNameExchange,
+    pub cl_ord_id: ClientOrderId,
+}
+
+/// OKX V5 private WebSocket event, sent in response to `login` & `subscribe` requests, or if a
+/// request fails.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "event": "login",
+///     "code": "0",
+///     "msg": "",
+///     "connId": "a4d3ae55"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OkxWsEvent {
+    pub event: SmolStr,
+    #[serde(default)]
+    pub code: SmolStr,
+    #[serde(default)]
+    pub msg: String,
+}
+
+/// OKX V5 private WebSocket `op` response, correlated to the request via the `id`.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "id": "1512",
+///     "op": "order",
+///     "data": [
+///         {
+///             "clOrdId": "",
+///             "ordId": "12345689",
+///             "tag": "",
+///             "ts": "1695190491421",
+///             "sCode": "0",
+///             "sMsg": ""
+///         }
+///     ],
+///     "code": "0",
+///     "msg": "",
+///     "inTime": "1695190491421339",
+///     "outTime": "1695190491423240"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OkxWsOpResponse {
+    pub id: String,
+    pub op: SmolStr,
+    pub code: SmolStr,
+    #[serde(default)]
+    pub msg: String,
+    #[serde(default)]
+    pub data: Vec<OkxOpResult>,
+}
+
+/// OKX V5 `order` & `cancel-order` result.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOpResult {
+    pub ord_id: SmolStr,
+    #[serde(default)]
+    pub cl_ord_id: SmolStr,
+    #[serde(
+        default = "Utc::now",
+        deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc"
+    )]
+    pub ts: DateTime<Utc>,
+    pub s_code: SmolStr,
+    #[serde(default)]
+    pub s_msg: String,
+}
+
+/// OKX V5 private WebSocket message.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum OkxPrivateMessage {
+    Push(OkxPush),
+    OpResponse(OkxWsOpResponse),
+    Event(OkxWsEvent),
+}
+
+/// OKX V5 private WebSocket channel push.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "arg": {
+///         "channel": "orders",
+///         "instType": "ANY",
+///         "uid": "614488474791936"
+///     },
+///     "data": []
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OkxPush {
+    pub arg: OkxPushArg,
+    pub data: OkxPushData,
+}
+
+/// OKX V5 private WebSocket channel push argument.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OkxPushArg {
+    pub channel: SmolStr,
+}
+
+/// OKX V5 private WebSocket `orders` or `account` channel push data.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum OkxPushData {
+    Orders(Vec<OkxOrder>),
+    Account(Vec<OkxAccount>),
+}
+
+impl OkxPush {
+    /// Map the `orders` or `account` channel [`OkxPush`] into zero or more
+    /// [`UnindexedAccountEvent`]s.
+    ///
+    /// `orders` updates caused by a fill generate a `Trade` followed by an `OrderSnapshot`.
+    pub fn into_account_events(self, exchange: ExchangeId) -> Vec<UnindexedAccountEvent> {
+        match self.data {
+            OkxPushData::Orders(orders) => orders
+                .into_iter()
+                .flat_map(|order| {
+                    let trade = order
+                        .fill()
+                        .map(|trade| UnindexedAccountEvent::new(exchange, trade));
+
+                    let snapshot = order_snapshot(exchange, order).map(|order| {
+                        UnindexedAccountEvent::new(
+                            exchange,
+                            AccountEventKind::OrderSnapshot(Snapshot(order)),
+                        )
+                    });
+
+                    trade.into_iter().chain(snapshot)
+                })
+                .collect(),
+            OkxPushData::Account(accounts) => accounts
+                .into_iter()
+                .flat_map(|account| account.details)
+                .map(|balance| {
+                    UnindexedAccountEvent::new(
+                        exchange,
+                        AccountEventKind::BalanceSnapshot(Snapshot(AssetBalance::from(balance))),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Map an [`OkxOrder`] update into an [`Order`] snapshot, returning `None` if the order type
+/// or state is not supported.
+fn order_snapshot(
+    exchange: ExchangeId,
+    order: OkxOrder,
+) -> Option<OrderSnapshot<ExchangeId, AssetNameExchange, InstrumentNameExchange>> {
+    let Some((kind, time_in_force)) = parse_order_kind(&order.ord_type) else {
+        warn!(
+            %exchange,
+            instrument = %order.inst_id,
+            order_id = %order.ord_id,
+            kind = %order.ord_type,
+            "ignoring OKX order update for unsupported order type"
+        );
+        return None;
+    };
+
+    let order_id = OrderId::new(order.ord_id.clone());
+    let state = match order.state.as_str() {
+        "live" | "partially_filled" => {
+            OrderState::active(Open::new(order_id, order.u_time, order.acc_fill_sz))
+        }
+        "filled" => OrderState::fully_filled(),
+        "canceled" | "mmp_canceled" => OrderState::inactive(Cancelled::new(order_id, order.u_time)),
+        state => {
+            warn!(
+                %exchange,
+                instrument = %order.inst_id,
+                order_id = %order.ord_id,
+                %state,
+                "ignoring OKX order update with unknown order state"
+            );
+            return None;
+        }
+    };
+
+    Some(Order {
+        key: OrderKey::new(
+            exchange,
+            order.inst_id,
+            StrategyId::unknown(),
+            ClientOrderId::new(order.cl_ord_id),
+        ),
+        side: order.side,
+        price: order.px,
+        quantity: order.sz,
+        kind,
+        time_in_force,
+        state,
+    })
+}
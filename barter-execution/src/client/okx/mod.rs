@@ -0,0 +1,896 @@
+use crate::{
+    InstrumentAccountSnapshot, UnindexedAccountEvent, UnindexedAccountSnapshot,
+    balance::AssetBalance,
+    client::{
+        ExecutionClient,
+        okx::{
+            account::{OkxCancelArgs, OkxOpResult, OkxOrderArgs, OkxPrivateMessage, OkxWsRequest},
+            order_entry::OkxOrderEntry,
+            request::{
+                FetchBalance, FetchFills, FetchFillsParams, FetchPendingOrders,
+                FetchPendingOrdersParams,
+            },
+        },
+        order_error,
+    },
+    error::{
+        ApiError, ClientError, ConnectivityError, OrderError, UnindexedClientError,
+        UnindexedOrderError,
+    },
+    order::{
+        Order, OrderKey, OrderKind, TimeInForce,
+        id::OrderId,
+        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        state::{Cancelled, Open, OrderState},
+    },
+    trade::Trade,
+};
+use barter_instrument::{
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::{
+    error::SocketError,
+    protocol::{
+        StreamParser,
+        http::{
+            HttpParser,
+            private::{
+                RequestSigner, Signer,
+                encoder::{Base64Encoder, Encoder},
+            },
+            rest::{RestRequest, client::RestClient},
+        },
+        websocket::{WebSocket, WebSocketParser, WsMessage, WsSink, connect},
+    },
+};
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt, stream::BoxStream};
+use hmac::{Hmac, Mac};
+use itertools::Itertools;
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::Sha256;
+use smol_str::SmolStr;
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{sync::Mutex, task::JoinHandle};
+use tracing::{error, info, warn};
+
+/// OKX V5 Http request and response models.
+pub mod request;
+
+/// OKX V5 private WebSocket request, response and channel models.
+pub mod account;
+
+/// OKX V5 private WebSocket order entry, correlating `op` responses to requests by `id`.
+pub mod order_entry;
+
+/// Http header containing the OKX API key.
+pub const HEADER_OKX_ACCESS_KEY: &str = "OK-ACCESS-KEY";
+
+/// Http header containing the OKX request signature.
+pub const HEADER_OKX_ACCESS_SIGN: &str = "OK-ACCESS-SIGN";
+
+/// Http header containing the OKX request timestamp.
+pub const HEADER_OKX_ACCESS_TIMESTAMP: &str = "OK-ACCESS-TIMESTAMP";
+
+/// Http header containing the OKX API key passphrase.
+pub const HEADER_OKX_ACCESS_PASSPHRASE: &str = "OK-ACCESS-PASSPHRASE";
+
+/// Http header routing requests to the OKX demo trading environment.
+pub const HEADER_OKX_SIMULATED_TRADING: &str = "x-simulated-trading";
+
+/// OKX closes private WebSocket connections that are idle for 30 seconds, so a text `ping` is
+/// sent at this interval.
+const PING_INTERVAL: Duration = Duration::from_secs(25);
+
+/// OKX private WebSocket application level heartbeat request.
+const OKX_PING: &str = "ping";
+
+/// OKX private WebSocket application level heartbeat response.
+const OKX_PONG: &str = "pong";
+
+/// Maximum time to wait for a private WebSocket `login` or `subscribe` response.
+const WS_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum number of orders or fills OKX returns per page.
+const FETCH_LIMIT: u8 = 100;
+
+/// OKX trade mode used when opening orders.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-place-order>
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OkxTradeMode {
+    /// Non-margin spot trading.
+    #[default]
+    Cash,
+    /// Cross margin.
+    Cross,
+    /// Isolated margin.
+    Isolated,
+}
+
+/// [`Okx`] execution client configuration.
+///
+/// Only the API credentials are required, with the remaining fields defaulting to the OKX
+/// production endpoints and spot `cash` trading.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct OkxConfig {
+    pub api_key: String,
+    pub secret: String,
+    pub passphrase: String,
+    #[serde(default = "default_base_url_rest")]
+    pub base_url_rest: String,
+    #[serde(default = "default_base_url_ws")]
+    pub base_url_ws: String,
+    #[serde(default)]
+    pub trade_mode: OkxTradeMode,
+    /// Maximum time to wait for an order entry response before returning
+    /// [`ConnectivityError::Timeout`].
+    #[serde(default = "default_order_timeout_ms")]
+    pub order_timeout_ms: u64,
+    /// Route Http requests to the OKX demo trading environment.
+    #[serde(default)]
+    pub demo_trading: bool,
+}
+
+impl OkxConfig {
+    /// Construct an [`OkxConfig`] for the OKX production endpoints.
+    pub fn new<S>(api_key: S, secret: S, passphrase: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            api_key: api_key.into(),
+            secret: secret.into(),
+            passphrase: passphrase.into(),
+            base_url_rest: default_base_url_rest(),
+            base_url_ws: default_base_url_ws(),
+            trade_mode: OkxTradeMode::default(),
+            order_timeout_ms: default_order_timeout_ms(),
+            demo_trading: false,
+        }
+    }
+
+    /// Construct an [`OkxConfig`] for the OKX demo trading endpoints.
+    ///
+    /// Note that demo trading requires separate API credentials.
+    pub fn demo<S>(api_key: S, secret: S, passphrase: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            base_url_ws: "wss://wspap.okx.com:8443/ws/v5/private".to_string(),
+            demo_trading: true,
+            ..Self::new(api_key, secret, passphrase)
+        }
+    }
+}
+
+impl Debug for OkxConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OkxConfig")
+            .field("api_key", &self.api_key)
+            .field("secret", &"<redacted>")
+            .field("passphrase", &"<redacted>")
+            .field("base_url_rest", &self.base_url_rest)
+            .field("base_url_ws", &self.base_url_ws)
+            .field("trade_mode", &self.trade_mode)
+            .field("order_timeout_ms", &self.order_timeout_ms)
+            .field("demo_trading", &self.demo_trading)
+            .finish()
+    }
+}
+
+fn default_base_url_rest() -> String {
+    "https://www.okx.com".to_string()
+}
+
+fn default_base_url_ws() -> String {
+    "wss://ws.okx.com:8443/ws/v5/private".to_string()
+}
+
+fn default_order_timeout_ms() -> u64 {
+    5000
+}
+
+/// Convenient type alias for the HMAC-SHA256 [`RequestSigner`] used to sign OKX V5 requests.
+pub type OkxRequestSigner = RequestSigner<OkxSigner, Hmac<Sha256>, Base64Encoder>;
+
+/// Construct an [`OkxRequestSigner`] from the provided API credentials, optionally routing
+/// requests to the demo trading environment.
+pub fn request_signer(
+    api_key: &str,
+    secret: &str,
+    passphrase: &str,
+    demo_trading: bool,
+) -> OkxRequestSigner {
+    RequestSigner::new(
+        OkxSigner {
+            api_key: api_key.to_string(),
+            passphrase: passphrase.to_string(),
+            demo_trading,
+        },
+        hmac_sha256(secret),
+        Base64Encoder,
+    )
+}
+
+fn hmac_sha256(secret: &str) -> Hmac<Sha256> {
+    Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC-SHA256 accepts keys of any length")
+}
+
+/// OKX V5 API specific [`Signer`] logic.
+///
+/// OKX requires the base64 encoded HMAC-SHA256 signature of
+/// `{timestamp}{method}{request_path}{body}`, where the timestamp is ISO 8601 formatted and the
+/// request path includes any query string.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#overview-rest-authentication-signature>
+#[derive(Debug, Clone)]
+pub struct OkxSigner {
+    pub api_key: String,
+    pub passphrase: String,
+    pub demo_trading: bool,
+}
+
+/// Configuration required to sign an OKX [`RestRequest`].
+#[derive(Debug)]
+pub struct OkxSignConfig<'a> {
+    pub api_key: &'a str,
+    pub passphrase: &'a str,
+    pub timestamp: String,
+    pub method: reqwest::Method,
+    pub request_path: String,
+    pub body: String,
+    pub demo_trading: bool,
+}
+
+impl Signer for OkxSigner {
+    type Config<'a>
+        = OkxSignConfig<'a>
+    where
+        Self: 'a;
+
+    fn config<'a, Request>(
+        &'a self,
+        _: Request,
+        builder: &reqwest::RequestBuilder,
+    ) -> Result<Self::Config<'a>, SocketError>
+    where
+        Request: RestRequest,
+    {
+        let request = builder
+            .try_clone()
+            .ok_or_else(|| SocketError::Unsupported {
+                entity: "Okx".to_string(),
+                item: "streaming request bodies".to_string(),
+            })?
+            .build()?;
+
+        let request_path = match request.url().query() {
+            Some(query) => format!("{}?{query}", request.url().path()),
+            None => request.url().path().to_string(),
+        };
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| String::from_utf8_lossy(body).to_string())
+            .unwrap_or_default();
+
+        Ok(OkxSignConfig {
+            api_key: self.api_key.as_str(),
+            passphrase: self.passphrase.as_str(),
+            timestamp: Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            method: request.method().clone(),
+            request_path,
+            body,
+            demo_trading: self.demo_trading,
+        })
+    }
+
+    fn add_bytes_to_sign<M>(mac: &mut M, config: &Self::Config<'_>)
+    where
+        M: Mac,
+    {
+        mac.update(config.timestamp.as_bytes());
+        mac.update(config.method.as_str().as_bytes());
+        mac.update(config.request_path.as_bytes());
+        mac.update(config.body.as_bytes());
+    }
+
+    fn build_signed_request(
+        config: Self::Config<'_>,
+        builder: reqwest::RequestBuilder,
+        signature: String,
+    ) -> Result<reqwest::Request, SocketError> {
+        let builder = builder
+            .header(HEADER_OKX_ACCESS_KEY, config.api_key)
+            .header(HEADER_OKX_ACCESS_SIGN, signature)
+            .header(HEADER_OKX_ACCESS_TIMESTAMP, config.timestamp)
+            .header(HEADER_OKX_ACCESS_PASSPHRASE, config.passphrase);
+
+        let builder = if config.demo_trading {
+            builder.header(HEADER_OKX_SIMULATED_TRADING, "1")
+        } else {
+            builder
+        };
+
+        builder.build().map_err(SocketError::from)
+    }
+}
+
+/// OKX V5 API error, identified by a non-zero `code`.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#error-code>
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct OkxApiError {
+    pub code: SmolStr,
+    pub msg: String,
+}
+
+/// [`HttpParser`] for OKX V5 Http APIs, mapping [`OkxApiError`]s to [`ClientError`]s.
+#[derive(Debug, Copy, Clone)]
+pub struct OkxParser;
+
+impl HttpParser for OkxParser {
+    type ApiError = OkxApiError;
+    type OutputError = ClientError<AssetNameExchange, InstrumentNameExchange>;
+
+    /// OKX responds with Http status 200 for many errors, so the `code` must be checked before
+    /// attempting to deserialise the `Response`.
+    fn parse<Response>(
+        &self,
+        status: StatusCode,
+        payload: &[u8],
+    ) -> Result<Response, Self::OutputError>
+    where
+        Response: DeserializeOwned,
+    {
+        match serde_json::from_slice::<OkxApiError>(payload) {
+            Ok(error) if error.code != "0" => return Err(self.parse_api_error(status, error)),
+            Err(_) if status == StatusCode::TOO_MANY_REQUESTS => {
+                return Err(ClientError::Api(ApiError::RateLimit));
+            }
+            _ => {}
+        }
+
+        serde_json::from_slice::<Response>(payload).map_err(|error| {
+            error!(
+                status_code = ?status,
+                ?error,
+                response_body = %String::from_utf8_lossy(payload),
+                "error deserializing OKX HTTP response"
+            );
+            ClientError::from(SocketError::DeserialiseBinary {
+                error,
+                payload: payload.to_vec(),
+            })
+        })
+    }
+
+    fn parse_api_error(&self, _: StatusCode, error: Self::ApiError) -> Self::OutputError {
+        api_error(&error.code, error.msg)
+    }
+}
+
+/// Map an OKX error `code` (or order specific `sCode`) to a [`ClientError`].
+pub fn api_error(code: &str, msg: String) -> UnindexedClientError {
+    match code {
+        // Rate limit reached (account & sub-account)
+        "50011" | "50061" => ClientError::Api(ApiError::RateLimit),
+        // Invalid timestamp, API key, passphrase, signature or IP & login failed
+        "50102" | "50103" | "50104" | "50105" | "50110" | "50111" | "50113" | "60004" | "60005"
+        | "60006" | "60009" | "60024" => ClientError::Connectivity(ConnectivityError::Socket(
+            format!("OKX authentication failed: {msg}"),
+        )),
+        // Order has already been cancelled
+        "51401" => ClientError::Api(ApiError::OrderAlreadyCancelled),
+        // Order has already been completed
+        "51402" => ClientError::Api(ApiError::OrderAlreadyFullyFilled),
+        _ => ClientError::Api(ApiError::OrderRejected(format!("OKX code {code}: {msg}"))),
+    }
+}
+
+/// Parse an OKX order type into an [`OrderKind`] and [`TimeInForce`].
+///
+/// Returns `None` for unsupported order types.
+pub fn parse_order_kind(ord_type: &str) -> Option<(OrderKind, TimeInForce)> {
+    match ord_type {
+        "market" | "optimal_limit_ioc" => Some((OrderKind::Market, TimeInForce::ImmediateOrCancel)),
+        "limit" => Some((
+            OrderKind::Limit,
+            TimeInForce::GoodUntilCancelled { post_only: false },
+        )),
+        "post_only" => Some((
+            OrderKind::Limit,
+            TimeInForce::GoodUntilCancelled { post_only: true },
+        )),
+        "ioc" => Some((OrderKind::Limit, TimeInForce::ImmediateOrCancel)),
+        "fok" => Some((OrderKind::Limit, TimeInForce::FillOrKill)),
+        _ => None,
+    }
+}
+
+/// Aborts the private WebSocket heartbeat task when dropped, ensuring it does not outlive the
+/// account stream it keeps alive.
+#[derive(Debug)]
+pub struct OkxHeartbeat(pub JoinHandle<()>);
+
+impl Drop for OkxHeartbeat {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// OKX V5 unified account live [`ExecutionClient`].
+///
+/// Orders are opened and cancelled over a dedicated authenticated private WebSocket for lower
+/// latency than the equivalent Http endpoints, with each response correlated to its request via
+/// the `id` echoed by OKX. The order entry connection is established on first use, and
+/// re-established on the next request if it fails.
+///
+/// The [`AccountStream`](ExecutionClient::AccountStream) is built on a separate private
+/// WebSocket subscribed to the `orders` & `account` channels, while account snapshots use the
+/// signed Http endpoints.
+///
+/// Cash trade mode orders cannot reduce a position, so open requests with reducing
+/// [`OrderFlags`](crate::order::OrderFlags) are rejected without a request.
+#[derive(Debug, Clone)]
+pub struct Okx {
+    pub config: OkxConfig,
+    rest: Arc<RestClient<'static, OkxRequestSigner, OkxParser>>,
+    order_entry: Arc<Mutex<Option<OkxOrderEntry>>>,
+}
+
+impl Okx {
+    /// Connect to the private WebSocket and `login`.
+    async fn connect_private(&self) -> Result<WebSocket, UnindexedClientError> {
+        let mut websocket = connect(self.config.base_url_ws.as_str())
+            .await
+            .map_err(|error| {
+                UnindexedClientError::Connectivity(ConnectivityError::Socket(error.to_string()))
+            })?;
+
+        let timestamp = Utc::now().timestamp();
+        let mut mac = hmac_sha256(&self.config.secret);
+        mac.update(format!("{timestamp}GET/users/self/verify").as_bytes());
+        let sign = Base64Encoder.encode(mac.finalize().into_bytes());
+
+        send_ws_request(
+            &mut websocket,
+            &OkxWsRequest::login(
+                &self.config.api_key,
+                &self.config.passphrase,
+                timestamp,
+                sign,
+            ),
+        )
+        .await?;
+        await_ws_event(&mut websocket, "login").await?;
+
+        Ok(websocket)
+    }
+
+    /// Get the current order entry connection, establishing a new one if required.
+    async fn order_entry(&self) -> Result<OkxOrderEntry, UnindexedClientError> {
+        let mut order_entry = self.order_entry.lock().await;
+
+        match order_entry.as_ref() {
+            Some(current) if !current.is_closed() => Ok(current.clone()),
+            _ => {
+                let connection = OkxOrderEntry::spawn(self.connect_private().await?);
+                info!(exchange = %Self::EXCHANGE, "connected OKX order entry WebSocket");
+                *order_entry = Some(connection.clone());
+                Ok(connection)
+            }
+        }
+    }
+
+    /// Send an order entry `op` request, mapping any error `code` or `sCode` in the response.
+    async fn order_op<Args>(
+        &self,
+        op: &'static str,
+        args: Args,
+    ) -> Result<OkxOpResult, UnindexedClientError>
+    where
+        Args: Serialize,
+    {
+        let args = serde_json::to_value(args).map_err(|error| {
+            UnindexedClientError::Connectivity(ConnectivityError::Socket(error.to_string()))
+        })?;
+
+        let response = self
+            .order_entry()
+            .await?
+            .request(
+                op,
+                args,
+                Duration::from_millis(self.config.order_timeout_ms),
+            )
+            .await?;
+
+        match response.data.into_iter().next() {
+            Some(result) if result.s_code != "0" => Err(api_error(&result.s_code, result.s_msg)),
+            Some(result) if response.code == "0" => Ok(result),
+            _ => Err(api_error(&response.code, response.msg)),
+        }
+    }
+}
+
+impl ExecutionClient for Okx {
+    const EXCHANGE: ExchangeId = ExchangeId::Okx;
+    type Config = OkxConfig;
+    type AccountStream = BoxStream<'static, UnindexedAccountEvent>;
+
+    fn new(config: Self::Config) -> Self {
+        let rest = RestClient::new(
+            config.base_url_rest.clone(),
+            request_signer(
+                &config.api_key,
+                &config.secret,
+                &config.passphrase,
+                config.demo_trading,
+            ),
+            OkxParser,
+        );
+
+        Self {
+            config,
+            rest: Arc::new(rest),
+            order_entry: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    async fn account_snapshot(
+        &self,
+        assets: &[AssetNameExchange],
+        instruments: &[InstrumentNameExchange],
+    ) -> Result<UnindexedAccountSnapshot, UnindexedClientError> {
+        let balances = self.fetch_balances().await?;
+        let open_orders = self.fetch_open_orders().await?;
+
+        let balances = balances
+            .into_iter()
+            .filter(|balance| assets.contains(&balance.asset))
+            .collect();
+
+        let mut orders_by_instrument = open_orders
+            .into_iter()
+            .filter(|order| instruments.contains(&order.key.instrument))
+            .into_group_map_by(|order| order.key.instrument.clone());
+
+        let instruments = instruments
+            .iter()
+            .map(|instrument| {
+                let orders = orders_by_instrument
+                    .remove(instrument)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|order| Order {
+                        key: order.key,
+                        side: order.side,
+                        price: order.price,
+                        quantity: order.quantity,
+                        kind: order.kind,
+                        time_in_force: order.time_in_force,
+                        state: OrderState::active(order.state),
+                    })
+                    .collect();
+
+                InstrumentAccountSnapshot::new(instrument.clone(), orders, None)
+            })
+            .collect();
+
+        Ok(UnindexedAccountSnapshot::new(
+            Self::EXCHANGE,
+            balances,
+            instruments,
+        ))
+    }
+
+    async fn account_stream(
+        &self,
+        _: &[AssetNameExchange],
+        _: &[InstrumentNameExchange],
+    ) -> Result<Self::AccountStream, UnindexedClientError> {
+        let mut websocket = self
+            .connect_private()
+            .await
+            .map_err(|error| UnindexedClientError::AccountStream(error.to_string()))?;
+
+        send_ws_request(&mut websocket, &OkxWsRequest::subscribe()).await?;
+        // OKX acknowledges each subscribed channel individually
+        await_ws_event(&mut websocket, "subscribe").await?;
+        await_ws_event(&mut websocket, "subscribe").await?;
+
+        info!(
+            exchange = %Self::EXCHANGE,
+            "connected to OKX private WebSocket"
+        );
+
+        let (sink, stream) = websocket.split();
+        let heartbeat = OkxHeartbeat(tokio::spawn(heartbeat(sink, PING_INTERVAL)));
+
+        Ok(stream
+            .filter_map(|message| std::future::ready(parse_ws_message(message)))
+            .scan(heartbeat, |_heartbeat, message| {
+                std::future::ready(match message {
+                    Ok(OkxPrivateMessage::Push(push)) => {
+                        Some(push.into_account_events(Self::EXCHANGE))
+                    }
+                    Ok(OkxPrivateMessage::Event(event)) => {
+                        if event.event == "error" {
+                            warn!(
+                                code = %event.code,
+                                msg = %event.msg,
+                                "received OKX private WebSocket error event"
+                            );
+                        }
+                        Some(vec![])
+                    }
+                    Ok(OkxPrivateMessage::OpResponse(_)) => Some(vec![]),
+                    Err(error @ SocketError::Deserialise { .. }) => {
+                        warn!(
+                            ?error,
+                            "failed to deserialise OKX private WebSocket message"
+                        );
+                        Some(vec![])
+                    }
+                    Err(error) => {
+                        warn!(?error, "OKX private WebSocket failed, ending stream");
+                        None
+                    }
+                })
+            })
+            .flat_map(futures::stream::iter)
+            .boxed())
+    }
+
+    async fn cancel_order(
+        &self,
+        request: OrderRequestCancel<ExchangeId, &InstrumentNameExchange>,
+    ) -> UnindexedOrderResponseCancel {
+        let args = OkxCancelArgs {
+            inst_id: request.key.instrument.clone(),
+            cl_ord_id: request.key.cid.clone(),
+        };
+
+        let result = self
+            .order_op("cancel-order", args)
+            .await
+            .map(|result| Cancelled::new(OrderId::new(result.ord_id), result.ts))
+            .map_err(order_error);
+
+        UnindexedOrderResponseCancel {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            state: result,
+        }
+    }
+
+    async fn open_order(
+        &self,
+        request: OrderRequestOpen<ExchangeId, &InstrumentNameExchange>,
+    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>> {
+        let args = OkxOrderArgs::new(
+            self.config.trade_mode,
+            request.key.instrument.clone(),
+            request.key.cid.clone(),
+            request.state.side,
+            request.state.kind,
+            request.state.time_in_force,
+            request.state.price,
+            request.state.quantity,
+            request.state.flags,
+        );
+
+        let state = match args {
+            _ if self.config.trade_mode == OkxTradeMode::Cash
+                && request.state.flags.is_reducing() =>
+            {
+                Err(OrderError::Rejected(ApiError::OrderRejected(
+                    "OKX cash trade mode does not support reduce only or close position orders"
+                        .to_string(),
+                )))
+            }
+            Some(args) => self
+                .order_op("order", args)
+                .await
+                .map(|result| Open::new(OrderId::new(result.ord_id), result.ts, Decimal::ZERO))
+                .map_err(order_error),
+            None => Err(OrderError::Rejected(ApiError::OrderRejected(format!(
+                "OKX does not support {} orders with {}",
+                request.state.kind, request.state.time_in_force
+            )))),
+        };
+
+        Order {
+            key: OrderKey {
+                exchange: request.key.exchange,
+                instrument: request.key.instrument.clone(),
+                strategy: request.key.strategy,
+                cid: request.key.cid,
+            },
+            side: request.state.side,
+            price: request.state.price,
+            quantity: request.state.quantity,
+            kind: request.state.kind,
+            time_in_force: request.state.time_in_force,
+            state,
+        }
+    }
+
+    async fn fetch_balances(
+        &self,
+    ) -> Result<Vec<AssetBalance<AssetNameExchange>>, UnindexedClientError> {
+        let (response, _) = self.rest.execute(FetchBalance).await?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .flat_map(|account| account.details)
+            .map(AssetBalance::from)
+            .collect())
+    }
+
+    async fn fetch_open_orders(
+        &self,
+    ) -> Result<Vec<Order<ExchangeId, InstrumentNameExchange, Open>>, UnindexedClientError> {
+        let mut orders = Vec::new();
+        let mut after = None;
+
+        loop {
+            let (response, _) = self
+                .rest
+                .execute(FetchPendingOrders {
+                    query: FetchPendingOrdersParams {
+                        limit: FETCH_LIMIT,
+                        after,
+                    },
+                })
+                .await?;
+
+            let page_len = response.data.len();
+            after = response.data.last().map(|order| order.ord_id.clone());
+
+            orders.extend(
+                response
+                    .data
+                    .into_iter()
+                    .filter_map(|order| order.into_open_order(Self::EXCHANGE)),
+            );
+
+            if page_len < usize::from(FETCH_LIMIT) {
+                break Ok(orders);
+            }
+        }
+    }
+
+    /// Fetch account trades since the provided time.
+    ///
+    /// OKX only returns fills from the last 3 days.
+    async fn fetch_trades(
+        &self,
+        time_since: DateTime<Utc>,
+    ) -> Result<Vec<Trade<QuoteAsset, InstrumentNameExchange>>, UnindexedClientError> {
+        let mut trades = Vec::new();
+        let mut after = None;
+
+        loop {
+            let (response, _) = self
+                .rest
+                .execute(FetchFills {
+                    query: FetchFillsParams {
+                        begin: time_since.timestamp_millis(),
+                        limit: FETCH_LIMIT,
+                        after,
+                    },
+                })
+                .await?;
+
+            let page_len = response.data.len();
+            after = response.data.last().map(|fill| fill.bill_id.clone());
+
+            trades.extend(response.data.into_iter().map(Trade::from));
+
+            if page_len < usize::from(FETCH_LIMIT) {
+                break Ok(trades);
+            }
+        }
+    }
+}
+
+/// Parse an OKX private WebSocket message, ignoring text heartbeat responses.
+fn parse_ws_message(
+    message: Result<WsMessage, barter_integration::protocol::websocket::WsError>,
+) -> Option<Result<OkxPrivateMessage, SocketError>> {
+    match message {
+        Ok(WsMessage::Text(text)) if text.as_str() == OKX_PONG => None,
+        message => <WebSocketParser as StreamParser<OkxPrivateMessage>>::parse(message),
+    }
+}
+
+async fn send_ws_request(
+    websocket: &mut WebSocket,
+    request: &OkxWsRequest,
+) -> Result<(), UnindexedClientError> {
+    websocket
+        .send(request.to_ws_message())
+        .await
+        .map_err(|error| {
+            UnindexedClientError::Connectivity(ConnectivityError::Socket(error.to_string()))
+        })
+}
+
+/// Wait for the private WebSocket event in response to a `login` or `subscribe` request,
+/// returning an error if OKX responds with an `error` event.
+async fn await_ws_event(
+    websocket: &mut WebSocket,
+    event: &str,
+) -> Result<(), UnindexedClientError> {
+    let response = async {
+        while let Some(message) = websocket.next().await {
+            match parse_ws_message(message) {
+                Some(Ok(OkxPrivateMessage::Event(response))) if response.event == event => {
+                    return Ok(());
+                }
+                Some(Ok(OkxPrivateMessage::Event(response))) if response.event == "error" => {
+                    return Err(api_error(&response.code, response.msg));
+                }
+                Some(Err(error)) => {
+                    return Err(UnindexedClientError::Connectivity(
+                        ConnectivityError::Socket(error.to_string()),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Err(UnindexedClientError::Connectivity(
+            ConnectivityError::Socket(format!(
+                "OKX private WebSocket closed before {event} response"
+            )),
+        ))
+    };
+
+    tokio::time::timeout(WS_RESPONSE_TIMEOUT, response)
+        .await
+        .unwrap_or(Err(UnindexedClientError::Connectivity(
+            ConnectivityError::Timeout,
+        )))
+}
+
+/// Periodically send the private WebSocket text heartbeat.
+///
+/// Runs until aborted by the [`OkxHeartbeat`] guard held by the account stream.
+async fn heartbeat(mut sink: WsSink, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+
+    // First tick completes immediately, and the connection was only just authenticated
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        if let Err(error) = sink.send(WsMessage::text(OKX_PING)).await {
+            warn!(?error, "failed to send OKX private WebSocket heartbeat");
+            break;
+        }
+    }
+}
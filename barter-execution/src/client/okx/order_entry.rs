@@ -0,0 +1,191 @@
+use crate::{
+    client::okx::{
+        OKX_PING, OKX_PONG, PING_INTERVAL,
+        account::{OkxPrivateMessage, OkxWsOpResponse, OkxWsRequest},
+    },
+    error::{ConnectivityError, UnindexedClientError},
+};
+use barter_integration::{
+    error::SocketError,
+    protocol::{
+        StreamParser,
+        websocket::{WebSocket, WebSocketParser, WsError, WsMessage},
+    },
+};
+use fnv::FnvHashMap;
+use futures::{SinkExt, StreamExt};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, warn};
+
+/// Handle to an authenticated OKX private WebSocket used for order entry.
+///
+/// Each `op` request is assigned a unique `id`, which OKX echoes in the corresponding
+/// [`OkxWsOpResponse`], allowing responses to be correlated with their request regardless of
+/// the order they arrive in.
+///
+/// The connection is owned by a background task that ends if the connection fails, or once
+/// every handle has been dropped.
+#[derive(Debug, Clone)]
+pub struct OkxOrderEntry {
+    tx: mpsc::UnboundedSender<OkxOrderEntryRequest>,
+    request_id: Arc<AtomicU64>,
+}
+
+#[derive(Debug)]
+struct OkxOrderEntryRequest {
+    request: OkxWsRequest,
+    response_tx: oneshot::Sender<OkxWsOpResponse>,
+}
+
+impl OkxOrderEntry {
+    /// Spawn the order entry task for the provided authenticated [`WebSocket`].
+    pub fn spawn(websocket: WebSocket) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(websocket, rx));
+
+        Self {
+            tx,
+            request_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    /// Returns true if the underlying connection has ended.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Send an `op` request, waiting up to `timeout` for the correlated [`OkxWsOpResponse`].
+    pub async fn request(
+        &self,
+        op: &'static str,
+        args: serde_json::Value,
+        timeout: Duration,
+    ) -> Result<OkxWsOpResponse, UnindexedClientError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let request = OkxWsRequest {
+            id: Some(self.request_id.fetch_add(1, Ordering::Relaxed).to_string()),
+            op,
+            args: vec![args],
+        };
+
+        self.tx
+            .send(OkxOrderEntryRequest {
+                request,
+                response_tx,
+            })
+            .map_err(|_| connection_closed())?;
+
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(connection_closed()),
+            Err(_) => Err(UnindexedClientError::Connectivity(
+                ConnectivityError::Timeout,
+            )),
+        }
+    }
+}
+
+fn connection_closed() -> UnindexedClientError {
+    UnindexedClientError::Connectivity(ConnectivityError::Socket(
+        "OKX order entry connection closed".to_string(),
+    ))
+}
+
+/// Run the order entry connection, forwarding requests and routing responses to the requester
+/// with the matching `id`.
+///
+/// In-flight requests are failed (by dropping their response channel) if the connection ends.
+async fn run(websocket: WebSocket, mut rx: mpsc::UnboundedReceiver<OkxOrderEntryRequest>) {
+    let (mut sink, mut stream) = websocket.split();
+    let mut in_flight = FnvHashMap::<String, oneshot::Sender<OkxWsOpResponse>>::default();
+    let mut heartbeat = tokio::time::interval(PING_INTERVAL);
+
+    // First tick completes immediately, and the connection was only just authenticated
+    heartbeat.tick().await;
+
+    loop {
+        tokio::select! {
+            request = rx.recv() => {
+                let Some(OkxOrderEntryRequest { request, response_tx }) = request else {
+                    debug!("OKX order entry handles dropped, closing connection");
+                    break;
+                };
+
+                if let Err(error) = sink.send(request.to_ws_message()).await {
+                    warn!(?error, "failed to send OKX order entry request, closing connection");
+                    break;
+                }
+
+                if let Some(id) = request.id {
+                    in_flight.insert(id, response_tx);
+                }
+            }
+            message = stream.next() => {
+                let Some(message) = message else {
+                    warn!("OKX order entry connection ended");
+                    break;
+                };
+
+                if !handle_message(message, &mut in_flight) {
+                    break;
+                }
+            }
+            _ = heartbeat.tick() => {
+                if let Err(error) = sink.send(WsMessage::text(OKX_PING)).await {
+                    warn!(?error, "failed to send OKX order entry heartbeat, closing connection");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Route an order entry connection message to the requester with the matching `id`, returning
+/// false if the connection has failed.
+fn handle_message(
+    message: Result<WsMessage, WsError>,
+    in_flight: &mut FnvHashMap<String, oneshot::Sender<OkxWsOpResponse>>,
+) -> bool {
+    if matches!(&message, Ok(WsMessage::Text(text)) if text.as_str() == OKX_PONG) {
+        return true;
+    }
+
+    match <WebSocketParser as StreamParser<OkxPrivateMessage>>::parse(message) {
+        Some(Ok(OkxPrivateMessage::OpResponse(response))) => match in_flight.remove(&response.id) {
+            Some(response_tx) => {
+                // Requester may have timed out, so failure is ignored
+                let _ = response_tx.send(response);
+            }
+            None => warn!(
+                id = %response.id,
+                op = %response.op,
+                "received OKX order entry response for unknown request id"
+            ),
+        },
+        Some(Ok(OkxPrivateMessage::Event(event))) if event.event == "error" => {
+            warn!(
+                code = %event.code,
+                msg = %event.msg,
+                "received OKX order entry error event"
+            );
+        }
+        Some(Ok(_)) | None => {}
+        Some(Err(error @ SocketError::Deserialise { .. })) => {
+            warn!(?error, "failed to deserialise OKX order entry message");
+        }
+        Some(Err(error)) => {
+            warn!(?error, "OKX order entry connection failed");
+            return false;
+        }
+    }
+
+    true
+}
@@ -0,0 +1,366 @@
+use crate::{
+    balance::{AssetBalance, Balance},
+    client::{de_decimal_or_zero, fees_quote, okx::parse_order_kind},
+    order::{
+        Order, OrderKey,
+        id::{ClientOrderId, OrderId, StrategyId},
+        state::Open,
+    },
+    trade::{AssetFees, Trade, TradeId},
+};
+use barter_instrument::{
+    Side,
+    asset::{QuoteAsset, name::AssetNameExchange},
+    exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::http::rest::RestRequest;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use std::borrow::Cow;
+use tracing::warn;
+
+/// OKX V5 Http response envelope.
+///
+/// The [`OkxParser`](super::OkxParser) only yields an [`OkxResponse`] if the `code` is "0".
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "code": "0",
+///     "msg": "",
+///     "data": []
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OkxResponse<T> {
+    pub data: Vec<T>,
+}
+
+/// OKX V5 signed request to fetch the trading account balance.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#trading-account-rest-api-get-balance>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchBalance;
+
+impl RestRequest for FetchBalance {
+    type Response = OkxResponse<OkxAccount>;
+    type QueryParams = ();
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v5/account/balance")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+}
+
+/// OKX V5 trading account, as returned by the balance endpoint and the private `account`
+/// channel.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "uTime": "1705474164160",
+///     "totalEq": "55837.43556134779",
+///     "details": [
+///         {
+///             "ccy": "USDT",
+///             "cashBal": "4850.435693622894",
+///             "availBal": "4834.317093622894",
+///             "frozenBal": "16.1186",
+///             "uTime": "1705449605015"
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxAccount {
+    pub details: Vec<OkxAccountBalance>,
+}
+
+/// OKX V5 trading account asset balance.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxAccountBalance {
+    pub ccy: AssetNameExchange,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub cash_bal: Decimal,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub avail_bal: Decimal,
+    #[serde(deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc")]
+    pub u_time: DateTime<Utc>,
+}
+
+impl From<OkxAccountBalance> for AssetBalance<AssetNameExchange> {
+    fn from(value: OkxAccountBalance) -> Self {
+        AssetBalance::new(
+            value.ccy,
+            Balance::new(value.cash_bal, value.avail_bal),
+            value.u_time,
+        )
+    }
+}
+
+/// OKX V5 signed request to fetch open orders.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-get-order-list>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchPendingOrders {
+    pub query: FetchPendingOrdersParams,
+}
+
+impl RestRequest for FetchPendingOrders {
+    type Response = OkxResponse<OkxOrder>;
+    type QueryParams = FetchPendingOrdersParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v5/trade/orders-pending")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchPendingOrders`] request query parameters.
+///
+/// Results are paginated in descending order, with `after` requesting orders older than the
+/// provided `ordId`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchPendingOrdersParams {
+    pub limit: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<SmolStr>,
+}
+
+/// OKX V5 order, as returned by the open orders endpoint and the private `orders` channel.
+///
+/// `orders` channel updates also contain the details of the latest fill, if any.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "instType": "SPOT",
+///     "instId": "BTC-USDT",
+///     "ordId": "312269865356374016",
+///     "clOrdId": "b1",
+///     "px": "999",
+///     "sz": "3",
+///     "ordType": "limit",
+///     "side": "buy",
+///     "state": "partially_filled",
+///     "accFillSz": "1",
+///     "fillPx": "999",
+///     "tradeId": "12345",
+///     "fillSz": "1",
+///     "fillTime": "1597026383085",
+///     "fillFee": "-0.001",
+///     "fillFeeCcy": "BTC",
+///     "uTime": "1597026383085"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOrder {
+    pub inst_id: InstrumentNameExchange,
+    pub ord_id: SmolStr,
+    pub cl_ord_id: SmolStr,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub px: Decimal,
+    pub sz: Decimal,
+    pub ord_type: SmolStr,
+    pub side: Side,
+    pub state: SmolStr,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub acc_fill_sz: Decimal,
+    #[serde(default)]
+    pub trade_id: SmolStr,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub fill_px: Decimal,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub fill_sz: Decimal,
+    #[serde(default, deserialize_with = "de_decimal_or_zero")]
+    pub fill_fee: Decimal,
+    #[serde(default)]
+    pub fill_fee_ccy: SmolStr,
+    #[serde(deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc")]
+    pub u_time: DateTime<Utc>,
+}
+
+impl OkxOrder {
+    /// Convert into an [`Order`] in the [`Open`] state, returning `None` if the OKX order type
+    /// is not supported.
+    pub fn into_open_order(
+        self,
+        exchange: ExchangeId,
+    ) -> Option<Order<ExchangeId, InstrumentNameExchange, Open>> {
+        let Some((kind, time_in_force)) = parse_order_kind(&self.ord_type) else {
+            warn!(
+                %exchange,
+                instrument = %self.inst_id,
+                order_id = %self.ord_id,
+                kind = %self.ord_type,
+                "ignoring OKX open order with unsupported type"
+            );
+            return None;
+        };
+
+        Some(Order {
+            key: OrderKey::new(
+                exchange,
+                self.inst_id,
+                StrategyId::unknown(),
+                ClientOrderId::new(self.cl_ord_id),
+            ),
+            side: self.side,
+            price: self.px,
+            quantity: self.sz,
+            kind,
+            time_in_force,
+            state: Open::new(OrderId::new(self.ord_id), self.u_time, self.acc_fill_sz),
+        })
+    }
+
+    /// Generate a [`Trade`] from the latest fill contained in an `orders` channel update, if
+    /// the update was caused by a fill.
+    pub fn fill(&self) -> Option<Trade<QuoteAsset, InstrumentNameExchange>> {
+        if self.trade_id.is_empty() || self.fill_sz.is_zero() {
+            return None;
+        }
+
+        Some(Trade {
+            id: TradeId::new(self.trade_id.clone()),
+            order_id: OrderId::new(self.ord_id.clone()),
+            instrument: self.inst_id.clone(),
+            strategy: StrategyId::unknown(),
+            time_exchange: self.u_time,
+            side: self.side,
+            price: self.fill_px,
+            quantity: self.fill_sz,
+            fees: AssetFees::quote_fees(okx_fees_quote(
+                &self.inst_id,
+                &self.fill_fee_ccy,
+                self.fill_fee,
+                self.fill_px,
+            )),
+        })
+    }
+}
+
+/// OKX V5 signed request to fetch account fills (trades) from the last 3 days.
+///
+/// See docs: <https://www.okx.com/docs-v5/en/#order-book-trading-trade-get-transaction-details-last-3-days>
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchFills {
+    pub query: FetchFillsParams,
+}
+
+impl RestRequest for FetchFills {
+    type Response = OkxResponse<OkxFill>;
+    type QueryParams = FetchFillsParams;
+    type Body = ();
+
+    fn path(&self) -> Cow<'static, str> {
+        Cow::Borrowed("/api/v5/trade/fills")
+    }
+
+    fn method() -> reqwest::Method {
+        reqwest::Method::GET
+    }
+
+    fn query_params(&self) -> Option<&Self::QueryParams> {
+        Some(&self.query)
+    }
+}
+
+/// [`FetchFills`] request query parameters.
+///
+/// Results are paginated in descending order, with `after` requesting fills older than the
+/// provided `billId`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchFillsParams {
+    pub begin: i64,
+    pub limit: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<SmolStr>,
+}
+
+/// OKX V5 account fill.
+///
+/// ### Raw Payload Examples
+/// ```json
+/// {
+///     "instId": "BTC-USDT",
+///     "tradeId": "123",
+///     "ordId": "123445",
+///     "billId": "1111",
+///     "fillPx": "29000",
+///     "fillSz": "0.01",
+///     "side": "buy",
+///     "fee": "-0.00001",
+///     "feeCcy": "BTC",
+///     "ts": "1597026383085"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxFill {
+    pub inst_id: InstrumentNameExchange,
+    pub trade_id: SmolStr,
+    pub ord_id: SmolStr,
+    pub bill_id: SmolStr,
+    pub fill_px: Decimal,
+    pub fill_sz: Decimal,
+    pub side: Side,
+    #[serde(deserialize_with = "de_decimal_or_zero")]
+    pub fee: Decimal,
+    #[serde(default)]
+    pub fee_ccy: SmolStr,
+    #[serde(deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc")]
+    pub ts: DateTime<Utc>,
+}
+
+impl From<OkxFill> for Trade<QuoteAsset, InstrumentNameExchange> {
+    fn from(value: OkxFill) -> Self {
+        let fees = okx_fees_quote(&value.inst_id, &value.fee_ccy, value.fee, value.fill_px);
+
+        Trade {
+            id: TradeId::new(value.trade_id),
+            order_id: OrderId::new(value.ord_id),
+            instrument: value.inst_id,
+            strategy: StrategyId::unknown(),
+            time_exchange: value.ts,
+            side: value.side,
+            price: value.fill_px,
+            quantity: value.fill_sz,
+            fees: AssetFees::quote_fees(fees),
+        }
+    }
+}
+
+/// OKX reports fees charged as negative values (and rebates as positive values), so the sign is
+/// flipped before converting into the quote asset.
+fn okx_fees_quote(
+    instrument: &InstrumentNameExchange,
+    fee_ccy: &SmolStr,
+    fee: Decimal,
+    price: Decimal,
+) -> Decimal {
+    fees_quote(
+        instrument,
+        &AssetNameExchange::new(fee_ccy.clone()),
+        -fee,
+        price,
+    )
+}
@@ -43,6 +43,12 @@ pub enum ClientError<AssetKey = AssetIndex, InstrumentKey = InstrumentIndex> {
     AccountStream(String),
 }
 
+impl<AssetKey, InstrumentKey> From<SocketError> for ClientError<AssetKey, InstrumentKey> {
+    fn from(value: SocketError) -> Self {
+        Self::Connectivity(ConnectivityError::from(value))
+    }
+}
+
 /// Represents all connectivity-centric errors.
 ///
 /// Connectivity errors are generally intermittent / non-deterministic (eg/ Timeout).
@@ -97,6 +103,11 @@ pub enum ApiError<AssetKey = AssetIndex, InstrumentKey = InstrumentIndex> {
     OrderAlreadyCancelled,
     #[error("order already fully filled")]
     OrderAlreadyFullyFilled,
+
+    /// A cancel-replace amend cancelled the original order, but failed to open the replacement,
+    /// so the original order is no longer open.
+    #[error("order cancelled, but replacement order failed: {0}")]
+    OrderReplaceFailed(String),
 }
 
 /// Represents all errors that can be generated when cancelling or opening orders.
@@ -69,6 +69,38 @@ impl AccountState {
         self.balances.get_mut(asset)
     }
 
+    pub fn order_open(
+        &self,
+        cid: &ClientOrderId,
+    ) -> Option<&Order<ExchangeId, InstrumentNameExchange, Open>> {
+        self.orders_open.get(cid)
+    }
+
+    pub fn insert_order_open(&mut self, order: Order<ExchangeId, InstrumentNameExchange, Open>) {
+        self.orders_open.insert(order.key.cid.clone(), order);
+    }
+
+    pub fn remove_order_open(
+        &mut self,
+        cid: &ClientOrderId,
+    ) -> Option<Order<ExchangeId, InstrumentNameExchange, Open>> {
+        self.orders_open.remove(cid)
+    }
+
+    pub fn order_cancelled(
+        &self,
+        cid: &ClientOrderId,
+    ) -> Option<&Order<ExchangeId, InstrumentNameExchange, Cancelled>> {
+        self.orders_cancelled.get(cid)
+    }
+
+    pub fn insert_order_cancelled(
+        &mut self,
+        order: Order<ExchangeId, InstrumentNameExchange, Cancelled>,
+    ) {
+        self.orders_cancelled.insert(order.key.cid.clone(), order);
+    }
+
     pub fn ack_trade(&mut self, trade: Trade<QuoteAsset, InstrumentNameExchange>) {
         self.trades.push(trade);
     }
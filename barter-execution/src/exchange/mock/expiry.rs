@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Determines how the [`MockExchange`](super::MockExchange) settles `Future` positions that
+/// are still open when their contract expires.
+///
+/// In both cases, the position is closed at the prevailing mark price with an `"expiry"`
+/// [`Trade`](crate::trade::Trade), realising its PnL in the settlement asset.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpirySettlement {
+    /// Position is only settled in cash.
+    #[default]
+    Cash,
+    /// Position is settled in cash, and the underlying base asset is then delivered in exchange
+    /// for its quote asset value at the settlement price.
+    ///
+    /// LONG positions receive the base asset, while SHORT positions deliver it.
+    Physical,
+}
@@ -0,0 +1,165 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Fault that can be injected by the [`MockExchange`](super::MockExchange) when actioning an
+/// open or cancel order request.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fault {
+    /// Reject the request with `ApiError::RateLimit`.
+    RateLimit,
+    /// Reject the request with `ApiError::BalanceInsufficient`.
+    BalanceInsufficient,
+    /// Reject the request with `ApiError::OrderRejected`.
+    OrderRejected,
+    /// Action the request, but never respond so the client times out.
+    Timeout,
+    /// Action the request, then drop the client account stream before sending the associated
+    /// notifications, so they are only recoverable via an account snapshot.
+    Disconnect,
+    /// Action the request, sending every associated notification twice.
+    Duplicate,
+    /// Action the request, delaying the associated notifications until after the next batch of
+    /// notifications has been sent.
+    Reorder,
+}
+
+/// Configuration of the [`Fault`]s injected by the [`MockExchange`](super::MockExchange).
+///
+/// Each open and cancel order request is assigned a sequence number, starting from zero. A
+/// [`Fault`] is injected into a request if it is scheduled for its sequence number, otherwise
+/// at most one [`Fault`] is sampled using the configured probabilities.
+///
+/// Sampling uses a random number generator seeded with `seed`, so runs are reproducible.
+///
+/// eg/ `{ "schedule": { "3": "timeout" }, "probabilities": { "rate_limit": 0.01 } }`
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+pub struct FaultConfig {
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub schedule: BTreeMap<u64, Fault>,
+    #[serde(default)]
+    pub probabilities: BTreeMap<Fault, Decimal>,
+}
+
+/// Seeded [`FaultConfig`] sampler used by the [`MockExchange`](super::MockExchange).
+#[derive(Debug, Clone)]
+pub struct FaultInjector {
+    pub config: FaultConfig,
+    sequence: u64,
+    rng: StdRng,
+}
+
+impl FaultInjector {
+    pub fn new(config: FaultConfig) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self {
+            config,
+            sequence: 0,
+            rng,
+        }
+    }
+
+    /// Determine the [`Fault`] to inject into the next order request, if any.
+    pub fn next_fault(&mut self) -> Option<Fault> {
+        let sequence = self.sequence;
+        self.sequence += 1;
+
+        if let Some(fault) = self.config.schedule.get(&sequence) {
+            return Some(*fault);
+        }
+
+        if self.config.probabilities.is_empty() {
+            return None;
+        }
+
+        let sample = Decimal::from_f64(self.rng.random::<f64>()).unwrap_or_default();
+
+        self.config
+            .probabilities
+            .iter()
+            .scan(Decimal::ZERO, |cumulative, (fault, probability)| {
+                *cumulative += *probability;
+                Some((*fault, *cumulative))
+            })
+            .find_map(|(fault, cumulative)| (sample < cumulative).then_some(fault))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_fault_injector_next_fault() {
+        // Scheduled faults are injected into specific requests
+        let mut injector = FaultInjector::new(FaultConfig {
+            seed: 0,
+            schedule: BTreeMap::from([(1, Fault::Timeout), (3, Fault::Duplicate)]),
+            probabilities: BTreeMap::new(),
+        });
+        let faults = (0..5).map(|_| injector.next_fault()).collect::<Vec<_>>();
+        assert_eq!(
+            faults,
+            vec![
+                None,
+                Some(Fault::Timeout),
+                None,
+                Some(Fault::Duplicate),
+                None
+            ]
+        );
+
+        // Probabilities summing to one always inject a fault
+        let config = FaultConfig {
+            seed: 42,
+            schedule: BTreeMap::new(),
+            probabilities: BTreeMap::from([
+                (Fault::RateLimit, dec!(0.5)),
+                (Fault::Disconnect, dec!(0.5)),
+            ]),
+        };
+        let mut injector = FaultInjector::new(config.clone());
+        let faults = (0..100)
+            .map(|_| injector.next_fault().unwrap())
+            .collect::<Vec<_>>();
+        assert!(faults.contains(&Fault::RateLimit));
+        assert!(faults.contains(&Fault::Disconnect));
+
+        // Same seed injects the same faults
+        let mut injector = FaultInjector::new(config);
+        let faults_replay = (0..100)
+            .map(|_| injector.next_fault().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(faults, faults_replay);
+    }
+
+    #[test]
+    fn test_fault_config_deserialise() {
+        let input = r#"
+        {
+            "seed": 7,
+            "schedule": { "3": "timeout", "10": "balance_insufficient" },
+            "probabilities": { "rate_limit": 0.01, "reorder": 0.05 }
+        }
+        "#;
+
+        let actual = serde_json::from_str::<FaultConfig>(input).unwrap();
+
+        assert_eq!(
+            actual,
+            FaultConfig {
+                seed: 7,
+                schedule: BTreeMap::from([(3, Fault::Timeout), (10, Fault::BalanceInsufficient)]),
+                probabilities: BTreeMap::from([
+                    (Fault::RateLimit, dec!(0.01)),
+                    (Fault::Reorder, dec!(0.05)),
+                ]),
+            }
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use barter_instrument::asset::name::AssetNameExchange;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Whether a fill added liquidity to the book (maker), or removed it (taker).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// Exchange fee schedule used by the [`MockExchange`](super::MockExchange) to charge fees on
+/// simulated fills.
+///
+/// Rates are fractions of the fill value (eg/ `0.001` is 10 bps). Negative rates are rebates
+/// paid to the account.
+///
+/// Volume based [`FeeTier`]s override the base `maker` and `taker` rates once the cumulative
+/// traded quote volume reaches the tier `volume_quote` threshold.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+pub struct FeeSchedule {
+    pub maker: Decimal,
+    pub taker: Decimal,
+    #[serde(default)]
+    pub tiers: Vec<FeeTier>,
+    #[serde(default)]
+    pub asset: FeeAsset,
+}
+
+/// Volume based [`FeeSchedule`] tier.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub struct FeeTier {
+    pub volume_quote: Decimal,
+    pub maker: Decimal,
+    pub taker: Decimal,
+}
+
+/// Asset a [`FeeSchedule`] charges fees in.
+///
+/// Defaults to [`FeeAsset::Quote`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeAsset {
+    /// Fees are charged in the instrument quote asset.
+    #[default]
+    Quote,
+    /// Fees are charged in the instrument base asset.
+    Base,
+    /// Fees are charged in a third asset (eg/ BNB), converted from the quote fee value using
+    /// the provided price of the fee asset denominated in the quote asset.
+    Other {
+        asset: AssetNameExchange,
+        price_quote: Decimal,
+    },
+}
+
+impl FeeSchedule {
+    /// Construct a [`FeeSchedule`] that charges the same quote asset rate to makers and takers.
+    pub fn flat(rate: Decimal) -> Self {
+        Self {
+            maker: rate,
+            taker: rate,
+            tiers: Vec::new(),
+            asset: FeeAsset::Quote,
+        }
+    }
+
+    /// Fee rate charged for a fill with the provided [`Liquidity`], given the cumulative
+    /// traded quote volume prior to the fill.
+    pub fn rate(&self, liquidity: Liquidity, volume_quote: Decimal) -> Decimal {
+        let (maker, taker) = self
+            .tiers
+            .iter()
+            .filter(|tier| tier.volume_quote <= volume_quote)
+            .max_by_key(|tier| tier.volume_quote)
+            .map_or((self.maker, self.taker), |tier| (tier.maker, tier.taker));
+
+        match liquidity {
+            Liquidity::Maker => maker,
+            Liquidity::Taker => taker,
+        }
+    }
+
+    /// Highest non-negative fee rate that could be charged by this [`FeeSchedule`], across all
+    /// [`Liquidity`] kinds and [`FeeTier`]s.
+    ///
+    /// Used to determine the balance to reserve for fees before it is known how an order will
+    /// be filled.
+    pub fn rate_max(&self) -> Decimal {
+        self.tiers
+            .iter()
+            .flat_map(|tier| [tier.maker, tier.taker])
+            .chain([self.maker, self.taker])
+            .fold(Decimal::ZERO, Decimal::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn tiered() -> FeeSchedule {
+        FeeSchedule {
+            maker: dec!(0.001),
+            taker: dec!(0.002),
+            tiers: vec![
+                FeeTier {
+                    volume_quote: dec!(10_000),
+                    maker: dec!(0.0005),
+                    taker: dec!(0.0015),
+                },
+                FeeTier {
+                    volume_quote: dec!(100_000),
+                    maker: dec!(-0.0001),
+                    taker: dec!(0.001),
+                },
+            ],
+            asset: FeeAsset::Quote,
+        }
+    }
+
+    #[test]
+    fn test_fee_schedule_rate() {
+        struct TestCase {
+            liquidity: Liquidity,
+            volume_quote: Decimal,
+            expected: Decimal,
+        }
+
+        let schedule = tiered();
+
+        let cases = vec![
+            // TC0: base maker rate before any tier is reached
+            TestCase {
+                liquidity: Liquidity::Maker,
+                volume_quote: dec!(0),
+                expected: dec!(0.001),
+            },
+            // TC1: base taker rate before any tier is reached
+            TestCase {
+                liquidity: Liquidity::Taker,
+                volume_quote: dec!(9_999),
+                expected: dec!(0.002),
+            },
+            // TC2: first tier taker rate at exactly the threshold
+            TestCase {
+                liquidity: Liquidity::Taker,
+                volume_quote: dec!(10_000),
+                expected: dec!(0.0015),
+            },
+            // TC3: highest tier reached pays a maker rebate
+            TestCase {
+                liquidity: Liquidity::Maker,
+                volume_quote: dec!(250_000),
+                expected: dec!(-0.0001),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = schedule.rate(test.liquidity, test.volume_quote);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_fee_schedule_rate_max() {
+        assert_eq!(tiered().rate_max(), dec!(0.002));
+        assert_eq!(FeeSchedule::flat(dec!(-0.0002)).rate_max(), dec!(0));
+    }
+
+    #[test]
+    fn test_fee_schedule_deserialise() {
+        let input = r#"
+        {
+            "maker": -0.0001,
+            "taker": 0.00075,
+            "tiers": [{ "volume_quote": 1000000, "maker": -0.0002, "taker": 0.0005 }],
+            "asset": { "type": "other", "asset": "bnb", "price_quote": 600 }
+        }
+        "#;
+
+        let actual = serde_json::from_str::<FeeSchedule>(input).unwrap();
+
+        assert_eq!(
+            actual,
+            FeeSchedule {
+                maker: dec!(-0.0001),
+                taker: dec!(0.00075),
+                tiers: vec![FeeTier {
+                    volume_quote: dec!(1000000),
+                    maker: dec!(-0.0002),
+                    taker: dec!(0.0005),
+                }],
+                asset: FeeAsset::Other {
+                    asset: AssetNameExchange::new("bnb"),
+                    price_quote: dec!(600),
+                },
+            }
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use rand::{Rng, SeedableRng, prelude::IndexedRandom, rngs::StdRng};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::{f64::consts::TAU, path::PathBuf, time::Duration};
+
+/// Default maximum sampled latency, in milliseconds.
+const LATENCY_MAX_MS_DEFAULT: Decimal = Decimal::from_parts(60_000, 0, 0, false, 0);
+
+/// Configuration of the simulated network latency between the
+/// [`MockExchange`](super::MockExchange) and its client.
+///
+/// Each leg is sampled independently from its own [`LatencyModel`], using a random number
+/// generator seeded with `seed` so runs are reproducible.
+///
+/// Sampled latencies are clamped to `max_ms`, so long tails and non-finite samples never stall
+/// the [`MockExchange`](super::MockExchange).
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct LatencyConfig {
+    /// Latency from the client sending a request to the exchange actioning it.
+    pub request: LatencyModel,
+    /// Latency from the exchange actioning a request to the client receiving the response.
+    pub response: LatencyModel,
+    /// Latency from the exchange generating an account notification to the client receiving it.
+    pub notification: LatencyModel,
+    #[serde(default)]
+    pub seed: u64,
+    /// Maximum latency of any leg, in milliseconds.
+    #[serde(default = "default_max_ms")]
+    pub max_ms: Decimal,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            request: LatencyModel::default(),
+            response: LatencyModel::default(),
+            notification: LatencyModel::default(),
+            seed: 0,
+            max_ms: LATENCY_MAX_MS_DEFAULT,
+        }
+    }
+}
+
+fn default_max_ms() -> Decimal {
+    LATENCY_MAX_MS_DEFAULT
+}
+
+impl LatencyConfig {
+    /// Construct a [`LatencyConfig`] with constant latencies equivalent to the legacy
+    /// `latency_ms` round trip configuration.
+    ///
+    /// The request leg is half the round trip, while responses and notifications take the full
+    /// `latency_ms`.
+    pub fn fixed(latency_ms: u64) -> Self {
+        Self {
+            request: LatencyModel::Fixed {
+                ms: Decimal::from(latency_ms) / Decimal::TWO,
+            },
+            response: LatencyModel::Fixed {
+                ms: Decimal::from(latency_ms),
+            },
+            notification: LatencyModel::Fixed {
+                ms: Decimal::from(latency_ms),
+            },
+            seed: 0,
+            max_ms: LATENCY_MAX_MS_DEFAULT.max(Decimal::from(latency_ms)),
+        }
+    }
+
+    /// Maximum latency of any leg, saturating at [`Duration::MAX`].
+    pub fn max_latency(&self) -> Duration {
+        Duration::try_from_secs_f64(to_f64(self.max_ms).max(0.0) / 1000.0).unwrap_or(Duration::MAX)
+    }
+}
+
+/// Distribution a single latency leg is sampled from, in milliseconds.
+///
+/// Sampled latencies are never negative, and never exceed the provided maximum.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LatencyModel {
+    /// Constant latency.
+    Fixed { ms: Decimal },
+    /// Normally distributed latency.
+    Normal {
+        mean_ms: Decimal,
+        std_dev_ms: Decimal,
+    },
+    /// Lognormally distributed latency, where `ln(latency)` is normally distributed with a
+    /// mean of `ln(median_ms)` and a standard deviation of `sigma`.
+    ///
+    /// Produces the long right tail typically observed in real network latencies.
+    LogNormal { median_ms: Decimal, sigma: Decimal },
+    /// See [`EmpiricalLatency`].
+    Empirical(EmpiricalLatency),
+}
+
+impl Default for LatencyModel {
+    fn default() -> Self {
+        Self::Fixed { ms: Decimal::ZERO }
+    }
+}
+
+impl LatencyModel {
+    /// Sample a latency [`Duration`] using the provided random number generator, clamped to
+    /// the provided maximum.
+    pub fn sample<R>(&self, rng: &mut R, max: Duration) -> Duration
+    where
+        R: Rng,
+    {
+        let ms = match self {
+            Self::Fixed { ms } => to_f64(*ms),
+            Self::Normal {
+                mean_ms,
+                std_dev_ms,
+            } => to_f64(*mean_ms) + to_f64(*std_dev_ms) * sample_standard_normal(rng),
+            Self::LogNormal { median_ms, sigma } => {
+                to_f64(*median_ms) * (to_f64(*sigma) * sample_standard_normal(rng)).exp()
+            }
+            Self::Empirical(histogram) => histogram.sample(rng),
+        };
+
+        // Non-finite samples (eg/ an overflowed LogNormal tail) saturate at the maximum
+        Duration::try_from_secs_f64(ms.max(0.0) / 1000.0)
+            .unwrap_or(max)
+            .min(max)
+    }
+}
+
+/// Empirical latency histogram, sampled by choosing a bucket with probability proportional to
+/// its weight.
+///
+/// Can be configured inline with its `buckets`, or loaded from a JSON file containing the
+/// array of buckets via `{ "path": "latency.json" }`.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+#[serde(try_from = "EmpiricalLatencySource")]
+pub struct EmpiricalLatency {
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// [`EmpiricalLatency`] histogram bucket.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub struct LatencyBucket {
+    pub ms: Decimal,
+    pub weight: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EmpiricalLatencySource {
+    Buckets { buckets: Vec<LatencyBucket> },
+    File { path: PathBuf },
+}
+
+impl TryFrom<EmpiricalLatencySource> for EmpiricalLatency {
+    type Error = String;
+
+    fn try_from(value: EmpiricalLatencySource) -> Result<Self, Self::Error> {
+        let buckets = match value {
+            EmpiricalLatencySource::Buckets { buckets } => buckets,
+            EmpiricalLatencySource::File { path } => {
+                Self::read_buckets(&path).map_err(|error| {
+                    format!(
+                        "failed to load EmpiricalLatency from {}: {error}",
+                        path.display()
+                    )
+                })?
+            }
+        };
+
+        if buckets.iter().all(|bucket| bucket.weight == 0) {
+            return Err("EmpiricalLatency requires at least one bucket with weight".to_string());
+        }
+
+        Ok(Self { buckets })
+    }
+}
+
+impl EmpiricalLatency {
+    fn read_buckets(path: &std::path::Path) -> Result<Vec<LatencyBucket>, String> {
+        let contents = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+        serde_json::from_str(&contents).map_err(|error| error.to_string())
+    }
+
+    fn sample<R>(&self, rng: &mut R) -> f64
+    where
+        R: Rng,
+    {
+        self.buckets
+            .choose_weighted(rng, |bucket| bucket.weight)
+            .map(|bucket| to_f64(bucket.ms))
+            .unwrap_or_default()
+    }
+}
+
+/// Seeded [`LatencyConfig`] sampler used by the [`MockExchange`](super::MockExchange).
+#[derive(Debug, Clone)]
+pub struct LatencySampler {
+    pub config: LatencyConfig,
+    max: Duration,
+    rng: StdRng,
+}
+
+impl LatencySampler {
+    pub fn new(config: LatencyConfig) -> Self {
+        let max = config.max_latency();
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self { config, max, rng }
+    }
+
+    /// Sample the client to exchange request latency.
+    pub fn request(&mut self) -> Duration {
+        self.config.request.sample(&mut self.rng, self.max)
+    }
+
+    /// Sample the exchange to client response latency.
+    pub fn response(&mut self) -> Duration {
+        self.config.response.sample(&mut self.rng, self.max)
+    }
+
+    /// Sample the exchange to client notification latency.
+    pub fn notification(&mut self) -> Duration {
+        self.config.notification.sample(&mut self.rng, self.max)
+    }
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+/// Sample the standard normal distribution using the Box-Muller transform.
+fn sample_standard_normal<R>(rng: &mut R) -> f64
+where
+    R: Rng,
+{
+    // Shift uniform sample to (0, 1] to avoid ln(0)
+    let uniform_a = 1.0 - rng.random::<f64>();
+    let uniform_b = rng.random::<f64>();
+    (-2.0 * uniform_a.ln()).sqrt() * (TAU * uniform_b).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn sample_n(model: LatencyModel, seed: u64, n: usize) -> Vec<Duration> {
+        let max = LatencyConfig::default().max_latency();
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n).map(|_| model.sample(&mut rng, max)).collect()
+    }
+
+    #[test]
+    fn test_latency_model_sample() {
+        // Fixed
+        let samples = sample_n(LatencyModel::Fixed { ms: dec!(50) }, 0, 10);
+        assert!(samples.iter().all(|ms| *ms == Duration::from_millis(50)));
+
+        // Normal is never negative, even with a large standard deviation
+        let normal = LatencyModel::Normal {
+            mean_ms: dec!(1),
+            std_dev_ms: dec!(100),
+        };
+        let samples = sample_n(normal.clone(), 1, 1000);
+        assert!(samples.contains(&Duration::ZERO));
+        assert!(samples.iter().any(|ms| *ms > Duration::from_millis(50)));
+
+        // Same seed produces the same samples
+        assert_eq!(sample_n(normal.clone(), 7, 100), sample_n(normal, 7, 100));
+
+        // LogNormal median is approximately median_ms
+        let mut samples = sample_n(
+            LatencyModel::LogNormal {
+                median_ms: dec!(20),
+                sigma: dec!(0.5),
+            },
+            2,
+            1001,
+        );
+        samples.sort_unstable();
+        let median = samples[500].as_secs_f64() * 1000.0;
+        assert!((18.0..22.0).contains(&median), "median: {median}");
+
+        // Empirical only produces latencies of weighted buckets
+        let empirical = LatencyModel::Empirical(EmpiricalLatency {
+            buckets: vec![
+                LatencyBucket {
+                    ms: dec!(5),
+                    weight: 3,
+                },
+                LatencyBucket {
+                    ms: dec!(100),
+                    weight: 1,
+                },
+                LatencyBucket {
+                    ms: dec!(1000),
+                    weight: 0,
+                },
+            ],
+        });
+        let samples = sample_n(empirical, 3, 1000);
+        assert!(samples.contains(&Duration::from_millis(5)));
+        assert!(samples.contains(&Duration::from_millis(100)));
+        assert!(!samples.contains(&Duration::from_millis(1000)));
+    }
+
+    #[test]
+    fn test_latency_model_sample_clamped_to_max() {
+        let max = Duration::from_millis(100);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // Latencies beyond the maximum are clamped
+        let fixed = LatencyModel::Fixed { ms: dec!(250) };
+        assert_eq!(fixed.sample(&mut rng, max), max);
+
+        // Infinite samples saturate at the maximum rather than panicking
+        let log_normal = LatencyModel::LogNormal {
+            median_ms: dec!(20),
+            sigma: Decimal::MAX,
+        };
+        assert!((0..100).all(|_| log_normal.sample(&mut rng, max) <= max));
+
+        // Non-finite samples never panic
+        let normal = LatencyModel::Normal {
+            mean_ms: Decimal::MAX,
+            std_dev_ms: Decimal::MAX,
+        };
+        assert!((0..100).all(|_| normal.sample(&mut rng, max) <= max));
+
+        // Unrepresentable maximums saturate at Duration::MAX
+        let config = LatencyConfig {
+            max_ms: Decimal::MAX,
+            ..LatencyConfig::default()
+        };
+        assert_eq!(config.max_latency(), Duration::MAX);
+    }
+
+    #[test]
+    fn test_latency_config_deserialise() {
+        let path = std::env::temp_dir().join("barter_execution_test_latency_histogram.json");
+        std::fs::write(
+            &path,
+            r#"[{ "ms": 10, "weight": 9 }, { "ms": 250, "weight": 1 }]"#,
+        )
+        .unwrap();
+
+        let input = format!(
+            r#"
+            {{
+                "request": {{ "type": "normal", "mean_ms": 20, "std_dev_ms": 5 }},
+                "response": {{ "type": "log_normal", "median_ms": 20, "sigma": 0.5 }},
+                "notification": {{ "type": "empirical", "path": {:?} }},
+                "seed": 42
+            }}
+            "#,
+            path.display().to_string()
+        );
+
+        let actual = serde_json::from_str::<LatencyConfig>(&input).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            actual,
+            LatencyConfig {
+                request: LatencyModel::Normal {
+                    mean_ms: dec!(20),
+                    std_dev_ms: dec!(5),
+                },
+                response: LatencyModel::LogNormal {
+                    median_ms: dec!(20),
+                    sigma: dec!(0.5),
+                },
+                notification: LatencyModel::Empirical(EmpiricalLatency {
+                    buckets: vec![
+                        LatencyBucket {
+                            ms: dec!(10),
+                            weight: 9,
+                        },
+                        LatencyBucket {
+                            ms: dec!(250),
+                            weight: 1,
+                        },
+                    ],
+                }),
+                seed: 42,
+                max_ms: dec!(60000),
+            }
+        );
+
+        // Missing histogram files fail to deserialise
+        let input = r#"{ "type": "empirical", "path": "/does/not/exist.json" }"#;
+        assert!(serde_json::from_str::<LatencyModel>(input).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+use barter_instrument::Side;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Default maintenance margin rate, as a fraction of position notional value.
+const MAINTENANCE_MARGIN_DEFAULT: Decimal = Decimal::from_parts(5, 0, 0, false, 3);
+
+/// Margin configuration used by the [`MockExchange`](super::MockExchange) to account for
+/// `Perpetual` and `Future` instruments.
+///
+/// Derivative positions lock an initial margin of `notional / leverage` in the instrument
+/// settlement asset, and are liquidated at the mark price if equity falls below the
+/// `maintenance_margin` fraction of their notional value.
+///
+/// Only linear contracts (settled in the quote asset) are supported, orders for inverse contracts
+/// are rejected.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct MarginConfig {
+    #[serde(default)]
+    pub mode: MarginMode,
+    /// Must be positive, validated when constructing the [`MockExchange`](super::MockExchange).
+    pub leverage: Decimal,
+    #[serde(default = "default_maintenance_margin")]
+    pub maintenance_margin: Decimal,
+}
+
+impl Default for MarginConfig {
+    fn default() -> Self {
+        Self {
+            mode: MarginMode::default(),
+            leverage: Decimal::ONE,
+            maintenance_margin: MAINTENANCE_MARGIN_DEFAULT,
+        }
+    }
+}
+
+fn default_maintenance_margin() -> Decimal {
+    MAINTENANCE_MARGIN_DEFAULT
+}
+
+/// Determines the collateral backing a derivative position.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MarginMode {
+    /// All positions sharing a settlement asset are backed by the full balance, and are
+    /// liquidated together.
+    #[default]
+    Cross,
+    /// Each position is only backed by its own initial margin, limiting losses to that margin.
+    Isolated,
+}
+
+/// Simulated derivative position held by the [`MockExchange`](super::MockExchange).
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub struct DerivativePosition {
+    /// Signed number of contracts, positive for long positions and negative for short.
+    pub quantity: Decimal,
+    /// Volume-weighted average entry price.
+    pub price_entry: Decimal,
+    /// Initial margin locked by the position.
+    pub margin: Decimal,
+}
+
+/// Settlement of a fill applied to a [`DerivativePosition`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct FillSettlement {
+    /// PnL realised by any position quantity closed by the fill.
+    pub pnl_realised: Decimal,
+    /// Change in the initial margin locked by the position (negative if released).
+    pub margin_delta: Decimal,
+}
+
+impl DerivativePosition {
+    /// Returns true if the position has no quantity.
+    pub fn is_flat(&self) -> bool {
+        self.quantity.is_zero()
+    }
+
+    /// Number of contracts of a fill with the provided [`Side`] and quantity that would reduce
+    /// the position, rather than increase it.
+    pub fn quantity_reducible(&self, side: Side, quantity: Decimal) -> Decimal {
+        match side {
+            Side::Buy if self.quantity < Decimal::ZERO => quantity.min(-self.quantity),
+            Side::Sell if self.quantity > Decimal::ZERO => quantity.min(self.quantity),
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Apply a fill to the position, increasing, reducing, closing or flipping it.
+    pub fn apply_fill(
+        &mut self,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        contract_size: Decimal,
+        leverage: Decimal,
+    ) -> FillSettlement {
+        let quantity = quantity.abs();
+        let quantity_closed = self.quantity_reducible(side, quantity);
+        let quantity_opened = quantity - quantity_closed;
+
+        let mut settlement = FillSettlement::default();
+
+        if quantity_closed > Decimal::ZERO {
+            let quantity_abs = self.quantity.abs();
+            let margin_released = self.margin * quantity_closed / quantity_abs;
+
+            settlement.pnl_realised = match side {
+                // Buy closes a short position
+                Side::Buy => (self.price_entry - price) * quantity_closed * contract_size,
+                // Sell closes a long position
+                Side::Sell => (price - self.price_entry) * quantity_closed * contract_size,
+            };
+            settlement.margin_delta -= margin_released;
+
+            self.margin -= margin_released;
+            self.quantity += match side {
+                Side::Buy => quantity_closed,
+                Side::Sell => -quantity_closed,
+            };
+            if self.quantity.is_zero() {
+                self.price_entry = Decimal::ZERO;
+                self.margin = Decimal::ZERO;
+            }
+        }
+
+        if quantity_opened > Decimal::ZERO {
+            let quantity_abs = self.quantity.abs();
+            let margin_locked = price * quantity_opened * contract_size / leverage;
+
+            self.price_entry = (self.price_entry * quantity_abs + price * quantity_opened)
+                / (quantity_abs + quantity_opened);
+            self.quantity += match side {
+                Side::Buy => quantity_opened,
+                Side::Sell => -quantity_opened,
+            };
+            self.margin += margin_locked;
+            settlement.margin_delta += margin_locked;
+        }
+
+        settlement
+    }
+
+    /// Unrealised PnL from closing the position at the provided mark price.
+    pub fn pnl_unrealised(&self, price_mark: Decimal, contract_size: Decimal) -> Decimal {
+        (price_mark - self.price_entry) * self.quantity * contract_size
+    }
+
+    /// Maintenance margin required to keep the position open at the provided mark price.
+    pub fn margin_maintenance(
+        &self,
+        price_mark: Decimal,
+        contract_size: Decimal,
+        maintenance_margin: Decimal,
+    ) -> Decimal {
+        price_mark * self.quantity.abs() * contract_size * maintenance_margin
+    }
+
+    /// Price at which the position equity (initial margin + unrealised PnL) would be zero.
+    pub fn price_bankruptcy(&self, contract_size: Decimal) -> Decimal {
+        if self.is_flat() {
+            return self.price_entry;
+        }
+
+        self.price_entry - self.margin / (self.quantity * contract_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_derivative_position_apply_fill() {
+        struct TestCase {
+            initial: DerivativePosition,
+            side: Side,
+            price: Decimal,
+            quantity: Decimal,
+            expected: DerivativePosition,
+            expected_settlement: FillSettlement,
+        }
+
+        let long = DerivativePosition {
+            quantity: dec!(2),
+            price_entry: dec!(100),
+            margin: dec!(20),
+        };
+
+        let cases = vec![
+            // TC0: open long from flat, locking 10x leveraged margin
+            TestCase {
+                initial: DerivativePosition::default(),
+                side: Side::Buy,
+                price: dec!(100),
+                quantity: dec!(2),
+                expected: long,
+                expected_settlement: FillSettlement {
+                    pnl_realised: dec!(0),
+                    margin_delta: dec!(20),
+                },
+            },
+            // TC1: increase long, averaging entry price
+            TestCase {
+                initial: long,
+                side: Side::Buy,
+                price: dec!(130),
+                quantity: dec!(1),
+                expected: DerivativePosition {
+                    quantity: dec!(3),
+                    price_entry: dec!(110),
+                    margin: dec!(33),
+                },
+                expected_settlement: FillSettlement {
+                    pnl_realised: dec!(0),
+                    margin_delta: dec!(13),
+                },
+            },
+            // TC2: reduce long in profit, releasing proportional margin
+            TestCase {
+                initial: long,
+                side: Side::Sell,
+                price: dec!(120),
+                quantity: dec!(1),
+                expected: DerivativePosition {
+                    quantity: dec!(1),
+                    price_entry: dec!(100),
+                    margin: dec!(10),
+                },
+                expected_settlement: FillSettlement {
+                    pnl_realised: dec!(20),
+                    margin_delta: dec!(-10),
+                },
+            },
+            // TC3: flip long to short at a loss
+            TestCase {
+                initial: long,
+                side: Side::Sell,
+                price: dec!(90),
+                quantity: dec!(3),
+                expected: DerivativePosition {
+                    quantity: dec!(-1),
+                    price_entry: dec!(90),
+                    margin: dec!(9),
+                },
+                expected_settlement: FillSettlement {
+                    pnl_realised: dec!(-20),
+                    margin_delta: dec!(-11),
+                },
+            },
+            // TC4: close short exactly in profit
+            TestCase {
+                initial: DerivativePosition {
+                    quantity: dec!(-2),
+                    price_entry: dec!(100),
+                    margin: dec!(20),
+                },
+                side: Side::Buy,
+                price: dec!(80),
+                quantity: dec!(2),
+                expected: DerivativePosition::default(),
+                expected_settlement: FillSettlement {
+                    pnl_realised: dec!(40),
+                    margin_delta: dec!(-20),
+                },
+            },
+        ];
+
+        for (index, mut test) in cases.into_iter().enumerate() {
+            let settlement =
+                test.initial
+                    .apply_fill(test.side, test.price, test.quantity, dec!(1), dec!(10));
+            assert_eq!(test.initial, test.expected, "TC{index} failed");
+            assert_eq!(settlement, test.expected_settlement, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_derivative_position_price_bankruptcy() {
+        // 10x long loses its margin after a 10% fall
+        let long = DerivativePosition {
+            quantity: dec!(2),
+            price_entry: dec!(100),
+            margin: dec!(20),
+        };
+        assert_eq!(long.price_bankruptcy(dec!(1)), dec!(90));
+
+        // 10x short loses its margin after a 10% rise
+        let short = DerivativePosition {
+            quantity: dec!(-2),
+            ..long
+        };
+        assert_eq!(short.price_bankruptcy(dec!(1)), dec!(110));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::order::TriggerSource;
+use barter_data::{
+    books::{Level, OrderBook},
+    event::DataKind,
+};
+use barter_instrument::Side;
+use chrono::{DateTime, Utc};
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+
+/// Prevailing market state of an instrument, as observed by the
+/// [`MockExchange`](super::MockExchange) via its market data feed.
+///
+/// Used to determine the price marketable orders are filled at, and whether resting orders
+/// have been crossed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MarketState {
+    pub book: OrderBook,
+    pub last_traded_price: Option<Decimal>,
+    /// Latest perpetual funding rate.
+    pub funding_rate: Option<Decimal>,
+    /// Time the latest `funding_rate` is next settled at, if it has not been settled yet.
+    pub time_next_funding: Option<DateTime<Utc>>,
+}
+
+impl MarketState {
+    /// Update the [`MarketState`] from a new [`DataKind`] market event.
+    ///
+    /// `OrderBookL1` snapshots replace the book with their top of book levels, while
+    /// `OrderBookEvent`s are applied to the existing book.
+    pub fn update(&mut self, kind: &DataKind) {
+        match kind {
+            DataKind::Trade(trade) => {
+                if let Some(price) = Decimal::from_f64(trade.price) {
+                    self.last_traded_price = Some(price);
+                }
+            }
+            DataKind::OrderBookL1(l1) => {
+                self.book = OrderBook::new(
+                    self.book.sequence(),
+                    Some(l1.last_update_time),
+                    l1.best_bid,
+                    l1.best_ask,
+                );
+            }
+            DataKind::OrderBook(event) => self.book.update(event),
+            DataKind::FundingRate(funding) => {
+                if let Some(rate) = Decimal::from_f64(funding.rate) {
+                    self.funding_rate = Some(rate);
+                    self.time_next_funding = Some(funding.time_next_funding);
+                }
+            }
+            DataKind::Candle(_) | DataKind::Liquidation(_) => {}
+        }
+    }
+
+    /// Best bid price, if the book has any bids.
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.book.bids().best().map(|level| level.price)
+    }
+
+    /// Best ask price, if the book has any asks.
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.book.asks().best().map(|level| level.price)
+    }
+
+    /// Price an order with the provided [`Side`] would be filled at if it took liquidity.
+    ///
+    /// Buys are filled at the best ask, and sells at the best bid. If the opposing side of the
+    /// book is empty, the last traded price is used.
+    pub fn price_taker(&self, side: Side) -> Option<Decimal> {
+        let best_opposing = match side {
+            Side::Buy => self.best_ask(),
+            Side::Sell => self.best_bid(),
+        };
+
+        best_opposing.or(self.last_traded_price)
+    }
+
+    /// Price used to mark open positions to market.
+    ///
+    /// Uses the mid price if both sides of the book are populated, otherwise the last traded
+    /// price, falling back to whichever side of the book is populated.
+    pub fn price_mark(&self) -> Option<Decimal> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+            (bid, ask) => self.last_traded_price.or(bid).or(ask),
+        }
+    }
+
+    /// Price a conditional order with the provided [`TriggerSource`] is triggered by.
+    ///
+    /// The [`MockExchange`](super::MockExchange) does not receive index prices, so
+    /// [`TriggerSource::Index`] orders are triggered by the mark price.
+    pub fn price_trigger(&self, source: TriggerSource) -> Option<Decimal> {
+        match source {
+            TriggerSource::Last => self.last_traded_price,
+            TriggerSource::Mark | TriggerSource::Index => self.price_mark(),
+        }
+    }
+
+    /// Displayed [`Level`]s on the opposite side of the book to an order with the provided
+    /// [`Side`], ordered from best to worst price.
+    pub fn levels_opposing(&self, side: Side) -> &[Level] {
+        match side {
+            Side::Buy => self.book.asks().levels(),
+            Side::Sell => self.book.bids().levels(),
+        }
+    }
+
+    /// Displayed amount resting at the provided price on the same side of the book as an order
+    /// with the provided [`Side`], if the price level is in the book.
+    pub fn amount_at(&self, side: Side, price: Decimal) -> Option<Decimal> {
+        let levels = match side {
+            Side::Buy => self.book.bids().levels(),
+            Side::Sell => self.book.asks().levels(),
+        };
+
+        levels
+            .iter()
+            .find(|level| level.price == price)
+            .map(|level| level.amount)
+    }
+}
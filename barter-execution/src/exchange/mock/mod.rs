@@ -5,115 +5,189 @@ use crate::{
     error::{ApiError, UnindexedApiError, UnindexedOrderError},
     exchange::mock::{
         account::AccountState,
+        expiry::ExpirySettlement,
+        faults::{Fault, FaultInjector},
+        fees::{FeeAsset, FeeSchedule, Liquidity},
+        latency::{LatencyConfig, LatencySampler},
+        margin::{DerivativePosition, MarginConfig, MarginMode},
+        market::MarketState,
         request::{MockExchangeRequest, MockExchangeRequestKind},
+        trigger::TriggerState,
     },
+    funding::FundingPayment,
     order::{
-        Order, OrderKind, UnindexedOrder,
-        id::OrderId,
-        request::{OrderRequestCancel, OrderRequestOpen},
-        state::{Cancelled, Open},
+        Order, OrderFlags, OrderKey, OrderKind, TimeInForce, TrailingOffset, UnindexedOrder,
+        id::{ClientOrderId, OrderId, StrategyId},
+        request::{
+            OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, RequestAmend, RequestOpen,
+            UnindexedOrderResponseAmend, UnindexedOrderResponseCancel,
+        },
+        state::{Cancelled, Open, OrderState},
     },
+    slippage::{SlippageConfig, SlippageModel},
     trade::{AssetFees, Trade, TradeId},
 };
+use barter_data::{
+    books::Level,
+    event::{DataKind, MarketEvent},
+};
 use barter_instrument::{
     Side,
-    asset::{QuoteAsset, name::AssetNameExchange},
+    asset::name::AssetNameExchange,
     exchange::ExchangeId,
-    instrument::{Instrument, name::InstrumentNameExchange},
+    instrument::{Instrument, kind::InstrumentKind, name::InstrumentNameExchange},
 };
 use barter_integration::snapshot::Snapshot;
 use chrono::{DateTime, TimeDelta, Utc};
 use fnv::FnvHashMap;
 use futures::stream::BoxStream;
 use itertools::Itertools;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, prelude::FromPrimitive};
 use smol_str::ToSmolStr;
 use std::fmt::Debug;
+use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::{StreamExt, wrappers::BroadcastStream};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub mod account;
+pub mod expiry;
+pub mod faults;
+pub mod fees;
+pub mod latency;
+pub mod margin;
+pub mod market;
 pub mod request;
+pub mod trigger;
+
+/// Invalid [`MockExecutionConfig`] provided to construct a [`MockExchange`].
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum MockExchangeConfigError {
+    /// [`MarginConfig`] leverage must be positive.
+    #[error("MockExchange MarginConfig leverage must be positive: {0}")]
+    Leverage(Decimal),
+}
 
 #[derive(Debug)]
 pub struct MockExchange {
     pub exchange: ExchangeId,
-    pub latency_ms: u64,
-    pub fees_percent: Decimal,
+    pub latency: LatencySampler,
+    pub faults: FaultInjector,
+    pub fee_schedule: FeeSchedule,
+    pub slippage: SlippageConfig,
+    pub margin: MarginConfig,
+    pub expiry_settlement: ExpirySettlement,
     pub request_rx: mpsc::UnboundedReceiver<MockExchangeRequest>,
+    pub market_rx: mpsc::UnboundedReceiver<MarketEvent<InstrumentNameExchange, DataKind>>,
     pub event_tx: broadcast::Sender<UnindexedAccountEvent>,
+    pub disconnect_tx: broadcast::Sender<()>,
+    pub notifications_delayed: Vec<UnindexedAccountEvent>,
     pub instruments: FnvHashMap<InstrumentNameExchange, Instrument<ExchangeId, AssetNameExchange>>,
     pub account: AccountState,
     pub order_sequence: u64,
+    pub trade_sequence: u64,
+    pub volume_quote: Decimal,
+    pub queue_ahead: FnvHashMap<ClientOrderId, Decimal>,
+    /// Conditional orders resting untriggered.
+    pub triggers: FnvHashMap<ClientOrderId, TriggerState>,
+    /// Resting orders with [`OrderFlags`] that constrain how they may affect a position.
+    pub flags: FnvHashMap<ClientOrderId, OrderFlags>,
     pub time_exchange_latest: DateTime<Utc>,
+    pub markets: FnvHashMap<InstrumentNameExchange, MarketState>,
+    pub positions: FnvHashMap<InstrumentNameExchange, DerivativePosition>,
+    /// Send responses and notifications immediately rather than after a sampled latency, so the
+    /// [`MockExchange`] can be driven in lock-step (eg/ by a backtest).
+    pub synchronous: bool,
 }
 
 impl MockExchange {
+    /// Construct a new [`MockExchange`] from the provided [`MockExecutionConfig`].
+    ///
+    /// Returns an error if the [`MockExecutionConfig`] is invalid (eg/ non-positive leverage).
     pub fn new(
         config: MockExecutionConfig,
         request_rx: mpsc::UnboundedReceiver<MockExchangeRequest>,
+        market_rx: mpsc::UnboundedReceiver<MarketEvent<InstrumentNameExchange, DataKind>>,
         event_tx: broadcast::Sender<UnindexedAccountEvent>,
+        disconnect_tx: broadcast::Sender<()>,
         instruments: FnvHashMap<InstrumentNameExchange, Instrument<ExchangeId, AssetNameExchange>>,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, MockExchangeConfigError> {
+        if config.margin.leverage <= Decimal::ZERO {
+            return Err(MockExchangeConfigError::Leverage(config.margin.leverage));
+        }
+
+        let account = AccountState::from(config.initial_state);
+
+        // Conditional orders in the initial state that have not been filled are untriggered
+        let triggers = account
+            .orders_open()
+            .filter(|order| order.kind.is_conditional() && order.state.filled_quantity.is_zero())
+            .map(|order| (order.key.cid.clone(), TriggerState::default()))
+            .collect();
+
+        Ok(Self {
             exchange: config.mocked_exchange,
-            latency_ms: config.latency_ms,
-            fees_percent: config.fees_percent,
+            latency: LatencySampler::new(
+                config
+                    .latency
+                    .unwrap_or_else(|| LatencyConfig::fixed(config.latency_ms)),
+            ),
+            faults: FaultInjector::new(config.faults),
+            fee_schedule: config
+                .fee_schedule
+                .unwrap_or_else(|| FeeSchedule::flat(config.fees_percent)),
+            slippage: config.slippage,
+            margin: config.margin,
+            expiry_settlement: config.expiry_settlement,
             request_rx,
+            market_rx,
             event_tx,
+            disconnect_tx,
+            notifications_delayed: Vec::new(),
             instruments,
-            account: AccountState::from(config.initial_state),
+            account,
             order_sequence: 0,
+            trade_sequence: 0,
+            volume_quote: Decimal::ZERO,
+            queue_ahead: FnvHashMap::default(),
+            triggers,
+            flags: FnvHashMap::default(),
             time_exchange_latest: Default::default(),
+            markets: FnvHashMap::default(),
+            positions: FnvHashMap::default(),
+            synchronous: false,
+        })
+    }
+
+    /// Send responses and notifications immediately rather than after a sampled latency.
+    ///
+    /// Sampled request latency still advances the exchange time of actioned requests.
+    pub fn with_synchronous_delivery(self) -> Self {
+        Self {
+            synchronous: true,
+            ..self
         }
     }
 
+    /// Run the [`MockExchange`], actioning client requests and simulating fills using the
+    /// market data feed until the client request channel is dropped.
+    ///
+    /// Client requests are prioritised over market events when both are ready. If the market
+    /// data feed ends, the [`MockExchange`] continues to action client requests.
     pub async fn run(mut self) {
-        while let Some(request) = self.request_rx.recv().await {
-            self.update_time_exchange(request.time_request);
+        loop {
+            tokio::select! {
+                biased;
 
-            match request.kind {
-                MockExchangeRequestKind::FetchAccountSnapshot { response_tx } => {
-                    let snapshot = self.account_snapshot();
-                    self.respond_with_latency(response_tx, snapshot);
-                }
-                MockExchangeRequestKind::FetchBalances { response_tx } => {
-                    let balances = self.account.balances().cloned().collect();
-                    self.respond_with_latency(response_tx, balances);
-                }
-                MockExchangeRequestKind::FetchOrdersOpen { response_tx } => {
-                    let orders_open = self.account.orders_open().cloned().collect();
-                    self.respond_with_latency(response_tx, orders_open);
-                }
-                MockExchangeRequestKind::FetchTrades {
-                    response_tx,
-                    time_since,
-                } => {
-                    let trades = self.account.trades(time_since).cloned().collect();
-                    self.respond_with_latency(response_tx, trades);
-                }
-                MockExchangeRequestKind::CancelOrder {
-                    response_tx: _,
-                    request,
-                } => {
-                    error!(
-                        exchange = %self.exchange,
-                        ?request,
-                        "MockExchange received cancel request but only Market orders are supported"
-                    );
+                request = self.request_rx.recv() => {
+                    let Some(request) = request else {
+                        break;
+                    };
+                    self.process_request(request);
                 }
-                MockExchangeRequestKind::OpenOrder {
-                    response_tx,
-                    request,
-                } => {
-                    let (response, notifications) = self.open_order(request);
-                    self.respond_with_latency(response_tx, response);
-
-                    if let Some(notifications) = notifications {
-                        self.account.ack_trade(notifications.trade.clone());
-                        self.send_notifications_with_latency(notifications);
-                    }
+
+                Some(event) = self.market_rx.recv() => {
+                    self.process_market_event_and_notify(event);
                 }
             }
         }
@@ -121,11 +195,101 @@ impl MockExchange {
         info!(exchange = %self.exchange, "MockExchange shutting down");
     }
 
+    /// Action a client [`MockExchangeRequest`], sending the response and any resulting
+    /// notifications to the client.
+    pub fn process_request(&mut self, request: MockExchangeRequest) {
+        self.update_time_exchange(request.time_request);
+
+        let notifications = self.settle_expiries();
+        self.send_notifications_with_latency(notifications);
+
+        match request.kind {
+            MockExchangeRequestKind::FetchAccountSnapshot { response_tx } => {
+                let snapshot = self.account_snapshot();
+                self.respond_with_latency(response_tx, snapshot);
+            }
+            MockExchangeRequestKind::FetchBalances { response_tx } => {
+                let balances = self.account.balances().cloned().collect();
+                self.respond_with_latency(response_tx, balances);
+            }
+            MockExchangeRequestKind::FetchOrdersOpen { response_tx } => {
+                let orders_open = self.account.orders_open().cloned().collect();
+                self.respond_with_latency(response_tx, orders_open);
+            }
+            MockExchangeRequestKind::FetchTrades {
+                response_tx,
+                time_since,
+            } => {
+                let trades = self.account.trades(time_since).cloned().collect();
+                self.respond_with_latency(response_tx, trades);
+            }
+            MockExchangeRequestKind::CancelOrder {
+                response_tx,
+                request,
+            } => {
+                let fault = self.faults.next_fault();
+                let side = self
+                    .account
+                    .order_open(&request.key.cid)
+                    .map_or(Side::Buy, |order| order.side);
+
+                let (response, notifications) = match fault
+                    .and_then(|fault| self.fault_rejection(fault, &request.key.instrument, side))
+                {
+                    Some(error) => (
+                        UnindexedOrderResponseCancel {
+                            key: request.key,
+                            state: Err(UnindexedOrderError::Rejected(error)),
+                        },
+                        vec![],
+                    ),
+                    None => self.cancel_order(request),
+                };
+
+                self.respond_with_fault(response_tx, response, fault);
+                self.send_notifications_with_fault(notifications, fault);
+            }
+            MockExchangeRequestKind::OpenOrder {
+                response_tx,
+                request,
+            } => {
+                let fault = self.faults.next_fault();
+
+                let (response, notifications) = match fault.and_then(|fault| {
+                    self.fault_rejection(fault, &request.key.instrument, request.state.side)
+                }) {
+                    Some(error) => (build_open_order_err_response(request, error), vec![]),
+                    None => self.open_order(request),
+                };
+
+                self.respond_with_fault(response_tx, response, fault);
+                self.send_notifications_with_fault(notifications, fault);
+            }
+            MockExchangeRequestKind::AmendOrder {
+                response_tx,
+                request,
+            } => {
+                let fault = self.faults.next_fault();
+
+                let (response, notifications) = match fault.and_then(|fault| {
+                    self.fault_rejection(fault, &request.key.instrument, request.state.side)
+                }) {
+                    Some(error) => (build_amend_order_err_response(request, error), vec![]),
+                    None => self.amend_order(request),
+                };
+
+                self.respond_with_fault(response_tx, response, fault);
+                self.send_notifications_with_fault(notifications, fault);
+            }
+        }
+    }
+
     fn update_time_exchange(&mut self, time_request: DateTime<Utc>) {
-        let client_to_exchange_latency = self.latency_ms / 2;
+        let client_to_exchange_latency = self.latency.request();
 
-        self.time_exchange_latest = time_request
-            .checked_add_signed(TimeDelta::milliseconds(client_to_exchange_latency as i64))
+        self.time_exchange_latest = TimeDelta::from_std(client_to_exchange_latency)
+            .ok()
+            .and_then(|latency| time_request.checked_add_signed(latency))
             .unwrap_or(time_request);
 
         self.account.update_time_exchange(self.time_exchange_latest)
@@ -159,6 +323,7 @@ impl MockExchange {
             .map(|(instrument, orders)| InstrumentAccountSnapshot {
                 instrument,
                 orders: orders.into_iter().collect(),
+                position: None,
             })
             .collect();
 
@@ -169,22 +334,90 @@ impl MockExchange {
         }
     }
 
-    /// Sends the provided `Response` via the [`oneshot::Sender`] after waiting for the latency
-    /// [`Duration`].
+    /// Determine the [`UnindexedApiError`] an order request is rejected with if the provided
+    /// [`Fault`] is a rejection.
+    fn fault_rejection(
+        &self,
+        fault: Fault,
+        instrument: &InstrumentNameExchange,
+        side: Side,
+    ) -> Option<UnindexedApiError> {
+        const REASON: &str = "MockExchange injected fault";
+
+        match fault {
+            Fault::RateLimit => Some(ApiError::RateLimit),
+            Fault::BalanceInsufficient => {
+                let instrument = self.find_instrument_data(instrument).ok()?;
+                let (asset, _) =
+                    self.balance_required(instrument, side, Decimal::ZERO, Decimal::ZERO);
+                Some(ApiError::BalanceInsufficient(
+                    asset.clone(),
+                    REASON.to_string(),
+                ))
+            }
+            Fault::OrderRejected => Some(ApiError::OrderRejected(REASON.to_string())),
+            Fault::Timeout | Fault::Disconnect | Fault::Duplicate | Fault::Reorder => None,
+        }
+    }
+
+    /// Sends the provided `Response` via the [`oneshot::Sender`], unless the provided [`Fault`]
+    /// is a [`Fault::Timeout`].
+    ///
+    /// Timed out responses are never sent, but the [`oneshot::Sender`] is held until the client
+    /// stops waiting so it does not observe a disconnection.
+    fn respond_with_fault<Response>(
+        &mut self,
+        mut response_tx: oneshot::Sender<Response>,
+        response: Response,
+        fault: Option<Fault>,
+    ) where
+        Response: Send + 'static,
+    {
+        if matches!(fault, Some(Fault::Timeout)) {
+            tokio::spawn(async move { response_tx.closed().await });
+        } else {
+            self.respond_with_latency(response_tx, response);
+        }
+    }
+
+    /// Sends the provided [`UnindexedAccountEvent`] notifications, applying the provided
+    /// notification [`Fault`] (if any).
+    fn send_notifications_with_fault(
+        &mut self,
+        notifications: Vec<UnindexedAccountEvent>,
+        fault: Option<Fault>,
+    ) {
+        match fault {
+            Some(Fault::Disconnect) => {
+                // No receivers means there is no account stream to drop
+                let _ = self.disconnect_tx.send(());
+                self.send_notifications_with_latency(notifications);
+            }
+            Some(Fault::Duplicate) => {
+                let duplicated = notifications
+                    .into_iter()
+                    .flat_map(|notification| [notification.clone(), notification])
+                    .collect();
+                self.send_notifications_with_latency(duplicated);
+            }
+            Some(Fault::Reorder) => self.notifications_delayed.extend(notifications),
+            _ => self.send_notifications_with_latency(notifications),
+        }
+    }
+
+    /// Sends the provided `Response` via the [`oneshot::Sender`] after waiting for a sampled
+    /// response latency [`Duration`].
     ///
     /// Used to simulate network latency between the exchange and client.
     fn respond_with_latency<Response>(
-        &self,
+        &mut self,
         response_tx: oneshot::Sender<Response>,
         response: Response,
     ) where
         Response: Send + 'static,
     {
         let exchange = self.exchange;
-        let latency = std::time::Duration::from_millis(self.latency_ms);
-
-        tokio::spawn(async move {
-            tokio::time::sleep(latency).await;
+        let send = move |response_tx: oneshot::Sender<Response>, response| {
             if response_tx.send(response).is_err() {
                 error!(
                     %exchange,
@@ -192,40 +425,154 @@ impl MockExchange {
                     "MockExchange failed to send oneshot response to client"
                 );
             }
+        };
+
+        if self.synchronous {
+            send(response_tx, response);
+            return;
+        }
+
+        let latency = self.latency.response();
+        tokio::spawn(async move {
+            tokio::time::sleep(latency).await;
+            send(response_tx, response);
         });
     }
 
-    /// Sends the provided `OpenOrderNotifications` via the `MockExchanges`
-    /// `broadcast::Sender<UnindexedAccountEvent>` after waiting for the latency
-    /// [`Duration`].
+    /// Sends the provided [`UnindexedAccountEvent`] notifications via the `MockExchanges`
+    /// `broadcast::Sender<UnindexedAccountEvent>` after waiting for a sampled notification
+    /// latency [`Duration`].
     ///
     /// Used to simulate network latency between the exchange and client.
-    fn send_notifications_with_latency(&self, notifications: OpenOrderNotifications) {
-        let balance = self.build_account_event(notifications.balance);
-        let trade = self.build_account_event(notifications.trade);
+    ///
+    /// Any notifications delayed by a [`Fault::Reorder`] are sent after the provided batch.
+    fn send_notifications_with_latency(&mut self, mut notifications: Vec<UnindexedAccountEvent>) {
+        if notifications.is_empty() {
+            return;
+        }
+
+        notifications.append(&mut self.notifications_delayed);
 
         let exchange = self.exchange;
-        let latency = std::time::Duration::from_millis(self.latency_ms);
         let tx = self.event_tx.clone();
+        let send = move |notifications: Vec<UnindexedAccountEvent>| {
+            for notification in notifications {
+                if tx.send(notification).is_err() {
+                    error!(
+                        %exchange,
+                        kind = "UnindexedAccountEvent",
+                        "MockExchange failed to send AccountEvent notification to client"
+                    );
+                }
+            }
+        };
+
+        if self.synchronous {
+            send(notifications);
+            return;
+        }
+
+        let latency = self.latency.notification();
         tokio::spawn(async move {
             tokio::time::sleep(latency).await;
+            send(notifications);
+        });
+    }
 
-            if tx.send(balance).is_err() {
-                error!(
-                    %exchange,
-                    kind = "Snapshot<AssetBalance<AssetNameExchange>",
-                    "MockExchange failed to send AccountEvent notification to client"
-                );
+    /// Process a [`MarketEvent`] from the market data feed (see [`Self::process_market_event`]),
+    /// sending any resulting notifications to the client.
+    pub fn process_market_event_and_notify(
+        &mut self,
+        event: MarketEvent<InstrumentNameExchange, DataKind>,
+    ) {
+        let notifications = self.process_market_event(event);
+        self.send_notifications_with_latency(notifications);
+    }
+
+    /// Process a [`MarketEvent`] from the market data feed, updating the instrument
+    /// [`MarketState`] and filling any resting open orders that are crossed by it.
+    ///
+    /// Public trades cross resting orders at or through the traded price (see
+    /// [`Self::match_orders_open`]), while `OrderBookL1` and `OrderBookEvent` updates cross
+    /// resting orders via the opposing best bid or ask, and advance their estimated queue
+    /// position.
+    ///
+    /// Any `Future` instruments that have expired are settled first (see
+    /// [`Self::settle_expiries`]).
+    ///
+    /// `FundingRate` updates are settled against any open `Perpetual` position once their
+    /// funding time is reached (see [`Self::settle_funding`]).
+    ///
+    /// Any untriggered conditional orders whose trigger price is reached are then executed (see
+    /// [`Self::trigger_orders_conditional`]).
+    ///
+    /// Any derivative positions left with insufficient margin at the updated mark price are
+    /// then liquidated (see [`Self::liquidate_positions_under_margin`]).
+    ///
+    /// Returns any [`UnindexedAccountEvent`] notifications that should be sent via the account
+    /// stream.
+    pub fn process_market_event(
+        &mut self,
+        event: MarketEvent<InstrumentNameExchange, DataKind>,
+    ) -> Vec<UnindexedAccountEvent> {
+        if !self.instruments.contains_key(&event.instrument) {
+            return vec![];
+        }
+
+        if event.time_exchange > self.time_exchange_latest {
+            self.time_exchange_latest = event.time_exchange;
+            self.account.update_time_exchange(self.time_exchange_latest);
+        }
+
+        // Settle any expired contracts & funding that is due, before the event updates the
+        // mark price & funding rate
+        let mut notifications = self.settle_expiries();
+        notifications.extend(self.settle_funding(&event.instrument, event.time_exchange));
+
+        let market = self.markets.entry(event.instrument.clone()).or_default();
+        market.update(&event.kind);
+
+        notifications.extend(match &event.kind {
+            DataKind::Trade(trade) => {
+                match (
+                    Decimal::from_f64(trade.price),
+                    Decimal::from_f64(trade.amount),
+                ) {
+                    (Some(price), Some(amount)) => {
+                        self.match_orders_open(&event.instrument, price, amount)
+                    }
+                    _ => vec![],
+                }
             }
+            DataKind::OrderBookL1(_) | DataKind::OrderBook(_) => {
+                let (best_bid, best_ask) = (market.best_bid(), market.best_ask());
+                self.update_queue_positions(&event.instrument);
 
-            if tx.send(trade).is_err() {
-                error!(
-                    %exchange,
-                    kind = "Trade<QuoteAsset, InstrumentNameExchange>",
-                    "MockExchange failed to send AccountEvent notification to client"
-                );
+                let mut notifications = Vec::new();
+                if let Some(best_ask) = best_ask {
+                    notifications.extend(self.match_orders_open_side(
+                        &event.instrument,
+                        Side::Buy,
+                        best_ask,
+                        None,
+                    ));
+                }
+                if let Some(best_bid) = best_bid {
+                    notifications.extend(self.match_orders_open_side(
+                        &event.instrument,
+                        Side::Sell,
+                        best_bid,
+                        None,
+                    ));
+                }
+                notifications
             }
+            DataKind::Candle(_) | DataKind::Liquidation(_) | DataKind::FundingRate(_) => vec![],
         });
+
+        notifications.extend(self.trigger_orders_conditional(&event.instrument));
+        notifications.extend(self.liquidate_positions_under_margin(&event.instrument));
+        notifications
     }
 
     pub fn account_stream(&self) -> BoxStream<'static, UnindexedAccountEvent> {
@@ -243,185 +590,1892 @@ impl MockExchange {
         ))
     }
 
+    /// Cancel a resting [`Order`], releasing any balance it reserved.
+    ///
+    /// Returns the cancel response, as well as any [`UnindexedAccountEvent`] notifications that
+    /// should be sent via the account stream (including an `OrderCancelled` event).
     pub fn cancel_order(
         &mut self,
-        _: OrderRequestCancel<ExchangeId, InstrumentNameExchange>,
-    ) -> Order<ExchangeId, InstrumentNameExchange, Result<Cancelled, UnindexedOrderError>> {
-        unimplemented!()
-    }
+        request: OrderRequestCancel<ExchangeId, InstrumentNameExchange>,
+    ) -> (UnindexedOrderResponseCancel, Vec<UnindexedAccountEvent>) {
+        self.queue_ahead.remove(&request.key.cid);
+        self.triggers.remove(&request.key.cid);
+        self.flags.remove(&request.key.cid);
 
-    pub fn open_order(
-        &mut self,
-        request: OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
-    ) -> (
-        Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>,
-        Option<OpenOrderNotifications>,
-    ) {
-        if let Err(error) = self.validate_order_kind_supported(request.state.kind) {
-            return (build_open_order_err_response(request, error), None);
-        }
+        let Some(order) = self.account.remove_order_open(&request.key.cid) else {
+            let error = self.cancel_order_error(&request);
+            let response = UnindexedOrderResponseCancel {
+                key: request.key,
+                state: Err(UnindexedOrderError::Rejected(error)),
+            };
+            return (response, vec![]);
+        };
 
-        let underlying = match self.find_instrument_data(&request.key.instrument) {
-            Ok(instrument) => instrument.underlying.clone(),
-            Err(error) => return (build_open_order_err_response(request, error), None),
+        let instrument = self
+            .find_instrument_data(&order.key.instrument)
+            .expect("MockExchange only accepts orders for configured instruments")
+            .clone();
+
+        let quantity_remaining = order.state.quantity_remaining(order.quantity);
+        let balance =
+            self.release_balance(&instrument, order.side, order.price, quantity_remaining);
+
+        let cancelled = Cancelled {
+            id: order.state.id.clone(),
+            time_exchange: self.time_exchange(),
         };
 
-        let time_exchange = self.time_exchange();
+        self.account.insert_order_cancelled(Order {
+            key: order.key.clone(),
+            side: order.side,
+            price: order.price,
+            quantity: order.quantity,
+            kind: order.kind,
+            time_in_force: order.time_in_force,
+            state: cancelled.clone(),
+        });
 
-        let balance_change_result = match request.state.side {
-            Side::Buy => {
-                // Buying Instrument requires sufficient QuoteAsset Balance
-                let current = self
-                    .account
-                    .balance_mut(&underlying.quote)
-                    .expect("MockExchange has Balance for all configured Instrument assets");
+        let response = UnindexedOrderResponseCancel {
+            key: order.key,
+            state: Ok(cancelled),
+        };
 
-                // Currently we only supported MarketKind orders, so they should be identical
-                assert_eq!(current.balance.total, current.balance.free);
+        let notifications = vec![
+            self.build_account_event(balance),
+            self.build_account_event(response.clone()),
+        ];
 
-                let order_value_quote = request.state.price * request.state.quantity.abs();
-                let order_fees_quote = order_value_quote * self.fees_percent;
-                let quote_required = order_value_quote + order_fees_quote;
+        (response, notifications)
+    }
 
-                let maybe_new_balance = current.balance.free - quote_required;
+    fn cancel_order_error(
+        &self,
+        request: &OrderRequestCancel<ExchangeId, InstrumentNameExchange>,
+    ) -> UnindexedApiError {
+        if self.account.order_cancelled(&request.key.cid).is_some() {
+            return ApiError::OrderAlreadyCancelled;
+        }
 
-                if maybe_new_balance >= Decimal::ZERO {
-                    current.balance.free = maybe_new_balance;
-                    current.balance.total = maybe_new_balance;
-                    current.time_exchange = time_exchange;
+        let order_fully_filled = request.state.id.as_ref().is_some_and(|id| {
+            self.account
+                .trades(DateTime::<Utc>::MIN_UTC)
+                .any(|trade| trade.order_id == *id)
+        });
 
-                    Ok((current.clone(), AssetFees::quote_fees(order_fees_quote)))
-                } else {
-                    Err(ApiError::BalanceInsufficient(
-                        underlying.quote,
-                        format!(
-                            "Available Balance: {}, Required Balance inc. fees: {}",
-                            current.balance.free, quote_required
-                        ),
-                    ))
-                }
-            }
-            Side::Sell => {
-                // Selling Instrument requires sufficient BaseAsset Balance
-                let current = self
-                    .account
-                    .balance_mut(&underlying.quote)
-                    .expect("MockExchange has Balance for all configured Instrument assets");
+        if order_fully_filled {
+            ApiError::OrderAlreadyFullyFilled
+        } else {
+            ApiError::OrderRejected(format!(
+                "MockExchange has no open order with ClientOrderId: {}",
+                request.key.cid
+            ))
+        }
+    }
 
-                // Currently we only supported MarketKind orders, so they should be identical
-                assert_eq!(current.balance.total, current.balance.free);
+    /// Amend the price and/or total quantity of a resting [`Order`], re-reserving the balance
+    /// it requires.
+    ///
+    /// Amends that only reduce the quantity retain the order's time priority and queue
+    /// position. Any other amend loses time priority, which is modelled by assigning the
+    /// order a new [`OrderId`].
+    ///
+    /// Amends that change the order side, kind or time in force, reduce the quantity to or
+    /// below the filled quantity, or would cross the prevailing market, are rejected.
+    ///
+    /// Returns the amend response, as well as any [`UnindexedAccountEvent`] notifications that
+    /// should be sent via the account stream.
+    pub fn amend_order(
+        &mut self,
+        request: OrderRequestAmend<ExchangeId, InstrumentNameExchange>,
+    ) -> (UnindexedOrderResponseAmend, Vec<UnindexedAccountEvent>) {
+        let Some(order) = self.account.order_open(&request.key.cid).cloned() else {
+            let error = ApiError::OrderRejected(format!(
+                "MockExchange has no open order with ClientOrderId: {}",
+                request.key.cid
+            ));
+            return (build_amend_order_err_response(request, error), vec![]);
+        };
 
-                let order_value_base = request.state.quantity.abs();
-                let order_fees_base = order_value_base * self.fees_percent;
-                let base_required = order_value_base + order_fees_base;
+        if let Err(error) = self.validate_amend(&order, &request.state) {
+            return (build_amend_order_err_response(request, error), vec![]);
+        }
 
-                let maybe_new_balance = current.balance.free - base_required;
+        let instrument = self
+            .find_instrument_data(&order.key.instrument)
+            .expect("MockExchange only accepts orders for configured instruments")
+            .clone();
 
-                if maybe_new_balance >= Decimal::ZERO {
-                    current.balance.free = maybe_new_balance;
-                    current.balance.total = maybe_new_balance;
-                    current.time_exchange = time_exchange;
+        let RequestAmend {
+            price, quantity, ..
+        } = request.state;
 
-                    let fees_quote = order_fees_base * request.state.price;
+        // Release the existing reservation before reserving the amended order requirements
+        let quantity_remaining = order.state.quantity_remaining(order.quantity);
+        self.release_balance(&instrument, order.side, order.price, quantity_remaining);
 
-                    Ok((current.clone(), AssetFees::quote_fees(fees_quote)))
-                } else {
-                    Err(ApiError::BalanceInsufficient(
-                        underlying.quote,
-                        format!(
-                            "Available Balance: {}, Required Balance inc. fees: {}",
-                            current.balance.free, base_required
-                        ),
-                    ))
-                }
+        let balance = match self.reserve_balance(
+            &instrument,
+            order.side,
+            price,
+            order.state.quantity_remaining(quantity),
+        ) {
+            Ok(balance) => balance,
+            Err(error) => {
+                self.reserve_balance(&instrument, order.side, order.price, quantity_remaining)
+                    .expect("MockExchange can always re-reserve a released balance");
+                return (build_amend_order_err_response(request, error), vec![]);
             }
         };
 
-        let (balance_snapshot, fees) = match balance_change_result {
-            Ok((balance_snapshot, fees)) => (Snapshot(balance_snapshot), fees),
-            Err(error) => return (build_open_order_err_response(request, error), None),
+        let priority_retained = price == order.price && quantity <= order.quantity;
+        let untriggered = self.triggers.contains_key(&order.key.cid);
+
+        let open = Open {
+            id: if priority_retained {
+                order.state.id.clone()
+            } else {
+                self.order_id_sequence_fetch_add()
+            },
+            time_exchange: self.time_exchange(),
+            filled_quantity: order.state.filled_quantity,
         };
 
-        let order_id = self.order_id_sequence_fetch_add();
-        let trade_id = TradeId(order_id.0.clone());
+        if !priority_retained && !untriggered {
+            let queue_ahead = self
+                .markets
+                .get(&order.key.instrument)
+                .and_then(|market| market.amount_at(order.side, price))
+                .unwrap_or_default();
+            self.queue_ahead.insert(order.key.cid.clone(), queue_ahead);
+        }
 
-        let order_response = Order {
-            key: request.key.clone(),
-            side: request.state.side,
-            price: request.state.price,
-            quantity: request.state.quantity,
-            kind: request.state.kind,
-            time_in_force: request.state.time_in_force,
-            state: Ok(Open {
-                id: order_id.clone(),
-                time_exchange: self.time_exchange(),
-                filled_quantity: request.state.quantity,
-            }),
+        let amended = Order {
+            key: order.key,
+            side: order.side,
+            price,
+            quantity,
+            kind: order.kind,
+            time_in_force: order.time_in_force,
+            state: open,
         };
+        self.account.insert_order_open(amended.clone());
 
-        let notifications = OpenOrderNotifications {
-            balance: balance_snapshot,
-            trade: Trade {
-                id: trade_id,
-                order_id: order_id.clone(),
-                instrument: request.key.instrument,
-                strategy: request.key.strategy,
-                time_exchange: self.time_exchange(),
-                side: request.state.side,
-                price: request.state.price,
-                quantity: request.state.quantity,
-                fees,
-            },
+        let response = UnindexedOrderResponseAmend {
+            key: amended.key,
+            side: amended.side,
+            price: amended.price,
+            quantity: amended.quantity,
+            kind: amended.kind,
+            time_in_force: amended.time_in_force,
+            state: Ok(amended.state),
         };
 
-        (order_response, Some(notifications))
+        (response, vec![self.build_account_event(balance)])
     }
 
-    pub fn validate_order_kind_supported(
+    fn validate_amend(
         &self,
-        order_kind: OrderKind,
-    ) -> Result<(), UnindexedOrderError> {
-        if order_kind == OrderKind::Market {
-            Ok(())
+        order: &Order<ExchangeId, InstrumentNameExchange, Open>,
+        amend: &RequestAmend,
+    ) -> Result<(), UnindexedApiError> {
+        if amend.side != order.side
+            || amend.kind != order.kind
+            || amend.time_in_force != order.time_in_force
+        {
+            return Err(ApiError::OrderRejected(
+                "MockExchange cannot amend the side, kind or time in force of an order".to_string(),
+            ));
+        }
+
+        if amend.quantity <= order.state.filled_quantity {
+            return Err(ApiError::OrderRejected(format!(
+                "MockExchange cannot amend quantity to {} with filled quantity: {}",
+                amend.quantity, order.state.filled_quantity
+            )));
+        }
+
+        let resting_limit = !order.kind.is_market() && !self.triggers.contains_key(&order.key.cid);
+        let price_taker = self
+            .markets
+            .get(&order.key.instrument)
+            .and_then(|market| market.price_taker(order.side));
+
+        match price_taker {
+            Some(price_taker) if resting_limit && crosses(order.side, amend.price, price_taker) => {
+                Err(ApiError::OrderRejected(format!(
+                    "MockExchange cannot amend resting order to cross the market at price: {}",
+                    amend.price
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Open a new [`Order`].
+    ///
+    /// `OrderKind::Market` orders are filled immediately against the prevailing [`MarketState`]
+    /// (best ask for buys, best bid for sells, falling back to the last traded price), adjusted
+    /// by the configured [`SlippageModel`]. If no market data has been received for the
+    /// instrument, they are filled at the requested price.
+    ///
+    /// `OrderKind::Limit` orders that cross the prevailing market consume the displayed opposing
+    /// liquidity within their limit price, generating a [`Trade`] per level. If no depth is
+    /// available, they are filled at the slipped market price, bounded by their limit price.
+    /// Any quantity remaining rests in the [`AccountState`] open orders, reserving the balance
+    /// it requires until it is filled or cancelled, and an `OrderSnapshot` of the resting order
+    /// is sent via the account stream.
+    ///
+    /// Post-only limit orders that would immediately match are rejected. Orders with
+    /// [`OrderFlags`] are rejected if they would increase or flip the existing
+    /// [`DerivativePosition`], and `close_position` orders are sized to close it entirely (see
+    /// [`Self::quantity_flagged`]).
+    ///
+    /// Returns the open response, as well as any [`UnindexedAccountEvent`] notifications that
+    /// should be sent via the account stream.
+    pub fn open_order(
+        &mut self,
+        mut request: OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
+    ) -> (
+        Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>,
+        Vec<UnindexedAccountEvent>,
+    ) {
+        if let Err(error) = self.validate_order_kind_supported(request.state.kind) {
+            return (build_open_order_err_response(request, error), vec![]);
+        }
+
+        let instrument = match self.find_instrument_data(&request.key.instrument) {
+            Ok(instrument) => instrument.clone(),
+            Err(error) => return (build_open_order_err_response(request, error), vec![]),
+        };
+
+        if let Some(expiry) = instrument
+            .kind
+            .expiry()
+            .filter(|expiry| *expiry <= self.time_exchange())
+        {
+            let error = ApiError::InstrumentInvalid(
+                request.key.instrument.clone(),
+                format!("MockExchange instrument expired at: {expiry}"),
+            );
+            return (build_open_order_err_response(request, error), vec![]);
+        }
+
+        // Margin is only accounted for linear contracts, which settle in the quote asset
+        if let Some(settlement_asset) = settlement_asset_margined(&instrument.kind)
+            && settlement_asset != &instrument.underlying.quote
+        {
+            let error = ApiError::InstrumentInvalid(
+                request.key.instrument.clone(),
+                format!(
+                    "MockExchange does not support inverse contracts settled in: {settlement_asset}"
+                ),
+            );
+            return (build_open_order_err_response(request, error), vec![]);
+        }
+
+        match self.validate_order_flags(&request, &instrument) {
+            Ok(quantity) => request.state.quantity = quantity,
+            Err(error) => return (build_open_order_err_response(request, error), vec![]),
+        }
+
+        let (cid, flags) = (request.key.cid.clone(), request.state.flags);
+
+        let (response, notifications) = if request.state.kind.is_conditional() {
+            self.open_order_conditional(request, instrument)
         } else {
-            Err(UnindexedOrderError::Rejected(ApiError::OrderRejected(
-                format!("MockExchange does not supported OrderKind: {order_kind}"),
-            )))
+            let fills = self.fills_taker(
+                &request.key.instrument,
+                request.state.kind.is_market(),
+                request.state.side,
+                request.state.price,
+                request.state.quantity,
+            );
+
+            self.open_order_with_fills(request, instrument, fills)
+        };
+
+        if flags.is_reducing() && self.account.orders_open().any(|order| order.key.cid == cid) {
+            self.flags.insert(cid, flags);
         }
+
+        (response, notifications)
     }
 
-    pub fn find_instrument_data(
+    /// Validate the post-only [`TimeInForce`] and [`OrderFlags`] of an open request against the
+    /// prevailing [`MarketState`] and [`DerivativePosition`].
+    ///
+    /// Returns the quantity the order should be opened with.
+    fn validate_order_flags(
         &self,
-        instrument: &InstrumentNameExchange,
-    ) -> Result<&Instrument<ExchangeId, AssetNameExchange>, UnindexedApiError> {
-        self.instruments.get(instrument).ok_or_else(|| {
-            ApiError::InstrumentInvalid(
-                instrument.clone(),
-                format!("MockExchange is not set-up for managing: {instrument}"),
-            )
-        })
+        request: &OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+    ) -> Result<Decimal, UnindexedApiError> {
+        let RequestOpen {
+            side,
+            price,
+            quantity,
+            kind,
+            time_in_force,
+            flags,
+        } = request.state;
+
+        let price_taker = self
+            .markets
+            .get(&request.key.instrument)
+            .and_then(|market| market.price_taker(side));
+
+        if kind == OrderKind::Limit
+            && time_in_force.is_post_only()
+            && let Some(price_taker) = price_taker
+            && crosses(side, price, price_taker)
+        {
+            return Err(ApiError::OrderRejected(format!(
+                "MockExchange post-only order would immediately match at price: {price_taker}"
+            )));
+        }
+
+        if !flags.is_reducing() {
+            return Ok(quantity);
+        }
+
+        let quantity_flagged = self.quantity_flagged(instrument, flags, side, quantity);
+        if quantity_flagged.is_zero() || (!flags.close_position && quantity_flagged < quantity) {
+            return Err(ApiError::OrderRejected(format!(
+                "MockExchange reduce only order would increase or flip position: {}",
+                request.key.instrument
+            )));
+        }
+
+        Ok(quantity_flagged)
     }
 
-    fn order_id_sequence_fetch_add(&mut self) -> OrderId {
-        let sequence = self.order_sequence;
-        self.order_sequence += 1;
-        OrderId::new(sequence.to_smolstr())
+    /// Quantity of an order with the provided [`OrderFlags`] that may be executed against the
+    /// existing [`DerivativePosition`] of the provided instrument.
+    ///
+    /// `reduce_only` orders are limited to the quantity that reduces the position, and
+    /// `close_position` orders are sized to the entire position. Orders without flags, and
+    /// orders for instruments that are not margined, are unconstrained.
+    ///
+    /// New `reduce_only` orders exceeding this quantity are rejected (see
+    /// [`Self::validate_order_flags`]), while resting and triggered orders are capped to it as
+    /// the position changes.
+    fn quantity_flagged(
+        &self,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+        flags: OrderFlags,
+        side: Side,
+        quantity: Decimal,
+    ) -> Decimal {
+        if !flags.is_reducing() || settlement_asset_margined(&instrument.kind).is_none() {
+            return quantity;
+        }
+
+        let quantity = if flags.close_position {
+            Decimal::MAX
+        } else {
+            quantity
+        };
+
+        self.quantity_reducible(instrument, side, quantity)
     }
 
-    fn build_account_event<Kind>(&self, kind: Kind) -> UnindexedAccountEvent
-    where
-        Kind: Into<AccountEventKind<ExchangeId, AssetNameExchange, InstrumentNameExchange>>,
-    {
-        UnindexedAccountEvent {
-            exchange: self.exchange,
-            kind: kind.into(),
+    /// Determine the `(price, quantity)` fills of an order taking liquidity from the
+    /// prevailing [`MarketState`].
+    ///
+    /// Market orders are filled at the slipped taker price, or at the provided price if no
+    /// market data has been received. Limit orders are only filled if they cross the market,
+    /// consuming the opposing liquidity within their limit price.
+    fn fills_taker(
+        &self,
+        instrument: &InstrumentNameExchange,
+        market_order: bool,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Vec<(Decimal, Decimal)> {
+        let market = self.markets.get(instrument);
+        let price_taker = market.and_then(|market| market.price_taker(side));
+        let book = market.map(|market| &market.book);
+
+        match (market_order, price_taker) {
+            (true, Some(price_taker)) => vec![(
+                self.slippage.price_fill(side, quantity, price_taker, book),
+                quantity,
+            )],
+            (true, None) => vec![(price, quantity)],
+            (false, Some(price_taker)) if crosses(side, price, price_taker) => {
+                let levels = market
+                    .map(|market| market.levels_opposing(side))
+                    .unwrap_or_default();
+
+                if levels.is_empty() {
+                    // Slippage never fills a marketable limit order through its limit price
+                    let price_slipped = self.slippage.price_fill(side, quantity, price_taker, book);
+                    let price_fill = match side {
+                        Side::Buy => price_slipped.min(price),
+                        Side::Sell => price_slipped.max(price),
+                    };
+                    vec![(price_fill, quantity)]
+                } else {
+                    fills_within_limit(levels, side, price, quantity)
+                }
+            }
+            (false, _) => vec![],
         }
     }
-}
 
-fn build_open_order_err_response<E>(
-    request: OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
-    error: E,
+    /// Open a new conditional [`Order`] (see [`OrderKind::is_conditional`]), which rests
+    /// untriggered in the [`AccountState`] open orders, reserving the balance it requires at
+    /// its price.
+    ///
+    /// Orders that would be triggered immediately by the prevailing trigger source price are
+    /// rejected, except for `TrailingStop` orders, which start trailing immediately.
+    fn open_order_conditional(
+        &mut self,
+        request: OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
+        instrument: Instrument<ExchangeId, AssetNameExchange>,
+    ) -> (
+        Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>,
+        Vec<UnindexedAccountEvent>,
+    ) {
+        let RequestOpen {
+            side,
+            price,
+            quantity,
+            kind,
+            time_in_force,
+            flags: _,
+        } = request.state.clone();
+
+        let mut trigger = TriggerState::default();
+        let price_trigger = kind.trigger().and_then(|trigger| {
+            self.markets
+                .get(&request.key.instrument)
+                .and_then(|market| market.price_trigger(trigger.source))
+        });
+
+        if let Some(price_trigger) = price_trigger
+            && trigger.update(kind, side, price_trigger)
+        {
+            let error = ApiError::OrderRejected(format!(
+                "MockExchange {kind} order would immediately trigger at price: {price_trigger}"
+            ));
+            return (build_open_order_err_response(request, error), vec![]);
+        }
+
+        let balance = match self.reserve_balance(&instrument, side, price, quantity) {
+            Ok(balance) => balance,
+            Err(error) => return (build_open_order_err_response(request, error), vec![]),
+        };
+
+        let open = Open {
+            id: self.order_id_sequence_fetch_add(),
+            time_exchange: self.time_exchange(),
+            filled_quantity: Decimal::ZERO,
+        };
+
+        self.triggers.insert(request.key.cid.clone(), trigger);
+        self.account.insert_order_open(Order {
+            key: request.key.clone(),
+            side,
+            price,
+            quantity,
+            kind,
+            time_in_force,
+            state: open.clone(),
+        });
+
+        let notifications = vec![
+            self.build_account_event(balance),
+            self.build_account_event(Snapshot(Order {
+                key: request.key.clone(),
+                side,
+                price,
+                quantity,
+                kind,
+                time_in_force,
+                state: OrderState::active(open.clone()),
+            })),
+        ];
+
+        let order_response = Order {
+            key: request.key,
+            side,
+            price,
+            quantity,
+            kind,
+            time_in_force,
+            state: Ok(open),
+        };
+
+        (order_response, notifications)
+    }
+
+    /// Execute any untriggered conditional orders for the provided instrument whose trigger
+    /// price has been reached by the prevailing [`MarketState`].
+    ///
+    /// Triggered orders are executed in time priority (see [`Self::trigger_order`]).
+    pub fn trigger_orders_conditional(
+        &mut self,
+        instrument: &InstrumentNameExchange,
+    ) -> Vec<UnindexedAccountEvent> {
+        let Some(market) = self.markets.get(instrument) else {
+            return vec![];
+        };
+
+        let triggered = self
+            .account
+            .orders_open()
+            .filter(|order| order.key.instrument == *instrument)
+            .filter_map(|order| {
+                let trigger = self.triggers.get_mut(&order.key.cid)?;
+                let price = market.price_trigger(order.kind.trigger()?.source)?;
+                trigger
+                    .update(order.kind, order.side, price)
+                    .then(|| (order_sequence(&order.state.id), order.key.cid.clone()))
+            })
+            .sorted_unstable()
+            .map(|(_, cid)| cid)
+            .collect::<Vec<_>>();
+
+        triggered
+            .into_iter()
+            .flat_map(|cid| self.trigger_order(&cid))
+            .collect()
+    }
+
+    /// Execute a triggered conditional [`Order`], releasing the balance it reserved.
+    ///
+    /// Market order kinds are filled against the prevailing [`MarketState`]. Limit order kinds
+    /// take any liquidity within their limit price, with any quantity remaining resting as a
+    /// limit order (unless it is `ImmediateOrCancel` or `FillOrKill`, in which case it expires).
+    ///
+    /// Orders with [`OrderFlags`] are resized against the position at the time they trigger,
+    /// and expire if there is no longer a position for them to reduce.
+    fn trigger_order(&mut self, cid: &ClientOrderId) -> Vec<UnindexedAccountEvent> {
+        self.triggers.remove(cid);
+        let flags = self.flags.get(cid).copied().unwrap_or_default();
+        let Some(mut order) = self.account.remove_order_open(cid) else {
+            return vec![];
+        };
+
+        let instrument = self
+            .find_instrument_data(&order.key.instrument)
+            .expect("MockExchange only accepts orders for configured instruments")
+            .clone();
+
+        let quantity_remaining = order.state.quantity_remaining(order.quantity);
+        let balance =
+            self.release_balance(&instrument, order.side, order.price, quantity_remaining);
+        let mut notifications = vec![self.build_account_event(balance)];
+
+        let quantity_remaining =
+            self.quantity_flagged(&instrument, flags, order.side, quantity_remaining);
+        if quantity_remaining.is_zero() {
+            self.flags.remove(cid);
+            notifications.push(self.build_account_event(Snapshot(Order {
+                key: order.key,
+                side: order.side,
+                price: order.price,
+                quantity: order.quantity,
+                kind: order.kind,
+                time_in_force: order.time_in_force,
+                state: OrderState::expired(),
+            })));
+            return notifications;
+        }
+        order.quantity = order.state.filled_quantity + quantity_remaining;
+
+        let fills = self.fills_taker(
+            &order.key.instrument,
+            order.kind.is_market(),
+            order.side,
+            order.price,
+            quantity_remaining,
+        );
+
+        for (price_fill, quantity_fill) in fills {
+            notifications.extend(self.fill_order(
+                &order.key,
+                &instrument,
+                order.state.id.clone(),
+                order.side,
+                price_fill,
+                quantity_fill,
+                Liquidity::Taker,
+            ));
+            order.state.filled_quantity += quantity_fill;
+        }
+        order.state.time_exchange = self.time_exchange();
+
+        let quantity_remaining = order.state.quantity_remaining(order.quantity);
+        let reserved = match order.time_in_force {
+            _ if quantity_remaining.is_zero() => None,
+            TimeInForce::ImmediateOrCancel | TimeInForce::FillOrKill => None,
+            _ => self
+                .reserve_balance(&instrument, order.side, order.price, quantity_remaining)
+                .ok(),
+        };
+
+        if reserved.is_none() {
+            self.flags.remove(cid);
+        }
+
+        let state = match reserved {
+            Some(balance) => {
+                notifications.push(self.build_account_event(balance));
+                self.account.insert_order_open(order.clone());
+                OrderState::active(order.state.clone())
+            }
+            None if quantity_remaining.is_zero() => OrderState::fully_filled(),
+            None => OrderState::expired(),
+        };
+
+        notifications.push(self.build_account_event(Snapshot(Order {
+            key: order.key,
+            side: order.side,
+            price: order.price,
+            quantity: order.quantity,
+            kind: order.kind,
+            time_in_force: order.time_in_force,
+            state,
+        })));
+
+        notifications
+    }
+
+    fn open_order_with_fills(
+        &mut self,
+        request: OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
+        instrument: Instrument<ExchangeId, AssetNameExchange>,
+        fills: Vec<(Decimal, Decimal)>,
+    ) -> (
+        Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>,
+        Vec<UnindexedAccountEvent>,
+    ) {
+        let (side, price, quantity, time_in_force) = (
+            request.state.side,
+            request.state.price,
+            request.state.quantity,
+            request.state.time_in_force,
+        );
+
+        let quantity_filled = fills.iter().map(|(_, quantity)| *quantity).sum::<Decimal>();
+        let quantity_remaining = quantity - quantity_filled;
+
+        let rejection = match time_in_force {
+            TimeInForce::FillOrKill if quantity_remaining > Decimal::ZERO => {
+                Some(ApiError::OrderRejected(format!(
+                    "MockExchange cannot fully fill {time_in_force} order at price: {price}",
+                )))
+            }
+            TimeInForce::ImmediateOrCancel if quantity_filled.is_zero() => {
+                Some(ApiError::OrderRejected(format!(
+                    "MockExchange cannot immediately fill {time_in_force} order at price: {price}",
+                )))
+            }
+            _ => None,
+        };
+
+        if let Some(error) = rejection {
+            return (build_open_order_err_response(request, error), vec![]);
+        }
+
+        // Fills are never worse than the limit price, so this covers all fills & any reservation
+        let price_required = fills
+            .iter()
+            .map(|(price, _)| *price)
+            .chain(std::iter::once(price))
+            .reduce(|a, b| match side {
+                Side::Buy => a.max(b),
+                Side::Sell => a.min(b),
+            })
+            .unwrap_or(price);
+
+        // Derivative fills that reduce an existing position release margin, rather than lock it
+        let quantity_required =
+            quantity - self.quantity_reducible(&instrument, side, quantity_filled);
+
+        if let Err(error) =
+            self.validate_balance_sufficient(&instrument, side, price_required, quantity_required)
+        {
+            return (build_open_order_err_response(request, error), vec![]);
+        }
+
+        let open = Open {
+            id: self.order_id_sequence_fetch_add(),
+            time_exchange: self.time_exchange(),
+            filled_quantity: quantity_filled,
+        };
+
+        let mut notifications = Vec::new();
+        for (price_fill, quantity_fill) in &fills {
+            notifications.extend(self.fill_order(
+                &request.key,
+                &instrument,
+                open.id.clone(),
+                side,
+                *price_fill,
+                *quantity_fill,
+                Liquidity::Taker,
+            ));
+        }
+
+        // Fills never trade against our own resting orders, which are instead expired (as a
+        // venue would with expire maker self-trade prevention)
+        if let Some((price_last, _)) = fills.last() {
+            notifications.extend(self.expire_orders_self_trade(
+                &request.key.instrument,
+                side,
+                *price_last,
+            ));
+        }
+
+        if quantity_remaining > Decimal::ZERO {
+            // Losses realised by derivative fills may leave insufficient margin to rest the
+            // remaining quantity, in which case it expires
+            let reserved = match time_in_force {
+                TimeInForce::ImmediateOrCancel => None,
+                _ => self
+                    .reserve_balance(&instrument, side, price, quantity_remaining)
+                    .ok(),
+            };
+
+            if let Some(balance) = reserved {
+                let queue_ahead = self
+                    .markets
+                    .get(&request.key.instrument)
+                    .and_then(|market| market.amount_at(side, price))
+                    .unwrap_or_default();
+                self.queue_ahead
+                    .insert(request.key.cid.clone(), queue_ahead);
+
+                self.account.insert_order_open(Order {
+                    key: request.key.clone(),
+                    side,
+                    price,
+                    quantity,
+                    kind: request.state.kind,
+                    time_in_force,
+                    state: open.clone(),
+                });
+
+                notifications.push(self.build_account_event(balance));
+                notifications.push(self.build_account_event(Snapshot(Order {
+                    key: request.key.clone(),
+                    side,
+                    price,
+                    quantity,
+                    kind: request.state.kind,
+                    time_in_force,
+                    state: OrderState::active(open.clone()),
+                })));
+            } else {
+                notifications.push(self.build_account_event(Snapshot(Order {
+                    key: request.key.clone(),
+                    side,
+                    price,
+                    quantity,
+                    kind: request.state.kind,
+                    time_in_force,
+                    state: OrderState::expired(),
+                })));
+            }
+        }
+
+        let order_response = Order {
+            key: request.key,
+            side,
+            price,
+            quantity,
+            kind: request.state.kind,
+            time_in_force,
+            state: Ok(open),
+        };
+
+        (order_response, notifications)
+    }
+
+    /// Expire resting open orders for the provided instrument on the opposite side of a taker
+    /// order, that are crossed by its fills, so the account never trades against itself.
+    fn expire_orders_self_trade(
+        &mut self,
+        instrument: &InstrumentNameExchange,
+        side_taker: Side,
+        price: Decimal,
+    ) -> Vec<UnindexedAccountEvent> {
+        self.markets
+            .entry(instrument.clone())
+            .or_default()
+            .last_traded_price = Some(price);
+
+        let side_maker = match side_taker {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+
+        let crossed = self
+            .account
+            .orders_open()
+            .filter(|order| {
+                order.key.instrument == *instrument
+                    && order.side == side_maker
+                    && crosses(order.side, order.price, price)
+                    && !self.triggers.contains_key(&order.key.cid)
+            })
+            .map(|order| order.key.cid.clone())
+            .collect::<Vec<_>>();
+
+        crossed
+            .iter()
+            .flat_map(|cid| self.expire_order_open(cid))
+            .collect()
+    }
+
+    /// Fill resting open orders for the provided instrument that are crossed by a trade with
+    /// the provided price and amount.
+    ///
+    /// Orders the trade went through are fully filled. Orders resting at exactly the traded
+    /// price are only filled with the amount remaining after the estimated queue ahead of them
+    /// has been consumed, producing partial fills.
+    ///
+    /// Orders are filled at their limit price in price-time priority.
+    pub fn match_orders_open(
+        &mut self,
+        instrument: &InstrumentNameExchange,
+        price: Decimal,
+        amount: Decimal,
+    ) -> Vec<UnindexedAccountEvent> {
+        self.markets
+            .entry(instrument.clone())
+            .or_default()
+            .last_traded_price = Some(price);
+
+        let mut notifications =
+            self.match_orders_open_side(instrument, Side::Buy, price, Some(amount));
+        notifications.extend(self.match_orders_open_side(
+            instrument,
+            Side::Sell,
+            price,
+            Some(amount),
+        ));
+        notifications
+    }
+
+    /// Fill resting open orders for the provided instrument and [`Side`] that are crossed
+    /// by the provided price.
+    ///
+    /// If an `amount` is provided, orders resting at exactly the provided price share it after
+    /// their queue ahead is consumed. Otherwise, all crossed orders are fully filled.
+    fn match_orders_open_side(
+        &mut self,
+        instrument: &InstrumentNameExchange,
+        side: Side,
+        price: Decimal,
+        mut amount: Option<Decimal>,
+    ) -> Vec<UnindexedAccountEvent> {
+        let crossed = self
+            .account
+            .orders_open()
+            .filter(|order| {
+                order.key.instrument == *instrument
+                    && order.side == side
+                    && crosses(order.side, order.price, price)
+                    && !self.triggers.contains_key(&order.key.cid)
+            })
+            .sorted_unstable_by(|a, b| {
+                let price_priority = match side {
+                    Side::Buy => b.price.cmp(&a.price),
+                    Side::Sell => a.price.cmp(&b.price),
+                };
+                price_priority
+                    .then_with(|| order_sequence(&a.state.id).cmp(&order_sequence(&b.state.id)))
+            })
+            .map(|order| {
+                (
+                    order.key.cid.clone(),
+                    order.price,
+                    order.state.quantity_remaining(order.quantity),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut notifications = Vec::new();
+        for (cid, order_price, quantity_remaining) in crossed {
+            let Some(quantity_remaining) =
+                self.quantity_flagged_open(&cid, instrument, side, quantity_remaining)
+            else {
+                notifications.extend(self.expire_order_open(&cid));
+                continue;
+            };
+
+            let quantity_fill = match amount.as_mut() {
+                Some(amount) if order_price == price => {
+                    let queue_ahead = self.queue_ahead.entry(cid.clone()).or_default();
+                    let queue_consumed = (*queue_ahead).min(*amount);
+                    *queue_ahead -= queue_consumed;
+                    *amount -= queue_consumed;
+
+                    let quantity_fill = quantity_remaining.min(*amount);
+                    *amount -= quantity_fill;
+                    quantity_fill
+                }
+                _ => quantity_remaining,
+            };
+
+            if quantity_fill > Decimal::ZERO {
+                notifications.extend(self.fill_order_open(&cid, quantity_fill));
+            }
+        }
+
+        notifications
+    }
+
+    /// Quantity of a resting open order that may be filled, given any [`OrderFlags`] it was
+    /// opened with (see [`Self::quantity_flagged`]).
+    ///
+    /// Returns `None` if the order can no longer reduce the existing position.
+    fn quantity_flagged_open(
+        &self,
+        cid: &ClientOrderId,
+        instrument: &InstrumentNameExchange,
+        side: Side,
+        quantity: Decimal,
+    ) -> Option<Decimal> {
+        let (Some(flags), Ok(instrument)) =
+            (self.flags.get(cid), self.find_instrument_data(instrument))
+        else {
+            return Some(quantity);
+        };
+
+        let quantity = self
+            .quantity_flagged(instrument, *flags, side, quantity)
+            .min(quantity);
+
+        (!quantity.is_zero()).then_some(quantity)
+    }
+
+    /// Expire a resting open [`Order`], releasing any balance it reserved.
+    fn expire_order_open(&mut self, cid: &ClientOrderId) -> Vec<UnindexedAccountEvent> {
+        self.queue_ahead.remove(cid);
+        self.triggers.remove(cid);
+        self.flags.remove(cid);
+        let Some(order) = self.account.remove_order_open(cid) else {
+            return vec![];
+        };
+
+        let instrument = self
+            .find_instrument_data(&order.key.instrument)
+            .expect("MockExchange only accepts orders for configured instruments")
+            .clone();
+
+        let quantity_remaining = order.state.quantity_remaining(order.quantity);
+        let balance =
+            self.release_balance(&instrument, order.side, order.price, quantity_remaining);
+
+        vec![
+            self.build_account_event(balance),
+            self.build_account_event(Snapshot(Order {
+                key: order.key,
+                side: order.side,
+                price: order.price,
+                quantity: order.quantity,
+                kind: order.kind,
+                time_in_force: order.time_in_force,
+                state: OrderState::expired(),
+            })),
+        ]
+    }
+
+    /// Reduce the estimated queue ahead of resting open orders for the provided instrument if
+    /// the displayed amount at their price level has shrunk (eg/ due to cancellations).
+    fn update_queue_positions(&mut self, instrument: &InstrumentNameExchange) {
+        let Some(market) = self.markets.get(instrument) else {
+            return;
+        };
+
+        for order in self
+            .account
+            .orders_open()
+            .filter(|order| order.key.instrument == *instrument)
+        {
+            if let (Some(queue_ahead), Some(displayed)) = (
+                self.queue_ahead.get_mut(&order.key.cid),
+                market.amount_at(order.side, order.price),
+            ) {
+                *queue_ahead = (*queue_ahead).min(displayed);
+            }
+        }
+    }
+
+    /// Fill the provided quantity of a resting open [`Order`] at its limit price.
+    ///
+    /// Partially filled orders remain open, with their `filled_quantity` advanced.
+    fn fill_order_open(
+        &mut self,
+        cid: &ClientOrderId,
+        quantity: Decimal,
+    ) -> Vec<UnindexedAccountEvent> {
+        let Some(mut order) = self.account.remove_order_open(cid) else {
+            return vec![];
+        };
+
+        let instrument = self
+            .find_instrument_data(&order.key.instrument)
+            .expect("MockExchange only accepts orders for configured instruments")
+            .clone();
+
+        // Release balance reserved by the resting order, before settling the fill
+        self.release_balance(&instrument, order.side, order.price, quantity);
+
+        let mut notifications = self.fill_order(
+            &order.key,
+            &instrument,
+            order.state.id.clone(),
+            order.side,
+            order.price,
+            quantity,
+            Liquidity::Maker,
+        );
+
+        order.state.filled_quantity += quantity;
+
+        let state = if order.state.quantity_remaining(order.quantity) > Decimal::ZERO {
+            let state = OrderState::active(order.state.clone());
+            self.account.insert_order_open(order.clone());
+            state
+        } else {
+            self.queue_ahead.remove(cid);
+            self.flags.remove(cid);
+            OrderState::fully_filled()
+        };
+
+        notifications.push(self.build_account_event(Snapshot(Order {
+            key: order.key,
+            side: order.side,
+            price: order.price,
+            quantity: order.quantity,
+            kind: order.kind,
+            time_in_force: order.time_in_force,
+            state,
+        })));
+
+        notifications
+    }
+
+    /// Settle an [`Order`] fill, updating the base, quote and fee [`AssetBalance`]s and
+    /// recording the associated [`Trade`].
+    ///
+    /// Fees are charged using the [`FeeSchedule`] rate for the fill [`Liquidity`] and the
+    /// cumulative traded quote volume. Regardless of the [`FeeAsset`] they are charged in, the
+    /// [`Trade`] fees are recorded as their quote asset equivalent.
+    ///
+    /// `Perpetual` and `Future` fills are settled against their [`DerivativePosition`] instead
+    /// (see [`Self::fill_order_derivative`]).
+    ///
+    /// Returns the [`UnindexedAccountEvent`] balance and trade notifications.
+    fn fill_order(
+        &mut self,
+        key: &OrderKey<ExchangeId, InstrumentNameExchange>,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+        order_id: OrderId,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        liquidity: Liquidity,
+    ) -> Vec<UnindexedAccountEvent> {
+        if let Some(settlement_asset) = settlement_asset_margined(&instrument.kind) {
+            return self.fill_order_derivative(
+                key,
+                instrument.kind.contract_size(),
+                settlement_asset,
+                order_id,
+                side,
+                price,
+                quantity,
+                liquidity,
+            );
+        }
+
+        let underlying = &instrument.underlying;
+        let time_exchange = self.time_exchange();
+        let quantity_abs = quantity.abs();
+        let value_quote = price * quantity_abs;
+        let rate = self.fee_schedule.rate(liquidity, self.volume_quote);
+        let fees_quote = value_quote * rate;
+        self.volume_quote += value_quote;
+
+        let mut deltas = match side {
+            Side::Buy => vec![
+                (underlying.base.clone(), quantity_abs),
+                (underlying.quote.clone(), -value_quote),
+            ],
+            Side::Sell => vec![
+                (underlying.base.clone(), -quantity_abs),
+                (underlying.quote.clone(), value_quote),
+            ],
+        };
+
+        let (fee_asset, fees) = match &self.fee_schedule.asset {
+            FeeAsset::Quote => (&underlying.quote, fees_quote),
+            FeeAsset::Base => (&underlying.base, quantity_abs * rate),
+            FeeAsset::Other { asset, price_quote } => {
+                if self.account.balance_mut(asset).is_some() && !price_quote.is_zero() {
+                    (asset, fees_quote / price_quote)
+                } else {
+                    error!(
+                        exchange = %self.exchange,
+                        %asset,
+                        "MockExchange has no Balance for FeeAsset (or it has a zero price) - charging fees in quote"
+                    );
+                    (&underlying.quote, fees_quote)
+                }
+            }
+        };
+
+        match deltas.iter_mut().find(|(asset, _)| asset == fee_asset) {
+            Some((_, delta)) => *delta -= fees,
+            None => deltas.push((fee_asset.clone(), -fees)),
+        }
+
+        let balances = deltas
+            .into_iter()
+            .map(|(asset, delta)| {
+                let current = self
+                    .account
+                    .balance_mut(&asset)
+                    .expect("MockExchange has Balance for all configured Instrument & fee assets");
+
+                current.balance.total += delta;
+                current.balance.free += delta;
+                current.time_exchange = time_exchange;
+
+                Snapshot(current.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let trade = Trade {
+            id: self.trade_id_sequence_fetch_add(),
+            order_id,
+            instrument: key.instrument.clone(),
+            strategy: key.strategy.clone(),
+            time_exchange,
+            side,
+            price,
+            quantity,
+            fees: AssetFees::quote_fees(fees_quote),
+        };
+
+        self.account.ack_trade(trade.clone());
+
+        balances
+            .into_iter()
+            .map(|balance| self.build_account_event(balance))
+            .chain(std::iter::once(self.build_account_event(trade)))
+            .collect()
+    }
+
+    /// Settle a `Perpetual` or `Future` [`Order`] fill against the instrument
+    /// [`DerivativePosition`], realising PnL on any quantity closed and locking or releasing
+    /// initial margin in the settlement asset.
+    ///
+    /// Fees are always charged in the settlement asset.
+    ///
+    /// Returns the [`UnindexedAccountEvent`] balance and trade notifications.
+    fn fill_order_derivative(
+        &mut self,
+        key: &OrderKey<ExchangeId, InstrumentNameExchange>,
+        contract_size: Decimal,
+        settlement_asset: &AssetNameExchange,
+        order_id: OrderId,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        liquidity: Liquidity,
+    ) -> Vec<UnindexedAccountEvent> {
+        let time_exchange = self.time_exchange();
+        let value_quote = price * quantity.abs() * contract_size;
+        let rate = self.fee_schedule.rate(liquidity, self.volume_quote);
+        let fees_quote = value_quote * rate;
+        self.volume_quote += value_quote;
+
+        let position = self.positions.entry(key.instrument.clone()).or_default();
+        let settlement =
+            position.apply_fill(side, price, quantity, contract_size, self.margin.leverage);
+        if position.is_flat() {
+            self.positions.remove(&key.instrument);
+        }
+
+        let current = self
+            .account
+            .balance_mut(settlement_asset)
+            .expect("MockExchange has Balance for all configured Instrument settlement assets");
+
+        current.balance.total += settlement.pnl_realised - fees_quote;
+        current.balance.free += settlement.pnl_realised - fees_quote - settlement.margin_delta;
+        current.time_exchange = time_exchange;
+        let balance = Snapshot(current.clone());
+
+        let trade = Trade {
+            id: self.trade_id_sequence_fetch_add(),
+            order_id,
+            instrument: key.instrument.clone(),
+            strategy: key.strategy.clone(),
+            time_exchange,
+            side,
+            price,
+            quantity,
+            fees: AssetFees::quote_fees(fees_quote),
+        };
+
+        self.account.ack_trade(trade.clone());
+
+        vec![
+            self.build_account_event(balance),
+            self.build_account_event(trade),
+        ]
+    }
+
+    /// Number of contracts of a fill that would reduce an existing [`DerivativePosition`] for
+    /// the provided instrument, and so require no additional margin.
+    ///
+    /// Always zero for instruments that are not margined.
+    fn quantity_reducible(
+        &self,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+        side: Side,
+        quantity: Decimal,
+    ) -> Decimal {
+        if settlement_asset_margined(&instrument.kind).is_none() {
+            return Decimal::ZERO;
+        }
+
+        self.positions
+            .get(&instrument.name_exchange)
+            .map_or(Decimal::ZERO, |position| {
+                position.quantity_reducible(side, quantity)
+            })
+    }
+
+    /// Settle the latest funding rate of the provided instrument against any open `Perpetual`
+    /// [`DerivativePosition`], if its funding time has been reached.
+    ///
+    /// Positive rates are paid by LONG positions to SHORT positions (and vice versa), in
+    /// proportion to the position notional value at the mark price. Each funding rate is only
+    /// settled once.
+    fn settle_funding(
+        &mut self,
+        instrument: &InstrumentNameExchange,
+        time_exchange: DateTime<Utc>,
+    ) -> Vec<UnindexedAccountEvent> {
+        let Some(market) = self.markets.get_mut(instrument) else {
+            return vec![];
+        };
+
+        let (Some(rate), Some(time_funding)) = (market.funding_rate, market.time_next_funding)
+        else {
+            return vec![];
+        };
+
+        if time_exchange < time_funding {
+            return vec![];
+        }
+        market.time_next_funding = None;
+        let price_mark = market.price_mark();
+
+        let (
+            Some(Instrument {
+                kind: InstrumentKind::Perpetual(contract),
+                ..
+            }),
+            Some(position),
+        ) = (
+            self.instruments.get(instrument),
+            self.positions.get(instrument),
+        )
+        else {
+            return vec![];
+        };
+
+        let price_mark = price_mark.unwrap_or(position.price_entry);
+        let amount = -rate * position.quantity * price_mark * contract.contract_size;
+
+        let funding = FundingPayment {
+            instrument: instrument.clone(),
+            time_exchange: time_funding,
+            rate,
+            price_mark,
+            quantity: position.quantity,
+            amount,
+        };
+
+        let time_exchange = self.time_exchange();
+        let current = self
+            .account
+            .balance_mut(&contract.settlement_asset)
+            .expect("MockExchange has Balance for all configured Instrument settlement assets");
+
+        current.balance.total += amount;
+        current.balance.free += amount;
+        current.time_exchange = time_exchange;
+        let balance = Snapshot(current.clone());
+
+        vec![
+            self.build_account_event(balance),
+            self.build_account_event(funding),
+        ]
+    }
+
+    /// Settle every `Future` instrument whose contract has expired at the current exchange time.
+    ///
+    /// Any open orders for the instrument are expired, releasing the balance they reserved, and
+    /// any open [`DerivativePosition`] is closed at the prevailing mark price with a taker fill
+    /// attributed to the `"expiry"` [`StrategyId`]. If configured for
+    /// [`ExpirySettlement::Physical`], the underlying base asset is then delivered.
+    ///
+    /// Expired instruments hold no orders or positions, and reject new orders, so each is only
+    /// settled once.
+    fn settle_expiries(&mut self) -> Vec<UnindexedAccountEvent> {
+        let time_exchange = self.time_exchange();
+
+        let expired = self
+            .instruments
+            .values()
+            .filter(|instrument| {
+                matches!(instrument.kind, InstrumentKind::Future(_))
+                    && instrument
+                        .kind
+                        .expiry()
+                        .is_some_and(|expiry| expiry <= time_exchange)
+            })
+            .map(|instrument| instrument.name_exchange.clone())
+            .collect::<Vec<_>>();
+
+        expired
+            .into_iter()
+            .flat_map(|instrument| self.settle_expiry(&instrument))
+            .collect()
+    }
+
+    fn settle_expiry(&mut self, instrument: &InstrumentNameExchange) -> Vec<UnindexedAccountEvent> {
+        let instrument_data = self
+            .find_instrument_data(instrument)
+            .expect("MockExchange only settles configured instruments")
+            .clone();
+
+        let orders_expired = self
+            .account
+            .orders_open()
+            .filter(|order| order.key.instrument == *instrument)
+            .map(|order| order.key.cid.clone())
+            .collect::<Vec<_>>();
+
+        let mut notifications = Vec::new();
+        for cid in orders_expired {
+            self.queue_ahead.remove(&cid);
+            self.triggers.remove(&cid);
+            self.flags.remove(&cid);
+            let Some(order) = self.account.remove_order_open(&cid) else {
+                continue;
+            };
+
+            let quantity_remaining = order.state.quantity_remaining(order.quantity);
+            let balance = self.release_balance(
+                &instrument_data,
+                order.side,
+                order.price,
+                quantity_remaining,
+            );
+
+            notifications.push(self.build_account_event(balance));
+            notifications.push(self.build_account_event(Snapshot(Order {
+                key: order.key,
+                side: order.side,
+                price: order.price,
+                quantity: order.quantity,
+                kind: order.kind,
+                time_in_force: order.time_in_force,
+                state: OrderState::expired(),
+            })));
+        }
+
+        let Some(position) = self.positions.get(instrument).copied() else {
+            return notifications;
+        };
+
+        let price = self
+            .markets
+            .get(instrument)
+            .and_then(MarketState::price_mark)
+            .unwrap_or(position.price_entry);
+
+        let side = if position.quantity > Decimal::ZERO {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+
+        let order_id = self.order_id_sequence_fetch_add();
+        let key = OrderKey {
+            exchange: self.exchange,
+            instrument: instrument.clone(),
+            strategy: StrategyId::new("expiry"),
+            cid: ClientOrderId::new(format!("expiry_{order_id}")),
+        };
+
+        info!(
+            exchange = %self.exchange,
+            %instrument,
+            quantity = %position.quantity,
+            %price,
+            settlement = ?self.expiry_settlement,
+            "MockExchange settling expired Future position"
+        );
+
+        notifications.extend(self.fill_order(
+            &key,
+            &instrument_data,
+            order_id,
+            side,
+            price,
+            position.quantity.abs(),
+            Liquidity::Taker,
+        ));
+
+        if self.expiry_settlement == ExpirySettlement::Physical {
+            notifications.extend(self.deliver_underlying(&instrument_data, &position, price));
+        }
+
+        notifications
+    }
+
+    /// Deliver the underlying base asset of an expired `Future` [`DerivativePosition`] in
+    /// exchange for its quote asset value at the settlement price.
+    ///
+    /// Falls back to cash settlement if the [`MockExchange`] has no balance for the underlying
+    /// assets.
+    fn deliver_underlying(
+        &mut self,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+        position: &DerivativePosition,
+        price: Decimal,
+    ) -> Vec<UnindexedAccountEvent> {
+        let underlying = &instrument.underlying;
+
+        if self.account.balance_mut(&underlying.base).is_none()
+            || self.account.balance_mut(&underlying.quote).is_none()
+        {
+            error!(
+                exchange = %self.exchange,
+                instrument = %instrument.name_exchange,
+                "MockExchange has no Balance for expired Future underlying - settling in cash"
+            );
+            return vec![];
+        }
+
+        let time_exchange = self.time_exchange();
+        let quantity_base = position.quantity * instrument.kind.contract_size();
+
+        let deltas = [
+            (&underlying.base, quantity_base),
+            (&underlying.quote, -quantity_base * price),
+        ];
+
+        let balances = deltas
+            .into_iter()
+            .map(|(asset, delta)| {
+                let current = self
+                    .account
+                    .balance_mut(asset)
+                    .expect("MockExchange Balance for Future underlying checked above");
+
+                current.balance.total += delta;
+                current.balance.free += delta;
+                current.time_exchange = time_exchange;
+
+                Snapshot(current.clone())
+            })
+            .collect::<Vec<_>>();
+
+        balances
+            .into_iter()
+            .map(|balance| self.build_account_event(balance))
+            .collect()
+    }
+
+    /// Liquidate derivative positions whose equity has fallen below their maintenance margin
+    /// after the mark price of the provided instrument has been updated.
+    ///
+    /// In [`MarginMode::Isolated`], only the position in the provided instrument is checked,
+    /// and it is closed no worse than its bankruptcy price so losses are limited to its margin.
+    ///
+    /// In [`MarginMode::Cross`], all positions sharing the settlement asset are backed by its
+    /// total balance, and are closed together at the mark price.
+    fn liquidate_positions_under_margin(
+        &mut self,
+        instrument: &InstrumentNameExchange,
+    ) -> Vec<UnindexedAccountEvent> {
+        if !self.positions.contains_key(instrument) {
+            return vec![];
+        }
+
+        let Some(settlement_asset) = self
+            .instruments
+            .get(instrument)
+            .and_then(|instrument| settlement_asset_margined(&instrument.kind))
+            .cloned()
+        else {
+            return vec![];
+        };
+
+        let maintenance_margin = self.margin.maintenance_margin;
+
+        // (instrument, position, contract_size, price_mark) of positions in the settlement asset
+        let positions = self
+            .positions
+            .iter()
+            .filter_map(|(name, position)| {
+                let kind = &self.instruments.get(name)?.kind;
+                (settlement_asset_margined(kind)? == &settlement_asset).then(|| {
+                    let price_mark = self
+                        .markets
+                        .get(name)
+                        .and_then(MarketState::price_mark)
+                        .unwrap_or(position.price_entry);
+                    (name.clone(), *position, kind.contract_size(), price_mark)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let liquidations = match self.margin.mode {
+            MarginMode::Isolated => positions
+                .into_iter()
+                .filter(|(name, position, contract_size, price_mark)| {
+                    let equity =
+                        position.margin + position.pnl_unrealised(*price_mark, *contract_size);
+                    name == instrument
+                        && equity
+                            < position.margin_maintenance(
+                                *price_mark,
+                                *contract_size,
+                                maintenance_margin,
+                            )
+                })
+                .map(|(name, position, contract_size, price_mark)| {
+                    let price_bankruptcy = position.price_bankruptcy(contract_size);
+                    let price = if position.quantity > Decimal::ZERO {
+                        price_mark.max(price_bankruptcy)
+                    } else {
+                        price_mark.min(price_bankruptcy)
+                    };
+                    (name, price)
+                })
+                .collect::<Vec<_>>(),
+            MarginMode::Cross => {
+                let balance_total = self
+                    .account
+                    .balances()
+                    .find(|balance| balance.asset == settlement_asset)
+                    .map_or(Decimal::ZERO, |balance| balance.balance.total);
+
+                let (pnl_unrealised, maintenance) = positions.iter().fold(
+                    (Decimal::ZERO, Decimal::ZERO),
+                    |(pnl, maintenance), (_, position, contract_size, price_mark)| {
+                        (
+                            pnl + position.pnl_unrealised(*price_mark, *contract_size),
+                            maintenance
+                                + position.margin_maintenance(
+                                    *price_mark,
+                                    *contract_size,
+                                    maintenance_margin,
+                                ),
+                        )
+                    },
+                );
+
+                if balance_total + pnl_unrealised < maintenance {
+                    positions
+                        .into_iter()
+                        .map(|(name, _, _, price_mark)| (name, price_mark))
+                        .collect()
+                } else {
+                    vec![]
+                }
+            }
+        };
+
+        liquidations
+            .into_iter()
+            .flat_map(|(instrument, price)| self.liquidate_position(&instrument, price))
+            .collect()
+    }
+
+    /// Forcibly close the [`DerivativePosition`] in the provided instrument with a taker fill
+    /// at the provided price.
+    ///
+    /// The liquidation [`Trade`] is attributed to the `"liquidation"` [`StrategyId`].
+    fn liquidate_position(
+        &mut self,
+        instrument: &InstrumentNameExchange,
+        price: Decimal,
+    ) -> Vec<UnindexedAccountEvent> {
+        let Some(position) = self.positions.get(instrument).copied() else {
+            return vec![];
+        };
+
+        let instrument_data = self
+            .find_instrument_data(instrument)
+            .expect("MockExchange only holds positions for configured instruments")
+            .clone();
+
+        let side = if position.quantity > Decimal::ZERO {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+
+        let order_id = self.order_id_sequence_fetch_add();
+        let key = OrderKey {
+            exchange: self.exchange,
+            instrument: instrument.clone(),
+            strategy: StrategyId::new("liquidation"),
+            cid: ClientOrderId::new(format!("liquidation_{order_id}")),
+        };
+
+        warn!(
+            exchange = %self.exchange,
+            %instrument,
+            quantity = %position.quantity,
+            %price,
+            "MockExchange liquidating position with insufficient margin"
+        );
+
+        self.fill_order(
+            &key,
+            &instrument_data,
+            order_id,
+            side,
+            price,
+            position.quantity.abs(),
+            Liquidity::Taker,
+        )
+    }
+
+    /// Determine the [`AssetNameExchange`] and amount (inc. fees) of balance required to open an
+    /// [`Order`].
+    ///
+    /// Buying an instrument requires quote asset balance, while selling requires base asset
+    /// balance. The maximum [`FeeSchedule`] rate is included if fees are charged in the same
+    /// asset, since it is not known upfront if the [`Order`] will be filled as a maker or taker.
+    /// Fees charged in the other instrument asset are deducted from the fill proceeds, and
+    /// fees charged in a third [`FeeAsset`] are not reserved.
+    ///
+    /// `Perpetual` and `Future` instruments instead require the initial margin of the order
+    /// notional value (`price * quantity * contract_size / leverage`), plus the maximum fees,
+    /// in the settlement asset.
+    fn balance_required<'a>(
+        &self,
+        instrument: &'a Instrument<ExchangeId, AssetNameExchange>,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> (&'a AssetNameExchange, Decimal) {
+        let rate = self.fee_schedule.rate_max();
+
+        if let Some(settlement_asset) = settlement_asset_margined(&instrument.kind) {
+            let notional = price * quantity.abs() * instrument.kind.contract_size();
+            return (
+                settlement_asset,
+                notional / self.margin.leverage + notional * rate,
+            );
+        }
+
+        let underlying = &instrument.underlying;
+
+        match (side, &self.fee_schedule.asset) {
+            (Side::Buy, FeeAsset::Quote) => {
+                let value_quote = price * quantity.abs();
+                (&underlying.quote, value_quote + value_quote * rate)
+            }
+            (Side::Buy, _) => (&underlying.quote, price * quantity.abs()),
+            (Side::Sell, FeeAsset::Base) => {
+                (&underlying.base, quantity.abs() + quantity.abs() * rate)
+            }
+            (Side::Sell, _) => (&underlying.base, quantity.abs()),
+        }
+    }
+
+    fn validate_balance_sufficient(
+        &mut self,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Result<(), UnindexedApiError> {
+        let (asset, required) = self.balance_required(instrument, side, price, quantity);
+
+        let current = self
+            .account
+            .balance_mut(asset)
+            .expect("MockExchange has Balance for all configured Instrument assets");
+
+        if current.balance.free >= required {
+            Ok(())
+        } else {
+            Err(ApiError::BalanceInsufficient(
+                asset.clone(),
+                format!(
+                    "Available Balance: {}, Required Balance inc. fees: {}",
+                    current.balance.free, required
+                ),
+            ))
+        }
+    }
+
+    fn reserve_balance(
+        &mut self,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Result<Snapshot<AssetBalance<AssetNameExchange>>, UnindexedApiError> {
+        self.validate_balance_sufficient(instrument, side, price, quantity)?;
+
+        let (asset, required) = self.balance_required(instrument, side, price, quantity);
+        Ok(self.update_balance_free(asset, -required))
+    }
+
+    fn release_balance(
+        &mut self,
+        instrument: &Instrument<ExchangeId, AssetNameExchange>,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Snapshot<AssetBalance<AssetNameExchange>> {
+        let (asset, reserved) = self.balance_required(instrument, side, price, quantity);
+        self.update_balance_free(asset, reserved)
+    }
+
+    fn update_balance_free(
+        &mut self,
+        asset: &AssetNameExchange,
+        delta: Decimal,
+    ) -> Snapshot<AssetBalance<AssetNameExchange>> {
+        let time_exchange = self.time_exchange();
+
+        let current = self
+            .account
+            .balance_mut(asset)
+            .expect("MockExchange has Balance for all configured Instrument assets");
+
+        current.balance.free += delta;
+        current.time_exchange = time_exchange;
+
+        Snapshot(current.clone())
+    }
+
+    pub fn validate_order_kind_supported(
+        &self,
+        order_kind: OrderKind,
+    ) -> Result<(), UnindexedOrderError> {
+        match order_kind {
+            OrderKind::TrailingStop {
+                offset: TrailingOffset::Price(offset) | TrailingOffset::Percent(offset),
+                ..
+            } if offset <= Decimal::ZERO => {
+                Err(UnindexedOrderError::Rejected(ApiError::OrderRejected(
+                    format!("MockExchange requires a positive TrailingStop offset: {offset}"),
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn find_instrument_data(
+        &self,
+        instrument: &InstrumentNameExchange,
+    ) -> Result<&Instrument<ExchangeId, AssetNameExchange>, UnindexedApiError> {
+        self.instruments.get(instrument).ok_or_else(|| {
+            ApiError::InstrumentInvalid(
+                instrument.clone(),
+                format!("MockExchange is not set-up for managing: {instrument}"),
+            )
+        })
+    }
+
+    fn trade_id_sequence_fetch_add(&mut self) -> TradeId {
+        let sequence = self.trade_sequence;
+        self.trade_sequence += 1;
+        TradeId::new(sequence.to_smolstr())
+    }
+
+    fn order_id_sequence_fetch_add(&mut self) -> OrderId {
+        let sequence = self.order_sequence;
+        self.order_sequence += 1;
+        OrderId::new(sequence.to_smolstr())
+    }
+
+    fn build_account_event<Kind>(&self, kind: Kind) -> UnindexedAccountEvent
+    where
+        Kind: Into<AccountEventKind<ExchangeId, AssetNameExchange, InstrumentNameExchange>>,
+    {
+        UnindexedAccountEvent {
+            exchange: self.exchange,
+            kind: kind.into(),
+        }
+    }
+}
+
+/// Settlement asset of `Perpetual` and `Future` instruments, which are margined rather than
+/// exchanging the underlying assets.
+fn settlement_asset_margined(
+    kind: &InstrumentKind<AssetNameExchange>,
+) -> Option<&AssetNameExchange> {
+    match kind {
+        InstrumentKind::Perpetual(contract) => Some(&contract.settlement_asset),
+        InstrumentKind::Future(contract) => Some(&contract.settlement_asset),
+        InstrumentKind::Spot | InstrumentKind::Option(_) => None,
+    }
+}
+
+/// Determine if an [`Order`] with the provided [`Side`] and price is crossed by the provided
+/// traded price.
+fn crosses(side: Side, order_price: Decimal, traded_price: Decimal) -> bool {
+    match side {
+        Side::Buy => order_price >= traded_price,
+        Side::Sell => order_price <= traded_price,
+    }
+}
+
+/// Determine the `(price, quantity)` fills of a marketable limit order consuming the provided
+/// opposing [`Level`]s within its limit price.
+fn fills_within_limit(
+    levels: &[Level],
+    side: Side,
+    price: Decimal,
+    quantity: Decimal,
+) -> Vec<(Decimal, Decimal)> {
+    let mut remaining = quantity;
+
+    levels
+        .iter()
+        .take_while(|level| crosses(side, price, level.price))
+        .map_while(|level| {
+            let fill = remaining.min(level.amount);
+            remaining -= fill;
+            (fill > Decimal::ZERO).then_some((level.price, fill))
+        })
+        .collect()
+}
+
+/// Parse the `MockExchange` [`OrderId`] sequence number, used to determine time priority.
+fn order_sequence(id: &OrderId) -> u64 {
+    id.0.parse().unwrap_or(u64::MAX)
+}
+
+fn build_open_order_err_response<E>(
+    request: OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
+    error: E,
 ) -> Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>
 where
     E: Into<UnindexedOrderError>,
@@ -437,8 +2491,1350 @@ where
     }
 }
 
-#[derive(Debug)]
-pub struct OpenOrderNotifications {
-    pub balance: Snapshot<AssetBalance<AssetNameExchange>>,
-    pub trade: Trade<QuoteAsset, InstrumentNameExchange>,
+fn build_amend_order_err_response<E>(
+    request: OrderRequestAmend<ExchangeId, InstrumentNameExchange>,
+    error: E,
+) -> UnindexedOrderResponseAmend
+where
+    E: Into<UnindexedOrderError>,
+{
+    Order {
+        key: request.key,
+        side: request.state.side,
+        price: request.state.price,
+        quantity: request.state.quantity,
+        kind: request.state.kind,
+        time_in_force: request.state.time_in_force,
+        state: Err(error.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exchange::mock::{faults::FaultConfig, fees::FeeTier},
+        order::{
+            OrderTrigger, TriggerSource,
+            id::{ClientOrderId, StrategyId},
+            request::RequestCancel,
+        },
+    };
+    use barter_data::{
+        books::{Level, OrderBook},
+        subscription::{
+            book::{OrderBookEvent, OrderBookL1},
+            funding::FundingRate,
+            trade::PublicTrade,
+        },
+    };
+    use barter_instrument::{
+        Underlying,
+        asset::QuoteAsset,
+        instrument::{
+            kind::{future::FutureContract, perpetual::PerpetualContract},
+            quote::InstrumentQuoteAsset,
+        },
+    };
+    use rust_decimal_macros::dec;
+
+    fn exchange() -> MockExchange {
+        try_exchange(MarginConfig::default()).unwrap()
+    }
+
+    fn try_exchange(margin: MarginConfig) -> Result<MockExchange, MockExchangeConfigError> {
+        let (_, request_rx) = mpsc::unbounded_channel();
+        let (_, market_rx) = mpsc::unbounded_channel();
+        let (event_tx, _) = broadcast::channel(16);
+        let (disconnect_tx, _) = broadcast::channel(1);
+
+        let instrument = Instrument::spot(
+            ExchangeId::Mock,
+            "mock_btc_usdt",
+            "BTCUSDT",
+            Underlying::new("btc", "usdt"),
+            None,
+        );
+
+        let config = MockExecutionConfig {
+            mocked_exchange: ExchangeId::Mock,
+            initial_state: UnindexedAccountSnapshot {
+                exchange: ExchangeId::Mock,
+                balances: vec![balance("btc", dec!(1)), balance("usdt", dec!(1000))],
+                instruments: vec![],
+            },
+            latency_ms: 0,
+            latency: None,
+            fees_percent: dec!(0.1),
+            fee_schedule: None,
+            slippage: SlippageConfig::default(),
+            faults: FaultConfig::default(),
+            margin,
+            expiry_settlement: ExpirySettlement::default(),
+        };
+
+        MockExchange::new(
+            config,
+            request_rx,
+            market_rx,
+            event_tx,
+            disconnect_tx,
+            FnvHashMap::from_iter([(instrument.name_exchange.clone(), instrument)]),
+        )
+    }
+
+    /// [`MockExchange`] with a fee free "BTCUSDT" linear perpetual, instead of the spot instrument.
+    fn exchange_perpetual(mode: MarginMode) -> MockExchange {
+        let mut exchange = exchange();
+        exchange.fee_schedule = FeeSchedule::flat(dec!(0));
+        exchange.margin = MarginConfig {
+            mode,
+            leverage: dec!(10),
+            maintenance_margin: dec!(0.005),
+        };
+
+        let instrument = Instrument::new(
+            ExchangeId::Mock,
+            "mock_btc_usdt_perpetual",
+            "BTCUSDT",
+            Underlying::new("btc", "usdt"),
+            InstrumentQuoteAsset::UnderlyingQuote,
+            InstrumentKind::Perpetual(PerpetualContract {
+                contract_size: dec!(1),
+                settlement_asset: AssetNameExchange::from("usdt"),
+            }),
+            None,
+        );
+        exchange.instruments =
+            FnvHashMap::from_iter([(instrument.name_exchange.clone(), instrument)]);
+        exchange
+    }
+
+    fn balance(asset: &str, amount: Decimal) -> AssetBalance<AssetNameExchange> {
+        AssetBalance {
+            asset: AssetNameExchange::from(asset),
+            balance: crate::balance::Balance::new(amount, amount),
+            time_exchange: Default::default(),
+        }
+    }
+
+    fn find_balance(exchange: &MockExchange, asset: &str) -> crate::balance::Balance {
+        exchange
+            .account
+            .balances()
+            .find(|balance| balance.asset == AssetNameExchange::from(asset))
+            .unwrap()
+            .balance
+    }
+
+    fn key(cid: &str) -> OrderKey<ExchangeId, InstrumentNameExchange> {
+        OrderKey {
+            exchange: ExchangeId::Mock,
+            instrument: InstrumentNameExchange::from("BTCUSDT"),
+            strategy: StrategyId::unknown(),
+            cid: ClientOrderId::new(cid),
+        }
+    }
+
+    fn request_open(
+        cid: &str,
+        side: Side,
+        kind: OrderKind,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> OrderRequestOpen<ExchangeId, InstrumentNameExchange> {
+        OrderRequestOpen {
+            key: key(cid),
+            state: RequestOpen {
+                side,
+                price,
+                quantity,
+                kind,
+                time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+                flags: OrderFlags::default(),
+            },
+        }
+    }
+
+    fn request_cancel(cid: &str) -> OrderRequestCancel<ExchangeId, InstrumentNameExchange> {
+        OrderRequestCancel {
+            key: key(cid),
+            state: RequestCancel { id: None },
+        }
+    }
+
+    #[test]
+    fn test_open_limit_order_rests_and_reserves_balance() {
+        let mut exchange = exchange();
+
+        let (response, _) = exchange.open_order(request_open(
+            "buy",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100),
+            dec!(5),
+        ));
+
+        let open = response.state.unwrap();
+        assert_eq!(open.filled_quantity, dec!(0));
+        assert_eq!(exchange.account.orders_open().count(), 1);
+
+        // 5 * 100 + 10% fees reserved
+        let usdt = find_balance(&exchange, "usdt");
+        assert_eq!(usdt.total, dec!(1000));
+        assert_eq!(usdt.free, dec!(450));
+
+        let (response, _) = exchange.open_order(request_open(
+            "sell",
+            Side::Sell,
+            OrderKind::Limit,
+            dec!(200),
+            dec!(0.5),
+        ));
+
+        assert!(response.state.is_ok());
+        let btc = find_balance(&exchange, "btc");
+        assert_eq!(btc.total, dec!(1));
+        assert_eq!(btc.free, dec!(0.5));
+
+        // Insufficient free balance remaining for another resting buy
+        let (response, notifications) = exchange.open_order(request_open(
+            "buy_insufficient",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100),
+            dec!(5),
+        ));
+
+        assert!(matches!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(
+                ApiError::BalanceInsufficient(_, _)
+            ))
+        ));
+        assert!(notifications.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_order_releases_balance() {
+        let mut exchange = exchange();
+
+        exchange.open_order(request_open(
+            "buy",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100),
+            dec!(5),
+        ));
+
+        let (response, notifications) = exchange.cancel_order(request_cancel("buy"));
+        assert!(response.state.is_ok());
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(
+            notifications[1].kind,
+            AccountEventKind::OrderCancelled(response.clone())
+        );
+        assert_eq!(exchange.account.orders_open().count(), 0);
+        assert_eq!(exchange.account.orders_cancelled().count(), 1);
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(1000), dec!(1000))
+        );
+
+        let (response, _) = exchange.cancel_order(request_cancel("buy"));
+        assert_eq!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(
+                ApiError::OrderAlreadyCancelled
+            ))
+        );
+
+        let (response, _) = exchange.cancel_order(request_cancel("unknown"));
+        assert!(matches!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(ApiError::OrderRejected(_)))
+        ));
+    }
+
+    #[test]
+    fn test_own_resting_order_expired_when_crossed_by_taker_order() {
+        let mut exchange = exchange();
+
+        exchange.open_order(request_open(
+            "buy_limit",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100),
+            dec!(2),
+        ));
+
+        // Market sell trades through the resting buy limit price, which must not self-match
+        let (response, notifications) = exchange.open_order(request_open(
+            "sell_market",
+            Side::Sell,
+            OrderKind::Market,
+            dec!(90),
+            dec!(1),
+        ));
+
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(1));
+        assert_eq!(exchange.account.orders_open().count(), 0);
+
+        let trades = notifications
+            .iter()
+            .filter_map(|event| match &event.kind {
+                AccountEventKind::Trade(trade) => Some(trade),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].price, dec!(90));
+
+        assert!(notifications.iter().any(|event| matches!(
+            &event.kind,
+            AccountEventKind::OrderSnapshot(Snapshot(order))
+                if order.key.cid == ClientOrderId::new("buy_limit")
+                    && order.state == OrderState::expired()
+        )));
+
+        // btc: 1 - 1 (sell) = 0
+        assert_eq!(
+            find_balance(&exchange, "btc"),
+            crate::balance::Balance::new(dec!(0), dec!(0))
+        );
+
+        // usdt: 1000 + (90 - 9 fees), with the buy limit reservation released
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(1081), dec!(1081))
+        );
+
+        // Marketable limit orders are filled immediately against the last traded price
+        let (response, _) = exchange.open_order(request_open(
+            "buy_marketable",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(101),
+            dec!(1),
+        ));
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(1));
+        assert_eq!(exchange.account.orders_open().count(), 0);
+    }
+
+    fn market_event(kind: DataKind) -> MarketEvent<InstrumentNameExchange, DataKind> {
+        MarketEvent {
+            time_exchange: Default::default(),
+            time_received: Default::default(),
+            exchange: ExchangeId::Mock,
+            instrument: InstrumentNameExchange::from("BTCUSDT"),
+            kind,
+        }
+    }
+
+    fn l1(best_bid: Decimal, best_ask: Decimal) -> DataKind {
+        DataKind::OrderBookL1(OrderBookL1 {
+            last_update_time: Default::default(),
+            best_bid: Some(Level::new(best_bid, dec!(10))),
+            best_ask: Some(Level::new(best_ask, dec!(10))),
+        })
+    }
+
+    fn trade(price: f64) -> DataKind {
+        trade_amount(price, 1.0)
+    }
+
+    fn trade_amount(price: f64, amount: f64) -> DataKind {
+        DataKind::Trade(PublicTrade {
+            id: "trade".to_string(),
+            price,
+            amount,
+            side: Side::Buy,
+        })
+    }
+
+    fn trades(
+        notifications: &[UnindexedAccountEvent],
+    ) -> Vec<&Trade<QuoteAsset, InstrumentNameExchange>> {
+        notifications
+            .iter()
+            .filter_map(|event| match &event.kind {
+                AccountEventKind::Trade(trade) => Some(trade),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_market_order_filled_against_prevailing_book() {
+        let mut exchange = exchange();
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(101))));
+
+        // Requested price is ignored in favour of the best ask
+        let (response, notifications) = exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(50),
+            dec!(1),
+        ));
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(1));
+        assert_eq!(trades(&notifications)[0].price, dec!(101));
+
+        // Sells are filled at the best bid
+        let (_, notifications) = exchange.open_order(request_open(
+            "sell_market",
+            Side::Sell,
+            OrderKind::Market,
+            dec!(200),
+            dec!(1),
+        ));
+        assert_eq!(trades(&notifications)[0].price, dec!(99));
+
+        // Marketable limit orders are filled at the prevailing best ask
+        let (response, notifications) = exchange.open_order(request_open(
+            "buy_limit_marketable",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(105),
+            dec!(1),
+        ));
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(1));
+        assert_eq!(trades(&notifications)[0].price, dec!(101));
+    }
+
+    #[test]
+    fn test_resting_limit_order_filled_by_market_data() {
+        let mut exchange = exchange();
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(101))));
+
+        exchange.open_order(request_open(
+            "buy_limit",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100),
+            dec!(1),
+        ));
+        exchange.open_order(request_open(
+            "sell_limit",
+            Side::Sell,
+            OrderKind::Limit,
+            dec!(110),
+            dec!(1),
+        ));
+        assert_eq!(exchange.account.orders_open().count(), 2);
+
+        // Trade above the buy limit price does not cross
+        let notifications = exchange.process_market_event(market_event(trade(100.5)));
+        assert!(notifications.is_empty());
+
+        // Trade at the buy limit price crosses, and fills at the limit price
+        let notifications = exchange.process_market_event(market_event(trade(100.0)));
+        let fills = trades(&notifications);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, dec!(100));
+        assert_eq!(exchange.account.orders_open().count(), 1);
+
+        // Best bid moving through the sell limit price crosses
+        let notifications = exchange.process_market_event(market_event(l1(dec!(111), dec!(112))));
+        let fills = trades(&notifications);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Sell);
+        assert_eq!(fills[0].price, dec!(110));
+        assert_eq!(exchange.account.orders_open().count(), 0);
+
+        // Market events for unknown instruments are ignored
+        let mut unknown = market_event(trade(1.0));
+        unknown.instrument = InstrumentNameExchange::from("ETHUSDT");
+        assert!(exchange.process_market_event(unknown).is_empty());
+    }
+
+    #[test]
+    fn test_stop_market_order_triggered_by_market_data() {
+        let mut exchange = exchange();
+        exchange.process_market_event(market_event(trade(100.0)));
+
+        let stop = |price| OrderKind::StopMarket {
+            trigger: OrderTrigger::new(price, TriggerSource::Last),
+        };
+
+        // Stop that would immediately trigger is rejected
+        let (response, _) = exchange.open_order(request_open(
+            "rejected",
+            Side::Sell,
+            stop(dec!(101)),
+            dec!(101),
+            dec!(1),
+        ));
+        assert!(response.state.is_err());
+
+        let (response, _) = exchange.open_order(request_open(
+            "stop",
+            Side::Sell,
+            stop(dec!(95)),
+            dec!(95),
+            dec!(1),
+        ));
+        assert!(response.state.is_ok());
+        assert_eq!(exchange.account.orders_open().count(), 1);
+        assert_eq!(find_balance(&exchange, "btc").free, dec!(0));
+
+        // Untriggered stop does not rest in the book, so is not crossed by trades through its price
+        let notifications = exchange.process_market_event(market_event(trade(96.0)));
+        assert!(trades(&notifications).is_empty());
+        assert_eq!(exchange.account.orders_open().count(), 1);
+
+        // Trade at the trigger price triggers the stop, which is filled at market
+        let notifications = exchange.process_market_event(market_event(trade(95.0)));
+        let fills = trades(&notifications);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Sell);
+        assert_eq!(fills[0].price, dec!(95));
+        assert_eq!(exchange.account.orders_open().count(), 0);
+        assert!(exchange.triggers.is_empty());
+    }
+
+    #[test]
+    fn test_take_profit_limit_order_rests_once_triggered() {
+        let mut exchange = exchange();
+        exchange.process_market_event(market_event(trade(100.0)));
+
+        let take_profit = OrderKind::TakeProfitLimit {
+            trigger: OrderTrigger::new(dec!(98), TriggerSource::Last),
+        };
+        let (response, _) = exchange.open_order(request_open(
+            "take_profit",
+            Side::Buy,
+            take_profit,
+            dec!(97),
+            dec!(1),
+        ));
+        assert!(response.state.is_ok());
+
+        // Triggered, but the limit price does not cross the market, so the order rests
+        let notifications = exchange.process_market_event(market_event(trade(98.0)));
+        assert!(trades(&notifications).is_empty());
+        assert_eq!(exchange.account.orders_open().count(), 1);
+        assert!(exchange.triggers.is_empty());
+
+        // Resting limit order is then crossed at its limit price
+        let notifications = exchange.process_market_event(market_event(trade(97.0)));
+        let fills = trades(&notifications);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, dec!(97));
+        assert_eq!(exchange.account.orders_open().count(), 0);
+    }
+
+    #[test]
+    fn test_market_order_filled_with_slippage() {
+        let mut exchange = exchange();
+        exchange.slippage = SlippageConfig::WalkBook(crate::slippage::WalkBookSlippage);
+
+        exchange.process_market_event(market_event(DataKind::OrderBook(OrderBookEvent::Snapshot(
+            OrderBook::new(
+                0,
+                None,
+                [(dec!(99), dec!(1))],
+                [(dec!(100), dec!(1)), (dec!(102), dec!(1))],
+            ),
+        ))));
+
+        // (1 * 100 + 1 * 102) / 2
+        let (_, notifications) = exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(100),
+            dec!(2),
+        ));
+        assert_eq!(trades(&notifications)[0].price, dec!(101));
+
+        // Marketable limit orders only consume liquidity within their limit price
+        let (response, notifications) = exchange.open_order(request_open(
+            "buy_limit",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100.5),
+            dec!(2),
+        ));
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(1));
+        let fills = trades(&notifications);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, dec!(100));
+        assert_eq!(fills[0].quantity, dec!(1));
+
+        // Remaining quantity rests at the limit price
+        let order = exchange
+            .account
+            .order_open(&ClientOrderId::new("buy_limit"))
+            .unwrap();
+        assert_eq!(order.state.filled_quantity, dec!(1));
+    }
+
+    #[test]
+    fn test_resting_limit_order_partially_filled_behind_queue() {
+        let mut exchange = exchange();
+
+        exchange.process_market_event(market_event(DataKind::OrderBook(OrderBookEvent::Snapshot(
+            OrderBook::new(0, None, [(dec!(100), dec!(3))], [(dec!(101), dec!(1))]),
+        ))));
+
+        // Joins the back of the queue behind the 3 displayed at 100
+        exchange.open_order(request_open(
+            "buy_limit",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100),
+            dec!(2),
+        ));
+        let cid = ClientOrderId::new("buy_limit");
+        assert_eq!(exchange.queue_ahead.get(&cid), Some(&dec!(3)));
+
+        // Queue ahead shrinks with the displayed level (eg/ cancellations)
+        exchange.process_market_event(market_event(DataKind::OrderBook(OrderBookEvent::Update(
+            OrderBook::new(1, None, [Level::new(dec!(100), dec!(2))], []),
+        ))));
+        assert_eq!(exchange.queue_ahead.get(&cid), Some(&dec!(2)));
+
+        // Trade at our price only consumes the queue ahead
+        let notifications = exchange.process_market_event(market_event(trade_amount(100.0, 1.5)));
+        assert!(notifications.is_empty());
+
+        // Trade at our price partially fills once the queue ahead is consumed
+        let notifications = exchange.process_market_event(market_event(trade_amount(100.0, 1.5)));
+        let fills = trades(&notifications);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, dec!(1));
+        assert!(notifications.iter().any(|event| matches!(
+            &event.kind,
+            AccountEventKind::OrderSnapshot(Snapshot(order))
+                if order.state == OrderState::active(Open {
+                    id: OrderId::new("0"),
+                    time_exchange: Default::default(),
+                    filled_quantity: dec!(1),
+                })
+        )));
+
+        // Released reservation for the filled quantity: 1000 - (100 + 10 fees) filled - 110 reserved
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(890), dec!(780))
+        );
+
+        // Trade through our price fills the remaining quantity
+        let notifications = exchange.process_market_event(market_event(trade_amount(99.0, 0.1)));
+        let fills = trades(&notifications);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, dec!(1));
+        assert_eq!(exchange.account.orders_open().count(), 0);
+        assert!(exchange.queue_ahead.is_empty());
+    }
+
+    fn request_amend(
+        cid: &str,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> OrderRequestAmend<ExchangeId, InstrumentNameExchange> {
+        OrderRequestAmend {
+            key: key(cid),
+            state: RequestAmend {
+                id: None,
+                side,
+                price,
+                quantity,
+                kind: OrderKind::Limit,
+                time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+                filled_quantity: Decimal::ZERO,
+            },
+        }
+    }
+
+    #[test]
+    fn test_amend_order_re_reserves_balance_and_updates_priority() {
+        let mut exchange = exchange();
+
+        exchange.process_market_event(market_event(DataKind::OrderBook(OrderBookEvent::Snapshot(
+            OrderBook::new(0, None, [(dec!(100), dec!(3))], [(dec!(101), dec!(1))]),
+        ))));
+
+        exchange.open_order(request_open(
+            "buy_limit",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(100),
+            dec!(5),
+        ));
+        let cid = ClientOrderId::new("buy_limit");
+
+        // Reducing quantity retains time priority & queue position
+        let (response, _) =
+            exchange.amend_order(request_amend("buy_limit", Side::Buy, dec!(100), dec!(2)));
+        assert_eq!(response.state.unwrap().id, OrderId::new("0"));
+        assert_eq!(exchange.queue_ahead.get(&cid), Some(&dec!(3)));
+        assert_eq!(find_balance(&exchange, "usdt").free, dec!(780));
+
+        // Changing price loses time priority & queue position
+        let (response, _) =
+            exchange.amend_order(request_amend("buy_limit", Side::Buy, dec!(99), dec!(2)));
+        assert_eq!(response.price, dec!(99));
+        assert_eq!(response.state.unwrap().id, OrderId::new("1"));
+        assert_eq!(exchange.queue_ahead.get(&cid), Some(&dec!(0)));
+        assert_eq!(find_balance(&exchange, "usdt").free, dec!(782.2));
+
+        // Amends that cross the market, change side, or target unknown orders are rejected
+        let rejected = [
+            request_amend("buy_limit", Side::Buy, dec!(102), dec!(2)),
+            request_amend("buy_limit", Side::Sell, dec!(99), dec!(2)),
+            request_amend("unknown", Side::Buy, dec!(99), dec!(2)),
+        ];
+        for (index, request) in rejected.into_iter().enumerate() {
+            let (response, notifications) = exchange.amend_order(request);
+            assert!(response.state.is_err(), "TC{index} failed");
+            assert!(notifications.is_empty(), "TC{index} failed");
+        }
+
+        let order = exchange.account.order_open(&cid).unwrap();
+        assert_eq!((order.price, order.quantity), (dec!(99), dec!(2)));
+        assert_eq!(find_balance(&exchange, "usdt").free, dec!(782.2));
+    }
+
+    #[test]
+    fn test_fills_charged_maker_taker_fees_in_base() {
+        let mut exchange = exchange();
+        exchange.fee_schedule = FeeSchedule {
+            maker: dec!(-0.001),
+            taker: dec!(0.002),
+            tiers: vec![],
+            asset: FeeAsset::Base,
+        };
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(101))));
+
+        // Taker fee is charged in base, and recorded as its quote equivalent
+        let (_, notifications) = exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(101),
+            dec!(1),
+        ));
+        assert_eq!(trades(&notifications)[0].fees.fees, dec!(0.202));
+        assert_eq!(
+            find_balance(&exchange, "btc"),
+            crate::balance::Balance::new(dec!(1.998), dec!(1.998))
+        );
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(899), dec!(899))
+        );
+
+        // Resting sell reserves base inc. the maximum fee rate
+        exchange.open_order(request_open(
+            "sell_limit",
+            Side::Sell,
+            OrderKind::Limit,
+            dec!(110),
+            dec!(1),
+        ));
+        assert_eq!(
+            find_balance(&exchange, "btc"),
+            crate::balance::Balance::new(dec!(1.998), dec!(0.996))
+        );
+
+        // Maker fill is paid a rebate
+        let notifications = exchange.process_market_event(market_event(trade(110.0)));
+        assert_eq!(trades(&notifications)[0].fees.fees, dec!(-0.11));
+        assert_eq!(
+            find_balance(&exchange, "btc"),
+            crate::balance::Balance::new(dec!(0.999), dec!(0.999))
+        );
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(1009), dec!(1009))
+        );
+    }
+
+    #[test]
+    fn test_fills_charged_tiered_fees_in_third_asset() {
+        let mut exchange = exchange();
+        exchange.account = AccountState::from(UnindexedAccountSnapshot {
+            exchange: ExchangeId::Mock,
+            balances: vec![
+                balance("btc", dec!(1)),
+                balance("usdt", dec!(1000)),
+                balance("bnb", dec!(1)),
+            ],
+            instruments: vec![],
+        });
+        exchange.fee_schedule = FeeSchedule {
+            maker: dec!(0.001),
+            taker: dec!(0.001),
+            tiers: vec![FeeTier {
+                volume_quote: dec!(100),
+                maker: dec!(0.0005),
+                taker: dec!(0.0005),
+            }],
+            asset: FeeAsset::Other {
+                asset: AssetNameExchange::new("bnb"),
+                price_quote: dec!(10),
+            },
+        };
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(100))));
+
+        let mut buy = |cid: &str| {
+            let (_, notifications) = exchange.open_order(request_open(
+                cid,
+                Side::Buy,
+                OrderKind::Market,
+                dec!(100),
+                dec!(1),
+            ));
+            trades(&notifications)[0].fees.fees
+        };
+
+        // Base rate, then tier rate once 100 quote volume has been traded
+        assert_eq!(buy("first"), dec!(0.1));
+        assert_eq!(buy("second"), dec!(0.05));
+
+        // bnb: 1 - (0.1 + 0.05) / 10
+        assert_eq!(
+            find_balance(&exchange, "bnb"),
+            crate::balance::Balance::new(dec!(0.985), dec!(0.985))
+        );
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(800), dec!(800))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_account_stream_order_events() {
+        let mut exchange = exchange().with_synchronous_delivery();
+        let mut account_stream = exchange.account_stream();
+
+        let mut next_order_event = async || {
+            loop {
+                let event = tokio::time::timeout(
+                    std::time::Duration::from_millis(50),
+                    futures::StreamExt::next(&mut account_stream),
+                )
+                .await
+                .ok()??;
+                match event.kind {
+                    AccountEventKind::BalanceSnapshot(_) => continue,
+                    kind => return Some(kind),
+                }
+            }
+        };
+
+        // Resting limit order -> expect OrderSnapshot of the open order
+        let (response_tx, response_rx) = oneshot::channel();
+        exchange.process_request(MockExchangeRequest::open_order(
+            Default::default(),
+            response_tx,
+            request_open("buy", Side::Buy, OrderKind::Limit, dec!(100), dec!(1)),
+        ));
+        let open = response_rx.await.unwrap().state.unwrap();
+
+        let Some(AccountEventKind::OrderSnapshot(Snapshot(order))) = next_order_event().await
+        else {
+            panic!("expected OrderSnapshot of resting order")
+        };
+        assert_eq!(order.key, key("buy"));
+        assert_eq!(order.state, OrderState::active(open));
+
+        // Cancel resting order -> expect OrderCancelled
+        let (response_tx, response_rx) = oneshot::channel();
+        exchange.process_request(MockExchangeRequest::cancel_order(
+            Default::default(),
+            response_tx,
+            request_cancel("buy"),
+        ));
+        let response = response_rx.await.unwrap();
+        assert!(response.state.is_ok());
+
+        assert_eq!(
+            next_order_event().await,
+            Some(AccountEventKind::OrderCancelled(response))
+        );
+        assert_eq!(next_order_event().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_order_requests_with_injected_faults() {
+        let mut exchange = exchange();
+        exchange.faults = FaultInjector::new(FaultConfig {
+            seed: 0,
+            schedule: std::collections::BTreeMap::from([
+                (0, Fault::RateLimit),
+                (1, Fault::Timeout),
+                (2, Fault::Reorder),
+                (3, Fault::Duplicate),
+                (4, Fault::Disconnect),
+            ]),
+            probabilities: Default::default(),
+        });
+        let mut event_rx = exchange.event_tx.subscribe();
+        let mut disconnect_rx = exchange.disconnect_tx.subscribe();
+
+        let mut open = |cid: &str| {
+            let (response_tx, response_rx) = oneshot::channel();
+            exchange.process_request(MockExchangeRequest::open_order(
+                Default::default(),
+                response_tx,
+                request_open(cid, Side::Buy, OrderKind::Limit, dec!(100), dec!(1)),
+            ));
+            response_rx
+        };
+
+        let mut recv_balance_free = async || {
+            loop {
+                let event =
+                    tokio::time::timeout(std::time::Duration::from_millis(50), event_rx.recv())
+                        .await
+                        .ok()?
+                        .unwrap();
+                if let AccountEventKind::BalanceSnapshot(Snapshot(balance)) = event.kind {
+                    return Some(balance.balance.free);
+                }
+            }
+        };
+
+        // Rejected without being actioned
+        let response = open("rate_limit").await.unwrap();
+        assert_eq!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(ApiError::RateLimit))
+        );
+
+        // Actioned, but the response is never sent
+        let mut response_rx = open("timeout");
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(50), &mut response_rx)
+                .await
+                .is_err()
+        );
+        assert_eq!(recv_balance_free().await, Some(dec!(890)));
+
+        // Notifications are delayed until after the next batch, which is duplicated
+        open("reorder").await.unwrap();
+        assert_eq!(recv_balance_free().await, None);
+        open("duplicate").await.unwrap();
+        assert_eq!(recv_balance_free().await, Some(dec!(670)));
+        assert_eq!(recv_balance_free().await, Some(dec!(670)));
+        assert_eq!(recv_balance_free().await, Some(dec!(780)));
+
+        // AccountStream is dropped
+        open("disconnect").await.unwrap();
+        assert!(disconnect_rx.recv().await.is_ok());
+    }
+
+    #[test]
+    fn test_perpetual_orders_lock_margin_and_realise_pnl() {
+        let mut exchange = exchange_perpetual(MarginMode::Cross);
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(100))));
+
+        // Opening 10 contracts at 10x leverage locks 100 usdt of initial margin
+        let (response, _) = exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(100),
+            dec!(10),
+        ));
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(10));
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(1000), dec!(900))
+        );
+        // Underlying assets are not exchanged
+        assert_eq!(find_balance(&exchange, "btc").total, dec!(1));
+
+        // Resting orders reserve initial margin for their quantity
+        exchange.open_order(request_open(
+            "sell_limit",
+            Side::Sell,
+            OrderKind::Limit,
+            dec!(120),
+            dec!(5),
+        ));
+        assert_eq!(find_balance(&exchange, "usdt").free, dec!(840));
+
+        // Reducing the position realises PnL & releases margin proportionally
+        let notifications = exchange.process_market_event(market_event(l1(dec!(120), dec!(121))));
+        assert_eq!(trades(&notifications)[0].price, dec!(120));
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(1100), dec!(1050))
+        );
+
+        // Reducing fills require no additional margin, and closing releases all margin
+        exchange.process_market_event(market_event(l1(dec!(110), dec!(111))));
+        exchange.open_order(request_open(
+            "sell_market",
+            Side::Sell,
+            OrderKind::Market,
+            dec!(110),
+            dec!(5),
+        ));
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(1150), dec!(1150))
+        );
+        assert!(exchange.positions.is_empty());
+    }
+
+    #[test]
+    fn test_perpetual_invalid_leverage_and_inverse_contracts_rejected() {
+        // Non-positive leverage is rejected when constructing the MockExchange
+        for leverage in [dec!(0), dec!(-1)] {
+            let margin = MarginConfig {
+                leverage,
+                ..MarginConfig::default()
+            };
+            assert_eq!(
+                try_exchange(margin).unwrap_err(),
+                MockExchangeConfigError::Leverage(leverage)
+            );
+        }
+
+        // Inverse contracts settled in the base asset are rejected
+        let mut exchange = exchange_perpetual(MarginMode::Cross);
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(100))));
+        for instrument in exchange.instruments.values_mut() {
+            instrument.kind = InstrumentKind::Perpetual(PerpetualContract {
+                contract_size: dec!(100),
+                settlement_asset: AssetNameExchange::from("btc"),
+            });
+        }
+
+        let (response, notifications) = exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(100),
+            dec!(1),
+        ));
+        assert!(matches!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(ApiError::InstrumentInvalid(
+                _,
+                _
+            )))
+        ));
+        assert!(notifications.is_empty());
+        assert!(exchange.positions.is_empty());
+    }
+
+    #[test]
+    fn test_post_only_order_rejected_if_it_would_immediately_match() {
+        let mut exchange = exchange();
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(100))));
+
+        let post_only = |cid, price| OrderRequestOpen {
+            key: key(cid),
+            state: RequestOpen {
+                time_in_force: TimeInForce::GoodUntilCancelled { post_only: true },
+                ..request_open(cid, Side::Buy, OrderKind::Limit, price, dec!(1)).state
+            },
+        };
+
+        // Crossing the best ask is rejected, rather than filled as a taker
+        let (response, notifications) = exchange.open_order(post_only("crossing", dec!(100)));
+        assert!(matches!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(ApiError::OrderRejected(_)))
+        ));
+        assert!(notifications.is_empty());
+
+        // Resting below the best ask is accepted
+        let (response, _) = exchange.open_order(post_only("resting", dec!(99)));
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(0));
+        assert_eq!(exchange.account.orders_open().count(), 1);
+    }
+
+    #[test]
+    fn test_perpetual_reduce_only_and_close_position_orders() {
+        let mut exchange = exchange_perpetual(MarginMode::Cross);
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(100))));
+
+        let flagged = |cid, side, kind, quantity, flags| OrderRequestOpen {
+            key: key(cid),
+            state: RequestOpen {
+                flags,
+                ..request_open(cid, side, kind, dec!(100), quantity).state
+            },
+        };
+
+        // Reduce only orders cannot open a position
+        let (response, _) = exchange.open_order(flagged(
+            "reduce_flat",
+            Side::Sell,
+            OrderKind::Market,
+            dec!(1),
+            OrderFlags::reduce_only(),
+        ));
+        assert!(response.state.is_err());
+
+        exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(100),
+            dec!(10),
+        ));
+
+        // Reduce only orders cannot flip a position
+        let (response, _) = exchange.open_order(flagged(
+            "reduce_flip",
+            Side::Sell,
+            OrderKind::Market,
+            dec!(15),
+            OrderFlags::reduce_only(),
+        ));
+        assert!(matches!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(ApiError::OrderRejected(_)))
+        ));
+        assert_eq!(
+            exchange.positions.values().next().unwrap().quantity,
+            dec!(10)
+        );
+
+        // Resting reduce only orders are expired once there is no position left to reduce
+        exchange.open_order(flagged(
+            "reduce_resting",
+            Side::Sell,
+            OrderKind::Limit,
+            dec!(4),
+            OrderFlags::reduce_only(),
+        ));
+
+        // Close position orders are sized to the entire position, irrespective of quantity
+        let (response, _) = exchange.open_order(flagged(
+            "close",
+            Side::Sell,
+            OrderKind::Market,
+            dec!(1),
+            OrderFlags::close_position(),
+        ));
+        assert_eq!(response.quantity, dec!(10));
+        assert_eq!(response.state.unwrap().filled_quantity, dec!(10));
+        assert!(exchange.positions.is_empty());
+
+        let notifications = exchange.process_market_event(market_event(l1(dec!(120), dec!(121))));
+        assert!(trades(&notifications).is_empty());
+        assert!(notifications.iter().any(|event| matches!(
+            &event.kind,
+            AccountEventKind::OrderSnapshot(Snapshot(order))
+                if order.key.cid.0 == "reduce_resting" && order.state == OrderState::expired()
+        )));
+        assert_eq!(exchange.account.orders_open().count(), 0);
+
+        // Closing 10 contracts bought at 100 at the best bid of 99 realises 10 usdt of losses,
+        // and the expired order releases its reserved margin
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(990), dec!(990))
+        );
+    }
+
+    #[test]
+    fn test_perpetual_position_liquidated_below_maintenance_margin() {
+        // Isolated: 10x long of 10 contracts is only backed by its 100 usdt margin
+        let mut exchange = exchange_perpetual(MarginMode::Isolated);
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(100))));
+        exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(100),
+            dec!(10),
+        ));
+
+        // Equity 5 usdt at mark 90.5 remains above maintenance margin
+        let notifications = exchange.process_market_event(market_event(l1(dec!(90), dec!(91))));
+        assert!(notifications.is_empty());
+
+        // Equity -5 usdt at mark 89.5 is liquidated, with losses capped at the bankruptcy price
+        let notifications = exchange.process_market_event(market_event(l1(dec!(89), dec!(90))));
+        let liquidation = trades(&notifications)[0];
+        assert_eq!(liquidation.side, Side::Sell);
+        assert_eq!(liquidation.price, dec!(90));
+        assert_eq!(liquidation.quantity, dec!(10));
+        assert_eq!(liquidation.strategy, StrategyId::new("liquidation"));
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(900), dec!(900))
+        );
+        assert!(exchange.positions.is_empty());
+
+        // Cross: 10x long of 90 contracts is backed by the full 1000 usdt balance
+        let mut exchange = exchange_perpetual(MarginMode::Cross);
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(100))));
+        exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(100),
+            dec!(90),
+        ));
+
+        // Losses beyond the initial margin are absorbed by the remaining balance
+        let notifications = exchange.process_market_event(market_event(l1(dec!(89), dec!(90))));
+        assert!(notifications.is_empty());
+
+        // Equity below maintenance margin at mark 88.5 is liquidated at the mark price
+        let notifications = exchange.process_market_event(market_event(l1(dec!(88), dec!(89))));
+        assert_eq!(trades(&notifications)[0].price, dec!(88.5));
+        assert_eq!(find_balance(&exchange, "usdt").total, dec!(-35));
+        assert!(exchange.positions.is_empty());
+    }
+
+    #[test]
+    fn test_perpetual_funding_settled_at_funding_time() {
+        let mut exchange = exchange_perpetual(MarginMode::Cross);
+        exchange.process_market_event(market_event(l1(dec!(99), dec!(101))));
+        exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(101),
+            dec!(10),
+        ));
+        assert_eq!(find_balance(&exchange, "usdt").total, dec!(1000));
+
+        let time_funding = DateTime::<Utc>::default() + TimeDelta::hours(8);
+        let market_event_at = |time_exchange, kind| MarketEvent {
+            time_exchange,
+            ..market_event(kind)
+        };
+
+        // Funding is not settled before the funding time
+        let notifications =
+            exchange.process_market_event(market_event(DataKind::FundingRate(FundingRate {
+                rate: 0.0001,
+                time_next_funding: time_funding,
+            })));
+        assert!(notifications.is_empty());
+
+        // LONG position pays funding on its notional value at the mark price
+        let notifications =
+            exchange.process_market_event(market_event_at(time_funding, l1(dec!(99), dec!(101))));
+        let funding = notifications
+            .iter()
+            .find_map(|event| match &event.kind {
+                AccountEventKind::Funding(funding) => Some(funding),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(funding.amount, dec!(-0.1));
+        assert_eq!(funding.time_exchange, time_funding);
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(999.9), dec!(898.9))
+        );
+
+        // Each funding rate is only settled once
+        let notifications = exchange.process_market_event(market_event_at(
+            time_funding + TimeDelta::hours(1),
+            l1(dec!(99), dec!(101)),
+        ));
+        assert!(notifications.is_empty());
+    }
+
+    #[test]
+    fn test_future_positions_settled_at_expiry() {
+        let time_expiry = DateTime::<Utc>::default() + TimeDelta::days(1);
+
+        let exchange_future = |settlement| {
+            let mut exchange = exchange_perpetual(MarginMode::Cross);
+            exchange.expiry_settlement = settlement;
+            for instrument in exchange.instruments.values_mut() {
+                instrument.kind = InstrumentKind::Future(FutureContract {
+                    contract_size: dec!(1),
+                    settlement_asset: AssetNameExchange::from("usdt"),
+                    expiry: time_expiry,
+                });
+            }
+            exchange.process_market_event(market_event(l1(dec!(99), dec!(101))));
+            exchange
+        };
+        let market_event_expiry = |kind| MarketEvent {
+            time_exchange: time_expiry,
+            ..market_event(kind)
+        };
+
+        // Cash: LONG position closed at the last mark price before expiry, and orders expired
+        let mut exchange = exchange_future(ExpirySettlement::Cash);
+        exchange.open_order(request_open(
+            "buy_market",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(101),
+            dec!(10),
+        ));
+        exchange.open_order(request_open(
+            "buy_limit",
+            Side::Buy,
+            OrderKind::Limit,
+            dec!(90),
+            dec!(5),
+        ));
+        assert_eq!(find_balance(&exchange, "usdt").free, dec!(854));
+
+        let notifications =
+            exchange.process_market_event(market_event_expiry(l1(dec!(119), dec!(121))));
+        let settlement = trades(&notifications)[0];
+        assert_eq!(settlement.side, Side::Sell);
+        assert_eq!(settlement.price, dec!(100));
+        assert_eq!(settlement.quantity, dec!(10));
+        assert_eq!(settlement.strategy, StrategyId::new("expiry"));
+        assert!(notifications.iter().any(|event| matches!(
+            &event.kind,
+            AccountEventKind::OrderSnapshot(Snapshot(order))
+                if order.key.cid == ClientOrderId::new("buy_limit")
+                    && order.state == OrderState::expired()
+        )));
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(990), dec!(990))
+        );
+        assert!(exchange.positions.is_empty());
+        assert_eq!(exchange.account.orders_open().count(), 0);
+
+        // Expired instruments reject new orders
+        let (response, notifications) = exchange.open_order(request_open(
+            "buy_market_expired",
+            Side::Buy,
+            OrderKind::Market,
+            dec!(121),
+            dec!(1),
+        ));
+        assert!(matches!(
+            response.state,
+            Err(UnindexedOrderError::Rejected(ApiError::InstrumentInvalid(
+                _,
+                _
+            )))
+        ));
+        assert!(notifications.is_empty());
+
+        // Physical: SHORT position is cash settled, then delivers the underlying base asset
+        let mut exchange = exchange_future(ExpirySettlement::Physical);
+        exchange.open_order(request_open(
+            "sell_market",
+            Side::Sell,
+            OrderKind::Market,
+            dec!(99),
+            dec!(1),
+        ));
+
+        exchange.process_market_event(market_event_expiry(l1(dec!(119), dec!(121))));
+        assert_eq!(find_balance(&exchange, "btc").total, dec!(0));
+        assert_eq!(
+            find_balance(&exchange, "usdt"),
+            crate::balance::Balance::new(dec!(1099), dec!(1099))
+        );
+        assert!(exchange.positions.is_empty());
+    }
 }
@@ -4,7 +4,10 @@ use crate::{
     error::UnindexedOrderError,
     order::{
         Order,
-        request::{OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseCancel},
+        request::{
+            OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, UnindexedOrderResponseAmend,
+            UnindexedOrderResponseCancel,
+        },
         state::Open,
     },
     trade::Trade,
@@ -101,6 +104,20 @@ impl MockExchangeRequest {
             },
         )
     }
+
+    pub fn amend_order(
+        time_request: DateTime<Utc>,
+        response_tx: oneshot::Sender<UnindexedOrderResponseAmend>,
+        request: OrderRequestAmend<ExchangeId, InstrumentNameExchange>,
+    ) -> Self {
+        Self::new(
+            time_request,
+            MockExchangeRequestKind::AmendOrder {
+                response_tx,
+                request,
+            },
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -128,4 +145,8 @@ pub enum MockExchangeRequestKind {
         >,
         request: OrderRequestOpen<ExchangeId, InstrumentNameExchange>,
     },
+    AmendOrder {
+        response_tx: oneshot::Sender<UnindexedOrderResponseAmend>,
+        request: OrderRequestAmend<ExchangeId, InstrumentNameExchange>,
+    },
 }
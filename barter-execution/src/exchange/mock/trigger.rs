@@ -0,0 +1,132 @@
+use crate::order::OrderKind;
+use barter_instrument::Side;
+use rust_decimal::Decimal;
+
+/// State of a conditional [`OrderKind`] resting untriggered in the
+/// [`MockExchange`](super::MockExchange).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct TriggerState {
+    /// Best [`TriggerSource`](crate::order::TriggerSource) price seen since a `TrailingStop`
+    /// was activated (ie/ the highest price for a sell, and the lowest price for a buy).
+    pub price_best: Option<Decimal>,
+}
+
+impl TriggerState {
+    /// Update the [`TriggerState`] of a conditional order with the provided [`OrderKind`] and
+    /// [`Side`] using the latest trigger source price, returning true if the order is triggered.
+    ///
+    /// `TrailingStop` orders are triggered once the price retraces from the best price seen
+    /// since activation by their offset.
+    pub fn update(&mut self, kind: OrderKind, side: Side, price: Decimal) -> bool {
+        let OrderKind::TrailingStop { offset, .. } = kind else {
+            return kind.is_triggered_by(side, price);
+        };
+
+        let price_best = match (self.price_best, side) {
+            (Some(best), Side::Buy) => best.min(price),
+            (Some(best), Side::Sell) => best.max(price),
+            (None, _) if kind.is_triggered_by(side, price) => price,
+            (None, _) => return false,
+        };
+        self.price_best = Some(price_best);
+
+        let price_stop = offset.price_stop(side, price_best);
+        match side {
+            Side::Buy => price >= price_stop,
+            Side::Sell => price <= price_stop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{OrderTrigger, TrailingOffset, TriggerSource};
+    use rust_decimal_macros::dec;
+
+    fn trigger(price: Decimal) -> OrderTrigger {
+        OrderTrigger::new(price, TriggerSource::Last)
+    }
+
+    #[test]
+    fn test_update_stop_and_take_profit() {
+        struct TestCase {
+            kind: OrderKind,
+            side: Side,
+            price: Decimal,
+            expected: bool,
+        }
+
+        let stop = OrderKind::StopMarket {
+            trigger: trigger(dec!(100)),
+        };
+        let take_profit = OrderKind::TakeProfitLimit {
+            trigger: trigger(dec!(100)),
+        };
+
+        let cases = vec![
+            // TC0: sell stop triggered at or below trigger price
+            TestCase {
+                kind: stop,
+                side: Side::Sell,
+                price: dec!(100),
+                expected: true,
+            },
+            // TC1: sell stop not triggered above trigger price
+            TestCase {
+                kind: stop,
+                side: Side::Sell,
+                price: dec!(101),
+                expected: false,
+            },
+            // TC2: buy stop triggered above trigger price
+            TestCase {
+                kind: stop,
+                side: Side::Buy,
+                price: dec!(101),
+                expected: true,
+            },
+            // TC3: sell take profit triggered above trigger price
+            TestCase {
+                kind: take_profit,
+                side: Side::Sell,
+                price: dec!(101),
+                expected: true,
+            },
+            // TC4: buy take profit not triggered above trigger price
+            TestCase {
+                kind: take_profit,
+                side: Side::Buy,
+                price: dec!(101),
+                expected: false,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = TriggerState::default().update(test.kind, test.side, test.price);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_update_trailing_stop() {
+        let kind = OrderKind::TrailingStop {
+            trigger: trigger(dec!(100)),
+            offset: TrailingOffset::Percent(dec!(0.1)),
+        };
+        let mut state = TriggerState::default();
+
+        // Not activated below the activation price
+        assert!(!state.update(kind, Side::Sell, dec!(95)));
+        assert_eq!(state.price_best, None);
+
+        // Activated, then trails the highest price seen
+        assert!(!state.update(kind, Side::Sell, dec!(100)));
+        assert!(!state.update(kind, Side::Sell, dec!(120)));
+        assert!(!state.update(kind, Side::Sell, dec!(109)));
+        assert_eq!(state.price_best, Some(dec!(120)));
+
+        // Triggered once the price retraces 10% from the highest price seen
+        assert!(state.update(kind, Side::Sell, dec!(108)));
+    }
+}
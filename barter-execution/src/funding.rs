@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use derive_more::Constructor;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Periodic funding payment settled against an open perpetual position.
+///
+/// The `amount` is denominated in the instrument settlement asset, and is positive if funding
+/// was received, or negative if it was paid.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct FundingPayment<InstrumentKey> {
+    pub instrument: InstrumentKey,
+    pub time_exchange: DateTime<Utc>,
+    pub rate: Decimal,
+    pub price_mark: Decimal,
+    /// Signed position quantity the funding was settled against, positive if LONG.
+    pub quantity: Decimal,
+    pub amount: Decimal,
+}
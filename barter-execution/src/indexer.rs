@@ -6,12 +6,15 @@ use crate::{
         ApiError, ClientError, KeyError, OrderError, UnindexedApiError, UnindexedClientError,
         UnindexedOrderError,
     },
+    funding::FundingPayment,
     map::ExecutionInstrumentMap,
     order::{
         Order, OrderEvent, OrderKey, OrderSnapshot, UnindexedOrderKey, UnindexedOrderSnapshot,
-        request::OrderResponseCancel,
+        group::OrderGroup,
+        request::{OrderResponseAmend, OrderResponseCancel, UnindexedOrderResponseAmend},
         state::{InactiveOrderState, OrderState, UnindexedOrderState},
     },
+    position::ExchangePosition,
     trade::Trade,
 };
 use barter_instrument::{
@@ -62,7 +65,17 @@ impl AccountEventIndexer {
             AccountEventKind::OrderCancelled(response) => {
                 AccountEventKind::OrderCancelled(self.order_response_cancel(response)?)
             }
+            AccountEventKind::OrderAmended(response) => {
+                AccountEventKind::OrderAmended(self.order_response_amend(response)?)
+            }
+            AccountEventKind::OrderGroupOpened(opened) => {
+                AccountEventKind::OrderGroupOpened(opened)
+            }
             AccountEventKind::Trade(trade) => AccountEventKind::Trade(self.trade(trade)?),
+            AccountEventKind::Funding(funding) => AccountEventKind::Funding(self.funding(funding)?),
+            AccountEventKind::PositionSnapshot(position) => {
+                AccountEventKind::PositionSnapshot(self.position(position.0).map(Snapshot)?)
+            }
         };
 
         Ok(AccountEvent { exchange, kind })
@@ -88,7 +101,11 @@ impl AccountEventIndexer {
         let instruments = instruments
             .into_iter()
             .map(|snapshot| {
-                let InstrumentAccountSnapshot { instrument, orders } = snapshot;
+                let InstrumentAccountSnapshot {
+                    instrument,
+                    orders,
+                    position,
+                } = snapshot;
 
                 let instrument = self.map.find_instrument_index(&instrument)?;
 
@@ -97,7 +114,15 @@ impl AccountEventIndexer {
                     .map(|order| self.order_snapshot(order))
                     .collect::<Result<Vec<_>, _>>()?;
 
-                Ok(InstrumentAccountSnapshot { instrument, orders })
+                let position = position
+                    .map(|position| self.position(position))
+                    .transpose()?;
+
+                Ok(InstrumentAccountSnapshot {
+                    instrument,
+                    orders,
+                    position,
+                })
             })
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -185,6 +210,34 @@ impl AccountEventIndexer {
         })
     }
 
+    pub fn order_response_amend(
+        &self,
+        response: UnindexedOrderResponseAmend,
+    ) -> Result<OrderResponseAmend, IndexError> {
+        let Order {
+            key,
+            side,
+            price,
+            quantity,
+            kind,
+            time_in_force,
+            state,
+        } = response;
+
+        Ok(Order {
+            key: self.order_key(key)?,
+            side,
+            price,
+            quantity,
+            kind,
+            time_in_force,
+            state: match state {
+                Ok(open) => Ok(open),
+                Err(error) => Err(self.order_error(error)?),
+            },
+        })
+    }
+
     pub fn order_key(&self, key: UnindexedOrderKey) -> Result<OrderKey, IndexError> {
         let UnindexedOrderKey {
             exchange,
@@ -216,6 +269,7 @@ impl AccountEventIndexer {
             UnindexedApiError::OrderRejected(reason) => ApiError::OrderRejected(reason),
             UnindexedApiError::OrderAlreadyCancelled => ApiError::OrderAlreadyCancelled,
             UnindexedApiError::OrderAlreadyFullyFilled => ApiError::OrderAlreadyFullyFilled,
+            UnindexedApiError::OrderReplaceFailed(reason) => ApiError::OrderReplaceFailed(reason),
         })
     }
 
@@ -251,6 +305,22 @@ impl AccountEventIndexer {
         })
     }
 
+    pub fn order_group_request(
+        &self,
+        group: &OrderGroup<ExchangeIndex, InstrumentIndex>,
+    ) -> Result<OrderGroup<ExchangeId, &InstrumentNameExchange>, KeyError> {
+        Ok(OrderGroup {
+            id: group.id.clone(),
+            kind: group.kind,
+            primary: self.order_request(&group.primary)?,
+            contingent: group
+                .contingent
+                .iter()
+                .map(|order| self.order_request(order))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+
     pub fn order_error(&self, error: UnindexedOrderError) -> Result<OrderError, IndexError> {
         Ok(match error {
             UnindexedOrderError::Connectivity(error) => OrderError::Connectivity(error),
@@ -297,4 +367,52 @@ impl AccountEventIndexer {
             fees,
         })
     }
+
+    pub fn funding(
+        &self,
+        funding: FundingPayment<InstrumentNameExchange>,
+    ) -> Result<FundingPayment<InstrumentIndex>, IndexError> {
+        let FundingPayment {
+            instrument,
+            time_exchange,
+            rate,
+            price_mark,
+            quantity,
+            amount,
+        } = funding;
+
+        let instrument_index = self.map.find_instrument_index(&instrument)?;
+
+        Ok(FundingPayment {
+            instrument: instrument_index,
+            time_exchange,
+            rate,
+            price_mark,
+            quantity,
+            amount,
+        })
+    }
+
+    pub fn position(
+        &self,
+        position: ExchangePosition<InstrumentNameExchange>,
+    ) -> Result<ExchangePosition<InstrumentIndex>, IndexError> {
+        let ExchangePosition {
+            instrument,
+            quantity,
+            price_entry,
+            pnl_unrealised,
+            time_exchange,
+        } = position;
+
+        let instrument_index = self.map.find_instrument_index(&instrument)?;
+
+        Ok(ExchangePosition {
+            instrument: instrument_index,
+            quantity,
+            price_entry,
+            pnl_unrealised,
+            time_exchange,
+        })
+    }
 }
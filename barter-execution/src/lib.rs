@@ -23,9 +23,21 @@
 //!
 //! See `README.md` for more information and examples.
 
+// Silence unused dev-dependencies warnings.
+#[cfg(test)]
+use hex as _;
+#[cfg(test)]
+use tokio_tungstenite as _;
+
 use crate::{
     balance::AssetBalance,
-    order::{Order, OrderSnapshot, request::OrderResponseCancel},
+    funding::FundingPayment,
+    order::{
+        Order, OrderSnapshot,
+        group::OrderGroupOpened,
+        request::{OrderResponseAmend, OrderResponseCancel},
+    },
+    position::ExchangePosition,
     trade::Trade,
 };
 use barter_instrument::{
@@ -43,9 +55,12 @@ pub mod balance;
 pub mod client;
 pub mod error;
 pub mod exchange;
+pub mod funding;
 pub mod indexer;
 pub mod map;
 pub mod order;
+pub mod position;
+pub mod slippage;
 pub mod trade;
 
 /// Convenient type alias for an [`AccountEvent`] keyed with [`ExchangeId`],
@@ -96,8 +111,20 @@ pub enum AccountEventKind<ExchangeKey, AssetKey, InstrumentKey> {
     /// Response to an [`OrderRequestCancel<ExchangeKey, InstrumentKey>`](order::request::OrderRequestOpen).
     OrderCancelled(OrderResponseCancel<ExchangeKey, AssetKey, InstrumentKey>),
 
+    /// Response to an [`OrderRequestAmend<ExchangeKey, InstrumentKey>`](order::request::OrderRequestAmend).
+    OrderAmended(OrderResponseAmend<ExchangeKey, AssetKey, InstrumentKey>),
+
+    /// Acknowledgement of an [`OrderGroup`](order::group::OrderGroup) open request.
+    OrderGroupOpened(OrderGroupOpened),
+
     /// [`Order<ExchangeKey, InstrumentKey, Open>`] partial or full-fill.
     Trade(Trade<QuoteAsset, InstrumentKey>),
+
+    /// Perpetual [`FundingPayment`] settled against an open position.
+    Funding(FundingPayment<InstrumentKey>),
+
+    /// Single exchange reported [`ExchangePosition`] snapshot.
+    PositionSnapshot(Snapshot<ExchangePosition<InstrumentKey>>),
 }
 
 impl<ExchangeKey, AssetKey, InstrumentKey> AccountEvent<ExchangeKey, AssetKey, InstrumentKey>
@@ -137,6 +164,9 @@ pub struct InstrumentAccountSnapshot<
     pub instrument: InstrumentKey,
     #[serde(default = "Vec::new")]
     pub orders: Vec<OrderSnapshot<ExchangeKey, AssetKey, InstrumentKey>>,
+    /// Exchange reported derivative position, if the exchange reports positions.
+    #[serde(default = "Option::default")]
+    pub position: Option<ExchangePosition<InstrumentKey>>,
 }
 
 impl<ExchangeKey, AssetKey, InstrumentKey> AccountSnapshot<ExchangeKey, AssetKey, InstrumentKey> {
@@ -0,0 +1,89 @@
+use crate::order::{
+    id::{ClientOrderId, OrderGroupId},
+    request::OrderRequestOpen,
+};
+use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
+use derive_more::{Constructor, Display};
+use serde::{Deserialize, Serialize};
+
+/// Contingency relationship between the orders of an [`OrderGroup`].
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Display,
+)]
+pub enum OrderGroupKind {
+    /// All orders are opened together, and a fill of any order cancels the others.
+    OneCancelsOther,
+    /// The primary order is opened first, and the contingent orders are opened once it is
+    /// fully filled (or ends after partially filling, sized to the filled quantity).
+    OneTriggersOther,
+    /// The primary (entry) order is opened first, and the contingent (exit) orders are opened
+    /// once it is fully filled (or ends after partially filling, sized to the filled quantity),
+    /// after which they behave as a
+    /// [`OneCancelsOther`](OrderGroupKind::OneCancelsOther) group.
+    ///
+    /// eg/ An entry with an associated take-profit and stop-loss.
+    Bracket,
+}
+
+/// Group of [`OrderRequestOpen`]s with a contingency relationship defined by the
+/// [`OrderGroupKind`].
+///
+/// All orders in a group are expected to be for the same exchange.
+///
+/// For a [`OrderGroupKind::OneCancelsOther`] group, the `primary` and `contingent` orders are
+/// siblings that are all opened together.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct OrderGroup<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> {
+    pub id: OrderGroupId,
+    pub kind: OrderGroupKind,
+    pub primary: OrderRequestOpen<ExchangeKey, InstrumentKey>,
+    pub contingent: Vec<OrderRequestOpen<ExchangeKey, InstrumentKey>>,
+}
+
+impl<ExchangeKey, InstrumentKey> OrderGroup<ExchangeKey, InstrumentKey> {
+    /// Exchange the `OrderGroup` is opened on.
+    pub fn exchange(&self) -> &ExchangeKey {
+        &self.primary.key.exchange
+    }
+
+    /// Returns an `Iterator` of every order in the `OrderGroup`, starting with the `primary`.
+    pub fn orders(&self) -> impl Iterator<Item = &OrderRequestOpen<ExchangeKey, InstrumentKey>> {
+        std::iter::once(&self.primary).chain(&self.contingent)
+    }
+
+    /// Returns an `Iterator` of the orders that are opened when the `OrderGroup` is first
+    /// opened.
+    ///
+    /// For a [`OrderGroupKind::OneCancelsOther`] group this is every order, otherwise it is
+    /// only the `primary` order.
+    pub fn orders_initial(
+        &self,
+    ) -> impl Iterator<Item = &OrderRequestOpen<ExchangeKey, InstrumentKey>> {
+        let contingent = match self.kind {
+            OrderGroupKind::OneCancelsOther => self.contingent.as_slice(),
+            OrderGroupKind::OneTriggersOther | OrderGroupKind::Bracket => &[],
+        };
+
+        std::iter::once(&self.primary).chain(contingent)
+    }
+
+    /// Returns true if the order with the provided [`ClientOrderId`] is a member of the
+    /// `OrderGroup`.
+    pub fn contains(&self, cid: &ClientOrderId) -> bool {
+        self.orders().any(|order| &order.key.cid == cid)
+    }
+}
+
+/// Acknowledgement that an [`OrderGroup`] open request was actioned by an execution client.
+///
+/// If the exchange supports the [`OrderGroupKind`] `native`ly, contingent orders are managed by
+/// the exchange, otherwise they must be emulated client-side by the consumer.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct OrderGroupOpened {
+    pub id: OrderGroupId,
+    pub native: bool,
+}
@@ -76,3 +76,14 @@ impl StrategyId {
         Self::new("unknown")
     }
 }
+
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Display, From,
+)]
+pub struct OrderGroupId(pub SmolStr);
+
+impl OrderGroupId {
+    pub fn new<S: AsRef<str>>(id: S) -> Self {
+        Self(SmolStr::new(id))
+    }
+}
@@ -1,6 +1,9 @@
 use crate::order::{
     id::StrategyId,
-    request::{OrderRequestCancel, OrderRequestOpen, RequestCancel, RequestOpen},
+    request::{
+        OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, RequestAmend, RequestCancel,
+        RequestOpen,
+    },
     state::UnindexedOrderState,
 };
 use barter_instrument::{
@@ -23,11 +26,16 @@ pub mod id;
 /// eg/ `OpenInFlight`, `Open`, `Rejected`, `Expired`, etc.
 pub mod state;
 
-/// Order open and cancel request types.
+/// Order open, cancel and amend request types.
 ///
-/// ie/ `OrderRequestOpen` & `OrderRequestCancel`.
+/// ie/ `OrderRequestOpen`, `OrderRequestCancel` & `OrderRequestAmend`.
 pub mod request;
 
+/// Contingent order groups.
+///
+/// eg/ One-Cancels-Other, One-Triggers-Other & Bracket `OrderGroup`s.
+pub mod group;
+
 /// Convenient type alias for an [`Order`] keyed with [`ExchangeId`] and [`InstrumentNameExchange`].
 pub type UnindexedOrder = Order<ExchangeId, InstrumentNameExchange, UnindexedOrderState>;
 
@@ -142,7 +150,10 @@ where
             ActiveOrderState::Open(open) => RequestCancel {
                 id: Some(open.id.clone()),
             },
-            _ => return None,
+            ActiveOrderState::AmendInFlight(amend) => RequestCancel {
+                id: amend.order.as_ref().map(|open| open.id.clone()),
+            },
+            ActiveOrderState::CancelInFlight(_) => return None,
         };
 
         Some(OrderRequestCancel {
@@ -150,14 +161,202 @@ where
             state: request_cancel,
         })
     }
+
+    /// Generate an [`OrderRequestAmend`] to change the price and total quantity of this order.
+    ///
+    /// Returns `None` if the order is not `Open`, or if the total `quantity` does not exceed the
+    /// quantity already filled.
+    pub fn to_request_amend(
+        &self,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Option<OrderRequestAmend<ExchangeKey, InstrumentKey>> {
+        let ActiveOrderState::Open(open) = &self.state else {
+            return None;
+        };
+
+        if quantity <= open.filled_quantity {
+            return None;
+        }
+
+        Some(OrderRequestAmend {
+            key: self.key.clone(),
+            state: RequestAmend {
+                id: Some(open.id.clone()),
+                side: self.side,
+                price,
+                quantity,
+                kind: self.kind,
+                time_in_force: self.time_in_force,
+                filled_quantity: open.filled_quantity,
+            },
+        })
+    }
 }
 
+/// Type of [`Order`].
+///
+/// Conditional kinds (stops, take profits & trailing stops) rest untriggered until their
+/// [`OrderTrigger`] price is reached, at which point they are executed as a `Market` or `Limit`
+/// order. Once triggered, the `Limit` variants use the [`Order`] price as their limit price.
 #[derive(
     Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Display,
 )]
 pub enum OrderKind {
     Market,
     Limit,
+    /// Market order triggered when the price moves against the order (ie/ a buy stop triggers
+    /// at or above, and a sell stop at or below, the trigger price).
+    #[display("StopMarket")]
+    StopMarket {
+        trigger: OrderTrigger,
+    },
+    /// Limit order triggered when the price moves against the order.
+    #[display("StopLimit")]
+    StopLimit {
+        trigger: OrderTrigger,
+    },
+    /// Market order triggered when the price moves in favour of the order (ie/ a buy take
+    /// profit triggers at or below, and a sell take profit at or above, the trigger price).
+    #[display("TakeProfitMarket")]
+    TakeProfitMarket {
+        trigger: OrderTrigger,
+    },
+    /// Limit order triggered when the price moves in favour of the order.
+    #[display("TakeProfitLimit")]
+    TakeProfitLimit {
+        trigger: OrderTrigger,
+    },
+    /// Market order with a stop price that trails the best price seen by the provided
+    /// [`TrailingOffset`].
+    ///
+    /// Trailing starts once the activation `trigger` price is reached (ie/ a sell trailing stop
+    /// activates at or above, and a buy trailing stop at or below, the trigger price).
+    #[display("TrailingStop")]
+    TrailingStop {
+        trigger: OrderTrigger,
+        offset: TrailingOffset,
+    },
+}
+
+impl OrderKind {
+    /// Returns the [`OrderTrigger`] of conditional order kinds.
+    pub fn trigger(&self) -> Option<OrderTrigger> {
+        match self {
+            Self::Market | Self::Limit => None,
+            Self::StopMarket { trigger }
+            | Self::StopLimit { trigger }
+            | Self::TakeProfitMarket { trigger }
+            | Self::TakeProfitLimit { trigger }
+            | Self::TrailingStop { trigger, .. } => Some(*trigger),
+        }
+    }
+
+    /// Returns true if the order kind rests untriggered until its [`OrderTrigger`] is reached.
+    pub fn is_conditional(&self) -> bool {
+        self.trigger().is_some()
+    }
+
+    /// Returns true if the order kind is executed as a market order (once triggered).
+    pub fn is_market(&self) -> bool {
+        matches!(
+            self,
+            Self::Market
+                | Self::StopMarket { .. }
+                | Self::TakeProfitMarket { .. }
+                | Self::TrailingStop { .. }
+        )
+    }
+
+    /// Returns true if a conditional order with the provided [`Side`] is triggered (or for a
+    /// `TrailingStop`, activated) by the provided [`OrderTrigger::source`] price.
+    ///
+    /// Always false for `Market` and `Limit` orders.
+    pub fn is_triggered_by(&self, side: Side, price: Decimal) -> bool {
+        match (self, side) {
+            (Self::Market | Self::Limit, _) => false,
+            (Self::StopMarket { trigger } | Self::StopLimit { trigger }, Side::Buy)
+            | (
+                Self::TakeProfitMarket { trigger }
+                | Self::TakeProfitLimit { trigger }
+                | Self::TrailingStop { trigger, .. },
+                Side::Sell,
+            ) => price >= trigger.price,
+            (Self::StopMarket { trigger } | Self::StopLimit { trigger }, Side::Sell)
+            | (
+                Self::TakeProfitMarket { trigger }
+                | Self::TakeProfitLimit { trigger }
+                | Self::TrailingStop { trigger, .. },
+                Side::Buy,
+            ) => price <= trigger.price,
+        }
+    }
+}
+
+/// Price that triggers a conditional [`OrderKind`], and the [`TriggerSource`] price it is
+/// compared against.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct OrderTrigger {
+    pub price: Decimal,
+    #[serde(default)]
+    pub source: TriggerSource,
+}
+
+/// Market price a conditional [`OrderKind`] [`OrderTrigger`] is compared against.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Default,
+    Deserialize,
+    Serialize,
+    Display,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerSource {
+    /// Last traded price.
+    #[default]
+    Last,
+    /// Derivative mark price.
+    Mark,
+    /// Underlying index price.
+    Index,
+}
+
+/// Distance a `TrailingStop` [`OrderKind`] stop price trails the best price seen.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Display,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingOffset {
+    /// Absolute price distance.
+    Price(Decimal),
+    /// Fractional distance relative to the best price seen (eg/ 0.01 for 1%).
+    Percent(Decimal),
+}
+
+impl TrailingOffset {
+    /// Calculate the stop price for an order with the provided [`Side`], given the best price
+    /// seen since the trailing stop was activated (ie/ the highest price for a sell, and the
+    /// lowest price for a buy).
+    pub fn price_stop(&self, side: Side, price_best: Decimal) -> Decimal {
+        let distance = match self {
+            Self::Price(distance) => *distance,
+            Self::Percent(percent) => price_best * percent,
+        };
+
+        match side {
+            Side::Buy => price_best + distance,
+            Side::Sell => price_best - distance,
+        }
+    }
 }
 
 #[derive(
@@ -170,6 +369,68 @@ pub enum TimeInForce {
     ImmediateOrCancel,
 }
 
+impl TimeInForce {
+    /// Returns true if the `TimeInForce` is post-only, meaning the order must rest in the book
+    /// as a maker, and is rejected if it would immediately match.
+    pub fn is_post_only(&self) -> bool {
+        matches!(self, Self::GoodUntilCancelled { post_only: true })
+    }
+}
+
+/// Execution flags that constrain how an [`Order`] may affect an existing position.
+///
+/// Post-only execution is configured via [`TimeInForce::GoodUntilCancelled`].
+///
+/// Spot instruments hold no positions that can be flipped, so exchanges only honour these
+/// flags for derivative instruments.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Default,
+    Deserialize,
+    Serialize,
+    Constructor,
+)]
+pub struct OrderFlags {
+    /// Order may only reduce an existing position, and is rejected if it would increase or
+    /// flip it.
+    pub reduce_only: bool,
+    /// Order closes the entire existing position when it is executed (eg/ triggered),
+    /// irrespective of its quantity.
+    ///
+    /// Implies `reduce_only`.
+    pub close_position: bool,
+}
+
+impl OrderFlags {
+    /// Construct `OrderFlags` for an order that may only reduce an existing position.
+    pub fn reduce_only() -> Self {
+        Self {
+            reduce_only: true,
+            close_position: false,
+        }
+    }
+
+    /// Construct `OrderFlags` for an order that closes the entire existing position.
+    pub fn close_position() -> Self {
+        Self {
+            reduce_only: true,
+            close_position: true,
+        }
+    }
+
+    /// Returns true if the order may only reduce an existing position.
+    pub fn is_reducing(&self) -> bool {
+        self.reduce_only || self.close_position
+    }
+}
+
 impl<ExchangeKey, InstrumentKey> From<&OrderRequestOpen<ExchangeKey, InstrumentKey>>
     for Order<ExchangeKey, InstrumentKey, ActiveOrderState>
 where
@@ -186,6 +447,7 @@ where
                     quantity,
                     kind,
                     time_in_force,
+                    flags: _,
                 },
         } = value;
 
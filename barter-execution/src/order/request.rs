@@ -1,6 +1,10 @@
 use crate::{
-    error::OrderError,
-    order::{OrderEvent, OrderKind, TimeInForce, id::OrderId, state::Cancelled},
+    error::{ApiError, OrderError},
+    order::{
+        Order, OrderEvent, OrderFlags, OrderKind, TimeInForce,
+        id::OrderId,
+        state::{Cancelled, Open},
+    },
 };
 use barter_instrument::{
     Side,
@@ -18,6 +22,9 @@ pub type OrderRequestOpen<ExchangeKey = ExchangeIndex, InstrumentKey = Instrumen
 pub type OrderRequestCancel<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> =
     OrderEvent<RequestCancel, ExchangeKey, InstrumentKey>;
 
+pub type OrderRequestAmend<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> =
+    OrderEvent<RequestAmend, ExchangeKey, InstrumentKey>;
+
 pub type OrderResponseCancel<
     ExchangeKey = ExchangeIndex,
     AssetKey = AssetIndex,
@@ -27,6 +34,18 @@ pub type OrderResponseCancel<
 pub type UnindexedOrderResponseCancel =
     OrderResponseCancel<ExchangeId, AssetNameExchange, InstrumentNameExchange>;
 
+/// Response to an [`OrderRequestAmend`], containing the amended [`Order`] if successful.
+///
+/// An `Err` indicates the amend was not actioned, and the original order remains unchanged.
+pub type OrderResponseAmend<
+    ExchangeKey = ExchangeIndex,
+    AssetKey = AssetIndex,
+    InstrumentKey = InstrumentIndex,
+> = Order<ExchangeKey, InstrumentKey, Result<Open, OrderError<AssetKey, InstrumentKey>>>;
+
+pub type UnindexedOrderResponseAmend =
+    OrderResponseAmend<ExchangeId, AssetNameExchange, InstrumentNameExchange>;
+
 #[derive(
     Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
 )]
@@ -36,6 +55,8 @@ pub struct RequestOpen {
     pub quantity: Decimal,
     pub kind: OrderKind,
     pub time_in_force: TimeInForce,
+    #[serde(default)]
+    pub flags: OrderFlags,
 }
 
 #[derive(
@@ -44,3 +65,42 @@ pub struct RequestOpen {
 pub struct RequestCancel {
     pub id: Option<OrderId>,
 }
+
+/// Request to amend the price and/or quantity of an open order.
+///
+/// The `quantity` is the new total order quantity (inclusive of the `filled_quantity`), which
+/// enables exchanges without native amend support to cancel-replace the remaining quantity.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct RequestAmend {
+    pub id: Option<OrderId>,
+    pub side: Side,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub kind: OrderKind,
+    pub time_in_force: TimeInForce,
+    pub filled_quantity: Decimal,
+}
+
+impl RequestAmend {
+    /// Quantity of the amended order that remains to be filled.
+    pub fn quantity_remaining(&self) -> Decimal {
+        self.quantity - self.filled_quantity
+    }
+
+    /// Validate the amended total `quantity` exceeds the `filled_quantity`, such that the
+    /// [`Self::quantity_remaining`] is positive.
+    pub fn validate_quantity<AssetKey, InstrumentKey>(
+        &self,
+    ) -> Result<(), ApiError<AssetKey, InstrumentKey>> {
+        if self.quantity > self.filled_quantity {
+            Ok(())
+        } else {
+            Err(ApiError::OrderRejected(format!(
+                "cannot amend quantity to {} with filled quantity: {}",
+                self.quantity, self.filled_quantity
+            )))
+        }
+    }
+}
@@ -49,6 +49,9 @@ impl<AssetKey, InstrumentKey> OrderState<AssetKey, InstrumentKey> {
                 ActiveOrderState::CancelInFlight(state) => {
                     state.order.as_ref().map(|order| order.time_exchange)
                 }
+                ActiveOrderState::AmendInFlight(state) => {
+                    state.order.as_ref().map(|order| order.time_exchange)
+                }
             },
             Self::Inactive(inactive) => match inactive {
                 InactiveOrderState::Cancelled(state) => Some(state.time_exchange),
@@ -63,6 +66,7 @@ pub enum ActiveOrderState {
     OpenInFlight(OpenInFlight),
     Open(Open),
     CancelInFlight(CancelInFlight),
+    AmendInFlight(AmendInFlight),
 }
 
 impl ActiveOrderState {
@@ -71,6 +75,7 @@ impl ActiveOrderState {
             Self::OpenInFlight(_) => None,
             Self::Open(open) => Some(open),
             Self::CancelInFlight(cancel) => cancel.order.as_ref(),
+            Self::AmendInFlight(amend) => amend.order.as_ref(),
         }
     }
 }
@@ -100,6 +105,16 @@ pub struct CancelInFlight {
     pub order: Option<Open>,
 }
 
+/// Amend request sent to the exchange, containing the pre-amend [`Open`] order state (if known).
+///
+/// The tracked order retains its pre-amend price and quantity until the amend is confirmed.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize, Constructor,
+)]
+pub struct AmendInFlight {
+    pub order: Option<Open>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, From)]
 pub enum InactiveOrderState<AssetKey, InstrumentKey> {
     Cancelled(Cancelled),
@@ -0,0 +1,36 @@
+use barter_instrument::Side;
+use chrono::{DateTime, Utc};
+use derive_more::Constructor;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Open derivative position as reported by an exchange.
+///
+/// Positions held on both sides of a hedge mode account are netted into a single position.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct ExchangePosition<InstrumentKey> {
+    pub instrument: InstrumentKey,
+    /// Signed position quantity, positive if LONG, negative if SHORT, and zero if flat.
+    pub quantity: Decimal,
+    pub price_entry: Decimal,
+    pub pnl_unrealised: Decimal,
+    pub time_exchange: DateTime<Utc>,
+}
+
+impl<InstrumentKey> ExchangePosition<InstrumentKey> {
+    /// Position direction, or `None` if the position is flat.
+    pub fn side(&self) -> Option<Side> {
+        match self.quantity.cmp(&Decimal::ZERO) {
+            Ordering::Greater => Some(Side::Buy),
+            Ordering::Less => Some(Side::Sell),
+            Ordering::Equal => None,
+        }
+    }
+
+    pub fn quantity_abs(&self) -> Decimal {
+        self.quantity.abs()
+    }
+}
@@ -0,0 +1,269 @@
+use barter_data::books::OrderBook;
+use barter_instrument::Side;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+
+/// Number of basis points in one whole unit (ie/ 100%).
+const BPS_PER_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+/// Slippage and market impact model used to estimate the average price a taker order is filled
+/// at.
+///
+/// Used by the [`MockExchange`](crate::exchange::mock::MockExchange) when simulating fills, and
+/// by `ClosePositionsStrategy` logic when pricing market orders.
+pub trait SlippageModel {
+    /// Estimate the average fill price of a taker order with the provided [`Side`] and quantity.
+    ///
+    /// The `price` is the reference price the order would be filled at without slippage (eg/ the
+    /// best opposing price), and the `book` is the prevailing [`OrderBook`], if available.
+    fn price_fill(
+        &self,
+        side: Side,
+        quantity: Decimal,
+        price: Decimal,
+        book: Option<&OrderBook>,
+    ) -> Decimal;
+}
+
+/// Configuration used to select a [`SlippageModel`].
+///
+/// Defaults to [`SlippageConfig::None`], filling every order at the reference price.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlippageConfig {
+    /// See [`NoSlippage`].
+    #[default]
+    None,
+    /// See [`FixedBpsSlippage`].
+    FixedBps(FixedBpsSlippage),
+    /// See [`SqrtImpactSlippage`].
+    SqrtImpact(SqrtImpactSlippage),
+    /// See [`WalkBookSlippage`].
+    WalkBook(WalkBookSlippage),
+}
+
+impl SlippageModel for SlippageConfig {
+    fn price_fill(
+        &self,
+        side: Side,
+        quantity: Decimal,
+        price: Decimal,
+        book: Option<&OrderBook>,
+    ) -> Decimal {
+        match self {
+            SlippageConfig::None => NoSlippage.price_fill(side, quantity, price, book),
+            SlippageConfig::FixedBps(model) => model.price_fill(side, quantity, price, book),
+            SlippageConfig::SqrtImpact(model) => model.price_fill(side, quantity, price, book),
+            SlippageConfig::WalkBook(model) => model.price_fill(side, quantity, price, book),
+        }
+    }
+}
+
+/// [`SlippageModel`] that fills every order at the reference price.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub struct NoSlippage;
+
+impl SlippageModel for NoSlippage {
+    fn price_fill(&self, _: Side, _: Decimal, price: Decimal, _: Option<&OrderBook>) -> Decimal {
+        price
+    }
+}
+
+/// [`SlippageModel`] that fills every order a fixed number of basis points through the
+/// reference price, regardless of size.
+///
+/// eg/ `bps: 5` fills a buy referencing 100.0 at 100.05.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub struct FixedBpsSlippage {
+    pub bps: Decimal,
+}
+
+impl SlippageModel for FixedBpsSlippage {
+    fn price_fill(&self, side: Side, _: Decimal, price: Decimal, _: Option<&OrderBook>) -> Decimal {
+        apply_impact(side, price, self.bps / BPS_PER_UNIT)
+    }
+}
+
+/// Volume proportional [`SlippageModel`] following the square-root market impact law.
+///
+/// The fractional price impact is `coefficient * sqrt(quantity / volume)`, where `volume` is
+/// the reference volume traded over the period of interest (eg/ average daily volume), and the
+/// `coefficient` scales the impact (typically the instrument volatility multiplied by an
+/// empirical constant close to one).
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub struct SqrtImpactSlippage {
+    pub coefficient: Decimal,
+    pub volume: Decimal,
+}
+
+impl SlippageModel for SqrtImpactSlippage {
+    fn price_fill(
+        &self,
+        side: Side,
+        quantity: Decimal,
+        price: Decimal,
+        _: Option<&OrderBook>,
+    ) -> Decimal {
+        if self.volume <= Decimal::ZERO {
+            return price;
+        }
+
+        let impact = (quantity.abs() / self.volume)
+            .sqrt()
+            .map(|participation| self.coefficient * participation)
+            .unwrap_or_default();
+
+        apply_impact(side, price, impact)
+    }
+}
+
+/// [`SlippageModel`] that walks the opposing side of the [`OrderBook`], filling at the volume
+/// weighted average price of the levels consumed.
+///
+/// Any quantity exceeding the displayed liquidity is filled at the worst level consumed. If no
+/// opposing levels are available, orders are filled at the reference price.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub struct WalkBookSlippage;
+
+impl SlippageModel for WalkBookSlippage {
+    fn price_fill(
+        &self,
+        side: Side,
+        quantity: Decimal,
+        price: Decimal,
+        book: Option<&OrderBook>,
+    ) -> Decimal {
+        let Some(book) = book else {
+            return price;
+        };
+
+        let levels = match side {
+            Side::Buy => book.asks().levels(),
+            Side::Sell => book.bids().levels(),
+        };
+
+        let quantity = quantity.abs();
+        let mut remaining = quantity;
+        let mut value = Decimal::ZERO;
+        let mut price_worst = None;
+
+        for level in levels {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+
+            let fill = remaining.min(level.amount);
+            value += fill * level.price;
+            remaining -= fill;
+            price_worst = Some(level.price);
+        }
+
+        let Some(price_worst) = price_worst else {
+            return price;
+        };
+
+        if quantity.is_zero() {
+            return price_worst;
+        }
+
+        (value + remaining * price_worst) / quantity
+    }
+}
+
+/// Move the provided price against the taker by the fractional impact.
+fn apply_impact(side: Side, price: Decimal, impact: Decimal) -> Decimal {
+    match side {
+        Side::Buy => price * (Decimal::ONE + impact),
+        Side::Sell => price * (Decimal::ONE - impact),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn book() -> OrderBook {
+        OrderBook::new(
+            0,
+            None,
+            [(dec!(99), dec!(1)), (dec!(98), dec!(2))],
+            [(dec!(101), dec!(1)), (dec!(102), dec!(2))],
+        )
+    }
+
+    #[test]
+    fn test_fixed_bps_slippage() {
+        let model = FixedBpsSlippage { bps: dec!(50) };
+        assert_eq!(
+            model.price_fill(Side::Buy, dec!(1), dec!(100), None),
+            dec!(100.5)
+        );
+        assert_eq!(
+            model.price_fill(Side::Sell, dec!(1), dec!(100), None),
+            dec!(99.5)
+        );
+    }
+
+    #[test]
+    fn test_sqrt_impact_slippage() {
+        let model = SqrtImpactSlippage {
+            coefficient: dec!(0.1),
+            volume: dec!(100),
+        };
+
+        // 0.1 * sqrt(1 / 100) = 1%
+        assert_eq!(
+            model.price_fill(Side::Buy, dec!(1), dec!(100), None),
+            dec!(101)
+        );
+
+        // 0.1 * sqrt(4 / 100) = 2%
+        assert_eq!(
+            model.price_fill(Side::Sell, dec!(4), dec!(100), None),
+            dec!(98)
+        );
+    }
+
+    #[test]
+    fn test_walk_book_slippage() {
+        let book = book();
+        let model = WalkBookSlippage;
+
+        // Within best level
+        assert_eq!(
+            model.price_fill(Side::Buy, dec!(1), dec!(101), Some(&book)),
+            dec!(101)
+        );
+
+        // (1 * 101 + 2 * 102) / 3
+        assert_eq!(
+            model
+                .price_fill(Side::Buy, dec!(3), dec!(101), Some(&book))
+                .round_dp(8),
+            dec!(101.66666667)
+        );
+
+        // Exceeds displayed liquidity: (1 * 99 + 2 * 98 + 1 * 98) / 4
+        assert_eq!(
+            model.price_fill(Side::Sell, dec!(4), dec!(99), Some(&book)),
+            dec!(98.25)
+        );
+
+        // No book falls back to the reference price
+        assert_eq!(
+            model.price_fill(Side::Sell, dec!(4), dec!(99), None),
+            dec!(99)
+        );
+    }
+}
@@ -28,6 +28,10 @@ pub struct Trade<AssetKey, InstrumentKey> {
     pub side: Side,
     pub price: Decimal,
     pub quantity: Decimal,
+    /// Fees charged for the [`Trade`], where negative fees are rebates.
+    ///
+    /// Fees charged in an asset other than the `AssetKey` (eg/ the base asset, or a third asset
+    /// such as BNB) are converted to their `AssetKey` equivalent.
     pub fees: AssetFees<AssetKey>,
 }
 
@@ -0,0 +1,618 @@
+use barter_execution::{
+    AccountEventKind,
+    balance::Balance,
+    client::{
+        ExecutionClient,
+        binance::futures::{BinanceFuturesUsd, BinanceFuturesUsdConfig, BinancePositionMode},
+    },
+    order::{
+        OrderFlags, OrderKey, OrderKind, OrderTrigger, TimeInForce, TrailingOffset, TriggerSource,
+        id::{ClientOrderId, OrderId, StrategyId},
+        request::{OrderRequestOpen, RequestOpen},
+        state::{Open, OrderState},
+    },
+    position::ExchangePosition,
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use rust_decimal_macros::dec;
+use sha2::Sha256;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+const API_KEY: &str = "test-api-key";
+const SECRET: &str = "test-secret";
+const LISTEN_KEY: &str = "test-listen-key";
+
+#[derive(Debug, Clone)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    query: String,
+}
+
+/// Minimal Binance USD-M Http server fixture that validates request signatures and responds with
+/// canned payloads.
+///
+/// The account holds a hedge mode BTCUSDT position of 0.3 LONG and 0.1 SHORT.
+#[derive(Debug, Clone, Default)]
+struct MockBinanceHttp {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockBinanceHttp {
+    async fn start() -> (Self, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = Self::default();
+
+        let handler = server.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handler.clone().handle(stream));
+            }
+        });
+
+        (server, base_url)
+    }
+
+    fn requests(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|request| request.method == method && request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    async fn handle(self, mut stream: TcpStream) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        }
+
+        let head = String::from_utf8_lossy(&buffer).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap().split(' ');
+        let method = request_line.next().unwrap().to_string();
+        let target = request_line.next().unwrap();
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), query.to_string()),
+            None => (target.to_string(), String::new()),
+        };
+        let api_key = lines
+            .filter_map(|line| line.split_once(": "))
+            .find(|(name, _)| name.eq_ignore_ascii_case("x-mbx-apikey"))
+            .map(|(_, value)| value.to_string());
+
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            query: query.clone(),
+        });
+
+        let (status, body) = if api_key.as_deref() != Some(API_KEY) {
+            (
+                401,
+                r#"{"code":-2015,"msg":"Invalid API-key."}"#.to_string(),
+            )
+        } else if path == "/fapi/v1/listenKey" {
+            (200, format!(r#"{{"listenKey":"{LISTEN_KEY}"}}"#))
+        } else if !signature_valid(&query) {
+            (
+                400,
+                r#"{"code":-1022,"msg":"Signature is not valid."}"#.to_string(),
+            )
+        } else {
+            route_signed(&method, &path, &query)
+        };
+
+        let response = format!(
+            "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.ok();
+    }
+}
+
+fn signature_valid(query: &str) -> bool {
+    let Some((payload, signature)) = query.rsplit_once("&signature=") else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+    mac.update(payload.as_bytes());
+
+    hex::encode(mac.finalize().into_bytes()) == signature && payload.contains("timestamp=")
+}
+
+fn route_signed(method: &str, path: &str, query: &str) -> (u16, String) {
+    let body = match (method, path) {
+        ("GET", "/fapi/v1/positionSide/dual") => r#"{"dualSidePosition":false}"#,
+        ("POST", "/fapi/v1/positionSide/dual") => r#"{"code":200,"msg":"success"}"#,
+        ("POST", "/fapi/v1/leverage") => {
+            return (
+                200,
+                format!(
+                    r#"{{"symbol":"BTCUSDT","leverage":{},"maxNotionalValue":"1000000"}}"#,
+                    query
+                        .split('&')
+                        .find_map(|param| param.strip_prefix("leverage="))
+                        .unwrap()
+                ),
+            );
+        }
+        ("GET", "/fapi/v2/account") => {
+            r#"{
+                "totalWalletBalance": "10000.00",
+                "availableBalance": "8000.00",
+                "assets": [
+                    {
+                        "asset": "USDT",
+                        "walletBalance": "10000.00",
+                        "unrealizedProfit": "30.00",
+                        "marginBalance": "10030.00",
+                        "availableBalance": "8000.00",
+                        "updateTime": 1700000000000
+                    },
+                    {
+                        "asset": "BNB",
+                        "walletBalance": "1.00",
+                        "unrealizedProfit": "0.00",
+                        "marginBalance": "1.00",
+                        "availableBalance": "1.00",
+                        "updateTime": 1600000000000
+                    }
+                ],
+                "positions": [
+                    {
+                        "symbol": "BTCUSDT",
+                        "initialMargin": "900",
+                        "unrealizedProfit": "40.00",
+                        "leverage": "10",
+                        "isolated": false,
+                        "entryPrice": "30000.0",
+                        "positionSide": "LONG",
+                        "positionAmt": "0.3",
+                        "updateTime": 1700000001000
+                    },
+                    {
+                        "symbol": "BTCUSDT",
+                        "initialMargin": "310",
+                        "unrealizedProfit": "-10.00",
+                        "leverage": "10",
+                        "isolated": false,
+                        "entryPrice": "31000.0",
+                        "positionSide": "SHORT",
+                        "positionAmt": "-0.1",
+                        "updateTime": 1700000001000
+                    },
+                    {
+                        "symbol": "ETHUSDT",
+                        "initialMargin": "0",
+                        "unrealizedProfit": "0.00",
+                        "leverage": "20",
+                        "isolated": false,
+                        "entryPrice": "0.0",
+                        "positionSide": "LONG",
+                        "positionAmt": "0",
+                        "updateTime": 0
+                    }
+                ]
+            }"#
+        }
+        ("GET", "/fapi/v1/openOrders") => {
+            r#"[{
+                "avgPrice": "0.00000",
+                "clientOrderId": "cid-resting",
+                "executedQty": "0",
+                "orderId": 21,
+                "origQty": "0.2",
+                "price": "29000",
+                "reduceOnly": false,
+                "side": "BUY",
+                "positionSide": "LONG",
+                "status": "NEW",
+                "symbol": "BTCUSDT",
+                "time": 1700000000000,
+                "timeInForce": "GTX",
+                "type": "LIMIT",
+                "updateTime": 1700000000500
+            }]"#
+        }
+        ("POST", "/fapi/v1/order") => {
+            r#"{
+                "clientOrderId": "cid-open",
+                "executedQty": "0",
+                "orderId": 22,
+                "origQty": "0.1",
+                "price": "0",
+                "side": "SELL",
+                "status": "FILLED",
+                "symbol": "BTCUSDT",
+                "timeInForce": "GTC",
+                "type": "MARKET",
+                "updateTime": 1700000002000
+            }"#
+        }
+        _ => return (404, r#"{"code":-1,"msg":"not found"}"#.to_string()),
+    };
+
+    (200, body.to_string())
+}
+
+/// Binance USD-M user data stream WebSocket server fixture.
+///
+/// Sends an order fill, the resulting account update and a margin call once connected, and
+/// `listenKeyExpired` when signalled.
+async fn start_user_data_stream() -> (String, oneshot::Sender<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("ws://{}", listener.local_addr().unwrap());
+    let (expire_tx, expire_rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut websocket = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let events = [
+            r#"{
+                "e": "ORDER_TRADE_UPDATE", "E": 1700000005001, "T": 1700000005000,
+                "o": {
+                    "s": "BTCUSDT", "c": "cid-close", "S": "BUY", "o": "MARKET", "f": "GTC",
+                    "q": "0.1", "p": "0", "ap": "30500", "sp": "0", "x": "TRADE",
+                    "X": "FILLED", "i": 23, "l": "0.1", "z": "0.1", "L": "30500",
+                    "N": "USDT", "n": "1.22", "T": 1700000005000, "t": 301, "b": "0",
+                    "a": "0", "m": false, "R": true, "wt": "CONTRACT_PRICE",
+                    "ot": "MARKET", "ps": "SHORT", "cp": false, "rp": "50.0"
+                }
+            }"#,
+            r#"{
+                "e": "ACCOUNT_UPDATE", "E": 1700000005002, "T": 1700000005000,
+                "a": {
+                    "m": "ORDER",
+                    "B": [{ "a": "USDT", "wb": "10048.78", "cw": "10048.78", "bc": "0" }],
+                    "P": [{
+                        "s": "BTCUSDT", "pa": "0", "ep": "0.0", "bep": "0", "cr": "50",
+                        "up": "0", "mt": "cross", "iw": "0", "ps": "SHORT"
+                    }]
+                }
+            }"#,
+            r#"{
+                "e": "MARGIN_CALL", "E": 1700000005003, "cw": "3.16812045",
+                "p": [{
+                    "s": "ETHUSDT", "ps": "LONG", "pa": "1.327", "mt": "CROSSED",
+                    "iw": "0", "mp": "187.17127", "up": "-1.166074", "mm": "1.614445"
+                }]
+            }"#,
+        ];
+
+        for event in events {
+            websocket.send(Message::text(event)).await.unwrap();
+        }
+
+        expire_rx.await.unwrap();
+        websocket
+            .send(Message::text(
+                r#"{"e": "listenKeyExpired", "E": 1700000010000, "listenKey": "test-listen-key"}"#,
+            ))
+            .await
+            .unwrap();
+
+        // Keep the connection open, so the stream can only end due to listenKeyExpired
+        while websocket.next().await.is_some() {}
+    });
+
+    (base_url, expire_tx)
+}
+
+fn client(
+    base_url_rest: String,
+    base_url_ws: String,
+    position_mode: BinancePositionMode,
+) -> BinanceFuturesUsd {
+    BinanceFuturesUsd::new(BinanceFuturesUsdConfig {
+        base_url_rest,
+        base_url_ws,
+        position_mode,
+        leverage: BTreeMap::from([(btc_usdt(), 10)]),
+        ..BinanceFuturesUsdConfig::new(API_KEY, SECRET)
+    })
+}
+
+fn btc_usdt() -> InstrumentNameExchange {
+    InstrumentNameExchange::new("BTCUSDT")
+}
+
+fn time(epoch_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(epoch_ms).unwrap()
+}
+
+#[tokio::test]
+async fn test_binance_futures_usd_account_snapshot() {
+    let (server, base_url) = MockBinanceHttp::start().await;
+    let client = client(base_url, String::new(), BinancePositionMode::Hedge);
+    let eth_usdt = InstrumentNameExchange::new("ETHUSDT");
+
+    let snapshot = client
+        .account_snapshot(
+            &[AssetNameExchange::new("USDT")],
+            &[btc_usdt(), eth_usdt.clone()],
+        )
+        .await
+        .unwrap();
+
+    // Position mode and leverage are configured before the first snapshot
+    assert_eq!(
+        server.requests("GET", "/fapi/v1/positionSide/dual").len(),
+        1
+    );
+    let position_mode = server.requests("POST", "/fapi/v1/positionSide/dual");
+    assert!(position_mode[0].query.contains("dualSidePosition=true"));
+    let leverage = server.requests("POST", "/fapi/v1/leverage");
+    assert!(leverage[0].query.contains("symbol=BTCUSDT&leverage=10"));
+
+    assert_eq!(snapshot.exchange, ExchangeId::BinanceFuturesUsd);
+    assert_eq!(snapshot.balances.len(), 1);
+    assert_eq!(
+        snapshot.balances[0].balance,
+        Balance::new(dec!(10000.00), dec!(8000.00))
+    );
+
+    // Hedge mode LONG & SHORT legs are netted, with flat positions reported for the rest
+    let btc = &snapshot.instruments[0];
+    assert_eq!(
+        btc.position,
+        Some(ExchangePosition::new(
+            btc_usdt(),
+            dec!(0.2),
+            dec!(30000.0),
+            dec!(30.00),
+            time(1700000001000)
+        ))
+    );
+    assert_eq!(btc.position.as_ref().unwrap().side(), Some(Side::Buy));
+    assert_eq!(
+        snapshot.instruments[1].position,
+        Some(ExchangePosition::new(
+            eth_usdt,
+            dec!(0),
+            dec!(0),
+            dec!(0),
+            time(1700000001000)
+        ))
+    );
+
+    // GTX orders are post only
+    let order = &btc.orders[0];
+    assert_eq!(order.key.cid, ClientOrderId::new("cid-resting"));
+    assert_eq!(
+        order.time_in_force,
+        TimeInForce::GoodUntilCancelled { post_only: true }
+    );
+    assert_eq!(
+        order.state,
+        OrderState::active(Open::new(OrderId::new("21"), time(1700000000500), dec!(0)))
+    );
+
+    // Account is only configured once
+    client.account_snapshot(&[], &[btc_usdt()]).await.unwrap();
+    assert_eq!(
+        server.requests("GET", "/fapi/v1/positionSide/dual").len(),
+        1
+    );
+    assert_eq!(server.requests("POST", "/fapi/v1/leverage").len(), 1);
+}
+
+#[tokio::test]
+async fn test_binance_futures_usd_open_order_reduce_only() {
+    let (server, base_url) = MockBinanceHttp::start().await;
+    let instrument = btc_usdt();
+
+    let request_open = |cid: &str| OrderRequestOpen {
+        key: OrderKey::new(
+            ExchangeId::BinanceFuturesUsd,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        ),
+        state: RequestOpen::new(
+            Side::Sell,
+            dec!(0),
+            dec!(0.1),
+            OrderKind::Market,
+            TimeInForce::ImmediateOrCancel,
+            OrderFlags::default(),
+        ),
+    };
+
+    // One-way mode uses the reduceOnly parameter
+    let one_way = client(base_url.clone(), String::new(), BinancePositionMode::OneWay);
+    let opened = one_way.open_order_reduce_only(request_open("cid-a")).await;
+    assert_eq!(
+        opened.state,
+        Ok(Open::new(OrderId::new("22"), time(1700000002000), dec!(0)))
+    );
+    one_way.open_order(request_open("cid-b")).await;
+
+    // Hedge mode routes orders to the position side they open or reduce
+    let hedge = client(base_url, String::new(), BinancePositionMode::Hedge);
+    hedge.open_order_reduce_only(request_open("cid-c")).await;
+    hedge.open_order(request_open("cid-d")).await;
+
+    let queries = server
+        .requests("POST", "/fapi/v1/order")
+        .into_iter()
+        .map(|request| request.query)
+        .collect::<Vec<_>>();
+    assert_eq!(queries.len(), 4);
+
+    assert!(queries[0].contains("symbol=BTCUSDT&side=SELL&type=MARKET&quantity=0.1"));
+    assert!(queries[0].contains("reduceOnly=true&newClientOrderId=cid-a&newOrderRespType=RESULT"));
+    assert!(!queries[0].contains("positionSide"));
+
+    assert!(!queries[1].contains("reduceOnly"));
+    assert!(!queries[1].contains("positionSide"));
+
+    assert!(queries[2].contains("side=SELL&positionSide=LONG"));
+    assert!(!queries[2].contains("reduceOnly"));
+
+    assert!(queries[3].contains("side=SELL&positionSide=SHORT"));
+    assert!(!queries[3].contains("reduceOnly"));
+}
+
+#[tokio::test]
+async fn test_binance_futures_usd_open_order_conditional() {
+    let (server, base_url) = MockBinanceHttp::start().await;
+    let instrument = btc_usdt();
+    let client = client(base_url, String::new(), BinancePositionMode::OneWay);
+
+    let request_open = |cid: &str, kind: OrderKind| OrderRequestOpen {
+        key: OrderKey::new(
+            ExchangeId::BinanceFuturesUsd,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        ),
+        state: RequestOpen::new(
+            Side::Sell,
+            dec!(39000),
+            dec!(0.1),
+            kind,
+            TimeInForce::GoodUntilCancelled { post_only: false },
+            OrderFlags::default(),
+        ),
+    };
+
+    let stop_market = OrderKind::StopMarket {
+        trigger: OrderTrigger::new(dec!(40000), TriggerSource::Mark),
+    };
+    let take_profit_limit = OrderKind::TakeProfitLimit {
+        trigger: OrderTrigger::new(dec!(45000), TriggerSource::Last),
+    };
+    let trailing_stop = OrderKind::TrailingStop {
+        trigger: OrderTrigger::new(dec!(44000), TriggerSource::Last),
+        offset: TrailingOffset::Percent(dec!(0.01)),
+    };
+    let stop_index = OrderKind::StopMarket {
+        trigger: OrderTrigger::new(dec!(40000), TriggerSource::Index),
+    };
+
+    client.open_order(request_open("cid-a", stop_market)).await;
+    client
+        .open_order(request_open("cid-b", take_profit_limit))
+        .await;
+    client
+        .open_order(request_open("cid-c", trailing_stop))
+        .await;
+
+    // Close position stop orders close the entire position, so are sent without a quantity
+    let mut close_position = request_open("cid-e", stop_market);
+    close_position.state.flags = OrderFlags::close_position();
+    client.open_order(close_position).await;
+
+    // Binance USD-M does not support index price triggers
+    let opened = client.open_order(request_open("cid-d", stop_index)).await;
+    assert!(opened.state.is_err());
+
+    let queries = server
+        .requests("POST", "/fapi/v1/order")
+        .into_iter()
+        .map(|request| request.query)
+        .collect::<Vec<_>>();
+    assert_eq!(queries.len(), 4);
+
+    assert!(
+        queries[0].contains("type=STOP_MARKET&quantity=0.1&stopPrice=40000&workingType=MARK_PRICE")
+    );
+    assert!(!queries[0].contains("price=39000"));
+
+    assert!(queries[1].contains(
+        "type=TAKE_PROFIT&timeInForce=GTC&quantity=0.1&price=39000&stopPrice=45000&workingType=CONTRACT_PRICE"
+    ));
+
+    assert!(queries[2].contains(
+        "type=TRAILING_STOP_MARKET&quantity=0.1&workingType=CONTRACT_PRICE&activationPrice=44000&callbackRate=1"
+    ));
+
+    assert!(
+        queries[3]
+            .contains("type=STOP_MARKET&stopPrice=40000&workingType=MARK_PRICE&closePosition=true")
+    );
+    assert!(!queries[3].contains("quantity"));
+    assert!(!queries[3].contains("reduceOnly"));
+}
+
+#[tokio::test]
+async fn test_binance_futures_usd_account_stream() {
+    let (_server, base_url_rest) = MockBinanceHttp::start().await;
+    let (base_url_ws, expire_tx) = start_user_data_stream().await;
+    let client = client(base_url_rest, base_url_ws, BinancePositionMode::Hedge);
+
+    let mut stream = client.account_stream(&[], &[btc_usdt()]).await.unwrap();
+
+    // ORDER_TRADE_UPDATE TRADE generates a Trade followed by an Order snapshot
+    let AccountEventKind::Trade(trade) = stream.next().await.unwrap().kind else {
+        panic!("expected Trade");
+    };
+    assert_eq!(trade.order_id, OrderId::new("23"));
+    assert_eq!(trade.side, Side::Buy);
+    assert_eq!(trade.price, dec!(30500));
+    assert_eq!(trade.fees.fees, dec!(1.22));
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cid-close"));
+    assert_eq!(order.0.state, OrderState::fully_filled());
+
+    // ACCOUNT_UPDATE generates balance snapshots, followed by netted position snapshots
+    let AccountEventKind::BalanceSnapshot(balance) = stream.next().await.unwrap().kind else {
+        panic!("expected BalanceSnapshot");
+    };
+    assert_eq!(balance.0.asset, AssetNameExchange::new("USDT"));
+    assert_eq!(
+        balance.0.balance,
+        Balance::new(dec!(10048.78), dec!(10048.78))
+    );
+    assert_eq!(balance.0.time_exchange, time(1700000005000));
+
+    // Closed SHORT leg is netted with the LONG leg seeded from the account information
+    let AccountEventKind::PositionSnapshot(position) = stream.next().await.unwrap().kind else {
+        panic!("expected PositionSnapshot");
+    };
+    assert_eq!(
+        position.0,
+        ExchangePosition::new(
+            btc_usdt(),
+            dec!(0.3),
+            dec!(30000.0),
+            dec!(40.00),
+            time(1700000005000)
+        )
+    );
+
+    // MARGIN_CALL is logged, and the stream ends once the listenKey expires
+    expire_tx.send(()).unwrap();
+    assert!(stream.next().await.is_none());
+}
@@ -0,0 +1,755 @@
+use barter_execution::{
+    AccountEventKind, UnindexedAccountEvent,
+    balance::Balance,
+    client::{
+        ExecutionClient,
+        binance::spot::{BinanceSpot, BinanceSpotConfig},
+    },
+    error::{ApiError, OrderError, UnindexedClientError},
+    order::{
+        OrderFlags, OrderKey, OrderKind, OrderTrigger, TimeInForce, TriggerSource,
+        group::{OrderGroup, OrderGroupKind},
+        id::{ClientOrderId, OrderGroupId, OrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, RequestCancel, RequestOpen},
+        state::{ActiveOrderState, Open, OrderState},
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use rust_decimal_macros::dec;
+use sha2::Sha256;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+};
+use tokio_tungstenite::tungstenite::{
+    Message,
+    handshake::server::{Request, Response},
+};
+
+const API_KEY: &str = "test-api-key";
+const SECRET: &str = "test-secret";
+const LISTEN_KEY: &str = "test-listen-key";
+
+#[derive(Debug, Clone)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    query: String,
+}
+
+/// Minimal Binance Spot Http server fixture that validates request signatures and responds with
+/// canned payloads.
+#[derive(Debug, Clone, Default)]
+struct MockBinanceHttp {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockBinanceHttp {
+    async fn start() -> (Self, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = Self::default();
+
+        let handler = server.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handler.clone().handle(stream));
+            }
+        });
+
+        (server, base_url)
+    }
+
+    fn requests(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|request| request.method == method && request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    async fn handle(self, mut stream: TcpStream) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        }
+
+        let head = String::from_utf8_lossy(&buffer).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap().split(' ');
+        let method = request_line.next().unwrap().to_string();
+        let target = request_line.next().unwrap();
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), query.to_string()),
+            None => (target.to_string(), String::new()),
+        };
+        let api_key = lines
+            .filter_map(|line| line.split_once(": "))
+            .find(|(name, _)| name.eq_ignore_ascii_case("x-mbx-apikey"))
+            .map(|(_, value)| value.to_string());
+
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            query: query.clone(),
+        });
+
+        let (status, body) = if api_key.as_deref() != Some(API_KEY) {
+            (
+                401,
+                r#"{"code":-2015,"msg":"Invalid API-key."}"#.to_string(),
+            )
+        } else if path.starts_with("/api/v3/userDataStream") {
+            route_user_data_stream(&method)
+        } else if !signature_valid(&query) {
+            (
+                400,
+                r#"{"code":-1022,"msg":"Signature is not valid."}"#.to_string(),
+            )
+        } else {
+            route_signed(&method, &path, &query)
+        };
+
+        let response = format!(
+            "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.ok();
+    }
+}
+
+fn signature_valid(query: &str) -> bool {
+    let Some((payload, signature)) = query.rsplit_once("&signature=") else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+    mac.update(payload.as_bytes());
+
+    hex::encode(mac.finalize().into_bytes()) == signature
+        && payload.contains("timestamp=")
+        && payload.contains("recvWindow=5000")
+}
+
+fn route_user_data_stream(method: &str) -> (u16, String) {
+    match method {
+        "POST" => (200, format!(r#"{{"listenKey":"{LISTEN_KEY}"}}"#)),
+        "PUT" => (200, "{}".to_string()),
+        _ => (404, r#"{"code":-1,"msg":"not found"}"#.to_string()),
+    }
+}
+
+fn route_signed(method: &str, path: &str, query: &str) -> (u16, String) {
+    let body = match (method, path) {
+        ("GET", "/api/v3/account") => {
+            r#"{
+                "makerCommission": 10,
+                "canTrade": true,
+                "updateTime": 1700000000000,
+                "accountType": "SPOT",
+                "balances": [
+                    { "asset": "BTC", "free": "1.5", "locked": "0.5" },
+                    { "asset": "USDT", "free": "10000", "locked": "0" },
+                    { "asset": "BNB", "free": "3", "locked": "0" }
+                ]
+            }"#
+        }
+        ("GET", "/api/v3/openOrders") => {
+            r#"[{
+                "symbol": "BTCUSDT",
+                "orderId": 11,
+                "orderListId": -1,
+                "clientOrderId": "cid-resting",
+                "price": "30000.00",
+                "origQty": "0.50",
+                "executedQty": "0.10",
+                "cummulativeQuoteQty": "3000.00",
+                "status": "PARTIALLY_FILLED",
+                "timeInForce": "GTC",
+                "type": "LIMIT",
+                "side": "BUY",
+                "time": 1700000000000,
+                "updateTime": 1700000001000,
+                "isWorking": true
+            }]"#
+        }
+        ("POST", "/api/v3/order") if query.contains("newClientOrderId=cid-poor") => {
+            return (
+                400,
+                r#"{"code":-2010,"msg":"Account has insufficient balance for requested action."}"#
+                    .to_string(),
+            );
+        }
+        ("POST", "/api/v3/order") => {
+            r#"{
+                "symbol": "BTCUSDT",
+                "orderId": 12,
+                "orderListId": -1,
+                "clientOrderId": "cid-open",
+                "transactTime": 1700000002000,
+                "price": "29000.00",
+                "origQty": "0.25",
+                "executedQty": "0.00",
+                "cummulativeQuoteQty": "0.00",
+                "status": "NEW",
+                "timeInForce": "GTC",
+                "type": "LIMIT_MAKER",
+                "side": "BUY"
+            }"#
+        }
+        ("POST", "/api/v3/orderList/oco") => {
+            r#"{
+                "orderListId": 1,
+                "contingencyType": "OCO",
+                "listStatusType": "EXEC_STARTED",
+                "listOrderStatus": "EXECUTING",
+                "listClientOrderId": "group-oco",
+                "transactionTime": 1700000002000,
+                "symbol": "BTCUSDT",
+                "orders": [
+                    {"symbol": "BTCUSDT", "orderId": 21, "clientOrderId": "cid-stop"},
+                    {"symbol": "BTCUSDT", "orderId": 22, "clientOrderId": "cid-target"}
+                ],
+                "orderReports": [
+                    {
+                        "symbol": "BTCUSDT",
+                        "orderId": 21,
+                        "orderListId": 1,
+                        "clientOrderId": "cid-stop",
+                        "transactTime": 1700000002000,
+                        "price": "0.00",
+                        "origQty": "0.25",
+                        "executedQty": "0.00",
+                        "cummulativeQuoteQty": "0.00",
+                        "status": "NEW",
+                        "timeInForce": "GTC",
+                        "type": "STOP_LOSS",
+                        "side": "SELL",
+                        "stopPrice": "28000.00"
+                    },
+                    {
+                        "symbol": "BTCUSDT",
+                        "orderId": 22,
+                        "orderListId": 1,
+                        "clientOrderId": "cid-target",
+                        "transactTime": 1700000002000,
+                        "price": "32000.00",
+                        "origQty": "0.25",
+                        "executedQty": "0.00",
+                        "cummulativeQuoteQty": "0.00",
+                        "status": "NEW",
+                        "timeInForce": "GTC",
+                        "type": "LIMIT_MAKER",
+                        "side": "SELL"
+                    }
+                ]
+            }"#
+        }
+        ("DELETE", "/api/v3/order") if query.contains("origClientOrderId=cid-unknown") => {
+            return (
+                400,
+                r#"{"code":-2011,"msg":"Unknown order sent."}"#.to_string(),
+            );
+        }
+        ("DELETE", "/api/v3/order") => {
+            r#"{
+                "symbol": "BTCUSDT",
+                "origClientOrderId": "cid-open",
+                "orderId": 12,
+                "orderListId": -1,
+                "clientOrderId": "cancel-cid-open",
+                "transactTime": 1700000003000,
+                "price": "29000.00",
+                "origQty": "0.25",
+                "executedQty": "0.00",
+                "cummulativeQuoteQty": "0.00",
+                "status": "CANCELED",
+                "timeInForce": "GTC",
+                "type": "LIMIT_MAKER",
+                "side": "BUY"
+            }"#
+        }
+        ("GET", "/api/v3/myTrades") => {
+            r#"[
+                {
+                    "symbol": "BTCUSDT",
+                    "id": 101,
+                    "orderId": 11,
+                    "orderListId": -1,
+                    "price": "30000.00",
+                    "qty": "0.10",
+                    "quoteQty": "3000.00",
+                    "commission": "0.0001",
+                    "commissionAsset": "BTC",
+                    "time": 1700000001000,
+                    "isBuyer": true,
+                    "isMaker": true,
+                    "isBestMatch": true
+                },
+                {
+                    "symbol": "BTCUSDT",
+                    "id": 102,
+                    "orderId": 13,
+                    "orderListId": -1,
+                    "price": "31000.00",
+                    "qty": "0.10",
+                    "quoteQty": "3100.00",
+                    "commission": "3.10",
+                    "commissionAsset": "USDT",
+                    "time": 1700000004000,
+                    "isBuyer": false,
+                    "isMaker": false,
+                    "isBestMatch": true
+                }
+            ]"#
+        }
+        _ => return (404, r#"{"code":-1,"msg":"not found"}"#.to_string()),
+    };
+
+    (200, body.to_string())
+}
+
+/// Binance Spot user data stream WebSocket server fixture.
+///
+/// Sends an order fill and balance update once connected, and `listenKeyExpired` when signalled.
+#[allow(clippy::result_large_err)]
+async fn start_user_data_stream() -> (String, oneshot::Sender<()>, oneshot::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("ws://{}", listener.local_addr().unwrap());
+    let (expire_tx, expire_rx) = oneshot::channel();
+    let (path_tx, path_rx) = oneshot::channel();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut path = None;
+        let mut websocket =
+            tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+                path = Some(request.uri().path().to_string());
+                Ok(response)
+            })
+            .await
+            .unwrap();
+        path_tx.send(path.unwrap()).unwrap();
+
+        let events = [
+            r#"{
+                "e": "executionReport", "E": 1700000005001, "s": "BTCUSDT",
+                "c": "cid-open", "S": "BUY", "o": "LIMIT", "f": "GTC", "q": "0.25",
+                "p": "29000.00", "x": "TRADE", "X": "PARTIALLY_FILLED", "r": "NONE",
+                "i": 12, "l": "0.05", "z": "0.05", "L": "29000.00", "n": "0.00005",
+                "N": "BTC", "T": 1700000005000, "t": 103, "C": ""
+            }"#,
+            r#"{
+                "e": "balanceUpdate", "E": 1700000005002, "a": "BTC", "d": "1.0",
+                "T": 1700000005002
+            }"#,
+            r#"{
+                "e": "outboundAccountPosition", "E": 1700000005003, "u": 1700000005000,
+                "B": [{ "a": "BTC", "f": "1.55", "l": "0.5" }]
+            }"#,
+        ];
+
+        for event in events {
+            websocket.send(Message::text(event)).await.unwrap();
+        }
+
+        expire_rx.await.unwrap();
+        websocket
+            .send(Message::text(
+                r#"{"e": "listenKeyExpired", "E": 1700000010000, "listenKey": "test-listen-key"}"#,
+            ))
+            .await
+            .unwrap();
+
+        // Keep the connection open, so the stream can only end due to listenKeyExpired
+        while websocket.next().await.is_some() {}
+    });
+
+    (base_url, expire_tx, path_rx)
+}
+
+fn client(base_url_rest: String, base_url_ws: String) -> BinanceSpot {
+    BinanceSpot::new(BinanceSpotConfig {
+        base_url_rest,
+        base_url_ws,
+        keep_alive_interval_secs: 1,
+        ..BinanceSpotConfig::new(API_KEY, SECRET)
+    })
+}
+
+fn btc_usdt() -> InstrumentNameExchange {
+    InstrumentNameExchange::new("BTCUSDT")
+}
+
+fn time(epoch_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(epoch_ms).unwrap()
+}
+
+#[tokio::test]
+async fn test_binance_spot_account_snapshot() {
+    let (server, base_url) = MockBinanceHttp::start().await;
+    let client = client(base_url, String::new());
+
+    let snapshot = client
+        .account_snapshot(
+            &[
+                AssetNameExchange::new("BTC"),
+                AssetNameExchange::new("USDT"),
+            ],
+            &[btc_usdt(), InstrumentNameExchange::new("ETHUSDT")],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot.exchange, ExchangeId::BinanceSpot);
+    assert_eq!(snapshot.balances.len(), 2);
+    assert_eq!(snapshot.balances[0].asset, AssetNameExchange::new("BTC"));
+    assert_eq!(
+        snapshot.balances[0].balance,
+        Balance::new(dec!(2.0), dec!(1.5))
+    );
+    assert_eq!(snapshot.balances[0].time_exchange, time(1700000000000));
+
+    assert_eq!(snapshot.instruments.len(), 2);
+    assert_eq!(snapshot.instruments[0].instrument, btc_usdt());
+    assert!(snapshot.instruments[1].orders.is_empty());
+
+    let order = &snapshot.instruments[0].orders[0];
+    assert_eq!(order.key.cid, ClientOrderId::new("cid-resting"));
+    assert_eq!(order.kind, OrderKind::Limit);
+    assert_eq!(
+        order.time_in_force,
+        TimeInForce::GoodUntilCancelled { post_only: false }
+    );
+    assert_eq!(
+        order.state,
+        OrderState::active(Open::new(
+            OrderId::new("11"),
+            time(1700000001000),
+            dec!(0.10)
+        ))
+    );
+
+    // Every signed request was accepted by the fixture signature validation
+    assert_eq!(server.requests("GET", "/api/v3/account").len(), 1);
+    assert_eq!(server.requests("GET", "/api/v3/openOrders").len(), 1);
+}
+
+#[tokio::test]
+async fn test_binance_spot_open_and_cancel_orders() {
+    let (server, base_url) = MockBinanceHttp::start().await;
+    let client = client(base_url, String::new());
+    let instrument = btc_usdt();
+
+    let request_open = |cid: &str, time_in_force| OrderRequestOpen {
+        key: OrderKey::new(
+            ExchangeId::BinanceSpot,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        ),
+        state: RequestOpen::new(
+            Side::Buy,
+            dec!(29000.00),
+            dec!(0.25),
+            OrderKind::Limit,
+            time_in_force,
+            OrderFlags::default(),
+        ),
+    };
+
+    // Post only limit orders are sent as LIMIT_MAKER
+    let opened = client
+        .open_order(request_open(
+            "cid-open",
+            TimeInForce::GoodUntilCancelled { post_only: true },
+        ))
+        .await;
+    assert_eq!(opened.key.strategy, StrategyId::new("strategy"));
+    assert_eq!(
+        opened.state,
+        Ok(Open::new(
+            OrderId::new("12"),
+            time(1700000002000),
+            dec!(0.00)
+        ))
+    );
+    let query = &server.requests("POST", "/api/v3/order")[0].query;
+    assert!(
+        query.contains("symbol=BTCUSDT&side=BUY&type=LIMIT_MAKER&quantity=0.25&price=29000.00")
+    );
+    assert!(!query.contains("timeInForce"));
+
+    // Binance API errors are mapped to OrderErrors
+    let rejected = client
+        .open_order(request_open(
+            "cid-poor",
+            TimeInForce::GoodUntilCancelled { post_only: false },
+        ))
+        .await;
+    assert_eq!(
+        rejected.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Binance error code -2010: Account has insufficient balance for requested action."
+                .to_string()
+        )))
+    );
+
+    // Unsupported TimeInForce is rejected without being sent
+    let unsupported = client
+        .open_order(request_open("cid-eod", TimeInForce::GoodUntilEndOfDay))
+        .await;
+    assert!(matches!(
+        unsupported.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(_)))
+    ));
+
+    // Reducing OrderFlags are rejected without being sent, rather than silently dropped
+    for flags in [OrderFlags::reduce_only(), OrderFlags::close_position()] {
+        let mut request = request_open(
+            "cid-reduce",
+            TimeInForce::GoodUntilCancelled { post_only: false },
+        );
+        request.state.flags = flags;
+        let reducing = client.open_order(request).await;
+        assert_eq!(
+            reducing.state,
+            Err(OrderError::Rejected(ApiError::OrderRejected(
+                "Binance Spot does not support reduce only or close position orders".to_string()
+            )))
+        );
+    }
+    assert_eq!(server.requests("POST", "/api/v3/order").len(), 2);
+
+    let request_cancel = |cid: &str| OrderRequestCancel {
+        key: OrderKey::new(
+            ExchangeId::BinanceSpot,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        ),
+        state: RequestCancel::new(Some(OrderId::new("12"))),
+    };
+
+    let cancelled = client.cancel_order(request_cancel("cid-open")).await;
+    let cancelled = cancelled.state.unwrap();
+    assert_eq!(cancelled.id, OrderId::new("12"));
+    assert_eq!(cancelled.time_exchange, time(1700000003000));
+
+    let unknown = client.cancel_order(request_cancel("cid-unknown")).await;
+    assert_eq!(
+        unknown.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Unknown order sent.".to_string()
+        )))
+    );
+}
+
+#[tokio::test]
+async fn test_binance_spot_open_order_group_oco() {
+    let (server, base_url) = MockBinanceHttp::start().await;
+    let client = client(base_url, String::new());
+    let instrument = btc_usdt();
+
+    let request_open = |cid: &str, price, kind| OrderRequestOpen {
+        key: OrderKey::new(
+            ExchangeId::BinanceSpot,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        ),
+        state: RequestOpen::new(
+            Side::Sell,
+            price,
+            dec!(0.25),
+            kind,
+            TimeInForce::GoodUntilCancelled { post_only: false },
+            OrderFlags::default(),
+        ),
+    };
+
+    let stop = request_open(
+        "cid-stop",
+        dec!(28000.00),
+        OrderKind::StopMarket {
+            trigger: OrderTrigger::new(dec!(28000.00), TriggerSource::Last),
+        },
+    );
+    let target = request_open("cid-target", dec!(32000.00), OrderKind::Limit);
+
+    // Stop-loss & limit OCO is opened natively as a single order list
+    let group = OrderGroup::new(
+        OrderGroupId::new("group-oco"),
+        OrderGroupKind::OneCancelsOther,
+        stop.clone(),
+        vec![target.clone()],
+    );
+    assert!(client.supports_order_group(&group));
+
+    let opened = client.open_order_group(group).await;
+    assert_eq!(opened.len(), 2);
+    assert_eq!(opened[0].key.cid, ClientOrderId::new("cid-stop"));
+    assert_eq!(
+        opened[0].state,
+        Ok(Open::new(
+            OrderId::new("21"),
+            time(1700000002000),
+            dec!(0.00)
+        ))
+    );
+    assert_eq!(opened[1].key.cid, ClientOrderId::new("cid-target"));
+    assert_eq!(
+        opened[1].state,
+        Ok(Open::new(
+            OrderId::new("22"),
+            time(1700000002000),
+            dec!(0.00)
+        ))
+    );
+
+    let query = &server.requests("POST", "/api/v3/orderList/oco")[0].query;
+    assert!(query.contains("listClientOrderId=group-oco&side=SELL&quantity=0.25"));
+    assert!(query.contains(
+        "aboveType=LIMIT_MAKER&aboveClientOrderId=cid-target&abovePrice=32000.00&belowType=STOP_LOSS"
+    ));
+    assert!(query.contains("belowClientOrderId=cid-stop&belowStopPrice=28000.00"));
+    assert!(server.requests("POST", "/api/v3/order").is_empty());
+
+    // Bracket groups are not supported natively, so only the primary order is opened
+    let group = OrderGroup::new(
+        OrderGroupId::new("group-bracket"),
+        OrderGroupKind::Bracket,
+        OrderRequestOpen {
+            state: RequestOpen {
+                side: Side::Buy,
+                kind: OrderKind::Limit,
+                ..target.state.clone()
+            },
+            ..request_open("cid-open", dec!(29000.00), OrderKind::Limit)
+        },
+        vec![stop, target],
+    );
+    assert!(!client.supports_order_group(&group));
+
+    let opened = client.open_order_group(group).await;
+    assert_eq!(opened.len(), 1);
+    assert_eq!(opened[0].key.cid, ClientOrderId::new("cid-open"));
+    assert_eq!(server.requests("POST", "/api/v3/order").len(), 1);
+    assert_eq!(server.requests("POST", "/api/v3/orderList/oco").len(), 1);
+}
+
+#[tokio::test]
+async fn test_binance_spot_fetch_trades_and_authentication_errors() {
+    let (server, base_url) = MockBinanceHttp::start().await;
+    let client = client(base_url.clone(), String::new());
+
+    // No instruments have been provided yet, so no trades can be fetched
+    assert!(client.fetch_trades(time(0)).await.unwrap().is_empty());
+
+    client.account_snapshot(&[], &[btc_usdt()]).await.unwrap();
+
+    let trades = client.fetch_trades(time(1700000000000)).await.unwrap();
+    assert_eq!(trades.len(), 2);
+
+    // Base asset commission is converted to quote asset
+    assert_eq!(trades[0].side, Side::Buy);
+    assert_eq!(trades[0].fees.fees, dec!(3.0));
+    assert_eq!(trades[1].side, Side::Sell);
+    assert_eq!(trades[1].fees.fees, dec!(3.10));
+
+    let query = &server.requests("GET", "/api/v3/myTrades")[0].query;
+    assert!(query.contains("symbol=BTCUSDT&startTime=1700000000000&limit=1000"));
+
+    // Invalid credentials are connectivity errors
+    let invalid = BinanceSpot::new(BinanceSpotConfig {
+        base_url_rest: base_url,
+        ..BinanceSpotConfig::new(API_KEY, "wrong-secret")
+    });
+    assert!(matches!(
+        invalid.fetch_balances().await,
+        Err(UnindexedClientError::Connectivity(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_binance_spot_account_stream() {
+    let (server, base_url_rest) = MockBinanceHttp::start().await;
+    let (base_url_ws, expire_tx, path_rx) = start_user_data_stream().await;
+    let client = client(base_url_rest, base_url_ws);
+
+    let mut stream = client.account_stream(&[], &[btc_usdt()]).await.unwrap();
+    assert_eq!(path_rx.await.unwrap(), format!("/ws/{LISTEN_KEY}"));
+    assert_eq!(server.requests("POST", "/api/v3/userDataStream").len(), 1);
+
+    // executionReport TRADE generates a Trade followed by an Order snapshot
+    let UnindexedAccountEvent {
+        exchange,
+        kind: AccountEventKind::Trade(trade),
+    } = stream.next().await.unwrap()
+    else {
+        panic!("expected Trade");
+    };
+    assert_eq!(exchange, ExchangeId::BinanceSpot);
+    assert_eq!(trade.order_id, OrderId::new("12"));
+    assert_eq!(trade.quantity, dec!(0.05));
+    assert_eq!(trade.fees.fees, dec!(1.45));
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cid-open"));
+    assert_eq!(
+        order.0.state,
+        OrderState::Active(ActiveOrderState::Open(Open::new(
+            OrderId::new("12"),
+            time(1700000005000),
+            dec!(0.05)
+        )))
+    );
+
+    // balanceUpdate is ignored, and outboundAccountPosition generates a balance snapshot
+    let AccountEventKind::BalanceSnapshot(balance) = stream.next().await.unwrap().kind else {
+        panic!("expected BalanceSnapshot");
+    };
+    assert_eq!(balance.0.asset, AssetNameExchange::new("BTC"));
+    assert_eq!(balance.0.balance, Balance::new(dec!(2.05), dec!(1.55)));
+
+    // listenKey is kept alive while the stream is active
+    let keep_alive = async {
+        while server.requests("PUT", "/api/v3/userDataStream").is_empty() {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), keep_alive)
+        .await
+        .expect("listenKey was not kept alive");
+    assert!(
+        server.requests("PUT", "/api/v3/userDataStream")[0]
+            .query
+            .contains(LISTEN_KEY)
+    );
+
+    // Stream ends once the listenKey expires
+    expire_tx.send(()).unwrap();
+    assert!(stream.next().await.is_none());
+}
@@ -0,0 +1,636 @@
+use barter_execution::{
+    AccountEventKind,
+    balance::Balance,
+    client::{
+        ExecutionClient,
+        bybit::{BybitConfig, BybitPerpetualsUsd, BybitSpot},
+    },
+    error::{ApiError, ClientError, ConnectivityError, OrderError},
+    order::{
+        OrderFlags, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, RequestCancel, RequestOpen},
+        state::{Cancelled, Open, OrderState},
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use rust_decimal_macros::dec;
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tokio_tungstenite::tungstenite::Message;
+
+const API_KEY: &str = "test-api-key";
+const SECRET: &str = "test-secret";
+
+#[derive(Debug, Clone)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    query: String,
+    body: String,
+}
+
+/// Minimal Bybit V5 Http server fixture that validates request signatures and responds with
+/// canned `retCode` envelopes.
+#[derive(Debug, Clone, Default)]
+struct MockBybitHttp {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockBybitHttp {
+    async fn start() -> (Self, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = Self::default();
+
+        let handler = server.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handler.clone().handle(stream));
+            }
+        });
+
+        (server, base_url)
+    }
+
+    fn requests(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|request| request.method == method && request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    async fn handle(self, mut stream: TcpStream) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let head_len = loop {
+            if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break position + 4;
+            }
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..head_len]).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap().split(' ');
+        let method = request_line.next().unwrap().to_string();
+        let target = request_line.next().unwrap();
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), query.to_string()),
+            None => (target.to_string(), String::new()),
+        };
+        let headers = lines
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect::<Vec<_>>();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let content_length = header("content-length")
+            .map(|length| length.parse::<usize>().unwrap())
+            .unwrap_or_default();
+        while buffer.len() < head_len + content_length {
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        }
+        let body = String::from_utf8_lossy(&buffer[head_len..]).to_string();
+
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            query: query.clone(),
+            body: body.clone(),
+        });
+
+        let payload = if method == "GET" { &query } else { &body };
+        let response = if header("x-bapi-api-key").as_deref() != Some(API_KEY) {
+            r#"{"retCode":10003,"retMsg":"API key is invalid.","result":{},"time":1700000000000}"#
+                .to_string()
+        } else if !signature_valid(
+            header("x-bapi-timestamp"),
+            header("x-bapi-recv-window"),
+            header("x-bapi-sign"),
+            payload,
+        ) {
+            r#"{"retCode":10004,"retMsg":"error sign!","result":{},"time":1700000000000}"#
+                .to_string()
+        } else {
+            route_signed(&method, &path, &query, &body)
+        };
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+            response.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.ok();
+    }
+}
+
+fn signature_valid(
+    timestamp: Option<String>,
+    recv_window: Option<String>,
+    signature: Option<String>,
+    payload: &str,
+) -> bool {
+    let (Some(timestamp), Some(recv_window), Some(signature)) = (timestamp, recv_window, signature)
+    else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+    mac.update(format!("{timestamp}{API_KEY}{recv_window}{payload}").as_bytes());
+
+    hex::encode(mac.finalize().into_bytes()) == signature
+}
+
+fn route_signed(method: &str, path: &str, query: &str, body: &str) -> String {
+    let result = match (method, path) {
+        ("GET", "/v5/account/wallet-balance") => {
+            r#"{"list": [{
+                "accountType": "UNIFIED",
+                "coin": [
+                    { "coin": "USDT", "walletBalance": "1000.5", "locked": "100" },
+                    { "coin": "BTC", "walletBalance": "0.5", "locked": "" },
+                    { "coin": "ETH", "walletBalance": "2" }
+                ]
+            }]}"#
+        }
+        // Open orders are paginated, with the second page requested via the cursor
+        ("GET", "/v5/order/realtime") if !query.contains("cursor=") => {
+            r#"{"list": [{
+                "symbol": "BTCUSDT", "orderId": "order-1", "orderLinkId": "cid-resting",
+                "side": "Buy", "orderType": "Limit", "timeInForce": "PostOnly",
+                "price": "29000", "qty": "0.2", "cumExecQty": "0.05", "orderStatus": "PartiallyFilled",
+                "rejectReason": "EC_NoError", "updatedTime": "1700000000500"
+            }], "nextPageCursor": "page-2"}"#
+        }
+        ("GET", "/v5/order/realtime") => {
+            r#"{"list": [{
+                "symbol": "ETHUSDT", "orderId": "order-2", "orderLinkId": "cid-other",
+                "side": "Sell", "orderType": "Limit", "timeInForce": "GTC",
+                "price": "2000", "qty": "1", "cumExecQty": "0", "orderStatus": "New",
+                "updatedTime": "1700000000600"
+            }], "nextPageCursor": ""}"#
+        }
+        ("GET", "/v5/execution/list") => {
+            r#"{"list": [
+                {
+                    "symbol": "BTCUSDT", "execId": "exec-1", "orderId": "order-1",
+                    "side": "Buy", "execType": "Trade", "execPrice": "29000",
+                    "execQty": "0.05", "execFee": "0.0001", "feeCurrency": "BTC",
+                    "execTime": "1700000000700"
+                },
+                {
+                    "symbol": "BTCUSDT", "execId": "exec-2", "orderId": "",
+                    "side": "Sell", "execType": "Funding", "execPrice": "29000",
+                    "execQty": "0.05", "execFee": "0.1", "execTime": "1700000000800"
+                }
+            ], "nextPageCursor": ""}"#
+        }
+        ("POST", "/v5/order/create") if body.contains("cid-rejected") => {
+            return r#"{"retCode":170131,"retMsg":"Insufficient balance.","result":{},"time":1700000002000}"#
+                .to_string();
+        }
+        ("POST", "/v5/order/create") => r#"{"orderId": "order-3", "orderLinkId": "cid-open"}"#,
+        ("POST", "/v5/order/cancel") if body.contains("cid-cancelled") => {
+            return r#"{"retCode":110010,"retMsg":"Order has been cancelled","result":{},"time":1700000003000}"#
+                .to_string();
+        }
+        ("POST", "/v5/order/cancel") => r#"{"orderId": "order-1", "orderLinkId": "cid-resting"}"#,
+        _ => {
+            return r#"{"retCode":10001,"retMsg":"not found","result":{},"time":1700000000000}"#
+                .to_string();
+        }
+    };
+
+    format!(r#"{{"retCode":0,"retMsg":"OK","result":{result},"time":1700000001000}}"#)
+}
+
+/// Bybit V5 private WebSocket server fixture.
+///
+/// Validates the `auth` signature and `subscribe` request, before sending `order`, `execution`
+/// & `wallet` topic updates for both the spot and linear categories.
+async fn start_private_stream() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("ws://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut websocket = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+        let auth = next_request(&mut websocket).await;
+        assert_eq!(auth["op"], "auth");
+        assert_eq!(auth["args"][0], API_KEY);
+        let expires = auth["args"][1].as_i64().unwrap();
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("GET/realtime{expires}").as_bytes());
+        assert_eq!(auth["args"][2], hex::encode(mac.finalize().into_bytes()));
+        websocket
+            .send(Message::text(
+                r#"{"success":true,"ret_msg":"","op":"auth","conn_id":"conn-1"}"#,
+            ))
+            .await
+            .unwrap();
+
+        let subscribe = next_request(&mut websocket).await;
+        assert_eq!(subscribe["op"], "subscribe");
+        assert_eq!(
+            subscribe["args"],
+            serde_json::json!(["order", "execution", "wallet"])
+        );
+        websocket
+            .send(Message::text(
+                r#"{"success":true,"ret_msg":"","op":"subscribe","conn_id":"conn-1"}"#,
+            ))
+            .await
+            .unwrap();
+
+        let updates = [
+            r#"{
+                "id": "1", "topic": "order", "creationTime": 1700000005000,
+                "data": [
+                    {
+                        "category": "linear", "symbol": "BTCUSDT", "orderId": "order-9",
+                        "orderLinkId": "cid-linear", "side": "Buy", "orderType": "Market",
+                        "timeInForce": "IOC", "price": "0", "qty": "0.01", "cumExecQty": "0.01",
+                        "orderStatus": "Filled", "rejectReason": "EC_NoError",
+                        "updatedTime": "1700000005000"
+                    },
+                    {
+                        "category": "spot", "symbol": "BTCUSDT", "orderId": "order-1",
+                        "orderLinkId": "cid-resting", "side": "Buy", "orderType": "Limit",
+                        "timeInForce": "PostOnly", "price": "29000", "qty": "0.2",
+                        "cumExecQty": "0.2", "orderStatus": "Filled", "rejectReason": "EC_NoError",
+                        "updatedTime": "1700000005000"
+                    },
+                    {
+                        "category": "spot", "symbol": "BTCUSDT", "orderId": "order-4",
+                        "orderLinkId": "cid-rejected", "side": "Sell", "orderType": "Limit",
+                        "timeInForce": "GTC", "price": "31000", "qty": "10",
+                        "cumExecQty": "0", "orderStatus": "Rejected",
+                        "rejectReason": "EC_InsufficientBalance", "updatedTime": "1700000005001"
+                    }
+                ]
+            }"#,
+            r#"{
+                "id": "2", "topic": "execution", "creationTime": 1700000005002,
+                "data": [
+                    {
+                        "category": "linear", "symbol": "BTCUSDT", "execId": "exec-9",
+                        "orderId": "order-9", "side": "Buy", "execType": "Trade",
+                        "execPrice": "30000", "execQty": "0.01", "execFee": "0.165",
+                        "feeCurrency": "", "execTime": "1700000005000"
+                    },
+                    {
+                        "category": "spot", "symbol": "BTCUSDT", "execId": "exec-3",
+                        "orderId": "order-1", "side": "Buy", "execType": "Trade",
+                        "execPrice": "29000", "execQty": "0.15", "execFee": "0.0003",
+                        "feeCurrency": "BTC", "execTime": "1700000005000"
+                    }
+                ]
+            }"#,
+            r#"{
+                "id": "3", "topic": "wallet", "creationTime": 1700000005003,
+                "data": [{
+                    "accountType": "UNIFIED",
+                    "coin": [{ "coin": "USDT", "walletBalance": "995.5", "locked": "0" }]
+                }]
+            }"#,
+        ];
+
+        for update in updates {
+            websocket.send(Message::text(update)).await.unwrap();
+        }
+
+        websocket.close(None).await.ok();
+    });
+
+    base_url
+}
+
+async fn next_request(
+    websocket: &mut tokio_tungstenite::WebSocketStream<TcpStream>,
+) -> serde_json::Value {
+    loop {
+        match websocket.next().await.unwrap().unwrap() {
+            Message::Text(text) => break serde_json::from_str(text.as_str()).unwrap(),
+            _ => continue,
+        }
+    }
+}
+
+fn config(base_url_rest: String, base_url_ws: String) -> BybitConfig {
+    BybitConfig {
+        base_url_rest,
+        base_url_ws,
+        ..BybitConfig::new(API_KEY, SECRET)
+    }
+}
+
+fn btc_usdt() -> InstrumentNameExchange {
+    InstrumentNameExchange::new("BTCUSDT")
+}
+
+fn time(epoch_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(epoch_ms).unwrap()
+}
+
+#[tokio::test]
+async fn test_bybit_account_snapshot_and_trades() {
+    let (server, base_url) = MockBybitHttp::start().await;
+    let client = BybitSpot::new(config(base_url, String::new()));
+
+    let snapshot = client
+        .account_snapshot(
+            &[
+                AssetNameExchange::new("USDT"),
+                AssetNameExchange::new("BTC"),
+            ],
+            &[btc_usdt()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot.exchange, ExchangeId::BybitSpot);
+    assert_eq!(snapshot.balances.len(), 2);
+    assert_eq!(
+        snapshot.balances[0].balance,
+        Balance::new(dec!(1000.5), dec!(900.5))
+    );
+    assert_eq!(snapshot.balances[0].time_exchange, time(1700000001000));
+    assert_eq!(
+        snapshot.balances[1].balance,
+        Balance::new(dec!(0.5), dec!(0.5))
+    );
+
+    // Open orders from every page are fetched, and filtered by the provided instruments
+    let wallet = server.requests("GET", "/v5/account/wallet-balance");
+    assert_eq!(wallet[0].query, "accountType=UNIFIED");
+    let open_orders = server.requests("GET", "/v5/order/realtime");
+    assert_eq!(open_orders.len(), 2);
+    assert_eq!(open_orders[0].query, "category=spot&limit=50");
+    assert_eq!(open_orders[1].query, "category=spot&limit=50&cursor=page-2");
+
+    assert_eq!(snapshot.instruments.len(), 1);
+    let order = &snapshot.instruments[0].orders[0];
+    assert_eq!(order.key.cid, ClientOrderId::new("cid-resting"));
+    assert_eq!(
+        order.time_in_force,
+        TimeInForce::GoodUntilCancelled { post_only: true }
+    );
+    assert_eq!(
+        order.state,
+        OrderState::active(Open::new(
+            OrderId::new("order-1"),
+            time(1700000000500),
+            dec!(0.05)
+        ))
+    );
+
+    // Only fills are mapped to trades, with base asset fees converted to the quote asset
+    let trades = client.fetch_trades(time(1700000000000)).await.unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].order_id, OrderId::new("order-1"));
+    assert_eq!(trades[0].quantity, dec!(0.05));
+    assert_eq!(trades[0].fees.fees, dec!(2.9));
+    assert_eq!(
+        server.requests("GET", "/v5/execution/list")[0].query,
+        "category=spot&startTime=1700000000000&limit=100"
+    );
+}
+
+#[tokio::test]
+async fn test_bybit_open_and_cancel_order() {
+    let (server, base_url) = MockBybitHttp::start().await;
+    let client = BybitPerpetualsUsd::new(config(base_url, String::new()));
+    let instrument = btc_usdt();
+
+    let key = |cid: &str| {
+        OrderKey::new(
+            ExchangeId::BybitPerpetualsUsd,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        )
+    };
+    let request_open = |cid: &str, kind, time_in_force| OrderRequestOpen {
+        key: key(cid),
+        state: RequestOpen::new(
+            Side::Sell,
+            dec!(31000),
+            dec!(0.1),
+            kind,
+            time_in_force,
+            OrderFlags::default(),
+        ),
+    };
+
+    let opened = client
+        .open_order(request_open(
+            "cid-open",
+            OrderKind::Limit,
+            TimeInForce::GoodUntilCancelled { post_only: true },
+        ))
+        .await;
+    assert_eq!(
+        opened.state,
+        Ok(Open::new(
+            OrderId::new("order-3"),
+            time(1700000001000),
+            dec!(0)
+        ))
+    );
+    let body = serde_json::from_str::<serde_json::Value>(
+        &server.requests("POST", "/v5/order/create")[0].body,
+    )
+    .unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "category": "linear",
+            "symbol": "BTCUSDT",
+            "side": "Sell",
+            "orderType": "Limit",
+            "qty": "0.1",
+            "price": "31000",
+            "timeInForce": "PostOnly",
+            "orderLinkId": "cid-open"
+        })
+    );
+
+    // Non-zero retCodes are mapped to ApiErrors
+    let rejected = client
+        .open_order(request_open(
+            "cid-rejected",
+            OrderKind::Market,
+            TimeInForce::ImmediateOrCancel,
+        ))
+        .await;
+    assert_eq!(
+        rejected.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Bybit retCode 170131: Insufficient balance.".to_string()
+        )))
+    );
+
+    // Unsupported TimeInForce is rejected without a request
+    let unsupported = client
+        .open_order(request_open(
+            "cid-eod",
+            OrderKind::Limit,
+            TimeInForce::GoodUntilEndOfDay,
+        ))
+        .await;
+    assert!(matches!(
+        unsupported.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(_)))
+    ));
+    assert_eq!(server.requests("POST", "/v5/order/create").len(), 2);
+
+    let cancelled = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cid-resting"),
+            state: RequestCancel::new(None),
+        })
+        .await;
+    assert_eq!(
+        cancelled.state,
+        Ok(Cancelled::new(OrderId::new("order-1"), time(1700000001000)))
+    );
+
+    let already_cancelled = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cid-cancelled"),
+            state: RequestCancel::new(None),
+        })
+        .await;
+    assert_eq!(
+        already_cancelled.state,
+        Err(OrderError::Rejected(ApiError::OrderAlreadyCancelled))
+    );
+}
+
+#[tokio::test]
+async fn test_bybit_spot_reducing_orders_rejected() {
+    let (server, base_url) = MockBybitHttp::start().await;
+    let client = BybitSpot::new(config(base_url, String::new()));
+    let instrument = btc_usdt();
+
+    // Reducing OrderFlags are rejected without a request, rather than silently dropped
+    for flags in [OrderFlags::reduce_only(), OrderFlags::close_position()] {
+        let reducing = client
+            .open_order(OrderRequestOpen {
+                key: OrderKey::new(
+                    ExchangeId::BybitSpot,
+                    &instrument,
+                    StrategyId::new("strategy"),
+                    ClientOrderId::new("cid-reduce"),
+                ),
+                state: RequestOpen::new(
+                    Side::Sell,
+                    dec!(31000),
+                    dec!(0.1),
+                    OrderKind::Limit,
+                    TimeInForce::GoodUntilCancelled { post_only: false },
+                    flags,
+                ),
+            })
+            .await;
+        assert_eq!(
+            reducing.state,
+            Err(OrderError::Rejected(ApiError::OrderRejected(
+                "Bybit spot does not support reduce only or close position orders".to_string()
+            )))
+        );
+    }
+    assert!(server.requests("POST", "/v5/order/create").is_empty());
+}
+
+#[tokio::test]
+async fn test_bybit_authentication_error() {
+    let (_server, base_url) = MockBybitHttp::start().await;
+    let client = BybitSpot::new(BybitConfig {
+        base_url_rest: base_url,
+        ..BybitConfig::new(API_KEY, "wrong-secret")
+    });
+
+    let error = client.fetch_balances().await.unwrap_err();
+    assert_eq!(
+        error,
+        ClientError::Connectivity(ConnectivityError::Socket(
+            "Bybit authentication failed: error sign!".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_bybit_account_stream() {
+    let base_url_ws = start_private_stream().await;
+    let client = BybitSpot::new(config(String::new(), base_url_ws));
+
+    let mut stream = client.account_stream(&[], &[btc_usdt()]).await.unwrap();
+
+    // Linear category updates are filtered from the spot stream
+    let event = stream.next().await.unwrap();
+    assert_eq!(event.exchange, ExchangeId::BybitSpot);
+    let AccountEventKind::OrderSnapshot(order) = event.kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cid-resting"));
+    assert_eq!(order.0.state, OrderState::fully_filled());
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cid-rejected"));
+    assert_eq!(
+        order.0.state,
+        OrderState::inactive(OrderError::Rejected(ApiError::OrderRejected(
+            "EC_InsufficientBalance".to_string()
+        )))
+    );
+
+    let AccountEventKind::Trade(trade) = stream.next().await.unwrap().kind else {
+        panic!("expected Trade");
+    };
+    assert_eq!(trade.order_id, OrderId::new("order-1"));
+    assert_eq!(trade.price, dec!(29000));
+    assert_eq!(trade.quantity, dec!(0.15));
+    assert_eq!(trade.fees.fees, dec!(8.7));
+
+    let AccountEventKind::BalanceSnapshot(balance) = stream.next().await.unwrap().kind else {
+        panic!("expected BalanceSnapshot");
+    };
+    assert_eq!(balance.0.asset, AssetNameExchange::new("USDT"));
+    assert_eq!(balance.0.balance, Balance::new(dec!(995.5), dec!(995.5)));
+    assert_eq!(balance.0.time_exchange, time(1700000005003));
+
+    // Stream ends once the connection is closed
+    assert!(stream.next().await.is_none());
+}
@@ -0,0 +1,751 @@
+use barter_execution::{
+    AccountEventKind,
+    balance::Balance,
+    client::{
+        ExecutionClient,
+        coinbase::{Coinbase, CoinbaseConfig},
+    },
+    error::{ApiError, ClientError, ConnectivityError, OrderError},
+    order::{
+        OrderFlags, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, RequestCancel, RequestOpen},
+        state::{Cancelled, Open, OrderState},
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{SinkExt, StreamExt};
+use p256::{
+    SecretKey,
+    ecdsa::{Signature, SigningKey, VerifyingKey, signature::Verifier},
+    pkcs8::LineEnding,
+};
+use rust_decimal_macros::dec;
+use std::sync::{Arc, Mutex};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tokio_tungstenite::{WebSocketStream, tungstenite::Message};
+
+const API_KEY: &str = "organizations/test-org/apiKeys/test-key";
+
+fn secret_key() -> SecretKey {
+    SecretKey::from_slice(&[7u8; 32]).unwrap()
+}
+
+fn secret() -> String {
+    secret_key()
+        .to_sec1_pem(LineEnding::LF)
+        .unwrap()
+        .to_string()
+}
+
+/// Verify the ES256 signature and standard claims of a Coinbase JWT, returning the claims.
+fn verify_jwt(jwt: &str) -> Option<serde_json::Value> {
+    let decode = |part: &str| {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(part)
+            .ok()
+    };
+
+    let (message, signature) = jwt.rsplit_once('.')?;
+    let (header, claims) = message.split_once('.')?;
+    let header = serde_json::from_slice::<serde_json::Value>(&decode(header)?).ok()?;
+    let claims = serde_json::from_slice::<serde_json::Value>(&decode(claims)?).ok()?;
+    let signature = Signature::from_slice(&decode(signature)?).ok()?;
+
+    VerifyingKey::from(&SigningKey::from(secret_key()))
+        .verify(message.as_bytes(), &signature)
+        .ok()?;
+
+    let valid = header["alg"] == "ES256"
+        && header["kid"] == API_KEY
+        && claims["sub"] == API_KEY
+        && claims["iss"] == "cdp"
+        && claims["exp"].as_i64()? - claims["nbf"].as_i64()? == 120;
+
+    valid.then_some(claims)
+}
+
+#[derive(Debug, Clone)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    query: String,
+    body: String,
+}
+
+/// Minimal Coinbase Advanced Trade Http server fixture that validates request JWTs and
+/// responds with canned payloads.
+#[derive(Debug, Clone, Default)]
+struct MockCoinbaseHttp {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockCoinbaseHttp {
+    async fn start() -> (Self, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = Self::default();
+
+        let handler = server.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handler.clone().handle(stream));
+            }
+        });
+
+        (server, base_url)
+    }
+
+    fn requests(&self, path: &str) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|request| request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    async fn handle(self, mut stream: TcpStream) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let head_len = loop {
+            if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break position + 4;
+            }
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..head_len]).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap().split(' ');
+        let method = request_line.next().unwrap().to_string();
+        let target = request_line.next().unwrap().to_string();
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), query.to_string()),
+            None => (target.clone(), String::new()),
+        };
+        let headers = lines
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect::<Vec<_>>();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        };
+
+        let content_length = header("content-length")
+            .parse::<usize>()
+            .unwrap_or_default();
+        while buffer.len() < head_len + content_length {
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        }
+        let body = String::from_utf8_lossy(&buffer[head_len..]).to_string();
+
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            query: query.clone(),
+            body: body.clone(),
+        });
+
+        let authorised = header("authorization")
+            .strip_prefix("Bearer ")
+            .and_then(verify_jwt)
+            .is_some_and(|claims| claims["uri"] == format!("{method} 127.0.0.1{path}"));
+
+        let (status, body) = if authorised {
+            ("200 OK", route_signed(&path, &query, &body))
+        } else {
+            (
+                "401 Unauthorized",
+                r#"{"error":"UNAUTHENTICATED","message":"invalid signature","error_details":""}"#
+                    .to_string(),
+            )
+        };
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.ok();
+    }
+}
+
+fn route_signed(path: &str, query: &str, body: &str) -> String {
+    match path {
+        "/api/v3/brokerage/accounts" if !query.contains("cursor") => r#"{
+            "accounts": [
+                {
+                    "uuid": "1", "name": "USD Wallet", "currency": "USD",
+                    "available_balance": { "value": "9000", "currency": "USD" },
+                    "hold": { "value": "1000", "currency": "USD" }
+                }
+            ],
+            "has_next": true,
+            "cursor": "page2",
+            "size": 1
+        }"#
+        .to_string(),
+        "/api/v3/brokerage/accounts" => r#"{
+            "accounts": [
+                {
+                    "uuid": "2", "name": "BTC Wallet", "currency": "BTC",
+                    "available_balance": { "value": "0.5", "currency": "BTC" },
+                    "hold": { "value": "0", "currency": "BTC" }
+                },
+                {
+                    "uuid": "3", "name": "ETH Wallet", "currency": "ETH",
+                    "available_balance": { "value": "2", "currency": "ETH" },
+                    "hold": { "value": "0", "currency": "ETH" }
+                }
+            ],
+            "has_next": false,
+            "cursor": "",
+            "size": 2
+        }"#
+        .to_string(),
+        "/api/v3/brokerage/orders/historical/batch" => r#"{
+            "orders": [
+                {
+                    "order_id": "301", "product_id": "BTC-USD", "side": "BUY",
+                    "client_order_id": "cidresting", "status": "OPEN",
+                    "order_configuration": {
+                        "limit_limit_gtc": { "base_size": "0.2", "limit_price": "29000", "post_only": true }
+                    },
+                    "filled_size": "0.05", "created_time": "2023-11-14T22:13:20.500Z"
+                },
+                {
+                    "order_id": "302", "product_id": "ETH-USD", "side": "SELL",
+                    "client_order_id": "cidother", "status": "OPEN",
+                    "order_configuration": {
+                        "limit_limit_gtc": { "base_size": "1", "limit_price": "2000", "post_only": false }
+                    },
+                    "filled_size": "0", "created_time": "2023-11-14T22:13:20.600Z"
+                },
+                {
+                    "order_id": "303", "product_id": "BTC-USD", "side": "BUY",
+                    "client_order_id": "cidgtd", "status": "OPEN",
+                    "order_configuration": {
+                        "limit_limit_gtd": { "base_size": "0.1", "limit_price": "28000", "end_time": "2023-11-15T00:00:00Z", "post_only": false }
+                    },
+                    "filled_size": "0", "created_time": "2023-11-14T22:13:20.700Z"
+                }
+            ],
+            "sequence": "0",
+            "has_next": false,
+            "cursor": ""
+        }"#
+        .to_string(),
+        "/api/v3/brokerage/orders/historical/fills" if !query.contains("cursor") => r#"{
+            "fills": [
+                {
+                    "entry_id": "501", "trade_id": "401", "order_id": "301",
+                    "trade_time": "2023-11-14T22:13:20.800Z", "trade_type": "FILL",
+                    "price": "29000", "size": "0.05", "commission": "1.45",
+                    "product_id": "BTC-USD", "side": "BUY", "size_in_quote": false
+                },
+                {
+                    "entry_id": "502", "trade_id": "402", "order_id": "300",
+                    "trade_time": "2023-11-14T22:13:20.900Z", "trade_type": "FILL",
+                    "price": "28000", "size": "2800", "commission": "2.8",
+                    "product_id": "BTC-USD", "side": "SELL", "size_in_quote": true
+                }
+            ],
+            "cursor": "next"
+        }"#
+        .to_string(),
+        "/api/v3/brokerage/orders/historical/fills" => r#"{"fills":[],"cursor":""}"#.to_string(),
+        "/api/v3/brokerage/orders" => {
+            let body = serde_json::from_str::<serde_json::Value>(body).unwrap();
+            let cid = body["client_order_id"].as_str().unwrap();
+            match cid {
+                "cidrejected" => serde_json::json!({
+                    "success": false,
+                    "failure_reason": "UNKNOWN_FAILURE_REASON",
+                    "order_id": "",
+                    "error_response": {
+                        "error": "INSUFFICIENT_FUND",
+                        "message": "Insufficient balance in source account",
+                        "error_details": "",
+                        "preview_failure_reason": "PREVIEW_INSUFFICIENT_FUND"
+                    }
+                }),
+                cid => serde_json::json!({
+                    "success": true,
+                    "success_response": {
+                        "order_id": format!("ord-{cid}"),
+                        "product_id": body["product_id"],
+                        "side": body["side"],
+                        "client_order_id": cid
+                    }
+                }),
+            }
+            .to_string()
+        }
+        "/api/v3/brokerage/orders/batch_cancel" => {
+            let body = serde_json::from_str::<serde_json::Value>(body).unwrap();
+            let order_id = body["order_ids"][0].as_str().unwrap();
+            let (success, failure_reason) = match order_id {
+                "ord-unknown" => (false, "UNKNOWN_CANCEL_ORDER"),
+                _ => (true, "UNKNOWN_CANCEL_FAILURE_REASON"),
+            };
+            serde_json::json!({
+                "results": [{
+                    "success": success,
+                    "failure_reason": failure_reason,
+                    "order_id": order_id
+                }]
+            })
+            .to_string()
+        }
+        _ => r#"{"error":"NOT_FOUND","message":"not found"}"#.to_string(),
+    }
+}
+
+/// Coinbase Advanced Trade `user` WebSocket server fixture.
+///
+/// Expects `user` & `heartbeats` subscriptions authenticated with a valid JWT, then sends the
+/// `user` channel snapshot followed by order updates.
+async fn start_user_ws() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("ws://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(handle_user_ws(
+                tokio_tungstenite::accept_async(stream).await.unwrap(),
+            ));
+        }
+    });
+
+    base_url
+}
+
+async fn handle_user_ws(mut websocket: WebSocketStream<TcpStream>) {
+    for channel in ["user", "heartbeats"] {
+        let subscribe = next_request(&mut websocket).await.unwrap();
+        assert_eq!(subscribe["type"], "subscribe");
+        assert_eq!(subscribe["channel"], channel);
+
+        // WebSocket JWTs do not include a uri claim
+        let claims = verify_jwt(subscribe["jwt"].as_str().unwrap()).unwrap();
+        assert!(claims.get("uri").is_none());
+    }
+
+    for message in USER_MESSAGES {
+        send(&mut websocket, message).await;
+    }
+
+    // Keep the connection open until the client disconnects
+    while next_request(&mut websocket).await.is_some() {}
+}
+
+const USER_MESSAGES: [&str; 6] = [
+    r#"{
+        "channel": "subscriptions", "client_id": "", "timestamp": "2023-11-14T22:13:20Z",
+        "sequence_num": 0,
+        "events": [{ "subscriptions": { "user": ["test-user"], "heartbeats": ["heartbeats"] } }]
+    }"#,
+    r#"{
+        "channel": "user", "client_id": "", "timestamp": "2023-11-14T22:13:21Z", "sequence_num": 1,
+        "events": [{
+            "type": "snapshot",
+            "orders": [
+                {
+                    "order_id": "301", "client_order_id": "cidresting", "product_id": "BTC-USD",
+                    "order_side": "BUY", "order_type": "Limit", "time_in_force": "GOOD_UNTIL_CANCELLED",
+                    "post_only": true, "status": "OPEN", "limit_price": "29000",
+                    "cumulative_quantity": "0.05", "leaves_quantity": "0.15", "avg_price": "29000",
+                    "filled_value": "1450", "total_fees": "1.45", "reject_reason": ""
+                },
+                {
+                    "order_id": "302", "client_order_id": "cidother", "product_id": "ETH-USD",
+                    "order_side": "SELL", "order_type": "Limit", "time_in_force": "GOOD_UNTIL_CANCELLED",
+                    "post_only": false, "status": "OPEN", "limit_price": "2000",
+                    "cumulative_quantity": "0", "leaves_quantity": "1", "avg_price": "0",
+                    "filled_value": "0", "total_fees": "0", "reject_reason": ""
+                }
+            ]
+        }]
+    }"#,
+    r#"{
+        "channel": "heartbeats", "client_id": "", "timestamp": "2023-11-14T22:13:22Z",
+        "sequence_num": 2,
+        "events": [{ "current_time": "2023-11-14 22:13:22.000000000 +0000 UTC", "heartbeat_counter": 1 }]
+    }"#,
+    r#"{
+        "channel": "user", "client_id": "", "timestamp": "2023-11-14T22:13:25Z", "sequence_num": 3,
+        "events": [{
+            "type": "update",
+            "orders": [{
+                "order_id": "301", "client_order_id": "cidresting", "product_id": "BTC-USD",
+                "order_side": "BUY", "order_type": "Limit", "time_in_force": "GOOD_UNTIL_CANCELLED",
+                "post_only": true, "status": "FILLED", "limit_price": "29000",
+                "cumulative_quantity": "0.2", "leaves_quantity": "0", "avg_price": "29000",
+                "filled_value": "5800", "total_fees": "5.8", "reject_reason": ""
+            }]
+        }]
+    }"#,
+    r#"{
+        "channel": "user", "client_id": "", "timestamp": "2023-11-14T22:13:25.001Z", "sequence_num": 4,
+        "events": [{
+            "type": "update",
+            "orders": [{
+                "order_id": "302", "client_order_id": "cidother", "product_id": "ETH-USD",
+                "order_side": "SELL", "order_type": "Limit", "time_in_force": "GOOD_UNTIL_CANCELLED",
+                "post_only": false, "status": "CANCELLED", "limit_price": "2000",
+                "cumulative_quantity": "0", "leaves_quantity": "1", "avg_price": "0",
+                "filled_value": "0", "total_fees": "0", "reject_reason": ""
+            }]
+        }]
+    }"#,
+    r#"{
+        "channel": "user", "client_id": "", "timestamp": "2023-11-14T22:13:25.002Z", "sequence_num": 5,
+        "events": [{
+            "type": "update",
+            "orders": [{
+                "order_id": "304", "client_order_id": "cidfailed", "product_id": "BTC-USD",
+                "order_side": "BUY", "order_type": "Market", "time_in_force": "IMMEDIATE_OR_CANCEL",
+                "post_only": false, "status": "FAILED", "limit_price": "",
+                "cumulative_quantity": "0", "leaves_quantity": "0.1", "avg_price": "",
+                "filled_value": "", "total_fees": "", "reject_reason": "INSUFFICIENT_FUNDS"
+            }]
+        }]
+    }"#,
+];
+
+async fn next_request(websocket: &mut WebSocketStream<TcpStream>) -> Option<serde_json::Value> {
+    loop {
+        match websocket.next().await?.ok()? {
+            Message::Text(text) => break Some(serde_json::from_str(text.as_str()).unwrap()),
+            Message::Close(_) => break None,
+            _ => continue,
+        }
+    }
+}
+
+async fn send(websocket: &mut WebSocketStream<TcpStream>, message: &str) {
+    websocket.send(Message::text(message)).await.unwrap();
+}
+
+fn config(base_url_rest: String, base_url_ws: String) -> CoinbaseConfig {
+    CoinbaseConfig {
+        base_url_rest,
+        base_url_ws,
+        ..CoinbaseConfig::new(API_KEY.to_string(), secret())
+    }
+}
+
+fn btc_usd() -> InstrumentNameExchange {
+    InstrumentNameExchange::new("BTC-USD")
+}
+
+fn time(epoch_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(epoch_ms).unwrap()
+}
+
+#[tokio::test]
+async fn test_coinbase_account_snapshot_and_trades() {
+    let (server, base_url) = MockCoinbaseHttp::start().await;
+    let client = Coinbase::new(config(base_url, String::new()));
+
+    let snapshot = client
+        .account_snapshot(
+            &[AssetNameExchange::new("USD"), AssetNameExchange::new("BTC")],
+            &[btc_usd()],
+        )
+        .await
+        .unwrap();
+
+    // Accounts are fetched across pages, with held funds included in the total balance
+    assert_eq!(snapshot.exchange, ExchangeId::Coinbase);
+    let accounts = server.requests("/api/v3/brokerage/accounts");
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[1].query, "limit=250&cursor=page2");
+    assert_eq!(snapshot.balances.len(), 2);
+    assert_eq!(
+        snapshot.balances[0].balance,
+        Balance::new(dec!(10000), dec!(9000))
+    );
+    assert_eq!(
+        snapshot.balances[1].balance,
+        Balance::new(dec!(0.5), dec!(0.5))
+    );
+
+    // Orders with unsupported configurations are ignored
+    assert_eq!(
+        server.requests("/api/v3/brokerage/orders/historical/batch")[0].query,
+        "order_status=OPEN&limit=250"
+    );
+    assert_eq!(snapshot.instruments.len(), 1);
+    assert_eq!(snapshot.instruments[0].orders.len(), 1);
+    let order = &snapshot.instruments[0].orders[0];
+    assert_eq!(order.key.cid, ClientOrderId::new("cidresting"));
+    assert_eq!(order.side, Side::Buy);
+    assert_eq!(order.price, dec!(29000));
+    assert_eq!(order.quantity, dec!(0.2));
+    assert_eq!(
+        order.time_in_force,
+        TimeInForce::GoodUntilCancelled { post_only: true }
+    );
+    assert_eq!(
+        order.state,
+        OrderState::active(Open::new(
+            OrderId::new("301"),
+            time(1700000000500),
+            dec!(0.05)
+        ))
+    );
+
+    // Fills sized in the quote asset are converted to the base asset
+    let trades = client.fetch_trades(time(1700000000000)).await.unwrap();
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[0].order_id, OrderId::new("301"));
+    assert_eq!(trades[0].fees.fees, dec!(1.45));
+    assert_eq!(trades[1].quantity, dec!(0.1));
+    let fills = server.requests("/api/v3/brokerage/orders/historical/fills");
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0].method, "GET");
+    assert_eq!(
+        fills[0].query,
+        "start_sequence_timestamp=2023-11-14T22%3A13%3A20.000Z&limit=250"
+    );
+}
+
+#[tokio::test]
+async fn test_coinbase_authentication_error() {
+    let (_server, base_url) = MockCoinbaseHttp::start().await;
+    let other_key = SecretKey::from_slice(&[9u8; 32])
+        .unwrap()
+        .to_sec1_pem(LineEnding::LF)
+        .unwrap()
+        .to_string();
+    let client = Coinbase::new(CoinbaseConfig {
+        base_url_rest: base_url,
+        ..CoinbaseConfig::new(API_KEY.to_string(), other_key)
+    });
+
+    let error = client.fetch_balances().await.unwrap_err();
+    assert_eq!(
+        error,
+        ClientError::Connectivity(ConnectivityError::Socket(
+            "Coinbase authentication failed: invalid signature".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_coinbase_open_and_cancel_orders() {
+    let (server, base_url) = MockCoinbaseHttp::start().await;
+    let client = Coinbase::new(config(base_url, String::new()));
+    let instrument = btc_usd();
+
+    let key = |cid: &str| {
+        OrderKey::new(
+            ExchangeId::Coinbase,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        )
+    };
+    let request_open = |cid: &str, time_in_force| OrderRequestOpen {
+        key: key(cid),
+        state: RequestOpen::new(
+            Side::Buy,
+            dec!(29000),
+            dec!(0.1),
+            OrderKind::Limit,
+            time_in_force,
+            OrderFlags::default(),
+        ),
+    };
+
+    let opened = client
+        .open_order(request_open(
+            "cidopen",
+            TimeInForce::GoodUntilCancelled { post_only: true },
+        ))
+        .await;
+    let Ok(open) = opened.state else {
+        panic!("expected Open, got: {:?}", opened.state);
+    };
+    assert_eq!(open.id, OrderId::new("ord-cidopen"));
+    assert_eq!(open.filled_quantity, dec!(0));
+
+    let body = serde_json::from_str::<serde_json::Value>(
+        &server.requests("/api/v3/brokerage/orders")[0].body,
+    )
+    .unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "client_order_id": "cidopen",
+            "product_id": "BTC-USD",
+            "side": "BUY",
+            "order_configuration": {
+                "limit_limit_gtc": { "base_size": "0.1", "limit_price": "29000", "post_only": true }
+            }
+        })
+    );
+
+    let rejected = client
+        .open_order(request_open("cidrejected", TimeInForce::FillOrKill))
+        .await;
+    assert_eq!(
+        rejected.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Coinbase INSUFFICIENT_FUND: Insufficient balance in source account".to_string()
+        )))
+    );
+
+    // Unsupported TimeInForce is rejected without a request
+    let unsupported = client
+        .open_order(request_open("cideod", TimeInForce::GoodUntilEndOfDay))
+        .await;
+    assert!(matches!(
+        unsupported.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(_)))
+    ));
+
+    // Reducing OrderFlags are rejected without a request, rather than silently dropped
+    for flags in [OrderFlags::reduce_only(), OrderFlags::close_position()] {
+        let mut request = request_open("cidreduce", TimeInForce::ImmediateOrCancel);
+        request.state.flags = flags;
+        let reducing = client.open_order(request).await;
+        assert_eq!(
+            reducing.state,
+            Err(OrderError::Rejected(ApiError::OrderRejected(
+                "Coinbase spot does not support reduce only or close position orders".to_string()
+            )))
+        );
+    }
+    assert_eq!(server.requests("/api/v3/brokerage/orders").len(), 2);
+
+    let cancelled = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cidopen"),
+            state: RequestCancel::new(Some(OrderId::new("ord-cidopen"))),
+        })
+        .await;
+    let Ok(cancelled) = cancelled.state else {
+        panic!("expected Cancelled, got: {:?}", cancelled.state);
+    };
+    assert_eq!(cancelled.id, OrderId::new("ord-cidopen"));
+
+    let unknown = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cidunknown"),
+            state: RequestCancel::new(Some(OrderId::new("ord-unknown"))),
+        })
+        .await;
+    assert_eq!(
+        unknown.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Coinbase cancel failed: UNKNOWN_CANCEL_ORDER".to_string()
+        )))
+    );
+
+    // Coinbase cannot cancel by ClientOrderId alone
+    let missing_id = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cidopen"),
+            state: RequestCancel::new(None),
+        })
+        .await;
+    assert_eq!(
+        missing_id.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Coinbase requires the exchange OrderId to cancel an order".to_string()
+        )))
+    );
+    assert_eq!(
+        server
+            .requests("/api/v3/brokerage/orders/batch_cancel")
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_coinbase_account_stream() {
+    let base_url_ws = start_user_ws().await;
+    let client = Coinbase::new(config(String::new(), base_url_ws));
+
+    let mut stream = client.account_stream(&[], &[btc_usd()]).await.unwrap();
+
+    // Trades are derived from the change in cumulative fill state since the snapshot
+    let event = stream.next().await.unwrap();
+    assert_eq!(event.exchange, ExchangeId::Coinbase);
+    let AccountEventKind::Trade(trade) = event.kind else {
+        panic!("expected Trade");
+    };
+    assert_eq!(trade.order_id, OrderId::new("301"));
+    assert_eq!(trade.price, dec!(29000));
+    assert_eq!(trade.quantity, dec!(0.15));
+    assert_eq!(trade.fees.fees, dec!(4.35));
+    assert_eq!(trade.time_exchange, time(1700000005000));
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cidresting"));
+    assert_eq!(order.0.state, OrderState::fully_filled());
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cidother"));
+    assert_eq!(
+        order.0.state,
+        OrderState::inactive(Cancelled::new(OrderId::new("302"), time(1700000005001)))
+    );
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cidfailed"));
+    assert_eq!(
+        order.0.state,
+        OrderState::inactive(OrderError::Rejected(ApiError::OrderRejected(
+            "Coinbase order failed: INSUFFICIENT_FUNDS".to_string()
+        )))
+    );
+}
+
+#[test]
+fn test_coinbase_config_validate() {
+    // Escaped newlines are accepted
+    let valid = CoinbaseConfig::new(API_KEY.to_string(), secret().replace('\n', "\\n"));
+    assert_eq!(valid.validate(), Ok(()));
+
+    let invalid = CoinbaseConfig::new(API_KEY, "not a private key");
+    assert!(
+        invalid
+            .validate()
+            .unwrap_err()
+            .starts_with("Coinbase API secret is not a valid EC private key")
+    );
+}
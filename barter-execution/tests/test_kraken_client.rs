@@ -0,0 +1,672 @@
+use barter_execution::{
+    AccountEventKind,
+    balance::Balance,
+    client::{
+        ExecutionClient,
+        kraken::{Kraken, KrakenConfig},
+    },
+    error::{ApiError, ClientError, ConnectivityError, OrderError},
+    order::{
+        OrderFlags, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        request::{
+            OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, RequestAmend, RequestCancel,
+            RequestOpen,
+        },
+        state::{Cancelled, Open, OrderState},
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::http::private::encoder::{Base64Encoder, Encoder};
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use rust_decimal_macros::dec;
+use sha2::{Digest, Sha256, Sha512};
+use std::sync::{Arc, Mutex};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tokio_tungstenite::{WebSocketStream, tungstenite::Message};
+
+const API_KEY: &str = "test-api-key";
+const SECRET: &[u8] = b"test-secret";
+const TOKEN: &str = "test-ws-token";
+const TOKEN_PATH: &str = "/0/private/GetWebSocketsToken";
+
+/// Minimal Kraken Http server fixture that validates `GetWebSocketsToken` request signatures.
+#[derive(Debug, Clone, Default)]
+struct MockKrakenHttp {
+    nonces: Arc<Mutex<Vec<u64>>>,
+}
+
+impl MockKrakenHttp {
+    async fn start() -> (Self, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = Self::default();
+
+        let handler = server.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handler.clone().handle(stream));
+            }
+        });
+
+        (server, base_url)
+    }
+
+    async fn handle(self, mut stream: TcpStream) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let head_len = loop {
+            if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+                break position + 4;
+            }
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        };
+
+        let head = String::from_utf8_lossy(&buffer[..head_len]).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap().split(' ');
+        let method = request_line.next().unwrap().to_string();
+        let path = request_line.next().unwrap().to_string();
+        let headers = lines
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect::<Vec<_>>();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        };
+
+        let content_length = header("content-length")
+            .parse::<usize>()
+            .unwrap_or_default();
+        while buffer.len() < head_len + content_length {
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        }
+        let body = String::from_utf8_lossy(&buffer[head_len..]).to_string();
+        let nonce = serde_json::from_str::<serde_json::Value>(&body).unwrap()["nonce"]
+            .as_u64()
+            .unwrap();
+        self.nonces.lock().unwrap().push(nonce);
+
+        let body = if method != "POST" || path != TOKEN_PATH {
+            r#"{"error":["EGeneral:Unknown method"]}"#
+        } else if header("api-key") != API_KEY {
+            r#"{"error":["EAPI:Invalid key"]}"#
+        } else if header("api-sign") != sign(&path, nonce, &body) {
+            r#"{"error":["EAPI:Invalid signature"]}"#
+        } else {
+            r#"{"error":[],"result":{"token":"test-ws-token","expires":900}}"#
+        };
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.ok();
+    }
+}
+
+fn sign(path: &str, nonce: u64, body: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(nonce.to_string())
+        .chain_update(body)
+        .finalize();
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(SECRET).unwrap();
+    mac.update(path.as_bytes());
+    mac.update(&digest);
+    Base64Encoder.encode(mac.finalize().into_bytes())
+}
+
+/// Kraken WebSocket v2 private server fixture, accepting any number of connections.
+///
+/// Every request must include the token issued by the [`MockKrakenHttp`] fixture. Order entry
+/// requests are responded to according to their `cl_ord_id`:
+/// - `cidbatch*`: held until two have been received, then responded to in reverse order.
+/// - `cidrejected`: rejected with an insufficient funds error.
+/// - `cidunknown`: cancel rejected as an unknown order.
+/// - `cidreplacefails`: cancel accepted, but open rejected with an insufficient funds error.
+///
+/// Subscriptions are acknowledged, followed by any requested snapshot. Subscribing to the
+/// `balances` channel without a snapshot is followed by `executions` & `balances` updates.
+async fn start_private_ws() -> (String, Arc<Mutex<usize>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("ws://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(Mutex::new(0));
+
+    let counter = Arc::clone(&connections);
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            *counter.lock().unwrap() += 1;
+            tokio::spawn(handle_private_ws(
+                tokio_tungstenite::accept_async(stream).await.unwrap(),
+            ));
+        }
+    });
+
+    (base_url, connections)
+}
+
+async fn handle_private_ws(mut websocket: WebSocketStream<TcpStream>) {
+    let mut held = Vec::new();
+    while let Some(request) = next_request(&mut websocket).await {
+        let params = &request["params"];
+        assert_eq!(params["token"], TOKEN);
+
+        match request["method"].as_str().unwrap() {
+            "subscribe" => {
+                send(&mut websocket, &response(&request, serde_json::json!({}))).await;
+                match params["channel"].as_str().unwrap() {
+                    "executions" if params["snap_orders"] == true => {
+                        send(&mut websocket, EXECUTIONS_SNAPSHOT_ORDERS).await;
+                    }
+                    "executions" if params["snap_trades"] == true => {
+                        send(&mut websocket, EXECUTIONS_SNAPSHOT_TRADES).await;
+                    }
+                    "balances" if params["snapshot"] == true => {
+                        send(&mut websocket, BALANCES_SNAPSHOT).await;
+                    }
+                    "balances" => {
+                        for update in CHANNEL_UPDATES {
+                            send(&mut websocket, update).await;
+                        }
+                    }
+                    channel => panic!("unexpected channel: {channel}"),
+                }
+            }
+            "add_order" | "cancel_order" => {
+                let cid = match &params["cl_ord_id"] {
+                    serde_json::Value::Array(cids) => cids[0].as_str().unwrap().to_string(),
+                    cid => cid.as_str().unwrap().to_string(),
+                };
+                if cid.starts_with("cidbatch") {
+                    held.push(request);
+                    if held.len() == 2 {
+                        while let Some(request) = held.pop() {
+                            send(&mut websocket, &order_response(&request)).await;
+                        }
+                    }
+                } else {
+                    send(&mut websocket, &order_response(&request)).await;
+                }
+            }
+            method => panic!("unexpected method: {method}"),
+        }
+    }
+}
+
+fn response(request: &serde_json::Value, result: serde_json::Value) -> String {
+    serde_json::json!({
+        "method": request["method"],
+        "req_id": request["req_id"],
+        "result": result,
+        "success": true,
+        "time_in": "2023-11-14T22:13:21.999000Z",
+        "time_out": "2023-11-14T22:13:22Z"
+    })
+    .to_string()
+}
+
+fn order_response(request: &serde_json::Value) -> String {
+    let params = &request["params"];
+    let cid = match &params["cl_ord_id"] {
+        serde_json::Value::Array(cids) => cids[0].as_str().unwrap(),
+        cid => cid.as_str().unwrap(),
+    };
+
+    let error = match (request["method"].as_str().unwrap(), cid) {
+        (_, "cidrejected") | ("add_order", "cidreplacefails") => "EOrder:Insufficient funds",
+        (_, "cidunknown") => "EOrder:Unknown order",
+        _ => {
+            return response(
+                request,
+                serde_json::json!({ "order_id": format!("ord-{cid}") }),
+            );
+        }
+    };
+
+    serde_json::json!({
+        "method": request["method"],
+        "req_id": request["req_id"],
+        "error": error,
+        "success": false,
+        "time_in": "2023-11-14T22:13:21.999000Z",
+        "time_out": "2023-11-14T22:13:22Z"
+    })
+    .to_string()
+}
+
+const EXECUTIONS_SNAPSHOT_ORDERS: &str = r#"{
+    "channel": "executions",
+    "type": "snapshot",
+    "data": [
+        {
+            "order_id": "OABC-1", "cl_ord_id": "cidresting", "exec_type": "new",
+            "symbol": "BTC/USD", "side": "buy", "order_type": "limit", "time_in_force": "GTC",
+            "post_only": true, "order_qty": 0.2, "limit_price": 29000.0, "cum_qty": 0.05,
+            "order_status": "partially_filled", "timestamp": "2023-11-14T22:13:20.500Z"
+        },
+        {
+            "order_id": "OABC-2", "cl_ord_id": "cidother", "exec_type": "new",
+            "symbol": "ETH/USD", "side": "sell", "order_type": "limit", "time_in_force": "GTC",
+            "order_qty": 1.0, "limit_price": 2000.0, "cum_qty": 0,
+            "order_status": "new", "timestamp": "2023-11-14T22:13:20.600Z"
+        }
+    ],
+    "sequence": 1
+}"#;
+
+const EXECUTIONS_SNAPSHOT_TRADES: &str = r#"{
+    "channel": "executions",
+    "type": "snapshot",
+    "data": [
+        {
+            "order_id": "OABC-1", "cl_ord_id": "cidresting", "exec_id": "TXYZ-1",
+            "exec_type": "trade", "symbol": "BTC/USD", "side": "buy", "order_type": "limit",
+            "order_qty": 0.2, "limit_price": 29000.0, "last_qty": 0.05, "last_price": 29000.0,
+            "cum_qty": 0.05, "order_status": "partially_filled",
+            "fees": [{ "asset": "USD", "qty": 2.9 }],
+            "timestamp": "2023-11-14T22:13:20.700Z"
+        },
+        {
+            "order_id": "OABC-0", "exec_id": "TXYZ-0", "exec_type": "trade",
+            "symbol": "BTC/USD", "side": "sell", "order_type": "market", "order_qty": 0.1,
+            "last_qty": 0.1, "last_price": 28000.0, "cum_qty": 0.1, "order_status": "filled",
+            "fees": [], "timestamp": "2023-11-14T22:13:10Z"
+        }
+    ],
+    "sequence": 1
+}"#;
+
+const BALANCES_SNAPSHOT: &str = r#"{
+    "channel": "balances",
+    "type": "snapshot",
+    "data": [
+        { "asset": "USD", "asset_class": "currency", "balance": 10000.0 },
+        { "asset": "BTC", "asset_class": "currency", "balance": 0.5 }
+    ],
+    "sequence": 1
+}"#;
+
+const CHANNEL_UPDATES: [&str; 4] = [
+    r#"{"channel":"heartbeat"}"#,
+    r#"{
+        "channel": "executions",
+        "type": "update",
+        "data": [{
+            "order_id": "OABC-1", "exec_id": "TXYZ-2", "exec_type": "trade",
+            "last_qty": 0.15, "last_price": 29000.0, "cum_qty": 0.2,
+            "order_status": "filled", "fees": [{ "asset": "USD", "qty": 8.7 }],
+            "timestamp": "2023-11-14T22:13:25Z"
+        }],
+        "sequence": 2
+    }"#,
+    r#"{
+        "channel": "executions",
+        "type": "update",
+        "data": [{
+            "order_id": "OABC-2", "exec_type": "canceled", "order_status": "canceled",
+            "timestamp": "2023-11-14T22:13:25.001Z"
+        }],
+        "sequence": 3
+    }"#,
+    r#"{
+        "channel": "balances",
+        "type": "update",
+        "data": [{
+            "asset": "USD", "asset_class": "currency", "amount": -4350.0,
+            "balance": 5650.0, "timestamp": "2023-11-14T22:13:25.002Z"
+        }],
+        "sequence": 4
+    }"#,
+];
+
+async fn next_request(websocket: &mut WebSocketStream<TcpStream>) -> Option<serde_json::Value> {
+    loop {
+        match websocket.next().await?.ok()? {
+            Message::Text(text) => break Some(serde_json::from_str(text.as_str()).unwrap()),
+            Message::Close(_) => break None,
+            _ => continue,
+        }
+    }
+}
+
+async fn send(websocket: &mut WebSocketStream<TcpStream>, message: &str) {
+    websocket.send(Message::text(message)).await.unwrap();
+}
+
+fn config(base_url_rest: String, base_url_ws: String) -> KrakenConfig {
+    KrakenConfig {
+        base_url_rest,
+        base_url_ws,
+        ..KrakenConfig::new(
+            API_KEY.to_string(),
+            base64::engine::general_purpose::STANDARD.encode(SECRET),
+        )
+    }
+}
+
+fn btc_usd() -> InstrumentNameExchange {
+    InstrumentNameExchange::new("BTC/USD")
+}
+
+fn time(epoch_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(epoch_ms).unwrap()
+}
+
+#[tokio::test]
+async fn test_kraken_account_snapshot_and_trades() {
+    let (server, base_url_rest) = MockKrakenHttp::start().await;
+    let (base_url_ws, _) = start_private_ws().await;
+    let client = Kraken::new(config(base_url_rest, base_url_ws));
+
+    let snapshot = client
+        .account_snapshot(
+            &[AssetNameExchange::new("USD"), AssetNameExchange::new("BTC")],
+            &[btc_usd()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot.exchange, ExchangeId::Kraken);
+    assert_eq!(snapshot.balances.len(), 2);
+    assert_eq!(
+        snapshot.balances[0].balance,
+        Balance::new(dec!(10000), dec!(10000))
+    );
+
+    assert_eq!(snapshot.instruments.len(), 1);
+    let order = &snapshot.instruments[0].orders[0];
+    assert_eq!(order.key.cid, ClientOrderId::new("cidresting"));
+    assert_eq!(order.side, Side::Buy);
+    assert_eq!(order.price, dec!(29000));
+    assert_eq!(
+        order.time_in_force,
+        TimeInForce::GoodUntilCancelled { post_only: true }
+    );
+    assert_eq!(
+        order.state,
+        OrderState::active(Open::new(
+            OrderId::new("OABC-1"),
+            time(1700000000500),
+            dec!(0.05)
+        ))
+    );
+
+    // Trades before time_since are filtered, and fees paid in the quote asset are unchanged
+    let trades = client.fetch_trades(time(1700000000000)).await.unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].order_id, OrderId::new("OABC-1"));
+    assert_eq!(trades[0].quantity, dec!(0.05));
+    assert_eq!(trades[0].fees.fees, dec!(2.9));
+
+    // Each private connection fetches a new token using a strictly increasing nonce
+    let nonces = server.nonces.lock().unwrap().clone();
+    assert_eq!(nonces.len(), 2);
+    assert!(nonces[0] < nonces[1]);
+}
+
+#[tokio::test]
+async fn test_kraken_authentication_error() {
+    let (_server, base_url_rest) = MockKrakenHttp::start().await;
+    let client = Kraken::new(KrakenConfig {
+        base_url_rest,
+        ..KrakenConfig::new(
+            API_KEY.to_string(),
+            base64::engine::general_purpose::STANDARD.encode(b"wrong-secret"),
+        )
+    });
+
+    let error = client.fetch_balances().await.unwrap_err();
+    assert_eq!(
+        error,
+        ClientError::Connectivity(ConnectivityError::Socket(
+            "Kraken authentication failed: EAPI:Invalid signature".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_kraken_websocket_order_entry() {
+    let (_server, base_url_rest) = MockKrakenHttp::start().await;
+    let (base_url_ws, connections) = start_private_ws().await;
+    let client = Kraken::new(config(base_url_rest, base_url_ws));
+    let instrument = btc_usd();
+
+    let key = |cid: &str| {
+        OrderKey::new(
+            ExchangeId::Kraken,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        )
+    };
+    let request_open = |cid: &str, time_in_force| OrderRequestOpen {
+        key: key(cid),
+        state: RequestOpen::new(
+            Side::Buy,
+            dec!(29000),
+            dec!(0.1),
+            OrderKind::Limit,
+            time_in_force,
+            OrderFlags::default(),
+        ),
+    };
+    let gtc = TimeInForce::GoodUntilCancelled { post_only: false };
+
+    // Responses arriving in reverse order are correlated to their request by req_id
+    let mut opened = client
+        .open_orders([
+            request_open("cidbatch1", gtc),
+            request_open("cidbatch2", gtc),
+        ])
+        .collect::<Vec<_>>()
+        .await;
+    opened.sort_by(|a, b| a.key.cid.cmp(&b.key.cid));
+    assert_eq!(opened.len(), 2);
+    for (order, cid) in opened.iter().zip(["cidbatch1", "cidbatch2"]) {
+        assert_eq!(order.key.cid, ClientOrderId::new(cid));
+        assert_eq!(
+            order.state,
+            Ok(Open::new(
+                OrderId::new(format!("ord-{cid}")),
+                time(1700000002000),
+                dec!(0)
+            ))
+        );
+    }
+
+    let rejected = client.open_order(request_open("cidrejected", gtc)).await;
+    assert_eq!(
+        rejected.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Kraken error: EOrder:Insufficient funds".to_string()
+        )))
+    );
+
+    // Unsupported TimeInForce is rejected without a request
+    let unsupported = client
+        .open_order(request_open("cidfok", TimeInForce::FillOrKill))
+        .await;
+    assert!(matches!(
+        unsupported.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(_)))
+    ));
+
+    // Reducing OrderFlags are rejected without a request, rather than silently dropped
+    for flags in [OrderFlags::reduce_only(), OrderFlags::close_position()] {
+        let mut request = request_open("cidreduce", gtc);
+        request.state.flags = flags;
+        let reducing = client.open_order(request).await;
+        assert_eq!(
+            reducing.state,
+            Err(OrderError::Rejected(ApiError::OrderRejected(
+                "Kraken spot does not support reduce only or close position orders".to_string()
+            )))
+        );
+    }
+
+    let cancelled = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cidresting"),
+            state: RequestCancel::new(None),
+        })
+        .await;
+    assert_eq!(
+        cancelled.state,
+        Ok(Cancelled::new(
+            OrderId::new("ord-cidresting"),
+            time(1700000002000)
+        ))
+    );
+
+    let unknown = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cidunknown"),
+            state: RequestCancel::new(None),
+        })
+        .await;
+    assert_eq!(
+        unknown.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Kraken error: EOrder:Unknown order".to_string()
+        )))
+    );
+
+    // Amends default to a cancel-replace of the remaining quantity
+    let request_amend = |cid: &str| OrderRequestAmend {
+        key: key(cid),
+        state: RequestAmend::new(
+            None,
+            Side::Buy,
+            dec!(29100),
+            dec!(0.3),
+            OrderKind::Limit,
+            gtc,
+            dec!(0.05),
+        ),
+    };
+    let amended = client.amend_order(request_amend("cidresting")).await;
+    assert_eq!((amended.price, amended.quantity), (dec!(29100), dec!(0.3)));
+    assert_eq!(
+        amended.state,
+        Ok(Open::new(
+            OrderId::new("ord-cidresting"),
+            time(1700000002000),
+            dec!(0.05)
+        ))
+    );
+
+    // Failed cancel leaves the original order untouched
+    let unknown = client.amend_order(request_amend("cidunknown")).await;
+    assert_eq!(
+        unknown.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "Kraken error: EOrder:Unknown order".to_string()
+        )))
+    );
+
+    // Failed replacement open after a successful cancel reports the original as cancelled
+    let replace_failed = client.amend_order(request_amend("cidreplacefails")).await;
+    assert_eq!(
+        replace_failed.state,
+        Err(OrderError::Rejected(ApiError::OrderReplaceFailed(
+            "order rejected: order rejected: Kraken error: EOrder:Insufficient funds".to_string()
+        )))
+    );
+
+    // Amended quantity not exceeding the filled quantity is rejected without cancelling
+    let mut request = request_amend("cidresting");
+    request.state.quantity = dec!(0.05);
+    let invalid = client.amend_order(request).await;
+    assert_eq!(
+        invalid.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "cannot amend quantity to 0.05 with filled quantity: 0.05".to_string()
+        )))
+    );
+
+    // All order entry requests share a single connection
+    assert_eq!(*connections.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_kraken_account_stream() {
+    let (_server, base_url_rest) = MockKrakenHttp::start().await;
+    let (base_url_ws, _) = start_private_ws().await;
+    let client = Kraken::new(config(base_url_rest, base_url_ws));
+
+    let mut stream = client.account_stream(&[], &[btc_usd()]).await.unwrap();
+
+    // Partial execution updates are merged with the cached snapshot order
+    let event = stream.next().await.unwrap();
+    assert_eq!(event.exchange, ExchangeId::Kraken);
+    let AccountEventKind::Trade(trade) = event.kind else {
+        panic!("expected Trade");
+    };
+    assert_eq!(trade.order_id, OrderId::new("OABC-1"));
+    assert_eq!(trade.instrument, btc_usd());
+    assert_eq!(trade.side, Side::Buy);
+    assert_eq!(trade.price, dec!(29000));
+    assert_eq!(trade.quantity, dec!(0.15));
+    assert_eq!(trade.fees.fees, dec!(8.7));
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cidresting"));
+    assert_eq!(order.0.state, OrderState::fully_filled());
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cidother"));
+    assert_eq!(
+        order.0.state,
+        OrderState::inactive(Cancelled::new(OrderId::new("OABC-2"), time(1700000005001)))
+    );
+
+    let AccountEventKind::BalanceSnapshot(balance) = stream.next().await.unwrap().kind else {
+        panic!("expected BalanceSnapshot");
+    };
+    assert_eq!(balance.0.asset, AssetNameExchange::new("USD"));
+    assert_eq!(balance.0.balance, Balance::new(dec!(5650), dec!(5650)));
+    assert_eq!(balance.0.time_exchange, time(1700000005002));
+}
+
+#[test]
+fn test_kraken_config_validate() {
+    let valid = config(String::new(), String::new());
+    assert_eq!(valid.validate(), Ok(()));
+
+    let invalid = KrakenConfig::new(API_KEY, "not base64!");
+    assert!(
+        invalid
+            .validate()
+            .unwrap_err()
+            .starts_with("Kraken API secret is not valid base64")
+    );
+}
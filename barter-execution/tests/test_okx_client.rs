@@ -0,0 +1,593 @@
+use barter_execution::{
+    AccountEventKind,
+    balance::Balance,
+    client::{
+        ExecutionClient,
+        okx::{Okx, OkxConfig},
+    },
+    error::{ApiError, ClientError, ConnectivityError, OrderError},
+    order::{
+        OrderFlags, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, OrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, RequestCancel, RequestOpen},
+        state::{Cancelled, Open, OrderState},
+    },
+};
+use barter_instrument::{
+    Side, asset::name::AssetNameExchange, exchange::ExchangeId,
+    instrument::name::InstrumentNameExchange,
+};
+use barter_integration::protocol::http::private::encoder::{Base64Encoder, Encoder};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use rust_decimal_macros::dec;
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tokio_tungstenite::{WebSocketStream, tungstenite::Message};
+
+const API_KEY: &str = "test-api-key";
+const SECRET: &str = "test-secret";
+const PASSPHRASE: &str = "test-passphrase";
+
+#[derive(Debug, Clone)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    query: String,
+}
+
+/// Minimal OKX V5 Http server fixture that validates request signatures and responds with
+/// canned payloads.
+#[derive(Debug, Clone, Default)]
+struct MockOkxHttp {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockOkxHttp {
+    async fn start() -> (Self, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = Self::default();
+
+        let handler = server.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(handler.clone().handle(stream));
+            }
+        });
+
+        (server, base_url)
+    }
+
+    fn requests(&self, path: &str) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|request| request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    async fn handle(self, mut stream: TcpStream) {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+            let bytes_read = stream.read(&mut chunk).await.unwrap();
+            if bytes_read == 0 {
+                return;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+        }
+
+        let head = String::from_utf8_lossy(&buffer).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap().split(' ');
+        let method = request_line.next().unwrap().to_string();
+        let target = request_line.next().unwrap().to_string();
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), query.to_string()),
+            None => (target.clone(), String::new()),
+        };
+        let headers = lines
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+            .collect::<Vec<_>>();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        };
+
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            query,
+        });
+
+        let expected_sign = sign(&format!(
+            "{}{method}{target}",
+            header("ok-access-timestamp")
+        ));
+        let body = if header("ok-access-key") != API_KEY {
+            r#"{"code":"50111","msg":"Invalid OK-ACCESS-KEY","data":[]}"#.to_string()
+        } else if header("ok-access-passphrase") != PASSPHRASE {
+            r#"{"code":"50105","msg":"Invalid OK-ACCESS-PASSPHRASE","data":[]}"#.to_string()
+        } else if header("ok-access-sign") != expected_sign {
+            r#"{"code":"50113","msg":"Invalid Sign","data":[]}"#.to_string()
+        } else {
+            route_signed(&path)
+        };
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.shutdown().await.ok();
+    }
+}
+
+fn sign(payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+    mac.update(payload.as_bytes());
+    Base64Encoder.encode(mac.finalize().into_bytes())
+}
+
+fn route_signed(path: &str) -> String {
+    let data = match path {
+        "/api/v5/account/balance" => {
+            r#"[{
+                "uTime": "1700000000000",
+                "totalEq": "12000",
+                "details": [
+                    { "ccy": "USDT", "cashBal": "10000", "availBal": "9000", "frozenBal": "1000", "uTime": "1700000000100" },
+                    { "ccy": "BTC", "cashBal": "0.5", "availBal": "", "frozenBal": "0", "uTime": "1700000000200" },
+                    { "ccy": "ETH", "cashBal": "2", "availBal": "2", "frozenBal": "0", "uTime": "1700000000300" }
+                ]
+            }]"#
+        }
+        "/api/v5/trade/orders-pending" => {
+            r#"[
+                {
+                    "instType": "SPOT", "instId": "BTC-USDT", "ordId": "301", "clOrdId": "cidresting",
+                    "px": "29000", "sz": "0.2", "ordType": "post_only", "side": "buy",
+                    "state": "partially_filled", "accFillSz": "0.05", "fillPx": "", "tradeId": "",
+                    "fillSz": "", "fillFee": "", "fillFeeCcy": "", "uTime": "1700000000500"
+                },
+                {
+                    "instType": "SPOT", "instId": "ETH-USDT", "ordId": "302", "clOrdId": "cidother",
+                    "px": "2000", "sz": "1", "ordType": "limit", "side": "sell",
+                    "state": "live", "accFillSz": "0", "uTime": "1700000000600"
+                }
+            ]"#
+        }
+        "/api/v5/trade/fills" => {
+            r#"[{
+                "instId": "BTC-USDT", "tradeId": "401", "ordId": "301", "billId": "501",
+                "fillPx": "29000", "fillSz": "0.05", "side": "buy", "fee": "-0.0001",
+                "feeCcy": "BTC", "ts": "1700000000700"
+            }]"#
+        }
+        _ => return r#"{"code":"50000","msg":"not found","data":[]}"#.to_string(),
+    };
+
+    format!(r#"{{"code":"0","msg":"","data":{data}}}"#)
+}
+
+/// OKX V5 private WebSocket server fixture, accepting any number of connections.
+///
+/// Each connection must `login` with a valid signature. Order entry requests are responded to
+/// according to their `clOrdId`:
+/// - `cidbatch*`: held until two have been received, then responded to in reverse order.
+/// - `cidrejected`: rejected with an insufficient balance `sCode`.
+/// - `cidcancelled`: cancel rejected as already cancelled.
+/// - `ciddrop`: connection is closed without a response.
+///
+/// Subscriptions are acknowledged, followed by `orders` & `account` channel pushes.
+async fn start_private_ws() -> (String, Arc<Mutex<usize>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("ws://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(Mutex::new(0));
+
+    let counter = Arc::clone(&connections);
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            *counter.lock().unwrap() += 1;
+            tokio::spawn(handle_private_ws(
+                tokio_tungstenite::accept_async(stream).await.unwrap(),
+            ));
+        }
+    });
+
+    (base_url, connections)
+}
+
+async fn handle_private_ws(mut websocket: WebSocketStream<TcpStream>) {
+    let login = next_request(&mut websocket).await.unwrap();
+    assert_eq!(login["op"], "login");
+    let args = &login["args"][0];
+    assert_eq!(args["apiKey"], API_KEY);
+    assert_eq!(args["passphrase"], PASSPHRASE);
+    let timestamp = args["timestamp"].as_str().unwrap();
+    assert_eq!(
+        args["sign"].as_str().unwrap(),
+        sign(&format!("{timestamp}GET/users/self/verify"))
+    );
+    send(
+        &mut websocket,
+        r#"{"event":"login","code":"0","msg":"","connId":"conn"}"#,
+    )
+    .await;
+
+    let mut held = Vec::new();
+    while let Some(request) = next_request(&mut websocket).await {
+        match request["op"].as_str().unwrap() {
+            "subscribe" => {
+                send(&mut websocket, r#"{"event":"subscribe","arg":{"channel":"orders","instType":"ANY"},"connId":"conn"}"#).await;
+                send(
+                    &mut websocket,
+                    r#"{"event":"subscribe","arg":{"channel":"account"},"connId":"conn"}"#,
+                )
+                .await;
+                for push in CHANNEL_PUSHES {
+                    send(&mut websocket, push).await;
+                }
+            }
+            "order" | "cancel-order" => {
+                let cid = request["args"][0]["clOrdId"].as_str().unwrap().to_string();
+                match cid.as_str() {
+                    "ciddrop" => return,
+                    cid if cid.starts_with("cidbatch") => {
+                        held.push(request);
+                        if held.len() == 2 {
+                            while let Some(request) = held.pop() {
+                                send(&mut websocket, &op_response(&request)).await;
+                            }
+                        }
+                    }
+                    _ => send(&mut websocket, &op_response(&request)).await,
+                }
+            }
+            op => panic!("unexpected op: {op}"),
+        }
+    }
+}
+
+fn op_response(request: &serde_json::Value) -> String {
+    let id = request["id"].as_str().unwrap();
+    let op = request["op"].as_str().unwrap();
+    let cid = request["args"][0]["clOrdId"].as_str().unwrap();
+
+    let (code, msg, s_code, s_msg) = match cid {
+        "cidrejected" => (
+            "1",
+            "Operation failed.",
+            "51008",
+            "Order failed. Insufficient USDT balance in account.",
+        ),
+        "cidcancelled" => (
+            "1",
+            "Operation failed.",
+            "51401",
+            "Cancellation failed as the order has been cancelled.",
+        ),
+        _ => ("0", "", "0", ""),
+    };
+
+    serde_json::json!({
+        "id": id,
+        "op": op,
+        "code": code,
+        "msg": msg,
+        "data": [{
+            "clOrdId": cid,
+            "ordId": format!("ord-{cid}"),
+            "tag": "",
+            "ts": "1700000002000",
+            "sCode": s_code,
+            "sMsg": s_msg
+        }],
+        "inTime": "1700000001999000",
+        "outTime": "1700000002001000"
+    })
+    .to_string()
+}
+
+const CHANNEL_PUSHES: [&str; 3] = [
+    r#"{
+        "arg": { "channel": "orders", "instType": "ANY", "uid": "1" },
+        "data": [{
+            "instType": "SPOT", "instId": "BTC-USDT", "ordId": "301", "clOrdId": "cidresting",
+            "px": "29000", "sz": "0.2", "ordType": "post_only", "side": "buy",
+            "state": "filled", "accFillSz": "0.2", "fillPx": "29000", "tradeId": "402",
+            "fillSz": "0.15", "fillTime": "1700000005000", "fillFee": "-0.0003",
+            "fillFeeCcy": "BTC", "uTime": "1700000005000"
+        }]
+    }"#,
+    r#"{
+        "arg": { "channel": "orders", "instType": "ANY", "uid": "1" },
+        "data": [{
+            "instType": "SPOT", "instId": "BTC-USDT", "ordId": "303", "clOrdId": "cidcancel",
+            "px": "31000", "sz": "0.1", "ordType": "limit", "side": "sell",
+            "state": "canceled", "accFillSz": "0", "fillPx": "", "tradeId": "",
+            "fillSz": "0", "fillFee": "0", "fillFeeCcy": "", "uTime": "1700000005001"
+        }]
+    }"#,
+    r#"{
+        "arg": { "channel": "account", "uid": "1" },
+        "data": [{
+            "uTime": "1700000005002",
+            "details": [
+                { "ccy": "USDT", "cashBal": "5650", "availBal": "5650", "frozenBal": "0", "uTime": "1700000005002" }
+            ]
+        }]
+    }"#,
+];
+
+async fn next_request(websocket: &mut WebSocketStream<TcpStream>) -> Option<serde_json::Value> {
+    loop {
+        match websocket.next().await?.ok()? {
+            Message::Text(text) if text.as_str() == "ping" => send(websocket, "pong").await,
+            Message::Text(text) => break Some(serde_json::from_str(text.as_str()).unwrap()),
+            Message::Close(_) => break None,
+            _ => continue,
+        }
+    }
+}
+
+async fn send(websocket: &mut WebSocketStream<TcpStream>, message: &str) {
+    websocket.send(Message::text(message)).await.unwrap();
+}
+
+fn config(base_url_rest: String, base_url_ws: String) -> OkxConfig {
+    OkxConfig {
+        base_url_rest,
+        base_url_ws,
+        ..OkxConfig::new(API_KEY, SECRET, PASSPHRASE)
+    }
+}
+
+fn btc_usdt() -> InstrumentNameExchange {
+    InstrumentNameExchange::new("BTC-USDT")
+}
+
+fn time(epoch_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(epoch_ms).unwrap()
+}
+
+#[tokio::test]
+async fn test_okx_account_snapshot_and_trades() {
+    let (server, base_url) = MockOkxHttp::start().await;
+    let client = Okx::new(config(base_url, String::new()));
+
+    let snapshot = client
+        .account_snapshot(
+            &[
+                AssetNameExchange::new("USDT"),
+                AssetNameExchange::new("BTC"),
+            ],
+            &[btc_usdt()],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot.exchange, ExchangeId::Okx);
+    assert_eq!(snapshot.balances.len(), 2);
+    assert_eq!(
+        snapshot.balances[0].balance,
+        Balance::new(dec!(10000), dec!(9000))
+    );
+    assert_eq!(snapshot.balances[0].time_exchange, time(1700000000100));
+    assert_eq!(
+        snapshot.balances[1].balance,
+        Balance::new(dec!(0.5), dec!(0))
+    );
+
+    assert_eq!(
+        server.requests("/api/v5/trade/orders-pending")[0].query,
+        "limit=100"
+    );
+    assert_eq!(snapshot.instruments.len(), 1);
+    let order = &snapshot.instruments[0].orders[0];
+    assert_eq!(order.key.cid, ClientOrderId::new("cidresting"));
+    assert_eq!(order.side, Side::Buy);
+    assert_eq!(
+        order.time_in_force,
+        TimeInForce::GoodUntilCancelled { post_only: true }
+    );
+    assert_eq!(
+        order.state,
+        OrderState::active(Open::new(
+            OrderId::new("301"),
+            time(1700000000500),
+            dec!(0.05)
+        ))
+    );
+
+    // Fees charged are reported as negative, and converted from the base asset to the quote
+    let trades = client.fetch_trades(time(1700000000000)).await.unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].order_id, OrderId::new("301"));
+    assert_eq!(trades[0].fees.fees, dec!(2.9));
+    let fills = server.requests("/api/v5/trade/fills");
+    assert_eq!(fills[0].method, "GET");
+    assert_eq!(fills[0].query, "begin=1700000000000&limit=100");
+}
+
+#[tokio::test]
+async fn test_okx_authentication_error() {
+    let (_server, base_url) = MockOkxHttp::start().await;
+    let client = Okx::new(OkxConfig {
+        base_url_rest: base_url,
+        ..OkxConfig::new(API_KEY, SECRET, "wrong-passphrase")
+    });
+
+    let error = client.fetch_balances().await.unwrap_err();
+    assert_eq!(
+        error,
+        ClientError::Connectivity(ConnectivityError::Socket(
+            "OKX authentication failed: Invalid OK-ACCESS-PASSPHRASE".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_okx_websocket_order_entry() {
+    let (base_url_ws, connections) = start_private_ws().await;
+    let client = Okx::new(config(String::new(), base_url_ws));
+    let instrument = btc_usdt();
+
+    let key = |cid: &str| {
+        OrderKey::new(
+            ExchangeId::Okx,
+            &instrument,
+            StrategyId::new("strategy"),
+            ClientOrderId::new(cid),
+        )
+    };
+    let request_open = |cid: &str| OrderRequestOpen {
+        key: key(cid),
+        state: RequestOpen::new(
+            Side::Buy,
+            dec!(29000),
+            dec!(0.1),
+            OrderKind::Limit,
+            TimeInForce::GoodUntilCancelled { post_only: false },
+            OrderFlags::default(),
+        ),
+    };
+
+    // Responses arriving in reverse order are correlated to their request by id
+    let mut opened = client
+        .open_orders([request_open("cidbatch1"), request_open("cidbatch2")])
+        .collect::<Vec<_>>()
+        .await;
+    opened.sort_by(|a, b| a.key.cid.cmp(&b.key.cid));
+    assert_eq!(opened.len(), 2);
+    for (order, cid) in opened.iter().zip(["cidbatch1", "cidbatch2"]) {
+        assert_eq!(order.key.cid, ClientOrderId::new(cid));
+        assert_eq!(
+            order.state,
+            Ok(Open::new(
+                OrderId::new(format!("ord-{cid}")),
+                time(1700000002000),
+                dec!(0)
+            ))
+        );
+    }
+
+    // Order specific sCodes are mapped to ApiErrors
+    let rejected = client.open_order(request_open("cidrejected")).await;
+    assert_eq!(
+        rejected.state,
+        Err(OrderError::Rejected(ApiError::OrderRejected(
+            "OKX code 51008: Order failed. Insufficient USDT balance in account.".to_string()
+        )))
+    );
+
+    // Reducing OrderFlags are rejected in cash trade mode without a request, rather than
+    // silently dropped
+    for flags in [OrderFlags::reduce_only(), OrderFlags::close_position()] {
+        let mut request = request_open("cidreduce");
+        request.state.flags = flags;
+        let reducing = client.open_order(request).await;
+        assert_eq!(
+            reducing.state,
+            Err(OrderError::Rejected(ApiError::OrderRejected(
+                "OKX cash trade mode does not support reduce only or close position orders"
+                    .to_string()
+            )))
+        );
+    }
+
+    let cancelled = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cidresting"),
+            state: RequestCancel::new(None),
+        })
+        .await;
+    assert_eq!(
+        cancelled.state,
+        Ok(Cancelled::new(
+            OrderId::new("ord-cidresting"),
+            time(1700000002000)
+        ))
+    );
+
+    let already_cancelled = client
+        .cancel_order(OrderRequestCancel {
+            key: key("cidcancelled"),
+            state: RequestCancel::new(None),
+        })
+        .await;
+    assert_eq!(
+        already_cancelled.state,
+        Err(OrderError::Rejected(ApiError::OrderAlreadyCancelled))
+    );
+
+    // All requests so far share a single order entry connection
+    assert_eq!(*connections.lock().unwrap(), 1);
+
+    // In-flight requests fail if the connection drops, and the next request reconnects
+    let dropped = client.open_order(request_open("ciddrop")).await;
+    assert_eq!(
+        dropped.state,
+        Err(OrderError::Connectivity(ConnectivityError::Socket(
+            "OKX order entry connection closed".to_string()
+        )))
+    );
+    let reconnected = client.open_order(request_open("cidretry")).await;
+    assert!(reconnected.state.is_ok());
+    assert_eq!(*connections.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_okx_account_stream() {
+    let (base_url_ws, _) = start_private_ws().await;
+    let client = Okx::new(config(String::new(), base_url_ws));
+
+    let mut stream = client.account_stream(&[], &[btc_usdt()]).await.unwrap();
+
+    // Order updates caused by a fill generate a Trade followed by an OrderSnapshot
+    let event = stream.next().await.unwrap();
+    assert_eq!(event.exchange, ExchangeId::Okx);
+    let AccountEventKind::Trade(trade) = event.kind else {
+        panic!("expected Trade");
+    };
+    assert_eq!(trade.order_id, OrderId::new("301"));
+    assert_eq!(trade.price, dec!(29000));
+    assert_eq!(trade.quantity, dec!(0.15));
+    assert_eq!(trade.fees.fees, dec!(8.7));
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cidresting"));
+    assert_eq!(order.0.state, OrderState::fully_filled());
+
+    let AccountEventKind::OrderSnapshot(order) = stream.next().await.unwrap().kind else {
+        panic!("expected OrderSnapshot");
+    };
+    assert_eq!(order.0.key.cid, ClientOrderId::new("cidcancel"));
+    assert_eq!(
+        order.0.state,
+        OrderState::inactive(Cancelled::new(OrderId::new("303"), time(1700000005001)))
+    );
+
+    let AccountEventKind::BalanceSnapshot(balance) = stream.next().await.unwrap().kind else {
+        panic!("expected BalanceSnapshot");
+    };
+    assert_eq!(balance.0.asset, AssetNameExchange::new("USDT"));
+    assert_eq!(balance.0.balance, Balance::new(dec!(5650), dec!(5650)));
+    assert_eq!(balance.0.time_exchange, time(1700000005002));
+}
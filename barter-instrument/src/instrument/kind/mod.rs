@@ -4,6 +4,7 @@ use crate::instrument::{
         MarketDataFutureContract, MarketDataInstrumentKind, MarketDataOptionContract,
     },
 };
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -50,6 +51,16 @@ impl<AssetKey> InstrumentKind<AssetKey> {
         }
     }
 
+    /// For `Future` & `Option` variants of [`Self`], returns the contract expiry, and `None` for
+    /// `Spot` & `Perpetual`.
+    pub fn expiry(&self) -> Option<DateTime<Utc>> {
+        match self {
+            InstrumentKind::Spot | InstrumentKind::Perpetual(_) => None,
+            InstrumentKind::Future(kind) => Some(kind.expiry),
+            InstrumentKind::Option(kind) => Some(kind.expiry),
+        }
+    }
+
     /// Determines if the provided [`MarketDataInstrumentKind`] is equivalent to [`Self`] (ignores
     /// settlement asset).
     pub fn eq_market_data_instrument_kind(&self, other: &MarketDataInstrumentKind) -> bool {
@@ -35,10 +35,11 @@ use barter_data::{
 use barter_execution::{
     AccountEvent,
     order::{
-        OrderKey, OrderKind, TimeInForce,
+        OrderFlags, OrderKey, OrderKind, TimeInForce,
         id::{ClientOrderId, StrategyId},
-        request::{OrderRequestCancel, OrderRequestOpen, RequestOpen},
+        request::{OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, RequestOpen},
     },
+    slippage::NoSlippage,
 };
 use barter_instrument::{
     Side,
@@ -323,6 +324,7 @@ impl AlgoStrategy for LoseMoneyStrategy {
                         quantity: Decimal::from_f64(trade_not_sent_as_order_open.amount).unwrap(),
                         kind: OrderKind::Market,
                         time_in_force: TimeInForce::ImmediateOrCancel,
+                        flags: OrderFlags::default(),
                     },
                 })
             });
@@ -347,7 +349,7 @@ impl ClosePositionsStrategy for LoseMoneyStrategy {
         AssetIndex: 'a,
         InstrumentIndex: 'a,
     {
-        close_open_positions_with_market_orders(&self.id, state, filter, |_| {
+        close_open_positions_with_market_orders(&self.id, state, filter, &NoSlippage, |_| {
             ClientOrderId::random()
         })
     }
@@ -443,6 +445,10 @@ impl InFlightRequestRecorder for LoseMoneyInstrumentData {
     fn record_in_flight_cancel(&mut self, _: &OrderRequestCancel<ExchangeIndex, InstrumentIndex>) {}
 
     fn record_in_flight_open(&mut self, _: &OrderRequestOpen<ExchangeIndex, InstrumentIndex>) {}
+
+    fn record_in_flight_amend(&mut self, _: &OrderRequestAmend<ExchangeIndex, InstrumentIndex>) {}
+
+    fn remove_in_flight_open(&mut self, _: &OrderRequestOpen<ExchangeIndex, InstrumentIndex>) {}
 }
 
 fn args_constant(
@@ -39,7 +39,7 @@ use barter_execution::{
     AccountEvent, AccountEventKind,
     order::{
         id::{ClientOrderId, StrategyId},
-        request::{OrderRequestCancel, OrderRequestOpen},
+        request::{OrderRequestAmend, OrderRequestCancel, OrderRequestOpen},
     },
 };
 use barter_instrument::{
@@ -298,6 +298,10 @@ impl InFlightRequestRecorder for MultiStrategyCustomInstrumentData {
     fn record_in_flight_cancel(&mut self, _: &OrderRequestCancel<ExchangeIndex, InstrumentIndex>) {}
 
     fn record_in_flight_open(&mut self, _: &OrderRequestOpen<ExchangeIndex, InstrumentIndex>) {}
+
+    fn record_in_flight_amend(&mut self, _: &OrderRequestAmend<ExchangeIndex, InstrumentIndex>) {}
+
+    fn remove_in_flight_open(&mut self, _: &OrderRequestOpen<ExchangeIndex, InstrumentIndex>) {}
 }
 
 impl Default for StrategyCustomInstrumentData {
@@ -14,9 +14,9 @@ use barter::{
     },
     logging::init_logging,
     risk::{
-        DefaultRiskManager, RiskApproved, RiskManager, RiskRefused,
+        DefaultRiskManager, RiskApproved, RiskManager, RiskRefused, RiskRefusedReason,
         check::{
-            CheckHigherThan, RiskCheck,
+            CheckHigherThan, CheckTriggerPrice, RiskCheck, TriggerPriceInput,
             util::{calculate_abs_percent_difference, calculate_quote_notional},
         },
     },
@@ -107,7 +107,7 @@ impl RiskManager
                 if let InstrumentKind::Option(_) = instrument_state.instrument.kind {
                     refused.push(RiskRefused::new(
                         request_open,
-                        "RiskManager cannot check Options orders without a strike price"
+                        RiskRefusedReason::InstrumentKindUnsupported,
                     ));
                     return (approved, refused);
                 }
@@ -129,11 +129,42 @@ impl RiskManager
                     );
                     refused.push(RiskRefused::new(
                         request_open,
-                        "RiskManager max_notional_per_instrument failed"
+                        RiskRefusedReason::MaxOrderNotional(error),
                     ));
                     return (approved, refused);
                 }
 
+                // Filter conditional orders that would trigger immediately at the market price
+                if request_open.state.kind.is_conditional() {
+                    let Some(market_price) = instrument_state.data.price() else {
+                        refused.push(RiskRefused::new(
+                            request_open,
+                            RiskRefusedReason::PriceUnavailable,
+                        ));
+                        return (approved, refused);
+                    };
+
+                    let input = TriggerPriceInput::new(
+                        request_open.state.side,
+                        request_open.state.kind,
+                        market_price,
+                    );
+
+                    if let Err(error) = CheckTriggerPrice.check(&input) {
+                        warn!(
+                            instrument = %instrument_state.instrument.name_internal,
+                            ?request_open,
+                            ?error,
+                            "RiskManager filtered order: check_trigger_price failed"
+                        );
+                        refused.push(RiskRefused::new(
+                            request_open,
+                            RiskRefusedReason::TriggerPrice(error),
+                        ));
+                        return (approved, refused);
+                    }
+                }
+
                 // Only need to make additional checks if OrderKind::Market, so can approve otherwise
                 if OrderKind::Market != request_open.state.kind {
                     approved.push(RiskApproved::new(request_open));
@@ -150,7 +181,7 @@ impl RiskManager
                     );
                     refused.push(RiskRefused::new(
                         request_open,
-                        "RiskManager max_market_order_price_percent_from_market failed"
+                        RiskRefusedReason::PriceUnavailable,
                     ));
                     return (approved, refused);
                 };
@@ -171,7 +202,7 @@ impl RiskManager
                     );
                     refused.push(RiskRefused::new(
                         request_open,
-                        "RiskManager max_market_order_price_percent_from_market failed",
+                        RiskRefusedReason::PriceBand(error),
                     ));
                     return (approved, refused);
                 }
@@ -137,6 +137,7 @@ fn generate_synthetic_updates(base_time: DateTime<Utc>) -> Vec<ContrivedEvents>
             price_entry_average: dec!(1.0),
             quantity_abs_max: dec!(1000.0),
             pnl_realised: dec!(2000.0), // 2000 usdt profit
+            pnl_funding: dec!(0.0),
             fees_enter: AssetFees {
                 asset: QuoteAsset,
                 fees: dec!(0.0),
@@ -168,6 +169,7 @@ fn generate_synthetic_updates(base_time: DateTime<Utc>) -> Vec<ContrivedEvents>
             price_entry_average: dec!(1.0),
             quantity_abs_max: dec!(2000.0),
             pnl_realised: dec!(1000.0), // 1000 usdt profit
+            pnl_funding: dec!(0.0),
             fees_enter: AssetFees::default(),
             fees_exit: AssetFees::default(),
             time_enter: base_time.checked_add_days(Days::new(2)).unwrap(),
@@ -193,6 +195,7 @@ fn generate_synthetic_updates(base_time: DateTime<Utc>) -> Vec<ContrivedEvents>
             price_entry_average: dec!(1.0),
             quantity_abs_max: dec!(2000.0),
             pnl_realised: dec!(-2000.0), // 2000 usdt loss
+            pnl_funding: dec!(0.0),
             fees_enter: AssetFees::default(),
             fees_exit: AssetFees::default(),
             time_enter: base_time.checked_add_days(Days::new(4)).unwrap(),
@@ -224,6 +227,7 @@ fn generate_synthetic_updates(base_time: DateTime<Utc>) -> Vec<ContrivedEvents>
             price_entry_average: dec!(1.0),
             quantity_abs_max: dec!(6000.0),
             pnl_realised: dec!(-1000.0), // 1000 usdt loss
+            pnl_funding: dec!(0.0),
             fees_enter: AssetFees::default(),
             fees_exit: AssetFees::default(),
             time_enter: base_time.checked_add_days(Days::new(6)).unwrap(),
@@ -253,6 +257,7 @@ fn generate_synthetic_updates(base_time: DateTime<Utc>) -> Vec<ContrivedEvents>
             price_entry_average: dec!(1.0),
             quantity_abs_max: dec!(6000.0),
             pnl_realised: dec!(500.0), // 500 usdt profit
+            pnl_funding: dec!(0.0),
             fees_enter: AssetFees::default(),
             fees_exit: AssetFees::default(),
             time_enter: base_time.checked_add_days(Days::new(10)).unwrap(),
@@ -0,0 +1,461 @@
+use crate::{
+    EngineEvent,
+    engine::{Processor, clock::EngineClock, execution_tx::MultiExchangeTxMap},
+    error::BarterError,
+    execution::{
+        AccountStreamEvent,
+        builder::generate_mock_exchange_instruments,
+        manager::{
+            process_amend_response, process_amend_timeout, process_cancel_response,
+            process_cancel_timeout, process_group_request, process_group_response,
+            process_open_response, process_open_timeout,
+        },
+        mock::{MockMarketEventKind, MockMarketTxMap},
+        request::ExecutionRequest,
+    },
+    system::config::ExecutionConfig,
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    streams::consumer::MarketStreamEvent,
+};
+use barter_execution::{
+    AccountEvent, AccountEventKind, UnindexedAccountEvent,
+    client::{
+        ExecutionClient,
+        mock::{MockExecution, MockExecutionClientConfig},
+    },
+    exchange::mock::MockExchange,
+    indexer::AccountEventIndexer,
+    map::generate_execution_instrument_map,
+};
+use barter_instrument::{
+    exchange::ExchangeId,
+    index::{IndexedInstruments, error::IndexError},
+    instrument::{InstrumentIndex, name::InstrumentNameExchange},
+};
+use barter_integration::{
+    Terminal,
+    channel::{UnboundedRx, mpsc_unbounded},
+};
+use chrono::{DateTime, Utc};
+use fnv::FnvHashMap;
+use futures::{Stream, StreamExt};
+use std::{pin::pin, sync::Arc, task::Poll};
+use tokio::sync::{
+    broadcast::{self, error::TryRecvError},
+    mpsc,
+};
+use tracing::{error, warn};
+
+/// Mock execution for a backtest, actioned synchronously in lock-step with the `Engine`.
+///
+/// Every [`MockExchange`] is driven from the same task as the `Engine`, delivering responses and
+/// notifications immediately rather than after a simulated latency. For each market event:
+/// 1. The `Engine` processes the market event.
+/// 2. Any `ExecutionRequest`s generated since the previous market event are actioned, and the
+///    responses processed by the `Engine`.
+/// 3. The market event is forwarded to each `MockExchange`, and the resulting notifications
+///    processed by the `Engine`.
+///
+/// Requests generated while processing responses & notifications are therefore actioned with the
+/// next market event, emulating a request round trip without advancing the clock.
+///
+/// This ensures a `MockExchange` never simulates fills using market data the `Engine` has not
+/// yet acted on, and that running a backtest with the same inputs produces identical results.
+pub(crate) struct BacktestExecution<FnTime> {
+    mock_market_tx_map: MockMarketTxMap,
+    mocks: Vec<BacktestMockExchange<FnTime>>,
+}
+
+struct BacktestMockExchange<FnTime> {
+    exchange: MockExchange,
+    client: MockExecution<FnTime>,
+    indexer: AccountEventIndexer,
+    execution_rx: UnboundedRx<ExecutionRequest>,
+    market_rx: mpsc::UnboundedReceiver<MarketEvent<InstrumentNameExchange, DataKind>>,
+    notification_rx: broadcast::Receiver<UnindexedAccountEvent>,
+}
+
+/// Construct the [`BacktestExecution`] for the provided [`ExecutionConfig`]s, and the
+/// [`MultiExchangeTxMap`] the `Engine` uses to send it `ExecutionRequest`s.
+///
+/// Returns an error if any [`ExecutionConfig`] is not a mock, or an exchange is mocked twice.
+pub(crate) fn build_backtest_execution<Clock>(
+    instruments: &IndexedInstruments,
+    executions: Vec<ExecutionConfig>,
+    clock: Clock,
+) -> Result<
+    (
+        BacktestExecution<impl Fn() -> DateTime<Utc> + Clone + Sync + use<Clock>>,
+        MultiExchangeTxMap,
+    ),
+    BarterError,
+>
+where
+    Clock: EngineClock + Clone + Sync,
+{
+    // Notifications are drained after every request & market event, so this is never exceeded
+    // in practice
+    const NOTIFICATION_CAPACITY: usize = 4096;
+
+    let mut mock_market_tx_map = MockMarketTxMap::default();
+    let mut execution_txs = FnvHashMap::default();
+    let mut mocks = Vec::with_capacity(executions.len());
+
+    for config in executions {
+        let config = match config {
+            ExecutionConfig::Mock(config) => config,
+            ExecutionConfig::Live(config) => {
+                return Err(BarterError::Config(format!(
+                    "backtests do not support live execution: {}",
+                    config.exchange.exchange()
+                )));
+            }
+        };
+
+        let mocked_exchange = config.mocked_exchange;
+        let instrument_map = Arc::new(generate_execution_instrument_map(
+            instruments,
+            mocked_exchange,
+        )?);
+
+        let (execution_tx, execution_rx) = mpsc_unbounded();
+        if execution_txs
+            .insert(mocked_exchange, execution_tx)
+            .is_some()
+        {
+            return Err(BarterError::ExecutionBuilder(format!(
+                "backtests do not support duplicate mocked exchanges: {mocked_exchange}"
+            )));
+        }
+
+        let (request_tx, request_rx) = mpsc::unbounded_channel();
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        let (event_tx, event_rx) = broadcast::channel(NOTIFICATION_CAPACITY);
+        let (disconnect_tx, disconnect_rx) = broadcast::channel(1);
+
+        mock_market_tx_map.insert(Arc::clone(&instrument_map), market_tx);
+
+        let exchange = MockExchange::new(
+            config,
+            request_rx,
+            mpsc::unbounded_channel().1,
+            event_tx,
+            disconnect_tx,
+            generate_mock_exchange_instruments(instruments, mocked_exchange),
+        )
+        .map_err(|error| BarterError::Config(error.to_string()))?
+        .with_synchronous_delivery();
+
+        let clock = clock.clone();
+        let client = <MockExecution<_> as ExecutionClient>::new(MockExecutionClientConfig {
+            mocked_exchange,
+            clock: move || clock.time(),
+            request_tx,
+            event_rx: event_rx.resubscribe(),
+            disconnect_rx,
+        });
+
+        mocks.push(BacktestMockExchange {
+            exchange,
+            client,
+            indexer: AccountEventIndexer::new(instrument_map),
+            execution_rx,
+            market_rx,
+            notification_rx: event_rx,
+        });
+    }
+
+    let execution_tx_map = instruments
+        .exchanges()
+        .iter()
+        .map(|exchange| (exchange.value, execution_txs.remove(&exchange.value)))
+        .collect();
+
+    let execution = BacktestExecution {
+        mock_market_tx_map,
+        mocks,
+    };
+
+    Ok((execution, execution_tx_map))
+}
+
+impl<FnTime> BacktestExecution<FnTime>
+where
+    FnTime: Fn() -> DateTime<Utc> + Clone + Sync,
+{
+    /// Run the `Engine` over the provided market `Stream` in lock-step with each [`MockExchange`],
+    /// until the `Stream` ends or the `Engine` produces a terminal audit.
+    pub(crate) async fn run<Engine, Kind, MarketStream>(
+        mut self,
+        engine: &mut Engine,
+        market_stream: MarketStream,
+    ) -> Result<(), BarterError>
+    where
+        Engine: Processor<EngineEvent<Kind>>,
+        Engine::Audit: Terminal,
+        Kind: MockMarketEventKind + Clone,
+        MarketStream: Stream<Item = MarketStreamEvent<InstrumentIndex, Kind>>,
+    {
+        // Sync the Engine with the initial account state of each MockExchange
+        let snapshots = self.account_snapshots()?;
+        if process_account_events(engine, snapshots) {
+            return Ok(());
+        }
+
+        let mut market_stream = pin!(market_stream);
+        while let Some(event) = market_stream.next().await {
+            if engine
+                .process(EngineEvent::Market(event.clone()))
+                .is_terminal()
+            {
+                break;
+            }
+
+            // Action requests generated since the previous market event, before any MockExchange
+            // can use this market event to simulate fills
+            let responses = self.process_requests().await;
+            if process_account_events(engine, responses) {
+                break;
+            }
+
+            let notifications = self.process_market_event(&event);
+            if process_account_events(engine, notifications) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn account_snapshots(&self) -> Result<Vec<AccountStreamEvent>, BarterError> {
+        self.mocks
+            .iter()
+            .map(|mock| {
+                let snapshot = mock.indexer.snapshot(mock.exchange.account_snapshot())?;
+                Ok(AccountStreamEvent::Item(AccountEvent {
+                    exchange: mock.indexer.map.exchange.key,
+                    kind: AccountEventKind::Snapshot(snapshot),
+                }))
+            })
+            .collect()
+    }
+
+    /// Action the `ExecutionRequest`s the `Engine` has sent to each [`MockExchange`], returning
+    /// the responses and any resulting notifications.
+    async fn process_requests(&mut self) -> Vec<AccountStreamEvent> {
+        let mut events = Vec::new();
+        for mock in &mut self.mocks {
+            events.extend(mock.process_requests().await);
+        }
+        events
+    }
+
+    /// Forward a `MarketStreamEvent` processed by the `Engine` to each [`MockExchange`],
+    /// returning the resulting notifications.
+    fn process_market_event<Kind>(
+        &mut self,
+        event: &MarketStreamEvent<InstrumentIndex, Kind>,
+    ) -> Vec<AccountStreamEvent>
+    where
+        Kind: MockMarketEventKind,
+    {
+        self.mock_market_tx_map.send(event);
+
+        self.mocks
+            .iter_mut()
+            .flat_map(|mock| {
+                while let Ok(event) = mock.market_rx.try_recv() {
+                    mock.exchange.process_market_event_and_notify(event);
+                }
+                mock.notifications()
+            })
+            .collect()
+    }
+}
+
+impl<FnTime> BacktestMockExchange<FnTime>
+where
+    FnTime: Fn() -> DateTime<Utc> + Clone + Sync,
+{
+    /// Action every `ExecutionRequest` the `Engine` has sent to this [`MockExchange`], returning
+    /// the responses and any resulting notifications.
+    async fn process_requests(&mut self) -> Vec<AccountStreamEvent> {
+        let mut events = Vec::new();
+
+        while let Ok(request) = self.execution_rx.rx.try_recv() {
+            match request {
+                ExecutionRequest::Shutdown => {}
+                ExecutionRequest::Cancel(request) => {
+                    let response = respond(
+                        &mut self.exchange,
+                        self.client
+                            .cancel_order(order_request(&self.indexer, &request, "cancel")),
+                    )
+                    .await;
+
+                    events.extend(match response {
+                        Some(response) => filter_index_error(
+                            &self.indexer,
+                            process_cancel_response(&self.indexer, response),
+                        ),
+                        None => Some(process_cancel_timeout(request)),
+                    });
+                }
+                ExecutionRequest::Open(request) => {
+                    let response = respond(
+                        &mut self.exchange,
+                        self.client
+                            .open_order(order_request(&self.indexer, &request, "open")),
+                    )
+                    .await;
+
+                    events.extend(match response {
+                        Some(response) => filter_index_error(
+                            &self.indexer,
+                            process_open_response(&self.indexer, response),
+                        ),
+                        None => Some(process_open_timeout(request)),
+                    });
+                }
+                ExecutionRequest::Amend(request) => {
+                    let response = respond(
+                        &mut self.exchange,
+                        self.client
+                            .amend_order(order_request(&self.indexer, &request, "amend")),
+                    )
+                    .await;
+
+                    events.extend(match response {
+                        Some(response) => filter_index_error(
+                            &self.indexer,
+                            process_amend_response(&self.indexer, response),
+                        ),
+                        None => Some(process_amend_timeout(request)),
+                    });
+                }
+                ExecutionRequest::OpenGroup(request) => {
+                    let (client_request, opened) =
+                        process_group_request(&self.indexer, &self.client, &request);
+                    events.push(opened);
+
+                    let response = respond(
+                        &mut self.exchange,
+                        self.client.open_order_group(client_request),
+                    )
+                    .await;
+
+                    events.extend(process_group_response(
+                        &self.indexer,
+                        response.ok_or(request),
+                    ));
+                }
+            }
+
+            events.extend(self.notifications());
+        }
+
+        events
+    }
+
+    /// Drain the notifications sent by the [`MockExchange`].
+    fn notifications(&mut self) -> Vec<AccountStreamEvent> {
+        let mut notifications = Vec::new();
+
+        loop {
+            match self.notification_rx.try_recv() {
+                Ok(notification) => {
+                    notifications.extend(filter_index_error(
+                        &self.indexer,
+                        self.indexer
+                            .account_event(notification)
+                            .map(AccountStreamEvent::Item),
+                    ));
+                }
+                Err(TryRecvError::Lagged(skipped)) => {
+                    error!(
+                        exchange = %self.indexer.map.exchange.value,
+                        skipped,
+                        "BacktestExecution lagged behind MockExchange notifications"
+                    );
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break notifications,
+            }
+        }
+    }
+}
+
+/// Process the provided [`AccountStreamEvent`]s with the `Engine`.
+///
+/// Any `ExecutionRequest`s generated are actioned with the next market event.
+///
+/// Returns true if the `Engine` produced a terminal audit.
+fn process_account_events<Engine, Kind>(
+    engine: &mut Engine,
+    events: Vec<AccountStreamEvent>,
+) -> bool
+where
+    Engine: Processor<EngineEvent<Kind>>,
+    Engine::Audit: Terminal,
+{
+    events
+        .into_iter()
+        .any(|event| engine.process(EngineEvent::Account(event)).is_terminal())
+}
+
+/// Drive the provided `MockExecution` client request `Future` to completion, actioning the
+/// requests it queues at the [`MockExchange`].
+///
+/// Returns `None` if the [`MockExchange`] never responds (eg/ due to an injected timeout fault).
+async fn respond<Fut>(exchange: &mut MockExchange, future: Fut) -> Option<Fut::Output>
+where
+    Fut: Future,
+{
+    let mut future = pin!(future);
+
+    loop {
+        if let Poll::Ready(output) = futures::poll!(future.as_mut()) {
+            return Some(output);
+        }
+
+        let Ok(request) = exchange.request_rx.try_recv() else {
+            return None;
+        };
+
+        exchange.process_request(request);
+    }
+}
+
+fn order_request<'a, Kind>(
+    indexer: &'a AccountEventIndexer,
+    request: &barter_execution::order::OrderEvent<
+        Kind,
+        barter_instrument::exchange::ExchangeIndex,
+        InstrumentIndex,
+    >,
+    kind: &'static str,
+) -> barter_execution::order::OrderEvent<Kind, ExchangeId, &'a InstrumentNameExchange>
+where
+    Kind: Clone,
+{
+    // Panic since the backtest is set up incorrectly, so it's foolish to continue
+    indexer.order_request(request).unwrap_or_else(|error| {
+        panic!("BacktestExecution received {kind} request for non-configured key: {error}")
+    })
+}
+
+fn filter_index_error(
+    indexer: &AccountEventIndexer,
+    event: Result<AccountStreamEvent, IndexError>,
+) -> Option<AccountStreamEvent> {
+    event
+        .inspect_err(|error| {
+            warn!(
+                exchange = %indexer.map.exchange.value,
+                ?error,
+                "BacktestExecution filtering event due to unrecognised index"
+            )
+        })
+        .ok()
+}
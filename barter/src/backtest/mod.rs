@@ -1,3 +1,7 @@
+use crate::{
+    backtest::execution::build_backtest_execution, engine::Engine,
+    execution::mock::MockMarketEventKind,
+};
 /// Backtesting utilities for algorithmic trading strategies.
 ///
 /// This module provides tools for running historical simulations of trading strategies
@@ -20,12 +24,7 @@ use crate::{
         algo::AlgoStrategy, close_positions::ClosePositionsStrategy,
         on_disconnect::OnDisconnectStrategy, on_trading_disabled::OnTradingDisabled,
     },
-    system::{builder::EngineFeedMode, config::ExecutionConfig},
-};
-use crate::{
-    engine::Engine,
-    execution::builder::{ExecutionBuild, ExecutionBuilder},
-    system::builder::{AuditMode, SystemBuild},
+    system::config::ExecutionConfig,
 };
 use barter_data::event::MarketEvent;
 use barter_execution::AccountEvent;
@@ -42,6 +41,9 @@ pub mod market_data;
 /// Contains data structures for representing backtest results and metrics.
 pub mod summary;
 
+/// Mock execution actioned in lock-step with the `Engine` during a backtest.
+mod execution;
+
 /// Configuration for constants used across all backtests in a batch.
 ///
 /// Contains shared inputs like instruments, execution configurations,
@@ -129,6 +131,7 @@ where
         + Send
         + 'static,
     InstrumentData: InstrumentDataState + Default + Send + 'static,
+    InstrumentData::MarketEventKind: MockMarketEventKind,
 {
     let time_start = std::time::Instant::now();
 
@@ -192,32 +195,24 @@ where
         + Send
         + 'static,
     InstrumentData: InstrumentDataState + Send + 'static,
+    InstrumentData::MarketEventKind: MockMarketEventKind,
 {
+    // Stepped, so the time of the Engine and MockExchanges is fully determined by market data
     let clock = args_constant
         .market_data
         .time_first_event()
         .await
-        .map(HistoricalClock::new)?;
+        .map(HistoricalClock::stepped)?;
     let market_stream = args_constant.market_data.stream().await?;
 
-    // Build Execution infrastructure
-    let ExecutionBuild {
-        execution_tx_map,
-        account_channel,
-        futures,
-    } = args_constant
-        .executions
-        .clone()
-        .into_iter()
-        .try_fold(
-            ExecutionBuilder::new(&args_constant.instruments),
-            |builder, config| match config {
-                ExecutionConfig::Mock(mock_config) => builder.add_mock(mock_config, clock.clone()),
-            },
-        )?
-        .build();
+    // Build Execution infrastructure, actioned in lock-step with the Engine
+    let (execution, execution_tx_map) = build_backtest_execution(
+        &args_constant.instruments,
+        args_constant.executions.clone(),
+        clock.clone(),
+    )?;
 
-    let engine = Engine::new(
+    let mut engine = Engine::new(
         clock,
         args_constant.engine_state.clone(),
         execution_tx_map,
@@ -225,18 +220,14 @@ where
         args_dynamic.risk,
     );
 
-    let system = SystemBuild::new(
-        engine,
-        EngineFeedMode::Stream,
-        AuditMode::Disabled,
-        market_stream,
-        account_channel,
-        futures,
-    )
-    .init()
-    .await?;
-
-    let (engine, _shutdown_audit) = system.shutdown_after_backtest().await?;
+    // Run on a dedicated task so concurrent backtests utilise multiple cores
+    let engine = tokio::spawn(async move {
+        execution
+            .run(&mut engine, market_stream)
+            .await
+            .map(|()| engine)
+    })
+    .await??;
 
     let trading_summary = engine
         .trading_summary_generator(args_dynamic.risk_free_return)
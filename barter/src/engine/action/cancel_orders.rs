@@ -1,6 +1,7 @@
 use crate::engine::{
     Engine,
-    action::send_requests::{SendRequests, SendRequestsOutput},
+    action::send_requests::SendRequestsOutput,
+    clock::EngineClock,
     execution_tx::ExecutionTxMap,
     state::{
         EngineState,
@@ -10,6 +11,8 @@ use crate::engine::{
 };
 use barter_execution::order::{Order, request::RequestCancel};
 use barter_instrument::{asset::AssetIndex, exchange::ExchangeIndex, instrument::InstrumentIndex};
+use fnv::FnvHashSet;
+use tracing::info;
 
 /// Trait that defines how the [`Engine`] cancels open order requests.
 ///
@@ -26,6 +29,9 @@ pub trait CancelOrders<
     /// Generates cancel order requests.
     ///
     /// Uses the provided [`InstrumentFilter`] to determine which orders to cancel.
+    ///
+    /// Any rate limited order requests queued for the filtered instruments are dropped first, so
+    /// queued opens are never sent and orders with a queued cancel are cancelled immediately.
     fn cancel_orders(
         &mut self,
         filter: &InstrumentFilter<ExchangeKey, AssetKey, InstrumentKey>,
@@ -35,6 +41,7 @@ pub trait CancelOrders<
 impl<Clock, GlobalData, InstrumentData, ExecutionTxs, Strategy, Risk> CancelOrders
     for Engine<Clock, EngineState<GlobalData, InstrumentData>, ExecutionTxs, Strategy, Risk>
 where
+    Clock: EngineClock,
     InstrumentData: InFlightRequestRecorder,
     ExecutionTxs: ExecutionTxMap,
 {
@@ -42,16 +49,35 @@ where
         &mut self,
         filter: &InstrumentFilter<ExchangeIndex, AssetIndex, InstrumentIndex>,
     ) -> SendRequestsOutput<RequestCancel, ExchangeIndex, InstrumentIndex> {
+        // Drop queued order requests, reverting their in flight records
+        if self.state.rate_limiter.has_queued() {
+            let instruments = self
+                .state
+                .instruments
+                .instruments(filter)
+                .map(|state| state.key)
+                .collect::<FnvHashSet<_>>();
+
+            let dropped = self
+                .state
+                .drop_queued_requests(|instrument| instruments.contains(instrument));
+            info!(
+                ?dropped,
+                "Engine dropped queued order requests before cancelling orders"
+            );
+        }
+
         let requests = self
             .state
             .instruments
             .orders(filter)
-            .flat_map(|state| state.orders().filter_map(Order::to_request_cancel));
+            .flat_map(|state| state.orders().filter_map(Order::to_request_cancel))
+            .collect::<Vec<_>>();
 
         // Bypass risk checks...
 
-        // Send order requests
-        let cancels = self.send_requests(requests);
+        // Send risk reducing order requests, regardless of the exchange rate limits
+        let cancels = self.send_requests_risk_reducing(requests);
 
         // Record in flight order requests
         self.state.record_in_flight_cancels(&cancels.sent);
@@ -1,17 +1,19 @@
 use crate::{
     engine::{
         Engine,
-        action::send_requests::{SendCancelsAndOpensOutput, SendRequests},
+        action::send_requests::SendCancelsAndOpensOutput,
+        clock::EngineClock,
         execution_tx::ExecutionTxMap,
         state::{
             instrument::filter::InstrumentFilter,
             order::in_flight_recorder::InFlightRequestRecorder,
         },
     },
+    risk::rate_limit::RateLimiterProvider,
     strategy::close_positions::ClosePositionsStrategy,
 };
 use barter_instrument::{asset::AssetIndex, exchange::ExchangeIndex, instrument::InstrumentIndex};
-use std::fmt::Debug;
+use std::{fmt::Debug, hash::Hash};
 
 /// Trait that defines how the [`Engine`] generates & sends order requests for closing open
 /// positions.
@@ -39,10 +41,12 @@ impl<Clock, State, ExecutionTxs, Strategy, Risk, ExchangeKey, AssetKey, Instrume
     ClosePositions<ExchangeKey, AssetKey, InstrumentKey>
     for Engine<Clock, State, ExecutionTxs, Strategy, Risk>
 where
-    State: InFlightRequestRecorder<ExchangeKey, InstrumentKey>,
+    Clock: EngineClock,
+    State: InFlightRequestRecorder<ExchangeKey, InstrumentKey>
+        + RateLimiterProvider<ExchangeKey, InstrumentKey>,
     ExecutionTxs: ExecutionTxMap<ExchangeKey, InstrumentKey>,
     Strategy: ClosePositionsStrategy<ExchangeKey, AssetKey, InstrumentKey, State = State>,
-    ExchangeKey: Debug + Clone,
+    ExchangeKey: Debug + Clone + Eq + Hash,
     InstrumentKey: Debug + Clone,
 {
     fn close_positions(
@@ -52,11 +56,15 @@ where
         // Generate orders
         let (cancels, opens) = self.strategy.close_positions_requests(&self.state, filter);
 
+        // Collect remaining Iterators (so we can access &mut self)
+        let cancels = cancels.into_iter().collect::<Vec<_>>();
+        let opens = opens.into_iter().collect::<Vec<_>>();
+
         // Bypass risk checks...
 
-        // Send order requests
-        let cancels = self.send_requests(cancels);
-        let opens = self.send_requests(opens);
+        // Send risk reducing order requests, regardless of the exchange rate limits
+        let cancels = self.send_requests_risk_reducing(cancels);
+        let opens = self.send_requests_risk_reducing(opens);
 
         // Record in flight order requests
         self.state.record_in_flight_cancels(&cancels.sent);
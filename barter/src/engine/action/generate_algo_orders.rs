@@ -2,11 +2,18 @@ use crate::{
     engine::{
         Engine,
         action::send_requests::{SendCancelsAndOpensOutput, SendRequests, SendRequestsOutput},
+        clock::EngineClock,
         error::UnrecoverableEngineError,
         execution_tx::ExecutionTxMap,
-        state::order::in_flight_recorder::InFlightRequestRecorder,
+        state::{
+            instrument::status::InstrumentStatusProvider,
+            order::in_flight_recorder::InFlightRequestRecorder,
+        },
+    },
+    risk::{
+        RiskApproved, RiskManager, RiskRefused, RiskRefusedReason,
+        rate_limit::{RateLimitDequeued, RateLimiterProvider},
     },
-    risk::{RiskApproved, RiskManager, RiskRefused},
     strategy::algo::AlgoStrategy,
 };
 use barter_execution::order::request::{
@@ -15,7 +22,7 @@ use barter_execution::order::request::{
 use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
 use barter_integration::collection::{none_one_or_many::NoneOneOrMany, one_or_many::OneOrMany};
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{fmt::Debug, hash::Hash};
 
 /// Trait that defines how the [`Engine`] generates and sends algorithmic order requests.
 ///
@@ -28,7 +35,14 @@ pub trait GenerateAlgoOrders<ExchangeKey = ExchangeIndex, InstrumentKey = Instru
     /// Returns a [`GenerateAlgoOrdersOutput`] containing work done:
     /// - Generated orders that were approved by the [`RiskManager`] and sent for execution.
     /// - Generated cancel requests that were refused by the [`RiskManager`].
-    /// - Generated open requests that were refused by the [`RiskManager`].
+    /// - Generated open requests that were refused by the [`RiskManager`], and generated or
+    ///   previously queued open requests refused because their instrument is no longer active
+    ///   (eg/ expired).
+    /// - Generated orders that exceeded an exchange rate limit, and were either queued or refused
+    ///   by the [`RateLimiter`](crate::risk::rate_limit::RateLimiter).
+    ///
+    /// Previously queued orders that the rate limits now allow are sent before any newly
+    /// generated orders.
     fn generate_algo_orders(&mut self) -> GenerateAlgoOrdersOutput<ExchangeKey, InstrumentKey>;
 }
 
@@ -36,34 +50,103 @@ impl<Clock, State, ExecutionTxs, Strategy, Risk, ExchangeKey, InstrumentKey>
     GenerateAlgoOrders<ExchangeKey, InstrumentKey>
     for Engine<Clock, State, ExecutionTxs, Strategy, Risk>
 where
-    State: InFlightRequestRecorder<ExchangeKey, InstrumentKey>,
+    Clock: EngineClock,
+    State: InFlightRequestRecorder<ExchangeKey, InstrumentKey>
+        + InstrumentStatusProvider<InstrumentKey>
+        + RateLimiterProvider<ExchangeKey, InstrumentKey>,
     ExecutionTxs: ExecutionTxMap<ExchangeKey, InstrumentKey>,
     Strategy: AlgoStrategy<ExchangeKey, InstrumentKey, State = State>,
     Risk: RiskManager<ExchangeKey, InstrumentKey, State = State>,
-    ExchangeKey: Debug + Clone,
+    ExchangeKey: Debug + Clone + Eq + Hash,
     InstrumentKey: Debug + Clone,
 {
     fn generate_algo_orders(&mut self) -> GenerateAlgoOrdersOutput<ExchangeKey, InstrumentKey> {
+        let time = self.clock.time();
+
+        // Dequeue previously rate limited order requests that can now be sent
+        let RateLimitDequeued {
+            cancels: cancels_dequeued,
+            opens: opens_dequeued,
+        } = self.state.rate_limiter_mut().dequeue(time);
+
+        // Refuse dequeued open requests for instruments that are no longer active
+        let (opens_dequeued, opens_dequeued_inactive): (Vec<_>, Vec<_>) =
+            opens_dequeued.into_iter().partition(|open| {
+                self.state
+                    .instrument_status(&open.key.instrument)
+                    .is_active()
+            });
+
         // Generate orders
         let (cancels, opens) = self.strategy.generate_algo_orders(&self.state);
 
+        // Refuse open requests for instruments that are no longer active (eg/ expired)
+        let (opens, opens_inactive): (Vec<_>, Vec<_>) = opens.into_iter().partition(|open| {
+            self.state
+                .instrument_status(&open.key.instrument)
+                .is_active()
+        });
+
         // RiskApprove & RiskRefuse order requests
         let (cancels, opens, refused_cancels, refused_opens) =
             self.risk.check(&self.state, cancels, opens);
 
-        // Send risk approved order requests
-        let cancels = self.send_requests(cancels.into_iter().map(|RiskApproved(cancel)| cancel));
-        let opens = self.send_requests(opens.into_iter().map(|RiskApproved(open)| open));
-
         // Collect remaining Iterators (so we can access &mut self)
-        let cancels_refused = refused_cancels.into_iter().collect();
-        let opens_refused = refused_opens.into_iter().collect();
+        let cancels = cancels
+            .into_iter()
+            .map(|RiskApproved(cancel)| cancel)
+            .collect::<Vec<_>>();
+        let opens = opens
+            .into_iter()
+            .map(|RiskApproved(open)| open)
+            .collect::<Vec<_>>();
+        let refused_cancels = refused_cancels.into_iter().collect::<Vec<_>>();
+        let refused_opens = refused_opens.into_iter().collect::<Vec<_>>();
 
-        // Record in flight order requests
-        self.state.record_in_flight_cancels(cancels.sent.iter());
-        self.state.record_in_flight_opens(opens.sent.iter());
+        // Remove in flight records of refused dequeued open requests (after generating orders,
+        // so the strategy does not re-generate them for an inactive instrument)
+        for open in &opens_dequeued_inactive {
+            self.state.remove_in_flight_open(open);
+        }
 
-        GenerateAlgoOrdersOutput::new(cancels, opens, cancels_refused, opens_refused)
+        // Rate limit risk approved order requests
+        let rate_limiter = self.state.rate_limiter_mut();
+        let cancels = rate_limiter.limit_cancels(time, cancels);
+        let opens = rate_limiter.limit_opens(time, opens);
+
+        // Send dequeued & rate limit approved order requests
+        let cancels_sent = self.send_requests(cancels_dequeued.into_iter().chain(cancels.approved));
+        let opens_sent = self.send_requests(opens_dequeued.into_iter().chain(opens.approved));
+
+        let cancels_refused = refused_cancels.into_iter().chain(cancels.refused).collect();
+        let opens_refused = refused_opens
+            .into_iter()
+            .chain(opens.refused)
+            .chain(
+                opens_dequeued_inactive
+                    .into_iter()
+                    .chain(opens_inactive)
+                    .map(|open| {
+                        let status = self.state.instrument_status(&open.key.instrument);
+                        RiskRefused::new(open, RiskRefusedReason::InstrumentInactive(status))
+                    }),
+            )
+            .collect();
+
+        // Record in flight order requests, including queued order requests so they are not
+        // re-generated by the strategy while waiting to be sent
+        self.state
+            .record_in_flight_cancels(cancels_sent.sent.iter().chain(&cancels.queued));
+        self.state
+            .record_in_flight_opens(opens_sent.sent.iter().chain(&opens.queued));
+
+        GenerateAlgoOrdersOutput {
+            cancels_and_opens: SendCancelsAndOpensOutput::new(cancels_sent, opens_sent),
+            cancels_queued: NoneOneOrMany::from(cancels.queued),
+            opens_queued: NoneOneOrMany::from(opens.queued),
+            cancels_refused,
+            opens_refused,
+        }
     }
 }
 
@@ -77,8 +160,13 @@ pub struct GenerateAlgoOrdersOutput<ExchangeKey = ExchangeIndex, InstrumentKey =
     pub cancels_and_opens: SendCancelsAndOpensOutput<ExchangeKey, InstrumentKey>,
     /// Generated cancel requests that were refused by the [`RiskManager`].
     pub cancels_refused: NoneOneOrMany<RiskRefused<OrderRequestCancel<ExchangeKey, InstrumentKey>>>,
-    /// Generated open requests that were refused by the [`RiskManager`].
+    /// Generated open requests that were refused by the [`RiskManager`], and generated or
+    /// previously queued open requests refused because their instrument is no longer active.
     pub opens_refused: NoneOneOrMany<RiskRefused<OrderRequestOpen<ExchangeKey, InstrumentKey>>>,
+    /// Generated cancel requests that exceeded an exchange rate limit, and were queued.
+    pub cancels_queued: NoneOneOrMany<OrderRequestCancel<ExchangeKey, InstrumentKey>>,
+    /// Generated open requests that exceeded an exchange rate limit, and were queued.
+    pub opens_queued: NoneOneOrMany<OrderRequestOpen<ExchangeKey, InstrumentKey>>,
 }
 
 impl<ExchangeKey, InstrumentKey> GenerateAlgoOrdersOutput<ExchangeKey, InstrumentKey> {
@@ -93,6 +181,8 @@ impl<ExchangeKey, InstrumentKey> GenerateAlgoOrdersOutput<ExchangeKey, Instrumen
             cancels_and_opens: SendCancelsAndOpensOutput::new(cancels, opens),
             cancels_refused,
             opens_refused,
+            cancels_queued: NoneOneOrMany::None,
+            opens_queued: NoneOneOrMany::None,
         }
     }
 
@@ -101,6 +191,8 @@ impl<ExchangeKey, InstrumentKey> GenerateAlgoOrdersOutput<ExchangeKey, Instrumen
         self.cancels_and_opens.is_empty()
             && self.cancels_refused.is_none()
             && self.opens_refused.is_none()
+            && self.cancels_queued.is_none()
+            && self.opens_queued.is_none()
     }
 
     /// Returns any unrecoverable errors that occurred during order request generation & sending.
@@ -115,6 +207,8 @@ impl<ExchangeKey, InstrumentKey> Default for GenerateAlgoOrdersOutput<ExchangeKe
             cancels_and_opens: SendCancelsAndOpensOutput::default(),
             cancels_refused: NoneOneOrMany::None,
             opens_refused: NoneOneOrMany::None,
+            cancels_queued: NoneOneOrMany::None,
+            opens_queued: NoneOneOrMany::None,
         }
     }
 }
@@ -1,11 +1,12 @@
 use crate::engine::{
     action::{
         generate_algo_orders::GenerateAlgoOrdersOutput,
+        order_groups::SendOrderGroupsOutput,
         send_requests::{SendCancelsAndOpensOutput, SendRequestsOutput},
     },
     error::UnrecoverableEngineError,
 };
-use barter_execution::order::request::{RequestCancel, RequestOpen};
+use barter_execution::order::request::{RequestAmend, RequestCancel, RequestOpen};
 use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
 use barter_integration::collection::one_or_many::OneOrMany;
 use derive_more::From;
@@ -21,6 +22,10 @@ pub mod close_positions;
 /// Defines the `Engine` action for generating and sending algorithmic order requests.
 pub mod generate_algo_orders;
 
+/// Defines the `Engine` action for opening contingent `OrderGroups`, and emulating their
+/// contingent behaviour.
+pub mod order_groups;
+
 /// Defines the `Engine` action for sending order `ExecutionRequests` to the execution manager.
 pub mod send_requests;
 
@@ -31,6 +36,8 @@ pub enum ActionOutput<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentInd
     GenerateAlgoOrders(GenerateAlgoOrdersOutput<ExchangeKey, InstrumentKey>),
     CancelOrders(SendRequestsOutput<RequestCancel, ExchangeKey, InstrumentKey>),
     OpenOrders(SendRequestsOutput<RequestOpen, ExchangeKey, InstrumentKey>),
+    AmendOrders(SendRequestsOutput<RequestAmend, ExchangeKey, InstrumentKey>),
+    OpenOrderGroups(SendOrderGroupsOutput<ExchangeKey, InstrumentKey>),
     ClosePositions(SendCancelsAndOpensOutput<ExchangeKey, InstrumentKey>),
 }
 
@@ -41,6 +48,8 @@ impl<ExchangeKey, InstrumentKey> ActionOutput<ExchangeKey, InstrumentKey> {
             ActionOutput::GenerateAlgoOrders(algo) => algo.cancels_and_opens.unrecoverable_errors(),
             ActionOutput::CancelOrders(cancels) => cancels.unrecoverable_errors(),
             ActionOutput::OpenOrders(opens) => opens.unrecoverable_errors(),
+            ActionOutput::AmendOrders(amends) => amends.unrecoverable_errors(),
+            ActionOutput::OpenOrderGroups(groups) => groups.unrecoverable_errors(),
             ActionOutput::ClosePositions(requests) => requests.unrecoverable_errors(),
         }
         .into_option()
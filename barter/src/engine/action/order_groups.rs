@@ -0,0 +1,145 @@
+use crate::{
+    engine::{
+        Engine,
+        action::send_requests::{SendCancelsAndOpensOutput, send_execution_request},
+        clock::EngineClock,
+        error::{EngineError, RecoverableEngineError, UnrecoverableEngineError},
+        execution_tx::ExecutionTxMap,
+        state::{EngineState, order::in_flight_recorder::InFlightRequestRecorder},
+    },
+    risk::rate_limit::RateLimitEndpoint,
+};
+use barter_execution::order::{Order, group::OrderGroup};
+use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
+use barter_integration::collection::none_one_or_many::NoneOneOrMany;
+use derive_more::Constructor;
+use itertools::{Either, Itertools};
+use serde::{Deserialize, Serialize};
+
+/// Trait that defines how the [`Engine`] opens contingent [`OrderGroup`]s, and emulates their
+/// contingent behaviour when the exchange does not support them natively.
+///
+/// # Type Parameters
+/// * `ExchangeKey` - Type used to identify an exchange (defaults to [`ExchangeIndex`]).
+/// * `InstrumentKey` - Type used to identify an instrument (defaults to [`InstrumentIndex`]).
+pub trait OrderGroups<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> {
+    /// Sends the provided [`OrderGroup`]s to the execution manager, and starts tracking them.
+    fn open_order_groups(
+        &mut self,
+        groups: impl IntoIterator<Item = OrderGroup<ExchangeKey, InstrumentKey>>,
+    ) -> SendOrderGroupsOutput<ExchangeKey, InstrumentKey>;
+
+    /// Emulates the contingent behaviour of any `Triggered` [`OrderGroup`]s by opening their
+    /// contingent orders, or cancelling their sibling orders.
+    fn emulate_order_groups(&mut self) -> SendCancelsAndOpensOutput<ExchangeKey, InstrumentKey>;
+}
+
+impl<Clock, GlobalData, InstrumentData, ExecutionTxs, Strategy, Risk> OrderGroups
+    for Engine<Clock, EngineState<GlobalData, InstrumentData>, ExecutionTxs, Strategy, Risk>
+where
+    Clock: EngineClock,
+    InstrumentData: InFlightRequestRecorder,
+    ExecutionTxs: ExecutionTxMap,
+{
+    fn open_order_groups(
+        &mut self,
+        groups: impl IntoIterator<Item = OrderGroup<ExchangeIndex, InstrumentIndex>>,
+    ) -> SendOrderGroupsOutput<ExchangeIndex, InstrumentIndex> {
+        // Bypass risk checks...
+
+        // Send order group requests within the exchange rate limits, consuming one open order
+        // request token per OrderGroup
+        let time = self.clock.time();
+        let (sent, errors): (Vec<_>, Vec<_>) = groups.into_iter().partition_map(|group| {
+            let endpoint = RateLimitEndpoint::Orders;
+            if !self
+                .state
+                .rate_limiter
+                .try_acquire(time, group.exchange(), endpoint)
+            {
+                let error = RecoverableEngineError::RateLimited(endpoint);
+                return Either::Right((group, EngineError::Recoverable(error)));
+            }
+
+            match send_execution_request(&self.execution_txs, group.exchange(), &group) {
+                Ok(()) => Either::Left(group),
+                Err(error) => Either::Right((group, error)),
+            }
+        });
+
+        // Record in flight order groups, and their initially opened orders
+        for group in &sent {
+            self.state.order_groups.record_in_flight(group);
+            self.state.record_in_flight_opens(group.orders_initial());
+        }
+
+        SendOrderGroupsOutput::new(NoneOneOrMany::from(sent), NoneOneOrMany::from(errors))
+    }
+
+    fn emulate_order_groups(
+        &mut self,
+    ) -> SendCancelsAndOpensOutput<ExchangeIndex, InstrumentIndex> {
+        let (opens, cancels) = self.state.order_groups.emulate_triggered();
+
+        // Cancel sibling orders that are still open
+        let cancels = cancels
+            .into_iter()
+            .filter_map(|key| {
+                self.state
+                    .instruments
+                    .instrument_index(&key.instrument)
+                    .orders
+                    .0
+                    .get(&key.cid)
+                    .and_then(Order::to_request_cancel)
+            })
+            .collect::<Vec<_>>();
+
+        // Send contingent order requests protecting the group position, and sibling cancels,
+        // regardless of the exchange rate limits
+        let cancels = self.send_requests_risk_reducing(cancels);
+        let opens = self.send_requests_risk_reducing(opens);
+
+        // Record in flight order requests
+        self.state.record_in_flight_cancels(&cancels.sent);
+        self.state.record_in_flight_opens(&opens.sent);
+
+        SendCancelsAndOpensOutput::new(cancels, opens)
+    }
+}
+
+/// Summary of contingent [`OrderGroup`]s sent by the [`Engine`] to the `ExecutionManager`.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct SendOrderGroupsOutput<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> {
+    pub sent: NoneOneOrMany<OrderGroup<ExchangeKey, InstrumentKey>>,
+    pub errors: NoneOneOrMany<(OrderGroup<ExchangeKey, InstrumentKey>, EngineError)>,
+}
+
+impl<ExchangeKey, InstrumentKey> SendOrderGroupsOutput<ExchangeKey, InstrumentKey> {
+    /// Returns `true` if no `SendOrderGroupsOutput` is completely empty.
+    pub fn is_empty(&self) -> bool {
+        self.sent.is_none() && self.errors.is_none()
+    }
+
+    /// Returns any unrecoverable errors that occurred during order group sending.
+    pub fn unrecoverable_errors(&self) -> NoneOneOrMany<UnrecoverableEngineError> {
+        self.errors
+            .iter()
+            .filter_map(|(_group, error)| match error {
+                EngineError::Unrecoverable(error) => Some(error.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl<ExchangeKey, InstrumentKey> Default for SendOrderGroupsOutput<ExchangeKey, InstrumentKey> {
+    fn default() -> Self {
+        Self {
+            sent: NoneOneOrMany::default(),
+            errors: NoneOneOrMany::default(),
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use crate::{
     engine::{
         Engine,
+        clock::EngineClock,
         error::{EngineError, RecoverableEngineError, UnrecoverableEngineError},
         execution_tx::ExecutionTxMap,
     },
     execution::request::ExecutionRequest,
+    risk::rate_limit::{RateLimitedRequest, RateLimiterProvider},
 };
 use barter_execution::order::{
     OrderEvent,
@@ -15,8 +17,8 @@ use barter_integration::{Unrecoverable, channel::Tx, collection::none_one_or_man
 use derive_more::Constructor;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
-use tracing::error;
+use std::{fmt::Debug, hash::Hash};
+use tracing::{error, warn};
 
 /// Trait that defines how the [`Engine`] sends order requests.
 ///
@@ -81,40 +83,147 @@ where
         ExecutionRequest<ExchangeKey, InstrumentKey>:
             From<OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
     {
-        match self
-            .execution_txs
-            .find(&request.key.exchange)?
-            .send(ExecutionRequest::from(request.clone()))
-        {
-            Ok(()) => Ok(()),
-            Err(error) if error.is_unrecoverable() => {
-                error!(
-                    exchange = ?request.key.exchange,
-                    ?request,
-                    ?error,
-                    "failed to send ExecutionRequest due to terminated channel"
+        send_execution_request(&self.execution_txs, &request.key.exchange, request)
+    }
+}
+
+impl<Clock, State, ExecutionTxs, Strategy, Risk>
+    Engine<Clock, State, ExecutionTxs, Strategy, Risk>
+{
+    /// Send order requests that bypass the [`RiskManager`](crate::risk::RiskManager) (eg/ user
+    /// `Command`s), consuming a token from the exchange rate limits of the
+    /// [`RateLimiter`](crate::risk::rate_limit::RateLimiter) for each request.
+    ///
+    /// Order requests exceeding a rate limit are not sent, and are returned with a
+    /// [`RecoverableEngineError::RateLimited`] error.
+    pub fn send_requests_rate_limited<Kind, ExchangeKey, InstrumentKey>(
+        &mut self,
+        requests: impl IntoIterator<Item = OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+    ) -> SendRequestsOutput<Kind, ExchangeKey, InstrumentKey>
+    where
+        Clock: EngineClock,
+        State: RateLimiterProvider<ExchangeKey, InstrumentKey>,
+        ExecutionTxs: ExecutionTxMap<ExchangeKey, InstrumentKey>,
+        Kind: RateLimitedRequest + Debug + Clone,
+        ExecutionRequest<ExchangeKey, InstrumentKey>:
+            From<OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+        ExchangeKey: Debug + Clone + Eq + Hash,
+        InstrumentKey: Debug + Clone,
+    {
+        let time = self.clock.time();
+
+        let (sent, errors): (Vec<_>, Vec<_>) = requests
+            .into_iter()
+            .map(|request| {
+                let acquired = self.state.rate_limiter_mut().try_acquire(
+                    time,
+                    &request.key.exchange,
+                    Kind::ENDPOINT,
                 );
-                Err(EngineError::Unrecoverable(
-                    UnrecoverableEngineError::ExecutionChannelTerminated(format!(
-                        "{:?} execution channel terminated: {:?}",
-                        request.key.exchange, error
-                    )),
-                ))
-            }
-            Err(error) => {
-                error!(
-                    exchange = ?request.key.exchange,
-                    ?request,
-                    ?error,
-                    "failed to send ExecutionRequest due to unhealthy channel"
+
+                if !acquired {
+                    warn!(
+                        ?request,
+                        endpoint = ?Kind::ENDPOINT,
+                        "Engine not sending order request that exceeds exchange rate limit"
+                    );
+                    let error = RecoverableEngineError::RateLimited(Kind::ENDPOINT);
+                    return Err((request, EngineError::Recoverable(error)));
+                }
+
+                self.send_request(&request)
+                    .map_err(|error| (request.clone(), error))
+                    .map(|_| request)
+            })
+            .partition_result();
+
+        SendRequestsOutput::new(NoneOneOrMany::from(sent), NoneOneOrMany::from(errors))
+    }
+
+    /// Send risk reducing order requests (eg/ cancelling orders, closing positions), consuming a
+    /// token from the exchange rate limits of the
+    /// [`RateLimiter`](crate::risk::rate_limit::RateLimiter) for each request where available.
+    ///
+    /// Risk reducing order requests are never refused by the `RateLimiter`, since refusing them
+    /// could leave orders and positions open after the
+    /// [`KillSwitch`](crate::risk::kill_switch::KillSwitch) has disabled trading.
+    pub fn send_requests_risk_reducing<Kind, ExchangeKey, InstrumentKey>(
+        &mut self,
+        requests: impl IntoIterator<Item = OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+    ) -> SendRequestsOutput<Kind, ExchangeKey, InstrumentKey>
+    where
+        Clock: EngineClock,
+        State: RateLimiterProvider<ExchangeKey, InstrumentKey>,
+        ExecutionTxs: ExecutionTxMap<ExchangeKey, InstrumentKey>,
+        Kind: RateLimitedRequest + Debug + Clone,
+        ExecutionRequest<ExchangeKey, InstrumentKey>:
+            From<OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+        ExchangeKey: Debug + Clone + Eq + Hash,
+        InstrumentKey: Debug + Clone,
+    {
+        let time = self.clock.time();
+
+        let requests = requests
+            .into_iter()
+            .inspect(|request| {
+                let acquired = self.state.rate_limiter_mut().try_acquire(
+                    time,
+                    &request.key.exchange,
+                    Kind::ENDPOINT,
                 );
-                Err(EngineError::Recoverable(
-                    RecoverableEngineError::ExecutionChannelUnhealthy(format!(
-                        "{:?} execution channel unhealthy: {:?}",
-                        request.key.exchange, error
-                    )),
-                ))
-            }
+
+                if !acquired {
+                    warn!(
+                        ?request,
+                        endpoint = ?Kind::ENDPOINT,
+                        "Engine sending risk reducing order request that exceeds exchange rate limit"
+                    );
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.send_requests(requests)
+    }
+}
+
+/// Send a request to the `ExecutionManager` of the provided exchange as an [`ExecutionRequest`].
+pub(crate) fn send_execution_request<ExecutionTxs, Request, ExchangeKey, InstrumentKey>(
+    execution_txs: &ExecutionTxs,
+    exchange: &ExchangeKey,
+    request: &Request,
+) -> Result<(), EngineError>
+where
+    ExecutionTxs: ExecutionTxMap<ExchangeKey, InstrumentKey>,
+    Request: Debug + Clone + Into<ExecutionRequest<ExchangeKey, InstrumentKey>>,
+    ExchangeKey: Debug,
+{
+    match execution_txs.find(exchange)?.send(request.clone()) {
+        Ok(()) => Ok(()),
+        Err(error) if error.is_unrecoverable() => {
+            error!(
+                ?exchange,
+                ?request,
+                ?error,
+                "failed to send ExecutionRequest due to terminated channel"
+            );
+            Err(EngineError::Unrecoverable(
+                UnrecoverableEngineError::ExecutionChannelTerminated(format!(
+                    "{exchange:?} execution channel terminated: {error:?}"
+                )),
+            ))
+        }
+        Err(error) => {
+            error!(
+                ?exchange,
+                ?request,
+                ?error,
+                "failed to send ExecutionRequest due to unhealthy channel"
+            );
+            Err(EngineError::Recoverable(
+                RecoverableEngineError::ExecutionChannelUnhealthy(format!(
+                    "{exchange:?} execution channel unhealthy: {error:?}"
+                )),
+            ))
         }
     }
 }
@@ -50,15 +50,30 @@ pub struct HistoricalClock {
 struct HistoricalClockInner {
     time_exchange_last: DateTime<Utc>,
     time_live_last_event: DateTime<Utc>,
+    extrapolate: bool,
 }
 
 impl HistoricalClock {
     /// Construct a new `HistoricalClock` using the provided `last_exchange_time` as a seed.
     pub fn new(last_exchange_time: DateTime<Utc>) -> Self {
+        Self::init(last_exchange_time, true)
+    }
+
+    /// Construct a new `HistoricalClock` using the provided `last_exchange_time` as a seed, which
+    /// only advances with processed event timestamps.
+    ///
+    /// Unlike [`HistoricalClock::new`], the live time elapsed since the last event is not added,
+    /// so the time is deterministic when events are processed synchronously (eg/ backtesting).
+    pub fn stepped(last_exchange_time: DateTime<Utc>) -> Self {
+        Self::init(last_exchange_time, false)
+    }
+
+    fn init(last_exchange_time: DateTime<Utc>, extrapolate: bool) -> Self {
         Self {
             inner: Arc::new(parking_lot::RwLock::new(HistoricalClockInner {
                 time_exchange_last: last_exchange_time,
                 time_live_last_event: Utc::now(),
+                extrapolate,
             })),
         }
     }
@@ -69,8 +84,13 @@ impl EngineClock for HistoricalClock {
         let lock = self.inner.read();
         let time_live_last_event = lock.time_live_last_event;
         let time_exchange_last = lock.time_exchange_last;
+        let extrapolate = lock.extrapolate;
         drop(lock);
 
+        if !extrapolate {
+            return time_exchange_last;
+        }
+
         let delta_since_last_event_live_time =
             Utc::now().signed_duration_since(time_live_last_event);
 
@@ -157,7 +177,13 @@ impl<MarketEventKind: Debug> TimeExchange for EngineEvent<MarketEventKind> {
                     .as_ref()
                     .map(|cancelled| cancelled.time_exchange)
                     .ok(),
+                AccountEventKind::OrderAmended(response) => {
+                    response.state.as_ref().map(|open| open.time_exchange).ok()
+                }
+                AccountEventKind::OrderGroupOpened(_) => None,
                 AccountEventKind::Trade(trade) => Some(trade.time_exchange),
+                AccountEventKind::Funding(funding) => Some(funding.time_exchange),
+                AccountEventKind::PositionSnapshot(position) => Some(position.0.time_exchange),
             },
             _ => None,
         }
@@ -299,6 +325,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_historical_clock_stepped_only_advances_with_events() {
+        let time_base = DateTime::<Utc>::MIN_UTC;
+        let mut clock = HistoricalClock::stepped(time_base);
+
+        spin_sleep::sleep(std::time::Duration::from_millis(10));
+        assert_eq!(clock.time(), time_base);
+
+        let time_event = time_base + TimeDelta::milliseconds(1000);
+        clock.process(&market_event(time_event));
+
+        spin_sleep::sleep(std::time::Duration::from_millis(10));
+        assert_eq!(clock.time(), time_event);
+    }
+
     #[test]
     fn test_historical_clock_time_delta_calculation() {
         let time_base = DateTime::<Utc>::MIN_UTC;
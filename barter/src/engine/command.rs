@@ -1,5 +1,8 @@
 use crate::engine::state::instrument::filter::InstrumentFilter;
-use barter_execution::order::request::{OrderRequestCancel, OrderRequestOpen};
+use barter_execution::order::{
+    group::OrderGroup,
+    request::{OrderRequestAmend, OrderRequestCancel, OrderRequestOpen},
+};
 use barter_instrument::{asset::AssetIndex, exchange::ExchangeIndex, instrument::InstrumentIndex};
 use barter_integration::collection::one_or_many::OneOrMany;
 use serde::{Deserialize, Serialize};
@@ -14,6 +17,8 @@ pub enum Command<
 > {
     SendCancelRequests(OneOrMany<OrderRequestCancel<ExchangeKey, InstrumentKey>>),
     SendOpenRequests(OneOrMany<OrderRequestOpen<ExchangeKey, InstrumentKey>>),
+    SendAmendRequests(OneOrMany<OrderRequestAmend<ExchangeKey, InstrumentKey>>),
+    SendOpenGroupRequests(OneOrMany<OrderGroup<ExchangeKey, InstrumentKey>>),
     ClosePositions(InstrumentFilter<ExchangeKey, AssetKey, InstrumentKey>),
     CancelOrders(InstrumentFilter<ExchangeKey, AssetKey, InstrumentKey>),
 }
@@ -1,3 +1,4 @@
+use crate::risk::rate_limit::RateLimitEndpoint;
 use barter_instrument::index::error::IndexError;
 use barter_integration::Unrecoverable;
 use serde::{Deserialize, Serialize};
@@ -24,6 +25,9 @@ pub enum EngineError {
 pub enum RecoverableEngineError {
     #[error("ExecutionRequest channel unhealthy: {0}")]
     ExecutionChannelUnhealthy(String),
+
+    #[error("exchange rate limit exceeded: {0:?}")]
+    RateLimited(RateLimitEndpoint),
 }
 
 /// Represents fatal error conditions that the [`Engine`](super::Engine) cannot recover from.
@@ -6,20 +6,28 @@ use crate::{
             cancel_orders::CancelOrders,
             close_positions::ClosePositions,
             generate_algo_orders::{GenerateAlgoOrders, GenerateAlgoOrdersOutput},
-            send_requests::SendRequests,
+            order_groups::OrderGroups,
+            send_requests::SendCancelsAndOpensOutput,
         },
         audit::{AuditTick, Auditor, EngineAudit, ProcessAudit, context::EngineContext},
         clock::EngineClock,
         command::Command,
         execution_tx::ExecutionTxMap,
         state::{
-            EngineState, instrument::data::InstrumentDataState,
-            order::in_flight_recorder::InFlightRequestRecorder, position::PositionExited,
+            EngineState,
+            instrument::{
+                data::InstrumentDataState, filter::InstrumentFilter, status::InstrumentExpired,
+            },
+            order::in_flight_recorder::InFlightRequestRecorder,
+            position::PositionExited,
             trading::TradingState,
         },
     },
     execution::{AccountStreamEvent, request::ExecutionRequest},
-    risk::RiskManager,
+    risk::{
+        RiskManager,
+        kill_switch::{KillSwitchOutput, calculate_pnl_total},
+    },
     shutdown::SyncShutdown,
     statistic::summary::TradingSummaryGenerator,
     strategy::{
@@ -161,7 +169,19 @@ where
             }
             EngineEvent::Account(account) => {
                 let output = self.update_from_account_stream(account);
-                ProcessAudit::with_account_update(event, output)
+                let process_audit = ProcessAudit::with_account_update(event, output);
+
+                // Emulate contingent OrderGroup behaviour regardless of TradingState, since
+                // cancelling siblings & opening contingent exits (eg/ stop-loss) protects positions
+                let output = self.emulate_order_groups();
+                if output.is_empty() {
+                    process_audit
+                } else {
+                    let unrecoverable = output.unrecoverable_errors();
+                    process_audit
+                        .add_output(EngineOutput::OrderGroups(output))
+                        .add_errors(unrecoverable)
+                }
             }
             EngineEvent::Market(market) => {
                 let output = self.update_from_market_stream(market);
@@ -169,6 +189,27 @@ where
             }
         };
 
+        // Deactivate instruments with contracts that have expired, so no more algo orders are
+        // generated for them
+        let time = self.clock.time();
+        let process_audit = self
+            .state
+            .instruments
+            .update_from_time(time)
+            .inspect(|expired| info!(?expired, "Engine deactivating expired instrument"))
+            .fold(process_audit, ProcessAudit::add_output);
+
+        // Disable trading if the KillSwitch session PnL limits have been breached
+        let process_audit = match self.update_kill_switch() {
+            Some(output) => {
+                let unrecoverable = output.unrecoverable_errors();
+                process_audit
+                    .add_output(EngineOutput::KillSwitch(output))
+                    .add_errors(unrecoverable)
+            }
+            None => process_audit,
+        };
+
         if let TradingState::Enabled = self.state.trading {
             let output = self.generate_algo_orders();
 
@@ -205,6 +246,7 @@ impl<Clock, GlobalData, InstrumentData, ExecutionTxs, Strategy, Risk>
     /// Action an `Engine` [`Command`], producing an [`ActionOutput`] of work done.
     pub fn action(&mut self, command: &Command) -> ActionOutput
     where
+        Clock: EngineClock,
         InstrumentData: InFlightRequestRecorder,
         ExecutionTxs: ExecutionTxMap,
         Strategy: ClosePositionsStrategy<State = EngineState<GlobalData, InstrumentData>>,
@@ -216,16 +258,32 @@ impl<Clock, GlobalData, InstrumentData, ExecutionTxs, Strategy, Risk>
                     ?requests,
                     "Engine actioning user Command::SendCancelRequests"
                 );
-                let output = self.send_requests(requests.clone());
+                let output = self.send_requests_risk_reducing(requests.clone());
                 self.state.record_in_flight_cancels(&output.sent);
                 ActionOutput::CancelOrders(output)
             }
             Command::SendOpenRequests(requests) => {
                 info!(?requests, "Engine actioning user Command::SendOpenRequests");
-                let output = self.send_requests(requests.clone());
+                let output = self.send_requests_rate_limited(requests.clone());
                 self.state.record_in_flight_opens(&output.sent);
                 ActionOutput::OpenOrders(output)
             }
+            Command::SendAmendRequests(requests) => {
+                info!(
+                    ?requests,
+                    "Engine actioning user Command::SendAmendRequests"
+                );
+                let output = self.send_requests_rate_limited(requests.clone());
+                self.state.record_in_flight_amends(&output.sent);
+                ActionOutput::AmendOrders(output)
+            }
+            Command::SendOpenGroupRequests(groups) => {
+                info!(
+                    ?groups,
+                    "Engine actioning user Command::SendOpenGroupRequests"
+                );
+                ActionOutput::OpenOrderGroups(self.open_order_groups(groups.clone()))
+            }
             Command::ClosePositions(filter) => {
                 info!(?filter, "Engine actioning user Command::ClosePositions");
                 ActionOutput::ClosePositions(self.close_positions(filter))
@@ -239,21 +297,83 @@ impl<Clock, GlobalData, InstrumentData, ExecutionTxs, Strategy, Risk>
 
     /// Update the `Engine` [`TradingState`].
     ///
-    /// If the `TradingState` transitions to `TradingState::Disabled`, the `Engine` will call
-    /// the configured [`OnTradingDisabled`] strategy logic.
+    /// If the `TradingState` transitions to `TradingState::Enabled`, the `Engine` re-arms the
+    /// [`KillSwitch`](crate::risk::kill_switch::KillSwitch), so it triggers again if a session PnL
+    /// limit is still breached.
+    ///
+    /// Whenever the `TradingState` is `TradingState::Disabled`, the `Engine` drops any rate
+    /// limited algorithmic order requests queued for sending, reverting their in flight records.
+    ///
+    /// If the `TradingState` transitions to `TradingState::Disabled`, the `Engine` also calls the
+    /// configured [`OnTradingDisabled`] strategy logic.
     pub fn update_from_trading_state_update(
         &mut self,
         update: TradingState,
     ) -> Option<Strategy::OnTradingDisabled>
     where
+        InstrumentData: InFlightRequestRecorder,
         Strategy:
             OnTradingDisabled<Clock, EngineState<GlobalData, InstrumentData>, ExecutionTxs, Risk>,
     {
-        self.state
-            .trading
-            .update(update)
-            .transitioned_to_disabled()
-            .then(|| Strategy::on_trading_disabled(self))
+        let audit = self.state.trading.update(update);
+
+        if audit.transitioned_to_enabled() {
+            self.state.kill_switch.rearm();
+        }
+
+        // Drop queued requests, since they were generated & risk approved while trading was enabled
+        if self.state.trading == TradingState::Disabled && self.state.rate_limiter.has_queued() {
+            let dropped = self.state.drop_queued_requests(|_| true);
+            info!(
+                ?dropped,
+                "Engine dropped queued order requests after TradingState::Disabled"
+            );
+        }
+
+        if !audit.transitioned_to_disabled() {
+            return None;
+        }
+
+        Some(Strategy::on_trading_disabled(self))
+    }
+
+    /// Update the [`KillSwitch`](crate::risk::kill_switch::KillSwitch) with the latest total
+    /// PnL, disabling trading if a session PnL limit has been breached.
+    ///
+    /// If triggered, the `Engine` sets `TradingState::Disabled` (calling the configured
+    /// [`OnTradingDisabled`] strategy logic), and optionally cancels all orders and closes all
+    /// positions.
+    pub fn update_kill_switch(&mut self) -> Option<KillSwitchOutput<Strategy::OnTradingDisabled>>
+    where
+        Clock: EngineClock,
+        InstrumentData: InFlightRequestRecorder,
+        ExecutionTxs: ExecutionTxMap,
+        Strategy: OnTradingDisabled<Clock, EngineState<GlobalData, InstrumentData>, ExecutionTxs, Risk>
+            + ClosePositionsStrategy<State = EngineState<GlobalData, InstrumentData>>,
+    {
+        if !self.state.kill_switch.config.is_enabled() {
+            return None;
+        }
+
+        let pnl = calculate_pnl_total(&self.state.instruments);
+        let triggered = self.state.kill_switch.update(self.time(), pnl)?;
+
+        let on_trading_disabled = self.update_from_trading_state_update(TradingState::Disabled);
+
+        let cancel_orders = triggered
+            .cancel_orders
+            .then(|| self.cancel_orders(&InstrumentFilter::None));
+
+        let close_positions = triggered
+            .close_positions
+            .then(|| self.close_positions(&InstrumentFilter::None));
+
+        Some(KillSwitchOutput::new(
+            triggered,
+            on_trading_disabled,
+            cancel_orders,
+            close_positions,
+        ))
     }
 
     /// Update the [`Engine`] from an [`AccountStreamEvent`].
@@ -382,6 +502,9 @@ pub enum EngineOutput<
     PositionExit(PositionExited<QuoteAsset, InstrumentKey>),
     MarketDisconnect(OnDisconnect),
     AlgoOrders(GenerateAlgoOrdersOutput<ExchangeKey, InstrumentKey>),
+    InstrumentExpired(InstrumentExpired<InstrumentKey>),
+    OrderGroups(SendCancelsAndOpensOutput<ExchangeKey, InstrumentKey>),
+    KillSwitch(KillSwitchOutput<OnTradingDisabled, ExchangeKey, InstrumentKey>),
 }
 
 /// Output produced by the [`Engine`] updating from an [`TradingState`], used to construct
@@ -427,6 +550,15 @@ impl<OnTradingDisabled, OnDisconnect, ExchangeKey, InstrumentKey>
     }
 }
 
+impl<OnTradingDisabled, OnDisconnect, ExchangeKey, InstrumentKey>
+    From<InstrumentExpired<InstrumentKey>>
+    for EngineOutput<OnTradingDisabled, OnDisconnect, ExchangeKey, InstrumentKey>
+{
+    fn from(value: InstrumentExpired<InstrumentKey>) -> Self {
+        Self::InstrumentExpired(value)
+    }
+}
+
 impl<OnTradingDisabled, OnDisconnect, ExchangeKey, InstrumentKey>
     From<GenerateAlgoOrdersOutput<ExchangeKey, InstrumentKey>>
     for EngineOutput<OnTradingDisabled, OnDisconnect, ExchangeKey, InstrumentKey>
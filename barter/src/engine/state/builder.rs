@@ -1,8 +1,12 @@
 use crate::engine::state::{
     EngineState, asset::generate_empty_indexed_asset_states,
     connectivity::generate_empty_indexed_connectivity_states,
-    instrument::generate_indexed_instrument_states, order::Orders, position::PositionManager,
-    trading::TradingState,
+    instrument::generate_indexed_instrument_states, order::Orders, order_group::OrderGroupStates,
+    position::PositionManager, trading::TradingState,
+};
+use crate::risk::{
+    kill_switch::{KillSwitch, KillSwitchConfig},
+    rate_limit::{RateLimitConfig, RateLimiter},
 };
 use barter_execution::balance::{AssetBalance, Balance};
 use barter_instrument::{
@@ -22,6 +26,8 @@ use tracing::debug;
 pub struct EngineStateBuilder<'a, GlobalData, FnInstrumentData> {
     instruments: &'a IndexedInstruments,
     trading_state: Option<TradingState>,
+    kill_switch: Option<KillSwitchConfig>,
+    rate_limit: Option<RateLimitConfig>,
     time_engine_start: Option<DateTime<Utc>>,
     global: GlobalData,
     balances: FnvHashMap<ExchangeAsset<AssetNameInternal>, Balance>,
@@ -45,6 +51,8 @@ impl<'a, GlobalData, FnInstrumentData> EngineStateBuilder<'a, GlobalData, FnInst
             instruments,
             time_engine_start: None,
             trading_state: None,
+            kill_switch: None,
+            rate_limit: None,
             global,
             balances: FnvHashMap::default(),
             instrument_data_init,
@@ -61,6 +69,26 @@ impl<'a, GlobalData, FnInstrumentData> EngineStateBuilder<'a, GlobalData, FnInst
         }
     }
 
+    /// Optionally provide the [`KillSwitchConfig`] session PnL limits.
+    ///
+    /// Defaults to a `KillSwitch` with no limits configured, which never triggers.
+    pub fn kill_switch(self, value: KillSwitchConfig) -> Self {
+        Self {
+            kill_switch: Some(value),
+            ..self
+        }
+    }
+
+    /// Optionally provide the [`RateLimitConfig`] exchange rate limits.
+    ///
+    /// Defaults to a `RateLimiter` with no rate limits configured.
+    pub fn rate_limit(self, value: RateLimitConfig) -> Self {
+        Self {
+            rate_limit: Some(value),
+            ..self
+        }
+    }
+
     /// Optionally provide the `time_engine_start`.
     ///
     /// Providing this is useful for back-test scenarios where the time should be seeded with a
@@ -107,6 +135,8 @@ impl<'a, GlobalData, FnInstrumentData> EngineStateBuilder<'a, GlobalData, FnInst
             instruments,
             time_engine_start,
             trading_state,
+            kill_switch,
+            rate_limit,
             global,
             balances,
             instrument_data_init,
@@ -134,6 +164,9 @@ impl<'a, GlobalData, FnInstrumentData> EngineStateBuilder<'a, GlobalData, FnInst
                 }))
         }
 
+        // Construct RateLimiter before the IndexedInstruments are shadowed
+        let rate_limiter = RateLimiter::new(rate_limit.unwrap_or_default(), instruments);
+
         // Generate empty InstrumentStates using provided FnInstrumentData etc.
         let instruments = generate_indexed_instrument_states(
             instruments,
@@ -149,6 +182,9 @@ impl<'a, GlobalData, FnInstrumentData> EngineStateBuilder<'a, GlobalData, FnInst
             connectivity,
             assets,
             instruments,
+            order_groups: OrderGroupStates::default(),
+            kill_switch: KillSwitch::new(kill_switch.unwrap_or_default()),
+            rate_limiter,
         }
     }
 }
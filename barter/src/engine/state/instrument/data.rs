@@ -8,7 +8,7 @@ use barter_data::{
 };
 use barter_execution::{
     AccountEvent,
-    order::request::{OrderRequestCancel, OrderRequestOpen},
+    order::request::{OrderRequestAmend, OrderRequestCancel, OrderRequestOpen},
 };
 use barter_instrument::{asset::AssetIndex, exchange::ExchangeIndex, instrument::InstrumentIndex};
 use derive_more::Constructor;
@@ -49,6 +49,15 @@ pub trait InstrumentDataState<
     /// - Volume-weighted mid-price from an `OrderBookL1`.
     /// - Volume-weighted mid-price from an `OrderBookL2`.
     fn price(&self) -> Option<Decimal>;
+
+    /// Latest delta of an `Option` instrument relative to its underlying, if available.
+    ///
+    /// Used by risk checks to calculate the delta exposure of `Option` positions and orders.
+    /// Defaults to `None`, in which case a worst case delta is assumed (ie/ 1.0 for a `Call`
+    /// and -1.0 for a `Put`).
+    fn delta(&self) -> Option<Decimal> {
+        None
+    }
 }
 
 /// Basic [`InstrumentDataState`] implementation that tracks the [`OrderBookL1`] and last traded
@@ -118,4 +127,8 @@ impl<ExchangeKey, InstrumentKey> InFlightRequestRecorder<ExchangeKey, Instrument
     fn record_in_flight_cancel(&mut self, _: &OrderRequestCancel<ExchangeKey, InstrumentKey>) {}
 
     fn record_in_flight_open(&mut self, _: &OrderRequestOpen<ExchangeKey, InstrumentKey>) {}
+
+    fn record_in_flight_amend(&mut self, _: &OrderRequestAmend<ExchangeKey, InstrumentKey>) {}
+
+    fn remove_in_flight_open(&mut self, _: &OrderRequestOpen<ExchangeKey, InstrumentKey>) {}
 }
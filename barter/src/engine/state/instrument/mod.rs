@@ -1,6 +1,10 @@
 use crate::{
     engine::state::{
-        instrument::{data::InstrumentDataState, filter::InstrumentFilter},
+        instrument::{
+            data::InstrumentDataState,
+            filter::InstrumentFilter,
+            status::{InstrumentExpired, InstrumentStatus},
+        },
         order::{Orders, manager::OrderManager},
         position::{PositionExited, PositionManager},
     },
@@ -9,9 +13,10 @@ use crate::{
 use barter_data::event::MarketEvent;
 use barter_execution::{
     InstrumentAccountSnapshot,
+    funding::FundingPayment,
     order::{
         Order, OrderKey,
-        request::OrderResponseCancel,
+        request::{OrderResponseAmend, OrderResponseCancel},
         state::{ActiveOrderState, OrderState},
     },
     trade::Trade,
@@ -40,6 +45,10 @@ pub mod data;
 /// Defines an `InstrumentFilter`, used to filter instrument-centric data structures.
 pub mod filter;
 
+/// Defines the [`InstrumentStatus`] of an instrument, and the [`InstrumentExpired`] record
+/// produced when a contract expires.
+pub mod status;
+
 /// Collection of [`InstrumentState`]s indexed by [`InstrumentIndex`].
 ///
 /// Note that the same instruments with the same [`InstrumentNameExchange`] (eg/ "btc_usdt") but
@@ -103,6 +112,19 @@ impl<InstrumentData> InstrumentStates<InstrumentData> {
             .unwrap_or_else(|| panic!("InstrumentStates does not contain: {key}"))
     }
 
+    /// Mark every `InstrumentState` with a contract that has expired at the provided time as
+    /// [`InstrumentStatus::Expired`].
+    ///
+    /// Returns an `Iterator` of [`InstrumentExpired`] for each newly expired instrument.
+    pub fn update_from_time(
+        &mut self,
+        time: DateTime<Utc>,
+    ) -> impl Iterator<Item = InstrumentExpired> + '_ {
+        self.0
+            .values_mut()
+            .filter_map(move |state| state.update_from_time(time))
+    }
+
     /// Return an `Iterator` of references to `InstrumentState`s being tracked, optionally filtered
     /// by the provided `InstrumentFilter`.
     pub fn instruments<'a>(
@@ -256,6 +278,9 @@ pub struct InstrumentState<
     /// Complete instrument definition.
     pub instrument: Instrument<ExchangeKey, AssetKey>,
 
+    /// Current trading status (eg/ expired `Future` contracts are no longer tradable).
+    pub status: InstrumentStatus,
+
     /// TearSheet generator for summarising the trading performance associated with an Instrument.
     pub tear_sheet: TearSheetGenerator,
 
@@ -277,6 +302,9 @@ impl<InstrumentData, ExchangeKey, AssetKey, InstrumentKey>
     ///
     /// This updates active orders for the instrument, using timestamps where relevant to ensure
     /// the most recent order state is applied.
+    ///
+    /// If the exchange reports positions, the current position is reconciled against the
+    /// exchange reported position (see [`PositionManager::reconcile`]).
     pub fn update_from_account_snapshot(
         &mut self,
         snapshot: &InstrumentAccountSnapshot<ExchangeKey, AssetKey, InstrumentKey>,
@@ -288,6 +316,10 @@ impl<InstrumentData, ExchangeKey, AssetKey, InstrumentKey>
         for order in &snapshot.orders {
             self.update_from_order_snapshot(Snapshot(order))
         }
+
+        if let Some(position) = &snapshot.position {
+            self.position.reconcile(position);
+        }
     }
 
     /// Updates the instrument state from an [`Order`] snapshot.
@@ -316,6 +348,19 @@ impl<InstrumentData, ExchangeKey, AssetKey, InstrumentKey>
             .update_from_cancel_response::<AssetKey>(response);
     }
 
+    /// Updates the instrument state from an
+    /// [`OrderRequestAmend`](barter_execution::order::request::OrderRequestAmend) response.
+    pub fn update_from_amend_response(
+        &mut self,
+        response: &OrderResponseAmend<ExchangeKey, AssetKey, InstrumentKey>,
+    ) where
+        ExchangeKey: Debug + Clone,
+        AssetKey: Debug + Clone,
+        InstrumentKey: Debug + Clone,
+    {
+        self.orders.update_from_amend_response::<AssetKey>(response);
+    }
+
     /// Updates the instrument state based on a new trade.
     ///
     /// This method handles:
@@ -333,6 +378,40 @@ impl<InstrumentData, ExchangeKey, AssetKey, InstrumentKey>
             .inspect(|closed| self.tear_sheet.update_from_position(closed))
     }
 
+    /// Updates the instrument state based on a new perpetual [`FundingPayment`], accumulating
+    /// it into any open [`Position`](super::position::Position) `pnl_funding`.
+    pub fn update_from_funding(&mut self, funding: &FundingPayment<InstrumentKey>)
+    where
+        InstrumentKey: Debug + PartialEq,
+    {
+        self.position.update_from_funding(funding)
+    }
+
+    /// Marks the instrument as [`InstrumentStatus::Expired`] if it has a contract expiry at or
+    /// before the provided time.
+    ///
+    /// Returns an [`InstrumentExpired`] if the instrument transitioned to expired.
+    pub fn update_from_time(
+        &mut self,
+        time: DateTime<Utc>,
+    ) -> Option<InstrumentExpired<InstrumentKey>>
+    where
+        InstrumentKey: Clone,
+    {
+        if !self.status.is_active() {
+            return None;
+        }
+
+        let expiry = self
+            .instrument
+            .kind
+            .expiry()
+            .filter(|expiry| *expiry <= time)?;
+
+        self.status = InstrumentStatus::Expired;
+        Some(InstrumentExpired::new(self.key.clone(), expiry))
+    }
+
     /// Updates the instrument state based on a new market event.
     ///
     /// If the market event has a price associated with it (eg/ `PublicTrade`, `OrderBookL1`), any
@@ -373,6 +452,7 @@ where
     let InstrumentState {
         key: _,
         instrument,
+        status: _,
         tear_sheet: _,
         position: _,
         orders,
@@ -381,6 +461,7 @@ where
 
     InstrumentAccountSnapshot {
         instrument: instrument.name_exchange.clone(),
+        position: None,
         orders: orders
             .orders()
             .filter_map(|order| {
@@ -443,6 +524,7 @@ where
                     InstrumentState::new(
                         instrument.key,
                         instrument.value.clone().map_exchange_key(exchange_index),
+                        InstrumentStatus::default(),
                         TearSheetGenerator::init(time_engine_start),
                         position_manager_init(),
                         orders_init(),
@@ -0,0 +1,53 @@
+use crate::engine::state::EngineState;
+use barter_instrument::instrument::InstrumentIndex;
+use chrono::{DateTime, Utc};
+use derive_more::Constructor;
+use serde::{Deserialize, Serialize};
+
+/// Trading status of an instrument tracked by the [`Engine`](crate::engine::Engine).
+///
+/// Algorithmic open order requests are only sent for `InstrumentStatus::Active` instruments.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+pub enum InstrumentStatus {
+    #[default]
+    Active,
+    /// `Future` or `Option` contract has expired, so the instrument can no longer be traded.
+    Expired,
+}
+
+impl InstrumentStatus {
+    /// Returns true if the instrument is `InstrumentStatus::Active`.
+    pub fn is_active(&self) -> bool {
+        matches!(self, InstrumentStatus::Active)
+    }
+}
+
+/// Instrument contract expiry observed by the [`Engine`](crate::engine::Engine).
+///
+/// Any open position is expected to be closed by the exchange settlement, producing a
+/// [`PositionExited`](crate::engine::state::position::PositionExited) via the normal trade path.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct InstrumentExpired<InstrumentKey = InstrumentIndex> {
+    pub instrument: InstrumentKey,
+    pub expiry: DateTime<Utc>,
+}
+
+/// Provides the [`InstrumentStatus`] of an instrument.
+///
+/// Used by the [`Engine`](crate::engine::Engine) to refuse algorithmic open order requests for
+/// instruments that are no longer tradable.
+pub trait InstrumentStatusProvider<InstrumentKey = InstrumentIndex> {
+    fn instrument_status(&self, instrument: &InstrumentKey) -> InstrumentStatus;
+}
+
+impl<GlobalData, InstrumentData> InstrumentStatusProvider<InstrumentIndex>
+    for EngineState<GlobalData, InstrumentData>
+{
+    fn instrument_status(&self, instrument: &InstrumentIndex) -> InstrumentStatus {
+        self.instruments.instrument_index(instrument).status
+    }
+}
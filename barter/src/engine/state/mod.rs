@@ -1,20 +1,29 @@
-use crate::engine::{
-    Processor,
-    state::{
-        asset::{AssetStates, filter::AssetFilter},
-        builder::EngineStateBuilder,
-        connectivity::ConnectivityStates,
-        instrument::{
-            InstrumentStates, data::InstrumentDataState, filter::InstrumentFilter,
-            generate_unindexed_instrument_account_snapshot,
+use crate::{
+    engine::{
+        Processor,
+        state::{
+            asset::{AssetStates, filter::AssetFilter},
+            builder::EngineStateBuilder,
+            connectivity::ConnectivityStates,
+            instrument::{
+                InstrumentStates, data::InstrumentDataState, filter::InstrumentFilter,
+                generate_unindexed_instrument_account_snapshot,
+            },
+            order::in_flight_recorder::InFlightRequestRecorder,
+            order_group::OrderGroupStates,
+            position::PositionExited,
+            trading::TradingState,
         },
-        position::PositionExited,
-        trading::TradingState,
+    },
+    risk::{
+        kill_switch::KillSwitch,
+        rate_limit::{RateLimitDequeued, RateLimiter},
     },
 };
 use barter_data::event::MarketEvent;
 use barter_execution::{
     AccountEvent, AccountEventKind, UnindexedAccountSnapshot, balance::AssetBalance,
+    order::request::OrderRequestOpen,
 };
 use barter_instrument::{
     Keyed,
@@ -42,6 +51,10 @@ pub mod instrument;
 /// Defines a synchronous `OrderManager` that tracks the lifecycle of exchange orders.
 pub mod order;
 
+/// Contingent `OrderGroup` state (ie/ One-Cancels-Other, One-Triggers-Other & Bracket), and the
+/// state transitions that determine when the `Engine` must emulate their contingent behaviour.
+pub mod order_group;
+
 /// Position data structures and their associated state management logic.
 pub mod position;
 
@@ -75,6 +88,18 @@ pub struct EngineState<GlobalData, InstrumentData> {
     /// State of every instrument (eg/ "okx_spot_btc_usdt", "bybit_perpetual_btc_usdt", etc.)
     /// being tracked by the `Engine`.
     pub instruments: InstrumentStates<InstrumentData, ExchangeIndex, AssetIndex, InstrumentIndex>,
+
+    /// State of every contingent `OrderGroup` (eg/ One-Cancels-Other, Bracket, etc.) being
+    /// tracked by the `Engine`.
+    pub order_groups: OrderGroupStates,
+
+    /// Drawdown and daily loss `KillSwitch` that disables trading when session PnL limits are
+    /// breached.
+    pub kill_switch: KillSwitch,
+
+    /// Token bucket `RateLimiter` that throttles the algorithmic order requests sent to each
+    /// exchange.
+    pub rate_limiter: RateLimiter,
 }
 
 impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
@@ -92,6 +117,48 @@ impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
         EngineStateBuilder::new(instruments, global, instrument_data_init)
     }
 
+    /// Drop the rate limited order requests queued for instruments matching the provided
+    /// predicate, reverting their in flight records.
+    ///
+    /// Queued open requests are removed from the tracked orders, and orders with a queued cancel
+    /// request return to their pre-cancel state.
+    pub fn drop_queued_requests(
+        &mut self,
+        predicate: impl Fn(&InstrumentIndex) -> bool,
+    ) -> RateLimitDequeued
+    where
+        InstrumentData: InFlightRequestRecorder,
+    {
+        let cancels = self.rate_limiter.remove_queued_cancels(&predicate);
+        for cancel in &cancels {
+            self.instruments
+                .instrument_index_mut(&cancel.key.instrument)
+                .orders
+                .revert_in_flight_cancel(cancel);
+        }
+
+        let opens = self.drop_queued_opens(predicate);
+
+        RateLimitDequeued::new(cancels, opens)
+    }
+
+    /// Drop the rate limited open order requests queued for instruments matching the provided
+    /// predicate, removing their in flight records.
+    pub fn drop_queued_opens(
+        &mut self,
+        predicate: impl Fn(&InstrumentIndex) -> bool,
+    ) -> Vec<OrderRequestOpen>
+    where
+        InstrumentData: InFlightRequestRecorder,
+    {
+        let opens = self.rate_limiter.remove_queued_opens(predicate);
+        for open in &opens {
+            self.remove_in_flight_open(open);
+        }
+
+        opens
+    }
+
     /// Updates the internal state from an `AccountEvent`.
     ///
     /// If the `AccountEvent` results in a new [`PositionExited`], that is returned.
@@ -101,6 +168,7 @@ impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
     ///   [`Health::Healthy`](connectivity::Health::Healthy) if it was not previously.
     /// - Updates the `GlobalData` with the `AccountEvent`.
     /// - Updates the associated `AssetStates` and `InstrumentStates` with the `AccountEvent`.
+    /// - Updates the status of any associated contingent `OrderGroup`.
     pub fn update_from_account(
         &mut self,
         event: &AccountEvent,
@@ -142,6 +210,7 @@ impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
 
                 instrument_state.update_from_order_snapshot(order.as_ref());
                 instrument_state.data.process(event);
+                self.order_groups.update_from_order_snapshot(order.as_ref());
                 None
             }
             AccountEventKind::OrderCancelled(response) => {
@@ -151,6 +220,20 @@ impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
 
                 instrument_state.update_from_cancel_response(response);
                 instrument_state.data.process(event);
+                self.order_groups.update_from_cancel_response(response);
+                None
+            }
+            AccountEventKind::OrderAmended(response) => {
+                let instrument_state = self
+                    .instruments
+                    .instrument_index_mut(&response.key.instrument);
+
+                instrument_state.update_from_amend_response(response);
+                instrument_state.data.process(event);
+                None
+            }
+            AccountEventKind::OrderGroupOpened(opened) => {
+                self.order_groups.update_from_opened(opened);
                 None
             }
             AccountEventKind::Trade(trade) => {
@@ -159,6 +242,23 @@ impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
                 instrument_state.data.process(event);
                 instrument_state.update_from_trade(trade)
             }
+            AccountEventKind::Funding(funding) => {
+                let instrument_state = self.instruments.instrument_index_mut(&funding.instrument);
+
+                instrument_state.data.process(event);
+                instrument_state.update_from_funding(funding);
+                None
+            }
+            AccountEventKind::PositionSnapshot(position) => {
+                // Positions are maintained from Trades, and only reconciled against exchange
+                // reported positions at AccountSnapshot time, since position updates are not
+                // guaranteed to be ordered with respect to the Trades that caused them
+                self.instruments
+                    .instrument_index_mut(&position.value().instrument)
+                    .data
+                    .process(event);
+                None
+            }
         };
 
         // Update any user provided GlobalData State
@@ -173,7 +273,8 @@ impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
     /// - Sets the market data [`ConnectivityState`](connectivity::ConnectivityState) to
     ///   [`Health::Healthy`](connectivity::Health::Healthy) if it was not previously.
     /// - Updates the `GlobalData` with the `MarketEvent`.
-    /// - Updates the associated [`InstrumentDataState`] with the `MarketEvent`.
+    /// - Updates the associated [`InstrumentDataState`] with the `MarketEvent`, and the unrealised
+    ///   PnL of any open `Position` with the latest market price.
     pub fn update_from_market(
         &mut self,
         event: &MarketEvent<InstrumentIndex, InstrumentData::MarketEventKind>,
@@ -188,7 +289,7 @@ impl<GlobalData, InstrumentData> EngineState<GlobalData, InstrumentData> {
         let instrument_state = self.instruments.instrument_index_mut(&event.instrument);
 
         self.global.process(event);
-        instrument_state.data.process(event);
+        instrument_state.update_from_market(event);
     }
 }
 
@@ -202,6 +303,9 @@ impl<GlobalData, InstrumentData> From<&EngineState<GlobalData, InstrumentData>>
             connectivity,
             assets,
             instruments,
+            order_groups: _,
+            kill_switch: _,
+            rate_limiter: _,
         } = value;
 
         // Allocate appropriately
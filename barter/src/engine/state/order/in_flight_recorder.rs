@@ -1,8 +1,8 @@
 use crate::engine::state::EngineState;
-use barter_execution::order::request::{OrderRequestCancel, OrderRequestOpen};
+use barter_execution::order::request::{OrderRequestAmend, OrderRequestCancel, OrderRequestOpen};
 use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
 
-/// Synchronous in-flight open and in-flight cancel order request tracker.
+/// Synchronous in-flight open, in-flight cancel and in-flight amend order request tracker.
 ///
 /// See [`Orders`](super::Orders) for an example implementation.
 pub trait InFlightRequestRecorder<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> {
@@ -30,9 +30,27 @@ pub trait InFlightRequestRecorder<ExchangeKey = ExchangeIndex, InstrumentKey = I
             .for_each(|request| self.record_in_flight_open(request))
     }
 
+    fn record_in_flight_amends<'a>(
+        &mut self,
+        requests: impl IntoIterator<Item = &'a OrderRequestAmend<ExchangeKey, InstrumentKey>>,
+    ) where
+        ExchangeKey: 'a,
+        InstrumentKey: 'a,
+    {
+        requests
+            .into_iter()
+            .for_each(|request| self.record_in_flight_amend(request))
+    }
+
     fn record_in_flight_cancel(&mut self, request: &OrderRequestCancel<ExchangeKey, InstrumentKey>);
 
     fn record_in_flight_open(&mut self, request: &OrderRequestOpen<ExchangeKey, InstrumentKey>);
+
+    fn record_in_flight_amend(&mut self, request: &OrderRequestAmend<ExchangeKey, InstrumentKey>);
+
+    /// Remove the in flight open recorded for an [`OrderRequestOpen`] that was never sent (eg/ a
+    /// rate limited request dropped from the queue).
+    fn remove_in_flight_open(&mut self, request: &OrderRequestOpen<ExchangeKey, InstrumentKey>);
 }
 
 impl<GlobalData, InstrumentData> InFlightRequestRecorder<ExchangeIndex, InstrumentIndex>
@@ -63,4 +81,28 @@ where
         instrument_state.orders.record_in_flight_open(request);
         instrument_state.data.record_in_flight_open(request);
     }
+
+    fn record_in_flight_amend(
+        &mut self,
+        request: &OrderRequestAmend<ExchangeIndex, InstrumentIndex>,
+    ) {
+        let instrument_state = self
+            .instruments
+            .instrument_index_mut(&request.key.instrument);
+
+        instrument_state.orders.record_in_flight_amend(request);
+        instrument_state.data.record_in_flight_amend(request);
+    }
+
+    fn remove_in_flight_open(
+        &mut self,
+        request: &OrderRequestOpen<ExchangeIndex, InstrumentIndex>,
+    ) {
+        let instrument_state = self
+            .instruments
+            .instrument_index_mut(&request.key.instrument);
+
+        instrument_state.orders.remove_in_flight_open(request);
+        instrument_state.data.remove_in_flight_open(request);
+    }
 }
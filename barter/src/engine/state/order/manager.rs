@@ -1,7 +1,7 @@
 use crate::engine::state::order::in_flight_recorder::InFlightRequestRecorder;
 use barter_execution::order::{
     Order,
-    request::OrderResponseCancel,
+    request::{OrderResponseAmend, OrderResponseCancel},
     state::{ActiveOrderState, OrderState},
 };
 use barter_integration::snapshot::Snapshot;
@@ -32,4 +32,10 @@ where
         response: &OrderResponseCancel<ExchangeKey, AssetKey, InstrumentKey>,
     ) where
         AssetKey: Debug + Clone;
+
+    fn update_from_amend_response<AssetKey>(
+        &mut self,
+        response: &OrderResponseAmend<ExchangeKey, AssetKey, InstrumentKey>,
+    ) where
+        AssetKey: Debug + Clone;
 }
@@ -1,11 +1,17 @@
 use crate::engine::state::order::{
     in_flight_recorder::InFlightRequestRecorder, manager::OrderManager,
 };
-use barter_execution::order::{
-    Order,
-    id::ClientOrderId,
-    request::{OrderRequestCancel, OrderRequestOpen, OrderResponseCancel},
-    state::{ActiveOrderState, CancelInFlight, OrderState},
+use barter_execution::{
+    error::{ApiError, OrderError},
+    order::{
+        Order,
+        id::ClientOrderId,
+        request::{
+            OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, OrderResponseAmend,
+            OrderResponseCancel,
+        },
+        state::{ActiveOrderState, AmendInFlight, CancelInFlight, OpenInFlight, OrderState},
+    },
 };
 use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
 use barter_integration::snapshot::Snapshot;
@@ -33,8 +39,9 @@ pub mod manager;
 /// Orders tend to progress through the following states:
 /// 1. OpenInFlight - Initial order request sent to exchange
 /// 2. Open - Order confirmed as open on exchange
-/// 3. CancelInFlight - Cancellation request sent to exchange
-/// 4. Cancelled/Expired/FullyFilled - Terminal states, once achieved order is no longer tracked.
+/// 3. AmendInFlight - Optional amend request sent to exchange, returning to Open once actioned
+/// 4. CancelInFlight - Cancellation request sent to exchange
+/// 5. Cancelled/Expired/FullyFilled - Terminal states, once achieved order is no longer tracked.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Constructor)]
 pub struct Orders<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex>(
     pub FnvHashMap<ClientOrderId, Order<ExchangeKey, InstrumentKey, ActiveOrderState>>,
@@ -46,6 +53,26 @@ impl<ExchangeKey, InstrumentKey> Default for Orders<ExchangeKey, InstrumentKey>
     }
 }
 
+impl<ExchangeKey, InstrumentKey> Orders<ExchangeKey, InstrumentKey> {
+    /// Revert the `CancelInFlight` order recorded for an [`OrderRequestCancel`] that was never
+    /// sent, back to its `Open` (or `OpenInFlight` if the open is unacknowledged) state.
+    pub fn revert_in_flight_cancel(
+        &mut self,
+        request: &OrderRequestCancel<ExchangeKey, InstrumentKey>,
+    ) {
+        let Some(order) = self.0.get_mut(&request.key.cid) else {
+            return;
+        };
+
+        if let ActiveOrderState::CancelInFlight(CancelInFlight { order: open }) = &order.state {
+            order.state = match open {
+                Some(open) => ActiveOrderState::Open(open.clone()),
+                None => ActiveOrderState::OpenInFlight(OpenInFlight),
+            };
+        }
+    }
+}
+
 impl<ExchangeKey, InstrumentKey> OrderManager<ExchangeKey, InstrumentKey>
     for Orders<ExchangeKey, InstrumentKey>
 where
@@ -172,6 +199,17 @@ where
                 );
                 current_entry.get_mut().state = ActiveOrderState::CancelInFlight(update);
             }
+            (ActiveOrderState::OpenInFlight(_), ActiveOrderState::AmendInFlight(update)) => {
+                debug!(
+                    exchange = ?snapshot.key.exchange,
+                    instrument = ?snapshot.key.instrument,
+                    strategy = %snapshot.key.strategy,
+                    cid = %snapshot.key.cid,
+                    update = ?snapshot,
+                    "OrderManager transitioned an OpenInFlight order to AmendInFlight"
+                );
+                current_entry.get_mut().state = ActiveOrderState::AmendInFlight(update);
+            }
             (ActiveOrderState::Open(_), ActiveOrderState::OpenInFlight(_)) => {
                 warn!(
                     exchange = ?snapshot.key.exchange,
@@ -225,6 +263,27 @@ where
                     order: Some(latest_open),
                 })
             }
+            (ActiveOrderState::Open(current), ActiveOrderState::AmendInFlight(mut update)) => {
+                debug!(
+                    exchange = ?snapshot.key.exchange,
+                    instrument = ?snapshot.key.instrument,
+                    strategy = %snapshot.key.strategy,
+                    cid = %snapshot.key.cid,
+                    update = ?snapshot,
+                    "OrderManager transitioned an Open order to AmendInFlight"
+                );
+
+                // Ensure next AmendInFlight.Open is populated and the most recent
+                let latest_open = update
+                    .order
+                    .take()
+                    .filter(|update| current.time_exchange <= update.time_exchange)
+                    .unwrap_or_else(|| current.clone());
+
+                current_entry.get_mut().state = ActiveOrderState::AmendInFlight(AmendInFlight {
+                    order: Some(latest_open),
+                })
+            }
             (ActiveOrderState::CancelInFlight(_), ActiveOrderState::OpenInFlight(_)) => {
                 error!(
                     exchange = ?snapshot.key.exchange,
@@ -268,6 +327,21 @@ where
                     "OrderManager received a duplicate CancelInFlight recording - ignoring"
                 );
             }
+            (ActiveOrderState::CancelInFlight(_), ActiveOrderState::AmendInFlight(_)) => {
+                warn!(
+                    exchange = ?snapshot.key.exchange,
+                    instrument = ?snapshot.key.instrument,
+                    strategy = %snapshot.key.strategy,
+                    cid = %snapshot.key.cid,
+                    update = ?snapshot,
+                    "OrderManager received an AmendInFlight recording for a CancelInFlight - ignoring"
+                );
+            }
+            (ActiveOrderState::AmendInFlight(current), update) => {
+                if let Some(next) = amend_in_flight_from_snapshot(current, update, snapshot) {
+                    current_entry.get_mut().state = next;
+                }
+            }
         }
     }
 
@@ -290,7 +364,12 @@ where
         };
 
         match (&order.get().state, &response.state) {
-            (ActiveOrderState::OpenInFlight(_) | ActiveOrderState::Open(_), Ok(_)) => {
+            (
+                ActiveOrderState::OpenInFlight(_)
+                | ActiveOrderState::Open(_)
+                | ActiveOrderState::AmendInFlight(_),
+                Ok(_),
+            ) => {
                 warn!(
                     exchange = ?response.key.exchange,
                     instrument = ?response.key.instrument,
@@ -312,7 +391,12 @@ where
                 );
                 order.remove();
             }
-            (ActiveOrderState::OpenInFlight(_) | ActiveOrderState::Open(_), Err(error)) => {
+            (
+                ActiveOrderState::OpenInFlight(_)
+                | ActiveOrderState::Open(_)
+                | ActiveOrderState::AmendInFlight(_),
+                Err(error),
+            ) => {
                 warn!(
                     exchange = ?response.key.exchange,
                     instrument = ?response.key.instrument,
@@ -353,6 +437,209 @@ where
             }
         }
     }
+
+    fn update_from_amend_response<AssetKey>(
+        &mut self,
+        response: &OrderResponseAmend<ExchangeKey, AssetKey, InstrumentKey>,
+    ) where
+        AssetKey: Debug + Clone,
+    {
+        let Entry::Occupied(mut order) = self.0.entry(response.key.cid.clone()) else {
+            warn!(
+                exchange = ?response.key.exchange,
+                instrument = ?response.key.instrument,
+                strategy = %response.key.strategy,
+                cid = %response.key.cid,
+                update = ?response,
+                "OrderManager received an OrderResponseAmend for untracked order - ignoring"
+            );
+            return;
+        };
+
+        let state = match (&order.get().state, &response.state) {
+            (_, Ok(open)) if open.quantity_remaining(response.quantity).is_zero() => {
+                debug!(
+                    exchange = ?response.key.exchange,
+                    instrument = ?response.key.instrument,
+                    strategy = %response.key.strategy,
+                    cid = %response.key.cid,
+                    update = ?response,
+                    "OrderManager received Ok(Amended) for tracked order which is FullyFilled - removing"
+                );
+                order.remove();
+                return;
+            }
+            (ActiveOrderState::CancelInFlight(_), Ok(open)) => {
+                debug!(
+                    exchange = ?response.key.exchange,
+                    instrument = ?response.key.instrument,
+                    strategy = %response.key.strategy,
+                    cid = %response.key.cid,
+                    update = ?response,
+                    "OrderManager received Ok(Amended) for tracked order CancelInFlight - updating CancelInFlight.Open"
+                );
+                ActiveOrderState::CancelInFlight(CancelInFlight {
+                    order: Some(open.clone()),
+                })
+            }
+            (_, Ok(open)) => {
+                debug!(
+                    exchange = ?response.key.exchange,
+                    instrument = ?response.key.instrument,
+                    strategy = %response.key.strategy,
+                    cid = %response.key.cid,
+                    update = ?response,
+                    "OrderManager received Ok(Amended) for tracked order - setting amended Open"
+                );
+                ActiveOrderState::Open(open.clone())
+            }
+            (_, Err(OrderError::Rejected(ApiError::OrderReplaceFailed(error)))) => {
+                debug!(
+                    exchange = ?response.key.exchange,
+                    instrument = ?response.key.instrument,
+                    strategy = %response.key.strategy,
+                    cid = %response.key.cid,
+                    update = ?response,
+                    ?error,
+                    "OrderManager received Err(Amended) for cancel-replaced order which is no longer open - removing"
+                );
+                order.remove();
+                return;
+            }
+            (ActiveOrderState::AmendInFlight(in_flight_amend), Err(error)) => {
+                // Expected, revert to pre-amend Open
+                if let Some(open) = &in_flight_amend.order {
+                    debug!(
+                        exchange = ?response.key.exchange,
+                        instrument = ?response.key.instrument,
+                        strategy = %response.key.strategy,
+                        cid = %response.key.cid,
+                        update = ?response,
+                        ?error,
+                        "OrderManager received Err(Amended) for previously Open order - setting Open"
+                    );
+                    order.get_mut().state = ActiveOrderState::Open(open.clone());
+                } else {
+                    debug!(
+                        exchange = ?response.key.exchange,
+                        instrument = ?response.key.instrument,
+                        strategy = %response.key.strategy,
+                        cid = %response.key.cid,
+                        update = ?response,
+                        ?error,
+                        "OrderManager received Err(Amended) for previously non-Open order - removing"
+                    );
+                    // Likely previously OpenInFlight, and attempted amend before Open snapshot
+                    // -> it's expected that an Order snapshot is inbound
+                    order.remove();
+                }
+                return;
+            }
+            (_, Err(error)) => {
+                warn!(
+                    exchange = ?response.key.exchange,
+                    instrument = ?response.key.instrument,
+                    strategy = %response.key.strategy,
+                    cid = %response.key.cid,
+                    update = ?response,
+                    ?error,
+                    "OrderManager received Err(Amended) for tracked order not AmendInFlight - ignoring"
+                );
+                return;
+            }
+        };
+
+        // Amended price & quantity only apply once confirmed by the exchange
+        let current = order.get_mut();
+        current.side = response.side;
+        current.price = response.price;
+        current.quantity = response.quantity;
+        current.kind = response.kind;
+        current.time_in_force = response.time_in_force;
+        current.state = state;
+    }
+}
+
+/// Determine the next [`ActiveOrderState`] of an `AmendInFlight` order from an order snapshot,
+/// returning `None` if the snapshot should be ignored.
+fn amend_in_flight_from_snapshot<ExchangeKey, AssetKey, InstrumentKey>(
+    current: &AmendInFlight,
+    update: ActiveOrderState,
+    snapshot: &Order<ExchangeKey, InstrumentKey, OrderState<AssetKey, InstrumentKey>>,
+) -> Option<ActiveOrderState>
+where
+    ExchangeKey: Debug,
+    AssetKey: Debug,
+    InstrumentKey: Debug,
+{
+    match update {
+        ActiveOrderState::OpenInFlight(_) => {
+            error!(
+                exchange = ?snapshot.key.exchange,
+                instrument = ?snapshot.key.instrument,
+                strategy = %snapshot.key.strategy,
+                cid = %snapshot.key.cid,
+                update = ?snapshot,
+                "OrderManager received an OpenInFlight recording for an AmendInFlight - ignoring"
+            );
+            None
+        }
+        ActiveOrderState::Open(update) => {
+            debug!(
+                exchange = ?snapshot.key.exchange,
+                instrument = ?snapshot.key.instrument,
+                strategy = %snapshot.key.strategy,
+                cid = %snapshot.key.cid,
+                update = ?snapshot,
+                "OrderManager received an Open order snapshot for an AmendInFlight - updating AmendInFlight.Open"
+            );
+
+            // Check if the update Open is more recent
+            let update_open_is_latest = current
+                .order
+                .as_ref()
+                .is_none_or(|current| current.time_exchange <= update.time_exchange);
+
+            update_open_is_latest.then_some({
+                ActiveOrderState::AmendInFlight(AmendInFlight {
+                    order: Some(update),
+                })
+            })
+        }
+        ActiveOrderState::CancelInFlight(update) => {
+            debug!(
+                exchange = ?snapshot.key.exchange,
+                instrument = ?snapshot.key.instrument,
+                strategy = %snapshot.key.strategy,
+                cid = %snapshot.key.cid,
+                update = ?snapshot,
+                "OrderManager transitioned an AmendInFlight order to CancelInFlight"
+            );
+
+            // Cancel supersedes the in-flight amend, so carry over the most recent Open
+            let latest_open = match (current.order.clone(), update.order) {
+                (Some(current), Some(update)) if current.time_exchange > update.time_exchange => {
+                    Some(current)
+                }
+                (current, update) => update.or(current),
+            };
+
+            Some(ActiveOrderState::CancelInFlight(CancelInFlight {
+                order: latest_open,
+            }))
+        }
+        ActiveOrderState::AmendInFlight(_) => {
+            warn!(
+                exchange = ?snapshot.key.exchange,
+                instrument = ?snapshot.key.instrument,
+                strategy = %snapshot.key.strategy,
+                cid = %snapshot.key.cid,
+                update = ?snapshot,
+                "OrderManager received a duplicate AmendInFlight recording - ignoring"
+            );
+            None
+        }
+    }
 }
 
 impl<ExchangeKey, InstrumentKey> InFlightRequestRecorder<ExchangeKey, InstrumentKey>
@@ -390,6 +677,38 @@ where
             );
         }
     }
+
+    fn record_in_flight_amend(&mut self, request: &OrderRequestAmend<ExchangeKey, InstrumentKey>) {
+        let Some(order) = self.0.get_mut(&request.key.cid) else {
+            error!(
+                cid = %request.key.cid,
+                event = ?request,
+                "OrderManager cannot mark AmendInFlight for untracked Order - ignoring"
+            );
+            return;
+        };
+
+        if let ActiveOrderState::CancelInFlight(_) = order.state {
+            warn!(
+                cid = %request.key.cid,
+                event = ?request,
+                "OrderManager cannot mark AmendInFlight for CancelInFlight Order - ignoring"
+            );
+            return;
+        }
+
+        order.state = ActiveOrderState::AmendInFlight(AmendInFlight {
+            order: order.state.open_meta().cloned(),
+        });
+    }
+
+    fn remove_in_flight_open(&mut self, request: &OrderRequestOpen<ExchangeKey, InstrumentKey>) {
+        if let Entry::Occupied(entry) = self.0.entry(request.key.cid.clone())
+            && matches!(entry.get().state, ActiveOrderState::OpenInFlight(_))
+        {
+            entry.remove();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -397,9 +716,9 @@ mod tests {
     use super::*;
     use crate::{engine::state::order::Orders, test_utils::time_plus_secs};
     use barter_execution::{
-        error::{ConnectivityError, OrderError},
+        error::ConnectivityError,
         order::{
-            Order, OrderKey, OrderKind, TimeInForce,
+            Order, OrderFlags, OrderKey, OrderKind, OrderTrigger, TimeInForce, TriggerSource,
             id::{ClientOrderId, OrderId, StrategyId},
             request::{RequestCancel, RequestOpen},
             state::{ActiveOrderState, CancelInFlight, Cancelled, Open, OpenInFlight},
@@ -587,6 +906,7 @@ mod tests {
                 quantity: dec!(1),
                 kind: OrderKind::Limit,
                 time_in_force: TimeInForce::GoodUntilEndOfDay,
+                flags: OrderFlags::default(),
             },
         }
     }
@@ -618,6 +938,23 @@ mod tests {
         }
     }
 
+    fn response_amend(
+        cid: ClientOrderId,
+        state: Result<Open, OrderError<u64, u64>>,
+    ) -> OrderResponseAmend<ExchangeId, u64, u64> {
+        Order {
+            price: dec!(2),
+            quantity: dec!(3),
+            ..order(cid, state)
+        }
+    }
+
+    fn request_amend(cid: ClientOrderId) -> OrderRequestAmend<ExchangeId, u64> {
+        order(cid, ActiveOrderState::from(open(DateTime::<Utc>::MIN_UTC)))
+            .to_request_amend(dec!(2), dec!(3))
+            .unwrap()
+    }
+
     #[test]
     fn test_update_from_order_snapshot() {
         struct TestCase {
@@ -1132,4 +1469,167 @@ mod tests {
             assert_eq!(test.state, test.expected, "TC{index} failed")
         }
     }
+
+    #[test]
+    fn test_update_from_amend_response() {
+        struct TestCase {
+            name: &'static str,
+            state: Orders<ExchangeId, u64>,
+            input: OrderResponseAmend<ExchangeId, u64, u64>,
+            expected: Orders<ExchangeId, u64>,
+        }
+
+        let cid = ClientOrderId::default();
+        let time_base = DateTime::<Utc>::MIN_UTC;
+        let time_plus = time_plus_secs(time_base, 1);
+        let amend_in_flight = |order| ActiveOrderState::from(AmendInFlight { order });
+        let amended = |state| Order {
+            price: dec!(2),
+            quantity: dec!(3),
+            ..order(cid.clone(), state)
+        };
+
+        let cases = vec![
+            TestCase {
+                name: "untracked, so ignore",
+                state: Orders::default(),
+                input: response_amend(cid.clone(), Ok(open(time_plus))),
+                expected: Orders::default(),
+            },
+            TestCase {
+                name: "tracked AmendInFlight, response Ok, so set amended Open",
+                state: orders([order(cid.clone(), amend_in_flight(Some(open(time_base))))]),
+                input: response_amend(cid.clone(), Ok(open(time_plus))),
+                expected: orders([amended(ActiveOrderState::from(open(time_plus)))]),
+            },
+            TestCase {
+                name: "tracked AmendInFlight, response Ok fully filled, so remove",
+                state: orders([order(cid.clone(), amend_in_flight(Some(open(time_base))))]),
+                input: response_amend(
+                    cid.clone(),
+                    Ok(Open {
+                        filled_quantity: dec!(3),
+                        ..open(time_plus)
+                    }),
+                ),
+                expected: Orders::default(),
+            },
+            TestCase {
+                name: "tracked CancelInFlight, response Ok, so update CancelInFlight.Open",
+                state: orders([order_cancel_in_flight(cid.clone())]),
+                input: response_amend(cid.clone(), Ok(open(time_plus))),
+                expected: orders([amended(ActiveOrderState::from(CancelInFlight {
+                    order: Some(open(time_plus)),
+                }))]),
+            },
+            TestCase {
+                name: "tracked AmendInFlight w/ Some(Open), response Err, so revert Open",
+                state: orders([order(cid.clone(), amend_in_flight(Some(open(time_base))))]),
+                input: response_amend(
+                    cid.clone(),
+                    Err(OrderError::Connectivity(ConnectivityError::Timeout)),
+                ),
+                expected: orders([order(cid.clone(), ActiveOrderState::from(open(time_base)))]),
+            },
+            TestCase {
+                name: "tracked AmendInFlight w/ None Open, response Err, so remove",
+                state: orders([order(cid.clone(), amend_in_flight(None))]),
+                input: response_amend(
+                    cid.clone(),
+                    Err(OrderError::Connectivity(ConnectivityError::Timeout)),
+                ),
+                expected: Orders::default(),
+            },
+            TestCase {
+                name: "tracked AmendInFlight, response Err cancel-replace failed, so remove",
+                state: orders([order(cid.clone(), amend_in_flight(Some(open(time_base))))]),
+                input: response_amend(
+                    cid.clone(),
+                    Err(OrderError::Rejected(ApiError::OrderReplaceFailed(
+                        "order rejected".to_string(),
+                    ))),
+                ),
+                expected: Orders::default(),
+            },
+            TestCase {
+                name: "tracked Open, response Err, so ignore",
+                state: orders([order(cid.clone(), ActiveOrderState::from(open(time_base)))]),
+                input: response_amend(
+                    cid.clone(),
+                    Err(OrderError::Connectivity(ConnectivityError::Timeout)),
+                ),
+                expected: orders([order(cid.clone(), ActiveOrderState::from(open(time_base)))]),
+            },
+        ];
+
+        for mut test in cases.into_iter() {
+            test.state.update_from_amend_response(&test.input);
+            assert_eq!(test.state, test.expected, "TC failed: {}", test.name);
+        }
+    }
+
+    #[test]
+    fn test_record_in_flight_amend() {
+        struct TestCase {
+            state: Orders<ExchangeId, u64>,
+            input: OrderRequestAmend<ExchangeId, u64>,
+            expected: Orders<ExchangeId, u64>,
+        }
+
+        let cid = ClientOrderId::default();
+        let time_base = DateTime::<Utc>::MIN_UTC;
+
+        let cases = vec![
+            TestCase {
+                // TC0: Ignore untracked InFlight
+                state: Orders::default(),
+                input: request_amend(cid.clone()),
+                expected: Orders::default(),
+            },
+            TestCase {
+                // TC1: Open order transitions to AmendInFlight, retaining pre-amend Open
+                state: orders([order(cid.clone(), ActiveOrderState::from(open(time_base)))]),
+                input: request_amend(cid.clone()),
+                expected: orders([order(
+                    cid.clone(),
+                    ActiveOrderState::from(AmendInFlight {
+                        order: Some(open(time_base)),
+                    }),
+                )]),
+            },
+            TestCase {
+                // TC2: Ignore CancelInFlight order, since cancel supersedes amend
+                state: orders([order_cancel_in_flight(cid.clone())]),
+                input: request_amend(cid.clone()),
+                expected: orders([order_cancel_in_flight(cid)]),
+            },
+        ];
+
+        for (index, mut test) in cases.into_iter().enumerate() {
+            test.state.record_in_flight_amend(&test.input);
+            assert_eq!(test.state, test.expected, "TC{index} failed")
+        }
+    }
+
+    #[test]
+    fn test_conditional_order_kind_tracked_through_lifecycle() {
+        let kind = OrderKind::StopMarket {
+            trigger: OrderTrigger::new(dec!(0.9), TriggerSource::Mark),
+        };
+
+        let mut request = request_open(ClientOrderId::new("cid"));
+        request.state.kind = kind;
+
+        let mut state = Orders::default();
+        state.record_in_flight_open(&request);
+
+        // Open snapshot updates the OrderState, retaining the requested conditional OrderKind
+        let snapshot =
+            order_snapshot_open(ClientOrderId::new("cid"), time_plus_secs(Utc::now(), 0));
+        state.update_from_order_snapshot(snapshot.as_ref());
+
+        let order = state.orders().next().unwrap();
+        assert_eq!(order.kind, kind);
+        assert!(matches!(order.state, ActiveOrderState::Open(_)));
+    }
 }
@@ -0,0 +1,576 @@
+use barter_execution::order::{
+    Order, OrderKey,
+    group::{OrderGroup, OrderGroupKind, OrderGroupOpened},
+    id::{ClientOrderId, OrderGroupId},
+    request::{OrderRequestOpen, OrderResponseCancel},
+    state::{ActiveOrderState, InactiveOrderState, OrderState},
+};
+use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
+use barter_integration::{collection::FnvIndexMap, snapshot::Snapshot};
+use derive_more::Constructor;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use tracing::debug;
+
+/// State of every contingent [`OrderGroup`] being tracked by the `Engine`, keyed by
+/// [`OrderGroupId`].
+///
+/// Groups are removed once [`OrderGroupStatus::Completed`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Constructor)]
+pub struct OrderGroupStates<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex>(
+    pub FnvIndexMap<OrderGroupId, OrderGroupState<ExchangeKey, InstrumentKey>>,
+);
+
+impl<ExchangeKey, InstrumentKey> Default for OrderGroupStates<ExchangeKey, InstrumentKey> {
+    fn default() -> Self {
+        Self(FnvIndexMap::default())
+    }
+}
+
+/// State of a contingent [`OrderGroup`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Constructor)]
+pub struct OrderGroupState<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> {
+    pub group: OrderGroup<ExchangeKey, InstrumentKey>,
+
+    /// True if the exchange manages the contingent orders of the group natively, in which case
+    /// the `Engine` only tracks the group status.
+    ///
+    /// Set once the [`OrderGroupOpened`] acknowledgement is received (which always precedes the
+    /// associated order responses), and is false until then.
+    pub native: bool,
+
+    pub status: OrderGroupStatus,
+
+    /// Quantity of the primary order filled so far, used to size the contingent orders of a
+    /// [`OrderGroupKind::OneTriggersOther`] or [`OrderGroupKind::Bracket`] group.
+    #[serde(default)]
+    pub primary_filled_quantity: Decimal,
+}
+
+/// Lifecycle status of an [`OrderGroup`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+pub enum OrderGroupStatus {
+    /// Initial orders of the group have been sent, or are working on the exchange.
+    #[default]
+    Working,
+
+    /// Primary order of a [`OrderGroupKind::Bracket`] group filled, and the contingent orders
+    /// are working as a One-Cancels-Other group.
+    ContingentWorking,
+
+    /// Order with the contained [`ClientOrderId`] filled or ended, and the `Engine` must emulate
+    /// the contingent behaviour of the group (ie/ open the contingent orders, or cancel the
+    /// siblings).
+    ///
+    /// The primary order of a [`OrderGroupKind::OneTriggersOther`] or [`OrderGroupKind::Bracket`]
+    /// group triggers once fully filled, or once ended after partially filling.
+    Triggered(ClientOrderId),
+
+    /// Group has no further contingent behaviour.
+    Completed,
+}
+
+/// Update to a member order of an [`OrderGroup`] that may progress the [`OrderGroupStatus`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+enum OrderUpdate {
+    Working,
+    PartiallyFilled(Decimal),
+    FullyFilled,
+    Ended,
+}
+
+impl<AssetKey, InstrumentKey> From<&OrderState<AssetKey, InstrumentKey>> for OrderUpdate {
+    fn from(value: &OrderState<AssetKey, InstrumentKey>) -> Self {
+        match value {
+            OrderState::Active(ActiveOrderState::OpenInFlight(_)) => Self::Working,
+            OrderState::Active(active) => match active.open_meta() {
+                Some(open) if open.filled_quantity > Decimal::ZERO => {
+                    Self::PartiallyFilled(open.filled_quantity)
+                }
+                _ => Self::Working,
+            },
+            OrderState::Inactive(InactiveOrderState::FullyFilled) => Self::FullyFilled,
+            OrderState::Inactive(_) => Self::Ended,
+        }
+    }
+}
+
+impl<ExchangeKey, InstrumentKey> OrderGroupStates<ExchangeKey, InstrumentKey> {
+    /// Start tracking a newly sent [`OrderGroup`].
+    pub fn record_in_flight(&mut self, group: &OrderGroup<ExchangeKey, InstrumentKey>)
+    where
+        ExchangeKey: Clone,
+        InstrumentKey: Clone,
+    {
+        self.0.insert(
+            group.id.clone(),
+            OrderGroupState::new(
+                group.clone(),
+                false,
+                OrderGroupStatus::Working,
+                Decimal::ZERO,
+            ),
+        );
+    }
+
+    /// Update the [`OrderGroupState::native`] flag from an [`OrderGroupOpened`] acknowledgement.
+    pub fn update_from_opened(&mut self, opened: &OrderGroupOpened) {
+        if let Some(state) = self.0.get_mut(&opened.id) {
+            state.native = opened.native;
+        }
+    }
+
+    /// Update the status of the [`OrderGroup`] containing the provided order snapshot, if any.
+    pub fn update_from_order_snapshot<AssetKey>(
+        &mut self,
+        order: Snapshot<&Order<ExchangeKey, InstrumentKey, OrderState<AssetKey, InstrumentKey>>>,
+    ) where
+        ExchangeKey: Debug,
+        InstrumentKey: Debug,
+    {
+        let order = order.value();
+        self.update_from_order(&order.key, OrderUpdate::from(&order.state));
+    }
+
+    /// Update the status of the [`OrderGroup`] containing the cancelled order, if any.
+    ///
+    /// Failed cancel requests leave the group status unchanged.
+    pub fn update_from_cancel_response<AssetKey>(
+        &mut self,
+        response: &OrderResponseCancel<ExchangeKey, AssetKey, InstrumentKey>,
+    ) where
+        ExchangeKey: Debug,
+        InstrumentKey: Debug,
+    {
+        if response.state.is_ok() {
+            self.update_from_order(&response.key, OrderUpdate::Ended)
+        }
+    }
+
+    fn update_from_order(&mut self, key: &OrderKey<ExchangeKey, InstrumentKey>, update: OrderUpdate)
+    where
+        ExchangeKey: Debug,
+        InstrumentKey: Debug,
+    {
+        let Some((id, state)) = self
+            .0
+            .iter_mut()
+            .find(|(_, state)| state.group.contains(&key.cid))
+        else {
+            return;
+        };
+
+        state.update_primary_filled_quantity(&key.cid, update);
+        state.status = state.next_status(&key.cid, update);
+
+        if state.status == OrderGroupStatus::Completed {
+            debug!(group = %id, ?key, "OrderGroup completed");
+            let id = id.clone();
+            self.0.shift_remove(&id);
+        }
+    }
+
+    /// Transition every `Triggered` [`OrderGroupState`], returning the contingent orders the
+    /// `Engine` must open, and the keys of the sibling orders it must cancel, to emulate the
+    /// contingent behaviour of the groups.
+    ///
+    /// A triggered [`OrderGroupKind::Bracket`] primary order transitions the group to
+    /// [`OrderGroupStatus::ContingentWorking`], otherwise triggered groups are completed.
+    ///
+    /// Contingent order quantities are capped at the primary filled quantity, so a primary order
+    /// that ended after partially filling only opens contingent orders for the filled quantity.
+    pub fn emulate_triggered(
+        &mut self,
+    ) -> (
+        Vec<OrderRequestOpen<ExchangeKey, InstrumentKey>>,
+        Vec<OrderKey<ExchangeKey, InstrumentKey>>,
+    )
+    where
+        ExchangeKey: Clone,
+        InstrumentKey: Clone,
+    {
+        let mut opens = Vec::new();
+        let mut cancels = Vec::new();
+
+        self.0.retain(|id, state| {
+            let OrderGroupStatus::Triggered(cid) = &state.status else {
+                return true;
+            };
+
+            let primary = &state.group.primary.key.cid == cid;
+
+            state.status = match state.group.kind {
+                OrderGroupKind::OneTriggersOther if primary => {
+                    opens.extend(state.contingent_opens());
+                    OrderGroupStatus::Completed
+                }
+                OrderGroupKind::Bracket if primary => {
+                    opens.extend(state.contingent_opens());
+                    OrderGroupStatus::ContingentWorking
+                }
+                _ => {
+                    cancels.extend(state.siblings(cid).cloned());
+                    OrderGroupStatus::Completed
+                }
+            };
+
+            debug!(group = %id, status = ?state.status, "OrderGroup triggered");
+            state.status != OrderGroupStatus::Completed
+        });
+
+        (opens, cancels)
+    }
+}
+
+impl<ExchangeKey, InstrumentKey> OrderGroupState<ExchangeKey, InstrumentKey> {
+    /// Update the [`Self::primary_filled_quantity`] after an update to the group order with the
+    /// provided [`ClientOrderId`].
+    fn update_primary_filled_quantity(&mut self, cid: &ClientOrderId, update: OrderUpdate) {
+        if &self.group.primary.key.cid != cid {
+            return;
+        }
+
+        match update {
+            OrderUpdate::PartiallyFilled(filled_quantity) => {
+                self.primary_filled_quantity = self.primary_filled_quantity.max(filled_quantity)
+            }
+            OrderUpdate::FullyFilled => {
+                self.primary_filled_quantity = self.group.primary.state.quantity
+            }
+            OrderUpdate::Working | OrderUpdate::Ended => {}
+        }
+    }
+
+    /// Returns the contingent orders to open once the primary order has `Triggered` the group,
+    /// with quantities capped at the primary filled quantity.
+    fn contingent_opens(
+        &self,
+    ) -> impl Iterator<Item = OrderRequestOpen<ExchangeKey, InstrumentKey>> + '_
+    where
+        ExchangeKey: Clone,
+        InstrumentKey: Clone,
+    {
+        self.group.contingent.iter().cloned().map(|mut open| {
+            open.state.quantity = open.state.quantity.min(self.primary_filled_quantity);
+            open
+        })
+    }
+
+    /// Determine the next [`OrderGroupStatus`] after an update to the group order with the
+    /// provided [`ClientOrderId`].
+    fn next_status(&self, cid: &ClientOrderId, update: OrderUpdate) -> OrderGroupStatus {
+        let primary = &self.group.primary.key.cid == cid;
+
+        match (&self.status, self.group.kind, update) {
+            // No further contingent behaviour to track until the Engine emulates the trigger
+            (OrderGroupStatus::Triggered(_) | OrderGroupStatus::Completed, _, _)
+            | (_, _, OrderUpdate::Working) => self.status.clone(),
+
+            // Exchange manages the contingent orders, so any fill or ended order completes it
+            _ if self.native => OrderGroupStatus::Completed,
+
+            // Any fill or ended order of a One-Cancels-Other group cancels the others
+            (OrderGroupStatus::Working, OrderGroupKind::OneCancelsOther, _) => {
+                OrderGroupStatus::Triggered(cid.clone())
+            }
+
+            // Contingent orders are opened once the primary order is fully filled
+            (
+                OrderGroupStatus::Working,
+                OrderGroupKind::OneTriggersOther | OrderGroupKind::Bracket,
+                OrderUpdate::FullyFilled,
+            ) if primary => OrderGroupStatus::Triggered(cid.clone()),
+
+            // Primary order ended after partially filling, so the contingent orders are opened
+            // for the filled quantity
+            (
+                OrderGroupStatus::Working,
+                OrderGroupKind::OneTriggersOther | OrderGroupKind::Bracket,
+                OrderUpdate::Ended,
+            ) if primary && self.primary_filled_quantity > Decimal::ZERO => {
+                OrderGroupStatus::Triggered(cid.clone())
+            }
+
+            // Primary order ended without filling, so the contingent orders are never opened
+            (
+                OrderGroupStatus::Working,
+                OrderGroupKind::OneTriggersOther | OrderGroupKind::Bracket,
+                OrderUpdate::Ended,
+            ) if primary => OrderGroupStatus::Completed,
+
+            // Bracket contingent orders behave as a One-Cancels-Other group
+            (OrderGroupStatus::ContingentWorking, OrderGroupKind::Bracket, _) if !primary => {
+                OrderGroupStatus::Triggered(cid.clone())
+            }
+
+            _ => self.status.clone(),
+        }
+    }
+
+    /// Returns an `Iterator` of the group orders to cancel once the order with the provided
+    /// [`ClientOrderId`] has `Triggered` the group.
+    pub fn siblings<'a>(
+        &'a self,
+        cid: &'a ClientOrderId,
+    ) -> impl Iterator<Item = &'a OrderKey<ExchangeKey, InstrumentKey>> {
+        // Primary order of a OneTriggersOther or Bracket group is never cancelled by a trigger
+        let primary = match self.group.kind {
+            OrderGroupKind::OneCancelsOther => Some(&self.group.primary),
+            OrderGroupKind::OneTriggersOther | OrderGroupKind::Bracket => None,
+        };
+
+        primary
+            .into_iter()
+            .chain(&self.group.contingent)
+            .map(|order| &order.key)
+            .filter(move |key| &key.cid != cid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_execution::order::{
+        OrderFlags, OrderKind, TimeInForce,
+        id::{OrderId, StrategyId},
+        request::RequestOpen,
+        state::{Cancelled, Open},
+    };
+    use barter_instrument::{Side, exchange::ExchangeId};
+    use chrono::{DateTime, Utc};
+    use rust_decimal_macros::dec;
+
+    fn request_open(cid: &str) -> OrderRequestOpen<ExchangeId, u64> {
+        OrderRequestOpen {
+            key: key(cid),
+            state: RequestOpen {
+                side: Side::Buy,
+                price: dec!(1),
+                quantity: dec!(1),
+                kind: OrderKind::Limit,
+                time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+                flags: OrderFlags::default(),
+            },
+        }
+    }
+
+    fn key(cid: &str) -> OrderKey<ExchangeId, u64> {
+        OrderKey {
+            exchange: ExchangeId::Simulated,
+            instrument: 1,
+            strategy: StrategyId::unknown(),
+            cid: ClientOrderId::new(cid),
+        }
+    }
+
+    fn group_states(kind: OrderGroupKind, native: bool) -> OrderGroupStates<ExchangeId, u64> {
+        let mut groups = OrderGroupStates::default();
+        groups.record_in_flight(&OrderGroup::new(
+            OrderGroupId::new("group"),
+            kind,
+            request_open("primary"),
+            vec![request_open("take_profit"), request_open("stop_loss")],
+        ));
+        groups.update_from_opened(&OrderGroupOpened::new(OrderGroupId::new("group"), native));
+        groups
+    }
+
+    fn snapshot(
+        cid: &str,
+        state: OrderState<u64, u64>,
+    ) -> Order<ExchangeId, u64, OrderState<u64, u64>> {
+        let OrderRequestOpen { key, state: open } = request_open(cid);
+        Order {
+            key,
+            side: open.side,
+            price: open.price,
+            quantity: open.quantity,
+            kind: open.kind,
+            time_in_force: open.time_in_force,
+            state,
+        }
+    }
+
+    fn filled(quantity: Decimal) -> OrderState<u64, u64> {
+        OrderState::active(Open::new(
+            OrderId::new("id"),
+            DateTime::<Utc>::MIN_UTC,
+            quantity,
+        ))
+    }
+
+    fn cancelled() -> OrderState<u64, u64> {
+        OrderState::inactive(Cancelled::new(OrderId::new("id"), DateTime::<Utc>::MIN_UTC))
+    }
+
+    fn status(groups: &OrderGroupStates<ExchangeId, u64>) -> Option<OrderGroupStatus> {
+        groups
+            .0
+            .get(&OrderGroupId::new("group"))
+            .map(|state| state.status.clone())
+    }
+
+    #[test]
+    fn test_update_from_order_snapshot() {
+        struct TestCase {
+            kind: OrderGroupKind,
+            native: bool,
+            cid: &'static str,
+            state: OrderState<u64, u64>,
+            expected: Option<OrderGroupStatus>,
+        }
+
+        let triggered = |cid| Some(OrderGroupStatus::Triggered(ClientOrderId::new(cid)));
+
+        let tests = vec![
+            TestCase {
+                // TC0: OCO working order update does not trigger
+                kind: OrderGroupKind::OneCancelsOther,
+                native: false,
+                cid: "stop_loss",
+                state: filled(dec!(0)),
+                expected: Some(OrderGroupStatus::Working),
+            },
+            TestCase {
+                // TC1: OCO partial fill triggers
+                kind: OrderGroupKind::OneCancelsOther,
+                native: false,
+                cid: "stop_loss",
+                state: filled(dec!(0.5)),
+                expected: triggered("stop_loss"),
+            },
+            TestCase {
+                // TC2: OCO cancelled order triggers
+                kind: OrderGroupKind::OneCancelsOther,
+                native: false,
+                cid: "primary",
+                state: cancelled(),
+                expected: triggered("primary"),
+            },
+            TestCase {
+                // TC3: native OCO fill completes the group
+                kind: OrderGroupKind::OneCancelsOther,
+                native: true,
+                cid: "primary",
+                state: OrderState::fully_filled(),
+                expected: None,
+            },
+            TestCase {
+                // TC4: Bracket primary partial fill does not trigger
+                kind: OrderGroupKind::Bracket,
+                native: false,
+                cid: "primary",
+                state: filled(dec!(0.5)),
+                expected: Some(OrderGroupStatus::Working),
+            },
+            TestCase {
+                // TC5: Bracket primary full fill triggers
+                kind: OrderGroupKind::Bracket,
+                native: false,
+                cid: "primary",
+                state: OrderState::fully_filled(),
+                expected: triggered("primary"),
+            },
+            TestCase {
+                // TC6: OTO primary cancelled completes the group
+                kind: OrderGroupKind::OneTriggersOther,
+                native: false,
+                cid: "primary",
+                state: cancelled(),
+                expected: None,
+            },
+            TestCase {
+                // TC7: unrelated order is ignored
+                kind: OrderGroupKind::OneTriggersOther,
+                native: false,
+                cid: "unrelated",
+                state: OrderState::fully_filled(),
+                expected: Some(OrderGroupStatus::Working),
+            },
+        ];
+
+        for (index, test) in tests.into_iter().enumerate() {
+            let mut groups = group_states(test.kind, test.native);
+            let order = snapshot(test.cid, test.state);
+            groups.update_from_order_snapshot(Snapshot(&order));
+            assert_eq!(status(&groups), test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_emulate_triggered() {
+        // OCO fill cancels every sibling, and completes the group
+        let mut groups = group_states(OrderGroupKind::OneCancelsOther, false);
+        let order = snapshot("take_profit", OrderState::fully_filled());
+        groups.update_from_order_snapshot(Snapshot(&order));
+        let (opens, cancels) = groups.emulate_triggered();
+        assert!(opens.is_empty());
+        assert_eq!(cancels, vec![key("primary"), key("stop_loss")]);
+        assert_eq!(status(&groups), None);
+
+        // Bracket primary fill opens the contingent orders
+        let mut groups = group_states(OrderGroupKind::Bracket, false);
+        let order = snapshot("primary", OrderState::fully_filled());
+        groups.update_from_order_snapshot(Snapshot(&order));
+        let (opens, cancels) = groups.emulate_triggered();
+        assert_eq!(
+            opens,
+            vec![request_open("take_profit"), request_open("stop_loss")]
+        );
+        assert!(cancels.is_empty());
+        assert_eq!(status(&groups), Some(OrderGroupStatus::ContingentWorking));
+
+        // Bracket contingent fill cancels the other contingent order only
+        let order = snapshot("stop_loss", filled(dec!(0.1)));
+        groups.update_from_order_snapshot(Snapshot(&order));
+        let (opens, cancels) = groups.emulate_triggered();
+        assert!(opens.is_empty());
+        assert_eq!(cancels, vec![key("take_profit")]);
+        assert_eq!(status(&groups), None);
+
+        // Bracket primary cancelled after partially filling opens contingent orders for the
+        // filled quantity
+        let mut groups = group_states(OrderGroupKind::Bracket, false);
+        let order = snapshot("primary", filled(dec!(0.4)));
+        groups.update_from_order_snapshot(Snapshot(&order));
+        assert_eq!(status(&groups), Some(OrderGroupStatus::Working));
+        let order = snapshot("primary", cancelled());
+        groups.update_from_order_snapshot(Snapshot(&order));
+        let (opens, cancels) = groups.emulate_triggered();
+        assert_eq!(
+            opens
+                .iter()
+                .map(|open| (open.key.cid.clone(), open.state.quantity))
+                .collect::<Vec<_>>(),
+            vec![
+                (ClientOrderId::new("take_profit"), dec!(0.4)),
+                (ClientOrderId::new("stop_loss"), dec!(0.4))
+            ]
+        );
+        assert!(cancels.is_empty());
+        assert_eq!(status(&groups), Some(OrderGroupStatus::ContingentWorking));
+
+        // OTO primary fill opens the contingent orders, and completes the group
+        let mut groups = group_states(OrderGroupKind::OneTriggersOther, false);
+        let order = snapshot("primary", OrderState::fully_filled());
+        groups.update_from_order_snapshot(Snapshot(&order));
+        let (opens, cancels) = groups.emulate_triggered();
+        assert_eq!(opens.len(), 2);
+        assert!(cancels.is_empty());
+        assert_eq!(status(&groups), None);
+
+        // OTO primary expired after partially filling opens contingent orders for the filled
+        // quantity, and completes the group
+        let mut groups = group_states(OrderGroupKind::OneTriggersOther, false);
+        let order = snapshot("primary", filled(dec!(0.25)));
+        groups.update_from_order_snapshot(Snapshot(&order));
+        let order = snapshot("primary", OrderState::expired());
+        groups.update_from_order_snapshot(Snapshot(&order));
+        let (opens, cancels) = groups.emulate_triggered();
+        assert!(opens.iter().all(|open| open.state.quantity == dec!(0.25)));
+        assert_eq!(opens.len(), 2);
+        assert!(cancels.is_empty());
+        assert_eq!(status(&groups), None);
+    }
+}
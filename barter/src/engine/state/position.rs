@@ -1,4 +1,8 @@
-use barter_execution::trade::{AssetFees, Trade, TradeId};
+use barter_execution::{
+    funding::FundingPayment,
+    position::ExchangePosition,
+    trade::{AssetFees, Trade, TradeId},
+};
 use barter_instrument::{
     Side,
     asset::{AssetIndex, QuoteAsset},
@@ -9,7 +13,7 @@ use derive_more::Constructor;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
-use tracing::error;
+use tracing::{error, warn};
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize, Constructor)]
 pub struct PositionManager<InstrumentKey = InstrumentIndex> {
@@ -52,6 +56,66 @@ impl<InstrumentKey> PositionManager<InstrumentKey> {
 
         closed
     }
+
+    /// Updates the current position state based on a new perpetual [`FundingPayment`].
+    ///
+    /// Funding settled while there is no current position is ignored.
+    pub fn update_from_funding(&mut self, funding: &FundingPayment<InstrumentKey>)
+    where
+        InstrumentKey: Debug + PartialEq,
+    {
+        if let Some(position) = &mut self.current {
+            position.update_from_funding(funding);
+        }
+    }
+
+    /// Reconciles the current position against an exchange reported [`ExchangePosition`].
+    ///
+    /// The exchange is the source of truth, so if the position side or quantity differs (eg/
+    /// trades were missed while disconnected), the current position is replaced with one
+    /// constructed from the [`ExchangePosition`]. Matching positions are left unchanged.
+    ///
+    /// Returns true if the current position was replaced.
+    pub fn reconcile(&mut self, exchange: &ExchangePosition<InstrumentKey>) -> bool
+    where
+        InstrumentKey: Debug + Clone,
+    {
+        let reconciled = match (&self.current, exchange.side()) {
+            (None, None) => true,
+            (Some(current), Some(side)) => {
+                current.side == side && current.quantity_abs == exchange.quantity_abs()
+            }
+            _ => false,
+        };
+
+        if reconciled {
+            return false;
+        }
+
+        warn!(
+            current = ?self.current,
+            ?exchange,
+            "PositionManager replacing current Position with mismatched exchange reported position"
+        );
+
+        self.current = exchange.side().map(|side| Position {
+            instrument: exchange.instrument.clone(),
+            side,
+            price_entry_average: exchange.price_entry,
+            quantity_abs: exchange.quantity_abs(),
+            quantity_abs_max: exchange.quantity_abs(),
+            pnl_unrealised: exchange.pnl_unrealised,
+            pnl_realised: Decimal::ZERO,
+            pnl_funding: Decimal::ZERO,
+            fees_enter: AssetFees::default(),
+            fees_exit: AssetFees::default(),
+            time_enter: exchange.time_exchange,
+            time_exchange_update: exchange.time_exchange,
+            trades: vec![],
+        });
+
+        true
+    }
 }
 
 /// Represents an open trading position for a specific instrument.
@@ -189,7 +253,16 @@ pub struct Position<AssetKey = AssetIndex, InstrumentKey = InstrumentIndex> {
     /// Note this includes fees.
     pub pnl_realised: Decimal,
 
+    /// Cumulative perpetual funding received (positive) or paid (negative) while the
+    /// [`Position`] has been open.
+    ///
+    /// Note this is not included in `pnl_realised`.
+    pub pnl_funding: Decimal,
+
     /// Cumulative fees paid when entering/increasing [`Position`] quantity.
+    ///
+    /// Note fees are denominated in the quote asset, regardless of the asset they were charged
+    /// in, and are negative if rebates exceeded the fees paid.
     pub fees_enter: AssetFees<AssetKey>,
 
     /// Cumulative fees paid when exiting/reducing [`Position`] quantity.
@@ -327,6 +400,25 @@ impl<InstrumentKey> Position<QuoteAsset, InstrumentKey> {
         }
     }
 
+    /// Updates the [`Position`] `pnl_funding` from a new perpetual [`FundingPayment`].
+    pub fn update_from_funding(&mut self, funding: &FundingPayment<InstrumentKey>)
+    where
+        InstrumentKey: Debug + PartialEq,
+    {
+        // Sanity check
+        if self.instrument != funding.instrument {
+            error!(
+                position = ?self,
+                funding = ?funding,
+                "Position tried to be updated from a FundingPayment for a different Instrument - ignoring"
+            );
+            return;
+        }
+
+        self.pnl_funding += funding.amount;
+        self.time_exchange_update = funding.time_exchange;
+    }
+
     /// Updates the volume-weighted average entry price of the [`Position`].
     ///
     /// Internally uses the logic defined in [`calculate_price_entry_average`].
@@ -388,6 +480,7 @@ where
             quantity_abs_max: trade.quantity.abs(),
             pnl_unrealised: Decimal::ZERO,
             pnl_realised: -trade.fees.fees,
+            pnl_funding: Decimal::ZERO,
             fees_enter: trade.fees.clone(),
             fees_exit: AssetFees::default(),
             time_enter: trade.time_exchange,
@@ -425,7 +518,16 @@ pub struct PositionExited<AssetKey, InstrumentKey = InstrumentIndex> {
     /// Note this includes fees.
     pub pnl_realised: Decimal,
 
+    /// Cumulative perpetual funding received (positive) or paid (negative) while the
+    /// [`Position`] was open.
+    ///
+    /// Note this is not included in `pnl_realised`.
+    pub pnl_funding: Decimal,
+
     /// Cumulative fees paid when entering the [`Position`].
+    ///
+    /// Note fees are denominated in the quote asset, and are negative if rebates exceeded the
+    /// fees paid.
     pub fees_enter: AssetFees<AssetKey>,
 
     /// Cumulative fees paid when exiting the [`Position`].
@@ -451,6 +553,7 @@ impl<AssetKey, InstrumentKey> From<Position<AssetKey, InstrumentKey>>
             price_entry_average: value.price_entry_average,
             quantity_abs_max: value.quantity_abs_max,
             pnl_realised: value.pnl_realised,
+            pnl_funding: value.pnl_funding,
             fees_enter: value.fees_enter,
             fees_exit: value.fees_exit,
             time_enter: value.time_enter,
@@ -585,6 +688,7 @@ mod tests {
                     quantity_abs_max: dec!(2.0),
                     pnl_unrealised: dec!(0.0),
                     pnl_realised: dec!(-20.0), // Sum of fees
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(20.0),
@@ -611,6 +715,7 @@ mod tests {
                     quantity_abs_max: dec!(2.0),
                     pnl_unrealised: dec!(67.5), // (150-100)*(2.0-0.5) - approx_exit_fees (1.5/2 * 10)
                     pnl_realised: dec!(10.0),   // (150-100)*0.5 - 15_fees
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -636,6 +741,7 @@ mod tests {
                     price_entry_average: dec!(100.0),
                     quantity_abs_max: dec!(1.0),
                     pnl_realised: dec!(30.0), // (150-100)*1 - 20 (total fees)
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -661,6 +767,7 @@ mod tests {
                     quantity_abs_max: dec!(1.0),
                     pnl_unrealised: dec!(0.0),
                     pnl_realised: dec!(-10.0), // Entry fees for new position (2-1)*(1/2)*20
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -679,6 +786,7 @@ mod tests {
                     price_entry_average: dec!(100.0),
                     quantity_abs_max: dec!(1.0),
                     pnl_realised: dec!(30.0), // (150-100)*1 - 20 (total fees)
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -704,6 +812,7 @@ mod tests {
                     quantity_abs_max: dec!(2.0),
                     pnl_unrealised: dec!(0.0), // (90-80)*2 - approx_exit_fees(2/2 * 20)
                     pnl_realised: dec!(-20.0), // Sum of entry fees
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(20.0),
@@ -730,6 +839,7 @@ mod tests {
                     quantity_abs_max: dec!(2.0),
                     pnl_unrealised: dec!(22.5), // (100-80)*1.5 - approx_exit_fees(1.5/2 * 10)
                     pnl_realised: dec!(-5.0),   // 10_fee_entry - (100-80)*0.5 - 5_fee_exit
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -755,6 +865,7 @@ mod tests {
                     price_entry_average: dec!(100.0),
                     quantity_abs_max: dec!(1.0),
                     pnl_realised: dec!(0.0), // (100-80)*1 - 20 (total fees)
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -780,6 +891,7 @@ mod tests {
                     quantity_abs_max: dec!(1.0),
                     pnl_unrealised: dec!(0.0),
                     pnl_realised: dec!(-10.0), // Entry fees for new position
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -798,6 +910,7 @@ mod tests {
                     price_entry_average: dec!(100.0),
                     quantity_abs_max: dec!(1.0),
                     pnl_realised: dec!(0.0), // (100-80)*1 - 20 (total fees)
+                    pnl_funding: dec!(0.0),
                     fees_enter: AssetFees {
                         asset: QuoteAsset,
                         fees: dec!(10.0),
@@ -811,6 +924,58 @@ mod tests {
                     trades: vec![TradeId::new("trade_id"), TradeId::new("trade_id")],
                 }),
             },
+            // TC8: Partial reduce long position entered with a maker rebate
+            TestCase {
+                initial_trade: trade(base_time, Side::Buy, 100.0, 2.0, -1.0),
+                update_trade: trade(time_plus_days(base_time, 1), Side::Sell, 150.0, 1.0, 3.0),
+                expected_position: Some(Position {
+                    instrument: InstrumentNameInternal::new("instrument"),
+                    side: Side::Buy,
+                    price_entry_average: dec!(100.0),
+                    quantity_abs: dec!(1.0),
+                    quantity_abs_max: dec!(2.0),
+                    pnl_unrealised: dec!(50.5), // (150-100)*1 - approx_exit_fees (1/2 * -1)
+                    pnl_realised: dec!(48.0),   // (150-100)*1 + 1 rebate - 3 fees
+                    pnl_funding: dec!(0.0),
+                    fees_enter: AssetFees {
+                        asset: QuoteAsset,
+                        fees: dec!(-1.0),
+                    },
+                    fees_exit: AssetFees {
+                        asset: QuoteAsset,
+                        fees: dec!(3.0),
+                    },
+                    time_enter: base_time,
+                    time_exchange_update: time_plus_days(base_time, 1),
+                    trades: vec![TradeId::new("trade_id"), TradeId::new("trade_id")],
+                }),
+                expected_position_exited: None,
+            },
+            // TC9: Exact short position close, entered and exited with maker rebates
+            TestCase {
+                initial_trade: trade(base_time, Side::Sell, 100.0, 1.0, -0.5),
+                update_trade: trade(base_time, Side::Buy, 90.0, 1.0, -0.45),
+                expected_position: None,
+                expected_position_exited: Some(PositionExited {
+                    instrument: InstrumentNameInternal::new("instrument"),
+                    side: Side::Sell,
+                    price_entry_average: dec!(100.0),
+                    quantity_abs_max: dec!(1.0),
+                    pnl_realised: dec!(10.95), // (100-90)*1 + 0.95 (total rebates)
+                    pnl_funding: dec!(0.0),
+                    fees_enter: AssetFees {
+                        asset: QuoteAsset,
+                        fees: dec!(-0.5),
+                    },
+                    fees_exit: AssetFees {
+                        asset: QuoteAsset,
+                        fees: dec!(-0.45),
+                    },
+                    time_enter: base_time,
+                    time_exit: base_time,
+                    trades: vec![TradeId::new("trade_id"), TradeId::new("trade_id")],
+                }),
+            },
         ];
 
         for (index, test) in cases.into_iter().enumerate() {
@@ -826,6 +991,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_position_manager_update_from_funding() {
+        let base_time = DateTime::<Utc>::MIN_UTC;
+        let funding = |time_exchange, amount| FundingPayment {
+            instrument: InstrumentNameInternal::new("instrument"),
+            time_exchange,
+            rate: dec!(0.0001),
+            price_mark: dec!(100.0),
+            quantity: dec!(1.0),
+            amount,
+        };
+
+        // Funding settled without a current Position is ignored
+        let mut manager = PositionManager::default();
+        manager.update_from_funding(&funding(base_time, dec!(-1.0)));
+        assert!(manager.current.is_none());
+
+        // Funding paid & received accumulates in the current Position pnl_funding
+        manager.update_from_trade(&trade(base_time, Side::Buy, 100.0, 1.0, 0.0));
+        manager.update_from_funding(&funding(time_plus_days(base_time, 1), dec!(-0.01)));
+        manager.update_from_funding(&funding(time_plus_days(base_time, 2), dec!(0.004)));
+        let position = manager.current.as_ref().unwrap();
+        assert_eq!(position.pnl_funding, dec!(-0.006));
+        assert_eq!(position.time_exchange_update, time_plus_days(base_time, 2));
+
+        // Funding is carried into the PositionExited, separately from pnl_realised
+        let exited = manager
+            .update_from_trade(&trade(
+                time_plus_days(base_time, 3),
+                Side::Sell,
+                110.0,
+                1.0,
+                0.0,
+            ))
+            .unwrap();
+        assert_eq!(exited.pnl_realised, dec!(10.0));
+        assert_eq!(exited.pnl_funding, dec!(-0.006));
+    }
+
+    #[test]
+    fn test_position_manager_reconcile() {
+        let base_time = DateTime::<Utc>::MIN_UTC;
+        let exchange = |quantity, price_entry| ExchangePosition {
+            instrument: InstrumentNameInternal::new("instrument"),
+            quantity,
+            price_entry,
+            pnl_unrealised: dec!(5.0),
+            time_exchange: time_plus_days(base_time, 1),
+        };
+
+        // Flat exchange position matches no current Position
+        let mut manager = PositionManager::default();
+        assert!(!manager.reconcile(&exchange(dec!(0.0), dec!(0.0))));
+        assert!(manager.current.is_none());
+
+        // Matching exchange position leaves the current Position unchanged
+        manager.update_from_trade(&trade(base_time, Side::Buy, 100.0, 1.0, 1.0));
+        let expected = manager.current.clone();
+        assert!(!manager.reconcile(&exchange(dec!(1.0), dec!(100.0))));
+        assert_eq!(manager.current, expected);
+
+        // Mismatched exchange position replaces the current Position
+        assert!(manager.reconcile(&exchange(dec!(-2.0), dec!(105.0))));
+        let position = manager.current.as_ref().unwrap();
+        assert_eq!(position.side, Side::Sell);
+        assert_eq!(position.quantity_abs, dec!(2.0));
+        assert_eq!(position.price_entry_average, dec!(105.0));
+        assert_eq!(position.pnl_unrealised, dec!(5.0));
+        assert!(position.trades.is_empty());
+
+        // Flat exchange position closes the current Position
+        assert!(manager.reconcile(&exchange(dec!(0.0), dec!(0.0))));
+        assert!(manager.current.is_none());
+    }
+
     #[test]
     fn test_calculate_price_entry_average() {
         struct TestCase {
@@ -67,6 +67,11 @@ impl TradingStateUpdateAudit {
     pub fn transitioned_to_disabled(&self) -> bool {
         self.current == TradingState::Disabled && self.prev != TradingState::Disabled
     }
+
+    /// Returns true only if the previous state was not `Enabled`, and the new state is.
+    pub fn transitioned_to_enabled(&self) -> bool {
+        self.current == TradingState::Enabled && self.prev != TradingState::Enabled
+    }
 }
 
 #[cfg(test)]
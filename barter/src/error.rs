@@ -12,6 +12,9 @@ pub enum BarterError {
     #[error("ExecutionBuilder: {0}")]
     ExecutionBuilder(String),
 
+    #[error("config: {0}")]
+    Config(String),
+
     #[error("ExchangeManager dropped it's ExecutionRequest receiver")]
     ExecutionRxDropped(#[from] RxDropped),
 
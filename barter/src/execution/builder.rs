@@ -2,11 +2,16 @@ use crate::{
     engine::{clock::EngineClock, execution_tx::MultiExchangeTxMap},
     error::BarterError,
     execution::{
-        AccountStreamEvent, Execution, error::ExecutionError, manager::ExecutionManager,
+        AccountStreamEvent, Execution,
+        error::ExecutionError,
+        manager::ExecutionManager,
+        mock::{MockExecutionRequestStream, MockMarketTxMap},
         request::ExecutionRequest,
     },
     shutdown::AsyncShutdown,
+    system::config::{ExchangeEnvironment, LiveExchangeConfig, LiveExecutionConfig},
 };
+use barter_data::event::{DataKind, MarketEvent};
 use barter_data::streams::{
     consumer::STREAM_RECONNECTION_POLICY, reconnect::stream::ReconnectingStream,
 };
@@ -14,7 +19,15 @@ use barter_execution::{
     UnindexedAccountEvent,
     client::{
         ExecutionClient,
+        binance::{
+            futures::{BinanceFuturesUsd, BinanceFuturesUsdConfig},
+            spot::{BinanceSpot, BinanceSpotConfig},
+        },
+        bybit::{BybitConfig, BybitPerpetualsUsd, BybitSpot},
+        coinbase::{Coinbase, CoinbaseConfig},
+        kraken::{Kraken, KrakenConfig},
         mock::{MockExecution, MockExecutionClientConfig, MockExecutionConfig},
+        okx::{Okx, OkxConfig},
     },
     exchange::mock::{MockExchange, request::MockExchangeRequest},
     indexer::AccountEventIndexer,
@@ -32,9 +45,9 @@ use barter_instrument::{
         spec::{InstrumentSpec, InstrumentSpecQuantity, OrderQuantityUnits},
     },
 };
-use barter_integration::channel::{Channel, UnboundedTx, mpsc_unbounded};
+use barter_integration::channel::{Channel, UnboundedRx, UnboundedTx, mpsc_unbounded};
 use fnv::FnvHashMap;
-use futures::{FutureExt, future::try_join_all};
+use futures::{FutureExt, Stream, future::try_join_all};
 use std::{pin::Pin, sync::Arc, time::Duration};
 use tokio::{
     sync::{broadcast, mpsc},
@@ -58,12 +71,14 @@ type RunFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
 /// - Building mock execution managers (mocks a specific exchange internally via the [`MockExchange`]).
 /// - Building live execution managers, setting up an external connection to each exchange.
 /// - Constructs a [`MultiExchangeTxMap`] with an entry for each mock/live execution manager.
+/// - Constructs a [`MockMarketTxMap`] used to forward market data to each [`MockExchange`].
 /// - Combines all exchange account streams into a unified [`AccountStreamEvent`] `Stream`.
 #[allow(missing_debug_implementations)]
 pub struct ExecutionBuilder<'a> {
     instruments: &'a IndexedInstruments,
     execution_txs: FnvHashMap<ExchangeId, (ExchangeIndex, UnboundedTx<ExecutionRequest>)>,
     merged_channel: Channel<AccountStreamEvent<ExchangeIndex, AssetIndex, InstrumentIndex>>,
+    mock_market_tx_map: MockMarketTxMap,
     mock_exchange_futures: Vec<RunFuture>,
     execution_init_futures: Vec<ExecutionInitFuture>,
 }
@@ -75,6 +90,7 @@ impl<'a> ExecutionBuilder<'a> {
             instruments,
             execution_txs: FnvHashMap::default(),
             merged_channel: Channel::default(),
+            mock_market_tx_map: MockMarketTxMap::default(),
             mock_exchange_futures: Vec::default(),
             execution_init_futures: Vec::default(),
         }
@@ -85,6 +101,10 @@ impl<'a> ExecutionBuilder<'a> {
     ///
     /// The provided [`MockExecutionConfig`] is used to configure the [`MockExchange`] and provide
     /// the initial account state.
+    ///
+    /// The [`MockExchange`] simulates fills using the market data forwarded to it via the
+    /// [`MockMarketTxMap`], which is passed on by the [`ExecutionManager`] after any requests
+    /// the `Engine` sent before it (see [`MockExecutionRequestStream`]).
     pub fn add_mock<Clock>(
         mut self,
         config: MockExecutionConfig,
@@ -97,23 +117,38 @@ impl<'a> ExecutionBuilder<'a> {
         const DUMMY_EXECUTION_REQUEST_TIMEOUT: Duration = Duration::from_secs(1);
 
         let (request_tx, request_rx) = mpsc::unbounded_channel();
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        let (market_processed_tx, market_processed_rx) = mpsc::unbounded_channel();
         let (event_tx, event_rx) = broadcast::channel(ACCOUNT_STREAM_CAPACITY);
+        let (disconnect_tx, disconnect_rx) = broadcast::channel(1);
+
+        // Register transmitter of market data processed by the Engine, which the
+        // ExecutionManager forwards to the MockExchange after any preceding requests
+        let instrument_map =
+            generate_execution_instrument_map(self.instruments, config.mocked_exchange)?;
+        self.mock_market_tx_map
+            .insert(Arc::new(instrument_map), market_processed_tx);
 
         let mock_execution_client_config = MockExecutionClientConfig {
             mocked_exchange: config.mocked_exchange,
             clock: move || clock.time(),
             request_tx,
             event_rx,
+            disconnect_rx,
         };
 
         // Register MockExchange init Future
-        let mock_exchange_future = self.init_mock_exchange(config, request_rx, event_tx);
+        let mock_exchange_future =
+            self.init_mock_exchange(config, request_rx, market_rx, event_tx, disconnect_tx)?;
         self.mock_exchange_futures.push(mock_exchange_future);
 
-        self.add_execution::<MockExecution<_>>(
+        self.add_execution::<MockExecution<_>, _>(
             mock_execution_client_config.mocked_exchange,
             mock_execution_client_config,
             DUMMY_EXECUTION_REQUEST_TIMEOUT,
+            |execution_rx| {
+                MockExecutionRequestStream::new(execution_rx, market_processed_rx, market_tx)
+            },
         )
     }
 
@@ -121,11 +156,23 @@ impl<'a> ExecutionBuilder<'a> {
         &self,
         config: MockExecutionConfig,
         request_rx: mpsc::UnboundedReceiver<MockExchangeRequest>,
+        market_rx: mpsc::UnboundedReceiver<MarketEvent<InstrumentNameExchange, DataKind>>,
         event_tx: broadcast::Sender<UnindexedAccountEvent>,
-    ) -> RunFuture {
+        disconnect_tx: broadcast::Sender<()>,
+    ) -> Result<RunFuture, BarterError> {
         let instruments =
             generate_mock_exchange_instruments(self.instruments, config.mocked_exchange);
-        Box::pin(MockExchange::new(config, request_rx, event_tx, instruments).run())
+        let exchange = MockExchange::new(
+            config,
+            request_rx,
+            market_rx,
+            event_tx,
+            disconnect_tx,
+            instruments,
+        )
+        .map_err(|error| BarterError::Config(error.to_string()))?;
+
+        Ok(Box::pin(exchange.run()))
     }
 
     /// Adds an [`ExecutionManager`] for a live exchange.
@@ -139,19 +186,122 @@ impl<'a> ExecutionBuilder<'a> {
         Client::AccountStream: Send,
         Client::Config: Send,
     {
-        self.add_execution::<Client>(Client::EXCHANGE, config, request_timeout)
+        self.add_execution::<Client, _>(
+            Client::EXCHANGE,
+            config,
+            request_timeout,
+            UnboundedRx::into_stream,
+        )
     }
 
-    fn add_execution<Client>(
+    /// Adds an [`ExecutionManager`] for the live exchange described by the provided
+    /// [`LiveExecutionConfig`], resolving the referenced API credentials.
+    ///
+    /// Returns an error if the credentials cannot be resolved or are invalid, or the exchange
+    /// does not provide the configured [`ExchangeEnvironment`].
+    pub fn add_live_config(self, config: LiveExecutionConfig) -> Result<Self, BarterError> {
+        let request_timeout = config.request_timeout();
+        let LiveExecutionConfig {
+            exchange,
+            credentials,
+            environment,
+            request_timeout_ms,
+        } = config;
+
+        let api_key = credentials.api_key.resolve()?;
+        let secret = credentials.secret.resolve()?;
+        let testnet = match environment {
+            ExchangeEnvironment::Mainnet => false,
+            ExchangeEnvironment::Testnet => true,
+        };
+
+        match exchange {
+            LiveExchangeConfig::BinanceSpot => {
+                let config = if testnet {
+                    BinanceSpotConfig::testnet(api_key, secret)
+                } else {
+                    BinanceSpotConfig::new(api_key, secret)
+                };
+                self.add_live::<BinanceSpot>(config, request_timeout)
+            }
+            LiveExchangeConfig::BinanceFuturesUsd {
+                position_mode,
+                leverage,
+            } => {
+                let config = if testnet {
+                    BinanceFuturesUsdConfig::testnet(api_key, secret)
+                } else {
+                    BinanceFuturesUsdConfig::new(api_key, secret)
+                };
+                self.add_live::<BinanceFuturesUsd>(
+                    BinanceFuturesUsdConfig {
+                        position_mode,
+                        leverage,
+                        ..config
+                    },
+                    request_timeout,
+                )
+            }
+            LiveExchangeConfig::BybitSpot => {
+                self.add_live::<BybitSpot>(bybit_config(api_key, secret, testnet), request_timeout)
+            }
+            LiveExchangeConfig::BybitPerpetualsUsd => self.add_live::<BybitPerpetualsUsd>(
+                bybit_config(api_key, secret, testnet),
+                request_timeout,
+            ),
+            LiveExchangeConfig::Okx {
+                passphrase,
+                trade_mode,
+            } => {
+                let passphrase = passphrase.resolve()?;
+                let config = if testnet {
+                    OkxConfig::demo(api_key, secret, passphrase)
+                } else {
+                    OkxConfig::new(api_key, secret, passphrase)
+                };
+                self.add_live::<Okx>(
+                    OkxConfig {
+                        trade_mode,
+                        order_timeout_ms: request_timeout_ms,
+                        ..config
+                    },
+                    request_timeout,
+                )
+            }
+            LiveExchangeConfig::Kraken if !testnet => {
+                let config = KrakenConfig {
+                    order_timeout_ms: request_timeout_ms,
+                    ..KrakenConfig::new(api_key, secret)
+                };
+                config.validate().map_err(BarterError::Config)?;
+                self.add_live::<Kraken>(config, request_timeout)
+            }
+            LiveExchangeConfig::Coinbase if !testnet => {
+                let config = CoinbaseConfig::new(api_key, secret);
+                config.validate().map_err(BarterError::Config)?;
+                self.add_live::<Coinbase>(config, request_timeout)
+            }
+            LiveExchangeConfig::Kraken | LiveExchangeConfig::Coinbase => {
+                Err(BarterError::Config(format!(
+                    "{} does not provide a testnet for live execution",
+                    exchange.exchange()
+                )))
+            }
+        }
+    }
+
+    fn add_execution<Client, RequestStream>(
         mut self,
         exchange: ExchangeId,
         config: Client::Config,
         request_timeout: Duration,
+        request_stream: impl FnOnce(UnboundedRx<ExecutionRequest>) -> RequestStream,
     ) -> Result<Self, BarterError>
     where
         Client: ExecutionClient + Send + Sync + 'static,
         Client::AccountStream: Send,
         Client::Config: Send,
+        RequestStream: Stream<Item = ExecutionRequest> + Unpin + Send + 'static,
     {
         let instrument_map = generate_execution_instrument_map(self.instruments, exchange)?;
 
@@ -171,7 +321,7 @@ impl<'a> ExecutionBuilder<'a> {
 
         // Init ExecutionManager Future
         let future_result = ExecutionManager::init(
-            execution_rx.into_stream(),
+            request_stream(execution_rx),
             request_timeout,
             Arc::new(Client::new(config)),
             AccountEventIndexer::new(Arc::new(instrument_map)),
@@ -226,6 +376,7 @@ impl<'a> ExecutionBuilder<'a> {
         ExecutionBuild {
             execution_tx_map,
             account_channel: self.merged_channel,
+            mock_market_tx_map: self.mock_market_tx_map,
             futures: ExecutionBuildFutures {
                 mock_exchange_run_futures: self.mock_exchange_futures,
                 execution_init_futures: self.execution_init_futures,
@@ -242,6 +393,7 @@ impl<'a> ExecutionBuilder<'a> {
 pub struct ExecutionBuild {
     pub execution_tx_map: MultiExchangeTxMap,
     pub account_channel: Channel<AccountStreamEvent>,
+    pub mock_market_tx_map: MockMarketTxMap,
     pub futures: ExecutionBuildFutures,
 }
 
@@ -281,6 +433,7 @@ impl ExecutionBuild {
         Ok(Execution {
             execution_txs: self.execution_tx_map,
             account_channel: self.account_channel,
+            mock_market_tx_map: self.mock_market_tx_map,
             handles,
         })
     }
@@ -386,7 +539,15 @@ impl IntoIterator for ExecutionHandles {
     }
 }
 
-fn generate_mock_exchange_instruments(
+fn bybit_config(api_key: String, secret: String, testnet: bool) -> BybitConfig {
+    if testnet {
+        BybitConfig::testnet(api_key, secret)
+    } else {
+        BybitConfig::new(api_key, secret)
+    }
+}
+
+pub(crate) fn generate_mock_exchange_instruments(
     instruments: &IndexedInstruments,
     exchange: ExchangeId,
 ) -> FnvHashMap<InstrumentNameExchange, Instrument<ExchangeId, AssetNameExchange>> {
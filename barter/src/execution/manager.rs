@@ -15,8 +15,10 @@ use barter_execution::{
     map::ExecutionInstrumentMap,
     order::{
         Order,
+        group::{OrderGroup, OrderGroupOpened},
         request::{
-            OrderRequestCancel, OrderRequestOpen, OrderResponseCancel, UnindexedOrderResponseCancel,
+            OrderRequestAmend, OrderRequestCancel, OrderRequestOpen, OrderResponseAmend,
+            OrderResponseCancel, UnindexedOrderResponseAmend, UnindexedOrderResponseCancel,
         },
         state::{Open, OrderState},
     },
@@ -221,19 +223,17 @@ where
     pub async fn run(mut self) {
         let mut in_flight_cancels = FuturesUnordered::new();
         let mut in_flight_opens = FuturesUnordered::new();
+        let mut in_flight_amends = FuturesUnordered::new();
+        let mut in_flight_groups = FuturesUnordered::new();
 
         loop {
-            let next_cancel_response = if in_flight_cancels.is_empty() {
-                Either::Left(std::future::pending())
-            } else {
-                Either::Right(in_flight_cancels.select_next_some())
-            };
-
-            let next_open_response = if in_flight_opens.is_empty() {
-                Either::Left(std::future::pending())
-            } else {
-                Either::Right(in_flight_opens.select_next_some())
-            };
+            let next_cancel_response = next_in_flight_response(&mut in_flight_cancels);
+
+            let next_open_response = next_in_flight_response(&mut in_flight_opens);
+
+            let next_amend_response = next_in_flight_response(&mut in_flight_amends);
+
+            let next_group_response = next_in_flight_response(&mut in_flight_groups);
 
             tokio::select! {
                 // Process Engine ExecutionRequests
@@ -271,13 +271,43 @@ where
                             request,
                         ))
                     }
+                    Some(ExecutionRequest::Amend(request)) => {
+                        // Panic since the system is set up incorrectly, so it's foolish to continue
+                        let client_request = self
+                            .indexer
+                            .order_request(&request)
+                            .unwrap_or_else(|error| panic!(
+                                "ExecutionManager received amend request for non-configured key: {error}"
+                            ));
+
+                        in_flight_amends.push(RequestFuture::new(
+                            self.client.amend_order(client_request),
+                            self.request_timeout,
+                            request,
+                        ))
+                    }
+                    Some(ExecutionRequest::OpenGroup(request)) => {
+                        // Acknowledge before any order responses, so the Engine knows whether
+                        // it must emulate the contingent behaviour of the group
+                        let (client_request, opened) =
+                            process_group_request(&self.indexer, self.client.as_ref(), &request);
+                        if self.response_tx.send(opened).is_err() {
+                            break;
+                        }
+
+                        in_flight_groups.push(RequestFuture::new(
+                            self.client.open_order_group(client_request),
+                            self.request_timeout,
+                            request,
+                        ))
+                    }
                 },
 
                 // Process next ExecutionRequest::Cancel response
                 response_cancel = next_cancel_response => {
                     let event = match response_cancel {
                         Ok(response) => {
-                            match self.process_cancel_response(response) {
+                            match process_cancel_response(&self.indexer, response) {
                                 Ok(indexed_event) => indexed_event,
                                 Err(error) => {
                                     warn!(
@@ -290,7 +320,7 @@ where
                             }
                         }
                         Err(request) => {
-                            Self::process_cancel_timeout(request)
+                            process_cancel_timeout(request)
                         }
                     };
 
@@ -303,7 +333,7 @@ where
                 response_open = next_open_response => {
                     let event = match response_open {
                         Ok(response) => {
-                            match self.process_open_response(response) {
+                            match process_open_response(&self.indexer, response) {
                                 Ok(indexed_event) => indexed_event,
                                 Err(error) => {
                                     warn!(
@@ -316,7 +346,33 @@ where
                             }
                         }
                         Err(request) => {
-                            Self::process_open_timeout(request)
+                            process_open_timeout(request)
+                        }
+                    };
+
+                    if self.response_tx.send(event).is_err() {
+                        break;
+                    }
+                }
+
+                // Process next ExecutionRequest::Amend response
+                response_amend = next_amend_response => {
+                    let event = match response_amend {
+                        Ok(response) => {
+                            match process_amend_response(&self.indexer, response) {
+                                Ok(indexed_event) => indexed_event,
+                                Err(error) => {
+                                    warn!(
+                                        exchange = %self.indexer.map.exchange.value,
+                                        ?error,
+                                        "ExecutionManager filtering amend response due to unrecognised index"
+                                    );
+                                    continue
+                                }
+                            }
+                        }
+                        Err(request) => {
+                            process_amend_timeout(request)
                         }
                     };
 
@@ -325,6 +381,15 @@ where
                     }
                 }
 
+                // Process next ExecutionRequest::OpenGroup response
+                response_group = next_group_response => {
+                    let events = process_group_response(&self.indexer, response_group);
+
+                    if events.into_iter().any(|event| self.response_tx.send(event).is_err()) {
+                        break;
+                    }
+                }
+
             }
         }
 
@@ -333,38 +398,59 @@ where
             "ExecutionManager shutting down"
         )
     }
+}
 
-    fn process_cancel_response(
-        &self,
-        order: UnindexedOrderResponseCancel,
-    ) -> Result<AccountStreamEvent, IndexError> {
-        let order = self.indexer.order_response_cancel(order)?;
+pub(crate) fn process_cancel_response(
+    indexer: &AccountEventIndexer,
+    order: UnindexedOrderResponseCancel,
+) -> Result<AccountStreamEvent, IndexError> {
+    let order = indexer.order_response_cancel(order)?;
 
-        Ok(AccountStreamEvent::Item(AccountEvent {
-            exchange: order.key.exchange,
-            kind: AccountEventKind::OrderCancelled(order),
-        }))
-    }
+    Ok(AccountStreamEvent::Item(AccountEvent {
+        exchange: order.key.exchange,
+        kind: AccountEventKind::OrderCancelled(order),
+    }))
+}
 
-    fn process_cancel_timeout(
-        order: OrderRequestCancel<ExchangeIndex, InstrumentIndex>,
-    ) -> AccountStreamEvent {
-        let OrderRequestCancel { key, state: _ } = order;
+pub(crate) fn process_cancel_timeout(
+    order: OrderRequestCancel<ExchangeIndex, InstrumentIndex>,
+) -> AccountStreamEvent {
+    let OrderRequestCancel { key, state: _ } = order;
 
-        AccountStreamEvent::Item(AccountEvent {
-            exchange: key.exchange,
-            kind: AccountEventKind::OrderCancelled(OrderResponseCancel {
-                key,
-                state: Err(OrderError::Connectivity(ConnectivityError::Timeout)),
-            }),
-        })
-    }
+    AccountStreamEvent::Item(AccountEvent {
+        exchange: key.exchange,
+        kind: AccountEventKind::OrderCancelled(OrderResponseCancel {
+            key,
+            state: Err(OrderError::Connectivity(ConnectivityError::Timeout)),
+        }),
+    })
+}
 
-    fn process_open_response(
-        &self,
-        order: Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>,
-    ) -> Result<AccountStreamEvent, IndexError> {
-        let Order {
+pub(crate) fn process_open_response(
+    indexer: &AccountEventIndexer,
+    order: Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>,
+) -> Result<AccountStreamEvent, IndexError> {
+    let Order {
+        key,
+        side,
+        price,
+        quantity,
+        kind,
+        time_in_force,
+        state,
+    } = order;
+
+    let key = indexer.order_key(key)?;
+
+    let state = match state {
+        Ok(open) if open.quantity_remaining(quantity).is_zero() => OrderState::fully_filled(),
+        Ok(open) => OrderState::active(open),
+        Err(error) => OrderState::inactive(indexer.order_error(error)?),
+    };
+
+    Ok(AccountStreamEvent::Item(AccountEvent {
+        exchange: key.exchange,
+        kind: AccountEventKind::OrderSnapshot(Snapshot(Order {
             key,
             side,
             price,
@@ -372,46 +458,128 @@ where
             kind,
             time_in_force,
             state,
-        } = order;
+        })),
+    }))
+}
 
-        let key = self.indexer.order_key(key)?;
+pub(crate) fn process_open_timeout(
+    order: OrderRequestOpen<ExchangeIndex, InstrumentIndex>,
+) -> AccountStreamEvent {
+    let OrderRequestOpen { key, state } = order;
 
-        let state = match state {
-            Ok(open) if open.quantity_remaining(quantity).is_zero() => OrderState::fully_filled(),
-            Ok(open) => OrderState::active(open),
-            Err(error) => OrderState::inactive(self.indexer.order_error(error)?),
-        };
+    AccountStreamEvent::Item(AccountEvent {
+        exchange: key.exchange,
+        kind: AccountEventKind::OrderSnapshot(Snapshot(Order {
+            key,
+            side: state.side,
+            price: state.price,
+            quantity: state.quantity,
+            kind: state.kind,
+            time_in_force: state.time_in_force,
+            state: OrderState::inactive(OrderError::Connectivity(ConnectivityError::Timeout)),
+        })),
+    })
+}
 
-        Ok(AccountStreamEvent::Item(AccountEvent {
-            exchange: key.exchange,
-            kind: AccountEventKind::OrderSnapshot(Snapshot(Order {
-                key,
-                side,
-                price,
-                quantity,
-                kind,
-                time_in_force,
-                state,
-            })),
-        }))
+pub(crate) fn process_group_response(
+    indexer: &AccountEventIndexer,
+    response: Result<
+        Vec<Order<ExchangeId, InstrumentNameExchange, Result<Open, UnindexedOrderError>>>,
+        OrderGroup<ExchangeIndex, InstrumentIndex>,
+    >,
+) -> Vec<AccountStreamEvent> {
+    match response {
+        Ok(orders) => orders
+            .into_iter()
+            .filter_map(|order| {
+                process_open_response(indexer, order)
+                    .inspect_err(|error| {
+                        warn!(
+                            exchange = %indexer.map.exchange.value,
+                            ?error,
+                            "ExecutionManager filtering order group response due to unrecognised index"
+                        )
+                    })
+                    .ok()
+            })
+            .collect(),
+        Err(group) => group
+            .orders_initial()
+            .cloned()
+            .map(process_open_timeout)
+            .collect(),
     }
+}
 
-    fn process_open_timeout(
-        order: OrderRequestOpen<ExchangeIndex, InstrumentIndex>,
-    ) -> AccountStreamEvent {
-        let OrderRequestOpen { key, state } = order;
-
-        AccountStreamEvent::Item(AccountEvent {
-            exchange: key.exchange,
-            kind: AccountEventKind::OrderSnapshot(Snapshot(Order {
-                key,
-                side: state.side,
-                price: state.price,
-                quantity: state.quantity,
-                kind: state.kind,
-                time_in_force: state.time_in_force,
-                state: OrderState::inactive(OrderError::Connectivity(ConnectivityError::Timeout)),
-            })),
-        })
+pub(crate) fn process_group_request<'a, Client>(
+    indexer: &'a AccountEventIndexer,
+    client: &Client,
+    group: &OrderGroup<ExchangeIndex, InstrumentIndex>,
+) -> (
+    OrderGroup<ExchangeId, &'a InstrumentNameExchange>,
+    AccountStreamEvent,
+)
+where
+    Client: ExecutionClient,
+{
+    // Panic since the system is set up incorrectly, so it's foolish to continue
+    let client_request = indexer.order_group_request(group).unwrap_or_else(|error| {
+        panic!("ExecutionManager received order group request for non-configured key: {error}")
+    });
+
+    let opened = AccountStreamEvent::Item(AccountEvent {
+        exchange: *group.exchange(),
+        kind: AccountEventKind::OrderGroupOpened(OrderGroupOpened::new(
+            group.id.clone(),
+            client.supports_order_group(group),
+        )),
+    });
+
+    (client_request, opened)
+}
+
+pub(crate) fn process_amend_response(
+    indexer: &AccountEventIndexer,
+    order: UnindexedOrderResponseAmend,
+) -> Result<AccountStreamEvent, IndexError> {
+    let order = indexer.order_response_amend(order)?;
+
+    Ok(AccountStreamEvent::Item(AccountEvent {
+        exchange: order.key.exchange,
+        kind: AccountEventKind::OrderAmended(order),
+    }))
+}
+
+pub(crate) fn process_amend_timeout(
+    order: OrderRequestAmend<ExchangeIndex, InstrumentIndex>,
+) -> AccountStreamEvent {
+    let OrderRequestAmend { key, state } = order;
+
+    AccountStreamEvent::Item(AccountEvent {
+        exchange: key.exchange,
+        kind: AccountEventKind::OrderAmended(OrderResponseAmend {
+            key,
+            side: state.side,
+            price: state.price,
+            quantity: state.quantity,
+            kind: state.kind,
+            time_in_force: state.time_in_force,
+            state: Err(OrderError::Connectivity(ConnectivityError::Timeout)),
+        }),
+    })
+}
+
+/// Returns a `Future` that resolves to the next in flight request response, or never resolves if
+/// there are no requests in flight.
+fn next_in_flight_response<Fut>(
+    in_flight: &mut FuturesUnordered<Fut>,
+) -> impl Future<Output = Fut::Output> + '_
+where
+    Fut: Future,
+{
+    if in_flight.is_empty() {
+        Either::Left(std::future::pending())
+    } else {
+        Either::Right(in_flight.select_next_some())
     }
 }
@@ -0,0 +1,285 @@
+use crate::{EngineEvent, execution::request::ExecutionRequest};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    streams::{consumer::MarketStreamEvent, reconnect},
+};
+use barter_execution::map::ExecutionInstrumentMap;
+use barter_instrument::{
+    exchange::ExchangeId,
+    instrument::{InstrumentIndex, name::InstrumentNameExchange},
+};
+use barter_integration::channel::UnboundedRx;
+use fnv::FnvHashMap;
+use futures::Stream;
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// `MarketEvent` kind that can be forwarded to a
+/// [`MockExchange`](barter_execution::exchange::mock::MockExchange) to drive fill simulation.
+///
+/// Custom `InstrumentDataState::MarketEventKind`s that do not contain data relevant to simulating
+/// fills can return `None`.
+pub trait MockMarketEventKind {
+    /// Return the [`DataKind`] a `MockExchange` should use to simulate fills, if any.
+    fn to_data_kind(&self) -> Option<DataKind>;
+}
+
+impl MockMarketEventKind for DataKind {
+    fn to_data_kind(&self) -> Option<DataKind> {
+        Some(self.clone())
+    }
+}
+
+/// `Engine` input event that may contain a `MarketStreamEvent` to forward to a
+/// [`MockExchange`](barter_execution::exchange::mock::MockExchange) once it has been processed.
+pub trait MockMarketEventSource<Kind> {
+    /// Return the `MarketStreamEvent` contained in this event, if any.
+    fn market_stream_event(&self) -> Option<&MarketStreamEvent<InstrumentIndex, Kind>>;
+}
+
+impl<Kind> MockMarketEventSource<Kind> for EngineEvent<Kind> {
+    fn market_stream_event(&self) -> Option<&MarketStreamEvent<InstrumentIndex, Kind>> {
+        match self {
+            EngineEvent::Market(event) => Some(event),
+            _ => None,
+        }
+    }
+}
+
+/// Market data link to every [`MockExchange`](barter_execution::exchange::mock::MockExchange)
+/// built by the [`ExecutionBuilder`](super::builder::ExecutionBuilder).
+///
+/// Forwards the `MarketStreamEvent`s the `Engine` has processed to the mocked exchange
+/// [`MockExecutionRequestStream`], translating each `InstrumentIndex` into its
+/// `InstrumentNameExchange`.
+#[derive(Debug, Clone, Default)]
+pub struct MockMarketTxMap {
+    txs: FnvHashMap<ExchangeId, MockMarketTx>,
+}
+
+#[derive(Debug, Clone)]
+struct MockMarketTx {
+    instrument_map: Arc<ExecutionInstrumentMap>,
+    tx: mpsc::UnboundedSender<MarketEvent<InstrumentNameExchange, DataKind>>,
+}
+
+impl MockMarketTxMap {
+    /// Register the market data transmitter of a mocked exchange [`MockExecutionRequestStream`].
+    pub fn insert(
+        &mut self,
+        instrument_map: Arc<ExecutionInstrumentMap>,
+        tx: mpsc::UnboundedSender<MarketEvent<InstrumentNameExchange, DataKind>>,
+    ) {
+        self.txs.insert(
+            instrument_map.exchange.value,
+            MockMarketTx { instrument_map, tx },
+        );
+    }
+
+    /// Returns true if no `MockExchange` market data transmitters have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+
+    /// Forward the provided `MarketStreamEvent` to the `MockExchange` of the associated exchange,
+    /// if there is one.
+    pub fn send<Kind>(&self, event: &MarketStreamEvent<InstrumentIndex, Kind>)
+    where
+        Kind: MockMarketEventKind,
+    {
+        let reconnect::Event::Item(event) = event else {
+            return;
+        };
+
+        let Some(mock) = self.txs.get(&event.exchange) else {
+            return;
+        };
+
+        let Some(kind) = event.kind.to_data_kind() else {
+            return;
+        };
+
+        let Ok(instrument) = mock
+            .instrument_map
+            .find_instrument_name_exchange(event.instrument)
+        else {
+            return;
+        };
+
+        if mock
+            .tx
+            .send(MarketEvent {
+                time_exchange: event.time_exchange,
+                time_received: event.time_received,
+                exchange: event.exchange,
+                instrument: instrument.clone(),
+                kind,
+            })
+            .is_err()
+        {
+            debug!(
+                exchange = %event.exchange,
+                "MockMarketTxMap failed to send MarketEvent to dropped MockExchange"
+            );
+        }
+    }
+}
+
+/// `Engine` feed adapter that forwards each `MarketStreamEvent` via the [`MockMarketTxMap`]
+/// once the `Engine` has processed it, ie/ when the `Engine` requests its next event.
+///
+/// This ensures a `MockExchange` never simulates fills using market data the `Engine` has not
+/// yet acted on.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct MockMarketFeed<Feed, Kind> {
+    #[pin]
+    feed: Feed,
+    mock_market_tx_map: MockMarketTxMap,
+    processed: Option<MarketStreamEvent<InstrumentIndex, Kind>>,
+}
+
+impl<Feed, Kind> MockMarketFeed<Feed, Kind> {
+    /// Construct a new `MockMarketFeed` that forwards the market events yielded by the provided
+    /// `Feed` via the [`MockMarketTxMap`].
+    pub fn new(feed: Feed, mock_market_tx_map: MockMarketTxMap) -> Self {
+        Self {
+            feed,
+            mock_market_tx_map,
+            processed: None,
+        }
+    }
+}
+
+impl<Feed, Kind> Iterator for MockMarketFeed<Feed, Kind>
+where
+    Feed: Iterator,
+    Feed::Item: MockMarketEventSource<Kind>,
+    Kind: MockMarketEventKind + Clone,
+{
+    type Item = Feed::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        forward_processed(&self.mock_market_tx_map, &mut self.processed);
+
+        let event = self.feed.next()?;
+        self.processed = market_event_to_forward(&self.mock_market_tx_map, &event);
+        Some(event)
+    }
+}
+
+impl<Feed, Kind> Stream for MockMarketFeed<Feed, Kind>
+where
+    Feed: Stream,
+    Feed::Item: MockMarketEventSource<Kind>,
+    Kind: MockMarketEventKind + Clone,
+{
+    type Item = Feed::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        forward_processed(this.mock_market_tx_map, this.processed);
+
+        let event = match this.feed.poll_next(cx) {
+            Poll::Ready(Some(event)) => event,
+            poll => return poll,
+        };
+
+        *this.processed = market_event_to_forward(this.mock_market_tx_map, &event);
+        Poll::Ready(Some(event))
+    }
+}
+
+fn forward_processed<Kind>(
+    mock_market_tx_map: &MockMarketTxMap,
+    processed: &mut Option<MarketStreamEvent<InstrumentIndex, Kind>>,
+) where
+    Kind: MockMarketEventKind,
+{
+    if let Some(event) = processed.take() {
+        mock_market_tx_map.send(&event);
+    }
+}
+
+fn market_event_to_forward<Event, Kind>(
+    mock_market_tx_map: &MockMarketTxMap,
+    event: &Event,
+) -> Option<MarketStreamEvent<InstrumentIndex, Kind>>
+where
+    Event: MockMarketEventSource<Kind>,
+    Kind: Clone,
+{
+    if mock_market_tx_map.is_empty() {
+        return None;
+    }
+
+    event.market_stream_event().cloned()
+}
+
+/// `Stream` of `Engine` [`ExecutionRequest`]s consumed by the
+/// [`ExecutionManager`](super::manager::ExecutionManager) of a mocked exchange.
+///
+/// Market events forwarded by the [`MockMarketTxMap`] are only passed on to the `MockExchange`
+/// once every `ExecutionRequest` the `Engine` sent before them has been yielded. Since the
+/// `MockExecution` client queues requests at the `MockExchange` as soon as they are issued, the
+/// `MockExchange` always receives the order requests the `Engine` generated from a market event
+/// before the market event itself.
+#[derive(Debug)]
+pub struct MockExecutionRequestStream {
+    requests: UnboundedRx<ExecutionRequest>,
+    market_rx: mpsc::UnboundedReceiver<MarketEvent<InstrumentNameExchange, DataKind>>,
+    market_tx: mpsc::UnboundedSender<MarketEvent<InstrumentNameExchange, DataKind>>,
+    market_pending: Option<MarketEvent<InstrumentNameExchange, DataKind>>,
+}
+
+impl MockExecutionRequestStream {
+    /// Construct a new `MockExecutionRequestStream`.
+    ///
+    /// Market events received via `market_rx` are forwarded to the `MockExchange` via
+    /// `market_tx`.
+    pub fn new(
+        requests: UnboundedRx<ExecutionRequest>,
+        market_rx: mpsc::UnboundedReceiver<MarketEvent<InstrumentNameExchange, DataKind>>,
+        market_tx: mpsc::UnboundedSender<MarketEvent<InstrumentNameExchange, DataKind>>,
+    ) -> Self {
+        Self {
+            requests,
+            market_rx,
+            market_tx,
+            market_pending: None,
+        }
+    }
+}
+
+impl Stream for MockExecutionRequestStream {
+    type Item = ExecutionRequest;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Poll::Ready(request) = self.requests.rx.poll_recv(cx) {
+                return Poll::Ready(request);
+            }
+
+            // Every request sent before the pending market event has now been yielded
+            if let Some(event) = self.market_pending.take()
+                && self.market_tx.send(event).is_err()
+            {
+                debug!(
+                    "MockExecutionRequestStream failed to send MarketEvent to dropped MockExchange"
+                );
+            }
+
+            // Requests are polled again before forwarding, since any sent before this market
+            // event are only guaranteed to be visible once it has been received
+            match self.market_rx.poll_recv(cx) {
+                Poll::Ready(Some(event)) => self.market_pending = Some(event),
+                Poll::Ready(None) | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -15,6 +15,10 @@ pub mod builder;
 /// Provides an error type that represents all errors that are generated by an execution link.
 pub mod error;
 
+/// Provides a [`MockMarketTxMap`](mock::MockMarketTxMap) that forwards the `Engine` market data
+/// to each `MockExchange`, driving simulated fills.
+pub mod mock;
+
 /// Per-exchange execution manager that actions order requests from the Engine and forwards back
 /// responses.
 pub mod manager;
@@ -33,10 +37,12 @@ pub type AccountStreamEvent<
 /// Initialised [`ExecutionBuild`](builder::ExecutionBuild).
 ///
 /// Contains execution component task handles as well as
-/// [`ExecutionRequest`](request::ExecutionRequest) and [`AccountStreamEvent`] channels.
+/// [`ExecutionRequest`](request::ExecutionRequest) and [`AccountStreamEvent`] channels, and the
+/// [`MockMarketTxMap`](mock::MockMarketTxMap) used to forward market data to `MockExchange`s.
 #[allow(missing_debug_implementations)]
 pub struct Execution {
     pub execution_txs: MultiExchangeTxMap,
     pub account_channel: Channel<AccountStreamEvent>,
+    pub mock_market_tx_map: mock::MockMarketTxMap,
     pub handles: ExecutionHandles,
 }
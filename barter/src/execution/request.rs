@@ -1,4 +1,7 @@
-use barter_execution::order::request::{OrderRequestCancel, OrderRequestOpen};
+use barter_execution::order::{
+    group::OrderGroup,
+    request::{OrderRequestAmend, OrderRequestCancel, OrderRequestOpen},
+};
 use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
 use derive_more::From;
 use serde::{Deserialize, Serialize};
@@ -19,6 +22,12 @@ pub enum ExecutionRequest<ExchangeKey = ExchangeIndex, InstrumentKey = Instrumen
 
     /// Request to open an new `Order`.
     Open(OrderRequestOpen<ExchangeKey, InstrumentKey>),
+
+    /// Request to amend the price and/or quantity of an existing `Order`.
+    Amend(OrderRequestAmend<ExchangeKey, InstrumentKey>),
+
+    /// Request to open a contingent [`OrderGroup`].
+    OpenGroup(OrderGroup<ExchangeKey, InstrumentKey>),
 }
 
 #[derive(Debug)]
@@ -1,4 +1,10 @@
+use barter_execution::order::OrderKind;
+use barter_instrument::{
+    Side,
+    instrument::spec::{InstrumentSpecNotional, InstrumentSpecPrice},
+};
 use derive_more::Constructor;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -57,7 +63,7 @@ where
 
 /// Error returned when a [`CheckHigherThan`] validation fails.
 #[derive(
-    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize, Constructor, Error,
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor, Error,
 )]
 #[error("CheckHigherThanFailed: input {input} > limit {limit}")]
 pub struct CheckFailHigherThan<T> {
@@ -67,3 +73,356 @@ pub struct CheckFailHigherThan<T> {
     /// The input value that caused the check to fail.
     pub input: T,
 }
+
+/// Risk check that validates a conditional [`OrderKind`] would not be triggered immediately by
+/// the prevailing market price (eg/ a sell stop placed above the market).
+///
+/// Non-conditional `Market` and `Limit` orders always pass.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, Deserialize, Serialize)]
+pub struct CheckTriggerPrice;
+
+/// [`CheckTriggerPrice`] input, being an order [`Side`] and [`OrderKind`], and the prevailing
+/// market price of its [`TriggerSource`](barter_execution::order::TriggerSource).
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize, Constructor,
+)]
+pub struct TriggerPriceInput {
+    pub side: Side,
+    pub kind: OrderKind,
+    pub price_market: Decimal,
+}
+
+impl RiskCheck for CheckTriggerPrice {
+    type Input = TriggerPriceInput;
+    type Error = CheckFailTriggerPrice;
+
+    fn name() -> &'static str {
+        "CheckTriggerPrice"
+    }
+
+    fn check(&self, input: &Self::Input) -> Result<(), Self::Error> {
+        // TrailingStop trigger prices are activation prices, so may be reached immediately
+        if matches!(input.kind, OrderKind::TrailingStop { .. })
+            || !input.kind.is_triggered_by(input.side, input.price_market)
+        {
+            Ok(())
+        } else {
+            Err(CheckFailTriggerPrice {
+                side: input.side,
+                kind: input.kind,
+                price_market: input.price_market,
+            })
+        }
+    }
+}
+
+/// Error returned when a [`CheckTriggerPrice`] validation fails.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Deserialize,
+    Serialize,
+    Constructor,
+    Error,
+)]
+#[error(
+    "CheckTriggerPriceFailed: {side} {kind} would trigger immediately at market price {price_market}"
+)]
+pub struct CheckFailTriggerPrice {
+    pub side: Side,
+    pub kind: OrderKind,
+    pub price_market: Decimal,
+}
+
+/// Risk check that validates an order conforms to the minimum price, tick size, minimum
+/// quantity, lot size and minimum notional of an instrument
+/// [`InstrumentSpec`](barter_instrument::instrument::spec::InstrumentSpec).
+///
+/// Zero valued spec fields are treated as unconstrained.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, Deserialize, Serialize)]
+pub struct CheckInstrumentSpec;
+
+/// [`CheckInstrumentSpec`] input, being the order values and the instrument spec constraints
+/// they must conform to.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize, Constructor,
+)]
+pub struct InstrumentSpecInput {
+    /// Order limit price, or `None` if the [`OrderKind`] is executed at the market price.
+    pub price: Option<Decimal>,
+    pub quantity: Decimal,
+    pub notional: Decimal,
+    pub spec_price: InstrumentSpecPrice,
+    /// Minimum quantity and quantity increment, or `None` if the instrument quantity is not
+    /// denominated in the same units as the order quantity.
+    pub spec_quantity: Option<(Decimal, Decimal)>,
+    pub spec_notional: InstrumentSpecNotional,
+}
+
+impl RiskCheck for CheckInstrumentSpec {
+    type Input = InstrumentSpecInput;
+    type Error = CheckFailInstrumentSpec;
+
+    fn name() -> &'static str {
+        "CheckInstrumentSpec"
+    }
+
+    fn check(&self, input: &Self::Input) -> Result<(), Self::Error> {
+        if let Some(price) = input.price {
+            let InstrumentSpecPrice { min, tick_size } = input.spec_price;
+            if price < min {
+                return Err(CheckFailInstrumentSpec::PriceBelowMin { price, min });
+            }
+            if !is_multiple_of(price, tick_size) {
+                return Err(CheckFailInstrumentSpec::PriceTickSize { price, tick_size });
+            }
+        }
+
+        if let Some((min, increment)) = input.spec_quantity {
+            let quantity = input.quantity;
+            if quantity < min {
+                return Err(CheckFailInstrumentSpec::QuantityBelowMin { quantity, min });
+            }
+            if !is_multiple_of(quantity, increment) {
+                return Err(CheckFailInstrumentSpec::QuantityIncrement {
+                    quantity,
+                    increment,
+                });
+            }
+        }
+
+        if input.notional < input.spec_notional.min {
+            return Err(CheckFailInstrumentSpec::NotionalBelowMin {
+                notional: input.notional,
+                min: input.spec_notional.min,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn is_multiple_of(value: Decimal, increment: Decimal) -> bool {
+    increment.is_zero() || (value % increment).is_zero()
+}
+
+/// Error returned when a [`CheckInstrumentSpec`] validation fails.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Error,
+)]
+pub enum CheckFailInstrumentSpec {
+    #[error("CheckInstrumentSpecFailed: price {price} < min price {min}")]
+    PriceBelowMin { price: Decimal, min: Decimal },
+
+    #[error("CheckInstrumentSpecFailed: price {price} is not a multiple of tick size {tick_size}")]
+    PriceTickSize { price: Decimal, tick_size: Decimal },
+
+    #[error("CheckInstrumentSpecFailed: quantity {quantity} < min quantity {min}")]
+    QuantityBelowMin { quantity: Decimal, min: Decimal },
+
+    #[error(
+        "CheckInstrumentSpecFailed: quantity {quantity} is not a multiple of increment {increment}"
+    )]
+    QuantityIncrement {
+        quantity: Decimal,
+        increment: Decimal,
+    },
+
+    #[error("CheckInstrumentSpecFailed: notional {notional} < min notional {min}")]
+    NotionalBelowMin { notional: Decimal, min: Decimal },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_execution::order::{OrderTrigger, TrailingOffset, TriggerSource};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_check_trigger_price() {
+        struct TestCase {
+            input: TriggerPriceInput,
+            expected: Result<(), CheckFailTriggerPrice>,
+        }
+
+        let trigger = OrderTrigger::new(dec!(100), TriggerSource::Last);
+        let stop_market = OrderKind::StopMarket { trigger };
+        let take_profit_limit = OrderKind::TakeProfitLimit { trigger };
+        let trailing_stop = OrderKind::TrailingStop {
+            trigger,
+            offset: TrailingOffset::Percent(dec!(0.01)),
+        };
+
+        let cases = vec![
+            // TC0: Market orders always pass
+            TestCase {
+                input: TriggerPriceInput::new(Side::Buy, OrderKind::Market, dec!(100)),
+                expected: Ok(()),
+            },
+            // TC1: Limit orders always pass
+            TestCase {
+                input: TriggerPriceInput::new(Side::Sell, OrderKind::Limit, dec!(100)),
+                expected: Ok(()),
+            },
+            // TC2: buy stop above market price passes
+            TestCase {
+                input: TriggerPriceInput::new(Side::Buy, stop_market, dec!(99)),
+                expected: Ok(()),
+            },
+            // TC3: buy stop at market price fails
+            TestCase {
+                input: TriggerPriceInput::new(Side::Buy, stop_market, dec!(100)),
+                expected: Err(CheckFailTriggerPrice::new(
+                    Side::Buy,
+                    stop_market,
+                    dec!(100),
+                )),
+            },
+            // TC4: sell stop below market price passes
+            TestCase {
+                input: TriggerPriceInput::new(Side::Sell, stop_market, dec!(101)),
+                expected: Ok(()),
+            },
+            // TC5: sell stop above market price fails
+            TestCase {
+                input: TriggerPriceInput::new(Side::Sell, stop_market, dec!(99)),
+                expected: Err(CheckFailTriggerPrice::new(
+                    Side::Sell,
+                    stop_market,
+                    dec!(99),
+                )),
+            },
+            // TC6: sell take profit above market price passes
+            TestCase {
+                input: TriggerPriceInput::new(Side::Sell, take_profit_limit, dec!(99)),
+                expected: Ok(()),
+            },
+            // TC7: buy take profit above market price fails
+            TestCase {
+                input: TriggerPriceInput::new(Side::Buy, take_profit_limit, dec!(99)),
+                expected: Err(CheckFailTriggerPrice::new(
+                    Side::Buy,
+                    take_profit_limit,
+                    dec!(99),
+                )),
+            },
+            // TC8: trailing stop activation price reached immediately passes
+            TestCase {
+                input: TriggerPriceInput::new(Side::Sell, trailing_stop, dec!(101)),
+                expected: Ok(()),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = CheckTriggerPrice.check(&test.input);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_check_instrument_spec() {
+        struct TestCase {
+            input: InstrumentSpecInput,
+            expected: Result<(), CheckFailInstrumentSpec>,
+        }
+
+        let spec_price = InstrumentSpecPrice::new(dec!(1), dec!(0.5));
+        let spec_quantity = Some((dec!(0.1), dec!(0.1)));
+        let spec_notional = InstrumentSpecNotional::new(dec!(10));
+
+        let input = |price: Option<Decimal>, quantity: Decimal| {
+            InstrumentSpecInput::new(
+                price,
+                quantity,
+                price.unwrap_or(dec!(100)) * quantity,
+                spec_price,
+                spec_quantity,
+                spec_notional,
+            )
+        };
+
+        let cases = vec![
+            // TC0: order conforms to spec
+            TestCase {
+                input: input(Some(dec!(100.5)), dec!(0.2)),
+                expected: Ok(()),
+            },
+            // TC1: price below min price
+            TestCase {
+                input: input(Some(dec!(0.5)), dec!(100)),
+                expected: Err(CheckFailInstrumentSpec::PriceBelowMin {
+                    price: dec!(0.5),
+                    min: dec!(1),
+                }),
+            },
+            // TC2: price not a multiple of tick size
+            TestCase {
+                input: input(Some(dec!(100.25)), dec!(0.2)),
+                expected: Err(CheckFailInstrumentSpec::PriceTickSize {
+                    price: dec!(100.25),
+                    tick_size: dec!(0.5),
+                }),
+            },
+            // TC3: market order price is not checked
+            TestCase {
+                input: input(None, dec!(0.2)),
+                expected: Ok(()),
+            },
+            // TC4: quantity below min quantity
+            TestCase {
+                input: input(Some(dec!(200)), dec!(0.05)),
+                expected: Err(CheckFailInstrumentSpec::QuantityBelowMin {
+                    quantity: dec!(0.05),
+                    min: dec!(0.1),
+                }),
+            },
+            // TC5: quantity not a multiple of increment
+            TestCase {
+                input: input(Some(dec!(100)), dec!(0.25)),
+                expected: Err(CheckFailInstrumentSpec::QuantityIncrement {
+                    quantity: dec!(0.25),
+                    increment: dec!(0.1),
+                }),
+            },
+            // TC6: quantity in different units to spec is not checked
+            TestCase {
+                input: InstrumentSpecInput {
+                    spec_quantity: None,
+                    ..input(Some(dec!(100)), dec!(0.25))
+                },
+                expected: Ok(()),
+            },
+            // TC7: notional below min notional
+            TestCase {
+                input: input(Some(dec!(10)), dec!(0.5)),
+                expected: Err(CheckFailInstrumentSpec::NotionalBelowMin {
+                    notional: dec!(5),
+                    min: dec!(10),
+                }),
+            },
+            // TC8: zero valued spec fields are unconstrained
+            TestCase {
+                input: InstrumentSpecInput::new(
+                    Some(dec!(0.123)),
+                    dec!(0.0001),
+                    dec!(0.0000123),
+                    InstrumentSpecPrice::new(dec!(0), dec!(0)),
+                    Some((dec!(0), dec!(0))),
+                    InstrumentSpecNotional::new(dec!(0)),
+                ),
+                expected: Ok(()),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = CheckInstrumentSpec.check(&test.input);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+}
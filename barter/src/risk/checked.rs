@@ -0,0 +1,703 @@
+use crate::{
+    engine::state::{
+        EngineState,
+        instrument::{InstrumentState, data::InstrumentDataState},
+    },
+    risk::{
+        RiskApproved, RiskManager, RiskRefused, RiskRefusedReason,
+        check::{
+            CheckHigherThan, CheckInstrumentSpec, CheckTriggerPrice, InstrumentSpecInput,
+            RiskCheck, TriggerPriceInput,
+            util::{calculate_abs_percent_difference, calculate_quote_notional},
+        },
+        exposure::{ExposureLimitConfig, PortfolioExposure},
+        self_trade::{SelfTradePreventionMode, SelfTradePreventionOutput},
+    },
+};
+use barter_execution::order::request::{OrderRequestCancel, OrderRequestOpen};
+use barter_instrument::instrument::{
+    InstrumentIndex, kind::InstrumentKind, spec::OrderQuantityUnits,
+};
+use fnv::FnvHashMap;
+use itertools::{Either, Itertools};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use tracing::warn;
+
+/// Configuration of a [`CheckedRiskManager`], defining the ordered list of pre-trade checks
+/// and portfolio exposure limits every open order request must pass.
+///
+/// ### Example Config
+/// ```json
+/// {
+///     "checks": [
+///         { "check": "instrument_spec" },
+///         { "check": "max_order_quantity", "limit": "1.5" },
+///         { "check": "max_order_notional", "limit": "50000" },
+///         { "check": "max_open_orders", "limit": 5 },
+///         { "check": "price_band", "max_percent": "0.05" },
+///         { "check": "trigger_price" }
+///     ],
+///     "exposure": [
+///         { "check": "max_position", "limit": "3" },
+///         { "check": "max_underlying_net_delta", "limit": "5" },
+///         { "check": "max_exchange_gross_notional", "limit": "250000" },
+///         { "check": "max_asset_concentration", "max_percent": "0.5" }
+///     ],
+///     "self_trade_prevention": "cancel_oldest"
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+pub struct RiskManagerConfig {
+    pub checks: Vec<RiskCheckConfig>,
+
+    /// Portfolio exposure limits, checked after every [`RiskCheckConfig`] has passed.
+    #[serde(default)]
+    pub exposure: Vec<ExposureLimitConfig>,
+
+    /// Optional [`SelfTradePreventionMode`], applied to open requests that pass every other
+    /// check.
+    #[serde(default)]
+    pub self_trade_prevention: Option<SelfTradePreventionMode>,
+}
+
+/// Pre-trade open order check performed by a [`CheckedRiskManager`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum RiskCheckConfig {
+    /// Refuse orders with a quote notional value higher than the `limit`.
+    ///
+    /// `Option` notional values are calculated using the strike price.
+    MaxOrderNotional { limit: Decimal },
+
+    /// Refuse orders with a quantity higher than the `limit`.
+    MaxOrderQuantity { limit: Decimal },
+
+    /// Refuse orders that would result in more than `limit` active orders for an instrument.
+    MaxOpenOrders { limit: usize },
+
+    /// Refuse `Market` and `Limit` orders with a price that deviates from the instrument market
+    /// price by more than `max_percent` (eg/ 0.05 for 5%).
+    PriceBand { max_percent: Decimal },
+
+    /// Refuse orders that do not conform to the instrument spec (see [`CheckInstrumentSpec`]).
+    ///
+    /// Orders for instruments without a spec always pass.
+    InstrumentSpec,
+
+    /// Refuse conditional orders that would trigger immediately at the instrument market price
+    /// (see [`CheckTriggerPrice`]).
+    ///
+    /// The instrument market price is used regardless of the order
+    /// [`TriggerSource`](barter_execution::order::TriggerSource). Non-conditional orders always
+    /// pass.
+    TriggerPrice,
+}
+
+impl RiskCheckConfig {
+    /// Check the provided [`OrderRequestOpen`] for an instrument, given the number of
+    /// `open_orders` that are already active for it.
+    pub fn check<InstrumentData>(
+        &self,
+        instrument: &InstrumentState<InstrumentData>,
+        request: &OrderRequestOpen,
+        open_orders: usize,
+    ) -> Result<(), RiskRefusedReason>
+    where
+        InstrumentData: InstrumentDataState,
+    {
+        match self {
+            Self::MaxOrderNotional { limit } => CheckHigherThan::new(*limit)
+                .check(&notional(instrument, request)?)
+                .map_err(RiskRefusedReason::MaxOrderNotional),
+            Self::MaxOrderQuantity { limit } => CheckHigherThan::new(*limit)
+                .check(&request.state.quantity)
+                .map_err(RiskRefusedReason::MaxOrderQuantity),
+            Self::MaxOpenOrders { limit } => CheckHigherThan::new(*limit)
+                .check(&(open_orders + 1))
+                .map_err(RiskRefusedReason::MaxOpenOrders),
+            Self::PriceBand { max_percent } => {
+                if request.state.kind.is_conditional() {
+                    return Ok(());
+                }
+
+                let price_market = instrument
+                    .data
+                    .price()
+                    .ok_or(RiskRefusedReason::PriceUnavailable)?;
+
+                let price_diff_percent =
+                    calculate_abs_percent_difference(request.state.price, price_market)
+                        .ok_or(RiskRefusedReason::CalculationOverflow)?;
+
+                CheckHigherThan::new(*max_percent)
+                    .check(&price_diff_percent)
+                    .map_err(RiskRefusedReason::PriceBand)
+            }
+            Self::InstrumentSpec => {
+                let Some(spec) = &instrument.instrument.spec else {
+                    return Ok(());
+                };
+
+                let spec_quantity = match spec.quantity.unit {
+                    OrderQuantityUnits::Quote => None,
+                    OrderQuantityUnits::Asset(_) | OrderQuantityUnits::Contract => {
+                        Some((spec.quantity.min, spec.quantity.increment))
+                    }
+                };
+
+                let input = InstrumentSpecInput::new(
+                    (!request.state.kind.is_market()).then_some(request.state.price),
+                    request.state.quantity,
+                    notional(instrument, request)?,
+                    spec.price,
+                    spec_quantity,
+                    spec.notional,
+                );
+
+                CheckInstrumentSpec
+                    .check(&input)
+                    .map_err(RiskRefusedReason::InstrumentSpec)
+            }
+            Self::TriggerPrice => {
+                if !request.state.kind.is_conditional() {
+                    return Ok(());
+                }
+
+                let price_market = instrument
+                    .data
+                    .price()
+                    .ok_or(RiskRefusedReason::PriceUnavailable)?;
+
+                let input =
+                    TriggerPriceInput::new(request.state.side, request.state.kind, price_market);
+
+                CheckTriggerPrice
+                    .check(&input)
+                    .map_err(RiskRefusedReason::TriggerPrice)
+            }
+        }
+    }
+}
+
+/// Calculate the quote notional value of an [`OrderRequestOpen`], using the strike price for
+/// `Option` instruments.
+fn notional<InstrumentData>(
+    instrument: &InstrumentState<InstrumentData>,
+    request: &OrderRequestOpen,
+) -> Result<Decimal, RiskRefusedReason> {
+    let price = match &instrument.instrument.kind {
+        InstrumentKind::Option(option) => option.strike,
+        _ => request.state.price,
+    };
+
+    calculate_quote_notional(
+        request.state.quantity,
+        price,
+        instrument.instrument.kind.contract_size(),
+    )
+    .ok_or(RiskRefusedReason::CalculationOverflow)
+}
+
+/// [`RiskManager`] that checks open order requests against the ordered list of
+/// [`RiskCheckConfig`]s in its [`RiskManagerConfig`].
+///
+/// Open requests are refused with the [`RiskRefusedReason`] of the first check they fail. Cancel
+/// requests can only reduce risk, so are always approved.
+///
+/// Approved open requests count towards the open orders and [`PortfolioExposure`] used to check
+/// subsequent requests in the same batch.
+#[derive(Debug, Clone)]
+pub struct CheckedRiskManager<State> {
+    pub config: RiskManagerConfig,
+    phantom: PhantomData<State>,
+}
+
+impl<State> CheckedRiskManager<State> {
+    /// Construct a new [`CheckedRiskManager`] from the provided [`RiskManagerConfig`].
+    pub fn new(config: RiskManagerConfig) -> Self {
+        Self {
+            config,
+            phantom: PhantomData,
+        }
+    }
+
+    fn check_open<InstrumentData>(
+        &self,
+        instrument: &InstrumentState<InstrumentData>,
+        request: &OrderRequestOpen,
+        open_orders: usize,
+    ) -> Result<(), RiskRefusedReason>
+    where
+        InstrumentData: InstrumentDataState,
+    {
+        self.config
+            .checks
+            .iter()
+            .try_for_each(|check| check.check(instrument, request, open_orders))
+    }
+
+    /// Check the [`PortfolioExposure`] resulting from the [`OrderRequestOpen`] against every
+    /// [`ExposureLimitConfig`], adding the request to the `exposure` if every limit passes.
+    fn check_exposure<GlobalData, InstrumentData>(
+        &self,
+        state: &EngineState<GlobalData, InstrumentData>,
+        exposure: &mut Result<PortfolioExposure, RiskRefusedReason>,
+        request: &OrderRequestOpen,
+    ) -> Result<(), RiskRefusedReason> {
+        if self.config.exposure.is_empty() {
+            return Ok(());
+        }
+
+        let exposure = match exposure {
+            Ok(exposure) => exposure,
+            Err(reason) => return Err(reason.clone()),
+        };
+
+        let key = &request.key.instrument;
+        let previous = exposure.instrument(key).clone();
+
+        // Orders that can only reduce the absolute position are never refused by exposure limits
+        let reducing = request.state.flags.is_reducing()
+            || previous.is_reducing(request.state.side, request.state.quantity);
+
+        exposure.instrument_mut(key).add_order(
+            request.state.side,
+            request.state.quantity,
+            request.state.price,
+        )?;
+
+        if reducing {
+            return Ok(());
+        }
+
+        self.config
+            .exposure
+            .iter()
+            .try_for_each(|limit| limit.check(state, exposure, key))
+            .inspect_err(|_| *exposure.instrument_mut(key) = previous)
+    }
+}
+
+impl<GlobalData, InstrumentData> RiskManager
+    for CheckedRiskManager<EngineState<GlobalData, InstrumentData>>
+where
+    InstrumentData: InstrumentDataState,
+{
+    type State = EngineState<GlobalData, InstrumentData>;
+
+    fn check(
+        &self,
+        state: &Self::State,
+        cancels: impl IntoIterator<Item = OrderRequestCancel>,
+        opens: impl IntoIterator<Item = OrderRequestOpen>,
+    ) -> (
+        impl IntoIterator<Item = RiskApproved<OrderRequestCancel>>,
+        impl IntoIterator<Item = RiskApproved<OrderRequestOpen>>,
+        impl IntoIterator<Item = RiskRefused<OrderRequestCancel>>,
+        impl IntoIterator<Item = RiskRefused<OrderRequestOpen>>,
+    ) {
+        // Active orders per instrument, including those approved earlier in this batch
+        let mut open_orders = FnvHashMap::<InstrumentIndex, usize>::default();
+
+        // Potential exposure, including requests approved earlier in this batch
+        let mut exposure = if self.config.exposure.is_empty() {
+            Ok(PortfolioExposure::default())
+        } else {
+            PortfolioExposure::new(state)
+        };
+
+        let (approved_opens, refused_opens): (Vec<_>, Vec<_>) =
+            opens.into_iter().partition_map(|request| {
+                let instrument = state.instruments.instrument_index(&request.key.instrument);
+                let open_orders = open_orders
+                    .entry(request.key.instrument)
+                    .or_insert_with(|| instrument.orders.0.len());
+
+                let result = self
+                    .check_open(instrument, &request, *open_orders)
+                    .and_then(|()| self.check_exposure(state, &mut exposure, &request));
+
+                match result {
+                    Ok(()) => {
+                        *open_orders += 1;
+                        Either::Left(RiskApproved::new(request))
+                    }
+                    Err(reason) => {
+                        warn!(
+                            instrument = %instrument.instrument.name_internal,
+                            ?request,
+                            %reason,
+                            "CheckedRiskManager refused open order request"
+                        );
+                        Either::Right(RiskRefused::new(request, reason))
+                    }
+                }
+            });
+
+        // Prevent potential self-matches against active orders, which may generate cancels
+        let cancels = cancels.into_iter().collect::<Vec<_>>();
+        let SelfTradePreventionOutput {
+            cancels: cancels_generated,
+            opens: approved_opens,
+            refused: refused_self_trade,
+        } = match self.config.self_trade_prevention {
+            Some(mode) => mode.apply(
+                &state.instruments,
+                &cancels,
+                approved_opens.into_iter().map(RiskApproved::into_item),
+            ),
+            None => SelfTradePreventionOutput {
+                opens: approved_opens
+                    .into_iter()
+                    .map(RiskApproved::into_item)
+                    .collect(),
+                ..Default::default()
+            },
+        };
+
+        (
+            cancels
+                .into_iter()
+                .chain(cancels_generated)
+                .map(RiskApproved::new),
+            approved_opens.into_iter().map(RiskApproved::new),
+            std::iter::empty(),
+            refused_opens.into_iter().chain(refused_self_trade),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Timed,
+        engine::state::{
+            instrument::{data::DefaultInstrumentMarketData, status::InstrumentStatus},
+            order::Orders,
+            position::PositionManager,
+        },
+        risk::check::{CheckFailHigherThan, CheckFailInstrumentSpec, CheckFailTriggerPrice},
+        statistic::summary::instrument::TearSheetGenerator,
+    };
+    use barter_execution::order::{
+        OrderFlags, OrderKey, OrderKind, OrderTrigger, TimeInForce, TriggerSource,
+        id::{ClientOrderId, StrategyId},
+        request::RequestOpen,
+    };
+    use barter_instrument::{
+        Side, Underlying,
+        asset::AssetIndex,
+        exchange::ExchangeIndex,
+        instrument::{
+            Instrument,
+            spec::{
+                InstrumentSpec, InstrumentSpecNotional, InstrumentSpecPrice, InstrumentSpecQuantity,
+            },
+        },
+    };
+    use chrono::{DateTime, Utc};
+    use rust_decimal_macros::dec;
+
+    fn instrument_state(
+        price_market: Option<Decimal>,
+    ) -> InstrumentState<DefaultInstrumentMarketData> {
+        let spec = InstrumentSpec::new(
+            InstrumentSpecPrice::new(dec!(0.01), dec!(0.01)),
+            InstrumentSpecQuantity::new(
+                OrderQuantityUnits::Asset(AssetIndex(0)),
+                dec!(0.001),
+                dec!(0.001),
+            ),
+            InstrumentSpecNotional::new(dec!(10)),
+        );
+
+        InstrumentState::new(
+            InstrumentIndex(0),
+            Instrument::spot(
+                ExchangeIndex(0),
+                "binance_spot-btc_usdt",
+                "BTCUSDT",
+                Underlying::new(AssetIndex(0), AssetIndex(1)),
+                Some(spec),
+            ),
+            InstrumentStatus::Active,
+            TearSheetGenerator::init(DateTime::<Utc>::MIN_UTC),
+            PositionManager::default(),
+            Orders::default(),
+            DefaultInstrumentMarketData::new(
+                Default::default(),
+                price_market.map(|price| Timed::new(price, DateTime::<Utc>::MIN_UTC)),
+            ),
+        )
+    }
+
+    fn request_open(kind: OrderKind, price: Decimal, quantity: Decimal) -> OrderRequestOpen {
+        OrderRequestOpen {
+            key: OrderKey {
+                exchange: ExchangeIndex(0),
+                instrument: InstrumentIndex(0),
+                strategy: StrategyId::new("strategy"),
+                cid: ClientOrderId::new("cid"),
+            },
+            state: RequestOpen {
+                side: Side::Buy,
+                price,
+                quantity,
+                kind,
+                time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+                flags: OrderFlags::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_risk_check_config_check() {
+        struct TestCase {
+            check: RiskCheckConfig,
+            price_market: Option<Decimal>,
+            request: OrderRequestOpen,
+            open_orders: usize,
+            expected: Result<(), RiskRefusedReason>,
+        }
+
+        let stop_market = OrderKind::StopMarket {
+            trigger: OrderTrigger::new(dec!(110), TriggerSource::Last),
+        };
+
+        let cases = vec![
+            // TC0: notional within limit
+            TestCase {
+                check: RiskCheckConfig::MaxOrderNotional { limit: dec!(100) },
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(1)),
+                open_orders: 0,
+                expected: Ok(()),
+            },
+            // TC1: notional exceeds limit
+            TestCase {
+                check: RiskCheckConfig::MaxOrderNotional { limit: dec!(100) },
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(1.5)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::MaxOrderNotional(
+                    CheckFailHigherThan::new(dec!(100), dec!(150)),
+                )),
+            },
+            // TC2: quantity exceeds limit
+            TestCase {
+                check: RiskCheckConfig::MaxOrderQuantity { limit: dec!(1) },
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(1.5)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::MaxOrderQuantity(
+                    CheckFailHigherThan::new(dec!(1), dec!(1.5)),
+                )),
+            },
+            // TC3: open orders within limit
+            TestCase {
+                check: RiskCheckConfig::MaxOpenOrders { limit: 2 },
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(1)),
+                open_orders: 1,
+                expected: Ok(()),
+            },
+            // TC4: open orders would exceed limit
+            TestCase {
+                check: RiskCheckConfig::MaxOpenOrders { limit: 2 },
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(1)),
+                open_orders: 2,
+                expected: Err(RiskRefusedReason::MaxOpenOrders(CheckFailHigherThan::new(
+                    2, 3,
+                ))),
+            },
+            // TC5: price within band
+            TestCase {
+                check: RiskCheckConfig::PriceBand {
+                    max_percent: dec!(0.05),
+                },
+                price_market: Some(dec!(100)),
+                request: request_open(OrderKind::Market, dec!(105), dec!(1)),
+                open_orders: 0,
+                expected: Ok(()),
+            },
+            // TC6: price outside band
+            TestCase {
+                check: RiskCheckConfig::PriceBand {
+                    max_percent: dec!(0.05),
+                },
+                price_market: Some(dec!(100)),
+                request: request_open(OrderKind::Limit, dec!(90), dec!(1)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::PriceBand(CheckFailHigherThan::new(
+                    dec!(0.05),
+                    dec!(0.1),
+                ))),
+            },
+            // TC7: price band requires a market price
+            TestCase {
+                check: RiskCheckConfig::PriceBand {
+                    max_percent: dec!(0.05),
+                },
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(1)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::PriceUnavailable),
+            },
+            // TC8: price band skips conditional orders
+            TestCase {
+                check: RiskCheckConfig::PriceBand {
+                    max_percent: dec!(0.05),
+                },
+                price_market: Some(dec!(100)),
+                request: request_open(stop_market, dec!(150), dec!(1)),
+                open_orders: 0,
+                expected: Ok(()),
+            },
+            // TC9: order conforms to instrument spec
+            TestCase {
+                check: RiskCheckConfig::InstrumentSpec,
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100.01), dec!(0.5)),
+                open_orders: 0,
+                expected: Ok(()),
+            },
+            // TC10: price is not a multiple of the tick size
+            TestCase {
+                check: RiskCheckConfig::InstrumentSpec,
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100.001), dec!(0.5)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::InstrumentSpec(
+                    CheckFailInstrumentSpec::PriceTickSize {
+                        price: dec!(100.001),
+                        tick_size: dec!(0.01),
+                    },
+                )),
+            },
+            // TC11: quantity is not a multiple of the lot size
+            TestCase {
+                check: RiskCheckConfig::InstrumentSpec,
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(0.5005)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::InstrumentSpec(
+                    CheckFailInstrumentSpec::QuantityIncrement {
+                        quantity: dec!(0.5005),
+                        increment: dec!(0.001),
+                    },
+                )),
+            },
+            // TC12: notional below the min notional
+            TestCase {
+                check: RiskCheckConfig::InstrumentSpec,
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(0.05)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::InstrumentSpec(
+                    CheckFailInstrumentSpec::NotionalBelowMin {
+                        notional: dec!(5),
+                        min: dec!(10),
+                    },
+                )),
+            },
+            // TC13: market order price estimates are not checked against the tick size
+            TestCase {
+                check: RiskCheckConfig::InstrumentSpec,
+                price_market: None,
+                request: request_open(OrderKind::Market, dec!(100.001), dec!(0.5)),
+                open_orders: 0,
+                expected: Ok(()),
+            },
+            // TC14: buy stop above the market price passes trigger price check
+            TestCase {
+                check: RiskCheckConfig::TriggerPrice,
+                price_market: Some(dec!(100)),
+                request: request_open(stop_market, dec!(110), dec!(1)),
+                open_orders: 0,
+                expected: Ok(()),
+            },
+            // TC15: buy stop below the market price would trigger immediately
+            TestCase {
+                check: RiskCheckConfig::TriggerPrice,
+                price_market: Some(dec!(120)),
+                request: request_open(stop_market, dec!(110), dec!(1)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::TriggerPrice(CheckFailTriggerPrice::new(
+                    Side::Buy,
+                    stop_market,
+                    dec!(120),
+                ))),
+            },
+            // TC16: trigger price check requires a market price for conditional orders
+            TestCase {
+                check: RiskCheckConfig::TriggerPrice,
+                price_market: None,
+                request: request_open(stop_market, dec!(110), dec!(1)),
+                open_orders: 0,
+                expected: Err(RiskRefusedReason::PriceUnavailable),
+            },
+            // TC17: trigger price check skips non-conditional orders
+            TestCase {
+                check: RiskCheckConfig::TriggerPrice,
+                price_market: None,
+                request: request_open(OrderKind::Limit, dec!(100), dec!(1)),
+                open_orders: 0,
+                expected: Ok(()),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let instrument = instrument_state(test.price_market);
+            let actual = test
+                .check
+                .check(&instrument, &test.request, test.open_orders);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_risk_manager_config_deserialise() {
+        let input = r#"
+        {
+            "checks": [
+                { "check": "instrument_spec" },
+                { "check": "max_order_quantity", "limit": "1.5" },
+                { "check": "max_open_orders", "limit": 5 },
+                { "check": "price_band", "max_percent": "0.05" },
+                { "check": "trigger_price" }
+            ],
+            "exposure": [
+                { "check": "max_position", "limit": "3" },
+                { "check": "max_asset_concentration", "max_percent": "0.5" }
+            ],
+            "self_trade_prevention": "cancel_both"
+        }
+        "#;
+
+        let actual = serde_json::from_str::<RiskManagerConfig>(input).unwrap();
+        let expected = RiskManagerConfig {
+            checks: vec![
+                RiskCheckConfig::InstrumentSpec,
+                RiskCheckConfig::MaxOrderQuantity { limit: dec!(1.5) },
+                RiskCheckConfig::MaxOpenOrders { limit: 5 },
+                RiskCheckConfig::PriceBand {
+                    max_percent: dec!(0.05),
+                },
+                RiskCheckConfig::TriggerPrice,
+            ],
+            exposure: vec![
+                ExposureLimitConfig::MaxPosition { limit: dec!(3) },
+                ExposureLimitConfig::MaxAssetConcentration {
+                    max_percent: dec!(0.5),
+                },
+            ],
+            self_trade_prevention: Some(SelfTradePreventionMode::CancelBoth),
+        };
+
+        assert_eq!(actual, expected);
+    }
+}
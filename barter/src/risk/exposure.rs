@@ -0,0 +1,732 @@
+use crate::{
+    engine::state::{
+        EngineState,
+        asset::AssetStates,
+        instrument::{InstrumentState, data::InstrumentDataState},
+    },
+    risk::{
+        RiskRefusedReason,
+        check::{
+            CheckHigherThan, RiskCheck,
+            util::{calculate_delta, calculate_quote_notional},
+        },
+    },
+};
+use barter_execution::order::{Order, state::ActiveOrderState};
+use barter_instrument::{
+    Side,
+    asset::name::AssetNameInternal,
+    exchange::ExchangeIndex,
+    instrument::{
+        InstrumentIndex,
+        kind::{InstrumentKind, option::OptionKind},
+        quote::InstrumentQuoteAsset,
+    },
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Portfolio exposure limit checked by a [`CheckedRiskManager`](super::checked::CheckedRiskManager)
+/// against the aggregate [`EngineState`].
+///
+/// Exposure includes current positions, active orders (including in flight open requests), and
+/// any open requests approved earlier in the same batch. Orders are assumed to be fully filled,
+/// such that every limit is checked against the worst case exposure.
+///
+/// Orders that can only reduce the absolute position quantity (eg/ closing a position, or
+/// orders with reducing [`OrderFlags`](barter_execution::order::OrderFlags)) are never refused.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum ExposureLimitConfig {
+    /// Refuse orders that could result in an absolute instrument position quantity higher than
+    /// the `limit`.
+    MaxPosition { limit: Decimal },
+
+    /// Refuse orders that could result in an absolute net delta higher than the `limit` for an
+    /// underlying base asset, aggregated across every instrument (eg/ spot, perpetuals, options)
+    /// and exchange.
+    MaxUnderlyingNetDelta { limit: Decimal },
+
+    /// Refuse orders that could result in a gross notional exposure higher than the `limit`
+    /// on an exchange.
+    ///
+    /// Notional values are denominated in each instrument quote asset, so the `limit` assumes
+    /// every instrument on the exchange shares a quote asset (eg/ usdt).
+    MaxExchangeGrossNotional { limit: Decimal },
+
+    /// Refuse orders that could result in the gross notional exposure to an underlying base asset
+    /// exceeding `max_percent` of the total quote asset balance across every exchange
+    /// (eg/ 0.25 for 25%).
+    MaxAssetConcentration { max_percent: Decimal },
+}
+
+impl ExposureLimitConfig {
+    /// Check the [`PortfolioExposure`] associated with an instrument against this limit.
+    ///
+    /// The provided `exposure` should already include the open order request being checked.
+    pub fn check<GlobalData, InstrumentData>(
+        &self,
+        state: &EngineState<GlobalData, InstrumentData>,
+        exposure: &PortfolioExposure,
+        instrument: &InstrumentIndex,
+    ) -> Result<(), RiskRefusedReason> {
+        let target = exposure.instrument(instrument);
+
+        match self {
+            Self::MaxPosition { limit } => CheckHigherThan::new(*limit)
+                .check(&target.quantity_abs_max())
+                .map_err(RiskRefusedReason::MaxPosition),
+            Self::MaxUnderlyingNetDelta { limit } => {
+                let (delta_min, delta_max) = exposure
+                    .instruments()
+                    .filter(|other| other.underlying == target.underlying)
+                    .map(InstrumentExposure::delta_range)
+                    .fold((Decimal::ZERO, Decimal::ZERO), |(min, max), (low, high)| {
+                        (min + low, max + high)
+                    });
+
+                CheckHigherThan::new(*limit)
+                    .check(&delta_min.abs().max(delta_max.abs()))
+                    .map_err(RiskRefusedReason::MaxUnderlyingNetDelta)
+            }
+            Self::MaxExchangeGrossNotional { limit } => {
+                let notional = exposure
+                    .instruments()
+                    .filter(|other| other.exchange == target.exchange)
+                    .map(InstrumentExposure::notional_gross)
+                    .sum::<Result<Decimal, _>>()?;
+
+                CheckHigherThan::new(*limit)
+                    .check(&notional)
+                    .map_err(RiskRefusedReason::MaxExchangeGrossNotional)
+            }
+            Self::MaxAssetConcentration { max_percent } => {
+                let notional = exposure
+                    .instruments()
+                    .filter(|other| {
+                        other.underlying == target.underlying && other.quote == target.quote
+                    })
+                    .map(InstrumentExposure::notional_gross)
+                    .sum::<Result<Decimal, _>>()?;
+
+                let balance = state
+                    .assets
+                    .assets()
+                    .filter(|asset| asset.asset.name_internal == target.quote)
+                    .filter_map(|asset| asset.balance.as_ref())
+                    .map(|balance| balance.value.total)
+                    .sum::<Decimal>();
+
+                let concentration = notional
+                    .checked_div(balance)
+                    .ok_or(RiskRefusedReason::BalanceUnavailable)?;
+
+                CheckHigherThan::new(*max_percent)
+                    .check(&concentration)
+                    .map_err(RiskRefusedReason::MaxAssetConcentration)
+            }
+        }
+    }
+}
+
+/// Potential exposure of every instrument in an [`EngineState`], indexed by [`InstrumentIndex`].
+#[derive(Debug, Clone, Eq, PartialEq, Default, Deserialize, Serialize)]
+pub struct PortfolioExposure(pub Vec<InstrumentExposure>);
+
+impl PortfolioExposure {
+    /// Construct a new [`PortfolioExposure`] from the positions and active orders of every
+    /// instrument in the [`EngineState`].
+    pub fn new<GlobalData, InstrumentData>(
+        state: &EngineState<GlobalData, InstrumentData>,
+    ) -> Result<Self, RiskRefusedReason>
+    where
+        InstrumentData: InstrumentDataState,
+    {
+        state
+            .instruments
+            .0
+            .values()
+            .map(|instrument| InstrumentExposure::new(&state.assets, instrument))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+
+    /// Return a reference to the [`InstrumentExposure`] associated with an [`InstrumentIndex`].
+    ///
+    /// Panics if the `InstrumentExposure` associated with the `InstrumentIndex` does not exist.
+    pub fn instrument(&self, key: &InstrumentIndex) -> &InstrumentExposure {
+        self.0
+            .get(key.index())
+            .unwrap_or_else(|| panic!("PortfolioExposure does not contain: {key}"))
+    }
+
+    /// Return a mutable reference to the [`InstrumentExposure`] associated with an
+    /// [`InstrumentIndex`].
+    ///
+    /// Panics if the `InstrumentExposure` associated with the `InstrumentIndex` does not exist.
+    pub fn instrument_mut(&mut self, key: &InstrumentIndex) -> &mut InstrumentExposure {
+        self.0
+            .get_mut(key.index())
+            .unwrap_or_else(|| panic!("PortfolioExposure does not contain: {key}"))
+    }
+
+    /// Return an `Iterator` of all [`InstrumentExposure`]s.
+    pub fn instruments(&self) -> impl Iterator<Item = &InstrumentExposure> {
+        self.0.iter()
+    }
+}
+
+/// Potential exposure of an instrument, being the current position plus the remaining quantity
+/// of every active order.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct InstrumentExposure {
+    pub exchange: ExchangeIndex,
+
+    /// Underlying base asset the instrument provides exposure to.
+    pub underlying: AssetNameInternal,
+
+    /// Asset the instrument notional values are denominated in.
+    pub quote: AssetNameInternal,
+
+    /// Delta of the instrument relative to the underlying (1.0 for non-`Option` instruments).
+    pub delta: Decimal,
+    pub contract_size: Decimal,
+
+    /// `Option` strike price, used to calculate `Option` notional values.
+    pub strike: Option<Decimal>,
+
+    /// Price used to calculate the position notional value, if available.
+    pub price_position: Option<Decimal>,
+
+    /// Signed position quantity (positive for `Side::Buy` LONG, negative for `Side::Sell` SHORT).
+    pub position: Decimal,
+
+    /// Sum of the remaining quantity of every active `Side::Buy` order.
+    pub quantity_buy: Decimal,
+
+    /// Sum of the remaining quantity of every active `Side::Sell` order.
+    pub quantity_sell: Decimal,
+
+    /// Sum of the notional value of every active order.
+    pub notional_orders: Decimal,
+}
+
+impl InstrumentExposure {
+    /// Construct a new [`InstrumentExposure`] from the position and active orders of an
+    /// [`InstrumentState`].
+    pub fn new<InstrumentData>(
+        assets: &AssetStates,
+        state: &InstrumentState<InstrumentData>,
+    ) -> Result<Self, RiskRefusedReason>
+    where
+        InstrumentData: InstrumentDataState,
+    {
+        let asset_name = |index| assets.asset_index(index).asset.name_internal.clone();
+        let underlying = asset_name(&state.instrument.underlying.base);
+        let quote = match state.instrument.quote {
+            InstrumentQuoteAsset::UnderlyingBase => underlying.clone(),
+            InstrumentQuoteAsset::UnderlyingQuote => asset_name(&state.instrument.underlying.quote),
+        };
+
+        let position = state.position.current.as_ref();
+
+        let (delta, strike, price_position) = match &state.instrument.kind {
+            InstrumentKind::Option(option) => {
+                let delta = state.data.delta().unwrap_or(match option.kind {
+                    OptionKind::Call => Decimal::ONE,
+                    OptionKind::Put => Decimal::NEGATIVE_ONE,
+                });
+                (delta, Some(option.strike), Some(option.strike))
+            }
+            _ => {
+                let price = state
+                    .data
+                    .price()
+                    .or(position.map(|position| position.price_entry_average));
+                (Decimal::ONE, None, price)
+            }
+        };
+
+        let mut exposure = Self {
+            exchange: state.instrument.exchange,
+            underlying,
+            quote,
+            delta,
+            contract_size: state.instrument.kind.contract_size(),
+            strike,
+            price_position,
+            position: position
+                .map(|position| match position.side {
+                    Side::Buy => position.quantity_abs,
+                    Side::Sell => -position.quantity_abs,
+                })
+                .unwrap_or_default(),
+            quantity_buy: Decimal::ZERO,
+            quantity_sell: Decimal::ZERO,
+            notional_orders: Decimal::ZERO,
+        };
+
+        for order in state.orders.0.values() {
+            exposure.add_order(order.side, quantity_remaining(order), order.price)?;
+        }
+
+        Ok(exposure)
+    }
+
+    /// Add the exposure of an order with the provided remaining quantity.
+    pub fn add_order(
+        &mut self,
+        side: Side,
+        quantity: Decimal,
+        price: Decimal,
+    ) -> Result<(), RiskRefusedReason> {
+        let notional =
+            calculate_quote_notional(quantity, self.strike.unwrap_or(price), self.contract_size)
+                .and_then(|notional| self.notional_orders.checked_add(notional))
+                .ok_or(RiskRefusedReason::CalculationOverflow)?;
+
+        match side {
+            Side::Buy => self.quantity_buy += quantity,
+            Side::Sell => self.quantity_sell += quantity,
+        }
+        self.notional_orders = notional;
+
+        Ok(())
+    }
+
+    /// Returns true if an order with the provided side and quantity can only reduce the absolute
+    /// position quantity, even if every active order on the same side is also filled.
+    pub fn is_reducing(&self, side: Side, quantity: Decimal) -> bool {
+        let quantity_reducing = match side {
+            Side::Buy if self.position < Decimal::ZERO => self.quantity_buy + quantity,
+            Side::Sell if self.position > Decimal::ZERO => self.quantity_sell + quantity,
+            _ => return false,
+        };
+
+        quantity_reducing <= self.position.abs()
+    }
+
+    /// Signed position quantity if every active `Side::Buy` order is filled.
+    pub fn quantity_long_max(&self) -> Decimal {
+        self.position + self.quantity_buy
+    }
+
+    /// Signed position quantity if every active `Side::Sell` order is filled.
+    pub fn quantity_short_max(&self) -> Decimal {
+        self.position - self.quantity_sell
+    }
+
+    /// Largest absolute position quantity that could result from active orders being filled.
+    pub fn quantity_abs_max(&self) -> Decimal {
+        self.quantity_long_max()
+            .abs()
+            .max(self.quantity_short_max().abs())
+    }
+
+    /// Range of net delta that could result from active orders being filled, returned as
+    /// `(min, max)`.
+    pub fn delta_range(&self) -> (Decimal, Decimal) {
+        let delta = |quantity: Decimal| {
+            let side = if quantity.is_sign_negative() {
+                Side::Sell
+            } else {
+                Side::Buy
+            };
+            calculate_delta(self.delta, self.contract_size, side, quantity.abs())
+        };
+
+        let long = delta(self.quantity_long_max());
+        let short = delta(self.quantity_short_max());
+
+        (long.min(short), long.max(short))
+    }
+
+    /// Gross notional value of the position and every active order.
+    pub fn notional_gross(&self) -> Result<Decimal, RiskRefusedReason> {
+        if self.position.is_zero() {
+            return Ok(self.notional_orders);
+        }
+
+        let price = self
+            .price_position
+            .ok_or(RiskRefusedReason::PriceUnavailable)?;
+
+        calculate_quote_notional(self.position.abs(), price, self.contract_size)
+            .and_then(|notional| notional.checked_add(self.notional_orders))
+            .ok_or(RiskRefusedReason::CalculationOverflow)
+    }
+}
+
+fn quantity_remaining<ExchangeKey, InstrumentKey>(
+    order: &Order<ExchangeKey, InstrumentKey, ActiveOrderState>,
+) -> Decimal {
+    order
+        .state
+        .open_meta()
+        .map(|open| open.quantity_remaining(order.quantity))
+        .unwrap_or(order.quantity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::state::{
+            global::DefaultGlobalData, instrument::data::DefaultInstrumentMarketData,
+            order::in_flight_recorder::InFlightRequestRecorder,
+        },
+        risk::{
+            RiskManager, RiskRefused,
+            check::CheckFailHigherThan,
+            checked::{CheckedRiskManager, RiskManagerConfig},
+        },
+    };
+    use barter_execution::{
+        balance::Balance,
+        order::{
+            OrderFlags, OrderKey, OrderKind, TimeInForce,
+            id::{ClientOrderId, OrderId, StrategyId},
+            request::{OrderRequestOpen, RequestOpen},
+        },
+        trade::{AssetFees, Trade, TradeId},
+    };
+    use barter_instrument::{
+        Underlying,
+        asset::{Asset, QuoteAsset},
+        exchange::ExchangeId,
+        index::IndexedInstruments,
+        instrument::{
+            Instrument, kind::perpetual::PerpetualContract, name::InstrumentNameInternal,
+        },
+    };
+    use chrono::{DateTime, Utc};
+    use rust_decimal_macros::dec;
+
+    type State = EngineState<DefaultGlobalData, DefaultInstrumentMarketData>;
+
+    const SPOT: &str = "binance_spot_btc_usdt";
+    const PERPETUAL: &str = "okx_perpetual_btc_usdt";
+
+    fn instrument_key(state: &State, name: &str) -> InstrumentIndex {
+        state
+            .instruments
+            .instrument(&InstrumentNameInternal::new(name))
+            .key
+    }
+
+    fn request_open(
+        state: &State,
+        instrument: &str,
+        side: Side,
+        quantity: Decimal,
+    ) -> OrderRequestOpen {
+        let instrument = state
+            .instruments
+            .instrument(&InstrumentNameInternal::new(instrument));
+
+        OrderRequestOpen {
+            key: OrderKey {
+                exchange: instrument.instrument.exchange,
+                instrument: instrument.key,
+                strategy: StrategyId::new("strategy"),
+                cid: ClientOrderId::random(),
+            },
+            state: RequestOpen {
+                side,
+                price: dec!(100),
+                quantity,
+                kind: OrderKind::Limit,
+                time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+                flags: OrderFlags::default(),
+            },
+        }
+    }
+
+    // Long 1 btc spot on binance @ 100 usdt, with an in flight request to sell 1 btc perpetual
+    // on okx @ 100 usdt, and 200 usdt on each exchange
+    fn state() -> State {
+        let instruments = IndexedInstruments::builder()
+            .add_instrument(Instrument::spot(
+                ExchangeId::BinanceSpot,
+                SPOT,
+                "BTCUSDT",
+                Underlying::new("btc", "usdt"),
+                None,
+            ))
+            .add_instrument(Instrument::new(
+                ExchangeId::Okx,
+                PERPETUAL,
+                "BTC-USDT-SWAP",
+                Underlying::new("btc", "usdt"),
+                InstrumentQuoteAsset::UnderlyingQuote,
+                InstrumentKind::Perpetual(PerpetualContract {
+                    contract_size: dec!(1),
+                    settlement_asset: Asset::from("usdt"),
+                }),
+                None,
+            ))
+            .build();
+
+        let mut state = EngineState::builder(&instruments, DefaultGlobalData, |_| {
+            DefaultInstrumentMarketData::default()
+        })
+        .time_engine_start(DateTime::<Utc>::MIN_UTC)
+        .balances([
+            (
+                ExchangeId::BinanceSpot,
+                "usdt",
+                Balance::new(dec!(200), dec!(200)),
+            ),
+            (ExchangeId::Okx, "usdt", Balance::new(dec!(200), dec!(200))),
+        ])
+        .build();
+
+        let spot = instrument_key(&state, SPOT);
+        state
+            .instruments
+            .instrument_index_mut(&spot)
+            .position
+            .update_from_trade(&Trade {
+                id: TradeId::new("trade"),
+                order_id: OrderId::new("order"),
+                instrument: spot,
+                strategy: StrategyId::new("strategy"),
+                time_exchange: DateTime::<Utc>::MIN_UTC,
+                side: Side::Buy,
+                price: dec!(100),
+                quantity: dec!(1),
+                fees: AssetFees {
+                    asset: QuoteAsset,
+                    fees: dec!(0),
+                },
+            });
+
+        let request = request_open(&state, PERPETUAL, Side::Sell, dec!(1));
+        state
+            .instruments
+            .instrument_index_mut(&request.key.instrument)
+            .orders
+            .record_in_flight_open(&request);
+
+        state
+    }
+
+    #[test]
+    fn test_portfolio_exposure_new() {
+        let state = state();
+        let exposure = PortfolioExposure::new(&state).unwrap();
+
+        let spot = exposure.instrument(&instrument_key(&state, SPOT));
+        assert_eq!(spot.position, dec!(1));
+        assert_eq!(spot.quantity_abs_max(), dec!(1));
+        assert_eq!(spot.delta_range(), (dec!(1), dec!(1)));
+        assert_eq!(spot.notional_gross(), Ok(dec!(100)));
+        assert!(spot.is_reducing(Side::Sell, dec!(1)));
+        assert!(!spot.is_reducing(Side::Sell, dec!(1.5)));
+        assert!(!spot.is_reducing(Side::Buy, dec!(1)));
+
+        let perpetual = exposure.instrument(&instrument_key(&state, PERPETUAL));
+        assert_eq!(perpetual.position, dec!(0));
+        assert_eq!(perpetual.quantity_sell, dec!(1));
+        assert_eq!(perpetual.quantity_abs_max(), dec!(1));
+        assert_eq!(perpetual.delta_range(), (dec!(-1), dec!(0)));
+        assert_eq!(perpetual.notional_gross(), Ok(dec!(100)));
+    }
+
+    #[test]
+    fn test_exposure_limit_config_check() {
+        struct TestCase {
+            limit: ExposureLimitConfig,
+            request: OrderRequestOpen,
+            expected: Result<(), RiskRefusedReason>,
+        }
+
+        let state = state();
+
+        let cases = vec![
+            // TC0: spot position within max position
+            TestCase {
+                limit: ExposureLimitConfig::MaxPosition { limit: dec!(1.5) },
+                request: request_open(&state, SPOT, Side::Buy, dec!(0.5)),
+                expected: Ok(()),
+            },
+            // TC1: spot position could exceed max position
+            TestCase {
+                limit: ExposureLimitConfig::MaxPosition { limit: dec!(1.5) },
+                request: request_open(&state, SPOT, Side::Buy, dec!(1)),
+                expected: Err(RiskRefusedReason::MaxPosition(CheckFailHigherThan::new(
+                    dec!(1.5),
+                    dec!(2),
+                ))),
+            },
+            // TC2: net delta across venues could exceed limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxUnderlyingNetDelta { limit: dec!(1.5) },
+                request: request_open(&state, SPOT, Side::Buy, dec!(1)),
+                expected: Err(RiskRefusedReason::MaxUnderlyingNetDelta(
+                    CheckFailHigherThan::new(dec!(1.5), dec!(2)),
+                )),
+            },
+            // TC3: perpetual short offsets spot long, so net delta within limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxUnderlyingNetDelta { limit: dec!(1.5) },
+                request: request_open(&state, PERPETUAL, Side::Sell, dec!(1)),
+                expected: Ok(()),
+            },
+            // TC4: exchange gross notional within limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxExchangeGrossNotional { limit: dec!(150) },
+                request: request_open(&state, PERPETUAL, Side::Buy, dec!(0.5)),
+                expected: Ok(()),
+            },
+            // TC5: in flight request counts towards exchange gross notional
+            TestCase {
+                limit: ExposureLimitConfig::MaxExchangeGrossNotional { limit: dec!(150) },
+                request: request_open(&state, PERPETUAL, Side::Buy, dec!(1)),
+                expected: Err(RiskRefusedReason::MaxExchangeGrossNotional(
+                    CheckFailHigherThan::new(dec!(150), dec!(200)),
+                )),
+            },
+            // TC6: btc concentration of 250 / 400 usdt within limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxAssetConcentration {
+                    max_percent: dec!(0.75),
+                },
+                request: request_open(&state, SPOT, Side::Buy, dec!(0.5)),
+                expected: Ok(()),
+            },
+            // TC7: btc concentration of 250 / 400 usdt exceeds limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxAssetConcentration {
+                    max_percent: dec!(0.5),
+                },
+                request: request_open(&state, SPOT, Side::Buy, dec!(0.5)),
+                expected: Err(RiskRefusedReason::MaxAssetConcentration(
+                    CheckFailHigherThan::new(dec!(0.5), dec!(0.625)),
+                )),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let mut exposure = PortfolioExposure::new(&state).unwrap();
+            exposure
+                .instrument_mut(&test.request.key.instrument)
+                .add_order(
+                    test.request.state.side,
+                    test.request.state.quantity,
+                    test.request.state.price,
+                )
+                .unwrap();
+
+            let actual = test
+                .limit
+                .check(&state, &exposure, &test.request.key.instrument);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_checked_risk_manager_approves_reducing_orders_at_limit() {
+        struct TestCase {
+            limit: ExposureLimitConfig,
+            request: OrderRequestOpen,
+            expected: Result<(), RiskRefusedReason>,
+        }
+
+        let state = state();
+
+        let reduce_only = |mut request: OrderRequestOpen| {
+            request.state.flags = OrderFlags::reduce_only();
+            request
+        };
+
+        let cases = vec![
+            // TC0: closing spot position at max position
+            TestCase {
+                limit: ExposureLimitConfig::MaxPosition { limit: dec!(1) },
+                request: request_open(&state, SPOT, Side::Sell, dec!(1)),
+                expected: Ok(()),
+            },
+            // TC1: closing spot position at exchange gross notional limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxExchangeGrossNotional { limit: dec!(100) },
+                request: request_open(&state, SPOT, Side::Sell, dec!(1)),
+                expected: Ok(()),
+            },
+            // TC2: partially closing spot position at btc concentration of 200 / 400 usdt
+            TestCase {
+                limit: ExposureLimitConfig::MaxAssetConcentration {
+                    max_percent: dec!(0.5),
+                },
+                request: request_open(&state, SPOT, Side::Sell, dec!(0.5)),
+                expected: Ok(()),
+            },
+            // TC3: selling more than the spot position could flip it, so exposure is checked
+            TestCase {
+                limit: ExposureLimitConfig::MaxExchangeGrossNotional { limit: dec!(100) },
+                request: request_open(&state, SPOT, Side::Sell, dec!(2)),
+                expected: Err(RiskRefusedReason::MaxExchangeGrossNotional(
+                    CheckFailHigherThan::new(dec!(100), dec!(300)),
+                )),
+            },
+            // TC4: perpetual buy at exchange gross notional limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxExchangeGrossNotional { limit: dec!(100) },
+                request: request_open(&state, PERPETUAL, Side::Buy, dec!(1)),
+                expected: Err(RiskRefusedReason::MaxExchangeGrossNotional(
+                    CheckFailHigherThan::new(dec!(100), dec!(200)),
+                )),
+            },
+            // TC5: reduce only perpetual buy at exchange gross notional limit
+            TestCase {
+                limit: ExposureLimitConfig::MaxExchangeGrossNotional { limit: dec!(100) },
+                request: reduce_only(request_open(&state, PERPETUAL, Side::Buy, dec!(1))),
+                expected: Ok(()),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let risk = CheckedRiskManager::<State>::new(RiskManagerConfig {
+                checks: vec![],
+                exposure: vec![test.limit],
+                self_trade_prevention: None,
+            });
+
+            let (_, _, _, refused) = risk.check(&state, vec![], vec![test.request]);
+
+            let actual = refused
+                .into_iter()
+                .next()
+                .map_or(Ok(()), |refused| Err(refused.reason));
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_checked_risk_manager_includes_approved_requests_in_exposure() {
+        let state = state();
+        let risk = CheckedRiskManager::<State>::new(RiskManagerConfig {
+            checks: vec![],
+            exposure: vec![ExposureLimitConfig::MaxPosition { limit: dec!(2) }],
+            self_trade_prevention: None,
+        });
+
+        let first = request_open(&state, SPOT, Side::Buy, dec!(0.75));
+        let second = request_open(&state, SPOT, Side::Buy, dec!(0.5));
+
+        let (_, approved, _, refused) =
+            risk.check(&state, vec![], vec![first.clone(), second.clone()]);
+
+        let approved = approved.into_iter().map(|approved| approved.0.key.cid);
+        assert!(approved.eq([first.key.cid]));
+
+        let refused = refused.into_iter().collect::<Vec<_>>();
+        assert_eq!(
+            refused,
+            vec![RiskRefused::new(
+                second,
+                RiskRefusedReason::MaxPosition(CheckFailHigherThan::new(dec!(2), dec!(2.25)))
+            )]
+        );
+    }
+}
@@ -0,0 +1,579 @@
+use crate::{
+    engine::{
+        action::send_requests::{SendCancelsAndOpensOutput, SendRequestsOutput},
+        error::UnrecoverableEngineError,
+        state::instrument::InstrumentStates,
+    },
+    risk::check::{CheckFailHigherThan, CheckHigherThan, RiskCheck},
+};
+use barter_execution::order::request::RequestCancel;
+use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
+use barter_integration::collection::none_one_or_many::NoneOneOrMany;
+use chrono::{DateTime, TimeDelta, Utc};
+use derive_more::Constructor;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Configuration of a [`KillSwitch`], defining the rolling session PnL limits that automatically
+/// disable trading when breached.
+///
+/// PnL is aggregated across every instrument, so limits are denominated in the instrument quote
+/// asset and assume every instrument shares a quote asset (eg/ usdt).
+///
+/// Note that PnL is calculated from instrument positions rather than `AssetState` balances,
+/// since balances also change from deposits, withdrawals and asset conversions (eg/ buying btc
+/// with usdt), none of which are profit or loss.
+///
+/// ### Example Config
+/// ```json
+/// {
+///     "max_daily_loss": "1000",
+///     "max_drawdown": "1500",
+///     "cancel_orders": true,
+///     "close_positions": true
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct KillSwitchConfig {
+    /// Maximum loss from the PnL at the start of the rolling session.
+    #[serde(default)]
+    pub max_daily_loss: Option<Decimal>,
+
+    /// Maximum loss from the peak PnL reached during the rolling session.
+    #[serde(default)]
+    pub max_drawdown: Option<Decimal>,
+
+    /// Duration of the rolling session window the PnL limits are measured over (defaults to
+    /// 1 day).
+    #[serde(default = "default_session_secs")]
+    pub session_secs: u64,
+
+    /// Cancel all open orders when triggered.
+    #[serde(default)]
+    pub cancel_orders: bool,
+
+    /// Close all open positions when triggered.
+    #[serde(default)]
+    pub close_positions: bool,
+}
+
+impl KillSwitchConfig {
+    /// Duration of the rolling session window the PnL limits are measured over.
+    pub fn session(&self) -> TimeDelta {
+        i64::try_from(self.session_secs)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .unwrap_or(TimeDelta::MAX)
+    }
+
+    /// Returns true if any session PnL limit is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.max_daily_loss.is_some() || self.max_drawdown.is_some()
+    }
+}
+
+impl Default for KillSwitchConfig {
+    fn default() -> Self {
+        Self {
+            max_daily_loss: None,
+            max_drawdown: None,
+            session_secs: default_session_secs(),
+            cancel_orders: false,
+            close_positions: false,
+        }
+    }
+}
+
+fn default_session_secs() -> u64 {
+    60 * 60 * 24
+}
+
+/// Number of PnL samples retained for the rolling session window, which determines the
+/// resolution of the window start (eg/ 1 minute for the default 1 day session).
+const SESSION_SAMPLES: i32 = 1440;
+
+/// Risk component that tracks the realised and unrealised PnL of a rolling session, triggering
+/// when a configured daily loss or max drawdown limit is breached.
+///
+/// When triggered, the [`Engine`](crate::engine::Engine) sets `TradingState::Disabled`, and
+/// optionally cancels all orders and closes all positions. Once triggered, the kill switch is
+/// disarmed until trading is re-enabled, at which point it triggers again if a limit is still
+/// breached.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct KillSwitch {
+    pub config: KillSwitchConfig,
+
+    /// False once triggered, until re-armed by trading being re-enabled.
+    pub armed: bool,
+
+    /// PnL samples of the rolling session window, oldest first.
+    pub samples: VecDeque<KillSwitchSample>,
+}
+
+impl Default for KillSwitch {
+    fn default() -> Self {
+        Self::new(KillSwitchConfig::default())
+    }
+}
+
+/// PnL recorded by the [`KillSwitch`] during a sample interval of the rolling session window.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct KillSwitchSample {
+    pub time_start: DateTime<Utc>,
+    pub pnl_open: Decimal,
+    pub pnl_close: Decimal,
+    pub pnl_peak: Decimal,
+}
+
+impl KillSwitchSample {
+    fn new(time_start: DateTime<Utc>, pnl: Decimal) -> Self {
+        Self {
+            time_start,
+            pnl_open: pnl,
+            pnl_close: pnl,
+            pnl_peak: pnl,
+        }
+    }
+}
+
+impl KillSwitch {
+    /// Construct a new armed [`KillSwitch`] from the provided [`KillSwitchConfig`].
+    pub fn new(config: KillSwitchConfig) -> Self {
+        Self {
+            config,
+            armed: true,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Re-arm the [`KillSwitch`], such that it triggers again if a limit is still breached.
+    pub fn rearm(&mut self) {
+        if !self.armed {
+            info!("KillSwitch re-armed");
+            self.armed = true;
+        }
+    }
+
+    /// Update the [`KillSwitch`] with the latest total PnL, returning a [`KillSwitchTriggered`]
+    /// if it is armed and a limit of the rolling session has been breached.
+    pub fn update(&mut self, time: DateTime<Utc>, pnl: Decimal) -> Option<KillSwitchTriggered> {
+        if !self.config.is_enabled() {
+            return None;
+        }
+
+        self.record(time, pnl);
+
+        if !self.armed {
+            return None;
+        }
+
+        let (pnl_start, pnl_peak) = self.session_pnl(time)?;
+
+        let reason = self
+            .config
+            .max_daily_loss
+            .and_then(|limit| {
+                CheckHigherThan::new(limit)
+                    .check(&(pnl_start - pnl))
+                    .err()
+                    .map(KillSwitchReason::DailyLoss)
+            })
+            .or_else(|| {
+                self.config.max_drawdown.and_then(|limit| {
+                    CheckHigherThan::new(limit)
+                        .check(&(pnl_peak - pnl))
+                        .err()
+                        .map(KillSwitchReason::Drawdown)
+                })
+            })?;
+
+        self.armed = false;
+
+        let triggered = KillSwitchTriggered::new(
+            time,
+            reason,
+            pnl - pnl_start,
+            self.config.cancel_orders,
+            self.config.close_positions,
+        );
+
+        warn!(?triggered, "KillSwitch triggered");
+
+        Some(triggered)
+    }
+
+    /// Returns the PnL at the start of the rolling session window ending at the provided time,
+    /// and the peak PnL within it.
+    ///
+    /// Returns `None` if no PnL has been recorded.
+    pub fn session_pnl(&self, time: DateTime<Utc>) -> Option<(Decimal, Decimal)> {
+        let window_start = self.window_start(time);
+        let mut samples = self.samples.iter();
+
+        // Sample open before the window started contributes its latest PnL only
+        let first = samples.next()?;
+        let (pnl_start, pnl_peak) = if first.time_start < window_start {
+            (first.pnl_close, first.pnl_close)
+        } else {
+            (first.pnl_open, first.pnl_peak)
+        };
+
+        let pnl_peak = samples
+            .map(|sample| sample.pnl_peak)
+            .fold(pnl_peak, Decimal::max);
+
+        Some((pnl_start, pnl_peak))
+    }
+
+    fn record(&mut self, time: DateTime<Utc>, pnl: Decimal) {
+        let interval = self.config.session() / SESSION_SAMPLES;
+
+        match self.samples.back_mut() {
+            Some(sample) if time < sample.time_start + interval => {
+                sample.pnl_close = pnl;
+                sample.pnl_peak = sample.pnl_peak.max(pnl);
+            }
+            _ => self.samples.push_back(KillSwitchSample::new(time, pnl)),
+        }
+
+        // Remove samples preceding the window, retaining the latest as the window start PnL
+        let window_start = self.window_start(time);
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|next| next.time_start <= window_start)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    fn window_start(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        time.checked_sub_signed(self.config.session())
+            .unwrap_or(DateTime::<Utc>::MIN_UTC)
+    }
+}
+
+/// Calculate the total PnL of every instrument, including the realised PnL of exited positions
+/// and the realised, unrealised and funding PnL of current positions.
+pub fn calculate_pnl_total<InstrumentData>(
+    instruments: &InstrumentStates<InstrumentData>,
+) -> Decimal {
+    instruments
+        .0
+        .values()
+        .map(|state| {
+            let pnl_current = state
+                .position
+                .current
+                .as_ref()
+                .map(|position| {
+                    position.pnl_realised + position.pnl_unrealised + position.pnl_funding
+                })
+                .unwrap_or_default();
+
+            state.tear_sheet.pnl_returns.pnl_raw + pnl_current
+        })
+        .sum()
+}
+
+/// Record of a [`KillSwitch`] being triggered.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct KillSwitchTriggered {
+    pub time: DateTime<Utc>,
+    pub reason: KillSwitchReason,
+
+    /// PnL since the start of the rolling session.
+    pub pnl_session: Decimal,
+    pub cancel_orders: bool,
+    pub close_positions: bool,
+}
+
+/// Reason a [`KillSwitch`] was triggered.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Error)]
+pub enum KillSwitchReason {
+    /// Loss from the PnL at the start of the session exceeded the configured maximum.
+    #[error("max daily loss exceeded: {0}")]
+    DailyLoss(CheckFailHigherThan<Decimal>),
+
+    /// Loss from the peak PnL of the session exceeded the configured maximum.
+    #[error("max drawdown exceeded: {0}")]
+    Drawdown(CheckFailHigherThan<Decimal>),
+}
+
+/// Output produced by the [`Engine`](crate::engine::Engine) when a [`KillSwitch`] is triggered,
+/// used to construct an `Engine` [`EngineAudit`](crate::engine::audit::EngineAudit).
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct KillSwitchOutput<
+    OnTradingDisabled,
+    ExchangeKey = ExchangeIndex,
+    InstrumentKey = InstrumentIndex,
+> {
+    pub triggered: KillSwitchTriggered,
+    pub on_trading_disabled: Option<OnTradingDisabled>,
+    pub cancel_orders: Option<SendRequestsOutput<RequestCancel, ExchangeKey, InstrumentKey>>,
+    pub close_positions: Option<SendCancelsAndOpensOutput<ExchangeKey, InstrumentKey>>,
+}
+
+impl<OnTradingDisabled, ExchangeKey, InstrumentKey>
+    KillSwitchOutput<OnTradingDisabled, ExchangeKey, InstrumentKey>
+{
+    /// Returns any unrecoverable errors that occurred cancelling orders or closing positions.
+    pub fn unrecoverable_errors(&self) -> NoneOneOrMany<UnrecoverableEngineError> {
+        let cancels = self
+            .cancel_orders
+            .as_ref()
+            .map(SendRequestsOutput::unrecoverable_errors)
+            .unwrap_or_default();
+
+        let closes = self
+            .close_positions
+            .as_ref()
+            .map(SendCancelsAndOpensOutput::unrecoverable_errors)
+            .unwrap_or_default();
+
+        cancels.extend(closes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::state::{
+            EngineState, global::DefaultGlobalData, instrument::data::DefaultInstrumentMarketData,
+        },
+        test_utils::{time_plus_days, time_plus_secs},
+    };
+    use barter_execution::{
+        funding::FundingPayment,
+        order::id::{OrderId, StrategyId},
+        trade::{AssetFees, Trade, TradeId},
+    };
+    use barter_instrument::{
+        Side, Underlying,
+        asset::{Asset, QuoteAsset},
+        exchange::ExchangeId,
+        index::IndexedInstruments,
+        instrument::{
+            Instrument,
+            kind::{InstrumentKind, perpetual::PerpetualContract},
+            quote::InstrumentQuoteAsset,
+        },
+    };
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_kill_switch_update() {
+        struct TestCase {
+            time_plus_secs: i64,
+            pnl: Decimal,
+            rearm: bool,
+            expected: Option<KillSwitchReason>,
+        }
+
+        let base_time = DateTime::<Utc>::MIN_UTC;
+
+        let mut kill_switch = KillSwitch::new(KillSwitchConfig {
+            max_daily_loss: Some(dec!(100)),
+            max_drawdown: Some(dec!(50)),
+            ..Default::default()
+        });
+
+        let cases = vec![
+            // TC0: session started with PnL of 200
+            TestCase {
+                time_plus_secs: 0,
+                pnl: dec!(200),
+                rearm: false,
+                expected: None,
+            },
+            // TC1: new peak PnL of 240
+            TestCase {
+                time_plus_secs: 120,
+                pnl: dec!(240),
+                rearm: false,
+                expected: None,
+            },
+            // TC2: drawdown of 40 from peak within limit
+            TestCase {
+                time_plus_secs: 240,
+                pnl: dec!(200),
+                rearm: false,
+                expected: None,
+            },
+            // TC3: drawdown of 60 from peak breaches limit
+            TestCase {
+                time_plus_secs: 360,
+                pnl: dec!(180),
+                rearm: false,
+                expected: Some(KillSwitchReason::Drawdown(CheckFailHigherThan::new(
+                    dec!(50),
+                    dec!(60),
+                ))),
+            },
+            // TC4: disarmed since triggered
+            TestCase {
+                time_plus_secs: 480,
+                pnl: dec!(0),
+                rearm: false,
+                expected: None,
+            },
+            // TC5: re-armed with daily loss of 200 still breaching limit (checked before drawdown)
+            TestCase {
+                time_plus_secs: 600,
+                pnl: dec!(0),
+                rearm: true,
+                expected: Some(KillSwitchReason::DailyLoss(CheckFailHigherThan::new(
+                    dec!(100),
+                    dec!(200),
+                ))),
+            },
+            // TC6: re-armed after session rolled past the losses, so no limit breached
+            TestCase {
+                time_plus_secs: 60 * 60 * 24 + 660,
+                pnl: dec!(0),
+                rearm: true,
+                expected: None,
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            if test.rearm {
+                kill_switch.rearm();
+            }
+            let time = time_plus_secs(base_time, test.time_plus_secs);
+            let actual = kill_switch
+                .update(time, test.pnl)
+                .map(|triggered| triggered.reason);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+
+        assert!(kill_switch.armed);
+        assert_eq!(
+            kill_switch.samples,
+            VecDeque::from([
+                KillSwitchSample::new(time_plus_secs(base_time, 600), dec!(0)),
+                KillSwitchSample::new(time_plus_secs(base_time, 60 * 60 * 24 + 660), dec!(0)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_kill_switch_update_rolling_session() {
+        let base_time = DateTime::<Utc>::MIN_UTC;
+        let mut kill_switch = KillSwitch::new(KillSwitchConfig {
+            max_daily_loss: Some(dec!(100)),
+            ..Default::default()
+        });
+
+        // Losses of 150 within 24 hours breach the limit, despite spanning 24 hours since start
+        assert_eq!(kill_switch.update(base_time, dec!(0)), None);
+        assert_eq!(
+            kill_switch.update(
+                time_plus_days(base_time, 0) + TimeDelta::hours(20),
+                dec!(-90)
+            ),
+            None
+        );
+        let triggered = kill_switch
+            .update(
+                time_plus_days(base_time, 1) + TimeDelta::hours(1),
+                dec!(-150),
+            )
+            .unwrap();
+        assert_eq!(
+            triggered.reason,
+            KillSwitchReason::DailyLoss(CheckFailHigherThan::new(dec!(100), dec!(150)))
+        );
+        assert_eq!(triggered.pnl_session, dec!(-150));
+
+        // Losses that occurred before the rolling session window are excluded
+        kill_switch.rearm();
+        let time = time_plus_days(base_time, 2) + TimeDelta::hours(2);
+        assert_eq!(kill_switch.update(time, dec!(-200)), None);
+        assert_eq!(
+            kill_switch.session_pnl(time),
+            Some((dec!(-150), dec!(-150)))
+        );
+    }
+
+    #[test]
+    fn test_calculate_pnl_total_includes_funding() {
+        let instruments = IndexedInstruments::builder()
+            .add_instrument(Instrument::new(
+                ExchangeId::Okx,
+                "okx_perpetual_btc_usdt",
+                "BTC-USDT-SWAP",
+                Underlying::new("btc", "usdt"),
+                InstrumentQuoteAsset::UnderlyingQuote,
+                InstrumentKind::Perpetual(PerpetualContract {
+                    contract_size: dec!(1),
+                    settlement_asset: Asset::from("usdt"),
+                }),
+                None,
+            ))
+            .build();
+
+        let mut state = EngineState::builder(&instruments, DefaultGlobalData, |_| {
+            DefaultInstrumentMarketData::default()
+        })
+        .time_engine_start(DateTime::<Utc>::MIN_UTC)
+        .build();
+
+        let instrument = InstrumentIndex(0);
+        state
+            .instruments
+            .instrument_index_mut(&instrument)
+            .position
+            .update_from_trade(&Trade {
+                id: TradeId::new("trade"),
+                order_id: OrderId::new("order"),
+                instrument,
+                strategy: StrategyId::new("strategy"),
+                time_exchange: DateTime::<Utc>::MIN_UTC,
+                side: Side::Buy,
+                price: dec!(100),
+                quantity: dec!(1),
+                fees: AssetFees {
+                    asset: QuoteAsset,
+                    fees: dec!(0),
+                },
+            });
+        assert_eq!(calculate_pnl_total(&state.instruments), dec!(0));
+
+        // Funding paid on the open position counts towards the total PnL
+        state
+            .instruments
+            .instrument_index_mut(&instrument)
+            .position
+            .current
+            .as_mut()
+            .unwrap()
+            .update_from_funding(&FundingPayment {
+                instrument,
+                time_exchange: DateTime::<Utc>::MIN_UTC,
+                rate: dec!(0.05),
+                price_mark: dec!(100),
+                quantity: dec!(1),
+                amount: dec!(-5),
+            });
+        assert_eq!(calculate_pnl_total(&state.instruments), dec!(-5));
+    }
+
+    #[test]
+    fn test_kill_switch_update_disabled_without_limits() {
+        let mut kill_switch = KillSwitch::default();
+        assert_eq!(
+            kill_switch.update(DateTime::<Utc>::MIN_UTC, dec!(-1000)),
+            None
+        );
+        assert!(kill_switch.samples.is_empty());
+    }
+}
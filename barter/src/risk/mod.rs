@@ -1,13 +1,44 @@
+use crate::{
+    engine::state::instrument::status::InstrumentStatus,
+    risk::{
+        check::{CheckFailHigherThan, CheckFailInstrumentSpec, CheckFailTriggerPrice},
+        rate_limit::RateLimitEndpoint,
+        self_trade::SelfTradeDetected,
+    },
+};
 use barter_execution::order::request::{OrderRequestCancel, OrderRequestOpen};
 use barter_instrument::{exchange::ExchangeIndex, instrument::InstrumentIndex};
 use barter_integration::Unrecoverable;
 use derive_more::{Constructor, Display, From};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+use thiserror::Error;
 
 /// RiskManager checks and utilities.
 pub mod check;
 
+/// Configurable [`RiskManager`] that performs an ordered list of pre-trade checks.
+///
+/// eg/ Max order notional, max open orders per instrument, price bands, etc.
+pub mod checked;
+
+/// Portfolio exposure limits that check the aggregate positions and orders of the
+/// [`EngineState`](crate::engine::state::EngineState).
+pub mod exposure;
+
+/// Drawdown and daily loss [`KillSwitch`](kill_switch::KillSwitch) that automatically disables
+/// trading when session PnL limits are breached.
+pub mod kill_switch;
+
+/// Token bucket [`RateLimiter`](rate_limit::RateLimiter) that throttles the order requests the
+/// `Engine` sends to each exchange endpoint class.
+pub mod rate_limit;
+
+/// Self-trade prevention that detects open order requests which could match against other
+/// active orders of the same account.
+pub mod self_trade;
+
 /// RiskManager interface that reviews and optionally filters cancel and open order requests
 /// generated by an [`AlgoStrategy`](super::strategy::algo::AlgoStrategy).
 ///
@@ -63,13 +94,13 @@ impl<T> RiskApproved<T> {
 /// Type that wraps order requests that have failed [`RiskManager`] checks, including the
 /// failure reason.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
-pub struct RiskRefused<T, Reason = String> {
+pub struct RiskRefused<T, Reason = RiskRefusedReason> {
     pub item: T,
     pub reason: Reason,
 }
 
 impl<T> RiskRefused<T> {
-    pub fn new(item: T, reason: impl Into<String>) -> Self {
+    pub fn new(item: T, reason: impl Into<RiskRefusedReason>) -> Self {
         Self {
             item,
             reason: reason.into(),
@@ -92,6 +123,87 @@ where
     }
 }
 
+/// Reason an order request was refused by a [`RiskManager`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Error)]
+pub enum RiskRefusedReason {
+    /// Instrument can no longer be traded (eg/ expired).
+    #[error("instrument is {0:?}")]
+    InstrumentInactive(InstrumentStatus),
+
+    /// Instrument kind cannot be checked by the [`RiskManager`] (eg/ an `Option` without a
+    /// strike price).
+    #[error("instrument kind is not supported by the RiskManager")]
+    InstrumentKindUnsupported,
+
+    /// No instrument market price is available to check the order against.
+    #[error("no instrument market price available")]
+    PriceUnavailable,
+
+    /// No quote asset balance is available to check the order against.
+    #[error("no quote asset balance available")]
+    BalanceUnavailable,
+
+    /// Calculating a risk check input (eg/ notional value) overflowed.
+    #[error("risk check input calculation overflowed")]
+    CalculationOverflow,
+
+    /// Order notional value exceeds the configured maximum.
+    #[error("max order notional exceeded: {0}")]
+    MaxOrderNotional(CheckFailHigherThan<Decimal>),
+
+    /// Order quantity exceeds the configured maximum.
+    #[error("max order quantity exceeded: {0}")]
+    MaxOrderQuantity(CheckFailHigherThan<Decimal>),
+
+    /// Number of open orders for the instrument would exceed the configured maximum.
+    #[error("max open orders per instrument exceeded: {0}")]
+    MaxOpenOrders(CheckFailHigherThan<usize>),
+
+    /// Order price deviates from the instrument market price by more than the configured
+    /// maximum percentage.
+    #[error("price band exceeded: {0}")]
+    PriceBand(CheckFailHigherThan<Decimal>),
+
+    /// Absolute instrument position quantity could exceed the configured maximum.
+    #[error("max position exceeded: {0}")]
+    MaxPosition(CheckFailHigherThan<Decimal>),
+
+    /// Absolute net delta of the underlying base asset could exceed the configured maximum.
+    #[error("max underlying net delta exceeded: {0}")]
+    MaxUnderlyingNetDelta(CheckFailHigherThan<Decimal>),
+
+    /// Gross notional exposure on the exchange could exceed the configured maximum.
+    #[error("max exchange gross notional exceeded: {0}")]
+    MaxExchangeGrossNotional(CheckFailHigherThan<Decimal>),
+
+    /// Gross notional exposure to the underlying base asset could exceed the configured maximum
+    /// percentage of the quote asset balance.
+    #[error("max asset concentration exceeded: {0}")]
+    MaxAssetConcentration(CheckFailHigherThan<Decimal>),
+
+    /// Conditional order would be triggered immediately at the market price.
+    #[error(transparent)]
+    TriggerPrice(#[from] CheckFailTriggerPrice),
+
+    /// Exchange endpoint rate limit exceeded.
+    #[error("{0:?} rate limit exceeded")]
+    RateLimited(RateLimitEndpoint),
+
+    /// Order could match against another active order of the same account.
+    #[error(transparent)]
+    SelfTrade(#[from] SelfTradeDetected),
+
+    /// Order does not conform to the instrument spec.
+    #[error(transparent)]
+    InstrumentSpec(#[from] CheckFailInstrumentSpec),
+}
+
+impl Unrecoverable for RiskRefusedReason {
+    fn is_unrecoverable(&self) -> bool {
+        false
+    }
+}
+
 /// Naive implementation of the [`RiskManager`] interface, approving all orders *without any
 /// risk checks*.
 ///
@@ -0,0 +1,681 @@
+use crate::{
+    engine::state::EngineState,
+    risk::{RiskRefused, RiskRefusedReason},
+};
+use barter_execution::order::{
+    OrderEvent,
+    request::{OrderRequestCancel, OrderRequestOpen, RequestAmend, RequestCancel, RequestOpen},
+};
+use barter_instrument::{
+    exchange::{ExchangeId, ExchangeIndex},
+    index::IndexedInstruments,
+    instrument::InstrumentIndex,
+};
+use chrono::{DateTime, Utc};
+use derive_more::Constructor;
+use fnv::FnvHashMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, hash::Hash};
+use tracing::warn;
+
+/// Configuration of the [`RateLimiter`] used by the [`Engine`](crate::engine::Engine) to
+/// throttle algorithmic order requests sent to each exchange.
+///
+/// ### Example Config
+/// ```json
+/// {
+///     "mode": "queue",
+///     "exchanges": [
+///         {
+///             "exchange": "binance_spot",
+///             "orders": { "burst": 10, "per_second": "5" },
+///             "cancels": { "burst": 20, "per_second": "10" }
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+pub struct RateLimitConfig {
+    /// How order requests exceeding a rate limit are handled.
+    #[serde(default)]
+    pub mode: RateLimitMode,
+
+    /// Rate limits of each exchange. Exchanges without a configuration are not rate limited.
+    #[serde(default)]
+    pub exchanges: Vec<ExchangeRateLimitConfig>,
+}
+
+/// Defines how the [`RateLimiter`] handles order requests that exceed a rate limit.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitMode {
+    /// Refuse the order request with [`RiskRefusedReason::RateLimited`].
+    #[default]
+    Refuse,
+
+    /// Queue the order request, sending it once the rate limit allows.
+    Queue,
+}
+
+/// Rate limit configuration for each endpoint class of an exchange.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct ExchangeRateLimitConfig {
+    pub exchange: ExchangeId,
+
+    /// Rate limit of open order requests.
+    #[serde(default)]
+    pub orders: Option<TokenBucketConfig>,
+
+    /// Rate limit of cancel order requests.
+    #[serde(default)]
+    pub cancels: Option<TokenBucketConfig>,
+}
+
+/// Configuration of a [`TokenBucket`].
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct TokenBucketConfig {
+    /// Maximum number of requests that can be sent in a burst.
+    pub burst: u32,
+
+    /// Number of requests replenished per second.
+    pub per_second: Decimal,
+}
+
+/// Exchange endpoint class that is rate limited by a [`TokenBucket`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitEndpoint {
+    Orders,
+    Cancels,
+}
+
+/// Defines the [`RateLimitEndpoint`] an order request kind (eg/ [`RequestOpen`]) consumes a
+/// token from.
+pub trait RateLimitedRequest {
+    const ENDPOINT: RateLimitEndpoint;
+}
+
+impl RateLimitedRequest for RequestOpen {
+    const ENDPOINT: RateLimitEndpoint = RateLimitEndpoint::Orders;
+}
+
+impl RateLimitedRequest for RequestAmend {
+    const ENDPOINT: RateLimitEndpoint = RateLimitEndpoint::Orders;
+}
+
+impl RateLimitedRequest for RequestCancel {
+    const ENDPOINT: RateLimitEndpoint = RateLimitEndpoint::Cancels;
+}
+
+/// Token bucket rate limit, replenished continuously based on the time provided by the
+/// [`EngineClock`](crate::engine::clock::EngineClock).
+///
+/// Driving the bucket with the `EngineClock` time ensures rate limits behave identically in
+/// live trading and back-tests using a `HistoricalClock`.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct TokenBucket {
+    pub capacity: Decimal,
+    pub per_second: Decimal,
+    pub tokens: Decimal,
+    pub time_last_refill: Option<DateTime<Utc>>,
+}
+
+impl From<TokenBucketConfig> for TokenBucket {
+    fn from(value: TokenBucketConfig) -> Self {
+        let capacity = Decimal::from(value.burst);
+        Self {
+            capacity,
+            per_second: value.per_second,
+            tokens: capacity,
+            time_last_refill: None,
+        }
+    }
+}
+
+impl TokenBucket {
+    /// Replenish tokens for the time elapsed since the last refill, up to the bucket capacity.
+    ///
+    /// Times earlier than the last refill are ignored.
+    pub fn refill(&mut self, time: DateTime<Utc>) {
+        let Some(time_last_refill) = self.time_last_refill else {
+            self.time_last_refill = Some(time);
+            return;
+        };
+
+        if time <= time_last_refill {
+            return;
+        }
+
+        let elapsed_secs =
+            Decimal::from((time - time_last_refill).num_milliseconds()) / Decimal::ONE_THOUSAND;
+
+        self.tokens = elapsed_secs
+            .checked_mul(self.per_second)
+            .and_then(|replenished| self.tokens.checked_add(replenished))
+            .map_or(self.capacity, |tokens| tokens.min(self.capacity));
+        self.time_last_refill = Some(time);
+    }
+
+    /// Attempt to acquire a token at the provided time, returning true if successful.
+    pub fn try_acquire(&mut self, time: DateTime<Utc>) -> bool {
+        self.refill(time);
+
+        if self.tokens >= Decimal::ONE {
+            self.tokens -= Decimal::ONE;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// [`TokenBucket`] rate limits for each endpoint class of an exchange.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+pub struct ExchangeRateLimits {
+    pub orders: Option<TokenBucket>,
+    pub cancels: Option<TokenBucket>,
+}
+
+impl ExchangeRateLimits {
+    fn bucket_mut(&mut self, endpoint: RateLimitEndpoint) -> Option<&mut TokenBucket> {
+        match endpoint {
+            RateLimitEndpoint::Orders => self.orders.as_mut(),
+            RateLimitEndpoint::Cancels => self.cancels.as_mut(),
+        }
+    }
+}
+
+/// Rate limiting risk layer that throttles the algorithmic order requests the
+/// [`Engine`](crate::engine::Engine) sends to each exchange, after they have been approved by
+/// the [`RiskManager`](super::RiskManager).
+///
+/// Order requests exceeding a rate limit are either refused, or queued and sent once the rate
+/// limit allows, depending on the configured [`RateLimitMode`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RateLimiter<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex>
+where
+    ExchangeKey: Eq + Hash,
+{
+    pub mode: RateLimitMode,
+    pub exchanges: FnvHashMap<ExchangeKey, ExchangeRateLimits>,
+    pub queued_cancels: VecDeque<OrderRequestCancel<ExchangeKey, InstrumentKey>>,
+    pub queued_opens: VecDeque<OrderRequestOpen<ExchangeKey, InstrumentKey>>,
+}
+
+impl<ExchangeKey, InstrumentKey> Default for RateLimiter<ExchangeKey, InstrumentKey>
+where
+    ExchangeKey: Eq + Hash,
+{
+    fn default() -> Self {
+        Self {
+            mode: RateLimitMode::default(),
+            exchanges: FnvHashMap::default(),
+            queued_cancels: VecDeque::new(),
+            queued_opens: VecDeque::new(),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Construct a new [`RateLimiter`] from the provided [`RateLimitConfig`], using the
+    /// [`IndexedInstruments`] to map each [`ExchangeId`] to its [`ExchangeIndex`].
+    ///
+    /// Configurations for exchanges that are not present in the `IndexedInstruments` are ignored.
+    pub fn new(config: RateLimitConfig, instruments: &IndexedInstruments) -> Self {
+        let exchanges = config
+            .exchanges
+            .into_iter()
+            .filter_map(
+                |exchange| match instruments.find_exchange_index(exchange.exchange) {
+                    Ok(index) => Some((
+                        index,
+                        ExchangeRateLimits {
+                            orders: exchange.orders.map(TokenBucket::from),
+                            cancels: exchange.cancels.map(TokenBucket::from),
+                        },
+                    )),
+                    Err(error) => {
+                        warn!(
+                            exchange = %exchange.exchange,
+                            ?error,
+                            "RateLimiter ignoring rate limit config for untracked exchange"
+                        );
+                        None
+                    }
+                },
+            )
+            .collect();
+
+        Self {
+            mode: config.mode,
+            exchanges,
+            queued_cancels: VecDeque::new(),
+            queued_opens: VecDeque::new(),
+        }
+    }
+}
+
+impl<ExchangeKey, InstrumentKey> RateLimiter<ExchangeKey, InstrumentKey>
+where
+    ExchangeKey: Eq + Hash,
+{
+    /// Returns true if any order requests are queued.
+    pub fn has_queued(&self) -> bool {
+        !self.queued_cancels.is_empty() || !self.queued_opens.is_empty()
+    }
+
+    /// Attempt to acquire a token for the exchange endpoint class at the provided time.
+    ///
+    /// Returns true if the exchange endpoint class is not rate limited.
+    pub fn try_acquire(
+        &mut self,
+        time: DateTime<Utc>,
+        exchange: &ExchangeKey,
+        endpoint: RateLimitEndpoint,
+    ) -> bool {
+        try_acquire(&mut self.exchanges, time, exchange, endpoint)
+    }
+
+    /// Remove and return the queued order requests the rate limits now allow to be sent, in the
+    /// order they were queued.
+    pub fn dequeue(
+        &mut self,
+        time: DateTime<Utc>,
+    ) -> RateLimitDequeued<ExchangeKey, InstrumentKey> {
+        let cancels = dequeue(
+            &mut self.exchanges,
+            &mut self.queued_cancels,
+            time,
+            RateLimitEndpoint::Cancels,
+        );
+        let opens = dequeue(
+            &mut self.exchanges,
+            &mut self.queued_opens,
+            time,
+            RateLimitEndpoint::Orders,
+        );
+
+        RateLimitDequeued::new(cancels, opens)
+    }
+
+    /// Remove and return the queued [`OrderRequestCancel`]s for instruments matching the
+    /// provided predicate.
+    pub fn remove_queued_cancels(
+        &mut self,
+        predicate: impl Fn(&InstrumentKey) -> bool,
+    ) -> Vec<OrderRequestCancel<ExchangeKey, InstrumentKey>> {
+        remove_queued(&mut self.queued_cancels, predicate)
+    }
+
+    /// Remove and return the queued [`OrderRequestOpen`]s for instruments matching the
+    /// provided predicate.
+    pub fn remove_queued_opens(
+        &mut self,
+        predicate: impl Fn(&InstrumentKey) -> bool,
+    ) -> Vec<OrderRequestOpen<ExchangeKey, InstrumentKey>> {
+        remove_queued(&mut self.queued_opens, predicate)
+    }
+
+    /// Rate limit the provided [`OrderRequestCancel`]s.
+    pub fn limit_cancels(
+        &mut self,
+        time: DateTime<Utc>,
+        requests: impl IntoIterator<Item = OrderRequestCancel<ExchangeKey, InstrumentKey>>,
+    ) -> RateLimitOutput<OrderRequestCancel<ExchangeKey, InstrumentKey>>
+    where
+        ExchangeKey: Clone,
+        InstrumentKey: Clone,
+    {
+        limit(
+            &mut self.exchanges,
+            self.mode,
+            &mut self.queued_cancels,
+            time,
+            RateLimitEndpoint::Cancels,
+            requests,
+        )
+    }
+
+    /// Rate limit the provided [`OrderRequestOpen`]s.
+    pub fn limit_opens(
+        &mut self,
+        time: DateTime<Utc>,
+        requests: impl IntoIterator<Item = OrderRequestOpen<ExchangeKey, InstrumentKey>>,
+    ) -> RateLimitOutput<OrderRequestOpen<ExchangeKey, InstrumentKey>>
+    where
+        ExchangeKey: Clone,
+        InstrumentKey: Clone,
+    {
+        limit(
+            &mut self.exchanges,
+            self.mode,
+            &mut self.queued_opens,
+            time,
+            RateLimitEndpoint::Orders,
+            requests,
+        )
+    }
+}
+
+fn try_acquire<ExchangeKey>(
+    exchanges: &mut FnvHashMap<ExchangeKey, ExchangeRateLimits>,
+    time: DateTime<Utc>,
+    exchange: &ExchangeKey,
+    endpoint: RateLimitEndpoint,
+) -> bool
+where
+    ExchangeKey: Eq + Hash,
+{
+    exchanges
+        .get_mut(exchange)
+        .and_then(|limits| limits.bucket_mut(endpoint))
+        .is_none_or(|bucket| bucket.try_acquire(time))
+}
+
+fn dequeue<Kind, ExchangeKey, InstrumentKey>(
+    exchanges: &mut FnvHashMap<ExchangeKey, ExchangeRateLimits>,
+    queue: &mut VecDeque<OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+    time: DateTime<Utc>,
+    endpoint: RateLimitEndpoint,
+) -> Vec<OrderEvent<Kind, ExchangeKey, InstrumentKey>>
+where
+    ExchangeKey: Eq + Hash,
+{
+    // Once a request is blocked, every subsequent request for the same exchange endpoint is also
+    // blocked (tokens cannot be replenished mid-pass), so the queue order is maintained
+    let (ready, pending): (Vec<_>, Vec<_>) = std::mem::take(queue)
+        .into_iter()
+        .partition(|request| try_acquire(exchanges, time, &request.key.exchange, endpoint));
+
+    *queue = VecDeque::from(pending);
+    ready
+}
+
+fn remove_queued<Kind, ExchangeKey, InstrumentKey>(
+    queue: &mut VecDeque<OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+    predicate: impl Fn(&InstrumentKey) -> bool,
+) -> Vec<OrderEvent<Kind, ExchangeKey, InstrumentKey>> {
+    let (removed, retained): (Vec<_>, Vec<_>) = std::mem::take(queue)
+        .into_iter()
+        .partition(|request| predicate(&request.key.instrument));
+
+    *queue = VecDeque::from(retained);
+    removed
+}
+
+fn limit<Kind, ExchangeKey, InstrumentKey>(
+    exchanges: &mut FnvHashMap<ExchangeKey, ExchangeRateLimits>,
+    mode: RateLimitMode,
+    queue: &mut VecDeque<OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+    time: DateTime<Utc>,
+    endpoint: RateLimitEndpoint,
+    requests: impl IntoIterator<Item = OrderEvent<Kind, ExchangeKey, InstrumentKey>>,
+) -> RateLimitOutput<OrderEvent<Kind, ExchangeKey, InstrumentKey>>
+where
+    Kind: Clone,
+    ExchangeKey: Eq + Hash + Clone,
+    InstrumentKey: Clone,
+{
+    let mut output = RateLimitOutput::default();
+
+    for request in requests {
+        if try_acquire(exchanges, time, &request.key.exchange, endpoint) {
+            output.approved.push(request);
+            continue;
+        }
+
+        match mode {
+            RateLimitMode::Refuse => output.refused.push(RiskRefused::new(
+                request,
+                RiskRefusedReason::RateLimited(endpoint),
+            )),
+            RateLimitMode::Queue => {
+                queue.push_back(request.clone());
+                output.queued.push(request);
+            }
+        }
+    }
+
+    output
+}
+
+/// Queued order requests removed from the [`RateLimiter`] queue, ready to be sent.
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor,
+)]
+pub struct RateLimitDequeued<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex> {
+    pub cancels: Vec<OrderRequestCancel<ExchangeKey, InstrumentKey>>,
+    pub opens: Vec<OrderRequestOpen<ExchangeKey, InstrumentKey>>,
+}
+
+/// Result of rate limiting order requests with a [`RateLimiter`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct RateLimitOutput<T> {
+    /// Order requests within the rate limit, which can be sent immediately.
+    pub approved: Vec<T>,
+
+    /// Order requests exceeding the rate limit that were queued (`RateLimitMode::Queue`).
+    pub queued: Vec<T>,
+
+    /// Order requests exceeding the rate limit that were refused (`RateLimitMode::Refuse`).
+    pub refused: Vec<RiskRefused<T>>,
+}
+
+impl<T> Default for RateLimitOutput<T> {
+    fn default() -> Self {
+        Self {
+            approved: Vec::new(),
+            queued: Vec::new(),
+            refused: Vec::new(),
+        }
+    }
+}
+
+/// Provides mutable access to the [`RateLimiter`].
+///
+/// Used by the [`Engine`](crate::engine::Engine) to rate limit algorithmic order requests.
+pub trait RateLimiterProvider<ExchangeKey = ExchangeIndex, InstrumentKey = InstrumentIndex>
+where
+    ExchangeKey: Eq + Hash,
+{
+    fn rate_limiter_mut(&mut self) -> &mut RateLimiter<ExchangeKey, InstrumentKey>;
+}
+
+impl<GlobalData, InstrumentData> RateLimiterProvider for EngineState<GlobalData, InstrumentData> {
+    fn rate_limiter_mut(&mut self) -> &mut RateLimiter {
+        &mut self.rate_limiter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::time_plus_secs;
+    use barter_execution::order::{
+        OrderKey,
+        id::{ClientOrderId, StrategyId},
+    };
+    use rust_decimal_macros::dec;
+
+    fn request_cancel(exchange: usize, cid: &str) -> OrderRequestCancel {
+        OrderRequestCancel {
+            key: OrderKey {
+                exchange: ExchangeIndex(exchange),
+                instrument: InstrumentIndex(0),
+                strategy: StrategyId::new("strategy"),
+                cid: ClientOrderId::new(cid),
+            },
+            state: RequestCancel { id: None },
+        }
+    }
+
+    fn rate_limiter(mode: RateLimitMode) -> RateLimiter {
+        RateLimiter {
+            mode,
+            exchanges: FnvHashMap::from_iter([(
+                ExchangeIndex(0),
+                ExchangeRateLimits {
+                    orders: None,
+                    cancels: Some(TokenBucket::from(TokenBucketConfig::new(2, dec!(1)))),
+                },
+            )]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_token_bucket_try_acquire() {
+        struct TestCase {
+            time_plus_secs: i64,
+            expected: bool,
+            expected_tokens: Decimal,
+        }
+
+        let base_time = DateTime::<Utc>::MIN_UTC;
+        let mut bucket = TokenBucket::from(TokenBucketConfig::new(2, dec!(0.5)));
+
+        let cases = vec![
+            // TC0: burst token acquired
+            TestCase {
+                time_plus_secs: 0,
+                expected: true,
+                expected_tokens: dec!(1),
+            },
+            // TC1: burst token acquired
+            TestCase {
+                time_plus_secs: 0,
+                expected: true,
+                expected_tokens: dec!(0),
+            },
+            // TC2: no tokens available
+            TestCase {
+                time_plus_secs: 1,
+                expected: false,
+                expected_tokens: dec!(0.5),
+            },
+            // TC3: token replenished after 2 seconds
+            TestCase {
+                time_plus_secs: 2,
+                expected: true,
+                expected_tokens: dec!(0),
+            },
+            // TC4: time earlier than last refill is ignored
+            TestCase {
+                time_plus_secs: 1,
+                expected: false,
+                expected_tokens: dec!(0),
+            },
+            // TC5: tokens replenished up to capacity
+            TestCase {
+                time_plus_secs: 100,
+                expected: true,
+                expected_tokens: dec!(1),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = bucket.try_acquire(time_plus_secs(base_time, test.time_plus_secs));
+            assert_eq!(actual, test.expected, "TC{index} failed");
+            assert_eq!(bucket.tokens, test.expected_tokens, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_limit_refuse() {
+        let time = DateTime::<Utc>::MIN_UTC;
+        let mut rate_limiter = rate_limiter(RateLimitMode::Refuse);
+
+        let output = rate_limiter.limit_cancels(
+            time,
+            [
+                request_cancel(0, "1"),
+                request_cancel(1, "2"),
+                request_cancel(0, "3"),
+                request_cancel(0, "4"),
+            ],
+        );
+
+        assert_eq!(
+            output,
+            RateLimitOutput {
+                approved: vec![
+                    request_cancel(0, "1"),
+                    request_cancel(1, "2"),
+                    request_cancel(0, "3")
+                ],
+                queued: vec![],
+                refused: vec![RiskRefused::new(
+                    request_cancel(0, "4"),
+                    RiskRefusedReason::RateLimited(RateLimitEndpoint::Cancels)
+                )],
+            }
+        );
+        assert!(!rate_limiter.has_queued());
+    }
+
+    #[test]
+    fn test_rate_limiter_limit_queue_and_dequeue() {
+        let time = DateTime::<Utc>::MIN_UTC;
+        let mut rate_limiter = rate_limiter(RateLimitMode::Queue);
+
+        let output = rate_limiter
+            .limit_cancels(time, (1..=5).map(|cid| request_cancel(0, &cid.to_string())));
+        assert_eq!(output.approved.len(), 2);
+        assert_eq!(output.queued.len(), 3);
+        assert!(output.refused.is_empty());
+
+        // No tokens replenished
+        let dequeued = rate_limiter.dequeue(time);
+        assert!(dequeued.cancels.is_empty());
+
+        // One token replenished, so oldest queued request is dequeued
+        let dequeued = rate_limiter.dequeue(time_plus_secs(time, 1));
+        assert_eq!(dequeued.cancels, vec![request_cancel(0, "3")]);
+
+        // Tokens replenished to capacity, so remaining queued requests are dequeued in order
+        let dequeued = rate_limiter.dequeue(time_plus_secs(time, 10));
+        assert_eq!(
+            dequeued.cancels,
+            vec![request_cancel(0, "4"), request_cancel(0, "5")]
+        );
+        assert!(!rate_limiter.has_queued());
+    }
+
+    #[test]
+    fn test_rate_limiter_remove_queued_cancels() {
+        let time = DateTime::<Utc>::MIN_UTC;
+        let mut rate_limiter = rate_limiter(RateLimitMode::Queue);
+
+        let requests = (1..=5).map(|cid| {
+            let mut request = request_cancel(0, &cid.to_string());
+            request.key.instrument = InstrumentIndex(cid % 2);
+            request
+        });
+
+        let output = rate_limiter.limit_cancels(time, requests);
+        assert_eq!(output.queued.len(), 3);
+
+        // Remove queued requests for InstrumentIndex(1), retaining the others in order
+        let removed = rate_limiter.remove_queued_cancels(|instrument| instrument.index() == 1);
+        assert_eq!(
+            removed
+                .iter()
+                .map(|request| request.key.cid.clone())
+                .collect::<Vec<_>>(),
+            vec![ClientOrderId::new("3"), ClientOrderId::new("5")]
+        );
+        assert_eq!(
+            rate_limiter.queued_cancels,
+            VecDeque::from([output.queued[1].clone()])
+        );
+
+        let removed = rate_limiter.remove_queued_cancels(|_| true);
+        assert_eq!(removed.len(), 1);
+        assert!(!rate_limiter.has_queued());
+    }
+}
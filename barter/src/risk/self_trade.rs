@@ -0,0 +1,606 @@
+use crate::{engine::state::instrument::InstrumentStates, risk::RiskRefused};
+use barter_execution::order::{
+    OrderKind,
+    id::ClientOrderId,
+    request::{OrderRequestCancel, OrderRequestOpen},
+    state::ActiveOrderState,
+};
+use barter_instrument::Side;
+use derive_more::Constructor;
+use fnv::FnvHashSet;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+/// Self-trade prevention (STP) mode, defining how the
+/// [`CheckedRiskManager`](super::checked::CheckedRiskManager) handles an open order request
+/// that could match against another active order of the same account.
+///
+/// Potential self-matches are detected across every strategy, since orders from different
+/// `StrategyId`s still trade against the same exchange account.
+///
+/// Active orders remain potential matches until they are acknowledged as cancelled, including
+/// orders with an in flight open or cancel request.
+///
+/// Since prevention is performed before the open request is sent, `CancelNewest` and `Refuse`
+/// have the same effect, only differing in the mode recorded in the [`SelfTradeDetected`] reason.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTradePreventionMode {
+    /// Cancel the newest order (ie/ the new open request is not sent).
+    CancelNewest,
+
+    /// Cancel the oldest orders (ie/ cancel requests are generated for every matching active
+    /// order).
+    ///
+    /// The new open request is refused until every matching active order is acknowledged as
+    /// cancelled, after which it can be re-sent. Matching open requests from earlier in the same
+    /// batch are not yet sent, so they are refused and the new open request is sent.
+    CancelOldest,
+
+    /// Cancel both the newest and oldest orders.
+    CancelBoth,
+
+    /// Refuse the new open request, leaving every matching active order untouched.
+    Refuse,
+}
+
+impl SelfTradePreventionMode {
+    /// Detect potential self-matches between the open requests and every active order tracked
+    /// in the [`InstrumentStates`], including open requests approved earlier in the same batch.
+    ///
+    /// Cancel requests are only generated for matching orders acknowledged as open, and not
+    /// already being cancelled (including those with a cancel request in the provided `cancels`
+    /// batch). Matching orders with an in flight open request cannot be cancelled until they
+    /// are acknowledged.
+    pub fn apply<InstrumentData>(
+        &self,
+        instruments: &InstrumentStates<InstrumentData>,
+        cancels: &[OrderRequestCancel],
+        opens: impl IntoIterator<Item = OrderRequestOpen>,
+    ) -> SelfTradePreventionOutput {
+        let mut cancelled = cancels
+            .iter()
+            .map(|cancel| cancel.key.cid.clone())
+            .collect::<FnvHashSet<_>>();
+
+        let mut output = SelfTradePreventionOutput::default();
+        let mut approved = Vec::<Option<OrderRequestOpen>>::new();
+
+        for request in opens {
+            let resting = instruments
+                .instrument_index(&request.key.instrument)
+                .orders
+                .0
+                .values()
+                .filter(|order| is_self_trade(&request, order.side, order.price, order.kind))
+                .collect::<Vec<_>>();
+
+            let batch = approved
+                .iter()
+                .enumerate()
+                .filter_map(|(index, open)| {
+                    open.as_ref()
+                        .filter(|open| {
+                            open.key.instrument == request.key.instrument
+                                && is_self_trade(
+                                    &request,
+                                    open.state.side,
+                                    open.state.price,
+                                    open.state.kind,
+                                )
+                        })
+                        .map(|_| index)
+                })
+                .collect::<Vec<_>>();
+
+            if resting.is_empty() && batch.is_empty() {
+                approved.push(Some(request));
+                continue;
+            }
+
+            let matched =
+                resting
+                    .iter()
+                    .map(|order| order.key.cid.clone())
+                    .chain(batch.iter().filter_map(|index| {
+                        approved[*index].as_ref().map(|open| open.key.cid.clone())
+                    }))
+                    .collect::<Vec<_>>();
+
+            warn!(
+                mode = ?self,
+                ?request,
+                ?matched,
+                "SelfTradePrevention detected potential self-match"
+            );
+
+            match self {
+                Self::CancelNewest | Self::Refuse => {
+                    output.refused.push(RiskRefused::new(
+                        request,
+                        SelfTradeDetected::new(*self, matched),
+                    ));
+                }
+                Self::CancelOldest | Self::CancelBoth => {
+                    // Cancel matching active orders, unless in flight or already being cancelled
+                    for order in &resting {
+                        if matches!(order.state, ActiveOrderState::OpenInFlight(_))
+                            || cancelled.contains(&order.key.cid)
+                        {
+                            continue;
+                        }
+
+                        if let Some(cancel) = order.to_request_cancel() {
+                            cancelled.insert(cancel.key.cid.clone());
+                            output.cancels.push(cancel);
+                        }
+                    }
+
+                    // Matching open requests from this batch are not yet sent, so refuse them
+                    for index in batch {
+                        if let Some(open) = approved[index].take() {
+                            output.refused.push(RiskRefused::new(
+                                open,
+                                SelfTradeDetected::new(*self, vec![request.key.cid.clone()]),
+                            ));
+                        }
+                    }
+
+                    // New request is held until matching active orders are cancelled
+                    if let Self::CancelOldest = self
+                        && resting.is_empty()
+                    {
+                        approved.push(Some(request));
+                    } else {
+                        output.refused.push(RiskRefused::new(
+                            request,
+                            SelfTradeDetected::new(*self, matched),
+                        ));
+                    }
+                }
+            }
+        }
+
+        output.opens = approved.into_iter().flatten().collect();
+        output
+    }
+}
+
+/// Result of applying a [`SelfTradePreventionMode`] to a batch of open order requests.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default, Deserialize, Serialize)]
+pub struct SelfTradePreventionOutput {
+    /// Generated cancel requests for active orders that could self-match.
+    pub cancels: Vec<OrderRequestCancel>,
+
+    /// Open requests that cannot self-match (or only matched earlier requests from the batch).
+    pub opens: Vec<OrderRequestOpen>,
+
+    /// Open requests refused to prevent a potential self-match.
+    pub refused: Vec<RiskRefused<OrderRequestOpen>>,
+}
+
+/// Potential self-match detected by a [`SelfTradePreventionMode`].
+#[derive(
+    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, Constructor, Error,
+)]
+#[error("{mode:?} self-trade prevention against orders: {matched:?}")]
+pub struct SelfTradeDetected {
+    pub mode: SelfTradePreventionMode,
+
+    /// [`ClientOrderId`]s of the orders that could have been matched against.
+    pub matched: Vec<ClientOrderId>,
+}
+
+/// Returns true if the open request could match against an order with the provided side, price
+/// and kind.
+///
+/// Untriggered conditional orders are not on the order book, so cannot match.
+fn is_self_trade(request: &OrderRequestOpen, side: Side, price: Decimal, kind: OrderKind) -> bool {
+    if request.state.side == side
+        || request.state.kind.trigger().is_some()
+        || kind.trigger().is_some()
+    {
+        return false;
+    }
+
+    if matches!(request.state.kind, OrderKind::Market) || matches!(kind, OrderKind::Market) {
+        return true;
+    }
+
+    let (price_buy, price_sell) = match request.state.side {
+        Side::Buy => (request.state.price, price),
+        Side::Sell => (price, request.state.price),
+    };
+
+    price_buy >= price_sell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::state::{
+            EngineState, global::DefaultGlobalData, instrument::data::DefaultInstrumentMarketData,
+        },
+        risk::RiskRefusedReason,
+    };
+    use barter_execution::order::{
+        Order, OrderFlags, OrderKey, TimeInForce,
+        id::{OrderId, StrategyId},
+        request::{RequestCancel, RequestOpen},
+        state::{CancelInFlight, Open, OpenInFlight},
+    };
+    use barter_instrument::{
+        Underlying,
+        exchange::{ExchangeId, ExchangeIndex},
+        index::IndexedInstruments,
+        instrument::{Instrument, InstrumentIndex},
+    };
+    use chrono::{DateTime, Utc};
+    use rust_decimal_macros::dec;
+
+    type State = EngineState<DefaultGlobalData, DefaultInstrumentMarketData>;
+
+    fn key(strategy: &str, cid: &str) -> OrderKey {
+        OrderKey {
+            exchange: ExchangeIndex(0),
+            instrument: InstrumentIndex(0),
+            strategy: StrategyId::new(strategy),
+            cid: ClientOrderId::new(cid),
+        }
+    }
+
+    fn request_open(cid: &str, side: Side, price: Decimal) -> OrderRequestOpen {
+        OrderRequestOpen {
+            key: key("strategy_a", cid),
+            state: RequestOpen {
+                side,
+                price,
+                quantity: dec!(1),
+                kind: OrderKind::Limit,
+                time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+                flags: OrderFlags::default(),
+            },
+        }
+    }
+
+    fn order(
+        cid: &str,
+        side: Side,
+        price: Decimal,
+        state: ActiveOrderState,
+    ) -> Order<ExchangeIndex, InstrumentIndex, ActiveOrderState> {
+        Order {
+            key: key("strategy_b", cid),
+            side,
+            price,
+            quantity: dec!(1),
+            kind: OrderKind::Limit,
+            time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+            state,
+        }
+    }
+
+    fn refused(
+        request: OrderRequestOpen,
+        mode: SelfTradePreventionMode,
+        matched: &[&str],
+    ) -> RiskRefused<OrderRequestOpen> {
+        RiskRefused::new(
+            request,
+            RiskRefusedReason::SelfTrade(SelfTradeDetected::new(
+                mode,
+                matched.iter().map(|cid| ClientOrderId::new(*cid)).collect(),
+            )),
+        )
+    }
+
+    fn open(id: &str) -> Open {
+        Open::new(OrderId::new(id), DateTime::<Utc>::MIN_UTC, Decimal::ZERO)
+    }
+
+    // Resting orders from strategy_b: Open Sell @ 100, Open Buy @ 90, and CancelInFlight Sell @ 105
+    fn state() -> State {
+        state_with_orders(vec![
+            order(
+                "resting_sell",
+                Side::Sell,
+                dec!(100),
+                ActiveOrderState::Open(open("1")),
+            ),
+            order(
+                "resting_buy",
+                Side::Buy,
+                dec!(90),
+                ActiveOrderState::Open(open("2")),
+            ),
+            order(
+                "cancelling_sell",
+                Side::Sell,
+                dec!(105),
+                ActiveOrderState::CancelInFlight(CancelInFlight {
+                    order: Some(open("3")),
+                }),
+            ),
+        ])
+    }
+
+    fn state_with_orders(
+        orders: Vec<Order<ExchangeIndex, InstrumentIndex, ActiveOrderState>>,
+    ) -> State {
+        let instruments = IndexedInstruments::builder()
+            .add_instrument(Instrument::spot(
+                ExchangeId::BinanceSpot,
+                "binance_spot_btc_usdt",
+                "BTCUSDT",
+                Underlying::new("btc", "usdt"),
+                None,
+            ))
+            .build();
+
+        let mut state = EngineState::builder(&instruments, DefaultGlobalData, |_| {
+            DefaultInstrumentMarketData::default()
+        })
+        .time_engine_start(DateTime::<Utc>::MIN_UTC)
+        .build();
+
+        state
+            .instruments
+            .instrument_index_mut(&InstrumentIndex(0))
+            .orders
+            .0 = orders
+            .into_iter()
+            .map(|order| (order.key.cid.clone(), order))
+            .collect();
+
+        state
+    }
+
+    #[test]
+    fn test_self_trade_prevention_mode_apply() {
+        struct TestCase {
+            mode: SelfTradePreventionMode,
+            expected: SelfTradePreventionOutput,
+        }
+
+        let state = state();
+
+        // Crosses resting_sell, passive crosses nothing, batch_sell crosses earlier batch buys
+        let crossing = request_open("crossing", Side::Buy, dec!(100));
+        let passive = request_open("passive", Side::Buy, dec!(99));
+        let batch_sell = request_open("batch_sell", Side::Sell, dec!(99));
+
+        let cancel_resting_sell = OrderRequestCancel {
+            key: key("strategy_b", "resting_sell"),
+            state: RequestCancel {
+                id: Some(OrderId::new("1")),
+            },
+        };
+
+        let cases = vec![
+            // TC0: Refuse new requests that could self-match
+            TestCase {
+                mode: SelfTradePreventionMode::Refuse,
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![],
+                    opens: vec![passive.clone()],
+                    refused: vec![
+                        refused(
+                            crossing.clone(),
+                            SelfTradePreventionMode::Refuse,
+                            &["resting_sell"],
+                        ),
+                        refused(
+                            batch_sell.clone(),
+                            SelfTradePreventionMode::Refuse,
+                            &["passive"],
+                        ),
+                    ],
+                },
+            },
+            // TC1: CancelNewest refuses new requests that could self-match
+            TestCase {
+                mode: SelfTradePreventionMode::CancelNewest,
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![],
+                    opens: vec![passive.clone()],
+                    refused: vec![
+                        refused(
+                            crossing.clone(),
+                            SelfTradePreventionMode::CancelNewest,
+                            &["resting_sell"],
+                        ),
+                        refused(
+                            batch_sell.clone(),
+                            SelfTradePreventionMode::CancelNewest,
+                            &["passive"],
+                        ),
+                    ],
+                },
+            },
+            // TC2: CancelOldest cancels resting orders, holding the new request until cancelled,
+            // & refuses earlier batch requests
+            TestCase {
+                mode: SelfTradePreventionMode::CancelOldest,
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![cancel_resting_sell.clone()],
+                    opens: vec![batch_sell.clone()],
+                    refused: vec![
+                        refused(
+                            crossing.clone(),
+                            SelfTradePreventionMode::CancelOldest,
+                            &["resting_sell"],
+                        ),
+                        refused(
+                            passive.clone(),
+                            SelfTradePreventionMode::CancelOldest,
+                            &["batch_sell"],
+                        ),
+                    ],
+                },
+            },
+            // TC3: CancelBoth cancels resting orders & refuses every matching request
+            TestCase {
+                mode: SelfTradePreventionMode::CancelBoth,
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![cancel_resting_sell.clone()],
+                    opens: vec![],
+                    refused: vec![
+                        refused(
+                            crossing.clone(),
+                            SelfTradePreventionMode::CancelBoth,
+                            &["resting_sell"],
+                        ),
+                        refused(
+                            passive.clone(),
+                            SelfTradePreventionMode::CancelBoth,
+                            &["batch_sell"],
+                        ),
+                        refused(
+                            batch_sell.clone(),
+                            SelfTradePreventionMode::CancelBoth,
+                            &["passive"],
+                        ),
+                    ],
+                },
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = test.mode.apply(
+                &state.instruments,
+                &[],
+                [crossing.clone(), passive.clone(), batch_sell.clone()],
+            );
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn test_self_trade_prevention_mode_apply_unacknowledged_orders() {
+        struct TestCase {
+            mode: SelfTradePreventionMode,
+            state: ActiveOrderState,
+            cancels: Vec<OrderRequestCancel>,
+            expected: SelfTradePreventionOutput,
+        }
+
+        let crossing = request_open("crossing", Side::Buy, dec!(100));
+
+        let cancel_resting = OrderRequestCancel {
+            key: key("strategy_b", "resting"),
+            state: RequestCancel {
+                id: Some(OrderId::new("1")),
+            },
+        };
+
+        let cancel_in_flight = ActiveOrderState::CancelInFlight(CancelInFlight {
+            order: Some(open("1")),
+        });
+
+        let cases = vec![
+            // TC0: Refuse new request matching an in flight open
+            TestCase {
+                mode: SelfTradePreventionMode::Refuse,
+                state: ActiveOrderState::OpenInFlight(OpenInFlight),
+                cancels: vec![],
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![],
+                    opens: vec![],
+                    refused: vec![refused(
+                        crossing.clone(),
+                        SelfTradePreventionMode::Refuse,
+                        &["resting"],
+                    )],
+                },
+            },
+            // TC1: Refuse new request matching an order with a cancel in the batch
+            TestCase {
+                mode: SelfTradePreventionMode::Refuse,
+                state: ActiveOrderState::Open(open("1")),
+                cancels: vec![cancel_resting.clone()],
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![],
+                    opens: vec![],
+                    refused: vec![refused(
+                        crossing.clone(),
+                        SelfTradePreventionMode::Refuse,
+                        &["resting"],
+                    )],
+                },
+            },
+            // TC2: CancelOldest cannot cancel an in flight open, so holds the new request
+            TestCase {
+                mode: SelfTradePreventionMode::CancelOldest,
+                state: ActiveOrderState::OpenInFlight(OpenInFlight),
+                cancels: vec![],
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![],
+                    opens: vec![],
+                    refused: vec![refused(
+                        crossing.clone(),
+                        SelfTradePreventionMode::CancelOldest,
+                        &["resting"],
+                    )],
+                },
+            },
+            // TC3: CancelOldest holds the new request until the in flight cancel is acknowledged
+            TestCase {
+                mode: SelfTradePreventionMode::CancelOldest,
+                state: cancel_in_flight.clone(),
+                cancels: vec![],
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![],
+                    opens: vec![],
+                    refused: vec![refused(
+                        crossing.clone(),
+                        SelfTradePreventionMode::CancelOldest,
+                        &["resting"],
+                    )],
+                },
+            },
+            // TC4: CancelOldest does not duplicate a cancel already in the batch
+            TestCase {
+                mode: SelfTradePreventionMode::CancelOldest,
+                state: ActiveOrderState::Open(open("1")),
+                cancels: vec![cancel_resting.clone()],
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![],
+                    opens: vec![],
+                    refused: vec![refused(
+                        crossing.clone(),
+                        SelfTradePreventionMode::CancelOldest,
+                        &["resting"],
+                    )],
+                },
+            },
+            // TC5: CancelBoth cancels an acknowledged open order
+            TestCase {
+                mode: SelfTradePreventionMode::CancelBoth,
+                state: ActiveOrderState::Open(open("1")),
+                cancels: vec![],
+                expected: SelfTradePreventionOutput {
+                    cancels: vec![cancel_resting],
+                    opens: vec![],
+                    refused: vec![refused(
+                        crossing.clone(),
+                        SelfTradePreventionMode::CancelBoth,
+                        &["resting"],
+                    )],
+                },
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let state =
+                state_with_orders(vec![order("resting", Side::Sell, dec!(100), test.state)]);
+
+            let actual = test
+                .mode
+                .apply(&state.instruments, &test.cancels, [crossing.clone()]);
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+}
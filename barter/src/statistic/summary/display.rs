@@ -109,6 +109,9 @@ where
 
         // Add metric rows
         self.add_instrument_metric_row(&mut table, "PnL", |ts| format!("{:.2}", ts.pnl));
+        self.add_instrument_metric_row(&mut table, "PnL Funding", |ts| {
+            format!("{:.2}", ts.pnl_funding)
+        });
         self.add_instrument_metric_row(&mut table, &format!("Return {interval}"), |ts| {
             format!(
                 "{:.2}%",
@@ -26,7 +26,10 @@ use serde::{Deserialize, Serialize};
 /// TearSheet summarising the trading performance related to an instrument.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub struct TearSheet<Interval> {
+    /// Total PnL, including any perpetual funding.
     pub pnl: Decimal,
+    /// Portion of the total PnL generated by perpetual funding payments.
+    pub pnl_funding: Decimal,
     pub pnl_return: RateOfReturn<Interval>,
     pub sharpe_ratio: SharpeRatio<Interval>,
     pub sortino_ratio: SortinoRatio<Interval>,
@@ -151,6 +154,7 @@ impl TearSheetGenerator {
             sortino_ratio,
             calmar_ratio,
             pnl: self.pnl_returns.pnl_raw,
+            pnl_funding: self.pnl_returns.pnl_funding,
             pnl_return,
             pnl_drawdown: current_pnl_drawdown,
             pnl_drawdown_mean,
@@ -31,6 +31,9 @@ pub struct PnLReturns {
     /// PnL across different instruments.
     pub pnl_raw: Decimal,
 
+    /// Portion of the raw PnL generated by perpetual funding payments.
+    pub pnl_funding: Decimal,
+
     /// PnL returns statistical summary for wins and losses.
     pub total: DataSetSummary,
 
@@ -40,17 +43,18 @@ pub struct PnLReturns {
 
 impl PnLReturns {
     /// Update the `PnLReturns` from the next [`PositionExited`].
+    ///
+    /// Any funding received or paid while the [`PositionExited`] was open is included in its PnL.
     pub fn update<AssetKey, InstrumentKey>(
         &mut self,
         position: &PositionExited<AssetKey, InstrumentKey>,
     ) {
-        self.pnl_raw += position.pnl_realised;
+        let pnl = position.pnl_realised + position.pnl_funding;
+        self.pnl_raw += pnl;
+        self.pnl_funding += position.pnl_funding;
 
-        let pnl_return = calculate_pnl_return(
-            position.pnl_realised,
-            position.price_entry_average,
-            position.quantity_abs_max,
-        );
+        let pnl_return =
+            calculate_pnl_return(pnl, position.price_entry_average, position.quantity_abs_max);
 
         self.total.update(pnl_return);
 
@@ -3,10 +3,13 @@ use crate::engine::state::{
     instrument::{InstrumentState, data::InstrumentDataState, filter::InstrumentFilter},
     position::Position,
 };
-use barter_execution::order::{
-    OrderKey, OrderKind, TimeInForce,
-    id::{ClientOrderId, StrategyId},
-    request::{OrderRequestCancel, OrderRequestOpen, RequestOpen},
+use barter_execution::{
+    order::{
+        OrderFlags, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, RequestOpen},
+    },
+    slippage::SlippageModel,
 };
 use barter_instrument::{
     Side, asset::AssetIndex, exchange::ExchangeIndex, instrument::InstrumentIndex,
@@ -60,10 +63,14 @@ pub trait ClosePositionsStrategy<
 ///
 /// This function finds all open positions and generates equal but opposite `Side` market orders
 /// that will neutralise the position.
+///
+/// Each market order is priced using the provided [`SlippageModel`], adjusting the instrument
+/// market price by the estimated cost of closing the full position size.
 pub fn close_open_positions_with_market_orders<'a, GlobalData, InstrumentData>(
     strategy_id: &'a StrategyId,
     state: &'a EngineState<GlobalData, InstrumentData>,
     filter: &'a InstrumentFilter,
+    slippage: &'a impl SlippageModel,
     gen_cid: impl Fn(&InstrumentState<InstrumentData>) -> ClientOrderId + Copy + 'a,
 ) -> (
     impl IntoIterator<Item = OrderRequestCancel<ExchangeIndex, InstrumentIndex>> + 'a,
@@ -83,13 +90,18 @@ where
             let position = state.position.current.as_ref()?;
             let price = state.data.price()?;
 
-            Some(build_ioc_market_order_to_close_position(
+            let mut order = build_ioc_market_order_to_close_position(
                 state.instrument.exchange,
                 position,
                 strategy_id.clone(),
                 price,
                 || gen_cid(state),
-            ))
+            );
+
+            order.state.price =
+                slippage.price_fill(order.state.side, order.state.quantity, price, None);
+
+            Some(order)
         });
 
     (std::iter::empty(), open_requests)
@@ -99,6 +111,9 @@ where
 /// provided [`Position`].
 ///
 /// For example, if [`Position`] is LONG by 100, build a market order request to sell 100.
+///
+/// The order is [`OrderFlags::reduce_only`], so it can never flip the position if it has
+/// already been (partially) closed by the time the order is executed.
 pub fn build_ioc_market_order_to_close_position<ExchangeKey, AssetKey, InstrumentKey>(
     exchange: ExchangeKey,
     position: &Position<AssetKey, InstrumentKey>,
@@ -126,6 +141,7 @@ where
             quantity: position.quantity_abs,
             kind: OrderKind::Market,
             time_in_force: TimeInForce::ImmediateOrCancel,
+            flags: OrderFlags::reduce_only(),
         },
     }
 }
@@ -13,9 +13,12 @@ use crate::{
         on_trading_disabled::OnTradingDisabled,
     },
 };
-use barter_execution::order::{
-    id::{ClientOrderId, StrategyId},
-    request::{OrderRequestCancel, OrderRequestOpen},
+use barter_execution::{
+    order::{
+        id::{ClientOrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen},
+    },
+    slippage::NoSlippage,
 };
 use barter_instrument::{
     asset::AssetIndex,
@@ -100,7 +103,7 @@ where
         AssetIndex: 'a,
         InstrumentIndex: 'a,
     {
-        close_open_positions_with_market_orders(&self.id, state, filter, |_| {
+        close_open_positions_with_market_orders(&self.id, state, filter, &NoSlippage, |_| {
             ClientOrderId::random()
         })
     }
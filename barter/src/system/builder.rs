@@ -11,11 +11,13 @@ use crate::{
     execution::{
         AccountStreamEvent,
         builder::{ExecutionBuildFutures, ExecutionBuilder},
+        mock::{MockMarketEventKind, MockMarketEventSource, MockMarketFeed, MockMarketTxMap},
     },
+    risk::{kill_switch::KillSwitchConfig, rate_limit::RateLimitConfig},
     shutdown::SyncShutdown,
     system::{System, SystemAuxillaryHandles, config::ExecutionConfig},
 };
-use barter_data::streams::reconnect::stream::ReconnectingStream;
+use barter_data::streams::{consumer::MarketStreamEvent, reconnect::stream::ReconnectingStream};
 use barter_execution::balance::Balance;
 use barter_instrument::{
     Keyed,
@@ -103,6 +105,8 @@ pub struct SystemBuilder<'a, Clock, Strategy, Risk, MarketStream, GlobalData, Fn
     engine_feed_mode: Option<EngineFeedMode>,
     audit_mode: Option<AuditMode>,
     trading_state: Option<TradingState>,
+    kill_switch: Option<KillSwitchConfig>,
+    rate_limit: Option<RateLimitConfig>,
     balances: FnvHashMap<ExchangeAsset<AssetNameInternal>, Balance>,
 }
 
@@ -120,6 +124,8 @@ impl<'a, Clock, Strategy, Risk, MarketStream, GlobalData, FnInstrumentData>
             engine_feed_mode: None,
             audit_mode: None,
             trading_state: None,
+            kill_switch: None,
+            rate_limit: None,
             balances: FnvHashMap::default(),
         }
     }
@@ -154,6 +160,26 @@ impl<'a, Clock, Strategy, Risk, MarketStream, GlobalData, FnInstrumentData>
         }
     }
 
+    /// Optionally configure the [`KillSwitchConfig`] session PnL limits.
+    ///
+    /// When breached, the `Engine` automatically disables algorithmic trading.
+    pub fn kill_switch(self, value: KillSwitchConfig) -> Self {
+        Self {
+            kill_switch: Some(value),
+            ..self
+        }
+    }
+
+    /// Optionally configure the [`RateLimitConfig`] exchange rate limits.
+    ///
+    /// Controls how the `Engine` throttles the algorithmic order requests sent to each exchange.
+    pub fn rate_limit(self, value: RateLimitConfig) -> Self {
+        Self {
+            rate_limit: Some(value),
+            ..self
+        }
+    }
+
     /// Optionally provide initial exchange asset `Balance`s.
     ///
     /// Useful for back-test scenarios where seeding EngineState with initial `Balance`s is
@@ -216,6 +242,8 @@ impl<'a, Clock, Strategy, Risk, MarketStream, GlobalData, FnInstrumentData>
             engine_feed_mode,
             audit_mode,
             trading_state,
+            kill_switch,
+            rate_limit,
             balances,
         } = self;
 
@@ -233,6 +261,7 @@ impl<'a, Clock, Strategy, Risk, MarketStream, GlobalData, FnInstrumentData>
                     ExecutionConfig::Mock(mock_config) => {
                         builder.add_mock(mock_config, clock.clone())
                     }
+                    ExecutionConfig::Live(live_config) => builder.add_live_config(live_config),
                 },
             )?
             .build();
@@ -241,6 +270,8 @@ impl<'a, Clock, Strategy, Risk, MarketStream, GlobalData, FnInstrumentData>
         let state = EngineStateBuilder::new(instruments, global_data, instrument_data_init)
             .time_engine_start(clock.time())
             .trading_state(trading_state)
+            .kill_switch(kill_switch.unwrap_or_default())
+            .rate_limit(rate_limit.unwrap_or_default())
             .balances(
                 balances
                     .into_iter()
@@ -257,6 +288,7 @@ impl<'a, Clock, Strategy, Risk, MarketStream, GlobalData, FnInstrumentData>
             audit_mode,
             market_stream,
             account_channel: execution.account_channel,
+            mock_market_tx_map: execution.mock_market_tx_map,
             execution_build_futures: execution.futures,
             phantom_event: PhantomData,
         })
@@ -283,13 +315,17 @@ pub struct SystemBuild<Engine, Event, MarketStream> {
     /// Channel for `AccountStreamEvent`.
     pub account_channel: Channel<AccountStreamEvent>,
 
+    /// Market data link used to forward `MarketStreamEvent`s processed by the `Engine` to each
+    /// `MockExchange`.
+    pub mock_market_tx_map: MockMarketTxMap,
+
     /// Futures for initialising `ExecutionBuild` components.
     pub execution_build_futures: ExecutionBuildFutures,
 
     phantom_event: PhantomData<Event>,
 }
 
-impl<Engine, Event, MarketStream> SystemBuild<Engine, Event, MarketStream>
+impl<Engine, Event, MarketStream, MarketKind> SystemBuild<Engine, Event, MarketStream>
 where
     Engine: Processor<Event>
         + Auditor<Engine::Audit, Context = EngineContext>
@@ -297,8 +333,15 @@ where
         + Send
         + 'static,
     Engine::Audit: From<FeedEnded> + Terminal + Debug + Clone + Send + 'static,
-    Event: From<MarketStream::Item> + From<AccountStreamEvent> + Debug + Clone + Send + 'static,
-    MarketStream: Stream + Send + 'static,
+    Event: From<MarketStream::Item>
+        + From<AccountStreamEvent>
+        + MockMarketEventSource<MarketKind>
+        + Debug
+        + Clone
+        + Send
+        + 'static,
+    MarketStream: Stream<Item = MarketStreamEvent<InstrumentIndex, MarketKind>> + Send + 'static,
+    MarketKind: MockMarketEventKind + Clone + Send + 'static,
 {
     /// Construct a new `SystemBuild` from the provided components.
     pub fn new(
@@ -307,6 +350,7 @@ where
         audit_mode: AuditMode,
         market_stream: MarketStream,
         account_channel: Channel<AccountStreamEvent>,
+        mock_market_tx_map: MockMarketTxMap,
         execution_build_futures: ExecutionBuildFutures,
     ) -> Self {
         Self {
@@ -315,6 +359,7 @@ where
             audit_mode,
             market_stream,
             account_channel,
+            mock_market_tx_map,
             execution_build_futures,
             phantom_event: Default::default(),
         }
@@ -347,6 +392,7 @@ where
             audit_mode,
             market_stream,
             account_channel,
+            mock_market_tx_map,
             execution_build_futures,
             phantom_event: _,
         } = self;
@@ -356,8 +402,10 @@ where
             .init_with_runtime(runtime.clone())
             .await?;
 
-        // Initialise central Engine channel
-        let (feed_tx, mut feed_rx) = mpsc_unbounded();
+        // Initialise central Engine channel, forwarding MarketStreamEvents to any MockExchanges
+        // once the Engine has processed them
+        let (feed_tx, feed_rx) = mpsc_unbounded();
+        let mut feed_rx = MockMarketFeed::new(feed_rx, mock_market_tx_map);
 
         // Forward MarketStreamEvents to Engine feed
         let market_to_engine = runtime
@@ -2,7 +2,10 @@
 ///
 /// Provides data structures for configuring various aspects of a trading system,
 /// including instruments and execution components.
-use barter_execution::client::mock::MockExecutionConfig;
+use crate::error::BarterError;
+use barter_execution::client::{
+    binance::futures::BinancePositionMode, mock::MockExecutionConfig, okx::OkxTradeMode,
+};
 use barter_instrument::{
     Underlying,
     asset::{Asset, name::AssetNameExchange},
@@ -19,7 +22,8 @@ use barter_instrument::{
     },
 };
 use derive_more::From;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, de::Error as _};
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
 
 /// Top-level configuration for a full trading system.
 ///
@@ -57,13 +61,188 @@ pub struct InstrumentConfig {
 
 /// Configuration for an execution link.
 ///
-/// Represents different types of execution configurations,
-/// currently only supporting mock execution for backtesting.
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize, From)]
+/// Represents different types of execution configurations, allowing the same system to be
+/// switched between mock (paper / backtest) and live execution by configuration alone.
+///
+/// Configurations with a `mocked_exchange` field are deserialised as [`MockExecutionConfig`],
+/// and all others as [`LiveExecutionConfig`], so malformed configs report the error of the
+/// intended variant.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, From)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecutionConfig {
     /// Mock execution configuration for backtesting
     Mock(MockExecutionConfig),
+
+    /// Live execution configuration for trading on an exchange.
+    Live(LiveExecutionConfig),
+}
+
+impl<'de> Deserialize<'de> for ExecutionConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        if value.get("mocked_exchange").is_some() {
+            MockExecutionConfig::deserialize(value)
+                .map(Self::Mock)
+                .map_err(|error| {
+                    D::Error::custom(format!("invalid mock execution config: {error}"))
+                })
+        } else {
+            LiveExecutionConfig::deserialize(value)
+                .map(Self::Live)
+                .map_err(|error| {
+                    D::Error::custom(format!("invalid live execution config: {error}"))
+                })
+        }
+    }
+}
+
+/// Configuration for live execution on an exchange.
+///
+/// API credentials are never provided inline, but instead referenced via a [`SecretRef`] that
+/// is resolved when the system is built.
+///
+/// ### Example
+/// ```json
+/// {
+///     "exchange": "binance_spot",
+///     "credentials": {
+///         "api_key": { "env": "BINANCE_API_KEY" },
+///         "secret": { "file": "/run/secrets/binance_secret" }
+///     },
+///     "environment": "testnet",
+///     "request_timeout_ms": 5000
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct LiveExecutionConfig {
+    /// Exchange and any exchange specific configuration, identified by the `exchange` field.
+    #[serde(flatten)]
+    pub exchange: LiveExchangeConfig,
+
+    /// References to the exchange API credentials.
+    pub credentials: ApiCredentialsConfig,
+
+    /// Exchange environment to connect to.
+    ///
+    /// Required, so trading real funds on mainnet is always an explicit choice.
+    pub environment: ExchangeEnvironment,
+
+    /// Maximum time to wait for the exchange to respond to an execution request.
+    #[serde(default = "default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+impl LiveExecutionConfig {
+    /// Maximum time to wait for the exchange to respond to an execution request.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+}
+
+fn default_request_timeout_ms() -> u64 {
+    5000
+}
+
+/// Live execution exchange, including any exchange specific configuration.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(tag = "exchange", rename_all = "snake_case")]
+pub enum LiveExchangeConfig {
+    BinanceSpot,
+    BinanceFuturesUsd {
+        /// Account position mode, applied before the first account snapshot.
+        #[serde(default)]
+        position_mode: BinancePositionMode,
+
+        /// Initial leverage per symbol, applied before the first account snapshot.
+        #[serde(default)]
+        leverage: BTreeMap<InstrumentNameExchange, u32>,
+    },
+    BybitSpot,
+    BybitPerpetualsUsd,
+    Okx {
+        /// Reference to the API key passphrase.
+        passphrase: SecretRef,
+
+        /// Margin mode used for orders (defaults to spot `cash` trading).
+        #[serde(default)]
+        trade_mode: OkxTradeMode,
+    },
+    Kraken,
+    Coinbase,
+}
+
+impl LiveExchangeConfig {
+    /// [`ExchangeId`] of the configured exchange.
+    pub fn exchange(&self) -> ExchangeId {
+        match self {
+            Self::BinanceSpot => ExchangeId::BinanceSpot,
+            Self::BinanceFuturesUsd { .. } => ExchangeId::BinanceFuturesUsd,
+            Self::BybitSpot => ExchangeId::BybitSpot,
+            Self::BybitPerpetualsUsd => ExchangeId::BybitPerpetualsUsd,
+            Self::Okx { .. } => ExchangeId::Okx,
+            Self::Kraken => ExchangeId::Kraken,
+            Self::Coinbase => ExchangeId::Coinbase,
+        }
+    }
+}
+
+/// Exchange environment used for live execution.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeEnvironment {
+    /// Production environment, trading real funds.
+    Mainnet,
+
+    /// Exchange testnet (or demo trading) environment, which usually requires separate API
+    /// credentials.
+    Testnet,
+}
+
+/// References to the API credentials used to authenticate with an exchange.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct ApiCredentialsConfig {
+    /// Reference to the API key.
+    pub api_key: SecretRef,
+
+    /// Reference to the API secret.
+    pub secret: SecretRef,
+}
+
+/// Reference to a secret value, which is resolved when the system is built.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretRef {
+    /// Name of an environment variable containing the secret.
+    Env(String),
+
+    /// Path to a file containing the secret, with surrounding whitespace trimmed.
+    File(PathBuf),
+}
+
+impl SecretRef {
+    /// Resolve the referenced secret value.
+    ///
+    /// Returns an error if the environment variable is not set, or the file cannot be read.
+    pub fn resolve(&self) -> Result<String, BarterError> {
+        let secret = match self {
+            Self::Env(name) => std::env::var(name).map_err(|error| {
+                BarterError::Config(format!("failed to read env var {name}: {error}"))
+            })?,
+            Self::File(path) => std::fs::read_to_string(path).map_err(|error| {
+                BarterError::Config(format!(
+                    "failed to read secret file {}: {error}",
+                    path.display()
+                ))
+            })?,
+        };
+
+        Ok(secret.trim().to_string())
+    }
 }
 
 impl From<InstrumentConfig> for Instrument<ExchangeId, Asset> {
@@ -121,3 +300,127 @@ impl From<InstrumentConfig> for Instrument<ExchangeId, Asset> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_de_execution_config_live() {
+        let input = r#"
+        {
+            "exchange": "okx",
+            "passphrase": { "env": "OKX_PASSPHRASE" },
+            "credentials": {
+                "api_key": { "env": "OKX_API_KEY" },
+                "secret": { "file": "/run/secrets/okx_secret" }
+            },
+            "environment": "testnet"
+        }
+        "#;
+
+        let actual = serde_json::from_str::<ExecutionConfig>(input).unwrap();
+
+        let expected = ExecutionConfig::Live(LiveExecutionConfig {
+            exchange: LiveExchangeConfig::Okx {
+                passphrase: SecretRef::Env("OKX_PASSPHRASE".to_string()),
+                trade_mode: OkxTradeMode::default(),
+            },
+            credentials: ApiCredentialsConfig {
+                api_key: SecretRef::Env("OKX_API_KEY".to_string()),
+                secret: SecretRef::File(PathBuf::from("/run/secrets/okx_secret")),
+            },
+            environment: ExchangeEnvironment::Testnet,
+            request_timeout_ms: default_request_timeout_ms(),
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_de_execution_config_errors() {
+        struct TestCase {
+            input: &'static str,
+            expected_error: &'static str,
+        }
+
+        let cases = vec![
+            // TC0: live config without an environment is rejected, rather than assuming mainnet
+            TestCase {
+                input: r#"
+                {
+                    "exchange": "kraken",
+                    "credentials": {
+                        "api_key": { "env": "KRAKEN_API_KEY" },
+                        "secret": { "env": "KRAKEN_SECRET" }
+                    }
+                }
+                "#,
+                expected_error: "invalid live execution config: missing field `environment`",
+            },
+            // TC1: malformed live config reports the live config error
+            TestCase {
+                input: r#"
+                {
+                    "exchange": "kraken",
+                    "credentials": { "api_key": { "env": "KRAKEN_API_KEY" } },
+                    "environment": "testnet"
+                }
+                "#,
+                expected_error: "invalid live execution config: missing field `secret`",
+            },
+            // TC2: malformed mock config reports the mock config error
+            TestCase {
+                input: r#"{ "mocked_exchange": "binance_spot" }"#,
+                expected_error: "invalid mock execution config: missing field",
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = serde_json::from_str::<ExecutionConfig>(test.input)
+                .unwrap_err()
+                .to_string();
+            assert!(
+                actual.starts_with(test.expected_error),
+                "TC{index} failed: {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_de_execution_config_live_not_mock() {
+        let input = r#"
+        {
+            "exchange": "binance_spot",
+            "credentials": {
+                "api_key": { "env": "BINANCE_API_KEY" },
+                "secret": { "env": "BINANCE_SECRET" }
+            },
+            "environment": "mainnet",
+            "request_timeout_ms": 1000
+        }
+        "#;
+
+        let actual = serde_json::from_str::<ExecutionConfig>(input).unwrap();
+        assert!(matches!(
+            actual,
+            ExecutionConfig::Live(LiveExecutionConfig {
+                exchange: LiveExchangeConfig::BinanceSpot,
+                environment: ExchangeEnvironment::Mainnet,
+                request_timeout_ms: 1000,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_secret_ref_resolve() {
+        let missing = SecretRef::Env("BARTER_TEST_SECRET_REF_MISSING".to_string());
+        assert!(matches!(missing.resolve(), Err(BarterError::Config(_))));
+
+        let path = std::env::temp_dir().join("barter_test_secret_ref_resolve");
+        std::fs::write(&path, "secret\n").unwrap();
+        assert_eq!(SecretRef::File(path.clone()).resolve().unwrap(), "secret");
+        std::fs::remove_file(path).unwrap();
+    }
+}
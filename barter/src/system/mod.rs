@@ -14,7 +14,10 @@ use crate::{
     execution::builder::ExecutionHandles,
     shutdown::{AsyncShutdown, Shutdown},
 };
-use barter_execution::order::request::{OrderRequestCancel, OrderRequestOpen};
+use barter_execution::order::{
+    group::OrderGroup,
+    request::{OrderRequestAmend, OrderRequestCancel, OrderRequestOpen},
+};
 use barter_integration::{
     channel::{Tx, UnboundedRx, UnboundedTx},
     collection::one_or_many::OneOrMany,
@@ -140,6 +143,22 @@ where
         self.send(Command::SendOpenRequests(requests))
     }
 
+    /// Send [`OrderRequestAmend`]s to the `Engine` for execution.
+    pub fn send_amend_requests(&self, requests: OneOrMany<OrderRequestAmend>)
+    where
+        Event: From<Command>,
+    {
+        self.send(Command::SendAmendRequests(requests))
+    }
+
+    /// Send contingent [`OrderGroup`]s to the `Engine` for execution.
+    pub fn send_open_group_requests(&self, groups: OneOrMany<OrderGroup>)
+    where
+        Event: From<Command>,
+    {
+        self.send(Command::SendOpenGroupRequests(groups))
+    }
+
     /// Instruct the `Engine` to close open positions.
     ///
     /// Use the `InstrumentFilter` to configure which positions are closed.
@@ -0,0 +1,269 @@
+use barter::{
+    backtest::{
+        BacktestArgsConstant, BacktestArgsDynamic, backtest, market_data::MarketDataInMemory,
+    },
+    engine::{
+        Engine,
+        clock::HistoricalClock,
+        execution_tx::MultiExchangeTxMap,
+        state::{
+            EngineState,
+            global::DefaultGlobalData,
+            instrument::{
+                data::{DefaultInstrumentMarketData, InstrumentDataState},
+                filter::InstrumentFilter,
+            },
+            trading::TradingState,
+        },
+    },
+    risk::DefaultRiskManager,
+    statistic::time::Daily,
+    strategy::{
+        algo::AlgoStrategy,
+        close_positions::{ClosePositionsStrategy, close_open_positions_with_market_orders},
+        on_disconnect::OnDisconnectStrategy,
+        on_trading_disabled::OnTradingDisabled,
+    },
+    system::config::ExecutionConfig,
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    streams::consumer::MarketStreamEvent,
+    subscription::trade::PublicTrade,
+};
+use barter_execution::{
+    order::{
+        OrderFlags, OrderKey, OrderKind, TimeInForce,
+        id::{ClientOrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, RequestOpen},
+    },
+    slippage::NoSlippage,
+};
+use barter_instrument::{
+    Side, Underlying,
+    asset::AssetIndex,
+    exchange::{ExchangeId, ExchangeIndex},
+    index::IndexedInstruments,
+    instrument::{Instrument, InstrumentIndex},
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use smol_str::SmolStr;
+use std::sync::Arc;
+
+type State = EngineState<DefaultGlobalData, DefaultInstrumentMarketData>;
+type Risk = DefaultRiskManager<State>;
+
+const NUM_MARKET_EVENTS: i64 = 500;
+
+#[tokio::test]
+async fn test_backtest_is_deterministic() {
+    let args_constant = Arc::new(backtest_args_constant());
+
+    let first = backtest(Arc::clone(&args_constant), backtest_args_dynamic())
+        .await
+        .unwrap();
+    let second = backtest(Arc::clone(&args_constant), backtest_args_dynamic())
+        .await
+        .unwrap();
+
+    // Ensure the strategy actually traded
+    let pnl = first
+        .trading_summary
+        .instruments
+        .values()
+        .map(|tear| tear.pnl)
+        .sum::<Decimal>();
+    assert_ne!(pnl, Decimal::ZERO);
+
+    assert_eq!(first, second);
+}
+
+/// Flips between entering a position with a market order & exiting it.
+#[derive(Debug, Clone)]
+struct TestFlipStrategy {
+    id: StrategyId,
+}
+
+impl AlgoStrategy for TestFlipStrategy {
+    type State = State;
+
+    fn generate_algo_orders(
+        &self,
+        state: &Self::State,
+    ) -> (
+        impl IntoIterator<Item = OrderRequestCancel<ExchangeIndex, InstrumentIndex>>,
+        impl IntoIterator<Item = OrderRequestOpen<ExchangeIndex, InstrumentIndex>>,
+    ) {
+        let opens = state
+            .instruments
+            .instruments(&InstrumentFilter::None)
+            .filter_map(|state| {
+                // Don't open more orders if there are already some InFlight
+                if !state.orders.0.is_empty() {
+                    return None;
+                }
+
+                let price = state.data.price()?;
+
+                let (side, quantity) = match &state.position.current {
+                    Some(position) => (Side::Sell, position.quantity_abs),
+                    None => (Side::Buy, dec!(0.01)),
+                };
+
+                Some(OrderRequestOpen {
+                    key: OrderKey {
+                        exchange: state.instrument.exchange,
+                        instrument: state.key,
+                        strategy: self.id.clone(),
+                        cid: ClientOrderId::random(),
+                    },
+                    state: RequestOpen {
+                        side,
+                        kind: OrderKind::Market,
+                        time_in_force: TimeInForce::ImmediateOrCancel,
+                        price,
+                        quantity,
+                        flags: OrderFlags::default(),
+                    },
+                })
+            });
+
+        (std::iter::empty(), opens)
+    }
+}
+
+impl ClosePositionsStrategy for TestFlipStrategy {
+    type State = State;
+
+    fn close_positions_requests<'a>(
+        &'a self,
+        state: &'a Self::State,
+        filter: &'a InstrumentFilter<ExchangeIndex, AssetIndex, InstrumentIndex>,
+    ) -> (
+        impl IntoIterator<Item = OrderRequestCancel<ExchangeIndex, InstrumentIndex>> + 'a,
+        impl IntoIterator<Item = OrderRequestOpen<ExchangeIndex, InstrumentIndex>> + 'a,
+    )
+    where
+        ExchangeIndex: 'a,
+        AssetIndex: 'a,
+        InstrumentIndex: 'a,
+    {
+        close_open_positions_with_market_orders(&self.id, state, filter, &NoSlippage, |state| {
+            ClientOrderId::new(state.key.to_string())
+        })
+    }
+}
+
+impl OnDisconnectStrategy<HistoricalClock, State, MultiExchangeTxMap, Risk> for TestFlipStrategy {
+    type OnDisconnect = ();
+
+    fn on_disconnect(
+        _: &mut Engine<HistoricalClock, State, MultiExchangeTxMap, Self, Risk>,
+        _: ExchangeId,
+    ) -> Self::OnDisconnect {
+    }
+}
+
+impl OnTradingDisabled<HistoricalClock, State, MultiExchangeTxMap, Risk> for TestFlipStrategy {
+    type OnTradingDisabled = ();
+
+    fn on_trading_disabled(
+        _: &mut Engine<HistoricalClock, State, MultiExchangeTxMap, Self, Risk>,
+    ) -> Self::OnTradingDisabled {
+    }
+}
+
+fn backtest_args_constant() -> BacktestArgsConstant<MarketDataInMemory<DataKind>, Daily, State> {
+    let instruments = IndexedInstruments::builder()
+        .add_instrument(Instrument::spot(
+            ExchangeId::BinanceSpot,
+            "binance_spot_btc_usdt",
+            "BTCUSDT",
+            Underlying::new("btc", "usdt"),
+            None,
+        ))
+        .build();
+
+    let time_start = time_start();
+    let market_events = (0..NUM_MARKET_EVENTS)
+        .map(|index| {
+            let time = time_start + TimeDelta::seconds(index);
+            MarketStreamEvent::Item(MarketEvent {
+                time_exchange: time,
+                time_received: time,
+                exchange: ExchangeId::BinanceSpot,
+                instrument: InstrumentIndex(0),
+                kind: DataKind::Trade(PublicTrade {
+                    id: index.to_string(),
+                    price: 50_000.0 + 100.0 * ((index as f64) / 10.0).sin(),
+                    amount: 1.0,
+                    side: if index % 2 == 0 {
+                        Side::Buy
+                    } else {
+                        Side::Sell
+                    },
+                }),
+            })
+        })
+        .collect();
+
+    // Non-zero latencies & random faults, which must be identical between runs
+    let execution = serde_json::from_value::<ExecutionConfig>(serde_json::json!({
+        "mocked_exchange": "binance_spot",
+        "latency_ms": 100,
+        "fees_percent": 0.05,
+        "faults": {
+            "seed": 7,
+            "probabilities": { "order_rejected": 0.2, "reorder": 0.1 }
+        },
+        "initial_state": {
+            "exchange": "binance_spot",
+            "balances": [
+                {
+                    "asset": "usdt",
+                    "balance": { "total": 100000, "free": 100000 },
+                    "time_exchange": time_start
+                },
+                {
+                    "asset": "btc",
+                    "balance": { "total": 0, "free": 0 },
+                    "time_exchange": time_start
+                }
+            ],
+            "instruments": [{ "instrument": "BTCUSDT", "orders": [] }]
+        }
+    }))
+    .unwrap();
+
+    let engine_state = EngineState::builder(&instruments, DefaultGlobalData, |_| {
+        DefaultInstrumentMarketData::default()
+    })
+    .time_engine_start(time_start)
+    .trading_state(TradingState::Enabled)
+    .build();
+
+    BacktestArgsConstant {
+        instruments,
+        executions: vec![execution],
+        market_data: MarketDataInMemory::new(Arc::new(market_events)),
+        summary_interval: Daily,
+        engine_state,
+    }
+}
+
+fn backtest_args_dynamic() -> BacktestArgsDynamic<TestFlipStrategy, Risk> {
+    BacktestArgsDynamic {
+        id: SmolStr::new("deterministic"),
+        risk_free_return: dec!(0.05),
+        strategy: TestFlipStrategy {
+            id: StrategyId::new("flip"),
+        },
+        risk: DefaultRiskManager::default(),
+    }
+}
+
+fn time_start() -> DateTime<Utc> {
+    "2025-03-24T21:30:00Z".parse().unwrap()
+}
@@ -5,11 +5,13 @@ use barter::{
         action::{
             ActionOutput,
             generate_algo_orders::GenerateAlgoOrdersOutput,
+            order_groups::SendOrderGroupsOutput,
             send_requests::{SendCancelsAndOpensOutput, SendRequestsOutput},
         },
         audit::EngineAudit,
         clock::HistoricalClock,
         command::Command,
+        error::{EngineError, RecoverableEngineError},
         execution_tx::MultiExchangeTxMap,
         process_with_audit,
         state::{
@@ -20,13 +22,23 @@ use barter::{
             instrument::{
                 data::{DefaultInstrumentMarketData, InstrumentDataState},
                 filter::InstrumentFilter,
+                status::{InstrumentExpired, InstrumentStatus},
             },
+            order::in_flight_recorder::InFlightRequestRecorder,
+            order_group::OrderGroupStatus,
             position::PositionExited,
             trading::TradingState,
         },
     },
     execution::{AccountStreamEvent, request::ExecutionRequest},
-    risk::DefaultRiskManager,
+    risk::{
+        DefaultRiskManager, RiskRefused, RiskRefusedReason,
+        kill_switch::{KillSwitch, KillSwitchConfig, KillSwitchReason},
+        rate_limit::{
+            ExchangeRateLimitConfig, RateLimitConfig, RateLimitEndpoint, RateLimitMode,
+            RateLimiter, TokenBucketConfig,
+        },
+    },
     strategy::{
         algo::AlgoStrategy,
         close_positions::{ClosePositionsStrategy, close_open_positions_with_market_orders},
@@ -44,20 +56,24 @@ use barter_execution::{
     AccountEvent, AccountEventKind, AccountSnapshot,
     balance::{AssetBalance, Balance},
     order::{
-        Order, OrderKey, OrderKind, TimeInForce,
-        id::{ClientOrderId, OrderId, StrategyId},
-        request::{OrderRequestCancel, OrderRequestOpen, RequestOpen},
-        state::{ActiveOrderState, Open, OrderState},
+        Order, OrderFlags, OrderKey, OrderKind, OrderTrigger, TimeInForce, TriggerSource,
+        group::{OrderGroup, OrderGroupKind, OrderGroupOpened},
+        id::{ClientOrderId, OrderGroupId, OrderId, StrategyId},
+        request::{OrderRequestCancel, OrderRequestOpen, RequestCancel, RequestOpen},
+        state::{ActiveOrderState, Open, OpenInFlight, OrderState},
     },
+    slippage::NoSlippage,
     trade::{AssetFees, Trade, TradeId},
 };
 use barter_instrument::{
     Side, Underlying,
-    asset::AssetIndex,
+    asset::{Asset, AssetIndex},
     exchange::{ExchangeId, ExchangeIndex},
     index::IndexedInstruments,
     instrument::{
         Instrument, InstrumentIndex,
+        kind::{InstrumentKind, future::FutureContract},
+        quote::InstrumentQuoteAsset,
         spec::{
             InstrumentSpec, InstrumentSpecNotional, InstrumentSpecPrice, InstrumentSpecQuantity,
             OrderQuantityUnits,
@@ -94,7 +110,7 @@ const QUOTE_FEES_PERCENT: f64 = 0.1; // 10%
 fn test_engine_process_engine_event_with_audit() {
     let (execution_tx, mut execution_rx) = mpsc_unbounded();
 
-    let mut engine = build_engine(TradingState::Disabled, execution_tx);
+    let mut engine = build_engine(TradingState::Disabled, execution_tx, instruments_spot());
     assert_eq!(engine.meta.sequence, Sequence(0));
     assert_eq!(engine.state.connectivity.global, Health::Reconnecting);
 
@@ -135,6 +151,7 @@ fn test_engine_process_engine_event_with_audit() {
             time_in_force: TimeInForce::ImmediateOrCancel,
             price: dec!(10_000),
             quantity: dec!(1),
+            flags: OrderFlags::default(),
         },
     };
     let eth_btc_buy_order = OrderRequestOpen {
@@ -150,6 +167,7 @@ fn test_engine_process_engine_event_with_audit() {
             time_in_force: TimeInForce::ImmediateOrCancel,
             price: dec!(0.1),
             quantity: dec!(1),
+            flags: OrderFlags::default(),
         },
     };
     assert_eq!(
@@ -336,6 +354,7 @@ fn test_engine_process_engine_event_with_audit() {
             time_in_force: TimeInForce::ImmediateOrCancel,
             price: dec!(20_000),
             quantity: dec!(1),
+            flags: OrderFlags::reduce_only(),
         },
     };
     assert_eq!(
@@ -423,6 +442,7 @@ fn test_engine_process_engine_event_with_audit() {
                 price_entry_average: dec!(10_000.0),
                 quantity_abs_max: dec!(1.0),
                 pnl_realised: dec!(7000.0), // (-10k entry - 1k fees)+(20k exit - 2k fees) = 7k
+                pnl_funding: dec!(0.0),
                 fees_enter: AssetFees::quote_fees(dec!(1_000.0)),
                 fees_exit: AssetFees::quote_fees(dec!(2_000.0)),
                 time_enter: time_plus_days(STARTING_TIMESTAMP, 2),
@@ -472,6 +492,7 @@ fn test_engine_process_engine_event_with_audit() {
             time_in_force: TimeInForce::GoodUntilCancelled { post_only: true },
             price: dec!(0.05),
             quantity: dec!(1),
+            flags: OrderFlags::default(),
         },
     };
     let event = EngineEvent::Command(Command::SendOpenRequests(OneOrMany::One(
@@ -603,6 +624,7 @@ fn test_engine_process_engine_event_with_audit() {
                 price_entry_average: dec!(0.1),
                 quantity_abs_max: dec!(1.0),
                 pnl_realised: dec!(-0.065), // 0.05 - 0.01 - 0.01 entry fees - 0.005 exit fees
+                pnl_funding: dec!(0.0),
                 fees_enter: AssetFees::quote_fees(dec!(0.01)), // 0.01 btc
                 fees_exit: AssetFees::quote_fees(dec!(0.005)), // 0.005 btc
                 time_enter: time_plus_days(STARTING_TIMESTAMP, 2),
@@ -649,6 +671,259 @@ fn test_engine_process_engine_event_with_audit() {
     // Todo: Additional assertions + TradingSummary assertions once generated (to test TimeInterval)
 }
 
+#[test]
+fn test_engine_deactivates_expired_instruments() {
+    let (execution_tx, _execution_rx) = mpsc_unbounded();
+
+    let expiry = time_plus_days(STARTING_TIMESTAMP, 2);
+    let instruments = IndexedInstruments::builder()
+        .add_instrument(Instrument::new(
+            ExchangeId::BinanceSpot,
+            "binance_btc_usdt_future",
+            "BTCUSDT_FUTURE",
+            Underlying::new("btc", "usdt"),
+            InstrumentQuoteAsset::UnderlyingQuote,
+            InstrumentKind::Future(FutureContract {
+                contract_size: dec!(1),
+                settlement_asset: Asset::from("usdt"),
+                expiry,
+            }),
+            None,
+        ))
+        .add_instrument(Instrument::spot(
+            ExchangeId::BinanceSpot,
+            "binance_spot_eth_btc",
+            "ETHBTC",
+            Underlying::new("eth", "btc"),
+            None,
+        ))
+        .build();
+
+    let mut engine = build_engine(TradingState::Disabled, execution_tx, instruments);
+
+    // Process MarketEvent before expiry
+    let event = market_event_trade(1, 0, 10_000.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(audit.event, EngineAudit::process(event));
+    assert_eq!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(0))
+            .status,
+        InstrumentStatus::Active
+    );
+
+    // Process MarketEvent at expiry -> expect InstrumentExpired audit output
+    let event = market_event_trade(2, 0, 10_000.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(
+        audit.event,
+        EngineAudit::process_with_output(
+            event,
+            EngineOutput::InstrumentExpired(InstrumentExpired::new(InstrumentIndex(0), expiry))
+        )
+    );
+    assert_eq!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(0))
+            .status,
+        InstrumentStatus::Expired
+    );
+
+    // TradingState::Enabled -> expect BuyAndHoldStrategy open to be refused, and not sent
+    let event = EngineEvent::TradingStateUpdate(TradingState::Enabled);
+    let audit = process_with_audit(&mut engine, event.clone());
+    let open = OrderRequestOpen {
+        key: OrderKey {
+            exchange: ExchangeIndex(0),
+            instrument: InstrumentIndex(0),
+            strategy: strategy_id(),
+            cid: gen_cid(0),
+        },
+        state: RequestOpen {
+            side: Side::Buy,
+            kind: OrderKind::Market,
+            time_in_force: TimeInForce::ImmediateOrCancel,
+            price: dec!(10_000),
+            quantity: dec!(1),
+            flags: OrderFlags::default(),
+        },
+    };
+    assert_eq!(
+        audit.event,
+        EngineAudit::process_with_output(
+            event,
+            EngineOutput::AlgoOrders(GenerateAlgoOrdersOutput {
+                opens_refused: NoneOneOrMany::One(RiskRefused::new(
+                    open,
+                    RiskRefusedReason::InstrumentInactive(InstrumentStatus::Expired),
+                )),
+                ..Default::default()
+            })
+        )
+    );
+}
+
+#[test]
+fn test_engine_emulates_bracket_order_group() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine(TradingState::Disabled, execution_tx, instruments_spot());
+
+    let request_open = |cid: &str, side, price, kind| OrderRequestOpen {
+        key: OrderKey {
+            exchange: ExchangeIndex(0),
+            instrument: InstrumentIndex(0),
+            strategy: strategy_id(),
+            cid: ClientOrderId::new(cid),
+        },
+        state: RequestOpen {
+            side,
+            price,
+            quantity: dec!(1),
+            kind,
+            time_in_force: TimeInForce::GoodUntilCancelled { post_only: false },
+            flags: OrderFlags::default(),
+        },
+    };
+    let entry = request_open("entry", Side::Buy, dec!(10_000), OrderKind::Limit);
+    let take_profit = request_open("take_profit", Side::Sell, dec!(11_000), OrderKind::Limit);
+    let stop_loss = request_open(
+        "stop_loss",
+        Side::Sell,
+        dec!(9_000),
+        OrderKind::StopMarket {
+            trigger: OrderTrigger::new(dec!(9_000), TriggerSource::Last),
+        },
+    );
+    let group = OrderGroup::new(
+        OrderGroupId::new("bracket"),
+        OrderGroupKind::Bracket,
+        entry.clone(),
+        vec![take_profit.clone(), stop_loss.clone()],
+    );
+
+    let order_snapshot = |request: &OrderRequestOpen, state| {
+        EngineEvent::Account(AccountStreamEvent::Item(AccountEvent {
+            exchange: ExchangeIndex(0),
+            kind: AccountEventKind::OrderSnapshot(Snapshot(Order {
+                key: request.key.clone(),
+                side: request.state.side,
+                price: request.state.price,
+                quantity: request.state.quantity,
+                kind: request.state.kind,
+                time_in_force: request.state.time_in_force,
+                state,
+            })),
+        }))
+    };
+    let open = |id: &str| {
+        OrderState::active(Open::new(
+            OrderId::new(id),
+            time_plus_days(STARTING_TIMESTAMP, 1),
+            dec!(0),
+        ))
+    };
+
+    // Command::SendOpenGroupRequests -> expect OrderGroup sent, and only the entry InFlight
+    let event = EngineEvent::Command(Command::SendOpenGroupRequests(OneOrMany::One(
+        group.clone(),
+    )));
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(
+        audit.event,
+        EngineAudit::process_with_output(
+            event,
+            EngineOutput::Commanded(ActionOutput::OpenOrderGroups(SendOrderGroupsOutput {
+                sent: NoneOneOrMany::One(group.clone()),
+                errors: NoneOneOrMany::None,
+            }))
+        )
+    );
+    assert_eq!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::OpenGroup(group.clone())
+    );
+    let orders = &engine
+        .state
+        .instruments
+        .instrument_index(&InstrumentIndex(0))
+        .orders
+        .0;
+    assert_eq!(orders.len(), 1);
+    assert!(orders.contains_key(&entry.key.cid));
+
+    // Emulated group acknowledged & entry opened -> expect no emulation
+    let event = EngineEvent::Account(AccountStreamEvent::Item(AccountEvent {
+        exchange: ExchangeIndex(0),
+        kind: AccountEventKind::OrderGroupOpened(OrderGroupOpened::new(group.id.clone(), false)),
+    }));
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(audit.event, EngineAudit::process(event));
+    let event = order_snapshot(&entry, open("entry"));
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(audit.event, EngineAudit::process(event));
+
+    // Entry fully filled -> expect contingent take-profit & stop-loss opened
+    let event = order_snapshot(&entry, OrderState::fully_filled());
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(
+        audit.event,
+        EngineAudit::process_with_output(
+            event,
+            EngineOutput::OrderGroups(SendCancelsAndOpensOutput {
+                cancels: SendRequestsOutput::default(),
+                opens: SendRequestsOutput::new(
+                    NoneOneOrMany::Many(vec![take_profit.clone(), stop_loss.clone()]),
+                    NoneOneOrMany::None,
+                ),
+            })
+        )
+    );
+    assert_eq!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Open(take_profit.clone())
+    );
+    assert_eq!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Open(stop_loss.clone())
+    );
+    assert_eq!(
+        engine.state.order_groups.0[&group.id].status,
+        OrderGroupStatus::ContingentWorking
+    );
+
+    // Contingent orders opened, then take-profit filled -> expect stop-loss cancelled
+    let event = order_snapshot(&stop_loss, open("stop_loss"));
+    process_with_audit(&mut engine, event);
+    let event = order_snapshot(&take_profit, OrderState::fully_filled());
+    let audit = process_with_audit(&mut engine, event.clone());
+    let cancel = OrderRequestCancel {
+        key: stop_loss.key.clone(),
+        state: RequestCancel::new(Some(OrderId::new("stop_loss"))),
+    };
+    assert_eq!(
+        audit.event,
+        EngineAudit::process_with_output(
+            event,
+            EngineOutput::OrderGroups(SendCancelsAndOpensOutput {
+                cancels: SendRequestsOutput::new(
+                    NoneOneOrMany::One(cancel.clone()),
+                    NoneOneOrMany::None
+                ),
+                opens: SendRequestsOutput::default(),
+            })
+        )
+    );
+    assert_eq!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Cancel(cancel)
+    );
+    assert!(engine.state.order_groups.0.is_empty());
+}
+
 struct TestBuyAndHoldStrategy {
     id: StrategyId,
 }
@@ -694,6 +969,7 @@ impl AlgoStrategy for TestBuyAndHoldStrategy {
                         time_in_force: TimeInForce::ImmediateOrCancel,
                         price,
                         quantity: dec!(1),
+                        flags: OrderFlags::default(),
                     },
                 })
             });
@@ -702,6 +978,491 @@ impl AlgoStrategy for TestBuyAndHoldStrategy {
     }
 }
 
+#[test]
+fn test_engine_kill_switch_disables_trading_and_closes_positions() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine(TradingState::Disabled, execution_tx, instruments_spot());
+    engine.state.kill_switch = KillSwitch::new(KillSwitchConfig {
+        max_daily_loss: Some(dec!(500)),
+        session_secs: 60 * 60 * 24 * 7,
+        close_positions: true,
+        ..Default::default()
+    });
+
+    // Process MarketEvent -> expect KillSwitch session started with no PnL
+    let event = market_event_trade(1, 0, 1_000.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(audit.event, EngineAudit::process(event));
+    assert_eq!(
+        engine
+            .state
+            .kill_switch
+            .samples
+            .front()
+            .map(|sample| sample.pnl_open),
+        Some(dec!(0))
+    );
+
+    // Process usdt balance withdrawal -> expect no KillSwitch trigger, since balance changes
+    // are not PnL
+    let event = account_event_balance(1, 1, 100.0, 100.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(audit.event, EngineAudit::process(event));
+
+    // Enter btc_usdt position with 100 usdt fees, within max daily loss
+    let audit = process_with_audit(
+        &mut engine,
+        account_event_trade(0, 1, Side::Buy, 1_000.0, 1.0),
+    );
+    let EngineAudit::Process(process) = audit.event else {
+        panic!("unexpected EngineAudit: {:?}", audit.event)
+    };
+    assert!(!matches!(
+        process.outputs,
+        NoneOneOrMany::One(EngineOutput::KillSwitch(_))
+    ));
+
+    // TradingState::Enabled
+    let _ = process_with_audit(
+        &mut engine,
+        EngineEvent::TradingStateUpdate(TradingState::Enabled),
+    );
+
+    // Process MarketEvent with btc_usdt price halving -> expect KillSwitch triggered
+    let event = market_event_trade(2, 0, 500.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    let EngineAudit::Process(process) = audit.event else {
+        panic!("unexpected EngineAudit: {:?}", audit.event)
+    };
+    let NoneOneOrMany::One(EngineOutput::KillSwitch(output)) = process.outputs else {
+        panic!("expected KillSwitch output: {:?}", process.outputs)
+    };
+    assert!(matches!(
+        output.triggered.reason,
+        KillSwitchReason::DailyLoss(_)
+    ));
+    assert_eq!(output.on_trading_disabled, Some(OnTradingDisabledOutput));
+    assert!(output.cancel_orders.is_none());
+    assert_eq!(engine.state.trading, TradingState::Disabled);
+
+    // Ensure ExecutionRequest to close btc_usdt position was sent to ExecutionManager
+    let close_positions = output.close_positions.unwrap();
+    let NoneOneOrMany::One(close) = close_positions.opens.sent else {
+        panic!("expected single close position order")
+    };
+    assert_eq!(close.state.side, Side::Sell);
+    assert_eq!(close.state.quantity, dec!(1));
+    assert_eq!(execution_rx.next().unwrap(), ExecutionRequest::Open(close));
+
+    // Process another loss -> KillSwitch disarmed until trading is re-enabled
+    let event = market_event_trade(3, 0, 400.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(audit.event, EngineAudit::process(event));
+
+    // TradingState::Enabled while max daily loss still breached -> expect KillSwitch re-triggered
+    let audit = process_with_audit(
+        &mut engine,
+        EngineEvent::TradingStateUpdate(TradingState::Enabled),
+    );
+    let EngineAudit::Process(process) = audit.event else {
+        panic!("unexpected EngineAudit: {:?}", audit.event)
+    };
+    assert!(
+        process
+            .outputs
+            .iter()
+            .any(|output| matches!(output, EngineOutput::KillSwitch(_))),
+        "expected KillSwitch output: {:?}",
+        process.outputs
+    );
+    assert_eq!(engine.state.trading, TradingState::Disabled);
+}
+
+#[test]
+fn test_engine_kill_switch_closes_positions_exceeding_rate_limit() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine(TradingState::Disabled, execution_tx, instruments_spot());
+    engine.state.kill_switch = KillSwitch::new(KillSwitchConfig {
+        max_daily_loss: Some(dec!(500)),
+        session_secs: 60 * 60 * 24 * 7,
+        close_positions: true,
+        ..Default::default()
+    });
+    engine.state.rate_limiter = RateLimiter::new(
+        RateLimitConfig {
+            mode: RateLimitMode::Refuse,
+            exchanges: vec![ExchangeRateLimitConfig {
+                exchange: ExchangeId::BinanceSpot,
+                orders: Some(TokenBucketConfig::new(1, dec!(0.00001))),
+                cancels: None,
+            }],
+        },
+        &instruments_spot(),
+    );
+
+    // Process MarketEvents & enter btc_usdt and eth_btc positions
+    let _ = process_with_audit(&mut engine, market_event_trade(1, 0, 1_000.0));
+    let _ = process_with_audit(&mut engine, market_event_trade(1, 1, 0.1));
+    let _ = process_with_audit(
+        &mut engine,
+        account_event_trade(0, 1, Side::Buy, 1_000.0, 1.0),
+    );
+    let _ = process_with_audit(&mut engine, account_event_trade(1, 1, Side::Buy, 0.1, 1.0));
+    let _ = process_with_audit(
+        &mut engine,
+        EngineEvent::TradingStateUpdate(TradingState::Enabled),
+    );
+
+    // Process MarketEvent with btc_usdt price halving -> expect KillSwitch triggered, and both
+    // positions closed despite the open order rate limit burst of one request
+    let event = market_event_trade(2, 0, 500.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    let EngineAudit::Process(process) = audit.event else {
+        panic!("unexpected EngineAudit: {:?}", audit.event)
+    };
+    let NoneOneOrMany::One(EngineOutput::KillSwitch(output)) = process.outputs else {
+        panic!("expected KillSwitch output: {:?}", process.outputs)
+    };
+    let close_positions = output.close_positions.unwrap();
+    assert_eq!(close_positions.opens.errors, NoneOneOrMany::None);
+    let NoneOneOrMany::Many(closes) = close_positions.opens.sent else {
+        panic!("expected two close position orders")
+    };
+    assert_eq!(closes.len(), 2);
+    for close in closes {
+        assert_eq!(close.state.side, Side::Sell);
+        assert_eq!(execution_rx.next().unwrap(), ExecutionRequest::Open(close));
+    }
+    assert!(execution_rx.rx.try_recv().is_err());
+}
+
+#[test]
+fn test_engine_rate_limits_queue_algo_orders() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine(TradingState::Disabled, execution_tx, instruments_spot());
+    engine.state.rate_limiter = RateLimiter::new(
+        RateLimitConfig {
+            mode: RateLimitMode::Queue,
+            exchanges: vec![ExchangeRateLimitConfig {
+                exchange: ExchangeId::BinanceSpot,
+                orders: Some(TokenBucketConfig::new(1, dec!(0.00001))),
+                cancels: None,
+            }],
+        },
+        &instruments_spot(),
+    );
+
+    // Process MarketEvents for btc_usdt & eth_btc
+    let _ = process_with_audit(&mut engine, market_event_trade(1, 0, 10_000.0));
+    let _ = process_with_audit(&mut engine, market_event_trade(1, 1, 0.1));
+
+    // TradingState::Enabled -> expect btc_usdt Buy order sent, and eth_btc Buy order queued
+    let event = EngineEvent::TradingStateUpdate(TradingState::Enabled);
+    let audit = process_with_audit(&mut engine, event);
+    let EngineAudit::Process(process) = audit.event else {
+        panic!("unexpected EngineAudit: {:?}", audit.event)
+    };
+    let NoneOneOrMany::One(EngineOutput::AlgoOrders(output)) = process.outputs else {
+        panic!("expected AlgoOrders output: {:?}", process.outputs)
+    };
+    let NoneOneOrMany::One(btc_usdt_buy_order) = output.cancels_and_opens.opens.sent else {
+        panic!("expected single sent order")
+    };
+    let NoneOneOrMany::One(eth_btc_buy_order) = output.opens_queued else {
+        panic!("expected single queued order")
+    };
+    assert_eq!(btc_usdt_buy_order.key.instrument, InstrumentIndex(0));
+    assert_eq!(eth_btc_buy_order.key.instrument, InstrumentIndex(1));
+    assert_eq!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Open(btc_usdt_buy_order)
+    );
+    assert!(execution_rx.rx.try_recv().is_err());
+
+    // Ensure queued eth_btc Buy order is InFlight, so it is not re-generated by the strategy
+    assert!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(1))
+            .orders
+            .0
+            .contains_key(&gen_cid(1))
+    );
+
+    // Process MarketEvent two days later -> expect rate limit replenished & queued order sent
+    let audit = process_with_audit(&mut engine, market_event_trade(3, 0, 10_000.0));
+    let EngineAudit::Process(process) = audit.event else {
+        panic!("unexpected EngineAudit: {:?}", audit.event)
+    };
+    let NoneOneOrMany::One(EngineOutput::AlgoOrders(output)) = process.outputs else {
+        panic!("expected AlgoOrders output: {:?}", process.outputs)
+    };
+    assert_eq!(
+        output.cancels_and_opens.opens.sent,
+        NoneOneOrMany::One(eth_btc_buy_order.clone())
+    );
+    assert_eq!(output.opens_queued, NoneOneOrMany::None);
+    assert_eq!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Open(eth_btc_buy_order)
+    );
+    assert!(!engine.state.rate_limiter.has_queued());
+}
+
+#[test]
+fn test_engine_drops_queued_algo_orders_when_trading_disabled() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine_with_queued_algo_order(execution_tx);
+    assert!(matches!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Open(_)
+    ));
+    let eth_btc_buy_order = engine.state.rate_limiter.queued_opens[0].clone();
+
+    // TradingState::Disabled -> expect queued eth_btc Buy order dropped & no longer InFlight
+    let _ = process_with_audit(
+        &mut engine,
+        EngineEvent::TradingStateUpdate(TradingState::Disabled),
+    );
+    assert!(!engine.state.rate_limiter.has_queued());
+    assert!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(1))
+            .orders
+            .0
+            .is_empty()
+    );
+
+    // Command::SendOpenRequests exceeding the rate limit -> expect order not sent
+    let event = EngineEvent::Command(Command::SendOpenRequests(OneOrMany::One(
+        eth_btc_buy_order.clone(),
+    )));
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(
+        audit.event,
+        EngineAudit::process_with_output(
+            event,
+            EngineOutput::Commanded(ActionOutput::OpenOrders(SendRequestsOutput {
+                sent: NoneOneOrMany::None,
+                errors: NoneOneOrMany::One((
+                    eth_btc_buy_order,
+                    EngineError::Recoverable(RecoverableEngineError::RateLimited(
+                        RateLimitEndpoint::Orders
+                    ))
+                )),
+            }))
+        )
+    );
+    assert!(execution_rx.rx.try_recv().is_err());
+}
+
+#[test]
+fn test_engine_drops_queued_algo_orders_whenever_trading_disabled() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine_with_queued_algo_order(execution_tx);
+    let ExecutionRequest::Open(btc_usdt_buy_order) = execution_rx.next().unwrap() else {
+        panic!("expected btc_usdt Buy order to be sent")
+    };
+
+    // TradingState::Disabled -> expect queued eth_btc Buy order dropped
+    let _ = process_with_audit(
+        &mut engine,
+        EngineEvent::TradingStateUpdate(TradingState::Disabled),
+    );
+    assert!(!engine.state.rate_limiter.has_queued());
+
+    // Queue a rate limited cancel for the in flight btc_usdt Buy order, and re-queue the eth_btc
+    // Buy order, as if they were generated before trading was disabled
+    let btc_usdt_cancel = OrderRequestCancel {
+        key: btc_usdt_buy_order.key,
+        state: RequestCancel { id: None },
+    };
+    engine.state.record_in_flight_cancel(&btc_usdt_cancel);
+    engine
+        .state
+        .rate_limiter
+        .queued_cancels
+        .push_back(btc_usdt_cancel);
+    let eth_btc_buy_order = OrderRequestOpen {
+        key: OrderKey {
+            exchange: ExchangeIndex(0),
+            instrument: InstrumentIndex(1),
+            strategy: strategy_id(),
+            cid: gen_cid(1),
+        },
+        state: RequestOpen {
+            side: Side::Buy,
+            kind: OrderKind::Market,
+            time_in_force: TimeInForce::ImmediateOrCancel,
+            price: dec!(0.1),
+            quantity: dec!(1),
+            flags: OrderFlags::default(),
+        },
+    };
+    engine.state.record_in_flight_open(&eth_btc_buy_order);
+    engine
+        .state
+        .rate_limiter
+        .queued_opens
+        .push_back(eth_btc_buy_order);
+
+    // TradingState::Disabled while already Disabled -> expect queued requests dropped, btc_usdt
+    // Buy order returned to OpenInFlight, and eth_btc Buy order no longer InFlight
+    let _ = process_with_audit(
+        &mut engine,
+        EngineEvent::TradingStateUpdate(TradingState::Disabled),
+    );
+    assert!(!engine.state.rate_limiter.has_queued());
+    assert_eq!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(0))
+            .orders
+            .0
+            .get(&gen_cid(0))
+            .unwrap()
+            .state,
+        ActiveOrderState::OpenInFlight(OpenInFlight)
+    );
+    assert!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(1))
+            .orders
+            .0
+            .is_empty()
+    );
+    assert!(execution_rx.rx.try_recv().is_err());
+}
+
+#[test]
+fn test_engine_refuses_dequeued_algo_orders_for_inactive_instruments() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine_with_queued_algo_order(execution_tx);
+    assert!(matches!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Open(_)
+    ));
+    let eth_btc_buy_order = engine.state.rate_limiter.queued_opens[0].clone();
+
+    // Deactivate eth_btc while its Buy order is queued
+    engine
+        .state
+        .instruments
+        .instrument_index_mut(&InstrumentIndex(1))
+        .status = InstrumentStatus::Expired;
+
+    // Process MarketEvent two days later -> expect rate limit replenished, but dequeued eth_btc
+    // Buy order refused & not sent
+    let event = market_event_trade(3, 0, 10_000.0);
+    let audit = process_with_audit(&mut engine, event.clone());
+    assert_eq!(
+        audit.event,
+        EngineAudit::process_with_output(
+            event,
+            EngineOutput::AlgoOrders(GenerateAlgoOrdersOutput {
+                opens_refused: NoneOneOrMany::One(RiskRefused::new(
+                    eth_btc_buy_order,
+                    RiskRefusedReason::InstrumentInactive(InstrumentStatus::Expired),
+                )),
+                ..Default::default()
+            })
+        )
+    );
+    assert!(execution_rx.rx.try_recv().is_err());
+    assert!(!engine.state.rate_limiter.has_queued());
+
+    // Ensure refused eth_btc Buy order is no longer InFlight
+    assert!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(1))
+            .orders
+            .0
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_engine_cancel_orders_drops_queued_algo_orders() {
+    let (execution_tx, mut execution_rx) = mpsc_unbounded();
+    let mut engine = build_engine_with_queued_algo_order(execution_tx);
+    let ExecutionRequest::Open(btc_usdt_buy_order) = execution_rx.next().unwrap() else {
+        panic!("expected btc_usdt Buy order to be sent")
+    };
+
+    // Command::CancelOrders -> expect queued eth_btc Buy order dropped & no longer InFlight, and
+    // in flight btc_usdt Buy order cancelled
+    let output = engine.action(&Command::CancelOrders(InstrumentFilter::None));
+    let expected_cancel = OrderRequestCancel {
+        key: btc_usdt_buy_order.key,
+        state: RequestCancel { id: None },
+    };
+    assert_eq!(
+        output,
+        ActionOutput::CancelOrders(SendRequestsOutput {
+            sent: NoneOneOrMany::One(expected_cancel.clone()),
+            errors: NoneOneOrMany::None,
+        })
+    );
+    assert_eq!(
+        execution_rx.next().unwrap(),
+        ExecutionRequest::Cancel(expected_cancel)
+    );
+    assert!(execution_rx.rx.try_recv().is_err());
+    assert!(!engine.state.rate_limiter.has_queued());
+    assert!(
+        engine
+            .state
+            .instruments
+            .instrument_index(&InstrumentIndex(1))
+            .orders
+            .0
+            .is_empty()
+    );
+}
+
+type TestEngine = Engine<
+    HistoricalClock,
+    EngineState<DefaultGlobalData, DefaultInstrumentMarketData>,
+    MultiExchangeTxMap<UnboundedTx<ExecutionRequest>>,
+    TestBuyAndHoldStrategy,
+    DefaultRiskManager<EngineState<DefaultGlobalData, DefaultInstrumentMarketData>>,
+>;
+
+/// Build an `Engine` with an open order rate limit of one request, and enable trading so the
+/// btc_usdt Buy order is sent and the eth_btc Buy order is queued.
+fn build_engine_with_queued_algo_order(execution_tx: UnboundedTx<ExecutionRequest>) -> TestEngine {
+    let mut engine = build_engine(TradingState::Disabled, execution_tx, instruments_spot());
+    engine.state.rate_limiter = RateLimiter::new(
+        RateLimitConfig {
+            mode: RateLimitMode::Queue,
+            exchanges: vec![ExchangeRateLimitConfig {
+                exchange: ExchangeId::BinanceSpot,
+                orders: Some(TokenBucketConfig::new(1, dec!(0.00001))),
+                cancels: None,
+            }],
+        },
+        &instruments_spot(),
+    );
+
+    let _ = process_with_audit(&mut engine, market_event_trade(1, 0, 10_000.0));
+    let _ = process_with_audit(&mut engine, market_event_trade(1, 1, 0.1));
+    let _ = process_with_audit(
+        &mut engine,
+        EngineEvent::TradingStateUpdate(TradingState::Enabled),
+    );
+    assert_eq!(engine.state.rate_limiter.queued_opens.len(), 1);
+
+    engine
+}
+
 fn strategy_id() -> StrategyId {
     StrategyId::new("TestBuyAndHoldStrategy")
 }
@@ -734,7 +1495,7 @@ impl ClosePositionsStrategy for TestBuyAndHoldStrategy {
         AssetIndex: 'a,
         InstrumentIndex: 'a,
     {
-        close_open_positions_with_market_orders(&self.id, state, filter, |state| {
+        close_open_positions_with_market_orders(&self.id, state, filter, &NoSlippage, |state| {
             ClientOrderId::new(state.key.to_string())
         })
     }
@@ -791,17 +1552,8 @@ impl
     }
 }
 
-fn build_engine(
-    trading_state: TradingState,
-    execution_tx: UnboundedTx<ExecutionRequest>,
-) -> Engine<
-    HistoricalClock,
-    EngineState<DefaultGlobalData, DefaultInstrumentMarketData>,
-    MultiExchangeTxMap<UnboundedTx<ExecutionRequest>>,
-    TestBuyAndHoldStrategy,
-    DefaultRiskManager<EngineState<DefaultGlobalData, DefaultInstrumentMarketData>>,
-> {
-    let instruments = IndexedInstruments::builder()
+fn instruments_spot() -> IndexedInstruments {
+    IndexedInstruments::builder()
         .add_instrument(Instrument::spot(
             ExchangeId::BinanceSpot,
             "binance_spot_btc_usdt",
@@ -828,8 +1580,14 @@ fn build_engine(
                 InstrumentSpecNotional::new(dec!(0.0001)),
             )),
         ))
-        .build();
+        .build()
+}
 
+fn build_engine(
+    trading_state: TradingState,
+    execution_tx: UnboundedTx<ExecutionRequest>,
+    instruments: IndexedInstruments,
+) -> TestEngine {
     let clock = HistoricalClock::new(STARTING_TIMESTAMP);
 
     let state = EngineState::builder(&instruments, DefaultGlobalData::default(), |_| {